@@ -6,7 +6,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, ExtensionField, FFTField, Field};
+use arith::{field_common, impl_pod, ExtensionField, FFTField, Field};
 use serdes::ExpSerde;
 
 use crate::{babybear::BabyBear, BabyBearExt3x16, BabyBearx16};
@@ -16,6 +16,8 @@ pub struct BabyBearExt3 {
     pub v: [BabyBear; 3],
 }
 
+impl_pod!(BabyBearExt3 { v: [BabyBear; 3] });
+
 field_common!(BabyBearExt3);
 
 impl Field for BabyBearExt3 {
@@ -78,13 +80,27 @@ impl Field for BabyBearExt3 {
         }
     }
 
+    /// Frobenius-based inversion, `a^-1 = a^p * a^(p^2) / N(a)` with `N(a) = a * a^p * a^(p^2)`
+    /// in the base field. Expanding the two conjugates leaves a single base field inversion.
     fn inv(&self) -> Option<Self> {
         if self.is_zero() {
-            None
-        } else {
-            let base_field_size = (1u128 << 31) - 2u128.pow(27) + 1;
-            Some(self.exp(base_field_size * base_field_size * base_field_size - 2))
+            return None;
         }
+
+        let a = &self.v;
+        let w = BabyBear::new(Self::W);
+        // c = a^p * a^(p^2)
+        let c = [
+            a[0].square() - w * a[1] * a[2],
+            w * a[2].square() - a[0] * a[1],
+            a[1].square() - a[0] * a[2],
+        ];
+        let norm = BabyBear::dot_product(a, &[c[0], w * c[2], w * c[1]]);
+        let norm_inv = norm.inv()?;
+
+        Some(Self {
+            v: [c[0] * norm_inv, c[1] * norm_inv, c[2] * norm_inv],
+        })
     }
 
     /// Squaring
@@ -235,8 +251,26 @@ impl BabyBearExt3 {
     pub fn as_u32_array(&self) -> [u32; 3] {
         unsafe { transmute(self.v) }
     }
+
+    /// The Frobenius map `a -> a^p`.
+    ///
+    /// Since `x^3 = 2`, `x^p = 2^((p - 1) / 3) * x`, so the map only scales the coefficients by
+    /// powers of a cube root of unity.
+    #[inline(always)]
+    pub fn frobenius(&self) -> Self {
+        Self {
+            v: [
+                self.v[0],
+                self.v[1] * BABY_BEAR_EXT3_FROBENIUS_COEFFS[0],
+                self.v[2] * BABY_BEAR_EXT3_FROBENIUS_COEFFS[1],
+            ],
+        }
+    }
 }
 
+/// `[w, w^2]` with `w = 2^((p - 1) / 3)` a primitive cube root of unity.
+const BABY_BEAR_EXT3_FROBENIUS_COEFFS: [BabyBear; 2] = BabyBear::new_array([1314723123, 698542797]);
+
 impl From<BabyBear> for BabyBearExt3 {
     #[inline(always)]
     fn from(x: BabyBear) -> Self {
@@ -299,12 +333,16 @@ fn sub_internal(a: &BabyBearExt3, b: &BabyBearExt3) -> BabyBearExt3 {
 // + (a0*b2 + a1*b1 + a2*b0)*x^2
 #[inline(always)]
 fn mul_internal(a: &BabyBearExt3, b: &BabyBearExt3) -> BabyBearExt3 {
+    // each coefficient is a dot product with a single (lazy) reduction
     let a = &a.v;
     let b = &b.v;
-    let mut res = [BabyBear::default(); 3];
-    res[0] = a[0] * b[0] + BabyBear::new(2) * (a[1] * b[2] + a[2] * b[1]);
-    res[1] = a[0] * b[1] + a[1] * b[0] + BabyBear::new(2) * a[2] * b[2];
-    res[2] = a[0] * b[2] + a[1] * b[1] + a[2] * b[0];
+    let b1_w = b[1].double();
+    let b2_w = b[2].double();
+    let res = [
+        BabyBear::dot_product(a, &[b[0], b2_w, b1_w]),
+        BabyBear::dot_product(a, &[b[1], b[0], b2_w]),
+        BabyBear::dot_product(a, &[b[2], b[1], b[0]]),
+    ];
     BabyBearExt3 { v: res }
 }
 
@@ -3,7 +3,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, ExtensionField, FFTField, Field, SimdField};
+use arith::{field_common, impl_pod, ExtensionField, FFTField, Field, SimdField};
 use ethnum::U256;
 use serdes::ExpSerde;
 
@@ -14,6 +14,10 @@ pub struct BabyBearExt3x16 {
     pub v: [BabyBearx16; 3],
 }
 
+impl_pod!(BabyBearExt3x16 {
+    v: [BabyBearx16; 3]
+});
+
 field_common!(BabyBearExt3x16);
 
 impl SimdField for BabyBearExt3x16 {
@@ -0,0 +1,409 @@
+use ethnum::U256;
+use rand::RngCore;
+use std::{
+    iter::{Product, Sum},
+    mem::transmute,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, impl_pod, ExtensionField, FFTField, Field};
+use serdes::ExpSerde;
+
+use crate::babybear::BabyBear;
+
+/// The degree 4 extension of BabyBear, i.e., `BabyBear[x] / (x^4 - 11)`.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, ExpSerde)]
+pub struct BabyBearExt4 {
+    pub v: [BabyBear; 4],
+}
+
+impl_pod!(BabyBearExt4 { v: [BabyBear; 4] });
+
+field_common!(BabyBearExt4);
+
+impl Field for BabyBearExt4 {
+    const NAME: &'static str = "Baby Bear Extension 4";
+
+    const SIZE: usize = 32 / 8 * 4;
+
+    const FIELD_SIZE: usize = 32 * 4;
+
+    const ZERO: Self = BabyBearExt4 {
+        v: [BabyBear::ZERO; 4],
+    };
+
+    const ONE: Self = BabyBearExt4 {
+        v: [
+            BabyBear::ONE,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+        ],
+    };
+
+    const INV_2: BabyBearExt4 = BabyBearExt4 {
+        v: [
+            BabyBear::INV_2,
+            BabyBear::new(0),
+            BabyBear::new(0),
+            BabyBear::new(0),
+        ],
+    };
+
+    const MODULUS: U256 = BabyBear::MODULUS;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        BabyBearExt4 {
+            v: [BabyBear::new(0); 4],
+        }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        BabyBearExt4 {
+            v: [
+                BabyBear::new(1),
+                BabyBear::new(0),
+                BabyBear::new(0),
+                BabyBear::new(0),
+            ],
+        }
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        BabyBearExt4 {
+            v: [
+                BabyBear::random_unsafe(&mut rng),
+                BabyBear::random_unsafe(&mut rng),
+                BabyBear::random_unsafe(&mut rng),
+                BabyBear::random_unsafe(&mut rng),
+            ],
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        BabyBearExt4 {
+            v: [
+                BabyBear::random_bool(&mut rng),
+                BabyBear::zero(),
+                BabyBear::zero(),
+                BabyBear::zero(),
+            ],
+        }
+    }
+
+    /// Frobenius-based inversion.
+    ///
+    /// `b = a * a^(p^2)` lies in the subfield `BabyBear[x^2]`, and `N(a) = b * b^p` lies in the
+    /// base field, so `a^-1 = a^(p^2) * b^p / N(a)` costs a single base field inversion.
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let a = &self.v;
+        let w = BabyBear::new(Self::W);
+        let w_a2 = w * a[2];
+        let w_a3 = w * a[3];
+
+        // b = b0 + b2 * x^2
+        let b0 = BabyBear::dot_product(&[a[0], a[2], a[1]], &[a[0], w_a2, -w_a3.double()]);
+        let b2 = BabyBear::dot_product(&[a[0], a[1], a[3]], &[a[2].double(), -a[1], -w_a3]);
+        let w_b2 = w * b2;
+
+        let norm = BabyBear::dot_product(&[b0, b2], &[b0, -w_b2]);
+        let norm_inv = norm.inv()?;
+
+        // a^(p^2) * b^p = (a0 - a1 x + a2 x^2 - a3 x^3) * (b0 - b2 x^2)
+        let res = [
+            BabyBear::dot_product(&[a[0], a[2]], &[b0, -w_b2]),
+            BabyBear::dot_product(&[a[1], a[3]], &[-b0, w_b2]),
+            BabyBear::dot_product(&[a[0], a[2]], &[-b2, b0]),
+            BabyBear::dot_product(&[a[1], a[3]], &[b2, -b0]),
+        ];
+
+        Some(Self {
+            v: res.map(|x| x * norm_inv),
+        })
+    }
+
+    /// Squaring
+    #[inline(always)]
+    fn square(&self) -> Self {
+        Self {
+            v: square_internal(&self.v),
+        }
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v[0].as_u32_unchecked()
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        assert!(bytes.len() >= 16);
+        let v1 = u32::from_be_bytes(bytes[0..4].try_into().unwrap());
+        let v2 = u32::from_be_bytes(bytes[4..8].try_into().unwrap());
+        let v3 = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        let v4 = u32::from_be_bytes(bytes[12..16].try_into().unwrap());
+        Self {
+            v: [
+                BabyBear::new(v1),
+                BabyBear::new(v2),
+                BabyBear::new(v3),
+                BabyBear::new(v4),
+            ],
+        }
+    }
+}
+
+impl ExtensionField for BabyBearExt4 {
+    const DEGREE: usize = 4;
+
+    /// Extension Field
+    const W: u32 = 11;
+
+    const X: Self = BabyBearExt4 {
+        v: [
+            BabyBear::ZERO,
+            BabyBear::ONE,
+            BabyBear::ZERO,
+            BabyBear::ZERO,
+        ],
+    };
+
+    /// Base field for the extension
+    type BaseField = BabyBear;
+
+    #[inline(always)]
+    /// Multiply the extension field with the base field
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        let mut res = self.v;
+        res.iter_mut().for_each(|x| *x *= base);
+        Self { v: res }
+    }
+
+    #[inline(always)]
+    /// Add the extension field with the base field
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        let mut res = self.v;
+        res[0] += base;
+        Self { v: res }
+    }
+
+    /// Multiply the extension field by x, i.e, 0 + x + 0 x^2 + 0 x^3 + ...
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        Self {
+            v: [
+                self.v[3] * BabyBear::new(Self::W),
+                self.v[0],
+                self.v[1],
+                self.v[2],
+            ],
+        }
+    }
+
+    /// Extract polynomial field coefficients from the extension field instance
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<Self::BaseField> {
+        self.v.to_vec()
+    }
+
+    /// Construct a new instance of extension field from coefficients
+    #[inline(always)]
+    fn from_limbs(limbs: &[Self::BaseField]) -> Self {
+        let mut v = [Self::BaseField::default(); Self::DEGREE];
+        if limbs.len() < Self::DEGREE {
+            v[..limbs.len()].copy_from_slice(limbs)
+        } else {
+            v.copy_from_slice(&limbs[..Self::DEGREE])
+        }
+        Self { v }
+    }
+}
+
+impl Mul<BabyBear> for BabyBearExt4 {
+    type Output = BabyBearExt4;
+
+    #[inline(always)]
+    fn mul(self, rhs: BabyBear) -> Self::Output {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl Add<BabyBear> for BabyBearExt4 {
+    type Output = BabyBearExt4;
+
+    #[inline(always)]
+    fn add(self, rhs: BabyBear) -> Self::Output {
+        self + BabyBearExt4::from(rhs)
+    }
+}
+
+impl Neg for BabyBearExt4 {
+    type Output = BabyBearExt4;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        BabyBearExt4 {
+            v: self.v.map(|x| -x),
+        }
+    }
+}
+
+impl From<u32> for BabyBearExt4 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        BabyBearExt4 {
+            v: [
+                BabyBear::from(x),
+                BabyBear::zero(),
+                BabyBear::zero(),
+                BabyBear::zero(),
+            ],
+        }
+    }
+}
+
+impl FFTField for BabyBearExt4 {
+    const TWO_ADICITY: usize = 27;
+
+    fn root_of_unity() -> Self {
+        Self::from(0x1a427a41)
+    }
+}
+
+impl BabyBearExt4 {
+    #[inline(always)]
+    pub fn to_base_field(&self) -> BabyBear {
+        assert!(
+            self.v[1].is_zero() && self.v[2].is_zero() && self.v[3].is_zero(),
+            "BabyBearExt4 cannot be converted to base field"
+        );
+
+        self.to_base_field_unsafe()
+    }
+
+    #[inline(always)]
+    pub fn to_base_field_unsafe(&self) -> BabyBear {
+        self.v[0]
+    }
+
+    #[inline(always)]
+    pub fn as_u32_array(&self) -> [u32; 4] {
+        unsafe { transmute(self.v) }
+    }
+
+    /// The Frobenius map `a -> a^p`.
+    ///
+    /// Since `x^4 = 11`, `x^p = z * x` for the 4-th root of unity `z = 11^((p - 1) / 4)`, hence
+    /// `a^p = a0 + z a1 x - a2 x^2 - z a3 x^3`.
+    #[inline(always)]
+    pub fn frobenius(&self) -> Self {
+        Self {
+            v: [
+                self.v[0],
+                self.v[1] * BABY_BEAR_EXT4_FROBENIUS_COEFF,
+                -self.v[2],
+                -(self.v[3] * BABY_BEAR_EXT4_FROBENIUS_COEFF),
+            ],
+        }
+    }
+}
+
+/// `11^((p - 1) / 4)`, a primitive 4-th root of unity.
+const BABY_BEAR_EXT4_FROBENIUS_COEFF: BabyBear = BabyBear::new(1728404513);
+
+impl From<BabyBear> for BabyBearExt4 {
+    #[inline(always)]
+    fn from(x: BabyBear) -> Self {
+        BabyBearExt4 {
+            v: [x, BabyBear::zero(), BabyBear::zero(), BabyBear::zero()],
+        }
+    }
+}
+
+impl From<&BabyBear> for BabyBearExt4 {
+    #[inline(always)]
+    fn from(x: &BabyBear) -> Self {
+        BabyBearExt4 {
+            v: [*x, BabyBear::zero(), BabyBear::zero(), BabyBear::zero()],
+        }
+    }
+}
+
+impl From<BabyBearExt4> for BabyBear {
+    #[inline(always)]
+    fn from(x: BabyBearExt4) -> Self {
+        x.to_base_field()
+    }
+}
+
+impl From<&BabyBearExt4> for BabyBear {
+    #[inline(always)]
+    fn from(x: &BabyBearExt4) -> Self {
+        x.to_base_field()
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &BabyBearExt4, b: &BabyBearExt4) -> BabyBearExt4 {
+    let mut vv = a.v;
+    vv.iter_mut().zip(b.v.iter()).for_each(|(x, y)| *x += y);
+
+    BabyBearExt4 { v: vv }
+}
+
+#[inline(always)]
+fn sub_internal(a: &BabyBearExt4, b: &BabyBearExt4) -> BabyBearExt4 {
+    let mut vv = a.v;
+    vv.iter_mut().zip(b.v.iter()).for_each(|(x, y)| *x -= y);
+
+    BabyBearExt4 { v: vv }
+}
+
+// polynomial mod (x^4 - 11)
+//
+//   (a0 + a1*x + a2*x^2 + a3*x^3) * (b0 + b1*x + b2*x^2 + b3*x^3) mod (x^4 - 11)
+// = a0*b0 + 11*(a1*b3 + a2*b2 + a3*b1)
+// + (a0*b1 + a1*b0 + 11*(a2*b3 + a3*b2))*x
+// + (a0*b2 + a1*b1 + a2*b0 + 11*a3*b3)*x^2
+// + (a0*b3 + a1*b2 + a2*b1 + a3*b0)*x^3
+//
+// Each coefficient is a dot product with a single (lazy) reduction.
+#[inline(always)]
+fn mul_internal(a: &BabyBearExt4, b: &BabyBearExt4) -> BabyBearExt4 {
+    let a = &a.v;
+    let b = &b.v;
+    let w = BabyBear::new(BabyBearExt4::W);
+    let b1_w = b[1] * w;
+    let b2_w = b[2] * w;
+    let b3_w = b[3] * w;
+    let res = [
+        BabyBear::dot_product(a, &[b[0], b3_w, b2_w, b1_w]),
+        BabyBear::dot_product(a, &[b[1], b[0], b3_w, b2_w]),
+        BabyBear::dot_product(a, &[b[2], b[1], b[0], b3_w]),
+        BabyBear::dot_product(a, &[b[3], b[2], b[1], b[0]]),
+    ];
+    BabyBearExt4 { v: res }
+}
+
+#[inline(always)]
+fn square_internal(a: &[BabyBear; 4]) -> [BabyBear; 4] {
+    let w = BabyBear::new(BabyBearExt4::W);
+    let a2_w = a[2] * w;
+    let a3_w = a[3] * w;
+    [
+        BabyBear::dot_product(&[a[0], a[1], a[2]], &[a[0], a3_w.double(), a2_w]),
+        BabyBear::dot_product(&[a[0], a[2]], &[a[1].double(), a3_w.double()]),
+        BabyBear::dot_product(&[a[0], a[1], a[3]], &[a[2].double(), a[1], a3_w]),
+        BabyBear::dot_product(&[a[0], a[1]], &[a[3].double(), a[2].double()]),
+    ]
+}
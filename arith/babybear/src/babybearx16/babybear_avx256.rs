@@ -1,4 +1,4 @@
-use arith::{field_common, Field, SimdField};
+use arith::{field_common, impl_pod, Field, SimdField};
 use ark_std::iterable::Iterable;
 use ethnum::U256;
 use rand::{Rng, RngCore};
@@ -41,6 +41,8 @@ pub struct AVXBabyBear {
     pub v: [__m256i; 2],
 }
 
+impl_pod!(AVXBabyBear { v: [__m256i; 2] });
+
 field_common!(AVXBabyBear);
 
 impl ExpSerde for AVXBabyBear {
@@ -1,4 +1,4 @@
-use arith::{field_common, Field, SimdField};
+use arith::{field_common, impl_pod, Field, SimdField};
 use ark_std::iterable::Iterable;
 use ethnum::U256;
 use rand::{Rng, RngCore};
@@ -43,6 +43,8 @@ pub struct AVXBabyBear {
     pub v: __m512i,
 }
 
+impl_pod!(AVXBabyBear { v: __m512i });
+
 field_common!(AVXBabyBear);
 
 impl ExpSerde for AVXBabyBear {
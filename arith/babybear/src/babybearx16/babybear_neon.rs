@@ -8,7 +8,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, Field, SimdField};
+use arith::{field_common, impl_pod, Field, SimdField};
 use ethnum::U256;
 use rand::RngCore;
 use serdes::{ExpSerde, SerdeResult};
@@ -22,6 +22,8 @@ pub struct NeonBabyBear {
     pub v: [uint32x4_t; 4],
 }
 
+impl_pod!(NeonBabyBear { v: [uint32x4_t; 4] });
+
 const PACKED_MOD: uint32x4_t = unsafe { transmute::<[u32; 4], uint32x4_t>([BABY_BEAR_MOD; 4]) };
 
 #[inline]
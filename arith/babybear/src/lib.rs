@@ -18,6 +18,9 @@ pub use babybearx16::BabyBearx16;
 mod babybear_ext;
 pub use babybear_ext::BabyBearExt3;
 
+mod babybear_ext4;
+pub use babybear_ext4::BabyBearExt4;
+
 mod babybear_ext3x16;
 pub use babybear_ext3x16::BabyBearExt3x16;
 
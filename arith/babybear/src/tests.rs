@@ -1,17 +1,22 @@
 use arith::{
-    random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_inversion_tests, random_simd_field_tests, Field, FieldParameters,
+    random_canonical_field_tests, random_extension_field_tests, random_fft_field_tests,
+    random_field_tests, random_inversion_tests, random_simd_differential_tests,
+    random_simd_field_tests, Field, FieldParameters,
 };
 use ark_std::test_rng;
 use serdes::ExpSerde;
 
-use crate::{babybear::BabyBearParameters, BabyBear, BabyBearExt3, BabyBearExt3x16, BabyBearx16};
+use crate::{
+    babybear::BabyBearParameters, BabyBear, BabyBearExt3, BabyBearExt3x16, BabyBearExt4,
+    BabyBearx16,
+};
 
 // CMD: RUSTFLAGS="-C target-feature=+avx512f" cargo test --package arith --lib --
 // tests::baby_bear::test_field --exact --show-output
 #[test]
 fn test_base_field() {
     random_field_tests::<BabyBear>("BabyBear".to_string());
+    random_canonical_field_tests::<BabyBear>("BabyBear".to_string());
 
     let mut rng = test_rng();
     random_inversion_tests::<BabyBear, _>(&mut rng, "BabyBear".to_string());
@@ -27,6 +32,7 @@ fn test_simd_field() {
     random_inversion_tests::<BabyBearx16, _>(&mut rng, "BabyBearx16".to_string());
 
     random_simd_field_tests::<BabyBearx16>("BabyBearx16".to_string());
+    random_simd_differential_tests::<BabyBearx16>("BabyBearx16".to_string());
 
     random_fft_field_tests::<BabyBearx16>("BabyBearx16".to_string());
 
@@ -53,15 +59,53 @@ fn test_ext_field() {
 
     random_fft_field_tests::<BabyBearExt3>("Baby Bear Ext3".to_string());
     random_fft_field_tests::<BabyBearExt3x16>("SIMD Baby Bear Ext3".to_string());
-    // // Deg 4
-    // random_field_tests::<BabyBearExt4>("Baby Bear Ext4".to_string());
-    // random_extension_field_tests::<BabyBearExt4>("Baby Bear Ext4".to_string());
+    // Deg 4
+    random_field_tests::<BabyBearExt4>("Baby Bear Ext4".to_string());
+    random_extension_field_tests::<BabyBearExt4>("Baby Bear Ext4".to_string());
+    random_fft_field_tests::<BabyBearExt4>("Baby Bear Ext4".to_string());
 
     // random_field_tests::<BabyBearExt4x16>("Simd Baby Bear Ext4".to_string());
     // random_extension_field_tests::<BabyBearExt4x16>("Simd Baby Bear Ext4".to_string());
     // random_simd_field_tests::<BabyBearExt4x16>("Simd Baby Bear Ext4".to_string());
 }
 
+#[test]
+fn test_ext_frobenius_and_inverse() {
+    let p = BabyBear::MODULUS.as_u128();
+    let mut rng = test_rng();
+
+    for _ in 0..100 {
+        let a = BabyBearExt3::random_unsafe(&mut rng);
+        assert_eq!(a.frobenius(), a.exp(p));
+        assert_eq!(a.inv().unwrap(), a.exp(p * p * p - 2));
+        assert_eq!(a * a.inv().unwrap(), BabyBearExt3::ONE);
+
+        let b = BabyBearExt4::random_unsafe(&mut rng);
+        assert_eq!(b.frobenius(), b.exp(p));
+        assert_eq!(b.inv().unwrap(), b.exp(p * p * p * p - 2));
+        assert_eq!(b * b.inv().unwrap(), BabyBearExt4::ONE);
+    }
+
+    // elements of the base field and the intermediate field BabyBear[x^2]
+    let a = BabyBearExt4 {
+        v: [
+            BabyBear::new(5),
+            BabyBear::ZERO,
+            BabyBear::new(7),
+            BabyBear::ZERO,
+        ],
+    };
+    assert_eq!(a * a.inv().unwrap(), BabyBearExt4::ONE);
+    let a = BabyBearExt4::from(BabyBear::new(3));
+    assert_eq!(
+        a.inv().unwrap().to_base_field(),
+        BabyBear::new(3).inv().unwrap()
+    );
+
+    assert!(BabyBearExt3::ZERO.inv().is_none());
+    assert!(BabyBearExt4::ZERO.inv().is_none());
+}
+
 #[test]
 fn baby_bear_two_inverse() {
     let two = BabyBear::new(2);
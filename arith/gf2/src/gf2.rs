@@ -6,7 +6,7 @@ use std::iter::{Product, Sum};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use arith::field_common;
-use arith::Field;
+use arith::{impl_pod, CanonicalField, Field};
 use ethnum::U256;
 use serdes::{ExpSerde, SerdeResult};
 
@@ -17,6 +17,8 @@ pub struct GF2 {
     pub v: u8,
 }
 
+impl_pod!(GF2 { v: u8 });
+
 impl PartialEq for GF2 {
     #[inline(always)]
     fn eq(&self, other: &Self) -> bool {
@@ -43,6 +45,23 @@ impl ExpSerde for GF2 {
     }
 }
 
+impl CanonicalField for GF2 {
+    const CANONICAL_SIZE: usize = 1;
+
+    #[inline(always)]
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        vec![self.v & 1]
+    }
+
+    #[inline(always)]
+    fn try_from_canonical_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [v] if (*v as u32) < MOD => Some(GF2 { v: *v }),
+            _ => None,
+        }
+    }
+}
+
 impl Field for GF2 {
     // still will pack 8 bits into a u8
 
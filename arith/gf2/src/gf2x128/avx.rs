@@ -5,7 +5,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::Field;
+use arith::{impl_pod, Field};
 use ethnum::U256;
 use serdes::{ExpSerde, SerdeResult};
 
@@ -16,6 +16,8 @@ pub struct AVXGF2x128 {
     pub v: __m128i,
 }
 
+impl_pod!(AVXGF2x128 { v: __m128i });
+
 impl ExpSerde for AVXGF2x128 {
     #[inline(always)]
     fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
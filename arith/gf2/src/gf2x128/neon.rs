@@ -4,7 +4,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::Field;
+use arith::{impl_pod, Field};
 use ethnum::U256;
 use serdes::{ExpSerde, SerdeResult};
 
@@ -15,6 +15,8 @@ pub struct NeonGF2x128 {
     pub(crate) v: uint32x4_t,
 }
 
+impl_pod!(NeonGF2x128 { v: uint32x4_t });
+
 impl ExpSerde for NeonGF2x128 {
     #[inline(always)]
     fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
@@ -3,7 +3,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{Field, SimdField};
+use arith::{impl_pod, Field, SimdField};
 use ethnum::U256;
 use serdes::ExpSerde;
 
@@ -14,6 +14,8 @@ pub struct GF2x64 {
     pub v: u64,
 }
 
+impl_pod!(GF2x64 { v: u64 });
+
 impl Field for GF2x64 {
     const NAME: &'static str = "Galois Field 2 SIMD 64";
 
@@ -1,6 +1,6 @@
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use arith::{Field, SimdField};
+use arith::{impl_pod, Field, SimdField};
 use ethnum::U256;
 use serdes::ExpSerde;
 
@@ -12,6 +12,8 @@ pub struct GF2x8 {
     pub v: u8,
 }
 
+impl_pod!(GF2x8 { v: u8 });
+
 impl Field for GF2x8 {
     // still will pack 8 bits into a u8
 
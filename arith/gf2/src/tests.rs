@@ -1,13 +1,17 @@
 use ark_std::test_rng;
 use std::io::Cursor;
 
-use arith::{random_field_tests, random_inversion_tests, random_simd_field_tests, SimdField};
+use arith::{
+    random_canonical_field_tests, random_field_tests, random_inversion_tests,
+    random_simd_differential_tests, random_simd_field_tests, SimdField,
+};
 
 use crate::{GF2x128, GF2x64, GF2x8, GF2};
 
 #[test]
 fn test_field() {
     random_field_tests::<GF2>("GF2".to_string());
+    random_canonical_field_tests::<GF2>("GF2".to_string());
 
     let mut rng = test_rng();
     random_inversion_tests::<GF2, _>(&mut rng, "GF2".to_string());
@@ -17,12 +21,15 @@ fn test_field() {
 fn test_simd_field() {
     random_field_tests::<GF2x8>("Vectorized GF2".to_string());
     random_simd_field_tests::<GF2x8>("Vectorized GF2".to_string());
+    random_simd_differential_tests::<GF2x8>("Vectorized GF2".to_string());
 
     random_field_tests::<GF2x64>("Vectorized GF2 len 64".to_string());
     random_simd_field_tests::<GF2x64>("Vectorized GF2 len 64".to_string());
+    random_simd_differential_tests::<GF2x64>("Vectorized GF2 len 64".to_string());
 
     random_field_tests::<GF2x128>("Vectorized GF2 len 128".to_string());
     random_simd_field_tests::<GF2x128>("Vectorized GF2 len 128".to_string());
+    random_simd_differential_tests::<GF2x128>("Vectorized GF2 len 128".to_string());
 }
 
 fn custom_serde_vectorize_gf2<F: SimdField<Scalar = GF2>>() {
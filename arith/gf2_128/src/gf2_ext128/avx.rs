@@ -6,7 +6,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, ExtensionField, Field};
+use arith::{field_common, impl_pod, ExtensionField, Field};
 use ethnum::U256;
 use gf2::GF2;
 use serdes::{ExpSerde, SerdeResult};
@@ -16,6 +16,8 @@ pub struct AVXGF2_128 {
     pub v: __m128i,
 }
 
+impl_pod!(AVXGF2_128 { v: __m128i });
+
 field_common!(AVXGF2_128);
 
 impl ExpSerde for AVXGF2_128 {
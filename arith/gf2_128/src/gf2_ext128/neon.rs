@@ -2,7 +2,7 @@ use std::iter::{Product, Sum};
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use std::{arch::aarch64::*, mem::transmute};
 
-use arith::{field_common, ExtensionField, Field};
+use arith::{field_common, impl_pod, ExtensionField, Field};
 use ethnum::U256;
 use gf2::GF2;
 use serdes::{ExpSerde, SerdeResult};
@@ -12,6 +12,8 @@ pub struct NeonGF2_128 {
     pub(crate) v: uint32x4_t,
 }
 
+impl_pod!(NeonGF2_128 { v: uint32x4_t });
+
 field_common!(NeonGF2_128);
 
 #[inline(always)]
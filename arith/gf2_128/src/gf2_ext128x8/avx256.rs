@@ -6,7 +6,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, ExtensionField, Field, SimdField};
+use arith::{field_common, impl_pod, ExtensionField, Field, SimdField};
 use ethnum::U256;
 use gf2::{GF2x8, GF2};
 use serdes::{ExpSerde, SerdeError, SerdeResult};
@@ -18,6 +18,8 @@ pub struct AVX256GF2_128x8 {
     data: [__m256i; 4],
 }
 
+impl_pod!(AVX256GF2_128x8 { data: [__m256i; 4] });
+
 field_common!(AVX256GF2_128x8);
 
 impl AVX256GF2_128x8 {
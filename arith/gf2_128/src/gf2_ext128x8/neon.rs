@@ -3,7 +3,7 @@ use std::iter::{Product, Sum};
 use std::mem::transmute;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use arith::{field_common, ExtensionField, Field, SimdField};
+use arith::{field_common, impl_pod, ExtensionField, Field, SimdField};
 use ethnum::U256;
 use gf2::{GF2x8, GF2};
 use serdes::{ExpSerde, SerdeResult};
@@ -16,6 +16,8 @@ pub struct NeonGF2_128x8 {
     v: [uint32x4_t; 8],
 }
 
+impl_pod!(NeonGF2_128x8 { v: [uint32x4_t; 8] });
+
 field_common!(NeonGF2_128x8);
 
 impl Default for NeonGF2_128x8 {
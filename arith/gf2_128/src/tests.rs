@@ -1,6 +1,6 @@
 use arith::{
     random_extension_field_tests, random_field_tests, random_from_limbs_to_limbs_tests,
-    random_inversion_tests, random_simd_field_tests,
+    random_inversion_tests, random_simd_differential_tests, random_simd_field_tests,
 };
 use ark_std::test_rng;
 use gf2::{GF2x8, GF2};
@@ -13,6 +13,7 @@ use crate::{GF2_128x8, GF2_128};
 #[test]
 fn test_simd_field() {
     random_simd_field_tests::<GF2_128x8>("Simd GF2 Ext128".to_string());
+    random_simd_differential_tests::<GF2_128x8>("Simd GF2 Ext128".to_string());
 }
 
 #[test]
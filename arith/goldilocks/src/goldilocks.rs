@@ -4,7 +4,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, FFTField, Field, SimdField};
+use arith::{field_common, impl_pod, CanonicalField, FFTField, Field, SimdField};
 use ethnum::U256;
 use rand::RngCore;
 use serdes::{ExpSerde, SerdeResult};
@@ -30,6 +30,8 @@ pub struct Goldilocks {
     pub v: u64,
 }
 
+impl_pod!(Goldilocks { v: u64 });
+
 impl PartialEq for Goldilocks {
     #[inline(always)]
     fn eq(&self, other: &Self) -> bool {
@@ -74,6 +76,21 @@ impl ExpSerde for Goldilocks {
     }
 }
 
+impl CanonicalField for Goldilocks {
+    const CANONICAL_SIZE: usize = 8;
+
+    #[inline(always)]
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        mod_reduce_u64(self.v).to_le_bytes().to_vec()
+    }
+
+    #[inline(always)]
+    fn try_from_canonical_bytes(bytes: &[u8]) -> Option<Self> {
+        let v = u64::from_le_bytes(bytes.try_into().ok()?);
+        (v < GOLDILOCKS_MOD).then_some(Goldilocks { v })
+    }
+}
+
 impl Field for Goldilocks {
     const NAME: &'static str = "Goldilocks";
 
@@ -3,7 +3,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, ExtensionField, FFTField, Field, SimdField};
+use arith::{field_common, impl_pod, ExtensionField, FFTField, Field, SimdField};
 use ethnum::U256;
 use rand::RngCore;
 use serdes::ExpSerde;
@@ -18,6 +18,8 @@ pub struct GoldilocksExt2 {
     pub v: [Goldilocks; 2],
 }
 
+impl_pod!(GoldilocksExt2 { v: [Goldilocks; 2] });
+
 field_common!(GoldilocksExt2);
 
 impl Field for GoldilocksExt2 {
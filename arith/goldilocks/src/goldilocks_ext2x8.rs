@@ -4,7 +4,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, ExtensionField, FFTField, Field, SimdField};
+use arith::{field_common, impl_pod, ExtensionField, FFTField, Field, SimdField};
 
 use ethnum::U256;
 use rand::RngCore;
@@ -20,6 +20,11 @@ pub struct GoldilocksExt2x8 {
     pub c1: Goldilocksx8, // coefficient of X
 }
 
+impl_pod!(GoldilocksExt2x8 {
+    c0: Goldilocksx8,
+    c1: Goldilocksx8
+});
+
 field_common!(GoldilocksExt2x8);
 
 impl SimdField for GoldilocksExt2x8 {
@@ -7,7 +7,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, FFTField, Field, SimdField};
+use arith::{field_common, impl_pod, FFTField, Field, SimdField};
 use ethnum::U256;
 use rand::Rng;
 use rand::RngCore;
@@ -39,6 +39,8 @@ pub struct AVXGoldilocks {
     pub v: [__m256i; 2],
 }
 
+impl_pod!(AVXGoldilocks { v: [__m256i; 2] });
+
 field_common!(AVXGoldilocks);
 
 impl ExpSerde for AVXGoldilocks {
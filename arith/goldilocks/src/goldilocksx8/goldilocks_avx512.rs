@@ -7,7 +7,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, FFTField, Field, SimdField};
+use arith::{field_common, impl_pod, FFTField, Field, SimdField};
 use ethnum::U256;
 use rand::Rng;
 use rand::RngCore;
@@ -36,6 +36,8 @@ pub struct AVXGoldilocks {
     pub v: __m512i,
 }
 
+impl_pod!(AVXGoldilocks { v: __m512i });
+
 field_common!(AVXGoldilocks);
 
 impl ExpSerde for AVXGoldilocks {
@@ -4,7 +4,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, FFTField, Field, SimdField};
+use arith::{field_common, impl_pod, FFTField, Field, SimdField};
 use ethnum::U256;
 use rand::RngCore;
 use serdes::ExpSerde;
@@ -22,6 +22,8 @@ pub struct NeonGoldilocks {
     pub v: [Goldilocks; 8],
 }
 
+impl_pod!(NeonGoldilocks { v: [Goldilocks; 8] });
+
 field_common!(NeonGoldilocks);
 
 impl Field for NeonGoldilocks {
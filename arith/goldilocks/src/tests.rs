@@ -1,7 +1,7 @@
 use arith::{
-    random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_from_limbs_to_limbs_tests, random_inversion_tests, random_simd_field_tests,
-    ExtensionField, Field,
+    random_canonical_field_tests, random_extension_field_tests, random_fft_field_tests,
+    random_field_tests, random_from_limbs_to_limbs_tests, random_inversion_tests,
+    random_simd_differential_tests, random_simd_field_tests, ExtensionField, Field,
 };
 use ark_std::test_rng;
 use ethnum::U256;
@@ -47,6 +47,7 @@ fn test_avx_version() {
 fn test_base_field() {
     random_field_tests::<Goldilocks>("Goldilocks".to_string());
     random_simd_field_tests::<Goldilocks>("Goldilocks".to_string());
+    random_canonical_field_tests::<Goldilocks>("Goldilocks".to_string());
 
     let mut rng = test_rng();
     random_inversion_tests::<Goldilocks, _>(&mut rng, "Goldilocks".to_string());
@@ -62,6 +63,7 @@ fn test_simd_field() {
     random_fft_field_tests::<Goldilocksx8>("Goldilocksx8".to_string());
 
     random_simd_field_tests::<Goldilocksx8>("Goldilocksx8".to_string());
+    random_simd_differential_tests::<Goldilocksx8>("Goldilocksx8".to_string());
 
     let a = Goldilocksx8::from(256u32 + 2);
     let mut buffer = vec![];
@@ -4,7 +4,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, Field, SimdField};
+use arith::{canonical_u32_from_bytes, field_common, impl_pod, CanonicalField, Field, SimdField};
 use ethnum::U256;
 use rand::RngCore;
 use serdes::{ExpSerde, SerdeResult};
@@ -37,6 +37,8 @@ pub struct M31 {
     pub v: u32,
 }
 
+impl_pod!(M31 { v: u32 });
+
 impl PartialEq for M31 {
     #[inline(always)]
     fn eq(&self, other: &Self) -> bool {
@@ -81,6 +83,20 @@ impl ExpSerde for M31 {
     }
 }
 
+impl CanonicalField for M31 {
+    const CANONICAL_SIZE: usize = 4;
+
+    #[inline(always)]
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        mod_reduce_u32_safe(self.v).to_le_bytes().to_vec()
+    }
+
+    #[inline(always)]
+    fn try_from_canonical_bytes(bytes: &[u8]) -> Option<Self> {
+        canonical_u32_from_bytes(bytes, M31_MOD).map(|v| M31 { v })
+    }
+}
+
 impl M31 {
     // Add two M31 without mod reduction
     #[inline(always)]
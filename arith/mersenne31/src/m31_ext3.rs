@@ -6,7 +6,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, Field};
+use arith::{field_common, impl_pod, CanonicalField, Field};
 use arith::{ExtensionField, SimdField};
 use serdes::ExpSerde;
 
@@ -20,6 +20,8 @@ pub struct M31Ext3 {
     pub v: [M31; 3],
 }
 
+impl_pod!(M31Ext3 { v: [M31; 3] });
+
 field_common!(M31Ext3);
 
 impl Field for M31Ext3 {
@@ -112,6 +114,27 @@ impl Field for M31Ext3 {
     }
 }
 
+impl CanonicalField for M31Ext3 {
+    const CANONICAL_SIZE: usize = 3 * M31::CANONICAL_SIZE;
+
+    #[inline(always)]
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.v.iter().flat_map(|v| v.to_canonical_bytes()).collect()
+    }
+
+    #[inline(always)]
+    fn try_from_canonical_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::CANONICAL_SIZE {
+            return None;
+        }
+        let mut v = [M31::ZERO; 3];
+        for (v, bytes) in v.iter_mut().zip(bytes.chunks(M31::CANONICAL_SIZE)) {
+            *v = M31::try_from_canonical_bytes(bytes)?;
+        }
+        Some(M31Ext3 { v })
+    }
+}
+
 impl ExtensionField for M31Ext3 {
     const DEGREE: usize = 3;
 
@@ -3,7 +3,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, ExtensionField, Field, SimdField};
+use arith::{field_common, impl_pod, ExtensionField, Field, SimdField};
 use ethnum::U256;
 use serdes::ExpSerde;
 
@@ -14,6 +14,8 @@ pub struct M31Ext3x16 {
     pub v: [M31x16; 3],
 }
 
+impl_pod!(M31Ext3x16 { v: [M31x16; 3] });
+
 field_common!(M31Ext3x16);
 
 impl SimdField for M31Ext3x16 {
@@ -1,4 +1,4 @@
-use arith::{field_common, ExtensionField, FFTField, Field};
+use arith::{field_common, impl_pod, ExtensionField, FFTField, Field};
 use ethnum::U256;
 use rand::RngCore;
 use serdes::ExpSerde;
@@ -14,6 +14,8 @@ pub struct M31Ext6 {
     pub v: [M31Ext3; 2],
 }
 
+impl_pod!(M31Ext6 { v: [M31Ext3; 2] });
+
 field_common!(M31Ext6);
 
 impl Field for M31Ext6 {
@@ -7,7 +7,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, Field, SimdField};
+use arith::{field_common, impl_pod, Field, SimdField};
 use ethnum::U256;
 use rand::{Rng, RngCore};
 use serdes::{ExpSerde, SerdeResult};
@@ -34,6 +34,8 @@ pub struct AVXM31 {
     pub v: [__m256i; 2],
 }
 
+impl_pod!(AVXM31 { v: [__m256i; 2] });
+
 field_common!(AVXM31);
 
 impl ExpSerde for AVXM31 {
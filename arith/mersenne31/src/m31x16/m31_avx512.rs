@@ -8,7 +8,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, Field, SimdField};
+use arith::{field_common, impl_pod, Field, SimdField};
 use ethnum::U256;
 use rand::{Rng, RngCore};
 use serdes::{ExpSerde, SerdeResult};
@@ -31,6 +31,8 @@ pub struct AVXM31 {
     pub v: __m512i,
 }
 
+impl_pod!(AVXM31 { v: __m512i });
+
 field_common!(AVXM31);
 
 impl ExpSerde for AVXM31 {
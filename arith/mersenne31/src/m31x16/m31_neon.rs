@@ -7,7 +7,7 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use arith::{field_common, Field, SimdField};
+use arith::{field_common, impl_pod, Field, SimdField};
 use ethnum::U256;
 use rand::{Rng, RngCore};
 use serdes::{ExpSerde, SerdeResult};
@@ -31,6 +31,8 @@ pub struct NeonM31 {
     pub v: [uint32x4_t; 4],
 }
 
+impl_pod!(NeonM31 { v: [uint32x4_t; 4] });
+
 field_common!(NeonM31);
 
 impl NeonM31 {
@@ -1,8 +1,8 @@
+use arith::{random_canonical_field_tests, random_from_limbs_to_limbs_tests, Field};
 use arith::{
     random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_inversion_tests, random_simd_field_tests, SimdField,
+    random_inversion_tests, random_simd_differential_tests, random_simd_field_tests, SimdField,
 };
-use arith::{random_from_limbs_to_limbs_tests, Field};
 use ark_std::test_rng;
 use ethnum::U256;
 use gkr_hashers::{FiatShamirHasher, PoseidonFiatShamirHasher, PoseidonStateTrait};
@@ -47,6 +47,7 @@ fn test_avx_version() {
 fn test_base_field() {
     random_field_tests::<M31>("M31".to_string());
     random_simd_field_tests::<M31>("M31".to_string());
+    random_canonical_field_tests::<M31>("M31".to_string());
 
     let mut rng = test_rng();
     random_inversion_tests::<M31, _>(&mut rng, "M31".to_string());
@@ -60,6 +61,7 @@ fn test_simd_field() {
     random_inversion_tests::<M31x16, _>(&mut rng, "Vectorized M31".to_string());
 
     random_simd_field_tests::<M31x16>("Vectorized M31".to_string());
+    random_simd_differential_tests::<M31x16>("Vectorized M31".to_string());
 
     let a = M31x16::from(256 + 2);
     let mut buffer = vec![];
@@ -75,6 +77,7 @@ fn test_ext_field() {
     random_field_tests::<M31Ext3>("M31 Ext3".to_string());
     random_extension_field_tests::<M31Ext3>("M31 Ext3".to_string());
     random_simd_field_tests::<M31Ext3>("Simd M31 Ext3".to_string());
+    random_canonical_field_tests::<M31Ext3>("M31 Ext3".to_string());
 
     random_field_tests::<M31Ext6>("M31 Ext6".to_string());
     random_extension_field_tests::<M31Ext6>("M31 Ext6".to_string());
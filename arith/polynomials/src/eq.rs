@@ -1,4 +1,10 @@
+use std::ops::Mul;
+
 use arith::Field;
+use rayon::prelude::*;
+
+/// Below this many terms the fused kernels stay on the current thread.
+const FUSED_PAR_THRESHOLD: usize = 1 << 14;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct EqPolynomial<F> {
@@ -139,8 +145,97 @@ impl<F: Field> EqPolynomial<F> {
     }
 }
 
+// Fused eq(r, i) * f_i kernels
+//
+// Instead of materializing the 2^n entries of eq(r, .), the kernels below split r in two halves
+// and build the two 2^(n/2) tables only, so that each f_i is read once and eq(r, i) is the product
+// of two table entries that stay in cache. The values may be packed SIMD fields, e.g., the circuit
+// values of a layer against a challenge over the extension field.
+impl<F: Field> EqPolynomial<F> {
+    /// sum_i eq(r, i) * vals[i], with i read LSB-first, i.e., the evaluation at r of the
+    /// multilinear extension of vals.
+    #[inline]
+    pub fn eq_weighted_sum<V, R>(r: &[F], vals: &[V]) -> R
+    where
+        V: Copy + Send + Sync,
+        F: Mul<V, Output = R>,
+        R: Field + Mul<F, Output = R>,
+    {
+        assert_eq!(vals.len(), 1 << r.len());
+
+        let (eq_lo, eq_hi) = Self::split_eq_tables(r);
+        Self::eq_weighted_sum_with_tables(&eq_lo, &eq_hi, vals)
+    }
+
+    /// Fix the lowest r.len() variables of vals to r, i.e.,
+    /// out[j] = sum_i eq(r, i) * vals[j * 2^r.len() + i].
+    #[inline]
+    pub fn eq_weighted_fold<V, R>(r: &[F], vals: &[V], out: &mut [R])
+    where
+        V: Copy + Send + Sync,
+        F: Mul<V, Output = R>,
+        R: Field + Mul<F, Output = R>,
+    {
+        let chunk_size = 1 << r.len();
+        assert_eq!(vals.len() % chunk_size, 0);
+        assert!(out.len() >= vals.len() / chunk_size);
+
+        let (eq_lo, eq_hi) = Self::split_eq_tables(r);
+        let fold_chunk = |(o, chunk): (&mut R, &[V])| {
+            *o = Self::eq_weighted_sum_with_tables(&eq_lo, &eq_hi, chunk)
+        };
+        if vals.len() < FUSED_PAR_THRESHOLD {
+            out.iter_mut()
+                .zip(vals.chunks(chunk_size))
+                .for_each(fold_chunk);
+        } else {
+            out.par_iter_mut()
+                .zip(vals.par_chunks(chunk_size))
+                .for_each(fold_chunk);
+        }
+    }
+}
+
 // Private functions
 impl<F: Field> EqPolynomial<F> {
+    /// eq(r_lo, .) and eq(r_hi, .), where r_lo is the lower (larger) half of r
+    #[inline]
+    fn split_eq_tables(r: &[F]) -> (Vec<F>, Vec<F>) {
+        let (r_lo, r_hi) = r.split_at(r.len() - r.len() / 2);
+        let mut eq_lo = vec![F::ZERO; 1 << r_lo.len()];
+        let mut eq_hi = vec![F::ZERO; 1 << r_hi.len()];
+        Self::build_eq_x_r_with_buf(r_lo, &F::ONE, &mut eq_lo);
+        Self::build_eq_x_r_with_buf(r_hi, &F::ONE, &mut eq_hi);
+        (eq_lo, eq_hi)
+    }
+
+    #[inline]
+    fn eq_weighted_sum_with_tables<V, R>(eq_lo: &[F], eq_hi: &[F], vals: &[V]) -> R
+    where
+        V: Copy + Send + Sync,
+        F: Mul<V, Output = R>,
+        R: Field + Mul<F, Output = R>,
+    {
+        let row_sum = |(row, w_hi): (&[V], &F)| {
+            let inner = row
+                .iter()
+                .zip(eq_lo)
+                .fold(R::ZERO, |acc, (v, w_lo)| acc + *w_lo * *v);
+            inner * *w_hi
+        };
+        if vals.len() < FUSED_PAR_THRESHOLD {
+            vals.chunks(eq_lo.len())
+                .zip(eq_hi)
+                .map(row_sum)
+                .fold(R::ZERO, |acc, t| acc + t)
+        } else {
+            vals.par_chunks(eq_lo.len())
+                .zip(eq_hi)
+                .map(row_sum)
+                .reduce(|| R::ZERO, |a, b| a + b)
+        }
+    }
+
     #[inline(always)]
     fn eq(x: &F, y: &F) -> F {
         // x * y + (1 - x) * (1 - y)
@@ -0,0 +1,39 @@
+/// The convention relating the variables of an evaluation point to the bits of a hypercube
+/// index, i.e., how evaluations of a multilinear polynomial are laid out in memory.
+///
+/// Converting a point between layouts amounts to reversing it, so a polynomial, a commitment
+/// or a claim in either layout can be used without permuting the evaluations themselves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HypercubeLayout {
+    /// `point[i]` binds bit `i` of the index, i.e., the first variable is the least significant
+    /// bit. This is Expander's convention, see `MultiLinearPoly::evaluate_with_buffer`.
+    #[default]
+    LsbFirst,
+    /// `point[i]` binds bit `n - 1 - i` of the index, i.e., the first variable is the most
+    /// significant bit. This is the convention of the generic sumcheck and of Hyperplonk, see
+    /// `MultiLinearPoly::eval_reverse_order`.
+    MsbFirst,
+}
+
+impl HypercubeLayout {
+    /// Express a point given in `self` layout in the `target` layout.
+    #[inline]
+    pub fn convert_point<F: Clone>(&self, point: &[F], target: HypercubeLayout) -> Vec<F> {
+        let mut ret = point.to_vec();
+        if *self != target {
+            ret.reverse();
+        }
+        ret
+    }
+
+    /// Express the index of a hypercube point in `self` layout in the `target` layout.
+    #[inline]
+    pub fn convert_index(&self, index: usize, num_vars: usize, target: HypercubeLayout) -> usize {
+        debug_assert!(num_vars >= usize::BITS as usize || index < 1 << num_vars);
+        if *self == target || num_vars == 0 {
+            index
+        } else {
+            index.reverse_bits() >> (usize::BITS as usize - num_vars)
+        }
+    }
+}
@@ -10,6 +10,12 @@ pub use univariate::*;
 mod eq;
 pub use eq::*;
 
+mod layout;
+pub use layout::*;
+
+mod matrix;
+pub use matrix::*;
+
 mod sum_of_products;
 pub use sum_of_products::*;
 
@@ -0,0 +1,147 @@
+use std::slice::ChunksExact;
+
+use arith::{ExtensionField, Field};
+use itertools::izip;
+use rayon::prelude::*;
+
+/// A zero-copy view of the evaluations of a multilinear polynomial as a row-major matrix: the
+/// low `num_col_vars` variables index the columns, and the high variables the rows, i.e.,
+/// entry (i, j) is the evaluation at index `i * num_cols + j`.
+///
+/// This is the matrix of the tensor PCSes, e.g., the rows committed to by Hyrax and Dory, or
+/// encoded by Ligero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixView<'a, F> {
+    evals: &'a [F],
+    num_col_vars: usize,
+}
+
+impl<'a, F> MatrixView<'a, F> {
+    /// The view of `evals` with `2^num_col_vars` columns.
+    ///
+    /// # Panics
+    /// If `evals` is empty or does not fill a whole number of rows.
+    #[inline]
+    pub fn new(evals: &'a [F], num_col_vars: usize) -> Self {
+        assert!(
+            !evals.is_empty() && evals.len() % (1 << num_col_vars) == 0,
+            "{} evaluations do not fill rows of {} columns",
+            evals.len(),
+            1usize << num_col_vars
+        );
+        Self {
+            evals,
+            num_col_vars,
+        }
+    }
+
+    /// The evaluations, row after row
+    #[inline]
+    pub fn evals(&self) -> &'a [F] {
+        self.evals
+    }
+
+    #[inline]
+    pub fn num_col_vars(&self) -> usize {
+        self.num_col_vars
+    }
+
+    #[inline]
+    pub fn num_cols(&self) -> usize {
+        1 << self.num_col_vars
+    }
+
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        self.evals.len() >> self.num_col_vars
+    }
+
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> &'a F {
+        assert!(col < self.num_cols());
+        &self.evals[(row << self.num_col_vars) + col]
+    }
+
+    #[inline]
+    pub fn row(&self, row: usize) -> &'a [F] {
+        &self.evals[row << self.num_col_vars..(row + 1) << self.num_col_vars]
+    }
+
+    #[inline]
+    pub fn rows(&self) -> ChunksExact<'a, F> {
+        self.evals.chunks_exact(self.num_cols())
+    }
+
+    #[inline]
+    pub fn par_rows(&self) -> rayon::slice::ChunksExact<'a, F>
+    where
+        F: Sync,
+    {
+        self.evals.par_chunks_exact(self.num_cols())
+    }
+
+    /// The `num_blocks` views of consecutive rows the matrix splits into, e.g., the shares of the
+    /// processes of a polynomial distributed over its top variables.
+    ///
+    /// # Panics
+    /// If the rows do not split evenly into `num_blocks` blocks.
+    #[inline]
+    pub fn row_blocks(&self, num_blocks: usize) -> impl Iterator<Item = MatrixView<'a, F>> {
+        assert!(num_blocks > 0 && self.num_rows() % num_blocks == 0);
+        let num_col_vars = self.num_col_vars;
+        self.evals
+            .chunks_exact(self.evals.len() / num_blocks)
+            .map(move |block| MatrixView::new(block, num_col_vars))
+    }
+
+    /// Write the matrix in column-major order into `out`, i.e., the evaluations with the row
+    /// variables moved below the column ones.
+    #[inline]
+    pub fn transpose_into(&self, out: &mut [F])
+    where
+        F: Copy,
+    {
+        assert_eq!(out.len(), self.evals.len());
+        transpose::transpose(self.evals, out, self.num_cols(), self.num_rows());
+    }
+
+    /// The matrix in column-major order, see `transpose_into`
+    #[inline]
+    pub fn transpose(&self) -> Vec<F>
+    where
+        F: Copy + Default,
+    {
+        let mut out = vec![F::default(); self.evals.len()];
+        self.transpose_into(&mut out);
+        out
+    }
+}
+
+impl<F: Field> MatrixView<'_, F> {
+    /// sum_i coeffs[i] * row_i, e.g., the evaluations with the row variables fixed to `r` for
+    /// the coefficients eq(r, .).
+    pub fn combine_rows<EvalF>(&self, coeffs: &[EvalF]) -> Vec<EvalF>
+    where
+        EvalF: ExtensionField<BaseField = F>,
+    {
+        assert_eq!(coeffs.len(), self.num_rows());
+        let num_cols = self.num_cols();
+
+        self.par_rows()
+            .zip(coeffs.par_iter())
+            .fold(
+                || vec![EvalF::ZERO; num_cols],
+                |mut acc, (row, coeff)| {
+                    izip!(&mut acc, row).for_each(|(a, e)| *a += coeff.mul_by_base_field(e));
+                    acc
+                },
+            )
+            .reduce(
+                || vec![EvalF::ZERO; num_cols],
+                |mut acc, other| {
+                    izip!(&mut acc, &other).for_each(|(a, o)| *a += o);
+                    acc
+                },
+            )
+    }
+}
@@ -2,7 +2,7 @@ use std::ops::{Add, Index, IndexMut, Mul};
 
 use arith::Field;
 
-use crate::MultiLinearPoly;
+use crate::{MatrixView, MultiLinearPoly};
 
 pub trait MultilinearExtension<F: Field>: Index<usize, Output = F> + Send + Sync {
     fn evaluate_with_buffer<ChallengeF, EvalF>(
@@ -42,6 +42,12 @@ pub trait MultilinearExtension<F: Field>: Index<usize, Output = F> + Send + Sync
 
     fn hypercube_basis_ref(&self) -> &[F];
 
+    /// The evaluations as a matrix of `2^num_col_vars` columns, see `MatrixView`
+    #[inline(always)]
+    fn as_matrix(&self, num_col_vars: usize) -> MatrixView<'_, F> {
+        MatrixView::new(self.hypercube_basis_ref(), num_col_vars)
+    }
+
     fn interpolate_over_hypercube(&self) -> Vec<F>;
 }
 
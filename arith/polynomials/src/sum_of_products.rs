@@ -1,14 +1,22 @@
+use std::sync::Arc;
+
 use arith::Field;
 
 use crate::{EqPolynomial, MultiLinearPoly, MultilinearExtension};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
-/// A special form of a multi-linear polynomial: f = f0*g0 + f1*g1 + ...
-/// where f0, f1, ...  and g0, g1, ... are multi-linear polynomials
+/// A special form of a multi-linear polynomial: f = c0*f0*g0 + c1*f1*g1 + ...
+/// where f0, f1, ...  and g0, g1, ... are multi-linear polynomials, and c0, c1, ... are scalars
 /// The sumcheck over this polynomial has a degree of 2
+///
+/// The polynomials are shared, so that building the sum of products of existing polynomials
+/// does not copy them. The sumcheck prover fixes the variables of shared polynomials into
+/// new, half sized ones, and in place otherwise.
 pub struct SumOfProductsPoly<F: Field> {
     /// The list of multi-linear polynomials to be summed
-    pub f_and_g_pairs: Vec<(MultiLinearPoly<F>, MultiLinearPoly<F>)>,
+    pub f_and_g_pairs: Vec<(Arc<MultiLinearPoly<F>>, Arc<MultiLinearPoly<F>>)>,
+    /// The weight of each pair, of the same length as `f_and_g_pairs`
+    pub weights: Vec<F>,
 }
 
 impl<F: Field> SumOfProductsPoly<F> {
@@ -17,9 +25,24 @@ impl<F: Field> SumOfProductsPoly<F> {
     pub fn new() -> Self {
         Self {
             f_and_g_pairs: vec![],
+            weights: vec![],
         }
     }
 
+    /// Create a SumOfProducts instance from pairs of weight one
+    #[inline]
+    pub fn from_pairs<P0, P1>(f_and_g_pairs: Vec<(P0, P1)>) -> Self
+    where
+        P0: Into<Arc<MultiLinearPoly<F>>>,
+        P1: Into<Arc<MultiLinearPoly<F>>>,
+    {
+        let mut poly = Self::new();
+        f_and_g_pairs
+            .into_iter()
+            .for_each(|(f, g)| poly.add_pair(f, g));
+        poly
+    }
+
     /// Get the number of variables in the polynomial
     #[inline]
     pub fn num_vars(&self) -> usize {
@@ -31,16 +54,46 @@ impl<F: Field> SumOfProductsPoly<F> {
     }
 
     #[inline]
-    pub fn add_pair(&mut self, poly0: MultiLinearPoly<F>, poly1: MultiLinearPoly<F>) {
+    pub fn add_pair(
+        &mut self,
+        poly0: impl Into<Arc<MultiLinearPoly<F>>>,
+        poly1: impl Into<Arc<MultiLinearPoly<F>>>,
+    ) {
+        self.add_weighted_pair(poly0, poly1, F::ONE);
+    }
+
+    /// Add the term weight * poly0 * poly1, e.g., a term of a random linear combination, or a
+    /// subtracted term with a weight of -1, without scaling either polynomial beforehand
+    #[inline]
+    pub fn add_weighted_pair(
+        &mut self,
+        poly0: impl Into<Arc<MultiLinearPoly<F>>>,
+        poly1: impl Into<Arc<MultiLinearPoly<F>>>,
+        weight: F,
+    ) {
+        let (poly0, poly1) = (poly0.into(), poly1.into());
         assert_eq!(poly0.num_vars(), poly1.num_vars());
         self.f_and_g_pairs.push((poly0, poly1));
+        self.weights.push(weight);
+    }
+
+    #[inline]
+    pub fn sub_pair(
+        &mut self,
+        poly0: impl Into<Arc<MultiLinearPoly<F>>>,
+        poly1: impl Into<Arc<MultiLinearPoly<F>>>,
+    ) {
+        self.add_weighted_pair(poly0, poly1, -F::ONE);
     }
 
     #[inline]
     pub fn evaluate(&self, point: &[F]) -> F {
+        assert_eq!(self.f_and_g_pairs.len(), self.weights.len());
+
         self.f_and_g_pairs
             .iter()
-            .map(|(f, g)| {
+            .zip(self.weights.iter())
+            .map(|((f, g), &weight)| {
                 // 1. point is big endian here
                 // 2. for smaller but dense multilinear polynomials, we assume the mle values
                 // locate at (0 -- poly_size)
@@ -51,15 +104,26 @@ impl<F: Field> SumOfProductsPoly<F> {
                 f.eval_reverse_order(point_vars_for_polys)
                     * g.eval_reverse_order(point_vars_for_polys)
                     * EqPolynomial::ith_eq_vec_elem(point_vars_remaining, 0).square()
+                    * weight
             })
             .sum()
     }
 
     #[inline]
     pub fn sum(&self) -> F {
+        assert_eq!(self.f_and_g_pairs.len(), self.weights.len());
+
         self.f_and_g_pairs
             .iter()
-            .flat_map(|(f, g)| f.coeffs.iter().zip(g.coeffs.iter()).map(|(&f, &g)| f * g))
+            .zip(self.weights.iter())
+            .map(|((f, g), &weight)| {
+                f.coeffs
+                    .iter()
+                    .zip(g.coeffs.iter())
+                    .map(|(&f, &g)| f * g)
+                    .sum::<F>()
+                    * weight
+            })
             .sum::<F>()
     }
 }
@@ -133,6 +133,28 @@ fn test_ith_eq_vec_elem() {
     }
 }
 
+#[test]
+fn test_eq_weighted_sum_and_fold() {
+    let mut rng = test_rng();
+    // 15 variables go through the multi-threaded path
+    for nv in [0, 1, 4, 7, 10, 15] {
+        let es: Vec<Fr> = (0..1 << nv).map(|_| Fr::random_unsafe(&mut rng)).collect();
+        let point: Vec<Fr> = (0..nv).map(|_| Fr::random_unsafe(&mut rng)).collect();
+        let mut scratch = vec![Fr::ZERO; 1 << nv];
+
+        let expect_eval = MultiLinearPoly::evaluate_with_buffer(&es, &point, &mut scratch);
+        assert_eq!(EqPolynomial::eq_weighted_sum(&point, &es), expect_eval);
+
+        // folding the lower variables, then summing the rest, is the full evaluation
+        for num_folded in [0, nv / 2, nv] {
+            let (lo, hi) = point.split_at(num_folded);
+            let mut folded = vec![Fr::ZERO; 1 << hi.len()];
+            EqPolynomial::eq_weighted_fold(lo, &es, &mut folded);
+            assert_eq!(EqPolynomial::eq_weighted_sum(hi, &folded), expect_eval);
+        }
+    }
+}
+
 /// Naive method to build eq(x, r).
 /// Only used for testing purpose.
 // Evaluate
@@ -259,3 +281,100 @@ fn test_univariate_degree_one_quotient() {
         assert_eq!(poly.coeffs, vec![Fr::ONE, Fr::ZERO, Fr::ZERO, Fr::ZERO]);
     }
 }
+
+#[test]
+fn test_hypercube_layout() {
+    let mut rng = test_rng();
+    for nv in 1..10 {
+        let mle = MultiLinearPoly::<Fr>::random(nv, &mut rng);
+        let point = (0..nv)
+            .map(|_| Fr::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+
+        // the same evaluations, laid out with the other convention
+        let permuted = MultiLinearPoly::new(
+            (0..1 << nv)
+                .map(|i| {
+                    let j =
+                        HypercubeLayout::MsbFirst.convert_index(i, nv, HypercubeLayout::LsbFirst);
+                    mle.coeffs[j]
+                })
+                .collect(),
+        );
+
+        let mut scratch = vec![Fr::zero(); 1 << nv];
+        let expected = MultiLinearPoly::evaluate_with_buffer(&mle.coeffs, &point, &mut scratch);
+
+        let converted = HypercubeLayout::LsbFirst.convert_point(&point, HypercubeLayout::MsbFirst);
+        assert_eq!(permuted.eval_reverse_order(&point), expected);
+        assert_eq!(
+            MultiLinearPoly::evaluate_with_buffer(&permuted.coeffs, &converted, &mut scratch),
+            expected
+        );
+        assert_eq!(
+            HypercubeLayout::MsbFirst.convert_point(&converted, HypercubeLayout::LsbFirst),
+            point
+        );
+    }
+}
+
+#[test]
+fn test_matrix_view() {
+    let mut rng = test_rng();
+    for nv in 1..10 {
+        let mle = MultiLinearPoly::<Fr>::random(nv, &mut rng);
+        let point = (0..nv)
+            .map(|_| Fr::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+        let mut scratch = vec![Fr::zero(); 1 << nv];
+        let expected = MultiLinearPoly::evaluate_with_buffer(&mle.coeffs, &point, &mut scratch);
+
+        for num_col_vars in 0..=nv {
+            let matrix = mle.as_matrix(num_col_vars);
+            let num_row_vars = nv - num_col_vars;
+            assert_eq!(matrix.num_rows(), 1 << num_row_vars);
+            assert_eq!(matrix.num_cols(), 1 << num_col_vars);
+            assert_eq!(matrix.rows().count(), matrix.num_rows());
+            matrix.rows().enumerate().for_each(|(i, row)| {
+                assert_eq!(row, matrix.row(i));
+                row.iter()
+                    .enumerate()
+                    .for_each(|(j, e)| assert_eq!(e, matrix.get(i, j)));
+            });
+
+            // the row variables fixed, then the column ones
+            let (col_point, row_point) = point.split_at(num_col_vars);
+            let row = matrix.combine_rows(&EqPolynomial::build_eq_x_r(row_point));
+            assert_eq!(
+                EqPolynomial::eq_weighted_sum::<Fr, Fr>(col_point, &row),
+                expected
+            );
+
+            // the transpose holds the row variables below the column ones
+            let transposed = matrix.transpose();
+            let rotated_point = [row_point, col_point].concat();
+            assert_eq!(
+                MultiLinearPoly::evaluate_with_buffer(&transposed, &rotated_point, &mut scratch),
+                expected
+            );
+            assert_eq!(
+                MatrixView::new(&transposed, num_row_vars).transpose(),
+                mle.coeffs
+            );
+
+            for num_block_vars in 0..=num_row_vars {
+                let blocks: Vec<_> = matrix.row_blocks(1 << num_block_vars).collect();
+                assert_eq!(blocks.len(), 1 << num_block_vars);
+                assert!(blocks.iter().all(|b| b.num_cols() == matrix.num_cols()));
+                assert_eq!(
+                    blocks
+                        .iter()
+                        .flat_map(|b| b.evals())
+                        .copied()
+                        .collect::<Vec<_>>(),
+                    mle.coeffs
+                );
+            }
+        }
+    }
+}
@@ -2,10 +2,28 @@ use ethnum::U256;
 use halo2curves::ff::{Field as Halo2Field, FromUniformBytes, PrimeField};
 use rand::RngCore;
 
-use crate::{ExtensionField, FFTField, Field, SimdField};
+use crate::{impl_pod, CanonicalField, ExtensionField, FFTField, Field, SimdField};
 
 pub use halo2curves::bn256::Fr;
 
+// NOTE: the field elements and the curve points of BN254 are sent to the other processes by the
+// distributed PCSes, the elements are of 4 limbs, and the points of their coordinates.
+//
+// The limbs of the elements are private to halo2curves, so only their sizes are checked: an
+// element is the array of its 4 limbs in Montgomery form, and any 4 limbs sent by another
+// process of the same build are the limbs of the element it sent.
+impl_pod!(opaque Fr { [u64; 4] });
+impl_pod!(opaque halo2curves::bn256::Fq { [u64; 4] });
+impl_pod!(halo2curves::bn256::G1Affine {
+    x: halo2curves::bn256::Fq,
+    y: halo2curves::bn256::Fq
+});
+impl_pod!(halo2curves::bn256::G1 {
+    x: halo2curves::bn256::Fq,
+    y: halo2curves::bn256::Fq,
+    z: halo2curves::bn256::Fq
+});
+
 pub(crate) const MODULUS: U256 = U256([
     0x2833e84879b9709143e1f593f0000001,
     0x30644e72e131a029b85045b68181585d,
@@ -151,6 +169,20 @@ impl SimdField for Fr {
     const PACK_SIZE: usize = 1;
 }
 
+impl CanonicalField for Fr {
+    const CANONICAL_SIZE: usize = 32;
+
+    #[inline(always)]
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+
+    #[inline(always)]
+    fn try_from_canonical_bytes(bytes: &[u8]) -> Option<Self> {
+        Fr::from_bytes(bytes.try_into().ok()?).into_option()
+    }
+}
+
 impl ExtensionField for Fr {
     const DEGREE: usize = 1;
 
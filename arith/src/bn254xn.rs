@@ -11,13 +11,17 @@ use halo2curves::{bn256::Fr, ff::Field as _};
 use rand::RngCore;
 use serdes::ExpSerde;
 
-use crate::{rep_field_common, ExtensionField, Field, SimdField};
+use crate::{rep_field_common, ExtensionField, Field, Pod, SimdField};
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct FrxN<const N: usize> {
     pub v: [Fr; N],
 }
 
+unsafe impl<const N: usize> Pod for FrxN<N> {
+    const FIELDS_SIZE: usize = <[Fr; N] as Pod>::FIELDS_SIZE;
+}
+
 impl<const N: usize> Default for FrxN<N> {
     fn default() -> Self {
         Self { v: [Fr::zero(); N] }
@@ -0,0 +1,60 @@
+use crate::Field;
+
+/// Checked conversions between field elements and their canonical encodings, for values coming
+/// from outside the prover, e.g., the witnesses of an external compiler or the arguments of an
+/// FFI call.
+///
+/// The canonical encoding of an element is the little endian bytes of its coefficients, each
+/// reduced to [0, modulus). Unlike `ExpSerde::deserialize_from`, which may silently reduce an
+/// out of range value, the conversions below reject any input that is not the canonical encoding
+/// of an element.
+pub trait CanonicalField: Field {
+    /// Number of bytes of the canonical encoding
+    const CANONICAL_SIZE: usize;
+
+    /// The canonical encoding of the element, of length `CANONICAL_SIZE`
+    fn to_canonical_bytes(&self) -> Vec<u8>;
+
+    /// The element encoded by `bytes`, or None if `bytes` is not of length `CANONICAL_SIZE` or not
+    /// canonical
+    fn try_from_canonical_bytes(bytes: &[u8]) -> Option<Self>;
+
+    /// The canonical encoding of the element as little endian u64 limbs, the last one zero padded
+    #[inline]
+    fn to_u64_limbs(&self) -> Vec<u64> {
+        self.to_canonical_bytes()
+            .chunks(8)
+            .map(|chunk| {
+                let mut limb = [0u8; 8];
+                limb[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(limb)
+            })
+            .collect()
+    }
+
+    /// The element encoded by the little endian u64 `limbs`, or None if the limbs are not the
+    /// zero padded canonical encoding of an element
+    #[inline]
+    fn try_from_u64_limbs(limbs: &[u64]) -> Option<Self> {
+        if limbs.len() != Self::CANONICAL_SIZE.div_ceil(8) {
+            return None;
+        }
+        let bytes = limbs
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect::<Vec<_>>();
+        let (bytes, padding) = bytes.split_at(Self::CANONICAL_SIZE);
+        if padding.iter().any(|&b| b != 0) {
+            return None;
+        }
+        Self::try_from_canonical_bytes(bytes)
+    }
+}
+
+/// The u32 encoded by the 4 `bytes` if it is below `modulus`, for the canonical encodings of
+/// 32-bit fields
+#[inline]
+pub fn canonical_u32_from_bytes(bytes: &[u8], modulus: u32) -> Option<u32> {
+    let v = u32::from_le_bytes(bytes.try_into().ok()?);
+    (v < modulus).then_some(v)
+}
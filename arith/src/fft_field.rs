@@ -1,4 +1,5 @@
 use itertools::izip;
+use serdes::ExpSerde;
 
 use crate::{bit_reverse, Field};
 
@@ -77,24 +78,76 @@ fn bit_reverse_swap<F: Copy>(elems: &mut [F]) {
     }
 }
 
+/// Twiddle factors of the radix-2 FFT of length `2^log_n` and of its inverse, computed once for
+/// all the FFTs of that length, and possibly persisted with `serdes::store_to_file`.
+#[derive(Debug, Clone, Default, PartialEq, ExpSerde)]
+pub struct FFTTwiddles<F: FFTField> {
+    pub log_n: usize,
+    /// omega^i for i in 0..n/2
+    pub twiddles: Vec<F>,
+    /// omega^-i for i in 0..n/2
+    pub inv_twiddles: Vec<F>,
+}
+
+impl<F: FFTField> FFTTwiddles<F> {
+    pub fn new(log_n: usize) -> Self {
+        let omega = F::two_adic_generator(log_n);
+        let omega_inv = omega.inv().unwrap();
+
+        Self {
+            log_n,
+            twiddles: powers(omega, (1 << log_n) / 2),
+            inv_twiddles: powers(omega_inv, (1 << log_n) / 2),
+        }
+    }
+
+    #[inline(always)]
+    pub fn fft_in_place(&self, poly: &mut [F]) {
+        assert_eq!(poly.len(), 1 << self.log_n);
+        radix2_fft_with_twiddles(poly, &self.twiddles)
+    }
+
+    #[inline(always)]
+    pub fn ifft_in_place(&self, evals: &mut [F]) {
+        assert_eq!(evals.len(), 1 << self.log_n);
+        let n_inv = F::ONE.double().exp(self.log_n as u128).inv().unwrap();
+
+        radix2_fft_with_twiddles(evals, &self.inv_twiddles);
+        evals.iter_mut().for_each(|x| *x *= n_inv);
+    }
+}
+
+#[inline(always)]
+fn powers<F: Field>(base: F, n: usize) -> Vec<F> {
+    (0..n)
+        .scan(F::ONE, |w, _| {
+            let tw = *w;
+            *w *= &base;
+            Some(tw)
+        })
+        .collect()
+}
+
 #[inline(always)]
 pub fn radix2_fft_single_threaded<F: FFTField>(coeffs: &mut [F], omega: F) {
     assert!(coeffs.len().is_power_of_two());
 
+    // precompute twiddle factors
+    let twiddles = powers(omega, coeffs.len() / 2);
+    radix2_fft_with_twiddles(coeffs, &twiddles)
+}
+
+/// Radix-2 FFT with the twiddle factors `omega^i` for i in 0..n/2 given.
+#[inline(always)]
+pub fn radix2_fft_with_twiddles<F: FFTField>(coeffs: &mut [F], twiddles: &[F]) {
+    assert!(coeffs.len().is_power_of_two());
+    assert_eq!(twiddles.len(), coeffs.len() / 2);
+
     bit_reverse_swap(coeffs);
 
     let n = coeffs.len();
     let log_n = n.ilog2() as usize;
 
-    // precompute twiddle factors
-    let twiddles: Vec<_> = (0..(n / 2))
-        .scan(F::ONE, |w, _| {
-            let tw = *w;
-            *w *= &omega;
-            Some(tw)
-        })
-        .collect();
-
     let mut chunk = 2_usize;
     let mut twiddle_chunk = n / 2;
     for _ in 0..log_n {
@@ -128,7 +181,9 @@ mod fft_test {
     use halo2curves::bn256::Fr;
     use itertools::izip;
 
-    use crate::{FFTField, Field};
+    use serdes::ExpSerde;
+
+    use crate::{FFTField, FFTTwiddles, Field};
 
     #[test]
     fn test_bn254_fft() {
@@ -146,4 +201,30 @@ mod fft_test {
             izip!(&coeffs, &coeffs_cloned).for_each(|(a, b)| assert_eq!(a, b));
         });
     }
+
+    #[test]
+    fn test_bn254_fft_twiddles() {
+        let mut rng = test_rng();
+
+        (1..10).for_each(|bits| {
+            let length = 1 << bits;
+
+            let mut buf = vec![];
+            FFTTwiddles::<Fr>::new(bits)
+                .serialize_into(&mut buf)
+                .unwrap();
+            let twiddles = FFTTwiddles::<Fr>::deserialize_from(buf.as_slice()).unwrap();
+
+            let coeffs: Vec<_> = (0..length).map(|_| Fr::random_unsafe(&mut rng)).collect();
+            let mut evals = coeffs.clone();
+            let mut expected = coeffs.clone();
+
+            twiddles.fft_in_place(&mut evals);
+            Fr::fft_in_place(&mut expected);
+            assert_eq!(evals, expected);
+
+            twiddles.ifft_in_place(&mut evals);
+            assert_eq!(evals, coeffs);
+        });
+    }
 }
@@ -3,6 +3,8 @@ use ethnum::U256;
 use rand::RngCore;
 use serdes::ExpSerde;
 
+use crate::Pod;
+
 use std::{
     fmt::Debug,
     hash::Hash,
@@ -38,10 +40,9 @@ pub trait Field:
     + ExpSerde
     + Hash
     + Eq
-    + PartialOrd
-    + Ord
     + Send
     + Sync
+    + Pod
 {
     /// name
     const NAME: &'static str;
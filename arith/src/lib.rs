@@ -1,9 +1,15 @@
 #![cfg_attr(target_arch = "x86_64", feature(stdarch_x86_avx512))]
 #![feature(slice_swap_unchecked)]
 
+mod pod;
+pub use pod::*;
+
 mod field;
 pub use field::*;
 
+mod canonical;
+pub use canonical::*;
+
 mod fft_field;
 pub use fft_field::*;
 
@@ -19,7 +19,7 @@ use ethnum::U256;
 use serdes::{ExpSerde, SerdeResult};
 use utils::{from_monty, monty_reduce, to_monty};
 
-use crate::{FFTField, Field};
+use crate::{canonical_u32_from_bytes, CanonicalField, FFTField, Field, Pod};
 
 mod param;
 pub use param::*;
@@ -53,6 +53,11 @@ pub struct MontyField31<MP: MontyParameters> {
     _phantom: PhantomData<MP>,
 }
 
+// NOTE: the phantom parameters take no space
+unsafe impl<MP: MontyParameters> Pod for MontyField31<MP> {
+    const FIELDS_SIZE: usize = u32::FIELDS_SIZE;
+}
+
 impl<MP: FieldParameters> Display for MontyField31<MP> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", from_monty::<MP>(self.value))
@@ -122,6 +127,23 @@ impl<MP: FieldParameters> MontyField31<MP> {
         }
         output
     }
+
+    /// Compute `sum_i lhs[i] * rhs[i]` with a single Montgomery reduction.
+    ///
+    /// The 64-bit products are accumulated in `[0, P << MONTY_BITS)`, which only costs a
+    /// conditional subtraction per term instead of a full reduction.
+    #[inline(always)]
+    pub fn dot_product<const N: usize>(lhs: &[Self; N], rhs: &[Self; N]) -> Self {
+        let bound = (MP::PRIME as u64) << MP::MONTY_BITS;
+        let mut acc = 0u64;
+        for (l, r) in lhs.iter().zip(rhs.iter()) {
+            acc += l.value as u64 * r.value as u64;
+            if acc >= bound {
+                acc -= bound;
+            }
+        }
+        Self::new_monty(monty_reduce::<MP>(acc))
+    }
 }
 
 impl<MP: FieldParameters> Neg for MontyField31<MP> {
@@ -323,6 +345,20 @@ where
     }
 }
 
+impl<MP: FieldParameters> CanonicalField for MontyField31<MP> {
+    const CANONICAL_SIZE: usize = 4;
+
+    #[inline(always)]
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        from_monty::<MP>(self.value).to_le_bytes().to_vec()
+    }
+
+    #[inline(always)]
+    fn try_from_canonical_bytes(bytes: &[u8]) -> Option<Self> {
+        canonical_u32_from_bytes(bytes, MP::PRIME).map(Self::new)
+    }
+}
+
 impl<MP: FieldParameters> Field for MontyField31<MP> {
     const NAME: &'static str = "Monty Field";
 
@@ -0,0 +1,182 @@
+//! Plain old data, the values the MPI collectives send to the other processes as their memory.
+
+/// A type whose values are plain bytes: they own no memory and hold no pointer, and their bytes
+/// are exactly those of their fields, with no padding in between. Such values are sent to the
+/// other processes as their memory, e.g., by `MPIEngine::gather_vec`.
+///
+/// `FIELDS_SIZE` is the sum of the sizes of the fields, checked against the size of the type at
+/// compile time wherever the values are sent, see `Pod::NO_PADDING`: the padding bytes of a value
+/// are uninitialized, and would be sent as garbage. Implement the trait with `impl_pod!`, which
+/// also checks the listed fields against the layout of the type where it is defined.
+///
+/// ```compile_fail
+/// use arith::{impl_pod, Pod};
+///
+/// #[derive(Clone, Copy)]
+/// struct Padded {
+///     a: u8,
+///     b: u32,
+/// }
+///
+/// // 3 padding bytes after `a`
+/// impl_pod!(Padded { a: u8, b: u32 });
+/// ```
+///
+/// ```compile_fail
+/// use arith::{impl_pod, Pod};
+///
+/// #[derive(Clone)]
+/// struct Owning {
+///     v: Vec<u8>,
+/// }
+///
+/// // a `Vec` arrives as a dangling pointer
+/// impl_pod!(Owning { v: Vec<u8> });
+/// ```
+///
+/// ```compile_fail
+/// use arith::{impl_pod, Pod};
+///
+/// #[derive(Clone, Copy)]
+/// struct Mislisted {
+///     a: u32,
+///     b: [u8; 4],
+/// }
+///
+/// // the sizes add up, but `a` is not an array of bytes
+/// impl_pod!(Mislisted { a: [u8; 4], b: u32 });
+/// ```
+///
+/// ```compile_fail
+/// use arith::{impl_pod, Pod};
+///
+/// #[derive(Clone, Copy)]
+/// struct Flag {
+///     set: bool,
+/// }
+///
+/// // a byte other than 0 or 1 is not a `bool`, a flag is sent as a `u8`
+/// impl_pod!(Flag { set: bool });
+/// ```
+///
+/// # Safety
+/// The type holds no pointer or reference, and the bytes of a valid value are a valid value in
+/// the other processes, which run the same build.
+pub unsafe trait Pod: Copy + Send + Sync + 'static {
+    /// The sum of the sizes of the fields
+    const FIELDS_SIZE: usize;
+
+    /// Fails to evaluate, and thus fails the build, if the type has padding bytes
+    const NO_PADDING: () = assert!(
+        size_of::<Self>() == Self::FIELDS_SIZE,
+        "the type has padding bytes, it cannot be sent as its memory"
+    );
+}
+
+/// Implement `Pod` for a struct of the listed fields, e.g., `impl_pod!(M31 { v: u32 })`, or for
+/// types with no fields, e.g., the primitives or the SIMD registers, as `impl_pod!(u32, u64)`.
+///
+/// The fields of a struct are checked at compile time to be its fields, of the listed types, by
+/// accessing them, and to cover its bytes with no padding and no alignment beyond theirs, by their
+/// offsets. The fields being accessed, the macro is invoked where they are visible.
+///
+/// A type whose fields are private to another crate is listed with the types of its fields only,
+/// e.g., `impl_pod!(opaque Fr { [u64; 4] })`: their sizes are still checked to add up to the size
+/// of the type, but not their types, which the invocation justifies.
+#[macro_export]
+macro_rules! impl_pod {
+    (opaque $t:ty { $($field_ty:ty),+ $(,)? }) => {
+        unsafe impl $crate::Pod for $t {
+            const FIELDS_SIZE: usize = 0 $(+ <$field_ty as $crate::Pod>::FIELDS_SIZE)+;
+        }
+
+        const _: () = <$t as $crate::Pod>::NO_PADDING;
+    };
+    ($t:ty { $($field:tt: $field_ty:ty),+ $(,)? }) => {
+        unsafe impl $crate::Pod for $t {
+            const FIELDS_SIZE: usize = 0 $(+ <$field_ty as $crate::Pod>::FIELDS_SIZE)+;
+        }
+
+        const _: () = <$t as $crate::Pod>::NO_PADDING;
+        // a raw pointer, unlike a reference, is not coerced to the target of a `Deref` field
+        $(const _: fn(&$t) -> *const $field_ty = |value| core::ptr::addr_of!(value.$field);)+
+        const _: () = assert!(
+            $crate::fields_tile_layout(
+                &[$((
+                    core::mem::offset_of!($t, $field),
+                    core::mem::size_of::<$field_ty>(),
+                    core::mem::align_of::<$field_ty>(),
+                )),+],
+                core::mem::size_of::<$t>(),
+                core::mem::align_of::<$t>(),
+            ),
+            "the listed fields overlap or leave padding or alignment in the type"
+        );
+    };
+    ($($t:ty),+ $(,)?) => {
+        $(
+            unsafe impl $crate::Pod for $t {
+                const FIELDS_SIZE: usize = core::mem::size_of::<$t>();
+            }
+        )+
+    };
+}
+
+/// Whether the fields of the offsets, sizes and alignments `fields` are disjoint and cover the
+/// `size` bytes of a type, whose alignment `align` is the largest of theirs, see `impl_pod!`.
+#[doc(hidden)]
+pub const fn fields_tile_layout(
+    fields: &[(usize, usize, usize)],
+    size: usize,
+    align: usize,
+) -> bool {
+    let mut covered = 0;
+    let mut max_align = 1;
+    let mut i = 0;
+    while i < fields.len() {
+        let (offset, len, field_align) = fields[i];
+        if offset + len > size {
+            return false;
+        }
+        let mut j = 0;
+        while j < i {
+            let (other_offset, other_len, _) = fields[j];
+            if offset < other_offset + other_len && other_offset < offset + len {
+                return false;
+            }
+            j += 1;
+        }
+        covered += len;
+        if field_align > max_align {
+            max_align = field_align;
+        }
+        i += 1;
+    }
+    covered == size && max_align == align
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(target_arch = "x86_64")]
+impl_pod!(
+    std::arch::x86_64::__m128i,
+    std::arch::x86_64::__m256i,
+    std::arch::x86_64::__m512i
+);
+
+#[cfg(target_arch = "aarch64")]
+impl_pod!(std::arch::aarch64::uint32x4_t);
+
+// NOTE: the elements of an array are laid out one after another with no padding, as the size of a
+// type is a multiple of its alignment, an array has padding only if its elements have
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {
+    const FIELDS_SIZE: usize = N * T::FIELDS_SIZE;
+}
+
+unsafe impl<A: Pod, B: Pod> Pod for (A, B) {
+    const FIELDS_SIZE: usize = A::FIELDS_SIZE + B::FIELDS_SIZE;
+}
+
+unsafe impl<A: Pod, B: Pod, C: Pod> Pod for (A, B, C) {
+    const FIELDS_SIZE: usize = A::FIELDS_SIZE + B::FIELDS_SIZE + C::FIELDS_SIZE;
+}
@@ -6,13 +6,16 @@ use field::{
 };
 use rand::RngCore;
 
-use crate::{ExtensionField, FFTField, Field, SimdField};
+use crate::{CanonicalField, ExtensionField, FFTField, Field, SimdField};
 
 #[cfg(test)]
 mod bn254;
 
 mod field;
 
+mod differential;
+pub use differential::random_simd_differential_tests;
+
 #[cfg(target_arch = "x86_64")]
 #[test]
 fn test_mm256_const_init() {
@@ -147,6 +150,41 @@ pub fn random_from_limbs_to_limbs_tests<F: Field, ExtF: ExtensionField<BaseField
     })
 }
 
+pub fn random_canonical_field_tests<F: CanonicalField>(_name: String) {
+    let mut rng = test_rng();
+
+    (0..1000).for_each(|_| {
+        let a = F::random_unsafe(&mut rng);
+        let bytes = a.to_canonical_bytes();
+        assert_eq!(bytes.len(), F::CANONICAL_SIZE);
+        assert_eq!(F::try_from_canonical_bytes(&bytes), Some(a));
+        assert_eq!(F::try_from_u64_limbs(&a.to_u64_limbs()), Some(a));
+    });
+
+    // the modulus, the smallest value out of range, in the lowest coefficient
+    let mut bytes = vec![0u8; F::CANONICAL_SIZE];
+    let n_modulus_bytes = F::CANONICAL_SIZE.min(32);
+    bytes[..n_modulus_bytes].copy_from_slice(&F::MODULUS.to_le_bytes()[..n_modulus_bytes]);
+    assert_eq!(F::try_from_canonical_bytes(&bytes), None);
+
+    // encodings of another length
+    let bytes = F::ONE.to_canonical_bytes();
+    assert_eq!(F::try_from_canonical_bytes(&bytes[1..]), None);
+    assert_eq!(
+        F::try_from_canonical_bytes(&[bytes, vec![0]].concat()),
+        None
+    );
+
+    // limbs with a non zero padding, or too many of them
+    let mut limbs = F::ONE.to_u64_limbs();
+    if F::CANONICAL_SIZE % 8 != 0 {
+        *limbs.last_mut().unwrap() |= 1 << 63;
+        assert_eq!(F::try_from_u64_limbs(&limbs), None);
+    }
+    let limbs = [F::ONE.to_u64_limbs(), vec![0]].concat();
+    assert_eq!(F::try_from_u64_limbs(&limbs), None);
+}
+
 pub fn random_inversion_tests<F: Field, R: RngCore>(mut rng: R, type_name: String) {
     assert!(F::zero().inv().is_none());
 
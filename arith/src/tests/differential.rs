@@ -0,0 +1,142 @@
+use std::fmt::Debug;
+
+use ark_std::test_rng;
+use rand::RngCore;
+
+use crate::{Field, SimdField};
+
+const NUM_INPUTS: usize = 4;
+const MAX_DEPTH: usize = 6;
+
+/// An arithmetic expression over the inputs, evaluated on packed elements, and on the scalars of
+/// a lane.
+#[derive(Debug, Clone)]
+enum Expr<S> {
+    Input(usize),
+    Const(S),
+    Add(Box<Expr<S>>, Box<Expr<S>>),
+    Sub(Box<Expr<S>>, Box<Expr<S>>),
+    Mul(Box<Expr<S>>, Box<Expr<S>>),
+    Neg(Box<Expr<S>>),
+    Double(Box<Expr<S>>),
+    Square(Box<Expr<S>>),
+    /// Multiplication by a scalar, i.e., `SimdField::scale` on packed elements
+    Scale(Box<Expr<S>>, S),
+}
+
+impl<S: Field> Expr<S> {
+    /// A random scalar, one of the edge values zero, one and minus one with probability 1/2
+    fn random_scalar(mut rng: impl RngCore) -> S {
+        match rng.next_u32() % 6 {
+            0 => S::ZERO,
+            1 => S::ONE,
+            2 => -S::ONE,
+            _ => S::random_unsafe(rng),
+        }
+    }
+
+    fn random<R: RngCore>(depth: usize, rng: &mut R) -> Self {
+        let leaf = depth == 0 || rng.next_u32() % 4 == 0;
+        let sub = |rng: &mut R| Box::new(Self::random(depth.saturating_sub(1), rng));
+        match (leaf, rng.next_u32() % 7) {
+            (true, 0) => Self::Const(Self::random_scalar(&mut *rng)),
+            (true, _) => Self::Input(rng.next_u32() as usize % NUM_INPUTS),
+            (false, 0) => Self::Add(sub(rng), sub(rng)),
+            (false, 1) => Self::Sub(sub(rng), sub(rng)),
+            (false, 2) => Self::Mul(sub(rng), sub(rng)),
+            (false, 3) => Self::Neg(sub(rng)),
+            (false, 4) => Self::Double(sub(rng)),
+            (false, 5) => Self::Square(sub(rng)),
+            _ => Self::Scale(sub(rng), Self::random_scalar(&mut *rng)),
+        }
+    }
+
+    fn eval_packed<F: SimdField<Scalar = S>>(&self, inputs: &[F]) -> F {
+        match self {
+            Self::Input(i) => inputs[*i],
+            Self::Const(c) => F::pack_full(c),
+            Self::Add(a, b) => a.eval_packed(inputs) + b.eval_packed(inputs),
+            Self::Sub(a, b) => a.eval_packed(inputs) - b.eval_packed(inputs),
+            Self::Mul(a, b) => a.eval_packed(inputs) * b.eval_packed(inputs),
+            Self::Neg(a) => -a.eval_packed(inputs),
+            Self::Double(a) => a.eval_packed(inputs).double(),
+            Self::Square(a) => a.eval_packed(inputs).square(),
+            Self::Scale(a, c) => a.eval_packed(inputs).scale(c),
+        }
+    }
+
+    fn eval_scalar(&self, inputs: &[S]) -> S {
+        match self {
+            Self::Input(i) => inputs[*i],
+            Self::Const(c) => *c,
+            Self::Add(a, b) => a.eval_scalar(inputs) + b.eval_scalar(inputs),
+            Self::Sub(a, b) => a.eval_scalar(inputs) - b.eval_scalar(inputs),
+            Self::Mul(a, b) => a.eval_scalar(inputs) * b.eval_scalar(inputs),
+            Self::Neg(a) => -a.eval_scalar(inputs),
+            Self::Double(a) => a.eval_scalar(inputs).double(),
+            Self::Square(a) => a.eval_scalar(inputs).square(),
+            Self::Scale(a, c) => a.eval_scalar(inputs) * *c,
+        }
+    }
+}
+
+/// Assert the packed `actual` agrees with the scalars `expected`, lane by lane, naming the first
+/// lane that does not
+fn assert_lanes_eq<F: SimdField>(
+    actual: &F,
+    expected: &[F::Scalar],
+    context: impl Debug,
+    type_name: &str,
+) {
+    let lanes = actual.unpack();
+    assert_eq!(lanes.len(), F::PACK_SIZE, "{type_name}: unpacked size");
+    if let Some(lane) = (0..F::PACK_SIZE).find(|lane| lanes[*lane] != expected[*lane]) {
+        panic!(
+            "{type_name}: lane {lane} is {:?} rather than {:?}, for {context:?}",
+            lanes[lane], expected[lane]
+        );
+    }
+}
+
+/// Differential tests of a SIMD field against its scalar field, catching the lane handling bugs
+/// that otherwise surface as unverifiable proofs: random expression trees, over inputs mixing
+/// random and edge values in each lane, are evaluated on the packed inputs, and on the scalars of
+/// each lane, and the results agree lane by lane.
+pub fn random_simd_differential_tests<F: SimdField>(type_name: String) {
+    let mut rng = test_rng();
+
+    for _ in 0..200 {
+        let lane_inputs: Vec<Vec<F::Scalar>> = (0..F::PACK_SIZE)
+            .map(|_| {
+                (0..NUM_INPUTS)
+                    .map(|_| Expr::random_scalar(&mut rng))
+                    .collect()
+            })
+            .collect();
+        let inputs: Vec<F> = (0..NUM_INPUTS)
+            .map(|i| {
+                let scalars: Vec<F::Scalar> = lane_inputs.iter().map(|lane| lane[i]).collect();
+                F::pack(&scalars)
+            })
+            .collect();
+        inputs.iter().enumerate().for_each(|(i, input)| {
+            let expected: Vec<F::Scalar> = lane_inputs.iter().map(|lane| lane[i]).collect();
+            assert_lanes_eq(input, &expected, format!("pack of input {i}"), &type_name);
+        });
+
+        let expr = Expr::random(MAX_DEPTH, &mut rng);
+        let actual = expr.eval_packed(&inputs);
+        let expected: Vec<F::Scalar> = lane_inputs
+            .iter()
+            .map(|lane| expr.eval_scalar(lane))
+            .collect();
+        assert_lanes_eq(&actual, &expected, &expr, &type_name);
+
+        let sum: F::Scalar = expected.iter().sum();
+        assert_eq!(
+            actual.horizontal_sum(),
+            sum,
+            "{type_name}: horizontal sum of {expr:?}"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Structured benchmarks for `expander bench`.
+//!
+//! A suite produces a `BenchReport` that is serialized to JSON, so that the report of one run
+//! can be used as the baseline of a later one and regressions tracked across commits.
+use std::{hint::black_box, path::Path, time::Instant};
+
+use arith::{Field, Fr};
+use babybear::{BabyBearExt3, BabyBearx16};
+use circuit::Circuit;
+use clap::ValueEnum;
+use gf2::GF2x8;
+use gf2_128::GF2_128;
+use gkr::{
+    BN254ConfigMIMC5KZG, BN254ConfigMIMC5Zeromorph, BN254ConfigSha2Dory, BN254ConfigSha2Hyrax,
+    BN254ConfigSha2IPA, BN254ConfigSha2Raw, BabyBearx16ConfigSha2Orion, BabyBearx16ConfigSha2Raw,
+    GF2ExtConfigSha2Orion, GF2ExtConfigSha2Raw, Goldilocksx8ConfigSha2Orion,
+    Goldilocksx8ConfigSha2Raw, M31x16ConfigSha2OrionVanilla, M31x16ConfigSha2RawVanilla, Prover,
+    Verifier,
+    utils::{
+        KECCAK_BABYBEAR_CIRCUIT, KECCAK_BABYBEAR_WITNESS, KECCAK_BN254_CIRCUIT,
+        KECCAK_BN254_WITNESS, KECCAK_GF2_CIRCUIT, KECCAK_GF2_WITNESS, KECCAK_GOLDILOCKS_CIRCUIT,
+        KECCAK_GOLDILOCKS_WITNESS, KECCAK_M31_CIRCUIT, KECCAK_M31_WITNESS,
+    },
+};
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, FieldType, GKREngine, MPIConfig,
+    Transcript,
+};
+use goldilocks::{GoldilocksExt2, Goldilocksx8};
+use mersenne31::{M31Ext3, M31x16};
+use poly_commit::expander_pcs_init_testing_only;
+use polynomials::MultiLinearPoly;
+use rand::{SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+/// Number of elements each field operation is applied to in one sample.
+pub const FIELD_BATCH_SIZE: usize = 1 << 16;
+
+/// Number of local variables of the polynomial committed by the PCS suite.
+pub const PCS_NUM_VARS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BenchSuite {
+    /// Proving and verifying the keccak circuits, requires the data files of `dev-setup`
+    Gkr,
+    /// Commit, open and verify of each polynomial commitment scheme
+    Pcs,
+    /// Batched arithmetic of the circuit and challenge fields
+    Field,
+}
+
+/// Timing of a single benchmark, in nanoseconds per sample.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub name: String,
+    pub samples: usize,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub suite: BenchSuite,
+    pub results: Vec<BenchResult>,
+}
+
+/// A benchmark whose median is slower than in the baseline by more than the tolerance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Regression {
+    pub name: String,
+    pub baseline_median_ns: u64,
+    pub median_ns: u64,
+}
+
+impl Regression {
+    #[inline]
+    pub fn slowdown(&self) -> f64 {
+        self.median_ns as f64 / self.baseline_median_ns as f64
+    }
+}
+
+impl BenchReport {
+    /// Compare the medians against `baseline`, flagging every benchmark that got slower by more
+    /// than `tolerance`, e.g., 0.1 for 10%. Benchmarks missing from either report are ignored.
+    pub fn regressions(&self, baseline: &BenchReport, tolerance: f64) -> Vec<Regression> {
+        self.results
+            .iter()
+            .filter_map(|result| {
+                let base = baseline.results.iter().find(|b| b.name == result.name)?;
+                let regression = Regression {
+                    name: result.name.clone(),
+                    baseline_median_ns: base.median_ns,
+                    median_ns: result.median_ns,
+                };
+                (result.median_ns as f64 > base.median_ns as f64 * (1.0 + tolerance))
+                    .then_some(regression)
+            })
+            .collect()
+    }
+}
+
+/// Run all the benchmarks of `suite`, timing each one over `samples` samples.
+pub fn run_suite(suite: BenchSuite, samples: usize) -> BenchReport {
+    assert!(samples > 0);
+
+    let mut results = vec![];
+    match suite {
+        BenchSuite::Gkr => {
+            bench_gkr::<M31x16ConfigSha2RawVanilla>("m31x16", samples, &mut results);
+            bench_gkr::<BN254ConfigSha2Raw>("bn254", samples, &mut results);
+            bench_gkr::<GF2ExtConfigSha2Raw>("gf2ext128", samples, &mut results);
+            bench_gkr::<Goldilocksx8ConfigSha2Raw>("goldilocksx8", samples, &mut results);
+            bench_gkr::<BabyBearx16ConfigSha2Raw>("babybearx16", samples, &mut results);
+        }
+        BenchSuite::Pcs => {
+            bench_pcs::<M31x16ConfigSha2RawVanilla>("raw_m31x16", samples, &mut results);
+            bench_pcs::<M31x16ConfigSha2OrionVanilla>("orion_m31x16", samples, &mut results);
+            bench_pcs::<GF2ExtConfigSha2Orion>("orion_gf2ext128", samples, &mut results);
+            bench_pcs::<Goldilocksx8ConfigSha2Orion>("orion_goldilocksx8", samples, &mut results);
+            bench_pcs::<BabyBearx16ConfigSha2Orion>("orion_babybearx16", samples, &mut results);
+            bench_pcs::<BN254ConfigSha2Hyrax>("hyrax_bn254", samples, &mut results);
+            bench_pcs::<BN254ConfigMIMC5KZG>("kzg_bn254", samples, &mut results);
+            bench_pcs::<BN254ConfigMIMC5Zeromorph>("zeromorph_bn254", samples, &mut results);
+            bench_pcs::<BN254ConfigSha2Dory>("dory_bn254", samples, &mut results);
+            bench_pcs::<BN254ConfigSha2IPA>("ipa_bn254", samples, &mut results);
+        }
+        BenchSuite::Field => {
+            let mut rng = StdRng::seed_from_u64(0);
+            bench_field::<M31x16>("m31x16", false, samples, &mut rng, &mut results);
+            bench_field::<M31Ext3>("m31ext3", true, samples, &mut rng, &mut results);
+            bench_field::<Fr>("bn254", true, samples, &mut rng, &mut results);
+            bench_field::<GF2x8>("gf2x8", false, samples, &mut rng, &mut results);
+            bench_field::<GF2_128>("gf2ext128", true, samples, &mut rng, &mut results);
+            bench_field::<Goldilocksx8>("goldilocksx8", false, samples, &mut rng, &mut results);
+            bench_field::<GoldilocksExt2>("goldilocksext2", true, samples, &mut rng, &mut results);
+            bench_field::<BabyBearx16>("babybearx16", false, samples, &mut rng, &mut results);
+            bench_field::<BabyBearExt3>("babybearext3", true, samples, &mut rng, &mut results);
+        }
+    }
+
+    BenchReport { suite, results }
+}
+
+/// Time `f` over `samples` runs, after a warm-up run.
+fn measure(name: String, samples: usize, mut f: impl FnMut()) -> BenchResult {
+    f();
+
+    let mut timings = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed().as_nanos() as u64
+        })
+        .collect::<Vec<_>>();
+    timings.sort_unstable();
+
+    BenchResult {
+        name,
+        samples,
+        median_ns: timings[samples / 2],
+        min_ns: timings[0],
+        max_ns: timings[samples - 1],
+    }
+}
+
+fn bench_field<F: Field>(
+    label: &str,
+    with_inv: bool,
+    samples: usize,
+    rng: &mut StdRng,
+    results: &mut Vec<BenchResult>,
+) {
+    let a = (0..FIELD_BATCH_SIZE)
+        .map(|_| F::random_unsafe(&mut *rng))
+        .collect::<Vec<_>>();
+    let b = (0..FIELD_BATCH_SIZE)
+        .map(|_| F::random_unsafe(&mut *rng))
+        .collect::<Vec<_>>();
+    let mut out = vec![F::ZERO; FIELD_BATCH_SIZE];
+
+    results.push(measure(format!("field/{label}/mul"), samples, || {
+        out.iter_mut()
+            .zip(a.iter().zip(b.iter()))
+            .for_each(|(o, (x, y))| *o = *x * *y);
+        black_box(&out);
+    }));
+    results.push(measure(format!("field/{label}/add"), samples, || {
+        out.iter_mut()
+            .zip(a.iter().zip(b.iter()))
+            .for_each(|(o, (x, y))| *o = *x + *y);
+        black_box(&out);
+    }));
+    results.push(measure(format!("field/{label}/square"), samples, || {
+        out.iter_mut()
+            .zip(a.iter())
+            .for_each(|(o, x)| *o = x.square());
+        black_box(&out);
+    }));
+    // inversion is not implemented for the packed fields
+    if with_inv {
+        results.push(measure(format!("field/{label}/inv"), samples, || {
+            out.iter_mut()
+                .zip(a.iter())
+                .for_each(|(o, x)| *o = x.inv().unwrap_or(F::ZERO));
+            black_box(&out);
+        }));
+    }
+}
+
+fn bench_pcs<Cfg: GKREngine>(label: &str, samples: usize, results: &mut Vec<BenchResult>) {
+    type Challenge<Cfg> = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField;
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let (params, proving_key, verification_key, mut scratch_pad) = expander_pcs_init_testing_only::<
+        Cfg::FieldConfig,
+        Cfg::PCSConfig,
+    >(PCS_NUM_VARS, &mpi_config);
+    let poly = MultiLinearPoly::<<Cfg::FieldConfig as FieldEngine>::SimdCircuitField>::random(
+        PCS_NUM_VARS,
+        &mut rng,
+    );
+
+    let simd_vars = Cfg::FieldConfig::get_field_pack_size().trailing_zeros() as usize;
+    let challenge = ExpanderSingleVarChallenge::<Cfg::FieldConfig>::new(
+        (0..PCS_NUM_VARS)
+            .map(|_| Challenge::<Cfg>::random_unsafe(&mut rng))
+            .collect(),
+        (0..simd_vars)
+            .map(|_| Challenge::<Cfg>::random_unsafe(&mut rng))
+            .collect(),
+        vec![],
+    );
+    let v = Cfg::FieldConfig::single_core_eval_circuit_vals_at_expander_challenge(
+        &poly.coeffs,
+        &challenge,
+    );
+
+    results.push(measure(format!("pcs/{label}/commit"), samples, || {
+        black_box(Cfg::PCSConfig::commit(
+            &params,
+            &mpi_config,
+            &proving_key,
+            &poly,
+            &mut scratch_pad,
+        ));
+    }));
+    let commitment =
+        Cfg::PCSConfig::commit(&params, &mpi_config, &proving_key, &poly, &mut scratch_pad)
+            .unwrap()
+            .unwrap();
+
+    results.push(measure(format!("pcs/{label}/open"), samples, || {
+        let mut transcript = Cfg::TranscriptConfig::new();
+        black_box(Cfg::PCSConfig::open(
+            &params,
+            &mpi_config,
+            &proving_key,
+            &poly,
+            &challenge,
+            &mut transcript,
+            &scratch_pad,
+        ));
+    }));
+    let mut transcript = Cfg::TranscriptConfig::new();
+    let opening = Cfg::PCSConfig::open(
+        &params,
+        &mpi_config,
+        &proving_key,
+        &poly,
+        &challenge,
+        &mut transcript,
+        &scratch_pad,
+    )
+    .unwrap()
+    .unwrap();
+
+    results.push(measure(format!("pcs/{label}/verify"), samples, || {
+        let mut transcript = Cfg::TranscriptConfig::new();
+        assert!(Cfg::PCSConfig::verify(
+            &params,
+            &verification_key,
+            &commitment,
+            &challenge,
+            v,
+            &mut transcript,
+            &opening,
+        ));
+    }));
+}
+
+fn bench_gkr<Cfg: GKREngine>(label: &str, samples: usize, results: &mut Vec<BenchResult>) {
+    let (circuit_path, witness_path) = match Cfg::FieldConfig::FIELD_TYPE {
+        FieldType::GF2Ext128 => (KECCAK_GF2_CIRCUIT, KECCAK_GF2_WITNESS),
+        FieldType::M31x1 | FieldType::M31x16 => (KECCAK_M31_CIRCUIT, KECCAK_M31_WITNESS),
+        FieldType::BN254 => (KECCAK_BN254_CIRCUIT, KECCAK_BN254_WITNESS),
+        FieldType::Goldilocksx1 | FieldType::Goldilocksx8 => {
+            (KECCAK_GOLDILOCKS_CIRCUIT, KECCAK_GOLDILOCKS_WITNESS)
+        }
+        FieldType::BabyBearx16 => (KECCAK_BABYBEAR_CIRCUIT, KECCAK_BABYBEAR_WITNESS),
+    };
+    if !Path::new(circuit_path).exists() || !Path::new(witness_path).exists() {
+        eprintln!("skipping gkr/{label}: {circuit_path} not found, run dev-setup first");
+        return;
+    }
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut circuit =
+        Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Cfg>(circuit_path);
+    circuit.load_witness_allow_padding_testing_only(witness_path, &mpi_config);
+    let mut verifier_circuit = circuit.clone();
+    circuit.evaluate();
+
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+
+    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    results.push(measure(format!("gkr/{label}/prove"), samples, || {
+        black_box(prover.prove(
+            &mut circuit,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        ));
+    }));
+    let (claimed_v, proof) = prover
+        .prove(
+            &mut circuit,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+
+    let verifier = Verifier::<Cfg>::new(mpi_config);
+    let public_input = verifier_circuit.public_input.clone();
+    results.push(measure(format!("gkr/{label}/verify"), samples, || {
+        assert!(verifier.verify(
+            &mut verifier_circuit,
+            &public_input,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &proof,
+        ));
+    }));
+}
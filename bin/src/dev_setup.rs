@@ -97,12 +97,14 @@ fn proof_gen<'a, C: GKREngine>(mpi_config: MPIConfig<'a>) {
         let mut prover = Prover::<C>::new(mpi_config.clone());
         prover.prepare_mem(&local_circuit);
 
-        let (claim, proof) = prover.prove(
-            &mut local_circuit,
-            &pcs_params,
-            &pcs_proving_key,
-            &mut pcs_scratch,
-        );
+        let (claim, proof) = prover
+            .prove(
+                &mut local_circuit,
+                &pcs_params,
+                &pcs_proving_key,
+                &mut pcs_scratch,
+            )
+            .unwrap();
         let mut buf = Vec::new();
         claim.serialize_into(&mut buf).unwrap();
         proof.serialize_into(&mut buf).unwrap();
@@ -4,7 +4,8 @@ use bin::executor::*;
 use clap::Parser;
 use gkr::gkr_configs::*;
 use gkr_engine::{
-    FiatShamirHashType, FieldType, MPIConfig, MPIEngine, PolynomialCommitmentType, root_println,
+    FiatShamirHashType, FieldType, MPIChunkSize, MPIConfig, MPIEngine, NodePlacement, NodeTopology,
+    PolynomialCommitmentType, root_println,
 };
 
 #[tokio::main]
@@ -16,8 +17,19 @@ async fn main() {
         PolynomialCommitmentType::from_str(&expander_exec_args.poly_commitment_scheme).unwrap();
 
     let universe = MPIConfig::init().unwrap();
-    let world = universe.world();
-    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+    let world = if expander_exec_args.optimize_topology {
+        MPIConfig::optimize_topology(&universe.world())
+    } else {
+        universe.world()
+    };
+    let node_topology = NodeTopology::split(&world);
+    let node_placement = NodePlacement::query(&world);
+    let mut mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world))
+        .with_node_topology(node_topology.as_ref())
+        .with_node_placement(Some(&node_placement));
+    if let Some(chunk_size) = &expander_exec_args.mpi_chunk_size {
+        mpi_config = mpi_config.with_chunk_size(MPIChunkSize::from_str(chunk_size).unwrap());
+    }
     root_println!(mpi_config, "Fiat-Shamir Hash Type: {:?}", &fs_hash_type);
     root_println!(
         mpi_config,
@@ -29,6 +41,7 @@ async fn main() {
     let circuit_file = match &expander_exec_args.subcommands {
         ExpanderExecSubCommand::Prove { circuit_file, .. } => circuit_file,
         ExpanderExecSubCommand::Verify { circuit_file, .. } => circuit_file,
+        ExpanderExecSubCommand::Draft { circuit_file, .. } => circuit_file,
         ExpanderExecSubCommand::Serve { circuit_file, .. } => circuit_file,
     };
 
@@ -45,6 +58,12 @@ async fn main() {
         (FiatShamirHashType::MIMC5, PolynomialCommitmentType::Raw, FieldType::BN254) => {
             run_command::<BN254ConfigMIMC5Raw>(&expander_exec_args, &mpi_config).await;
         }
+        (FiatShamirHashType::Anemoi, PolynomialCommitmentType::Raw, FieldType::BN254) => {
+            run_command::<BN254ConfigAnemoiRaw>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::Griffin, PolynomialCommitmentType::Raw, FieldType::BN254) => {
+            run_command::<BN254ConfigGriffinRaw>(&expander_exec_args, &mpi_config).await;
+        }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::BN254) => {
             run_command::<BN254ConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
         }
@@ -54,6 +73,15 @@ async fn main() {
         (FiatShamirHashType::MIMC5, PolynomialCommitmentType::KZG, FieldType::BN254) => {
             run_command::<BN254ConfigMIMC5KZG>(&expander_exec_args, &mpi_config).await;
         }
+        (FiatShamirHashType::MIMC5, PolynomialCommitmentType::Zeromorph, FieldType::BN254) => {
+            run_command::<BN254ConfigMIMC5Zeromorph>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Dory, FieldType::BN254) => {
+            run_command::<BN254ConfigSha2Dory>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::IPA, FieldType::BN254) => {
+            run_command::<BN254ConfigSha2IPA>(&expander_exec_args, &mpi_config).await;
+        }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::GF2Ext128) => {
             run_command::<GF2ExtConfigSha2Orion>(&expander_exec_args, &mpi_config).await;
         }
@@ -64,7 +92,36 @@ async fn main() {
             run_command::<Goldilocksx8ConfigSha2Orion>(&expander_exec_args, &mpi_config).await;
         }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::BabyBearx16) => {
-            run_command::<BabyBearx16ConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
+            run_command::<BabyBearx16ConfigSha2Orion>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Brakedown, FieldType::M31x16) => {
+            run_command::<M31x16ConfigSha2BrakedownVanilla>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Basefold, FieldType::M31x16) => {
+            run_command::<M31x16ConfigSha2BasefoldVanilla>(&expander_exec_args, &mpi_config).await;
+        }
+        (
+            FiatShamirHashType::SHA256,
+            PolynomialCommitmentType::Basefold,
+            FieldType::Goldilocksx8,
+        ) => {
+            run_command::<Goldilocksx8ConfigSha2Basefold>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Ligero, FieldType::Goldilocksx8) => {
+            run_command::<Goldilocksx8ConfigSha2Ligero>(&expander_exec_args, &mpi_config).await;
+        }
+        (
+            FiatShamirHashType::SHA256,
+            PolynomialCommitmentType::Basefold,
+            FieldType::BabyBearx16,
+        ) => {
+            run_command::<BabyBearx16ConfigSha2Basefold>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Whir, FieldType::Goldilocksx8) => {
+            run_command::<Goldilocksx8ConfigSha2Whir>(&expander_exec_args, &mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Whir, FieldType::BabyBearx16) => {
+            run_command::<BabyBearx16ConfigSha2Whir>(&expander_exec_args, &mpi_config).await;
         }
         _ => panic!(
             "FS: {fs_hash_type:?}, PCS: {pcs_type:?}, Field: {field_type:?} setting is not yet integrated in expander-exec"
@@ -1,22 +1,29 @@
 use std::{
     fs,
-    io::Cursor,
+    io::{BufWriter, Cursor, ErrorKind, Write},
     process::exit,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use arith::Field;
-use circuit::Circuit;
+use circuit::{Circuit, DEFAULT_WITNESS_PREFETCH_DEPTH};
 use clap::{Parser, Subcommand};
-use gkr::{Prover, Verifier};
+use gkr::{Prover, Verifier, circuit_digest, draft_check};
 use gkr_engine::{
-    BN254Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine, Goldilocksx8Config, M31x16Config,
-    MPIConfig, MPIEngine, MPISharedMemory, Proof,
+    BN254Config, ExpanderPCS, FieldEngine, FieldType, GF2ExtConfig, GKREngine, Goldilocksx8Config,
+    M31x16Config, MPIConfig, MPIEngine, MPIResult, MPISharedMemory, Proof,
 };
 use log::info;
-use poly_commit::expander_pcs_init_testing_only;
+use poly_commit::{expander_pcs_init_testing_only, expander_pcs_init_with_srs_file_testing_only};
 use serdes::{ExpSerde, SerdeError};
-use warp::{Filter, http::StatusCode, reply};
+use warp::{
+    Filter, Reply,
+    http::{HeaderValue, StatusCode},
+    reply,
+};
+
+use crate::proof_store::{ProofKey, ProofStore};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -29,7 +36,15 @@ pub struct ExpanderExecArgs {
     #[arg(short, long, default_value = "Raw")]
     pub poly_commitment_scheme: String,
 
-    /// Prove, Verify, or Serve subcommands
+    /// MPI chunk size: a number of bytes, or auto to pick it from the world size and the payload
+    #[arg(long)]
+    pub mpi_chunk_size: Option<String>,
+
+    /// Relabel the MPI ranks node after node, see `MPIConfig::optimize_topology`
+    #[arg(long)]
+    pub optimize_topology: bool,
+
+    /// Prove, Verify, Draft, or Serve subcommands
     #[clap(subcommand)]
     pub subcommands: ExpanderExecSubCommand,
 }
@@ -41,13 +56,18 @@ pub enum ExpanderExecSubCommand {
         #[arg(short, long)]
         circuit_file: String,
 
-        /// Witness File Path
+        /// Witness File Path, read by the root process only
         #[arg(short, long)]
         witness_file: String,
 
         /// Output Proof Path
         #[arg(short, long)]
         output_proof_file: String,
+
+        /// The number of SIMD lanes of the witness the root reads ahead from the disk, 0 to read
+        /// each lane when it is sent
+        #[arg(long, default_value_t = DEFAULT_WITNESS_PREFETCH_DEPTH)]
+        witness_prefetch_depth: usize,
     },
     Verify {
         /// Circuit File Path
@@ -66,6 +86,17 @@ pub enum ExpanderExecSubCommand {
         #[arg(short, long, default_value_t = 1)]
         mpi_size: u32,
     },
+    /// Evaluate a circuit on a witness and check the outputs asserted to be zero.
+    /// Unsound and without PCS, this does NOT produce a proof.
+    Draft {
+        /// Circuit File Path
+        #[arg(short, long)]
+        circuit_file: String,
+
+        /// Witness File Path
+        #[arg(short, long)]
+        witness_file: String,
+    },
     Serve {
         /// Circuit File Path
         #[arg(short, long)]
@@ -78,17 +109,66 @@ pub enum ExpanderExecSubCommand {
         /// IP Port
         #[arg(short, long)]
         port: u16,
+
+        /// SRS file, with the precomputed tables of the PCS, generated and stored on first use
+        #[arg(short, long)]
+        srs_file: Option<String>,
+
+        /// The number of seconds a proof is served again to the requests of the same statement,
+        /// forever if not set
+        #[arg(long)]
+        proof_ttl_secs: Option<u64>,
     },
 }
 
+/// The exact number of bytes written by `dump_proof_and_claimed_v_into`.
+pub fn proof_and_claimed_v_serialized_size<F: Field>(
+    proof: &Proof,
+    claimed_v: &F,
+) -> Result<usize, SerdeError> {
+    let mut claimed_v_bytes = Vec::new();
+    claimed_v.serialize_into(&mut claimed_v_bytes)?;
+
+    Ok(proof.serialized_size() + claimed_v_bytes.len())
+}
+
+/// Serialize the proof and the claimed value directly into a writer, e.g., a file,
+/// without materializing the serialized bytes in memory.
+pub fn dump_proof_and_claimed_v_into<F: Field>(
+    proof: &Proof,
+    claimed_v: &F,
+    mut writer: impl Write,
+) -> Result<(), SerdeError> {
+    proof.serialize_into(&mut writer)?;
+    claimed_v.serialize_into(&mut writer)?;
+
+    Ok(())
+}
+
+/// Serialize the proof and the claimed value into a caller-provided buffer, that should hold at
+/// least `proof_and_claimed_v_serialized_size` bytes. Returns the number of bytes written.
+pub fn dump_proof_and_claimed_v_into_slice<F: Field>(
+    proof: &Proof,
+    claimed_v: &F,
+    buffer: &mut [u8],
+) -> Result<usize, SerdeError> {
+    let size = proof_and_claimed_v_serialized_size(proof, claimed_v)?;
+    if buffer.len() < size {
+        return Err(SerdeError::IOError(ErrorKind::WriteZero.into()));
+    }
+
+    dump_proof_and_claimed_v_into(proof, claimed_v, &mut buffer[..size])?;
+
+    Ok(size)
+}
+
 pub fn dump_proof_and_claimed_v<F: Field>(
     proof: &Proof,
     claimed_v: &F,
 ) -> Result<Vec<u8>, SerdeError> {
-    let mut bytes = Vec::new();
+    let mut bytes = Vec::with_capacity(proof_and_claimed_v_serialized_size(proof, claimed_v)?);
 
-    proof.serialize_into(&mut bytes)?;
-    claimed_v.serialize_into(&mut bytes)?;
+    dump_proof_and_claimed_v_into(proof, claimed_v, &mut bytes)?;
 
     Ok(bytes)
 }
@@ -121,10 +201,10 @@ pub fn detect_field_type_from_circuit_file(circuit_file: &str) -> FieldType {
 pub fn prove<Cfg: GKREngine>(
     circuit: &mut Circuit<Cfg::FieldConfig>,
     mpi_config: MPIConfig,
-) -> (
+) -> MPIResult<(
     <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField,
     Proof,
-)
+)>
 where
     Cfg::FieldConfig: FieldEngine,
 {
@@ -174,6 +254,13 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
 ) where
     Cfg::FieldConfig: FieldEngine,
 {
+    if let Err(e) = Cfg::PCSConfig::check_world_size(mpi_config.world_size()) {
+        if mpi_config.is_root() {
+            eprintln!("{e}");
+        }
+        exit(1);
+    }
+
     let subcommands = command.subcommands.clone();
 
     match subcommands {
@@ -181,21 +268,41 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
             circuit_file,
             witness_file,
             output_proof_file,
+            witness_prefetch_depth,
         } => {
             let (mut circuit, mut window) =
                 Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(&circuit_file, mpi_config);
             let prover = Prover::<Cfg>::new(mpi_config.clone());
 
-            circuit.prover_load_witness_file(&witness_file, mpi_config);
-            let (claimed_v, proof) = prove::<Cfg>(&mut circuit, mpi_config.clone());
+            if let Err(e) = circuit.try_prover_stream_witness_file_with_prefetch(
+                &witness_file,
+                mpi_config,
+                witness_prefetch_depth,
+            ) {
+                eprintln!("Rank {}: {e}", mpi_config.world_rank());
+                circuit.discard_control_of_shared_mem();
+                mpi_config.free_shared_mem(&mut window).unwrap();
+                exit(1);
+            }
+            let (claimed_v, proof) = match prove::<Cfg>(&mut circuit, mpi_config.clone()) {
+                Ok(proved) => proved,
+                Err(e) => {
+                    eprintln!("{e}");
+                    circuit.discard_control_of_shared_mem();
+                    exit(1);
+                }
+            };
 
             if prover.mpi_config.is_root() {
-                let bytes = dump_proof_and_claimed_v(&proof, &claimed_v)
-                    .expect("Unable to serialize proof.");
-                fs::write(output_proof_file, bytes).expect("Unable to write proof to file.");
+                let file =
+                    fs::File::create(output_proof_file).expect("Unable to create proof file.");
+                let mut writer = BufWriter::new(file);
+                dump_proof_and_claimed_v_into(&proof, &claimed_v, &mut writer)
+                    .expect("Unable to write proof to file.");
+                writer.flush().expect("Unable to write proof to file.");
             }
             circuit.discard_control_of_shared_mem();
-            mpi_config.free_shared_mem(&mut window);
+            mpi_config.free_shared_mem(&mut window).unwrap();
         }
         ExpanderExecSubCommand::Verify {
             circuit_file,
@@ -242,10 +349,32 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
 
             println!("success");
         }
+        ExpanderExecSubCommand::Draft {
+            circuit_file,
+            witness_file,
+        } => {
+            let mpi_config = MPIConfig::prover_new(None, None);
+            let mut circuit = Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Cfg>(
+                &circuit_file,
+            );
+            if let Err(e) = circuit.try_prover_load_witness_file(&witness_file, &mpi_config) {
+                eprintln!("{e}");
+                exit(1);
+            }
+
+            let report = draft_check::<Cfg>(&mut circuit);
+
+            println!("{report}");
+            if !report.passed() {
+                exit(1);
+            }
+        }
         ExpanderExecSubCommand::Serve {
             circuit_file,
             host_ip,
             port,
+            srs_file,
+            proof_ttl_secs,
         } => {
             let mpi_config = MPIConfig::prover_new(None, None);
             let prover = Prover::<Cfg>::new(mpi_config.clone());
@@ -264,13 +393,21 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
             let (circuit, _) =
                 Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(&circuit_file, &mpi_config);
 
-            // TODO: Read PCS setup from files
             let (pcs_params, pcs_proving_key, pcs_verification_key, pcs_scratch) =
-                expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+                expander_pcs_init_with_srs_file_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
                     circuit.log_input_size(),
                     &prover.mpi_config,
+                    srs_file.as_deref(),
                 );
 
+            // NOTE: the proofs are stored by circuit, public input and witness
+            let circuit_digest =
+                circuit_digest(&fs::read(&circuit_file).expect("Unable to read circuit file."));
+            let proof_store = Arc::new(Mutex::new(ProofStore::new(
+                proof_ttl_secs.map(Duration::from_secs),
+            )));
+            let proof_store_clone_for_lookup = proof_store.clone();
+
             let circuit = Arc::new(Mutex::new(circuit));
             let circuit_clone_for_verifier = circuit.clone();
             let pcs_params = Arc::new(Mutex::new(pcs_params));
@@ -299,17 +436,61 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
                         let mut pcs_scratch = pcs_scratch.lock().unwrap();
 
                         circuit.load_witness_bytes(&witness_bytes, &prover.mpi_config, true, true);
-                        let (claimed_v, proof) = prover.prove(
-                            &mut circuit,
-                            &pcs_params,
-                            &pcs_proving_key,
-                            &mut pcs_scratch,
-                        );
-                        reply::with_status(
-                            dump_proof_and_claimed_v(&proof, &claimed_v).unwrap(),
-                            StatusCode::OK,
-                        )
+                        // NOTE: only the proof of this very witness is served from the store
+                        let key =
+                            ProofKey::new(circuit_digest, &circuit.public_input, &witness_bytes);
+                        let cached = proof_store.lock().unwrap().get(&key);
+                        let (proof_bytes, stored) = match cached {
+                            Some(proof_bytes) => {
+                                info!("Serving the stored proof {key}.");
+                                (proof_bytes, true)
+                            }
+                            None => {
+                                let (claimed_v, proof) = prover
+                                    .prove(
+                                        &mut circuit,
+                                        &pcs_params,
+                                        &pcs_proving_key,
+                                        &mut pcs_scratch,
+                                    )
+                                    .expect("a single process does not communicate");
+                                let proof_bytes =
+                                    dump_proof_and_claimed_v(&proof, &claimed_v).unwrap();
+                                // only the witnesses satisfying the circuit are stored
+                                if claimed_v.is_zero() {
+                                    (proof_store.lock().unwrap().insert(key, proof_bytes), true)
+                                } else {
+                                    (Arc::new(proof_bytes), false)
+                                }
+                            }
+                        };
+
+                        let mut response = reply::with_status(proof_bytes.to_vec(), StatusCode::OK)
+                            .into_response();
+                        // the key of a proof that is not stored would not be found by a lookup
+                        if stored {
+                            response.headers_mut().insert(
+                                "x-proof-key",
+                                HeaderValue::from_str(&key.to_string()).unwrap(),
+                            );
+                        }
+                        response
                     });
+            let lookup = warp::path!("proof" / String).map(move |key: String| {
+                info!("Received proof lookup request.");
+                match key.parse::<ProofKey>() {
+                    Ok(key) => match proof_store_clone_for_lookup.lock().unwrap().get(&key) {
+                        Some(proof_bytes) => {
+                            reply::with_status(proof_bytes.to_vec(), StatusCode::OK)
+                        }
+                        None => reply::with_status(
+                            format!("No proof stored for {key}").into_bytes(),
+                            StatusCode::NOT_FOUND,
+                        ),
+                    },
+                    Err(e) => reply::with_status(e.into_bytes(), StatusCode::BAD_REQUEST),
+                }
+            });
             let verify =
                 warp::path("verify")
                     .and(warp::body::bytes())
@@ -354,7 +535,7 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
             warp::serve(
                 warp::post()
                     .and(prove.or(verify))
-                    .or(warp::get().and(ready)),
+                    .or(warp::get().and(ready.or(lookup))),
             )
             .run((host, port))
             .await;
@@ -0,0 +1,585 @@
+use std::{fs, io::Cursor, process::ExitCode};
+
+use bin::{
+    bench::{BenchReport, BenchSuite, run_suite},
+    executor::{detect_field_type_from_circuit_file, load_proof_and_claimed_v},
+    inspect::{ProofJson, VerifyingKeyJson},
+    recommend::{CircuitStats, OptimizationTarget, recommend_config},
+};
+use circuit::{Circuit, InputKind, LayerRetention, RecursiveCircuit, ShardLocation, Witness};
+use clap::{Parser, Subcommand};
+use gkr::{Prover, Verifier, gkr_configs::*};
+use gkr_engine::{
+    BN254Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine, Goldilocksx8Config, M31x16Config,
+    MPIConfig, Proof, Transcript,
+};
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+use transcript::{RecordingTranscript, Traced};
+
+/// Expander command line tools
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a benchmark suite, optionally checking for regressions against a baseline report
+    Bench {
+        /// Benchmark suite to run
+        #[arg(short, long, value_enum)]
+        suite: BenchSuite,
+
+        /// Print the report as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+
+        /// Write the JSON report to this file, to be used as a later baseline
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// JSON report of a previous run to compare against
+        #[arg(short, long)]
+        baseline: Option<String>,
+
+        /// Relative slowdown of a median over the baseline counted as a regression
+        #[arg(short, long, default_value_t = 0.1)]
+        tolerance: f64,
+
+        /// Number of timed samples of each benchmark
+        #[arg(short = 'n', long, default_value_t = 10)]
+        samples: usize,
+    },
+    /// Print the sections of a proof, and the verifying key of its circuit if given. The config
+    /// is recognized from the fingerprint the proof starts with
+    Inspect {
+        /// Proof file written by `expander-exec prove`
+        #[arg(short, long)]
+        proof: String,
+
+        /// Circuit file the proof is for
+        #[arg(short, long)]
+        circuit: Option<String>,
+
+        /// MPI size the proof was generated with
+        #[arg(short, long, default_value_t = 1)]
+        mpi_size: u32,
+
+        /// Print `{"proof": .., "verifying_key": ..}` as canonical JSON instead of a summary
+        #[arg(long)]
+        json: bool,
+    },
+    /// Verify a proof and prove its witness again, recording both transcripts, and print the
+    /// verifier transcript with the first operation where the two differ. The config is
+    /// recognized from the fingerprint the proof starts with
+    Trace {
+        /// Proof file written by `expander-exec prove` on a single process
+        #[arg(short, long)]
+        proof: String,
+
+        /// Circuit file the proof is for
+        #[arg(short, long)]
+        circuit: String,
+
+        /// Witness file the proof is for
+        #[arg(short, long)]
+        witness: String,
+
+        /// Print all the bytes of every operation instead of their first 32
+        #[arg(long)]
+        all_bytes: bool,
+    },
+    /// Check a witness file against the sharding of its values over the MPI processes and SIMD
+    /// lanes, and print the shard of each process and where the requested values are proven
+    Shards {
+        /// Circuit file the witness is for
+        #[arg(short, long)]
+        circuit: String,
+
+        /// Witness file to check
+        #[arg(short, long)]
+        witness: String,
+
+        /// MPI size the witness is proven with
+        #[arg(short, long, default_value_t = 1)]
+        mpi_size: u32,
+
+        /// Index of a value of the witness file to locate, repeatable
+        #[arg(short, long)]
+        index: Vec<usize>,
+
+        /// Position in the global input polynomial to locate, repeatable
+        #[arg(short, long)]
+        position: Vec<usize>,
+    },
+    /// Rank the configs for a circuit by their estimated costs, flagging the poor fits
+    Recommend {
+        /// Circuit file to prove
+        #[arg(short, long)]
+        circuit: String,
+
+        /// MPI size the circuit is to be proven with
+        #[arg(short, long, default_value_t = 1)]
+        mpi_size: u32,
+
+        /// Cost to rank the configs by
+        #[arg(short, long, value_enum, default_value_t = OptimizationTarget::ProofSize)]
+        target: OptimizationTarget,
+    },
+}
+
+/// `$func` for the config whose fingerprint is `$fingerprint`, among the configs listed
+macro_rules! by_fingerprint {
+    ($fingerprint:expr, $func:ident as $func_ty:ty) => {
+        by_fingerprint!(
+            @configs $fingerprint,
+            $func as $func_ty,
+            M31x1ConfigSha2RawVanilla,
+            M31x16ConfigPoseidonRawVanilla,
+            M31x16ConfigPoseidonRawSquare,
+            M31x16ConfigSha2OrionVanilla,
+            M31x16ConfigSha2OrionSquare,
+            M31x16ConfigSha2RawVanilla,
+            M31x16ConfigSha2RawSquare,
+            BN254ConfigMIMC5Raw,
+            BN254ConfigSha2Raw,
+            BN254ConfigAnemoiRaw,
+            BN254ConfigGriffinRaw,
+            BN254ConfigSha2Hyrax,
+            BN254ConfigSha2KZG,
+            BN254ConfigMIMC5KZG,
+            GF2ExtConfigSha2Orion,
+            GF2ExtConfigSha2Raw,
+            Goldilocksx1ConfigSha2Raw,
+            Goldilocksx8ConfigSha2Raw,
+            Goldilocksx8ConfigSha2Orion,
+            BabyBearx16ConfigSha2Raw,
+            BabyBearx16ConfigSha2Orion,
+        )
+    };
+    (@configs $fingerprint:expr, $func:ident as $func_ty:ty, $($cfg:ty),* $(,)?) => {
+        $(if $fingerprint == <$cfg as GKREngine>::FINGERPRINT {
+            Some($func::<$cfg> as $func_ty)
+        } else)* {
+            None
+        }
+    };
+}
+
+fn inspect<Cfg: GKREngine>(
+    proof_file: &[u8],
+    circuit_file: Option<String>,
+    mpi_size: u32,
+    json: bool,
+) -> ExitCode {
+    let (proof, claimed_v) =
+        load_proof_and_claimed_v::<<Cfg::FieldConfig as FieldEngine>::ChallengeField>(proof_file)
+            .expect("Malformed proof file");
+    let proof = ProofJson::new::<Cfg>(&proof, &claimed_v).expect("Malformed proof");
+
+    let verifying_key = circuit_file.map(|path| {
+        assert_eq!(
+            detect_field_type_from_circuit_file(&path),
+            <Cfg::FieldConfig as FieldEngine>::FIELD_TYPE,
+            "The circuit is over another field than the proof"
+        );
+        let circuit = Circuit::<Cfg::FieldConfig>::verifier_load_circuit::<Cfg>(&path);
+        // TODO: Read PCS setup from files
+        let (pcs_params, _, pcs_verification_key, _) =
+            expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+                circuit.log_input_size(),
+                &MPIConfig::verifier_new(mpi_size as i32),
+            );
+        let circuit_bytes = fs::read(&path).expect("Failed to read circuit file");
+        VerifyingKeyJson::new::<Cfg>(&circuit, &circuit_bytes, &pcs_params, &pcs_verification_key)
+            .unwrap()
+    });
+
+    if json {
+        let document = serde_json::json!({
+            "proof": proof,
+            "verifying_key": verifying_key,
+        });
+        println!("{}", serde_json::to_string_pretty(&document).unwrap());
+    } else {
+        println!("{proof}");
+        if let Some(verifying_key) = verifying_key {
+            println!("{verifying_key}");
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Number of operations printed on each side of the first divergence of two transcripts
+const DIVERGENCE_CONTEXT: usize = 3;
+
+fn trace<Cfg: GKREngine>(
+    proof_file: &[u8],
+    circuit_file: String,
+    witness_file: String,
+    all_bytes: bool,
+) -> ExitCode {
+    let (proof, claimed_v) =
+        load_proof_and_claimed_v::<<Cfg::FieldConfig as FieldEngine>::ChallengeField>(proof_file)
+            .expect("Malformed proof file");
+    // TODO: Read PCS setup from files
+    let mpi_config = MPIConfig::prover_new(None, None);
+
+    let mut circuit =
+        Circuit::<Cfg::FieldConfig>::verifier_load_circuit::<Traced<Cfg>>(&circuit_file);
+    circuit.verifier_load_witness_file(&witness_file, &mpi_config);
+    let (pcs_params, _, pcs_verification_key, _) = expander_pcs_init_testing_only::<
+        Cfg::FieldConfig,
+        Cfg::PCSConfig,
+    >(circuit.log_input_size(), &mpi_config);
+    let public_input = circuit.public_input.clone();
+    let mut verifier_transcript = RecordingTranscript::new();
+    let verified = Verifier::<Traced<Cfg>>::new(mpi_config.clone()).verify_with_transcript(
+        &mut circuit,
+        &public_input,
+        &claimed_v,
+        &pcs_params,
+        &pcs_verification_key,
+        &proof,
+        &mut verifier_transcript,
+    );
+    let verifier_trace = verifier_transcript.into_trace();
+
+    let mut circuit = Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Traced<Cfg>>(
+        &circuit_file,
+    );
+    if let Err(e) = circuit.try_prover_load_witness_file(&witness_file, &mpi_config) {
+        eprintln!("{e}");
+        return ExitCode::FAILURE;
+    }
+    let (pcs_params, pcs_proving_key, _, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+    let mut prover = Prover::<Traced<Cfg>>::new(mpi_config);
+    prover.prepare_mem(&circuit);
+    let (_, _, prover_transcript) = prover
+        .prove_with_transcript(
+            &mut circuit,
+            LayerRetention::Full,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .expect("a single process does not communicate");
+    let prover_trace = prover_transcript.into_trace();
+
+    if all_bytes {
+        println!("{verifier_trace:#}");
+    } else {
+        println!("{verifier_trace}");
+    }
+    println!(
+        "verification: {}",
+        if verified { "passed" } else { "failed" }
+    );
+
+    let Some(divergence) = prover_trace.first_divergence(&verifier_trace) else {
+        println!("the transcripts of the prover and the verifier agree");
+        return if verified {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    };
+
+    println!(
+        "the transcripts of the prover and the verifier differ from operation {divergence}, in \
+         phase {}",
+        verifier_trace
+            .phase_of(divergence)
+            .or(prover_trace.phase_of(divergence))
+            .unwrap_or("-")
+    );
+    let context =
+        divergence.saturating_sub(DIVERGENCE_CONTEXT)..divergence + DIVERGENCE_CONTEXT + 1;
+    for (side, side_trace) in [("prover", &prover_trace), ("verifier", &verifier_trace)] {
+        println!("{side}:");
+        side_trace
+            .events
+            .iter()
+            .enumerate()
+            .skip(context.start)
+            .take(context.len())
+            .for_each(|(i, event)| {
+                let marker = if i == divergence { ">" } else { " " };
+                if all_bytes {
+                    println!("{marker}{i:>7}  {event:#}");
+                } else {
+                    println!("{marker}{i:>7}  {event}");
+                }
+            });
+    }
+    ExitCode::FAILURE
+}
+
+fn describe(location: &ShardLocation, input_position: Option<usize>) -> String {
+    let kind = match location.kind {
+        InputKind::Private => "private",
+        InputKind::Public => "public",
+    };
+    let position = input_position.map_or("not committed".to_string(), |p| format!("position {p}"));
+    format!(
+        "rank {} lane {} {kind} input {}, {position}",
+        location.rank, location.lane, location.index
+    )
+}
+
+fn shards<C: FieldEngine>(
+    circuit_file: String,
+    witness_file: String,
+    mpi_size: u32,
+    indices: Vec<usize>,
+    positions: Vec<usize>,
+) -> ExitCode {
+    let circuit = RecursiveCircuit::<C>::load(&circuit_file)
+        .expect("Malformed circuit file")
+        .flatten();
+    let witness_bytes = fs::read(&witness_file).expect("Failed to read witness file");
+    let witness =
+        Witness::<C>::deserialize_from(Cursor::new(witness_bytes)).expect("Malformed witness file");
+    let scheme = circuit.sharding_scheme(&witness, &MPIConfig::verifier_new(mpi_size as i32));
+
+    println!(
+        "{} processes x {} lanes, {} private and {} public inputs per witness",
+        scheme.world_size, scheme.pack_size, scheme.num_private_inputs, scheme.num_public_inputs
+    );
+    if let Err(e) = scheme.validate(&witness) {
+        eprintln!("{e}");
+        return ExitCode::FAILURE;
+    }
+    if witness.num_witnesses > scheme.num_witnesses() {
+        println!(
+            "values from {} on are dropped, {} witnesses out of {}",
+            scheme.len(),
+            witness.num_witnesses - scheme.num_witnesses(),
+            witness.num_witnesses
+        );
+    }
+
+    // the hashes checked against the manifest of the root on loading
+    for rank in 0..scheme.world_size {
+        let hash = witness.shard_hash(rank);
+        println!(
+            "rank {rank:>4}: values {}..{}, shard hash {}",
+            rank * scheme.shard_len(),
+            (rank + 1) * scheme.shard_len(),
+            hash.iter().map(|b| format!("{b:02x}")).collect::<String>()
+        );
+    }
+
+    let mut located = true;
+    for index in indices {
+        if index < scheme.len() {
+            let location = scheme.locate(index);
+            let input_position = scheme.input_position(&location);
+            println!("value {index}: {}", describe(&location, input_position));
+        } else {
+            eprintln!("value {index}: out of the {} values proven", scheme.len());
+            located = false;
+        }
+    }
+    for position in positions {
+        if position < scheme.num_witnesses() * scheme.num_private_inputs {
+            let location = scheme.locate_input_position(position);
+            println!(
+                "position {position}: value {}, {}",
+                scheme.global_index(&location),
+                describe(&location, Some(position))
+            );
+        } else {
+            eprintln!("position {position}: out of the input polynomial");
+            located = false;
+        }
+    }
+
+    if located {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn recommend<C: FieldEngine>(
+    circuit_file: String,
+    mpi_size: u32,
+    target: OptimizationTarget,
+) -> ExitCode {
+    let circuit = RecursiveCircuit::<C>::load(&circuit_file)
+        .expect("Malformed circuit file")
+        .flatten();
+    let stats = CircuitStats::new(&circuit, mpi_size as usize);
+    println!(
+        "{} layers, {} gates, 2^{} inputs over {} processes, by {target:?}",
+        stats.num_layers, stats.num_gates, stats.log_input_size, stats.mpi_size
+    );
+
+    for (rank, suggestion) in recommend_config(&stats, target).iter().enumerate() {
+        let estimate = &suggestion.estimate;
+        println!(
+            "{:>3}. {:<14} {:<10} {:<9} prover {:>14} ns/witness  proof {:>12} B  gas {:>14}",
+            rank + 1,
+            format!("{:?}", suggestion.field),
+            format!("{:?}", suggestion.pcs),
+            format!("{:?}", suggestion.hash),
+            estimate.prover_ns_per_witness,
+            estimate.proof_bytes,
+            estimate.verifier_gas
+        );
+        for warning in &suggestion.warnings {
+            println!("       warning: {warning}");
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Bench {
+            suite,
+            json,
+            output,
+            baseline,
+            tolerance,
+            samples,
+        } => {
+            // read the baseline first, a bad path should not cost a full run
+            let baseline = baseline.map(|path| {
+                let bytes = std::fs::read(&path).expect("Failed to read baseline report");
+                serde_json::from_slice::<BenchReport>(&bytes).expect("Malformed baseline report")
+            });
+
+            let report = run_suite(suite, samples);
+            let report_json = serde_json::to_string_pretty(&report).unwrap();
+
+            if json {
+                println!("{report_json}");
+            } else {
+                for result in &report.results {
+                    println!(
+                        "{:<36} median {:>14} ns  min {:>14} ns  max {:>14} ns",
+                        result.name, result.median_ns, result.min_ns, result.max_ns
+                    );
+                }
+            }
+            if let Some(path) = output {
+                std::fs::write(path, &report_json).expect("Failed to write report");
+            }
+
+            let Some(baseline) = baseline else {
+                return ExitCode::SUCCESS;
+            };
+            assert_eq!(
+                baseline.suite, suite,
+                "Baseline is a report of another suite"
+            );
+
+            let regressions = report.regressions(&baseline, tolerance);
+            for regression in &regressions {
+                eprintln!(
+                    "regression: {} {} ns -> {} ns ({:.2}x)",
+                    regression.name,
+                    regression.baseline_median_ns,
+                    regression.median_ns,
+                    regression.slowdown()
+                );
+            }
+            if regressions.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Command::Inspect {
+            proof,
+            circuit,
+            mpi_size,
+            json,
+        } => {
+            let proof_file = fs::read(&proof).expect("Failed to read proof file");
+            let proof = Proof::deserialize_from(Cursor::new(&proof_file)).expect("Malformed proof");
+            let fingerprint = proof.bytes.get(..32).unwrap_or_default();
+
+            let inspector = by_fingerprint!(
+                fingerprint,
+                inspect as fn(&[u8], Option<String>, u32, bool) -> ExitCode
+            );
+            match inspector {
+                Some(inspect) => inspect(&proof_file, circuit, mpi_size, json),
+                None => {
+                    eprintln!("The proof is not for any known config");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Trace {
+            proof,
+            circuit,
+            witness,
+            all_bytes,
+        } => {
+            let proof_file = fs::read(&proof).expect("Failed to read proof file");
+            let proof = Proof::deserialize_from(Cursor::new(&proof_file)).expect("Malformed proof");
+            let fingerprint = proof.bytes.get(..32).unwrap_or_default();
+
+            let tracer = by_fingerprint!(
+                fingerprint,
+                trace as fn(&[u8], String, String, bool) -> ExitCode
+            );
+            match tracer {
+                Some(trace) => trace(&proof_file, circuit, witness, all_bytes),
+                None => {
+                    eprintln!("The proof is not for any known config");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Shards {
+            circuit,
+            witness,
+            mpi_size,
+            index,
+            position,
+        } => match detect_field_type_from_circuit_file(&circuit) {
+            FieldType::M31x16 => {
+                shards::<M31x16Config>(circuit, witness, mpi_size, index, position)
+            }
+            FieldType::BN254 => shards::<BN254Config>(circuit, witness, mpi_size, index, position),
+            FieldType::GF2Ext128 => {
+                shards::<GF2ExtConfig>(circuit, witness, mpi_size, index, position)
+            }
+            FieldType::Goldilocksx8 => {
+                shards::<Goldilocksx8Config>(circuit, witness, mpi_size, index, position)
+            }
+            field_type => {
+                eprintln!("Circuits over {field_type:?} are not recognized");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Recommend {
+            circuit,
+            mpi_size,
+            target,
+        } => match detect_field_type_from_circuit_file(&circuit) {
+            FieldType::M31x16 => recommend::<M31x16Config>(circuit, mpi_size, target),
+            FieldType::BN254 => recommend::<BN254Config>(circuit, mpi_size, target),
+            FieldType::GF2Ext128 => recommend::<GF2ExtConfig>(circuit, mpi_size, target),
+            FieldType::Goldilocksx8 => recommend::<Goldilocksx8Config>(circuit, mpi_size, target),
+            field_type => {
+                eprintln!("Circuits over {field_type:?} are not recognized");
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
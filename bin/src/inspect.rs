@@ -0,0 +1,347 @@
+//! Canonical JSON views of proofs and verifying keys for `expander inspect`.
+//!
+//! Byte strings are lowercase hex without a `0x` prefix, and the keys are emitted in the order
+//! below, so the JSON of a proof is a function of the proof alone. A proof, as written by
+//! `expander-exec prove` together with its claimed value, is
+//!
+//! ```text
+//! {
+//!   "schema": "expander-proof/v1",
+//!   "config": {
+//!     "field": "M31x16",          // FieldType of the config
+//!     "pcs": "Orion",             // PolynomialCommitmentType of the config
+//!     "scheme": "Vanilla",        // GKRScheme of the config
+//!     "fingerprint": "<hex>"      // GKREngine::FINGERPRINT, also covers the Fiat-Shamir hash
+//!   },
+//!   "claimed_v": "<hex>",         // claimed evaluation of the outputs, serialized
+//!   "commitment": "<hex>",        // PCS commitment to the inputs, serialized
+//!   "transcript": "<hex>",        // rest of the proof, see below
+//!   "size": 1234                  // proof size in bytes
+//! }
+//! ```
+//!
+//! The proof bytes are the fingerprint, the commitment and the transcript, concatenated. The
+//! transcript holds the grinding, the sumcheck messages of every layer and the PCS openings, in
+//! the order the verifier reads them. The verifying key of a circuit is
+//!
+//! ```text
+//! {
+//!   "schema": "expander-vk/v1",
+//!   "config": { ... },            // as above
+//!   "circuit": {
+//!     "digest": "<hex>",          // SHA256 of the circuit file
+//!     "expected_num_output_zeros": 8,
+//!     "layers": [                 // from the input layer up
+//!       { "input_var_num": 10, "output_var_num": 9, "mul": 512, "add": 0, "const": 0, "uni": 0 }
+//!     ]
+//!   },
+//!   "pcs_num_vars": 10,           // PCSParams::num_vars of the PCS params
+//!   "pcs_verification_key": "<hex>"
+//! }
+//! ```
+use std::{fmt, io::Cursor};
+
+use circuit::Circuit;
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, GKREngine, PCSParams, Proof, StructuredReferenceString,
+};
+use serde::{Deserialize, Serialize};
+use serdes::{ExpSerde, SerdeError};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+pub const PROOF_SCHEMA: &str = "expander-proof/v1";
+
+pub const VK_SCHEMA: &str = "expander-vk/v1";
+
+#[derive(Debug, Error)]
+pub enum InspectError {
+    #[error("malformed JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unsupported schema {0}")]
+    Schema(String),
+
+    #[error("invalid hex string")]
+    Hex,
+
+    #[error("the document is for a config with fingerprint {0}")]
+    ConfigMismatch(String),
+
+    #[error("malformed proof: {0}")]
+    Serde(#[from] SerdeError),
+}
+
+pub type InspectResult<T> = Result<T, InspectError>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigJson {
+    pub field: String,
+    pub pcs: String,
+    pub scheme: String,
+    pub fingerprint: String,
+}
+
+impl ConfigJson {
+    pub fn new<Cfg: GKREngine>() -> Self {
+        Self {
+            field: format!("{:?}", <Cfg::FieldConfig as FieldEngine>::FIELD_TYPE),
+            pcs: format!(
+                "{:?}",
+                <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::PCS_TYPE
+            ),
+            scheme: format!("{:?}", Cfg::SCHEME),
+            fingerprint: to_hex(&Cfg::FINGERPRINT),
+        }
+    }
+
+    fn check<Cfg: GKREngine>(&self) -> InspectResult<()> {
+        match self.fingerprint == to_hex(&Cfg::FINGERPRINT) {
+            true => Ok(()),
+            false => Err(InspectError::ConfigMismatch(self.fingerprint.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofJson {
+    pub schema: String,
+    pub config: ConfigJson,
+    pub claimed_v: String,
+    pub commitment: String,
+    pub transcript: String,
+    pub size: usize,
+}
+
+impl ProofJson {
+    /// Split `proof` into its sections, fails if it does not start with the fingerprint and a
+    /// commitment of `Cfg`.
+    pub fn new<Cfg: GKREngine>(
+        proof: &Proof,
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+    ) -> InspectResult<Self> {
+        let fingerprint = proof.bytes.get(..Cfg::FINGERPRINT.len());
+        if fingerprint != Some(Cfg::FINGERPRINT.as_slice()) {
+            return Err(InspectError::ConfigMismatch(to_hex(
+                fingerprint.unwrap_or(&proof.bytes),
+            )));
+        }
+
+        // the commitment is only deserialized to find where it ends
+        let body = &proof.bytes[Cfg::FINGERPRINT.len()..];
+        let mut cursor = Cursor::new(body);
+        <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Commitment::deserialize_from(
+            &mut cursor,
+        )?;
+        let (commitment, transcript) = body.split_at(cursor.position() as usize);
+
+        let mut claimed_v_bytes = vec![];
+        claimed_v.serialize_into(&mut claimed_v_bytes)?;
+
+        Ok(Self {
+            schema: PROOF_SCHEMA.to_owned(),
+            config: ConfigJson::new::<Cfg>(),
+            claimed_v: to_hex(&claimed_v_bytes),
+            commitment: to_hex(commitment),
+            transcript: to_hex(transcript),
+            size: proof.bytes.len(),
+        })
+    }
+
+    /// Reassemble the proof and the claimed value.
+    pub fn to_proof<Cfg: GKREngine>(
+        &self,
+    ) -> InspectResult<(Proof, <Cfg::FieldConfig as FieldEngine>::ChallengeField)> {
+        self.config.check::<Cfg>()?;
+
+        let claimed_v = <Cfg::FieldConfig as FieldEngine>::ChallengeField::deserialize_from(
+            from_hex(&self.claimed_v)?.as_slice(),
+        )?;
+
+        let mut bytes = Cfg::FINGERPRINT.to_vec();
+        bytes.extend(from_hex(&self.commitment)?);
+        bytes.extend(from_hex(&self.transcript)?);
+
+        Ok((Proof { bytes }, claimed_v))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> InspectResult<Self> {
+        let proof: Self = serde_json::from_str(json)?;
+        match proof.schema == PROOF_SCHEMA {
+            true => Ok(proof),
+            false => Err(InspectError::Schema(proof.schema)),
+        }
+    }
+}
+
+impl fmt::Display for ProofJson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "proof ({})", self.schema)?;
+        writeln!(
+            f,
+            "  config       {} / {} / {}",
+            self.config.field, self.config.pcs, self.config.scheme
+        )?;
+        writeln!(f, "  fingerprint  {}", self.config.fingerprint)?;
+        writeln!(f, "  claimed_v    {}", self.claimed_v)?;
+        writeln!(
+            f,
+            "  commitment   {} bytes  {}",
+            self.commitment.len() / 2,
+            self.commitment
+        )?;
+        writeln!(f, "  transcript   {} bytes", self.transcript.len() / 2)?;
+        write!(f, "  size         {} bytes", self.size)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerJson {
+    pub input_var_num: usize,
+    pub output_var_num: usize,
+    pub mul: usize,
+    pub add: usize,
+    #[serde(rename = "const")]
+    pub const_: usize,
+    pub uni: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitJson {
+    pub digest: String,
+    pub expected_num_output_zeros: usize,
+    pub layers: Vec<LayerJson>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyingKeyJson {
+    pub schema: String,
+    pub config: ConfigJson,
+    pub circuit: CircuitJson,
+    pub pcs_num_vars: usize,
+    pub pcs_verification_key: String,
+}
+
+impl VerifyingKeyJson {
+    /// `circuit_file` is the content of the file `circuit` was loaded from.
+    pub fn new<Cfg: GKREngine>(
+        circuit: &Circuit<Cfg::FieldConfig>,
+        circuit_file: &[u8],
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+    ) -> InspectResult<Self> {
+        let layers = circuit
+            .layers
+            .iter()
+            .map(|layer| LayerJson {
+                input_var_num: layer.input_var_num,
+                output_var_num: layer.output_var_num,
+                mul: layer.mul.len(),
+                add: layer.add.len(),
+                const_: layer.const_.len(),
+                uni: layer.uni.len(),
+            })
+            .collect();
+
+        let mut vk_bytes = vec![];
+        pcs_verification_key.serialize_into(&mut vk_bytes)?;
+
+        Ok(Self {
+            schema: VK_SCHEMA.to_owned(),
+            config: ConfigJson::new::<Cfg>(),
+            circuit: CircuitJson {
+                digest: to_hex(&Sha256::digest(circuit_file)),
+                expected_num_output_zeros: circuit.expected_num_output_zeros,
+                layers,
+            },
+            pcs_num_vars: pcs_params.num_vars(),
+            pcs_verification_key: to_hex(&vk_bytes),
+        })
+    }
+
+    pub fn pcs_verification_key<Cfg: GKREngine>(
+        &self,
+    ) -> InspectResult<
+        <<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+    > {
+        self.config.check::<Cfg>()?;
+
+        Ok(ExpSerde::deserialize_from(
+            from_hex(&self.pcs_verification_key)?.as_slice(),
+        )?)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> InspectResult<Self> {
+        let vk: Self = serde_json::from_str(json)?;
+        match vk.schema == VK_SCHEMA {
+            true => Ok(vk),
+            false => Err(InspectError::Schema(vk.schema)),
+        }
+    }
+}
+
+impl fmt::Display for VerifyingKeyJson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "verifying key ({})", self.schema)?;
+        writeln!(
+            f,
+            "  config       {} / {} / {}",
+            self.config.field, self.config.pcs, self.config.scheme
+        )?;
+        writeln!(f, "  fingerprint  {}", self.config.fingerprint)?;
+        writeln!(f, "  circuit      {}", self.circuit.digest)?;
+        writeln!(
+            f,
+            "  zero outputs {}",
+            self.circuit.expected_num_output_zeros
+        )?;
+        for (i, layer) in self.circuit.layers.iter().enumerate() {
+            writeln!(
+                f,
+                "  layer {:<6} vars {:>2} -> {:>2}  mul {:>8}  add {:>8}  const {:>8}  uni {:>8}",
+                i,
+                layer.input_var_num,
+                layer.output_var_num,
+                layer.mul,
+                layer.add,
+                layer.const_,
+                layer.uni
+            )?;
+        }
+        writeln!(f, "  pcs vars     {}", self.pcs_num_vars)?;
+        write!(
+            f,
+            "  pcs vk       {} bytes",
+            self.pcs_verification_key.len() / 2
+        )
+    }
+}
+
+#[inline]
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[inline]
+fn from_hex(hex: &str) -> InspectResult<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return Err(InspectError::Hex);
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .filter(|byte| byte.bytes().all(|c| c.is_ascii_hexdigit()))
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or(InspectError::Hex)
+        })
+        .collect()
+}
@@ -1 +1,5 @@
+pub mod bench;
 pub mod executor;
+pub mod inspect;
+pub mod proof_store;
+pub mod recommend;
@@ -237,12 +237,14 @@ where
         let mut prover = Prover::<Cfg>::new(mpi_config.clone());
         prover.prepare_mem(&local_circuit);
 
-        let (claim, proof) = prover.prove(
-            &mut local_circuit,
-            &pcs_params,
-            &pcs_proving_key,
-            &mut pcs_scratch,
-        );
+        let (claim, proof) = prover
+            .prove(
+                &mut local_circuit,
+                &pcs_params,
+                &pcs_proving_key,
+                &mut pcs_scratch,
+            )
+            .unwrap();
         let mut buf = Vec::new();
         claim.serialize_into(&mut buf).unwrap();
         proof.serialize_into(&mut buf).unwrap();
@@ -268,7 +270,9 @@ where
                 let mut prover = Prover::<Cfg>::new(local_mpi_config);
                 prover.prepare_mem(&c);
                 loop {
-                    prover.prove(&mut c, &pcs_params, &pcs_proving_key, &mut pcs_scratch);
+                    prover
+                        .prove(&mut c, &pcs_params, &pcs_proving_key, &mut pcs_scratch)
+                        .unwrap();
                     // update cnt
                     let mut cnt = partial_proof_cnt.lock().unwrap();
                     let proof_cnt_this_round = circuit_copy_size * pack_size;
@@ -14,9 +14,14 @@ use gkr::{
         POSEIDON_M31_WITNESS,
     },
 };
+#[cfg(feature = "raw-distributed")]
+use gkr::{
+    BN254ConfigSha2RawDistributed, GF2ExtConfigSha2RawDistributed,
+    Goldilocksx8ConfigSha2RawDistributed, M31x16ConfigSha2RawDistributedVanilla,
+};
 use gkr_engine::{
-    FieldEngine, FieldType, GKREngine, MPIConfig, MPIEngine, MPISharedMemory,
-    PolynomialCommitmentType, root_println,
+    ExpanderPCS, FieldEngine, FieldType, GKREngine, MPIConfig, MPIEngine, MPIEnvironment,
+    MPISharedMemory, PolynomialCommitmentType, root_println,
 };
 use poly_commit::expander_pcs_init_testing_only;
 use serdes::ExpSerde;
@@ -33,7 +38,8 @@ struct Args {
     #[arg(short, long, default_value_t = String::from("keccak"))]
     circuit: String,
 
-    /// Polynomial Commitment Scheme: Raw, Hyrax, Orion, KZG
+    /// Polynomial Commitment Scheme: Raw, Hyrax, Orion, KZG, or RawDistributed, which is not
+    /// binding, with the `raw-distributed` feature
     #[arg(short, long, default_value_t = String::from("Raw"))]
     pcs: String,
 
@@ -45,9 +51,8 @@ struct Args {
 fn main() {
     let args = Args::parse();
 
-    let universe = MPIConfig::init().unwrap();
-    let world = universe.world();
-    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+    let mpi_env = MPIEnvironment::auto();
+    let mpi_config = mpi_env.config();
 
     print_info(&args, &mpi_config);
 
@@ -67,6 +72,14 @@ fn main() {
                 "poseidon" => run_benchmark::<M31x16ConfigSha2RawSquare>(&args, mpi_config.clone()),
                 _ => unreachable!(),
             },
+            #[cfg(feature = "raw-distributed")]
+            PolynomialCommitmentType::RawDistributed => match args.circuit.as_str() {
+                "keccak" => run_benchmark::<M31x16ConfigSha2RawDistributedVanilla>(
+                    &args,
+                    mpi_config.clone(),
+                ),
+                _ => unreachable!(),
+            },
             PolynomialCommitmentType::Orion => match args.circuit.as_str() {
                 "keccak" => {
                     run_benchmark::<M31x16ConfigSha2OrionVanilla>(&args, mpi_config.clone())
@@ -83,6 +96,13 @@ fn main() {
                 "keccak" => run_benchmark::<BN254ConfigSha2Raw>(&args, mpi_config.clone()),
                 _ => unreachable!(),
             },
+            #[cfg(feature = "raw-distributed")]
+            PolynomialCommitmentType::RawDistributed => match args.circuit.as_str() {
+                "keccak" => {
+                    run_benchmark::<BN254ConfigSha2RawDistributed>(&args, mpi_config.clone())
+                }
+                _ => unreachable!(),
+            },
             PolynomialCommitmentType::Hyrax => match args.circuit.as_str() {
                 "keccak" => run_benchmark::<BN254ConfigSha2Hyrax>(&args, mpi_config.clone()),
                 _ => unreachable!(),
@@ -98,6 +118,13 @@ fn main() {
                 "keccak" => run_benchmark::<GF2ExtConfigSha2Raw>(&args, mpi_config.clone()),
                 _ => unreachable!(),
             },
+            #[cfg(feature = "raw-distributed")]
+            PolynomialCommitmentType::RawDistributed => match args.circuit.as_str() {
+                "keccak" => {
+                    run_benchmark::<GF2ExtConfigSha2RawDistributed>(&args, mpi_config.clone())
+                }
+                _ => unreachable!(),
+            },
             PolynomialCommitmentType::Orion => match args.circuit.as_str() {
                 "keccak" => run_benchmark::<GF2ExtConfigSha2Orion>(&args, mpi_config.clone()),
                 _ => unreachable!(),
@@ -109,6 +136,13 @@ fn main() {
                 "keccak" => run_benchmark::<Goldilocksx8ConfigSha2Raw>(&args, mpi_config.clone()),
                 _ => unreachable!(),
             },
+            #[cfg(feature = "raw-distributed")]
+            PolynomialCommitmentType::RawDistributed => match args.circuit.as_str() {
+                "keccak" => {
+                    run_benchmark::<Goldilocksx8ConfigSha2RawDistributed>(&args, mpi_config.clone())
+                }
+                _ => unreachable!(),
+            },
             PolynomialCommitmentType::Orion => match args.circuit.as_str() {
                 "keccak" => run_benchmark::<Goldilocksx8ConfigSha2Orion>(&args, mpi_config.clone()),
                 _ => unreachable!(),
@@ -117,12 +151,23 @@ fn main() {
         },
         _ => unreachable!(),
     };
+
+    root_println!(
+        mpi_config,
+        "collectives of the root:\n{}",
+        mpi_config.comm_stats()
+    );
 }
 
 fn run_benchmark<Cfg: GKREngine>(args: &Args, mpi_config: MPIConfig)
 where
     Cfg::FieldConfig: FieldEngine,
 {
+    if let Err(e) = Cfg::PCSConfig::check_world_size(mpi_config.world_size()) {
+        root_println!(mpi_config, "{e}");
+        std::process::exit(1);
+    }
+
     let pack_size = <Cfg::FieldConfig as FieldEngine>::get_field_pack_size();
 
     // load circuit
@@ -208,12 +253,14 @@ where
 
     // calculate the proof size
     {
-        let (claim, proof) = prover.prove(
-            &mut circuit,
-            &pcs_params,
-            &pcs_proving_key,
-            &mut pcs_scratch,
-        );
+        let (claim, proof) = prover
+            .prove(
+                &mut circuit,
+                &pcs_params,
+                &pcs_proving_key,
+                &mut pcs_scratch,
+            )
+            .unwrap();
         let mut buf = Vec::new();
         claim.serialize_into(&mut buf).unwrap();
         proof.serialize_into(&mut buf).unwrap();
@@ -231,15 +278,17 @@ where
         "We are now calculating average throughput, please wait until {N_PROOF} proofs are computed"
     );
     for i in 0..args.repeats {
-        mpi_config.barrier(); // wait until everyone is here
+        mpi_config.barrier().unwrap(); // wait until everyone is here
         let start_time = std::time::Instant::now();
         for _j in 0..N_PROOF {
-            prover.prove(
-                &mut circuit,
-                &pcs_params,
-                &pcs_proving_key,
-                &mut pcs_scratch,
-            );
+            prover
+                .prove(
+                    &mut circuit,
+                    &pcs_params,
+                    &pcs_proving_key,
+                    &mut pcs_scratch,
+                )
+                .unwrap();
         }
         let stop_time = std::time::Instant::now();
         let duration = stop_time.duration_since(start_time);
@@ -253,7 +302,7 @@ where
         );
     }
     circuit.discard_control_of_shared_mem();
-    mpi_config.free_shared_mem(&mut window);
+    mpi_config.free_shared_mem(&mut window).unwrap();
 }
 
 fn print_info(args: &Args, mpi_config: &MPIConfig) {
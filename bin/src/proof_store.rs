@@ -0,0 +1,265 @@
+//! The proofs served by `expander-exec serve`, addressed by their circuit and witness.
+//!
+//! A proof is stored under the digests of its circuit, of its public input and of its witness,
+//! so that a request to prove the same witness twice, e.g., retried by a client, is answered with
+//! the proof of the first request instead of proving it again. A request is never answered with
+//! the proof of another witness, which could not be checked against its own witness and, the
+//! proofs not being zero knowledge, would reveal something of the other witness. For the same
+//! reason, the key of a proof can only be computed by the holders of its witness.
+//!
+//! Only the proofs of a zero claimed value are stored, i.e., of witnesses satisfying the
+//! circuit.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serdes::ExpSerde;
+use sha2::{Digest, Sha256};
+
+/// The content address of a proof: the digests of the circuit, of the public input and of the
+/// witness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProofKey {
+    pub circuit_digest: [u8; 32],
+    pub public_input_digest: [u8; 32],
+    pub witness_digest: [u8; 32],
+}
+
+impl ProofKey {
+    /// The key of the proof of the circuit of `circuit_digest`, see `gkr::circuit_digest`, on
+    /// `public_input` and the serialized witness `witness_bytes`
+    pub fn new<T: ExpSerde>(
+        circuit_digest: [u8; 32],
+        public_input: &[T],
+        witness_bytes: &[u8],
+    ) -> Self {
+        let mut bytes = vec![];
+        public_input
+            .iter()
+            .for_each(|v| v.serialize_into(&mut bytes).unwrap());
+
+        Self {
+            circuit_digest,
+            public_input_digest: Sha256::digest(&bytes).into(),
+            witness_digest: Sha256::digest(witness_bytes).into(),
+        }
+    }
+}
+
+/// The hex of the circuit digest, of the public input digest and of the witness digest.
+impl fmt::Display for ProofKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.circuit_digest
+            .iter()
+            .chain(&self.public_input_digest)
+            .chain(&self.witness_digest)
+            .try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
+
+impl FromStr for ProofKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 192 || !s.is_ascii() {
+            return Err(format!(
+                "Expected 192 hex digits for a proof key, got '{s}'"
+            ));
+        }
+
+        let mut bytes = [0u8; 96];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)
+                .map_err(|e| format!("Invalid proof key '{s}': {e}"))?;
+        }
+        Ok(Self {
+            circuit_digest: bytes[..32].try_into().unwrap(),
+            public_input_digest: bytes[32..64].try_into().unwrap(),
+            witness_digest: bytes[64..].try_into().unwrap(),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StoredProof {
+    bytes: Arc<Vec<u8>>,
+    stored_at: Instant,
+}
+
+/// Serialized proofs by their `ProofKey`, see the module documentation.
+///
+/// A proof expires `ttl` after it is stored, if any, and is then neither returned nor counted.
+#[derive(Debug, Clone, Default)]
+pub struct ProofStore {
+    proofs: HashMap<ProofKey, StoredProof>,
+    ttl: Option<Duration>,
+}
+
+impl ProofStore {
+    pub fn new(ttl: Option<Duration>) -> Self {
+        Self {
+            proofs: HashMap::new(),
+            ttl,
+        }
+    }
+
+    /// The proof of `key`, unless there is none or it expired
+    pub fn get(&self, key: &ProofKey) -> Option<Arc<Vec<u8>>> {
+        let now = Instant::now();
+        self.proofs
+            .get(key)
+            .filter(|proof| is_live(self.ttl, proof, now))
+            .map(|proof| proof.bytes.clone())
+    }
+
+    #[inline]
+    pub fn contains(&self, key: &ProofKey) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Store the proof of `key`, unless a live one is stored already, in which
+    /// case that one is kept. Returns the proof stored for `key`.
+    pub fn insert(&mut self, key: ProofKey, bytes: Vec<u8>) -> Arc<Vec<u8>> {
+        if let Some(bytes) = self.get(&key) {
+            return bytes;
+        }
+
+        self.purge_expired();
+        let bytes = Arc::new(bytes);
+        self.proofs.insert(
+            key,
+            StoredProof {
+                bytes: bytes.clone(),
+                stored_at: Instant::now(),
+            },
+        );
+        bytes
+    }
+
+    pub fn remove(&mut self, key: &ProofKey) -> Option<Arc<Vec<u8>>> {
+        self.proofs.remove(key).map(|proof| proof.bytes)
+    }
+
+    /// Drop the expired proofs, returns how many were dropped
+    pub fn purge_expired(&mut self) -> usize {
+        let now = Instant::now();
+        let before = self.proofs.len();
+        let ttl = self.ttl;
+        self.proofs.retain(|_, proof| is_live(ttl, proof, now));
+        before - self.proofs.len()
+    }
+
+    /// The keys of the live proofs of the circuit of `circuit_digest`
+    pub fn keys_of_circuit(&self, circuit_digest: &[u8; 32]) -> Vec<ProofKey> {
+        let now = Instant::now();
+        self.proofs
+            .iter()
+            .filter(|(key, proof)| {
+                key.circuit_digest == *circuit_digest && is_live(self.ttl, proof, now)
+            })
+            .map(|(key, _)| *key)
+            .collect()
+    }
+
+    /// The number of live proofs
+    pub fn len(&self) -> usize {
+        let now = Instant::now();
+        self.proofs
+            .values()
+            .filter(|proof| is_live(self.ttl, proof, now))
+            .count()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[inline(always)]
+fn is_live(ttl: Option<Duration>, proof: &StoredProof, now: Instant) -> bool {
+    ttl.is_none_or(|ttl| now.duration_since(proof.stored_at) < ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ProofKey, ProofStore};
+
+    fn key(circuit_byte: u8, public_input: &[u32]) -> ProofKey {
+        ProofKey::new([circuit_byte; 32], public_input, b"witness")
+    }
+
+    #[test]
+    fn test_proof_store_insert() {
+        let mut store = ProofStore::new(None);
+        assert!(store.is_empty());
+
+        // the first proof of a witness is kept
+        let first = store.insert(key(1, &[1, 2]), vec![1]);
+        let second = store.insert(key(1, &[1, 2]), vec![2]);
+        assert_eq!(*first, vec![1]);
+        assert_eq!(*second, vec![1]);
+        assert_eq!(store.len(), 1);
+
+        store.insert(key(1, &[2, 1]), vec![3]);
+        store.insert(key(2, &[1, 2]), vec![4]);
+        assert_eq!(store.len(), 3);
+        assert_eq!(*store.get(&key(1, &[2, 1])).unwrap(), vec![3]);
+        assert_eq!(store.keys_of_circuit(&[1; 32]).len(), 2);
+
+        // nor is the proof of another witness of the same statement returned
+        let other_witness = ProofKey::new([1; 32], &[1u32, 2], b"another witness");
+        assert_eq!(store.get(&other_witness), None);
+        assert_eq!(*store.insert(other_witness, vec![5]), vec![5]);
+        assert_eq!(store.len(), 4);
+        assert_eq!(*store.remove(&other_witness).unwrap(), vec![5]);
+
+        assert_eq!(*store.remove(&key(1, &[1, 2])).unwrap(), vec![1]);
+        assert!(!store.contains(&key(1, &[1, 2])));
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn test_proof_store_ttl() {
+        let mut store = ProofStore::new(Some(Duration::ZERO));
+        assert_eq!(*store.insert(key(1, &[1]), vec![1]), vec![1]);
+        assert_eq!(store.get(&key(1, &[1])), None);
+        assert!(store.keys_of_circuit(&[1; 32]).is_empty());
+        assert_eq!(store.len(), 0);
+        assert_eq!(store.purge_expired(), 1);
+        assert_eq!(store.purge_expired(), 0);
+
+        // an expired proof is replaced by the next one
+        store.insert(key(1, &[1]), vec![1]);
+        assert_eq!(*store.insert(key(1, &[1]), vec![2]), vec![2]);
+
+        let mut store = ProofStore::new(Some(Duration::from_secs(3600)));
+        store.insert(key(1, &[1]), vec![1]);
+        assert_eq!(*store.get(&key(1, &[1])).unwrap(), vec![1]);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.purge_expired(), 0);
+    }
+
+    #[test]
+    fn test_proof_key_string() {
+        let key = key(0xab, &[1, 2, 3]);
+        let s = key.to_string();
+        assert_eq!(s.len(), 192);
+        assert!(s.starts_with(&"ab".repeat(32)));
+        assert_eq!(s.parse::<ProofKey>(), Ok(key));
+        assert_eq!(s.to_uppercase().parse::<ProofKey>(), Ok(key));
+
+        assert!(s[..190].parse::<ProofKey>().is_err());
+        assert!(format!("{s}00").parse::<ProofKey>().is_err());
+        assert!(format!("{}zz", &s[..190]).parse::<ProofKey>().is_err());
+        assert!(format!("{}é", &s[..190]).parse::<ProofKey>().is_err());
+        assert!("".parse::<ProofKey>().is_err());
+    }
+}
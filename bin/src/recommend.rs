@@ -0,0 +1,783 @@
+//! Config recommendations for `expander recommend`.
+//!
+//! The configs `expander-exec` runs are ranked by a coarse cost model of a proof, estimating
+//!
+//! - the prover time per witness, with the SIMD lanes and the MPI processes all filled,
+//! - the proof size, i.e., the sumcheck messages of every layer and the PCS opening,
+//! - the gas of verifying the proof on an EVM chain, calldata included.
+//!
+//! The model counts the dominant operations of the GKR prover and of each PCS and weights them
+//! by unit costs measured once on a laptop, so the estimates are orders of magnitude to compare
+//! configs by, not predictions. The gate counts of the circuit are taken for every field alike,
+//! although a circuit compiled for another field has other gate counts.
+use circuit::Circuit;
+use clap::ValueEnum;
+use gkr_engine::{FiatShamirHashType, FieldEngine, FieldType, PolynomialCommitmentType};
+use serde::{Deserialize, Serialize};
+
+/// Number of variables of the input polynomial from which on the KZG-based schemes commit to
+/// and open over a million rows, and a hash-based PCS proves faster.
+pub const LARGE_OPENING_NUM_VARS: usize = 20;
+
+/// Size of the input polynomial in bytes from which on the raw PCS, that writes it to the proof
+/// as is, is considered too large.
+pub const LARGE_RAW_WITNESS_BYTES: u64 = 1 << 20;
+
+/// The (field, PCS, Fiat-Shamir hash) combinations `expander-exec` runs a config for.
+const SUPPORTED_CONFIGS: &[(FieldType, PolynomialCommitmentType, FiatShamirHashType)] = &[
+    (
+        FieldType::M31x16,
+        PolynomialCommitmentType::Orion,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::M31x16,
+        PolynomialCommitmentType::Raw,
+        FiatShamirHashType::Poseidon,
+    ),
+    (
+        FieldType::M31x16,
+        PolynomialCommitmentType::Brakedown,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::M31x16,
+        PolynomialCommitmentType::Basefold,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::Raw,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::Raw,
+        FiatShamirHashType::MIMC5,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::Raw,
+        FiatShamirHashType::Anemoi,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::Raw,
+        FiatShamirHashType::Griffin,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::Hyrax,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::KZG,
+        FiatShamirHashType::MIMC5,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::Zeromorph,
+        FiatShamirHashType::MIMC5,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::Dory,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::BN254,
+        PolynomialCommitmentType::IPA,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::GF2Ext128,
+        PolynomialCommitmentType::Orion,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::GF2Ext128,
+        PolynomialCommitmentType::Raw,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::Goldilocksx8,
+        PolynomialCommitmentType::Orion,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::Goldilocksx8,
+        PolynomialCommitmentType::Basefold,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::Goldilocksx8,
+        PolynomialCommitmentType::Ligero,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::Goldilocksx8,
+        PolynomialCommitmentType::Whir,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::BabyBearx16,
+        PolynomialCommitmentType::Basefold,
+        FiatShamirHashType::SHA256,
+    ),
+    (
+        FieldType::BabyBearx16,
+        PolynomialCommitmentType::Whir,
+        FiatShamirHashType::SHA256,
+    ),
+];
+
+/// EVM gas of a byte of calldata.
+const CALLDATA_GAS_PER_BYTE: u64 = 16;
+
+/// EVM gas of a pairing check of two pairings, with the EIP-197 precompile.
+const PAIRING_CHECK_GAS: u64 = 45_000 + 2 * 34_000;
+
+/// EVM gas of a scalar multiplication in G1, with the EIP-196 precompile.
+const ECMUL_GAS: u64 = 6_000;
+
+/// EVM gas of a multiplication in the target group of BN254, that has no precompile.
+const GT_MUL_GAS: u64 = 4_000;
+
+/// Time of a variable-base MSM per point, in nanoseconds.
+const MSM_NS_PER_POINT: u64 = 300;
+
+/// Time of a pairing, in nanoseconds.
+const PAIRING_NS: u64 = 500_000;
+
+/// Serialized sizes of the BN254 groups, in bytes.
+const G1_BYTES: u64 = 32;
+const G2_BYTES: u64 = 64;
+const GT_BYTES: u64 = 384;
+
+/// Number of queries of the hash-based schemes, for about 100 bits of security.
+const NUM_QUERIES: u64 = 128;
+
+/// What to rank the configs by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OptimizationTarget {
+    /// Smallest proof
+    ProofSize,
+    /// Fastest prover per witness
+    ProverTime,
+    /// Cheapest verification on an EVM chain
+    OnChainGas,
+}
+
+/// The shape of a circuit the cost model reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitStats {
+    pub num_layers: usize,
+    /// Gates of all the layers, skip gates included
+    pub num_gates: usize,
+    /// Variables of the inputs of all the layers, each bound by two sumcheck phases
+    pub num_sumcheck_vars: usize,
+    /// Variables of the input layer of a process
+    pub log_input_size: usize,
+    pub mpi_size: usize,
+}
+
+impl CircuitStats {
+    pub fn new<C: FieldEngine>(circuit: &Circuit<C>, mpi_size: usize) -> Self {
+        assert!(mpi_size > 0);
+
+        let num_gates = circuit
+            .layers
+            .iter()
+            .map(|layer| {
+                layer.mul.len()
+                    + layer.add.len()
+                    + layer.const_.len()
+                    + layer.uni.len()
+                    + layer
+                        .skip_inputs
+                        .iter()
+                        .map(|skip| skip.gates.len())
+                        .sum::<usize>()
+            })
+            .sum();
+
+        Self {
+            num_layers: circuit.layers.len(),
+            num_gates,
+            num_sumcheck_vars: circuit.layers.iter().map(|l| l.input_var_num).sum(),
+            log_input_size: circuit.log_input_size(),
+            mpi_size,
+        }
+    }
+
+    /// Variables of the input polynomial over all the parties, virtual parties included.
+    #[inline]
+    pub fn global_input_num_vars(&self) -> usize {
+        self.log_input_size + self.mpi_size.next_power_of_two().ilog2() as usize
+    }
+}
+
+/// Estimated costs of a proof of a circuit with a config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostEstimate {
+    pub prover_ns_per_witness: u64,
+    pub proof_bytes: u64,
+    pub verifier_gas: u64,
+}
+
+impl CostEstimate {
+    #[inline]
+    pub fn of(&self, target: OptimizationTarget) -> u64 {
+        match target {
+            OptimizationTarget::ProofSize => self.proof_bytes,
+            OptimizationTarget::ProverTime => self.prover_ns_per_witness,
+            OptimizationTarget::OnChainGas => self.verifier_gas,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigSuggestion {
+    pub field: FieldType,
+    pub pcs: PolynomialCommitmentType,
+    pub hash: FiatShamirHashType,
+    pub estimate: CostEstimate,
+    /// Why the config is a poor fit for the circuit, if it is
+    pub warnings: Vec<String>,
+}
+
+/// Unit costs of the arithmetic of a field config.
+struct FieldCosts {
+    pack_size: u64,
+    /// Bytes of a circuit field element, i.e., of a lane of the input polynomial
+    circuit_bytes: u64,
+    /// Bytes of a challenge field element, i.e., of a sumcheck message entry
+    challenge_bytes: u64,
+    /// Time of proving a gate over all the SIMD lanes
+    gate_ns: u64,
+    /// EVM gas of a multiplication in the challenge field
+    challenge_mul_gas: u64,
+}
+
+fn field_costs(field: &FieldType) -> FieldCosts {
+    let (pack_size, circuit_bytes, challenge_bytes, gate_ns, challenge_mul_gas) = match field {
+        FieldType::M31x16 => (16, 4, 12, 8, 80),
+        FieldType::M31x1 => (1, 4, 12, 4, 80),
+        FieldType::BN254 => (1, 32, 32, 40, 8),
+        FieldType::GF2Ext128 => (8, 1, 16, 10, 2_000),
+        FieldType::Goldilocksx8 => (8, 8, 16, 12, 40),
+        FieldType::Goldilocksx1 => (1, 8, 16, 6, 40),
+        FieldType::BabyBearx16 => (16, 4, 12, 8, 80),
+    };
+    FieldCosts {
+        pack_size,
+        circuit_bytes,
+        challenge_bytes,
+        gate_ns,
+        challenge_mul_gas,
+    }
+}
+
+/// Time and EVM gas of hashing a block of 64 bytes into the transcript.
+fn hash_costs(hash: &FiatShamirHashType) -> (u64, u64) {
+    match hash {
+        FiatShamirHashType::SHA256 => (300, 84),
+        FiatShamirHashType::Keccak256 => (300, 42),
+        FiatShamirHashType::Poseidon => (2_000, 50_000),
+        FiatShamirHashType::MIMC5 => (20_000, 4_000),
+        FiatShamirHashType::Anemoi => (8_000, 20_000),
+        FiatShamirHashType::Griffin => (8_000, 20_000),
+    }
+}
+
+/// Costs of committing to and opening the input polynomial.
+struct PcsCosts {
+    prover_ns: u64,
+    proof_bytes: u64,
+    verifier_hashes: u64,
+    verifier_field_ops: u64,
+    /// Gas of the group operations of the verifier
+    verifier_group_gas: u64,
+}
+
+fn pcs_costs(
+    pcs: &PolynomialCommitmentType,
+    field: &FieldCosts,
+    num_vars: usize,
+    mpi_size: usize,
+) -> PcsCosts {
+    let n = num_vars as u64;
+    let len = 1u64 << num_vars;
+    let lanes = len * field.pack_size;
+    let sqrt_lanes = 1u64 << (lanes.ilog2() as u64).div_ceil(2);
+    let merkle_path_bytes = 32 * lanes.ilog2() as u64;
+
+    let (prover_ns, proof_bytes, verifier_hashes, verifier_field_ops, verifier_group_gas) =
+        match pcs {
+            // the evaluations are the proof, and the verifier evaluates them
+            PolynomialCommitmentType::Raw => (lanes, lanes * field.circuit_bytes, 0, lanes, 0),
+            // an evaluation of its share per process, combined by the verifier
+            PolynomialCommitmentType::RawDistributed => {
+                let mpi_size = mpi_size.next_power_of_two() as u64;
+                (lanes, mpi_size * field.challenge_bytes, 0, mpi_size, 0)
+            }
+            // HyperKZG: an MSM over the polynomial and over each of its foldings
+            PolynomialCommitmentType::KZG => (
+                3 * len * MSM_NS_PER_POINT,
+                2 * n * G1_BYTES + 3 * n * field.challenge_bytes,
+                0,
+                4 * n,
+                PAIRING_CHECK_GAS + 2 * n * ECMUL_GAS,
+            ),
+            PolynomialCommitmentType::Zeromorph => (
+                3 * len * MSM_NS_PER_POINT,
+                (n + 2) * G1_BYTES,
+                0,
+                4 * n,
+                PAIRING_CHECK_GAS + (n + 3) * ECMUL_GAS,
+            ),
+            // the commitment is a point per row of the square matrix of the evaluations
+            PolynomialCommitmentType::Hyrax => (
+                len * MSM_NS_PER_POINT,
+                sqrt_lanes * (G1_BYTES + field.challenge_bytes),
+                0,
+                sqrt_lanes,
+                2 * sqrt_lanes * ECMUL_GAS,
+            ),
+            // a reduce round per variable of a side of the square matrix, each exponentiating
+            // elements of the target group
+            PolynomialCommitmentType::Dory => {
+                let rounds = n.div_ceil(2);
+                (
+                    len * MSM_NS_PER_POINT + sqrt_lanes * PAIRING_NS,
+                    rounds * (6 * GT_BYTES + 3 * G1_BYTES + 3 * G2_BYTES) + 2 * GT_BYTES,
+                    0,
+                    0,
+                    (rounds * 10 + 4) * 256 * GT_MUL_GAS,
+                )
+            }
+            // a round per variable, the verifier folding the bases with an MSM as long as the
+            // polynomial
+            PolynomialCommitmentType::IPA => (
+                3 * len * MSM_NS_PER_POINT,
+                2 * n * G1_BYTES + field.challenge_bytes,
+                0,
+                4 * n,
+                (len + 2 * n + 2) * ECMUL_GAS,
+            ),
+            // linear codes with a proximity test over a square matrix
+            PolynomialCommitmentType::Orion
+            | PolynomialCommitmentType::Brakedown
+            | PolynomialCommitmentType::Ligero => {
+                let encode_ns = match pcs {
+                    PolynomialCommitmentType::Ligero => 30,
+                    _ => 10,
+                };
+                (
+                    lanes * encode_ns,
+                    2 * sqrt_lanes * field.challenge_bytes
+                        + NUM_QUERIES * (sqrt_lanes * field.circuit_bytes + merkle_path_bytes),
+                    NUM_QUERIES * n,
+                    NUM_QUERIES * sqrt_lanes,
+                    0,
+                )
+            }
+            // a Merkle opening per query per folding round
+            PolynomialCommitmentType::FRI | PolynomialCommitmentType::Basefold => (
+                lanes * 30,
+                NUM_QUERIES * n * (2 * field.challenge_bytes + merkle_path_bytes),
+                NUM_QUERIES * n * n,
+                NUM_QUERIES * n,
+                0,
+            ),
+            // fewer queries, over folding rounds of several variables each
+            PolynomialCommitmentType::Whir => {
+                let rounds = n.div_ceil(4);
+                let queries = NUM_QUERIES / 4;
+                (
+                    lanes * 30,
+                    queries * rounds * (16 * field.challenge_bytes + merkle_path_bytes),
+                    queries * rounds * n,
+                    queries * rounds * 16,
+                    0,
+                )
+            }
+        };
+
+    PcsCosts {
+        prover_ns,
+        proof_bytes,
+        verifier_hashes,
+        verifier_field_ops,
+        verifier_group_gas,
+    }
+}
+
+/// Estimate the costs of proving a circuit of `stats` with a config.
+pub fn estimate_cost(
+    stats: &CircuitStats,
+    field: &FieldType,
+    pcs: &PolynomialCommitmentType,
+    hash: &FiatShamirHashType,
+) -> CostEstimate {
+    let field_costs = field_costs(field);
+    let (hash_ns, hash_gas) = hash_costs(hash);
+    let pcs_costs = pcs_costs(
+        pcs,
+        &field_costs,
+        stats.global_input_num_vars(),
+        stats.mpi_size,
+    );
+
+    // two sumcheck phases per layer, a degree 3 message per variable
+    let sumcheck_rounds = 2 * stats.num_sumcheck_vars as u64;
+    let gkr_proof_bytes =
+        (sumcheck_rounds * 4 + 4 * stats.num_layers as u64) * field_costs.challenge_bytes;
+    let num_hashes = sumcheck_rounds + stats.num_layers as u64 + pcs_costs.verifier_hashes;
+
+    let witnesses = field_costs.pack_size * stats.mpi_size as u64;
+    let prover_ns = stats.num_gates as u64 * field_costs.gate_ns * stats.mpi_size as u64
+        + pcs_costs.prover_ns
+        + num_hashes * hash_ns;
+    let proof_bytes = gkr_proof_bytes + pcs_costs.proof_bytes;
+
+    // the verifier evaluates the wiring of every gate and checks every sumcheck message
+    let verifier_field_ops =
+        3 * stats.num_gates as u64 + 8 * sumcheck_rounds + pcs_costs.verifier_field_ops;
+    let verifier_gas = proof_bytes * CALLDATA_GAS_PER_BYTE
+        + verifier_field_ops * field_costs.challenge_mul_gas
+        + num_hashes * hash_gas
+        + pcs_costs.verifier_group_gas;
+
+    CostEstimate {
+        prover_ns_per_witness: prover_ns.div_ceil(witnesses),
+        proof_bytes,
+        verifier_gas,
+    }
+}
+
+/// The reasons a config is a poor fit for a circuit of `stats`.
+fn warnings(
+    stats: &CircuitStats,
+    field: &FieldType,
+    pcs: &PolynomialCommitmentType,
+    target: OptimizationTarget,
+) -> Vec<String> {
+    let num_vars = stats.global_input_num_vars();
+    let mut warnings = vec![];
+
+    match pcs {
+        PolynomialCommitmentType::KZG
+        | PolynomialCommitmentType::Zeromorph
+        | PolynomialCommitmentType::Dory
+        | PolynomialCommitmentType::IPA
+            if num_vars >= LARGE_OPENING_NUM_VARS =>
+        {
+            warnings.push(format!(
+                "commits to and opens 2^{num_vars} rows with MSMs over as many points"
+            ));
+        }
+        PolynomialCommitmentType::Raw => {
+            let witness_bytes =
+                (field_costs(field).pack_size * field_costs(field).circuit_bytes) << num_vars;
+            if witness_bytes >= LARGE_RAW_WITNESS_BYTES {
+                warnings.push(format!(
+                    "writes the whole input polynomial of {witness_bytes} bytes to the proof"
+                ));
+            }
+        }
+        PolynomialCommitmentType::RawDistributed => {
+            warnings
+                .push("is not binding, the verifier never sees the input polynomial".to_string());
+        }
+        _ => (),
+    }
+    if matches!(
+        pcs,
+        PolynomialCommitmentType::Zeromorph
+            | PolynomialCommitmentType::Dory
+            | PolynomialCommitmentType::IPA
+    ) && stats.mpi_size > 1
+    {
+        warnings.push("gathers the input polynomial and opens it on the root alone".to_string());
+    }
+    if matches!(
+        pcs,
+        PolynomialCommitmentType::Orion
+            | PolynomialCommitmentType::Brakedown
+            | PolynomialCommitmentType::Ligero
+            | PolynomialCommitmentType::KZG
+    ) && !stats.mpi_size.is_power_of_two()
+    {
+        warnings.push(format!(
+            "is distributed over the processes, {} of them is not a power of two",
+            stats.mpi_size
+        ));
+    }
+    if target == OptimizationTarget::OnChainGas && *pcs == PolynomialCommitmentType::Dory {
+        warnings.push(
+            "verifies with exponentiations in the pairing target group, that the EVM has no \
+             precompile for"
+                .to_string(),
+        );
+    }
+
+    warnings
+}
+
+/// Rank the configs `expander-exec` runs for a circuit of `stats`, the best for `target` first.
+/// The configs with warnings, e.g., a KZG-based PCS opening millions of rows or the raw PCS over
+/// a huge witness, are ranked after all the others.
+pub fn recommend_config(stats: &CircuitStats, target: OptimizationTarget) -> Vec<ConfigSuggestion> {
+    let mut suggestions: Vec<ConfigSuggestion> = SUPPORTED_CONFIGS
+        .iter()
+        .map(|(field, pcs, hash)| ConfigSuggestion {
+            field: field.clone(),
+            pcs: pcs.clone(),
+            hash: hash.clone(),
+            estimate: estimate_cost(stats, field, pcs, hash),
+            warnings: warnings(stats, field, pcs, target),
+        })
+        .collect();
+
+    suggestions.sort_by_key(|s| (!s.warnings.is_empty(), s.estimate.of(target)));
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use circuit::{Circuit, CircuitLayer, CoefType, Gate, SkipInputs};
+    use gkr_engine::{FiatShamirHashType, FieldType, M31x16Config, PolynomialCommitmentType};
+
+    use super::{
+        CircuitStats, ConfigSuggestion, CostEstimate, OptimizationTarget, SUPPORTED_CONFIGS,
+        estimate_cost, recommend_config,
+    };
+
+    const TARGETS: [OptimizationTarget; 3] = [
+        OptimizationTarget::ProofSize,
+        OptimizationTarget::ProverTime,
+        OptimizationTarget::OnChainGas,
+    ];
+
+    fn stats(log_input_size: usize, mpi_size: usize) -> CircuitStats {
+        CircuitStats {
+            num_layers: 8,
+            num_gates: 4096,
+            num_sumcheck_vars: 80,
+            log_input_size,
+            mpi_size,
+        }
+    }
+
+    fn config(suggestion: &ConfigSuggestion) -> (FieldType, PolynomialCommitmentType) {
+        (suggestion.field.clone(), suggestion.pcs.clone())
+    }
+
+    fn is_kzg_based(pcs: &PolynomialCommitmentType) -> bool {
+        matches!(
+            pcs,
+            PolynomialCommitmentType::KZG
+                | PolynomialCommitmentType::Zeromorph
+                | PolynomialCommitmentType::Dory
+                | PolynomialCommitmentType::IPA
+        )
+    }
+
+    // every config once, those without warnings first, each group by increasing cost
+    fn check_ranking(suggestions: &[ConfigSuggestion], target: OptimizationTarget) {
+        assert_eq!(suggestions.len(), SUPPORTED_CONFIGS.len());
+        suggestions.windows(2).for_each(|pair| {
+            let key = |s: &ConfigSuggestion| (!s.warnings.is_empty(), s.estimate.of(target));
+            assert!(key(&pair[0]) <= key(&pair[1]), "{pair:?}");
+        });
+    }
+
+    #[test]
+    fn test_recommend_config_small_circuit() {
+        let stats = stats(10, 1);
+        let expected_first = [
+            (FieldType::GF2Ext128, PolynomialCommitmentType::Raw),
+            (FieldType::GF2Ext128, PolynomialCommitmentType::Raw),
+            (FieldType::BN254, PolynomialCommitmentType::Hyrax),
+        ];
+        for (target, expected_first) in TARGETS.into_iter().zip(expected_first) {
+            let suggestions = recommend_config(&stats, target);
+            check_ranking(&suggestions, target);
+            assert_eq!(config(&suggestions[0]), expected_first, "{target:?}");
+            assert!(suggestions[0].warnings.is_empty());
+        }
+
+        // only Dory is flagged, for its exponentiations in the target group on chain
+        let suggestions = recommend_config(&stats, OptimizationTarget::OnChainGas);
+        let flagged: Vec<_> = suggestions
+            .iter()
+            .filter(|s| !s.warnings.is_empty())
+            .map(config)
+            .collect();
+        assert_eq!(
+            flagged,
+            vec![(FieldType::BN254, PolynomialCommitmentType::Dory)]
+        );
+    }
+
+    #[test]
+    fn test_recommend_config_large_input() {
+        // 2^22 rows: the KZG-based schemes would give the smallest proofs and the cheapest
+        // verification, and the raw PCS the fastest prover, but are ranked after the others
+        let stats = stats(22, 1);
+        let expected_first = [
+            (FieldType::BN254, PolynomialCommitmentType::Hyrax),
+            (FieldType::M31x16, PolynomialCommitmentType::Orion),
+            (FieldType::Goldilocksx8, PolynomialCommitmentType::Whir),
+        ];
+        for (target, expected_first) in TARGETS.into_iter().zip(expected_first) {
+            let suggestions = recommend_config(&stats, target);
+            check_ranking(&suggestions, target);
+            assert_eq!(config(&suggestions[0]), expected_first, "{target:?}");
+
+            let cheapest = suggestions
+                .iter()
+                .min_by_key(|s| s.estimate.of(target))
+                .unwrap();
+            match target {
+                OptimizationTarget::ProverTime => {
+                    assert_eq!(cheapest.pcs, PolynomialCommitmentType::Raw)
+                }
+                _ => assert!(is_kzg_based(&cheapest.pcs)),
+            }
+
+            let num_clean = suggestions.iter().filter(|s| s.warnings.is_empty()).count();
+            suggestions
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| is_kzg_based(&s.pcs) || s.pcs == PolynomialCommitmentType::Raw)
+                .for_each(|(rank, s)| {
+                    assert!(!s.warnings.is_empty(), "{s:?}");
+                    assert!(rank >= num_clean, "{s:?}");
+                });
+        }
+    }
+
+    #[test]
+    fn test_recommend_config_mpi() {
+        // the schemes distributed over a power of two of processes are flagged on 3 of them
+        let suggestions = recommend_config(&stats(10, 3), OptimizationTarget::ProofSize);
+        check_ranking(&suggestions, OptimizationTarget::ProofSize);
+        assert_eq!(
+            config(&suggestions[0]),
+            (FieldType::BN254, PolynomialCommitmentType::Hyrax)
+        );
+        suggestions
+            .iter()
+            .filter(|s| {
+                matches!(
+                    s.pcs,
+                    PolynomialCommitmentType::Orion
+                        | PolynomialCommitmentType::Brakedown
+                        | PolynomialCommitmentType::Ligero
+                        | PolynomialCommitmentType::KZG
+                        | PolynomialCommitmentType::Zeromorph
+                        | PolynomialCommitmentType::Dory
+                        | PolynomialCommitmentType::IPA
+                )
+            })
+            .for_each(|s| assert!(!s.warnings.is_empty(), "{s:?}"));
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let stats = stats(10, 1);
+        assert_eq!(
+            estimate_cost(
+                &stats,
+                &FieldType::M31x16,
+                &PolynomialCommitmentType::Orion,
+                &FiatShamirHashType::SHA256
+            ),
+            CostEstimate {
+                prover_ns_per_witness: 39_438,
+                proof_bytes: 134_016,
+                verifier_gas: 4_662_048,
+            }
+        );
+        assert_eq!(
+            estimate_cost(
+                &stats,
+                &FieldType::BN254,
+                &PolynomialCommitmentType::KZG,
+                &FiatShamirHashType::MIMC5
+            ),
+            CostEstimate {
+                prover_ns_per_witness: 4_445_440,
+                proof_bytes: 23_104,
+                verifier_gas: 1_383_528,
+            }
+        );
+
+        // the witnesses of 4 processes are proven at once, over an input of 2 more variables
+        let estimate = estimate_cost(
+            &CircuitStats {
+                mpi_size: 4,
+                ..stats
+            },
+            &FieldType::M31x16,
+            &PolynomialCommitmentType::Orion,
+            &FiatShamirHashType::SHA256,
+        );
+        assert_eq!(estimate.prover_ns_per_witness, 20_276);
+        assert_eq!(estimate.proof_bytes, 210_816);
+    }
+
+    fn gate<const INPUT_NUM: usize>(
+        i_ids: [usize; INPUT_NUM],
+        o_id: usize,
+    ) -> Gate<M31x16Config, INPUT_NUM> {
+        Gate {
+            i_ids,
+            o_id,
+            coef_type: CoefType::Constant,
+            coef: Default::default(),
+            gate_type: 0,
+        }
+    }
+
+    #[test]
+    fn test_circuit_stats() {
+        let mut circuit = Circuit::<M31x16Config>::default();
+        circuit.layers.push(CircuitLayer {
+            input_var_num: 3,
+            output_var_num: 2,
+            mul: vec![gate([0, 1], 0), gate([2, 3], 1)],
+            add: vec![gate([4], 2)],
+            ..Default::default()
+        });
+        circuit.layers.push(CircuitLayer {
+            input_var_num: 2,
+            output_var_num: 1,
+            const_: vec![gate([], 0)],
+            uni: vec![gate([1], 1)],
+            skip_inputs: vec![SkipInputs {
+                layer: 0,
+                gates: vec![gate([5], 0), gate([6], 1)],
+            }],
+            ..Default::default()
+        });
+
+        let stats = CircuitStats::new(&circuit, 3);
+        assert_eq!(
+            stats,
+            CircuitStats {
+                num_layers: 2,
+                num_gates: 7,
+                num_sumcheck_vars: 5,
+                log_input_size: 3,
+                mpi_size: 3,
+            }
+        );
+        // 3 processes are padded to 4
+        assert_eq!(stats.global_input_num_vars(), 5);
+    }
+}
@@ -0,0 +1,61 @@
+//! Circuits embedded in the binary by `config_macros::include_circuit!`, for verifiers deployed
+//! as a single binary, with no circuit file to ship along with it.
+//!
+//! The serialized circuit is part of the read-only data of the binary, uncompressed, and is only
+//! deserialized on the first load. The recursive circuit is kept for the later loads, which only
+//! flatten it. The circuit is not parsed at compile time: `include_circuit!` only checks its
+//! version header there, the gates are checked by the first load.
+
+use std::sync::OnceLock;
+
+use gkr_engine::FieldEngine;
+use serdes::ExpSerde;
+
+use crate::{has_circuit_version, Circuit, RecursiveCircuit};
+
+pub struct EmbeddedCircuit<C: FieldEngine> {
+    bytes: &'static [u8],
+    recursive: OnceLock<RecursiveCircuit<C>>,
+}
+
+impl<C: FieldEngine> EmbeddedCircuit<C> {
+    /// The circuit serialized in `bytes`, usually created by `config_macros::include_circuit!`,
+    /// which checks the header at compile time. Called directly, the header is checked at compile
+    /// time in a constant context only, e.g., the initializer of a static.
+    ///
+    /// # Panics
+    /// If `bytes` do not start with `CIRCUIT_VERSION`
+    #[inline]
+    pub const fn new(bytes: &'static [u8]) -> Self {
+        assert!(
+            has_circuit_version(bytes),
+            "not a circuit serialized in the format of CIRCUIT_VERSION"
+        );
+        Self {
+            bytes,
+            recursive: OnceLock::new(),
+        }
+    }
+
+    #[inline]
+    pub fn bytes(&self) -> &'static [u8] {
+        self.bytes
+    }
+
+    /// The recursive circuit, deserialized on the first call
+    ///
+    /// # Panics
+    /// If the bytes are not a circuit over the circuit field of `C`
+    pub fn recursive_circuit(&self) -> &RecursiveCircuit<C> {
+        self.recursive
+            .get_or_init(|| RecursiveCircuit::<C>::deserialize_from(self.bytes).unwrap())
+    }
+
+    // Flatten the circuit, as Circuit::verifier_load_circuit does from a file
+    // Used for verifier
+    pub fn verifier_load_circuit(&self) -> Circuit<C> {
+        let mut c = self.recursive_circuit().flatten();
+        c.pre_process_gkr();
+        c
+    }
+}
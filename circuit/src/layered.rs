@@ -1,7 +1,16 @@
+mod checkpoint;
 mod circuit;
+mod custom_gates;
+mod data_parallel;
 mod gates;
 mod serde;
 mod shared_mem;
+mod skip;
 
+pub use checkpoint::*;
 pub use circuit::*;
+pub use custom_gates::*;
+pub use data_parallel::*;
 pub use gates::*;
+pub(crate) use serde::par_deserialize_gates;
+pub use skip::*;
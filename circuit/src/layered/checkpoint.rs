@@ -0,0 +1,85 @@
+use gkr_engine::FieldEngine;
+
+use crate::*;
+
+/// How much of the evaluated witness is kept in memory between `evaluate` and the GKR prover.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayerRetention {
+    /// Keep the input values of every layer.
+    #[default]
+    Full,
+    /// Keep the input values of every `interval`-th layer only (layer 0 is always kept).
+    ///
+    /// The prover walks from the output layer to the input layer, and recomputes the values of
+    /// a block of `interval` layers from its checkpoint the first time the block is entered.
+    /// This costs about one extra circuit evaluation, in exchange for storing roughly
+    /// `layer_num / interval + interval` layers instead of `layer_num`.
+    Checkpoint { interval: usize },
+}
+
+impl LayerRetention {
+    #[inline]
+    fn is_checkpoint(&self, layer_idx: usize) -> bool {
+        match self {
+            LayerRetention::Full => true,
+            LayerRetention::Checkpoint { interval } => layer_idx % interval == 0,
+        }
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// Same as `evaluate`, but only retains the layer values required by `retention`.
+    pub fn evaluate_with_retention(&mut self, retention: LayerRetention) {
+        if let LayerRetention::Checkpoint { interval } = retention {
+            assert!(interval > 0, "checkpoint interval must be positive");
+        }
+
+        self.evaluate_layers(0, self.layers.len() - 1, retention);
+
+        let mut output = vec![];
+        let (layer, layers_below) = self.layers.split_last().unwrap();
+        layer.evaluate(&mut output, &self.public_input);
+        layer.evaluate_skip_inputs(layers_below, &mut output);
+        self.layers.last_mut().unwrap().output_vals = output;
+        if !self.is_retained(self.layers.len() - 1, retention) {
+            self.layers.last_mut().unwrap().input_vals = vec![];
+        }
+    }
+
+    /// Make sure the input values of layer `layer_idx` are available, recomputing them and the
+    /// ones of the layers below it from the closest retained layer if needed.
+    pub fn restore_layer_vals(&mut self, layer_idx: usize) {
+        let start = (0..=layer_idx)
+            .rev()
+            .find(|&i| !self.layers[i].input_vals.is_empty())
+            .expect("the input layer is always retained");
+        self.evaluate_layers(start, layer_idx, LayerRetention::Full);
+    }
+
+    /// Drop the input values of layer `layer_idx` unless `retention` keeps it as a checkpoint,
+    /// or a later layer reads them through its skip inputs.
+    pub fn release_layer_vals(&mut self, layer_idx: usize, retention: LayerRetention) {
+        if !self.is_retained(layer_idx, retention) {
+            self.layers[layer_idx].input_vals = vec![];
+        }
+    }
+
+    /// Evaluate the input values of layers `start + 1..=end` from the ones of layer `start`.
+    fn evaluate_layers(&mut self, start: usize, end: usize, retention: LayerRetention) {
+        for i in start..end {
+            let retained = self.is_retained(i, retention);
+            let (layer_p_1, layer_p_2) = self.layers.split_at_mut(i + 1);
+            let (layer, layers_below) = layer_p_1.split_last_mut().unwrap();
+            layer.evaluate(&mut layer_p_2[0].input_vals, &self.public_input);
+            layer.evaluate_skip_inputs(layers_below, &mut layer_p_2[0].input_vals);
+            if !retained {
+                layer.input_vals = vec![];
+            }
+        }
+    }
+
+    #[inline]
+    fn is_retained(&self, layer_idx: usize, retention: LayerRetention) -> bool {
+        retention.is_checkpoint(layer_idx) || self.is_skip_source(layer_idx)
+    }
+}
@@ -1,14 +1,23 @@
-use std::fs;
-use std::io::Cursor;
+use std::fs::{self, File};
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::sync::mpsc;
+use std::thread;
 
-use arith::{Field, SimdField};
+use arith::{CanonicalField, Field, SimdField};
 use ark_std::test_rng;
-use gkr_engine::{root_println, FieldEngine, GKREngine, MPIConfig, MPIEngine, Transcript};
+use gkr_engine::{
+    root_println, ExpErrors, FieldEngine, GKREngine, MPIConfig, MPIEngine, MPISharedMemory,
+    Transcript,
+};
 use mpi::ffi::ompi_win_t;
-use serdes::ExpSerde;
+use serdes::{ExpSerde, SerdeError};
 
 use crate::*;
 
+/// The number of SIMD lanes of a streamed witness the root reads ahead of the scatter by default,
+/// see `Circuit::try_prover_stream_witness_file_with_prefetch`
+pub const DEFAULT_WITNESS_PREFETCH_DEPTH: usize = 2;
+
 #[derive(Debug, Clone, Default)]
 pub struct StructureInfo {
     // If a layer contains only linear combination of fan-in-one gates, we can skip the second
@@ -28,6 +37,8 @@ pub struct CircuitLayer<C: FieldEngine> {
     pub add: Vec<GateAdd<C>>,
     pub const_: Vec<GateConst<C>>,
     pub uni: Vec<GateUni<C>>,
+    /// add gates reading the inputs of earlier layers, see `SkipInputs`
+    pub skip_inputs: Vec<SkipInputs<C>>,
 
     pub structure_info: StructureInfo,
 }
@@ -65,26 +76,53 @@ impl<C: FieldEngine> CircuitLayer<C> {
             *o += coef;
         }
 
+        let custom_gates = self.custom_gates();
         for gate in &self.uni {
             let i0 = &self.input_vals[gate.i_ids[0]];
             let o = &mut res[gate.o_id];
             match gate.gate_type {
-                12345 => {
-                    // pow5
+                POW5_GATE_TYPE => {
                     let i0_2 = i0.square();
                     let i0_4 = i0_2.square();
                     let i0_5 = i0_4 * i0;
                     *o += i0_5 * gate.coef;
                 }
-                12346 => {
-                    // pow1
+                POW1_GATE_TYPE => {
                     *o += *i0 * gate.coef;
                 }
-                _ => panic!("Unknown gate type: {}", gate.gate_type),
+                gate_type => {
+                    let def = custom_gates.iter().find(|def| def.gate_type == gate_type);
+                    *o += (def.unwrap().eval_circuit)(*i0) * gate.coef;
+                }
             }
         }
     }
 
+    /// The custom gates of the layer, once each, see `register_custom_gate`.
+    ///
+    /// Panics if a univariate gate of the layer is neither built-in nor registered.
+    pub fn custom_gates(&self) -> Vec<CustomGateDef<C>> {
+        let mut custom_gates: Vec<CustomGateDef<C>> = vec![];
+        for gate in &self.uni {
+            if gate.gate_type == POW5_GATE_TYPE
+                || gate.gate_type == POW1_GATE_TYPE
+                || custom_gates
+                    .iter()
+                    .any(|def| def.gate_type == gate.gate_type)
+            {
+                continue;
+            }
+            custom_gates.push(custom_gate(gate.gate_type).unwrap_or_else(|| {
+                panic!(
+                    "Unknown gate type: {}, custom gates are to be registered with \
+                     register_custom_gate",
+                    gate.gate_type
+                )
+            }));
+        }
+        custom_gates
+    }
+
     #[inline]
     pub fn identify_rnd_coefs(&mut self, rnd_coefs: &mut Vec<*mut C::CircuitField>) {
         for gate in &mut self.mul {
@@ -107,6 +145,11 @@ impl<C: FieldEngine> CircuitLayer<C> {
                 rnd_coefs.push(&mut gate.coef);
             }
         }
+        for gate in self.skip_inputs.iter_mut().flat_map(|skip| &mut skip.gates) {
+            if gate.coef_type == CoefType::Random {
+                rnd_coefs.push(&mut gate.coef);
+            }
+        }
     }
 
     #[inline]
@@ -182,11 +225,56 @@ impl<C: FieldEngine> Circuit<C> {
             None
         };
 
-        let (mut circuit, window) = mpi_config.consume_obj_and_create_shared(circuit);
+        let (mut circuit, window) = mpi_config.consume_obj_and_create_shared(circuit).unwrap();
         circuit.pre_process_gkr();
         (circuit, window)
     }
 
+    // The root process loads a witness from a file and shares it with other processes
+    // with shared memory, so that the processes hold a single copy of the witness, each reading
+    // its shard in place, e.g., with `Witness::shard`
+    // Used in the mpi case, ok if mpi_size = 1, but
+    // witness.discard_control_of_shared_mem() and mpi_config.free_shared_mem(window) should be
+    // called once the witness is no longer read
+    pub fn prover_load_shared_witness(
+        filename: &str,
+        mpi_config: &MPIConfig,
+    ) -> (Witness<C>, *mut ompi_win_t) {
+        let witness = if mpi_config.is_root() {
+            let file_bytes = fs::read(filename)
+                .unwrap_or_else(|_| panic!("Failed to read witness file: {filename}"));
+            Some(Witness::<C>::deserialize_from(Cursor::new(file_bytes)).unwrap())
+        } else {
+            None
+        };
+
+        mpi_config.consume_obj_and_create_shared(witness).unwrap()
+    }
+
+    /// Counterpart of `prover_load_witness_file` where the witness is loaded once into shared
+    /// memory by the root, see `prover_load_shared_witness`, rather than by every process. Each
+    /// process packs its shard into its inputs, then the shared memory is released. Every
+    /// process must call it.
+    pub fn prover_load_witness_file_shared(&mut self, filename: &str, mpi_config: &MPIConfig) {
+        let (witness, mut window) = Self::prover_load_shared_witness(filename, mpi_config);
+
+        let private_input_size = 1 << self.log_input_size();
+        assert_eq!(witness.num_private_inputs_per_witness, private_input_size);
+        let scheme = self.sharding_scheme(&witness, mpi_config);
+        assert!(
+            witness.num_witnesses >= scheme.num_witnesses(),
+            "Not enough witness, expected {}, got {}",
+            scheme.num_witnesses(),
+            witness.num_witnesses
+        );
+        self.prover_pack_witness(&witness, mpi_config);
+
+        // the other processes may still read the witness until all are done
+        mpi_config.barrier().unwrap();
+        witness.discard_control_of_shared_mem();
+        mpi_config.free_shared_mem(&mut window).unwrap();
+    }
+
     pub fn load_witness_allow_padding_testing_only(
         &mut self,
         filename: &str,
@@ -202,6 +290,228 @@ impl<C: FieldEngine> Circuit<C> {
         self.load_witness_bytes(&file_bytes, mpi_config, true, false);
     }
 
+    /// Fallible counterpart of `prover_load_witness_file`. Every process must call it; if the
+    /// witness fails to load on any process, all processes return an error instead of blocking
+    /// in a later collective operation.
+    pub fn try_prover_load_witness_file(
+        &mut self,
+        filename: &str,
+        mpi_config: &MPIConfig,
+    ) -> Result<(), ExpErrors> {
+        let witness = fs::read(filename)
+            .map_err(ExpErrors::from)
+            .and_then(|file_bytes| {
+                Witness::<C>::deserialize_from(Cursor::new(file_bytes)).map_err(ExpErrors::from)
+            })
+            .and_then(|witness| {
+                let private_input_size = 1 << self.log_input_size();
+                if witness.num_private_inputs_per_witness != private_input_size {
+                    return Err(ExpErrors::WitnessError(format!(
+                        "{filename}: expected {} private inputs per witness, got {}",
+                        private_input_size, witness.num_private_inputs_per_witness
+                    )));
+                }
+                let desired_number_of_witnesses =
+                    C::get_field_pack_size() * mpi_config.world_size();
+                if witness.num_witnesses < desired_number_of_witnesses {
+                    return Err(ExpErrors::WitnessError(format!(
+                        "{filename}: not enough witness, expected {}, got {}",
+                        desired_number_of_witnesses, witness.num_witnesses
+                    )));
+                }
+                Ok(witness)
+            });
+
+        let witness = mpi_config.sync_result(witness)?;
+        Self::check_witness_shards(&witness, mpi_config)?;
+        self.load_witness(witness, mpi_config, true, false);
+        Ok(())
+    }
+
+    /// Counterpart of `try_prover_load_witness_file` where only the root reads the witness file,
+    /// and scatters the witnesses one SIMD lane at a time: the root reads the witnesses of the
+    /// next lanes while those of the current lane are in flight, and the processes pack those of
+    /// the previous lane into their inputs. Every process must call it.
+    ///
+    /// The root reads up to `DEFAULT_WITNESS_PREFETCH_DEPTH` lanes ahead, see
+    /// `try_prover_stream_witness_file_with_prefetch`.
+    pub fn try_prover_stream_witness_file(
+        &mut self,
+        filename: &str,
+        mpi_config: &MPIConfig,
+    ) -> Result<(), ExpErrors> {
+        self.try_prover_stream_witness_file_with_prefetch(
+            filename,
+            mpi_config,
+            DEFAULT_WITNESS_PREFETCH_DEPTH,
+        )
+    }
+
+    /// Counterpart of `try_prover_stream_witness_file` with the queue depth of the reads of the
+    /// root: a background thread reads the lanes from the disk, at most `prefetch_depth` of them
+    /// ahead of the scatter, so that a disk resident witness is read while the previous lanes are
+    /// in flight and packed. With a depth of 0 the root reads each lane when it is scattered.
+    pub fn try_prover_stream_witness_file_with_prefetch(
+        &mut self,
+        filename: &str,
+        mpi_config: &MPIConfig,
+        prefetch_depth: usize,
+    ) -> Result<(), ExpErrors> {
+        // the root reads the header, the layout of the values is known to all from there on
+        let mut reader = None;
+        let header = if mpi_config.is_root() {
+            File::open(filename)
+                .map_err(ExpErrors::from)
+                .and_then(|file| {
+                    let mut file = BufReader::new(file);
+                    let header = <[usize; 3]>::deserialize_from(&mut file)?;
+                    let _modulus = <[u64; 4]>::deserialize_from(&mut file)?;
+                    let values_start = file.stream_position()?;
+                    reader = Some((file, values_start));
+                    Ok(header)
+                })
+        } else {
+            Ok([0; 3])
+        };
+        let mut header = mpi_config.sync_result(header)?;
+        mpi_config.root_broadcast_f(&mut header)?;
+        let [num_witnesses, num_private_inputs, num_public_inputs] = header;
+
+        let scheme = ShardingScheme::new::<C>(
+            mpi_config.world_size(),
+            1 << self.log_input_size(),
+            num_public_inputs,
+        );
+        if num_private_inputs != scheme.num_private_inputs {
+            return Err(ExpErrors::WitnessError(format!(
+                "{filename}: expected {} private inputs per witness, got {num_private_inputs}",
+                scheme.num_private_inputs
+            )));
+        }
+        if num_witnesses < scheme.num_witnesses() {
+            return Err(ExpErrors::WitnessError(format!(
+                "{filename}: not enough witness, expected {}, got {num_witnesses}",
+                scheme.num_witnesses()
+            )));
+        }
+
+        // the witnesses of a lane, those of the processes one after the other
+        let elem_size = C::CircuitField::CANONICAL_SIZE;
+        let read_lane = move |file: &mut BufReader<File>, values_start: u64, lane: usize| {
+            let mut values = Vec::with_capacity(scheme.world_size * scheme.witness_len());
+            let mut bytes = vec![0u8; scheme.witness_len() * elem_size];
+            for rank in 0..scheme.world_size {
+                let first = scheme.global_index(&ShardLocation {
+                    rank,
+                    lane,
+                    kind: InputKind::Private,
+                    index: 0,
+                });
+                file.seek(SeekFrom::Start(values_start + (first * elem_size) as u64))?;
+                file.read_exact(&mut bytes)?;
+                for elem in bytes.chunks(elem_size) {
+                    values.push(
+                        C::CircuitField::try_from_canonical_bytes(elem)
+                            .ok_or(ExpErrors::SerdeError(SerdeError::DeserializeError))?,
+                    );
+                }
+            }
+            Ok::<_, ExpErrors>(values)
+        };
+
+        // a failed read is reported after the stream, the other processes being in it
+        let mut read_error = None;
+        let mut lanes = vec![vec![]; scheme.pack_size];
+        thread::scope(|scope| {
+            let prefetched = match reader.take() {
+                Some((mut file, values_start)) if prefetch_depth > 0 => {
+                    let (sender, receiver) = mpsc::sync_channel(prefetch_depth);
+                    scope.spawn(move || {
+                        for lane in 0..scheme.pack_size {
+                            let values = read_lane(&mut file, values_start, lane);
+                            // the receiver is only dropped early if the stream failed
+                            if sender.send(values).is_err() {
+                                break;
+                            }
+                        }
+                    });
+                    Some(receiver)
+                }
+                on_demand => {
+                    reader = on_demand;
+                    None
+                }
+            };
+
+            mpi_config.scatter_vec_streaming(
+                scheme.pack_size,
+                scheme.witness_len(),
+                |lane| {
+                    let values = match (&prefetched, reader.as_mut()) {
+                        (Some(receiver), _) => receiver
+                            .recv()
+                            .expect("The witness prefetch thread exited early"),
+                        (None, Some((file, values_start))) => read_lane(file, *values_start, lane),
+                        (None, None) => unreachable!("Only the root produces the lanes"),
+                    };
+                    values.unwrap_or_else(|e| {
+                        read_error.get_or_insert(e);
+                        vec![C::CircuitField::ZERO; scheme.world_size * scheme.witness_len()]
+                    })
+                },
+                |lane, witness| lanes[lane] = witness.to_vec(),
+            )
+        })?;
+        mpi_config.sync_result(read_error.map_or(Ok(()), Err))?;
+
+        let pack = |offset: usize| {
+            C::SimdCircuitField::pack(&lanes.iter().map(|lane| lane[offset]).collect::<Vec<_>>())
+        };
+        self.layers[0].input_vals = (0..scheme.num_private_inputs).map(pack).collect();
+        self.public_input = (0..scheme.num_public_inputs)
+            .map(|i| pack(scheme.num_private_inputs + i))
+            .collect();
+        Ok(())
+    }
+
+    /// Check that every process holds the witness shard the root would assign it, so that a
+    /// process reading another witness file fails before proving, with its rank in the error.
+    ///
+    /// The root broadcasts the hashes of the shards of all processes, and every process compares
+    /// the hash of its own shard against its entry.
+    pub fn check_witness_shards(
+        witness: &Witness<C>,
+        mpi_config: &MPIConfig,
+    ) -> Result<(), ExpErrors> {
+        if mpi_config.is_single_process() {
+            return Ok(());
+        }
+
+        let rank = mpi_config.world_rank();
+        let mut manifest = if mpi_config.is_root() {
+            (0..mpi_config.world_size())
+                .flat_map(|i| witness.shard_hash(i))
+                .collect()
+        } else {
+            vec![]
+        };
+        mpi_config.root_broadcast_bytes(&mut manifest)?;
+
+        let to_hex = |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        let expected = &manifest[rank * 32..(rank + 1) * 32];
+        let local = witness.shard_hash(rank);
+        let result = if local[..] == *expected {
+            Ok(())
+        } else {
+            Err(ExpErrors::WitnessError(format!(
+                "witness shard hash {} does not match {} in the manifest of the root",
+                to_hex(&local),
+                to_hex(expected)
+            )))
+        };
+        mpi_config.sync_result(result)
+    }
+
     pub fn verifier_load_witness_file(&mut self, filename: &str, mpi_config: &MPIConfig) {
         let file_bytes = fs::read(filename)
             .unwrap_or_else(|_| panic!("Failed to read witness file: {filename}"));
@@ -216,8 +526,17 @@ impl<C: FieldEngine> Circuit<C> {
         allow_padding_for_testing: bool, // TODO: Consider remove this
     ) {
         let cursor = Cursor::new(file_bytes);
-        let mut witness = Witness::<C>::deserialize_from(cursor).unwrap();
+        let witness = Witness::<C>::deserialize_from(cursor).unwrap();
+        self.load_witness(witness, mpi_config, is_prover, allow_padding_for_testing);
+    }
 
+    pub fn load_witness(
+        &mut self,
+        mut witness: Witness<C>,
+        mpi_config: &MPIConfig,
+        is_prover: bool,
+        allow_padding_for_testing: bool,
+    ) {
         // sizes for a single piece of witness
         let private_input_size = 1 << self.log_input_size();
         let public_input_size = witness.num_public_inputs_per_witness;
@@ -271,57 +590,44 @@ impl<C: FieldEngine> Circuit<C> {
     }
 
     pub fn prover_process_witness(&mut self, witness: Witness<C>, mpi_config: &MPIConfig) {
+        self.prover_pack_witness(&witness, mpi_config);
+    }
+
+    /// Pack the shard of this process of `witness` into the inputs, reading the witness in place
+    fn prover_pack_witness(&mut self, witness: &Witness<C>, mpi_config: &MPIConfig) {
         let rank = mpi_config.world_rank();
-        let private_input_size = 1 << self.log_input_size();
-        let public_input_size = witness.num_public_inputs_per_witness;
-        let total_size =
-            witness.num_private_inputs_per_witness + witness.num_public_inputs_per_witness;
-        let input = &witness.values[rank * total_size * C::get_field_pack_size()
-            ..(rank + 1) * total_size * C::get_field_pack_size()];
-        let private_input = &mut self.layers[0].input_vals;
-        let public_input = &mut self.public_input;
-
-        private_input.clear();
-        public_input.clear();
-
-        for i in 0..private_input_size {
-            let mut private_wit_i = vec![];
-            for j in 0..C::get_field_pack_size() {
-                private_wit_i.push(input[j * total_size + i]);
-            }
-            private_input.push(C::SimdCircuitField::pack(&private_wit_i));
-        }
+        let scheme = self.sharding_scheme(witness, mpi_config);
 
-        for i in 0..public_input_size {
-            let mut public_wit_i = vec![];
-            for j in 0..C::get_field_pack_size() {
-                public_wit_i.push(input[j * total_size + private_input_size + i]);
-            }
-            public_input.push(C::SimdCircuitField::pack(&public_wit_i));
-        }
+        self.layers[0].input_vals = (0..scheme.num_private_inputs)
+            .map(|i| scheme.pack_input(witness, rank, InputKind::Private, i))
+            .collect();
+        self.public_input = (0..scheme.num_public_inputs)
+            .map(|i| scheme.pack_input(witness, rank, InputKind::Public, i))
+            .collect();
     }
 
     pub fn verifier_process_witness(&mut self, witness: Witness<C>, mpi_config: &MPIConfig) {
-        let private_input_size = 1 << self.log_input_size();
-        let public_input_size = witness.num_public_inputs_per_witness;
-        let total_size =
-            witness.num_private_inputs_per_witness + witness.num_public_inputs_per_witness;
-
-        let public_input = &mut self.public_input;
-        public_input.clear();
-
-        for i_rank in 0..mpi_config.world_size() {
-            let input = &witness.values[i_rank * total_size * C::get_field_pack_size()
-                ..(i_rank + 1) * total_size * C::get_field_pack_size()];
+        let scheme = self.sharding_scheme(&witness, mpi_config);
+        let witness = &witness;
+
+        // NOTE: a virtual party has the public inputs of the process it mirrors
+        self.public_input = (0..mpi_config.n_parties())
+            .flat_map(|party| {
+                let i_rank = mpi_config.mirror_rank(party);
+                (0..scheme.num_public_inputs)
+                    .map(move |i| scheme.pack_input(witness, i_rank, InputKind::Public, i))
+            })
+            .collect();
+    }
 
-            for i in 0..public_input_size {
-                let mut public_wit_i = vec![];
-                for j in 0..C::get_field_pack_size() {
-                    public_wit_i.push(input[j * total_size + private_input_size + i]);
-                }
-                public_input.push(C::SimdCircuitField::pack(&public_wit_i));
-            }
-        }
+    /// The layout of `witness` over the processes of `mpi_config`
+    #[inline]
+    pub fn sharding_scheme(&self, witness: &Witness<C>, mpi_config: &MPIConfig) -> ShardingScheme {
+        ShardingScheme::new::<C>(
+            mpi_config.world_size(),
+            1 << self.log_input_size(),
+            witness.num_public_inputs_per_witness,
+        )
     }
 }
 
@@ -330,6 +636,24 @@ impl<C: FieldEngine> Circuit<C> {
         self.layers[0].input_var_num
     }
 
+    /// The number of gates of all the layers, the gates of the skip inputs included
+    pub fn num_gates(&self) -> usize {
+        self.layers
+            .iter()
+            .map(|layer| {
+                layer.mul.len()
+                    + layer.add.len()
+                    + layer.const_.len()
+                    + layer.uni.len()
+                    + layer
+                        .skip_inputs
+                        .iter()
+                        .map(|skip| skip.gates.len())
+                        .sum::<usize>()
+            })
+            .sum()
+    }
+
     // Build a random mock circuit with binary inputs
     pub fn set_random_input_for_test(&mut self) {
         let mut rng = test_rng();
@@ -341,10 +665,9 @@ impl<C: FieldEngine> Circuit<C> {
     pub fn evaluate(&mut self) {
         for i in 0..self.layers.len() - 1 {
             let (layer_p_1, layer_p_2) = self.layers.split_at_mut(i + 1);
-            layer_p_1
-                .last()
-                .unwrap()
-                .evaluate(&mut layer_p_2[0].input_vals, &self.public_input);
+            let (layer, layers_below) = layer_p_1.split_last().unwrap();
+            layer.evaluate(&mut layer_p_2[0].input_vals, &self.public_input);
+            layer.evaluate_skip_inputs(layers_below, &mut layer_p_2[0].input_vals);
             log::trace!(
                 "layer {} evaluated - First 10 values: {:?}",
                 i,
@@ -356,10 +679,9 @@ impl<C: FieldEngine> Circuit<C> {
             );
         }
         let mut output = vec![];
-        self.layers
-            .last()
-            .unwrap()
-            .evaluate(&mut output, &self.public_input);
+        let (layer, layers_below) = self.layers.split_last().unwrap();
+        layer.evaluate(&mut output, &self.public_input);
+        layer.evaluate_skip_inputs(layers_below, &mut output);
         self.layers.last_mut().unwrap().output_vals = output;
 
         log::trace!("output evaluated");
@@ -404,6 +726,22 @@ impl<C: FieldEngine> Circuit<C> {
             .for_each(|(&r, sr)| unsafe { *r = *sr });
     }
 
+    /// The current values of the random coefficients, in the order they are filled in.
+    pub fn rnd_coef_values(&self) -> Vec<C::CircuitField> {
+        assert!(self.rnd_coefs_identified);
+        self.rnd_coefs.iter().map(|&r| unsafe { *r }).collect()
+    }
+
+    /// Set the random coefficients to values read with `rnd_coef_values`.
+    pub fn set_rnd_coefs(&mut self, values: &[C::CircuitField]) {
+        assert!(self.rnd_coefs_identified);
+        assert_eq!(self.rnd_coefs.len(), values.len());
+        self.rnd_coefs
+            .iter()
+            .zip(values.iter())
+            .for_each(|(&r, v)| unsafe { *r = *v });
+    }
+
     pub fn identify_structure_info(&mut self) {
         for layer in &mut self.layers {
             layer.identify_structure_info();
@@ -435,5 +773,9 @@ impl<C: FieldEngine> Circuit<C> {
         input_relay_layer.structure_info.skip_sumcheck_phase_two = true;
 
         self.layers.insert(0, input_relay_layer);
+        self.layers
+            .iter_mut()
+            .flat_map(|layer| &mut layer.skip_inputs)
+            .for_each(|skip| skip.layer += 1);
     }
 }
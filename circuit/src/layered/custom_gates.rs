@@ -0,0 +1,124 @@
+//! Univariate gates defined outside this crate, e.g., by a library of domain specific gates.
+//!
+//! A custom gate applies a polynomial of degree at most `MAX_CUSTOM_GATE_DEGREE` to its single
+//! input, scaled by the gate coefficient, like the built-in pow1 and pow5 gates. Its gates are
+//! the `GateUni`s of a layer whose `gate_type` is the tag of the gate, and are proven and
+//! verified by the GKR square scheme once the gate is registered for the field config with
+//! `register_custom_gate`, on the prover and on the verifier alike. Vanilla GKR proves the
+//! mul, add and const gates only, and ignores the univariate gates altogether.
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use arith::Field;
+use gkr_engine::FieldEngine;
+
+/// The `gate_type` of the built-in pow5 gates, `out += coef * in^5`
+pub const POW5_GATE_TYPE: usize = 12345;
+/// The `gate_type` of the built-in pow1 gates, `out += coef * in`
+pub const POW1_GATE_TYPE: usize = 12346;
+
+/// The highest degree of a custom gate, for the GKR square sumcheck to stay of degree 6
+pub const MAX_CUSTOM_GATE_DEGREE: usize = 5;
+
+/// A univariate gate `out += coef * evaluate(in)`.
+pub trait CustomGate: 'static {
+    /// The tag of the gate in the circuit file, i.e., the `gate_type` of its `GateUni`s
+    const GATE_TYPE: usize;
+
+    /// The degree of `evaluate`, at most `MAX_CUSTOM_GATE_DEGREE`
+    const DEGREE: usize;
+
+    /// A name for the error messages
+    const NAME: &'static str;
+
+    /// The gate on input `x`, before the gate coefficient is applied. It evaluates the circuit on
+    /// the prover, and the gate polynomial at the input claim on the verifier, so it must be the
+    /// same polynomial over every field.
+    fn evaluate<V: Field>(x: V) -> V;
+}
+
+/// A custom gate instantiated over the fields of the config `C`.
+pub struct CustomGateDef<C: FieldEngine> {
+    pub gate_type: usize,
+    pub degree: usize,
+    pub name: &'static str,
+    pub eval_circuit: fn(C::SimdCircuitField) -> C::SimdCircuitField,
+    pub eval_field: fn(C::Field) -> C::Field,
+    pub eval_challenge: fn(C::ChallengeField) -> C::ChallengeField,
+}
+
+// derived, the bounds would be on C instead of its fields
+impl<C: FieldEngine> Clone for CustomGateDef<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: FieldEngine> Copy for CustomGateDef<C> {}
+
+impl<C: FieldEngine> CustomGateDef<C> {
+    pub fn of<G: CustomGate>() -> Self {
+        Self {
+            gate_type: G::GATE_TYPE,
+            degree: G::DEGREE,
+            name: G::NAME,
+            eval_circuit: G::evaluate::<C::SimdCircuitField>,
+            eval_field: G::evaluate::<C::Field>,
+            eval_challenge: G::evaluate::<C::ChallengeField>,
+        }
+    }
+}
+
+type Registry = RwLock<HashMap<(TypeId, usize), Box<dyn Any + Send + Sync>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register the gate `G` for the circuits over the field config `C`. Registering a gate again is
+/// a no-op.
+///
+/// Panics if the tag of `G` is that of a built-in gate or of another registered gate, or if its
+/// degree is above `MAX_CUSTOM_GATE_DEGREE`.
+pub fn register_custom_gate<C: FieldEngine, G: CustomGate>() {
+    assert!(
+        G::GATE_TYPE != POW5_GATE_TYPE && G::GATE_TYPE != POW1_GATE_TYPE,
+        "custom gate {} uses the tag of a built-in gate",
+        G::NAME
+    );
+    assert!(
+        G::DEGREE <= MAX_CUSTOM_GATE_DEGREE,
+        "custom gate {} is of degree {}, above {MAX_CUSTOM_GATE_DEGREE}",
+        G::NAME,
+        G::DEGREE
+    );
+
+    let registered = registry()
+        .write()
+        .unwrap()
+        .entry((TypeId::of::<C>(), G::GATE_TYPE))
+        .or_insert_with(|| Box::new(CustomGateDef::<C>::of::<G>()))
+        .downcast_ref::<CustomGateDef<C>>()
+        .unwrap()
+        .name;
+    assert!(
+        registered == G::NAME,
+        "custom gates {registered} and {} share the tag {}",
+        G::NAME,
+        G::GATE_TYPE
+    );
+}
+
+/// The custom gate registered for `C` under `gate_type`, if any
+pub fn custom_gate<C: FieldEngine>(gate_type: usize) -> Option<CustomGateDef<C>> {
+    registry()
+        .read()
+        .unwrap()
+        .get(&(TypeId::of::<C>(), gate_type))
+        .map(|def| *def.downcast_ref::<CustomGateDef<C>>().unwrap())
+}
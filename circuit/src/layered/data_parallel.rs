@@ -0,0 +1,123 @@
+use gkr_engine::FieldEngine;
+
+use crate::*;
+
+/// Map a wire index of a single copy to its index in the replicated layer.
+///
+/// The copy index occupies the lowest `log_n_copies` variables, so that the copy dimension sits
+/// right next to the SIMD dimension, i.e., the replicated challenge reads
+/// `rz = [r_copy, rz_local]`.
+#[inline(always)]
+pub fn copy_wire_id(local_id: usize, copy_idx: usize, log_n_copies: usize) -> usize {
+    (local_id << log_n_copies) | copy_idx
+}
+
+/// Interleave per-copy values (inputs, public inputs) into the layout expected by a replicated
+/// circuit. All copies must have the same length.
+pub fn interleave_copies<T: Copy>(copies: &[Vec<T>]) -> Vec<T> {
+    assert!(copies.len().is_power_of_two());
+    let log_n_copies = copies.len().trailing_zeros() as usize;
+    let local_len = copies[0].len();
+    assert!(copies.iter().all(|c| c.len() == local_len));
+
+    let mut ret = Vec::with_capacity(local_len * copies.len());
+    for local_id in 0..local_len {
+        for (copy_idx, copy) in copies.iter().enumerate() {
+            debug_assert_eq!(ret.len(), copy_wire_id(local_id, copy_idx, log_n_copies));
+            ret.push(copy[local_id]);
+        }
+    }
+    ret
+}
+
+/// Inverse of `interleave_copies`.
+pub fn deinterleave_copies<T: Copy>(vals: &[T], log_n_copies: usize) -> Vec<Vec<T>> {
+    let n_copies = 1 << log_n_copies;
+    assert_eq!(vals.len() % n_copies, 0);
+    (0..n_copies)
+        .map(|copy_idx| {
+            vals.iter()
+                .skip(copy_idx)
+                .step_by(n_copies)
+                .copied()
+                .collect()
+        })
+        .collect()
+}
+
+#[inline]
+fn replicate_gates<C: FieldEngine, const INPUT_NUM: usize>(
+    gates: &[Gate<C, INPUT_NUM>],
+    log_n_copies: usize,
+) -> Vec<Gate<C, INPUT_NUM>> {
+    let n_copies = 1 << log_n_copies;
+    let mut ret = Vec::with_capacity(gates.len() * n_copies);
+    for gate in gates {
+        for copy_idx in 0..n_copies {
+            let mut g = *gate;
+            g.i_ids
+                .iter_mut()
+                .for_each(|id| *id = copy_wire_id(*id, copy_idx, log_n_copies));
+            g.o_id = copy_wire_id(g.o_id, copy_idx, log_n_copies);
+            if let CoefType::PublicInput(input_idx) = g.coef_type {
+                g.coef_type =
+                    CoefType::PublicInput(copy_wire_id(input_idx, copy_idx, log_n_copies));
+            }
+            ret.push(g);
+        }
+    }
+    ret
+}
+
+impl<C: FieldEngine> CircuitLayer<C> {
+    /// Replicate the wiring of this layer over `2^log_n_copies` independent copies.
+    /// Values are not carried over.
+    pub fn replicate(&self, log_n_copies: usize) -> Self {
+        Self {
+            input_var_num: self.input_var_num + log_n_copies,
+            output_var_num: self.output_var_num + log_n_copies,
+            input_vals: vec![],
+            output_vals: vec![],
+            mul: replicate_gates(&self.mul, log_n_copies),
+            add: replicate_gates(&self.add, log_n_copies),
+            const_: replicate_gates(&self.const_, log_n_copies),
+            uni: replicate_gates(&self.uni, log_n_copies),
+            skip_inputs: self
+                .skip_inputs
+                .iter()
+                .map(|skip| SkipInputs {
+                    layer: skip.layer,
+                    gates: replicate_gates(&skip.gates, log_n_copies),
+                })
+                .collect(),
+            structure_info: self.structure_info.clone(),
+        }
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// Build a data-parallel circuit that evaluates `2^log_n_copies` copies of `self` with a
+    /// single GKR run.
+    ///
+    /// The copy index is placed at the lowest variables of every layer, hence
+    /// - inputs and public inputs of the copies are interleaved, see `interleave_copies`,
+    /// - the first `log_n_copies` entries of `rz` in an `ExpanderSingleVarChallenge` form the copy
+    ///   challenge, see `ExpanderSingleVarChallenge::split_copy_vars`,
+    /// - the first `expected_num_output_zeros` outputs of every copy stay a prefix of the output.
+    ///
+    /// Random coefficients are sampled independently for each copy. The result should go through
+    /// `pre_process_gkr` like any freshly loaded circuit.
+    pub fn replicate(&self, log_n_copies: usize) -> Self {
+        Self {
+            layers: self
+                .layers
+                .iter()
+                .map(|layer| layer.replicate(log_n_copies))
+                .collect(),
+            public_input: vec![],
+            expected_num_output_zeros: self.expected_num_output_zeros << log_n_copies,
+            rnd_coefs_identified: false,
+            rnd_coefs: vec![],
+        }
+    }
+}
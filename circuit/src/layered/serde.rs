@@ -2,7 +2,7 @@ use std::io::{Read, Write};
 
 use arith::Field;
 use gkr_engine::FieldEngine;
-use serdes::{ExpSerde, SerdeResult};
+use serdes::{par_deserialize_records, ExpSerde, SerdeError, SerdeResult};
 
 use super::circuit::{Circuit, CircuitLayer, StructureInfo};
 use super::gates::{CoefType, Gate, GateAdd, GateConst, GateMul, GateUni};
@@ -97,6 +97,32 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for Gate<C, INPUT_NUM> {
     }
 }
 
+/// Deserialize a length-prefixed list of gates, i.e., a serialized `Vec<Gate>`, in parallel.
+///
+/// The size of a gate is given by its coefficient tag, so the list is split into chunks with a
+/// cheap scan over the tags, and the gates themselves are deserialized on the rayon thread pool.
+pub(crate) fn par_deserialize_gates<C: FieldEngine, const INPUT_NUM: usize, R: Read>(
+    mut reader: R,
+) -> SerdeResult<Vec<Gate<C, INPUT_NUM>>> {
+    let len = usize::deserialize_from(&mut reader)?;
+
+    let mut buf = vec![];
+    C::CircuitField::ZERO.serialize_into(&mut buf)?;
+    let field_size = buf.len();
+
+    // i_ids, o_id and the coefficient tag
+    let header_size = (INPUT_NUM + 1) * 8 + 1;
+    par_deserialize_records(reader, len, header_size, |header| {
+        let coef_size = match header[header_size - 1] {
+            1 => field_size,
+            2 => 0,
+            3 => 8,
+            _ => return Err(SerdeError::DeserializeError),
+        };
+        Ok(header_size + coef_size)
+    })
+}
+
 impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
     fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
         self.input_var_num.serialize_into(&mut writer)?;
@@ -105,16 +131,18 @@ impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
         self.add.serialize_into(&mut writer)?;
         self.const_.serialize_into(&mut writer)?;
         self.uni.serialize_into(&mut writer)?;
+        self.skip_inputs.serialize_into(&mut writer)?;
         Ok(())
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
         let input_var_num = usize::deserialize_from(&mut reader)?;
         let output_var_num = usize::deserialize_from(&mut reader)?;
-        let mul = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
-        let add = Vec::<GateAdd<C>>::deserialize_from(&mut reader)?;
-        let const_ = Vec::<GateConst<C>>::deserialize_from(&mut reader)?;
+        let mul: Vec<GateMul<C>> = par_deserialize_gates(&mut reader)?;
+        let add: Vec<GateAdd<C>> = par_deserialize_gates(&mut reader)?;
+        let const_: Vec<GateConst<C>> = par_deserialize_gates(&mut reader)?;
         let uni = Vec::<GateUni<C>>::deserialize_from(&mut reader)?;
+        let skip_inputs = Vec::<SkipInputs<C>>::deserialize_from(&mut reader)?;
         Ok(CircuitLayer {
             input_var_num,
             output_var_num,
@@ -126,6 +154,7 @@ impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
             add,
             const_,
             uni,
+            skip_inputs,
 
             structure_info: StructureInfo::default(),
         })
@@ -1,5 +1,7 @@
 use super::circuit::{Circuit, CircuitLayer, StructureInfo};
 use super::gates::{GateAdd, GateConst, GateMul, GateUni};
+use super::skip::SkipInputs;
+use crate::Witness;
 
 use gkr_engine::{FieldEngine, MPISharedMemory};
 
@@ -10,6 +12,12 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
             + self.add.bytes_size()
             + self.const_.bytes_size()
             + self.uni.bytes_size()
+            + self.skip_inputs.len().bytes_size()
+            + self
+                .skip_inputs
+                .iter()
+                .map(|skip| skip.layer.bytes_size() + skip.gates.bytes_size())
+                .sum::<usize>()
     }
 
     fn to_memory(&self, ptr: &mut *mut u8) {
@@ -19,6 +27,11 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
         self.add.to_memory(ptr);
         self.const_.to_memory(ptr);
         self.uni.to_memory(ptr);
+        self.skip_inputs.len().to_memory(ptr);
+        self.skip_inputs.iter().for_each(|skip| {
+            skip.layer.to_memory(ptr);
+            skip.gates.to_memory(ptr);
+        });
     }
 
     fn new_from_memory(ptr: &mut *mut u8) -> Self {
@@ -28,6 +41,13 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
         let add = Vec::<GateAdd<C>>::new_from_memory(ptr);
         let const_ = Vec::<GateConst<C>>::new_from_memory(ptr);
         let uni = Vec::<GateUni<C>>::new_from_memory(ptr);
+        let n_skip_inputs = usize::new_from_memory(ptr);
+        let skip_inputs = (0..n_skip_inputs)
+            .map(|_| SkipInputs {
+                layer: usize::new_from_memory(ptr),
+                gates: Vec::<GateAdd<C>>::new_from_memory(ptr),
+            })
+            .collect();
 
         CircuitLayer {
             input_var_num,
@@ -40,6 +60,7 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
             add,
             const_,
             uni,
+            skip_inputs,
 
             structure_info: StructureInfo::default(),
         }
@@ -50,6 +71,9 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
         self.add.discard_control_of_shared_mem();
         self.const_.discard_control_of_shared_mem();
         self.uni.discard_control_of_shared_mem();
+        self.skip_inputs
+            .into_iter()
+            .for_each(|skip| skip.gates.discard_control_of_shared_mem());
     }
 }
 
@@ -95,3 +119,29 @@ impl<C: FieldEngine> MPISharedMemory for Circuit<C> {
             .for_each(|layer| layer.discard_control_of_shared_mem());
     }
 }
+
+impl<C: FieldEngine> MPISharedMemory for Witness<C> {
+    fn bytes_size(&self) -> usize {
+        8 + 8 + 8 + self.values.bytes_size()
+    }
+
+    fn to_memory(&self, ptr: &mut *mut u8) {
+        self.num_witnesses.to_memory(ptr);
+        self.num_private_inputs_per_witness.to_memory(ptr);
+        self.num_public_inputs_per_witness.to_memory(ptr);
+        self.values.to_memory(ptr);
+    }
+
+    fn new_from_memory(ptr: &mut *mut u8) -> Self {
+        Witness {
+            num_witnesses: usize::new_from_memory(ptr),
+            num_private_inputs_per_witness: usize::new_from_memory(ptr),
+            num_public_inputs_per_witness: usize::new_from_memory(ptr),
+            values: Vec::<C::CircuitField>::new_from_memory(ptr),
+        }
+    }
+
+    fn discard_control_of_shared_mem(self) {
+        self.values.discard_control_of_shared_mem();
+    }
+}
@@ -0,0 +1,212 @@
+//! Layers reading the values of layers further down than the previous one.
+//!
+//! A value fanned out to a layer other than the next one is relayed by the compiler through
+//! every layer in between, one add gate with coefficient one per layer. A layer can instead read
+//! it where it is computed: layer `j` adds to its outputs the add gates of its `skip_inputs`,
+//! each reading the input values of an earlier layer `i < j`, so that the circuit is a DAG of
+//! layers rather than a chain. `Circuit::skip_relays` rewrites the relay chains of a circuit that
+//! way.
+//!
+//! The GKR prover carries the claims on the skip inputs of layer `j` down to layer `i`, where
+//! they are combined with the claims on the inputs of layer `i` into a single one.
+
+use std::collections::HashSet;
+
+use arith::Field;
+use gkr_engine::FieldEngine;
+use serdes::ExpSerde;
+
+use crate::*;
+
+/// Add gates of a layer reading the input values of an earlier layer
+#[derive(Debug, Clone, Default, ExpSerde)]
+pub struct SkipInputs<C: FieldEngine> {
+    /// the layer whose input values the gates read, below the layer of the gates
+    pub layer: usize,
+    /// `i_ids` index the input values of `layer`, `o_id` the outputs of the layer of the gates
+    pub gates: Vec<GateAdd<C>>,
+}
+
+impl<C: FieldEngine> SkipInputs<C> {
+    /// The weight of each of the `n_source_vals` input values of `self.layer` in the weighted
+    /// sum `output_weights` of the outputs the gates add to
+    pub fn source_weights(
+        &self,
+        output_weights: &[C::ChallengeField],
+        n_source_vals: usize,
+    ) -> Vec<C::ChallengeField> {
+        let mut weights = vec![C::ChallengeField::ZERO; n_source_vals];
+        for gate in &self.gates {
+            weights[gate.i_ids[0]] += output_weights[gate.o_id] * gate.coef;
+        }
+        weights
+    }
+}
+
+impl<C: FieldEngine> CircuitLayer<C> {
+    /// Add the gates of the skip inputs to `res`, the outputs of this layer, `layers_below` being
+    /// the layers under this one
+    #[inline]
+    pub fn evaluate_skip_inputs(
+        &self,
+        layers_below: &[CircuitLayer<C>],
+        res: &mut [C::SimdCircuitField],
+    ) {
+        for skip in &self.skip_inputs {
+            let vals = &layers_below[skip.layer].input_vals;
+            for gate in &skip.gates {
+                res[gate.o_id] += vals[gate.i_ids[0]] * gate.coef;
+            }
+        }
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// Whether some layer reads the values of a layer further down than the previous one
+    #[inline]
+    pub fn has_skip_inputs(&self) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| !layer.skip_inputs.is_empty())
+    }
+
+    /// Whether the input values of layer `layer_idx` are read by the skip inputs of a later
+    /// layer, in which case they are kept until that layer is proven
+    #[inline]
+    pub fn is_skip_source(&self, layer_idx: usize) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.skip_inputs.iter().any(|skip| skip.layer == layer_idx))
+    }
+
+    /// Replace the relay chains of the circuit by skip inputs, and return the number of relay
+    /// gates removed.
+    ///
+    /// A relay is an add gate with constant coefficient one, the only gate writing its output.
+    /// A chain of relays whose intermediate values are read by the next relay only is replaced
+    /// by a skip input of the layer of its last relay, reading the value the chain starts from.
+    /// The values in between are left unwritten, i.e., zero.
+    ///
+    /// The prover and the verifier are to rewrite the circuit the same way. A circuit shared
+    /// with `prover_load_circuit` is rewritten on the root before it is shared.
+    pub fn skip_relays(&mut self) -> usize {
+        let n_layers = self.layers.len();
+        let n_vals = |k: usize| match k {
+            k if k < n_layers => 1 << self.layers[k].input_var_num,
+            _ => 1 << self.layers[n_layers - 1].output_var_num,
+        };
+
+        // relay_src[k][b]: the index of the add gate of layer k - 1 relaying a value into b
+        let mut relay_src = vec![vec![]; n_layers + 1];
+        let mut n_readers = (0..n_layers)
+            .map(|k| vec![0usize; n_vals(k)])
+            .collect::<Vec<_>>();
+        for (k, layer) in self.layers.iter().enumerate() {
+            let mut n_writers = vec![0usize; n_vals(k + 1)];
+            let mut relay = vec![None; n_vals(k + 1)];
+            layer.mul.iter().for_each(|g| n_writers[g.o_id] += 1);
+            layer.const_.iter().for_each(|g| n_writers[g.o_id] += 1);
+            layer.uni.iter().for_each(|g| n_writers[g.o_id] += 1);
+            for skip in &layer.skip_inputs {
+                for g in &skip.gates {
+                    n_writers[g.o_id] += 1;
+                    n_readers[skip.layer][g.i_ids[0]] += 1;
+                }
+            }
+            for (idx, g) in layer.add.iter().enumerate() {
+                n_writers[g.o_id] += 1;
+                if g.coef_type == CoefType::Constant && g.coef == C::CircuitField::ONE {
+                    relay[g.o_id] = Some(idx);
+                }
+            }
+            relay_src[k + 1] = relay
+                .into_iter()
+                .zip(&n_writers)
+                .map(|(relay, &n)| relay.filter(|_| n == 1))
+                .collect();
+
+            for g in &layer.mul {
+                n_readers[k][g.i_ids[0]] += 1;
+                n_readers[k][g.i_ids[1]] += 1;
+            }
+            for g in layer.add.iter().chain(&layer.uni) {
+                n_readers[k][g.i_ids[0]] += 1;
+            }
+        }
+
+        // read_by_relay[k][b]: whether value b of layer k is the input of a relay of layer k
+        let mut read_by_relay = (0..n_layers)
+            .map(|k| vec![false; n_vals(k)])
+            .collect::<Vec<_>>();
+        for (k, relays) in relay_src.iter().enumerate().skip(1) {
+            for idx in relays.iter().flatten() {
+                read_by_relay[k - 1][self.layers[k - 1].add[*idx].i_ids[0]] = true;
+            }
+        }
+        // a value relayed from below, and read only by a relay of its layer
+        let is_intermediate = |k: usize, b: usize| {
+            k > 0
+                && k < n_layers
+                && relay_src[k][b].is_some()
+                && n_readers[k][b] == 1
+                && read_by_relay[k][b]
+        };
+
+        let mut removed = HashSet::new();
+        let mut skips = vec![];
+        for k in 1..=n_layers {
+            for c in 0..n_vals(k) {
+                // chains are rewritten from their last relay
+                let Some(last) = relay_src[k][c] else {
+                    continue;
+                };
+                if is_intermediate(k, c) {
+                    continue;
+                }
+
+                let (mut layer, mut val) = (k - 1, self.layers[k - 1].add[last].i_ids[0]);
+                let mut chain = vec![];
+                while is_intermediate(layer, val) {
+                    let relay = relay_src[layer][val].unwrap();
+                    chain.push((layer - 1, relay));
+                    (layer, val) = (layer - 1, self.layers[layer - 1].add[relay].i_ids[0]);
+                }
+                if chain.is_empty() {
+                    continue;
+                }
+
+                removed.insert((k - 1, last));
+                removed.extend(chain);
+                let gate = GateAdd {
+                    i_ids: [val],
+                    ..self.layers[k - 1].add[last]
+                };
+                skips.push((k - 1, layer, gate));
+            }
+        }
+
+        for (k, layer) in self.layers.iter_mut().enumerate() {
+            let mut idx = 0;
+            layer.add.retain(|_| {
+                idx += 1;
+                !removed.contains(&(k, idx - 1))
+            });
+        }
+        for (k, source, gate) in skips {
+            let skip_inputs = &mut self.layers[k].skip_inputs;
+            match skip_inputs.iter_mut().find(|skip| skip.layer == source) {
+                Some(skip) => skip.gates.push(gate),
+                None => skip_inputs.push(SkipInputs {
+                    layer: source,
+                    gates: vec![gate],
+                }),
+            }
+        }
+
+        // the pointers to the random coefficients move with the gates
+        if self.rnd_coefs_identified {
+            self.identify_rnd_coefs();
+        }
+        removed.len()
+    }
+}
@@ -1,11 +1,17 @@
 mod ecc_circuit;
 pub use ecc_circuit::*;
 
+mod embedded;
+pub use embedded::*;
+
 mod layered;
 pub use layered::*;
 
 mod witness;
 pub use witness::*;
 
+mod sharding;
+pub use sharding::*;
+
 mod serde;
 pub use serde::*;
@@ -1,13 +1,13 @@
-use arith::Field;
+use arith::{CanonicalField, Field};
 use gkr_engine::FieldEngine;
-use serdes::{ExpSerde, SerdeResult};
+use serdes::{par_parse_fixed_size, ExpSerde, SerdeResult};
 use std::{
     io::{Read, Write},
     vec,
 };
 
 use super::{Allocation, CoefType, Gate, RecursiveCircuit, Segment, Witness};
-use crate::{GateAdd, GateConst, GateMul, SegmentId};
+use crate::{par_deserialize_gates, GateAdd, GateConst, GateMul, SegmentId};
 pub struct CustomGateWrapper<C: FieldEngine, const INPUT_NUM: usize> {
     pub custom_gate: Gate<C, INPUT_NUM>,
 }
@@ -82,9 +82,9 @@ impl<C: FieldEngine> ExpSerde for Segment<C> {
         assert!(o_len.is_power_of_two());
 
         let child_segs = Vec::<(SegmentId, Vec<Allocation>)>::deserialize_from(&mut reader)?;
-        let gate_muls = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
-        let gate_adds = Vec::<GateAdd<C>>::deserialize_from(&mut reader)?;
-        let gate_consts = Vec::<GateConst<C>>::deserialize_from(&mut reader)?;
+        let gate_muls: Vec<GateMul<C>> = par_deserialize_gates(&mut reader)?;
+        let gate_adds: Vec<GateAdd<C>> = par_deserialize_gates(&mut reader)?;
+        let gate_consts: Vec<GateConst<C>> = par_deserialize_gates(&mut reader)?;
 
         let mut gate_uni = vec![];
         let len = <usize as ExpSerde>::deserialize_from(&mut reader)?;
@@ -104,7 +104,25 @@ impl<C: FieldEngine> ExpSerde for Segment<C> {
     }
 }
 
-const VERSION_NUM: usize = 3914834606642317635; // b'CIRCUIT6'
+/// The version a serialized circuit starts with
+pub const CIRCUIT_VERSION: [u8; 8] = *b"CIRCUIT6";
+
+const VERSION_NUM: usize = usize::from_le_bytes(CIRCUIT_VERSION);
+
+/// Whether `bytes` start with `CIRCUIT_VERSION`, in const contexts
+pub const fn has_circuit_version(bytes: &[u8]) -> bool {
+    if bytes.len() < CIRCUIT_VERSION.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < CIRCUIT_VERSION.len() {
+        if bytes[i] != CIRCUIT_VERSION[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
 
 impl<C: FieldEngine> ExpSerde for RecursiveCircuit<C> {
     fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
@@ -141,8 +159,19 @@ impl<C: FieldEngine> ExpSerde for RecursiveCircuit<C> {
 }
 
 impl<C: FieldEngine> ExpSerde for Witness<C> {
-    fn serialize_into<W: std::io::Write>(&self, mut _writer: W) -> SerdeResult<()> {
-        todo!()
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        self.num_witnesses.serialize_into(&mut writer)?;
+        self.num_private_inputs_per_witness
+            .serialize_into(&mut writer)?;
+        self.num_public_inputs_per_witness
+            .serialize_into(&mut writer)?;
+        C::CircuitField::MODULUS.serialize_into(&mut writer)?;
+
+        // in the canonical encoding `deserialize_from` checks
+        for value in &self.values {
+            writer.write_all(&value.to_canonical_bytes())?;
+        }
+        Ok(())
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
@@ -153,11 +182,14 @@ impl<C: FieldEngine> ExpSerde for Witness<C> {
             <usize as ExpSerde>::deserialize_from(&mut reader).unwrap();
         let _modulus = <[u64; 4]>::deserialize_from(&mut reader).unwrap();
 
-        let mut values = vec![];
-        for _ in 0..num_witnesses * (num_private_inputs_per_witness + num_public_inputs_per_witness)
-        {
-            values.push(C::CircuitField::deserialize_from(&mut reader).unwrap());
-        }
+        // the witnesses come from an external compiler: reject values out of the field instead
+        // of reducing them
+        let values = par_parse_fixed_size(
+            &mut reader,
+            num_witnesses * (num_private_inputs_per_witness + num_public_inputs_per_witness),
+            C::CircuitField::CANONICAL_SIZE,
+            C::CircuitField::try_from_canonical_bytes,
+        )?;
 
         Ok(Self {
             num_witnesses,
@@ -0,0 +1,277 @@
+//! The canonical mapping of the values of a witness file onto the MPI processes and SIMD lanes.
+//!
+//! A witness file holds `num_witnesses` witnesses one after the other, each of its private inputs
+//! followed by its public inputs. Witness `w` is proven by process `w / pack_size`, in SIMD lane
+//! `w % pack_size`, so the witnesses of a process are consecutive and form its shard:
+//!
+//! ```text
+//! global index = (rank * pack_size + lane) * witness_len + offset
+//! offset       = index                      for private input `index`
+//!              = num_private_inputs + index for public input `index`
+//! ```
+//!
+//! Private input `index` of all the lanes of a process is the SIMD element `index` of the inputs
+//! of the circuit on that process, whose lane `lane` is the value of the witness. The global input
+//! polynomial, committed to by the PCS, is indexed LSB-first by the lane, then the local index,
+//! then the rank:
+//!
+//! ```text
+//! input position = lane + pack_size * (index + num_private_inputs * rank)
+//! ```
+//!
+//! Public inputs are not committed, and are packed the same way into the public inputs of the
+//! circuit, those of all processes one after the other on the verifier.
+//!
+//! When `world_size` is not a power of two, the proof is over `world_size.next_power_of_two()`
+//! parties, the virtual party `rank + n_parties / 2` proving the shard of process `rank` again: its
+//! positions in the global input polynomial and in the public inputs of the verifier hold the
+//! values of that shard.
+
+use arith::SimdField;
+use gkr_engine::{ExpErrors, FieldEngine};
+
+use crate::Witness;
+
+/// Whether a witness value is a private or a public input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Private,
+    Public,
+}
+
+/// Where a value of the witness file is proven
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardLocation {
+    pub rank: usize,
+    pub lane: usize,
+    pub kind: InputKind,
+    /// the index of the SIMD element among the private or the public inputs of the process
+    pub index: usize,
+}
+
+/// The layout of a witness file over `world_size` processes of `pack_size` SIMD lanes, see the
+/// module documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardingScheme {
+    pub world_size: usize,
+    pub pack_size: usize,
+    /// the number of private inputs of a witness, that of the inputs of the circuit
+    pub num_private_inputs: usize,
+    pub num_public_inputs: usize,
+}
+
+impl ShardingScheme {
+    /// The scheme of the witnesses over the fields of `C`
+    pub fn new<C: FieldEngine>(
+        world_size: usize,
+        num_private_inputs: usize,
+        num_public_inputs: usize,
+    ) -> Self {
+        assert!(world_size > 0);
+        assert!(num_private_inputs.is_power_of_two());
+        Self {
+            world_size,
+            pack_size: C::SimdCircuitField::PACK_SIZE,
+            num_private_inputs,
+            num_public_inputs,
+        }
+    }
+
+    /// The number of values of a witness
+    #[inline]
+    pub fn witness_len(&self) -> usize {
+        self.num_private_inputs + self.num_public_inputs
+    }
+
+    /// The number of witnesses proven, one per lane of each process
+    #[inline]
+    pub fn num_witnesses(&self) -> usize {
+        self.world_size * self.pack_size
+    }
+
+    /// The number of values of the shard of a process
+    #[inline]
+    pub fn shard_len(&self) -> usize {
+        self.pack_size * self.witness_len()
+    }
+
+    /// The number of values of the witnesses proven
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.world_size * self.shard_len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Where the value at `global_index` of the witness file is proven
+    pub fn locate(&self, global_index: usize) -> ShardLocation {
+        assert!(global_index < self.len(), "index out of the witnesses");
+        let (witness, offset) = (
+            global_index / self.witness_len(),
+            global_index % self.witness_len(),
+        );
+        let (kind, index) = if offset < self.num_private_inputs {
+            (InputKind::Private, offset)
+        } else {
+            (InputKind::Public, offset - self.num_private_inputs)
+        };
+        ShardLocation {
+            rank: witness / self.pack_size,
+            lane: witness % self.pack_size,
+            kind,
+            index,
+        }
+    }
+
+    /// The index in the witness file of the value proven at `location`, inverse of `locate`
+    pub fn global_index(&self, location: &ShardLocation) -> usize {
+        let offset = match location.kind {
+            InputKind::Private => location.index,
+            InputKind::Public => self.num_private_inputs + location.index,
+        };
+        (location.rank * self.pack_size + location.lane) * self.witness_len() + offset
+    }
+
+    /// The index of the value proven at `location` in the shard of its process
+    #[inline]
+    pub fn shard_index(&self, location: &ShardLocation) -> usize {
+        self.global_index(location) - location.rank * self.shard_len()
+    }
+
+    /// The position of a private input in the hypercube of the global input polynomial, None for
+    /// a public input
+    pub fn input_position(&self, location: &ShardLocation) -> Option<usize> {
+        match location.kind {
+            InputKind::Private => Some(
+                location.lane
+                    + self.pack_size * (location.index + self.num_private_inputs * location.rank),
+            ),
+            InputKind::Public => None,
+        }
+    }
+
+    /// The private input at `position` of the global input polynomial, inverse of
+    /// `input_position`
+    pub fn locate_input_position(&self, position: usize) -> ShardLocation {
+        assert!(
+            position < self.num_witnesses() * self.num_private_inputs,
+            "position out of the input polynomial"
+        );
+        let element = position / self.pack_size;
+        ShardLocation {
+            rank: element / self.num_private_inputs,
+            lane: position % self.pack_size,
+            kind: InputKind::Private,
+            index: element % self.num_private_inputs,
+        }
+    }
+
+    /// The SIMD element `index` of the `kind` inputs of process `rank`, packing the values of
+    /// `witness` for all its lanes
+    pub fn pack_input<C: FieldEngine>(
+        &self,
+        witness: &Witness<C>,
+        rank: usize,
+        kind: InputKind,
+        index: usize,
+    ) -> C::SimdCircuitField {
+        let lanes = (0..self.pack_size)
+            .map(|lane| {
+                witness.values[self.global_index(&ShardLocation {
+                    rank,
+                    lane,
+                    kind,
+                    index,
+                })]
+            })
+            .collect::<Vec<_>>();
+        C::SimdCircuitField::pack(&lanes)
+    }
+
+    /// Check that `witness` fits the scheme: the inputs of a witness, and at least one witness
+    /// per lane of each process. Additional witnesses are dropped on loading.
+    pub fn validate<C: FieldEngine>(&self, witness: &Witness<C>) -> Result<(), ExpErrors> {
+        let check = |what: &str, expected: usize, actual: usize| {
+            if expected == actual {
+                Ok(())
+            } else {
+                Err(ExpErrors::WitnessError(format!(
+                    "expected {expected} {what}, got {actual}"
+                )))
+            }
+        };
+
+        check("SIMD lanes", self.pack_size, C::SimdCircuitField::PACK_SIZE)?;
+        check(
+            "private inputs per witness",
+            self.num_private_inputs,
+            witness.num_private_inputs_per_witness,
+        )?;
+        check(
+            "public inputs per witness",
+            self.num_public_inputs,
+            witness.num_public_inputs_per_witness,
+        )?;
+        check(
+            "values",
+            witness.num_witnesses * self.witness_len(),
+            witness.values.len(),
+        )?;
+        if witness.num_witnesses < self.num_witnesses() {
+            return Err(ExpErrors::WitnessError(format!(
+                "expected at least {} witnesses, {} lanes of {} processes, got {}",
+                self.num_witnesses(),
+                self.pack_size,
+                self.world_size,
+                witness.num_witnesses
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that `private_inputs` and `public_inputs`, the inputs of the circuit on process
+    /// `rank`, are the values of `witness` the scheme assigns to it. The error names the first
+    /// value out of place.
+    pub fn validate_shard<C: FieldEngine>(
+        &self,
+        witness: &Witness<C>,
+        rank: usize,
+        private_inputs: &[C::SimdCircuitField],
+        public_inputs: &[C::SimdCircuitField],
+    ) -> Result<(), ExpErrors> {
+        self.validate(witness)?;
+        for (kind, inputs, expected_len) in [
+            (InputKind::Private, private_inputs, self.num_private_inputs),
+            (InputKind::Public, public_inputs, self.num_public_inputs),
+        ] {
+            if inputs.len() != expected_len {
+                return Err(ExpErrors::WitnessError(format!(
+                    "rank {rank}: expected {expected_len} {kind:?} inputs, got {}",
+                    inputs.len()
+                )));
+            }
+
+            for (index, input) in inputs.iter().enumerate() {
+                for (lane, value) in input.unpack().into_iter().enumerate() {
+                    let location = ShardLocation {
+                        rank,
+                        lane,
+                        kind,
+                        index,
+                    };
+                    let global_index = self.global_index(&location);
+                    if value != witness.values[global_index] {
+                        return Err(ExpErrors::WitnessError(format!(
+                            "rank {rank}: {kind:?} input {index} in lane {lane} is not value \
+                             {global_index} of the witness"
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
 use gkr_engine::FieldEngine;
+use gkr_hashers::{FiatShamirHasher, SHA256hasher};
+use serdes::ExpSerde;
 
 // A direct copy of the witness struct from ecc
 #[derive(Debug, Clone)]
@@ -8,3 +10,24 @@ pub struct Witness<C: FieldEngine> {
     pub num_public_inputs_per_witness: usize,
     pub values: Vec<C::CircuitField>,
 }
+
+impl<C: FieldEngine> Witness<C> {
+    /// The values of the witnesses packed into the inputs of MPI process `rank`.
+    pub fn shard(&self, rank: usize) -> &[C::CircuitField] {
+        let shard_size = (self.num_private_inputs_per_witness + self.num_public_inputs_per_witness)
+            * C::get_field_pack_size();
+        &self.values[rank * shard_size..(rank + 1) * shard_size]
+    }
+
+    /// SHA256 of the serialized shard of MPI process `rank`.
+    pub fn shard_hash(&self, rank: usize) -> [u8; 32] {
+        let mut bytes = vec![];
+        for v in self.shard(rank) {
+            v.serialize_into(&mut bytes).unwrap();
+        }
+
+        let mut hash = [0u8; 32];
+        SHA256hasher.hash(&mut hash, &bytes);
+        hash
+    }
+}
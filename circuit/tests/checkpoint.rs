@@ -0,0 +1,69 @@
+use arith::Field;
+use ark_std::test_rng;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul, LayerRetention};
+use gkr_engine::{FieldEngine, M31x16Config};
+
+type C = M31x16Config;
+type F = <C as FieldEngine>::CircuitField;
+type SimdF = <C as FieldEngine>::SimdCircuitField;
+
+// every layer computes out[0] = in[0] * in[1], out[1] = in[0] + in[1]
+fn chain_circuit(layer_num: usize) -> Circuit<C> {
+    let mut layer = CircuitLayer::<C> {
+        input_var_num: 1,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    layer.mul.push(GateMul {
+        i_ids: [0, 1],
+        o_id: 0,
+        coef: F::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 0,
+    });
+    for i in 0..2 {
+        layer.add.push(GateAdd {
+            i_ids: [i],
+            o_id: 1,
+            coef: F::ONE,
+            coef_type: CoefType::Constant,
+            gate_type: 1,
+        });
+    }
+
+    Circuit {
+        layers: vec![layer; layer_num],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_checkpointed_layers_are_restored() {
+    let mut rng = test_rng();
+    let layer_num = 7;
+
+    let mut full = chain_circuit(layer_num);
+    full.layers[0].input_vals = (0..2).map(|_| SimdF::random_unsafe(&mut rng)).collect();
+    let mut checkpointed = full.clone();
+
+    full.evaluate();
+
+    let retention = LayerRetention::Checkpoint { interval: 3 };
+    checkpointed.evaluate_with_retention(retention);
+    assert_eq!(
+        checkpointed.layers.last().unwrap().output_vals,
+        full.layers.last().unwrap().output_vals
+    );
+    for (i, layer) in checkpointed.layers.iter().enumerate() {
+        assert_eq!(layer.input_vals.is_empty(), i % 3 != 0);
+    }
+
+    // same access pattern as the GKR prover
+    for i in (0..layer_num).rev() {
+        checkpointed.restore_layer_vals(i);
+        assert_eq!(checkpointed.layers[i].input_vals, full.layers[i].input_vals);
+        checkpointed.release_layer_vals(i, retention);
+    }
+    assert!(!checkpointed.layers[0].input_vals.is_empty());
+    assert!(checkpointed.layers[1].input_vals.is_empty());
+}
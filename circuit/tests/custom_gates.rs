@@ -0,0 +1,119 @@
+use arith::Field;
+use ark_std::test_rng;
+use circuit::{
+    custom_gate, register_custom_gate, Circuit, CircuitLayer, CoefType, CustomGate, GateUni,
+    POW5_GATE_TYPE,
+};
+use gkr_engine::{FieldEngine, M31x16Config};
+
+type C = M31x16Config;
+type F = <C as FieldEngine>::CircuitField;
+type SimdF = <C as FieldEngine>::SimdCircuitField;
+
+// x^3 + x
+struct CubePlusSelf;
+
+impl CustomGate for CubePlusSelf {
+    const GATE_TYPE: usize = 20001;
+    const DEGREE: usize = 3;
+    const NAME: &'static str = "cube plus self";
+
+    fn evaluate<V: Field>(x: V) -> V {
+        x.square() * x + x
+    }
+}
+
+struct Pow5Impostor;
+
+impl CustomGate for Pow5Impostor {
+    const GATE_TYPE: usize = POW5_GATE_TYPE;
+    const DEGREE: usize = 5;
+    const NAME: &'static str = "pow5 impostor";
+
+    fn evaluate<V: Field>(x: V) -> V {
+        x.exp(5)
+    }
+}
+
+struct TagThief;
+
+impl CustomGate for TagThief {
+    const GATE_TYPE: usize = CubePlusSelf::GATE_TYPE;
+    const DEGREE: usize = 1;
+    const NAME: &'static str = "tag thief";
+
+    fn evaluate<V: Field>(x: V) -> V {
+        x
+    }
+}
+
+// out[0] = 2 * (in[0]^3 + in[0]), out[1] = in[1]^3 + in[1]
+fn custom_gate_circuit() -> Circuit<C> {
+    let mut layer = CircuitLayer::<C> {
+        input_var_num: 1,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    for (i, coef) in [(0, F::from(2)), (1, F::ONE)] {
+        layer.uni.push(GateUni {
+            i_ids: [i],
+            o_id: i,
+            coef,
+            coef_type: CoefType::Constant,
+            gate_type: CubePlusSelf::GATE_TYPE,
+        });
+    }
+
+    Circuit {
+        layers: vec![layer],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_custom_gate_evaluation() {
+    register_custom_gate::<C, CubePlusSelf>();
+    // registering again is a no-op
+    register_custom_gate::<C, CubePlusSelf>();
+    assert_eq!(custom_gate::<C>(CubePlusSelf::GATE_TYPE).unwrap().degree, 3);
+
+    let mut rng = test_rng();
+    let mut circuit = custom_gate_circuit();
+    let inputs = (0..2)
+        .map(|_| SimdF::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    circuit.layers[0].input_vals = inputs.clone();
+    circuit.evaluate();
+
+    let cube_plus_self = |x: SimdF| x.square() * x + x;
+    assert_eq!(
+        circuit.layers[0].output_vals,
+        vec![
+            cube_plus_self(inputs[0]) * F::from(2),
+            cube_plus_self(inputs[1])
+        ]
+    );
+    assert_eq!(circuit.layers[0].custom_gates().len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Unknown gate type")]
+fn test_unregistered_custom_gate() {
+    let mut circuit = custom_gate_circuit();
+    circuit.layers[0].uni[0].gate_type = 20002;
+    circuit.layers[0].input_vals = vec![SimdF::ONE; 2];
+    circuit.evaluate();
+}
+
+#[test]
+#[should_panic(expected = "tag of a built-in gate")]
+fn test_custom_gate_with_builtin_tag() {
+    register_custom_gate::<C, Pow5Impostor>();
+}
+
+#[test]
+#[should_panic(expected = "share the tag")]
+fn test_custom_gates_sharing_a_tag() {
+    register_custom_gate::<C, CubePlusSelf>();
+    register_custom_gate::<C, TagThief>();
+}
@@ -0,0 +1,82 @@
+use arith::Field;
+use ark_std::test_rng;
+use circuit::{
+    deinterleave_copies, interleave_copies, Circuit, CircuitLayer, CoefType, GateAdd, GateConst,
+    GateMul,
+};
+use gkr_engine::{FieldEngine, M31x16Config};
+
+type C = M31x16Config;
+type F = <C as FieldEngine>::CircuitField;
+type SimdF = <C as FieldEngine>::SimdCircuitField;
+
+// out[0] = in[0] * in[1], out[1] = in[2] + 2 * in[3] + public_input[1]
+fn sub_circuit() -> Circuit<C> {
+    let mut layer = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    layer.mul.push(GateMul {
+        i_ids: [0, 1],
+        o_id: 0,
+        coef: F::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 0,
+    });
+    for (i, coef) in [(2, F::ONE), (3, F::from(2u32))] {
+        layer.add.push(GateAdd {
+            i_ids: [i],
+            o_id: 1,
+            coef,
+            coef_type: CoefType::Constant,
+            gate_type: 1,
+        });
+    }
+    layer.const_.push(GateConst {
+        i_ids: [],
+        o_id: 1,
+        coef: F::ZERO,
+        coef_type: CoefType::PublicInput(1),
+        gate_type: 2,
+    });
+
+    Circuit {
+        layers: vec![layer],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_replicated_circuit_matches_copies() {
+    let mut rng = test_rng();
+    let log_n_copies = 2;
+    let n_copies = 1 << log_n_copies;
+
+    let base = sub_circuit();
+    let mut replicated = base.replicate(log_n_copies);
+    assert_eq!(
+        replicated.log_input_size(),
+        base.log_input_size() + log_n_copies
+    );
+
+    let inputs: Vec<Vec<SimdF>> = (0..n_copies)
+        .map(|_| (0..4).map(|_| SimdF::random_unsafe(&mut rng)).collect())
+        .collect();
+    let public_inputs: Vec<Vec<SimdF>> = (0..n_copies)
+        .map(|_| (0..2).map(|_| SimdF::random_unsafe(&mut rng)).collect())
+        .collect();
+
+    replicated.layers[0].input_vals = interleave_copies(&inputs);
+    replicated.public_input = interleave_copies(&public_inputs);
+    replicated.evaluate();
+    let outputs = deinterleave_copies(&replicated.layers[0].output_vals, log_n_copies);
+
+    for ((input, public_input), output) in inputs.iter().zip(&public_inputs).zip(&outputs) {
+        let mut copy = base.clone();
+        copy.layers[0].input_vals = input.clone();
+        copy.public_input = public_input.clone();
+        copy.evaluate();
+        assert_eq!(&copy.layers[0].output_vals, output);
+    }
+}
@@ -0,0 +1,70 @@
+use std::fs;
+
+use circuit::{has_circuit_version, Circuit, EmbeddedCircuit, CIRCUIT_VERSION};
+use config_macros::{declare_gkr_config, include_circuit};
+use gkr_engine::{GKREngine, GKRScheme, M31x16Config, MPIConfig};
+use gkr_hashers::SHA256hasher;
+use poly_commit::RawExpanderGKR;
+use serdes::ExpSerde;
+use transcript::BytesHashTranscript;
+
+// a single layer computing [in_0 * in_1, in_0]
+const TINY_M31_CIRCUIT: &str = "tests/data/circuit_m31_tiny.txt";
+
+static TINY_CIRCUIT: EmbeddedCircuit<M31x16Config> =
+    include_circuit!("tests/data/circuit_m31_tiny.txt");
+
+declare_gkr_config!(
+    M31x16ConfigSha2Raw,
+    FieldType::M31x16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+
+fn serialize(circuit: &Circuit<M31x16Config>) -> Vec<u8> {
+    let mut bytes = vec![];
+    circuit.serialize_into(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn test_embedded_circuit() {
+    assert_eq!(TINY_CIRCUIT.bytes(), fs::read(TINY_M31_CIRCUIT).unwrap());
+
+    // the embedded circuit loads as the circuit file does, on every load
+    let loaded =
+        Circuit::<M31x16Config>::verifier_load_circuit::<M31x16ConfigSha2Raw>(TINY_M31_CIRCUIT);
+    let embedded = TINY_CIRCUIT.verifier_load_circuit();
+    assert_eq!(embedded.layers.len(), 1);
+    assert_eq!(embedded.layers[0].mul.len(), 1);
+    assert_eq!(embedded.layers[0].add.len(), 1);
+    assert_eq!(serialize(&embedded), serialize(&loaded));
+    assert_eq!(
+        serialize(&TINY_CIRCUIT.verifier_load_circuit()),
+        serialize(&loaded)
+    );
+
+    // the header is checked at compile time outside of a static too
+    let circuit: EmbeddedCircuit<M31x16Config> =
+        include_circuit!("tests/data/circuit_m31_tiny.txt");
+    assert_eq!(
+        serialize(&circuit.verifier_load_circuit()),
+        serialize(&loaded)
+    );
+}
+
+#[test]
+fn test_circuit_version() {
+    let bytes = fs::read(TINY_M31_CIRCUIT).unwrap();
+    assert_eq!(bytes[..8], CIRCUIT_VERSION);
+    assert!(has_circuit_version(&bytes));
+    assert!(!has_circuit_version(&bytes[..7]));
+    assert!(!has_circuit_version(b"CIRCUIT5 and the rest of a circuit"));
+}
+
+#[test]
+#[should_panic(expected = "CIRCUIT_VERSION")]
+fn test_embedded_circuit_of_another_version() {
+    EmbeddedCircuit::<M31x16Config>::new(b"CIRCUIT5 and the rest of a circuit");
+}
@@ -0,0 +1,152 @@
+use arith::{Field, SimdField};
+use ark_std::test_rng;
+use circuit::{Circuit, CircuitLayer, InputKind, ShardLocation, ShardingScheme, Witness};
+use gkr_engine::{FieldEngine, M31x16Config, MPIConfig};
+
+type C = M31x16Config;
+type F = <C as FieldEngine>::CircuitField;
+
+const WORLD_SIZE: usize = 4;
+const NUM_PRIVATE_INPUTS: usize = 8;
+const NUM_PUBLIC_INPUTS: usize = 3;
+
+fn random_witness(scheme: &ShardingScheme) -> Witness<C> {
+    let mut rng = test_rng();
+    Witness {
+        num_witnesses: scheme.num_witnesses(),
+        num_private_inputs_per_witness: scheme.num_private_inputs,
+        num_public_inputs_per_witness: scheme.num_public_inputs,
+        values: (0..scheme.len())
+            .map(|_| F::random_unsafe(&mut rng))
+            .collect(),
+    }
+}
+
+#[test]
+fn test_sharding_scheme_mapping() {
+    let scheme = ShardingScheme::new::<C>(WORLD_SIZE, NUM_PRIVATE_INPUTS, NUM_PUBLIC_INPUTS);
+    assert_eq!(scheme.pack_size, 16);
+    assert_eq!(scheme.shard_len(), 16 * 11);
+
+    // the first values are the private then the public inputs of lane 0 of rank 0
+    assert_eq!(
+        scheme.locate(9),
+        ShardLocation {
+            rank: 0,
+            lane: 0,
+            kind: InputKind::Public,
+            index: 1,
+        }
+    );
+    // then comes lane 1
+    assert_eq!(
+        scheme.locate(11),
+        ShardLocation {
+            rank: 0,
+            lane: 1,
+            kind: InputKind::Private,
+            index: 0,
+        }
+    );
+
+    for global_index in 0..scheme.len() {
+        let location = scheme.locate(global_index);
+        assert_eq!(scheme.global_index(&location), global_index);
+        assert_eq!(
+            scheme.shard_index(&location),
+            global_index - location.rank * scheme.shard_len()
+        );
+        match scheme.input_position(&location) {
+            Some(position) => assert_eq!(scheme.locate_input_position(position), location),
+            None => assert_eq!(location.kind, InputKind::Public),
+        }
+    }
+
+    // the input positions cover the global input polynomial
+    let mut positions = (0..scheme.len())
+        .filter_map(|i| scheme.input_position(&scheme.locate(i)))
+        .collect::<Vec<_>>();
+    positions.sort();
+    assert_eq!(
+        positions,
+        (0..scheme.num_witnesses() * NUM_PRIVATE_INPUTS).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_sharding_scheme_matches_witness_loading() {
+    let scheme = ShardingScheme::new::<C>(WORLD_SIZE, NUM_PRIVATE_INPUTS, NUM_PUBLIC_INPUTS);
+    let witness = random_witness(&scheme);
+    scheme.validate(&witness).unwrap();
+
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: NUM_PRIVATE_INPUTS.ilog2() as usize,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    for rank in 0..WORLD_SIZE {
+        let mpi_config = MPIConfig {
+            world_size: WORLD_SIZE as i32,
+            world_rank: rank as i32,
+            ..Default::default()
+        };
+        circuit.prover_process_witness(witness.clone(), &mpi_config);
+        assert_eq!(circuit.sharding_scheme(&witness, &mpi_config), scheme);
+        scheme
+            .validate_shard(
+                &witness,
+                rank,
+                &circuit.layers[0].input_vals,
+                &circuit.public_input,
+            )
+            .unwrap();
+
+        // the committed input polynomial is laid out by input position
+        for (i, input) in circuit.layers[0].input_vals.iter().enumerate() {
+            for (lane, value) in input.unpack().into_iter().enumerate() {
+                let position = (rank * NUM_PRIVATE_INPUTS + i) * scheme.pack_size + lane;
+                let location = scheme.locate_input_position(position);
+                assert_eq!(witness.values[scheme.global_index(&location)], value);
+            }
+        }
+    }
+
+    // the inputs of a rank are not those of another
+    let mpi_config = MPIConfig {
+        world_size: WORLD_SIZE as i32,
+        world_rank: 1,
+        ..Default::default()
+    };
+    circuit.prover_process_witness(witness.clone(), &mpi_config);
+    let err = scheme
+        .validate_shard(
+            &witness,
+            2,
+            &circuit.layers[0].input_vals,
+            &circuit.public_input,
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("rank 2"));
+}
+
+#[test]
+fn test_sharding_scheme_validation() {
+    let scheme = ShardingScheme::new::<C>(WORLD_SIZE, NUM_PRIVATE_INPUTS, NUM_PUBLIC_INPUTS);
+
+    let mut witness = random_witness(&scheme);
+    witness.num_witnesses -= 1;
+    witness
+        .values
+        .truncate(witness.num_witnesses * scheme.witness_len());
+    assert!(scheme.validate(&witness).is_err());
+
+    let mut witness = random_witness(&scheme);
+    witness.num_public_inputs_per_witness += 1;
+    assert!(scheme.validate(&witness).is_err());
+
+    let mut witness = random_witness(&scheme);
+    witness.values.pop();
+    assert!(scheme.validate(&witness).is_err());
+}
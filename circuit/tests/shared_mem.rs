@@ -1,7 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use arith::Field;
-use circuit::Circuit;
+use circuit::{Circuit, Witness};
 use config_macros::declare_gkr_config;
 use gkr_engine::{
     BN254Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine, GKRScheme, Goldilocksx8Config,
@@ -18,6 +18,7 @@ pub const KECCAK_M31_CIRCUIT: &str = "data/circuit_m31.txt";
 pub const KECCAK_GF2_CIRCUIT: &str = "data/circuit_gf2.txt";
 pub const KECCAK_BN254_CIRCUIT: &str = "data/circuit_bn254.txt";
 pub const KECCAK_GOLDILOCKS_CIRCUIT: &str = "data/circuit_goldilocks.txt";
+pub const KECCAK_M31_WITNESS: &str = "data/witness_m31.txt";
 
 declare_gkr_config!(
     M31x16ConfigSha2Raw,
@@ -92,6 +93,16 @@ fn test_shared_mem() {
         ]),
     );
 
+    let witness = mpi_config.is_root().then(|| Witness::<M31x16Config> {
+        num_witnesses: 16,
+        num_private_inputs_per_witness: 4,
+        num_public_inputs_per_witness: 1,
+        values: (0..16 * 5)
+            .map(|_| <M31x16Config as FieldEngine>::CircuitField::random_unsafe(&mut rng))
+            .collect(),
+    });
+    test_shared_mem_helper(&mpi_config, witness);
+
     let circuit = load_circuit::<M31x16ConfigSha2Raw>(&mpi_config);
     test_shared_mem_helper(&mpi_config, circuit);
     let circuit = load_circuit::<GF2ExtConfigSha2Raw>(&mpi_config);
@@ -109,6 +120,8 @@ fn test_shared_mem() {
     test_shared_mem_on_heap_helper(&mpi_config, circuit);
     let circuit = load_circuit::<Goldilocksx8ConfigSha2Raw>(&mpi_config);
     test_shared_mem_on_heap_helper(&mpi_config, circuit);
+
+    test_shared_witness_helper(&mpi_config);
 }
 
 #[allow(unreachable_patterns)]
@@ -122,9 +135,9 @@ fn test_shared_mem_helper<T: MPISharedMemory + ExpSerde + std::fmt::Debug>(
             .unwrap()
             .serialize_into(&mut original_serialization)
             .unwrap();
-        mpi_config.consume_obj_and_create_shared(t)
+        mpi_config.consume_obj_and_create_shared(t).unwrap()
     } else {
-        mpi_config.consume_obj_and_create_shared(t)
+        mpi_config.consume_obj_and_create_shared(t).unwrap()
     };
 
     let mut shared_serialization = vec![];
@@ -135,7 +148,9 @@ fn test_shared_mem_helper<T: MPISharedMemory + ExpSerde + std::fmt::Debug>(
     } else {
         vec![]
     };
-    mpi_config.gather_vec(&shared_serialization, &mut gathered_bytes);
+    mpi_config
+        .gather_vec(&shared_serialization, &mut gathered_bytes)
+        .unwrap();
     if mpi_config.is_root() {
         gathered_bytes
             .chunks_exact_mut(original_serialization.len())
@@ -150,7 +165,7 @@ fn test_shared_mem_helper<T: MPISharedMemory + ExpSerde + std::fmt::Debug>(
             });
     }
     data.discard_control_of_shared_mem();
-    mpi_config.free_shared_mem(&mut window);
+    mpi_config.free_shared_mem(&mut window).unwrap();
 }
 
 fn test_shared_mem_on_heap_helper<T: MPISharedMemory + ExpSerde + std::fmt::Debug + Default>(
@@ -165,9 +180,9 @@ fn test_shared_mem_on_heap_helper<T: MPISharedMemory + ExpSerde + std::fmt::Debu
             .unwrap()
             .serialize_into(&mut original_serialization)
             .unwrap();
-        mpi_config.consume_obj_and_create_shared(t)
+        mpi_config.consume_obj_and_create_shared(t).unwrap()
     } else {
-        mpi_config.consume_obj_and_create_shared(t)
+        mpi_config.consume_obj_and_create_shared(t).unwrap()
     };
 
     *data_on_heap.lock().unwrap() = data;
@@ -184,7 +199,9 @@ fn test_shared_mem_on_heap_helper<T: MPISharedMemory + ExpSerde + std::fmt::Debu
     } else {
         vec![]
     };
-    mpi_config.gather_vec(&shared_serialization, &mut gathered_bytes);
+    mpi_config
+        .gather_vec(&shared_serialization, &mut gathered_bytes)
+        .unwrap();
     if mpi_config.is_root() {
         gathered_bytes
             .chunks_exact_mut(original_serialization.len())
@@ -209,5 +226,25 @@ fn test_shared_mem_on_heap_helper<T: MPISharedMemory + ExpSerde + std::fmt::Debu
         }
     }
 
-    mpi_config.free_shared_mem(&mut window);
+    mpi_config.free_shared_mem(&mut window).unwrap();
+}
+
+fn test_shared_witness_helper(mpi_config: &MPIConfig) {
+    let circuit_path = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let witness_path = "../".to_owned() + KECCAK_M31_WITNESS;
+
+    // the inputs packed from the shared witness are those packed from a copy of the witness
+    let (mut circuit, mut window) = Circuit::<M31x16Config>::prover_load_circuit::<
+        M31x16ConfigSha2Raw,
+    >(&circuit_path, mpi_config);
+    circuit.prover_load_witness_file(&witness_path, mpi_config);
+    let input_vals = circuit.layers[0].input_vals.clone();
+    let public_input = circuit.public_input.clone();
+
+    circuit.prover_load_witness_file_shared(&witness_path, mpi_config);
+    assert_eq!(circuit.layers[0].input_vals, input_vals);
+    assert_eq!(circuit.public_input, public_input);
+
+    circuit.discard_control_of_shared_mem();
+    mpi_config.free_shared_mem(&mut window).unwrap();
 }
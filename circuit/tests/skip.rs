@@ -0,0 +1,115 @@
+use arith::Field;
+use ark_std::test_rng;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul, LayerRetention};
+use gkr_engine::{FieldEngine, M31x16Config};
+use serdes::ExpSerde;
+
+type C = M31x16Config;
+type F = <C as FieldEngine>::CircuitField;
+type SimdF = <C as FieldEngine>::SimdCircuitField;
+
+// every layer squares in[0] into out[0] and relays in[1] into out[1], the last layer computes
+// out[0] = in[0] * in[1]
+fn relay_circuit(layer_num: usize) -> Circuit<C> {
+    let gate_mul = |i_ids| GateMul {
+        i_ids,
+        o_id: 0,
+        coef: F::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 0,
+    };
+    let mut layer = CircuitLayer::<C> {
+        input_var_num: 1,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    layer.mul.push(gate_mul([0, 0]));
+    layer.add.push(GateAdd {
+        i_ids: [1],
+        o_id: 1,
+        coef: F::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 1,
+    });
+
+    let mut layers = vec![layer; layer_num];
+    let last_layer = layers.last_mut().unwrap();
+    last_layer.mul = vec![gate_mul([0, 1])];
+    last_layer.add.clear();
+
+    Circuit {
+        layers,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_skip_relays() {
+    let mut rng = test_rng();
+    let layer_num = 5;
+
+    let mut relayed = relay_circuit(layer_num);
+    relayed.layers[0].input_vals = (0..2).map(|_| SimdF::random_unsafe(&mut rng)).collect();
+    let mut skipped = relayed.clone();
+    relayed.evaluate();
+
+    // the chain of relays through layers 0..=3 is read by layer 3 directly
+    assert_eq!(skipped.skip_relays(), layer_num - 1);
+    assert!(skipped.layers.iter().all(|layer| layer.add.is_empty()));
+    let skip_inputs = &skipped.layers[layer_num - 2].skip_inputs;
+    assert_eq!(skip_inputs.len(), 1);
+    assert_eq!(skip_inputs[0].layer, 0);
+    assert_eq!(skip_inputs[0].gates[0].i_ids, [1]);
+    assert_eq!(skip_inputs[0].gates[0].o_id, 1);
+    assert!(skipped.is_skip_source(0));
+    assert_eq!(skipped.skip_relays(), 0);
+
+    skipped.evaluate();
+    assert_eq!(
+        skipped.layers.last().unwrap().output_vals,
+        relayed.layers.last().unwrap().output_vals
+    );
+    assert!(skipped.layers[2].input_vals[1].is_zero());
+
+    // the skip inputs are serialized with their layer
+    let mut buffer = vec![];
+    skipped.serialize_into(&mut buffer).unwrap();
+    let deserialized = Circuit::<C>::deserialize_from(&buffer[..]).unwrap();
+    let deserialized_skip = &deserialized.layers[layer_num - 2].skip_inputs[0];
+    assert_eq!(deserialized_skip.layer, 0);
+    assert_eq!(deserialized_skip.gates[0].i_ids, [1]);
+    let mut buffer2 = vec![];
+    deserialized.serialize_into(&mut buffer2).unwrap();
+    assert_eq!(buffer, buffer2);
+
+    // the input relay layer shifts the layer the skip inputs read
+    let mut preprocessed = skipped.clone();
+    preprocessed.pre_process_gkr();
+    assert_eq!(preprocessed.layers[layer_num - 1].skip_inputs[0].layer, 1);
+    preprocessed.layers[0].input_vals = relayed.layers[0].input_vals.clone();
+    preprocessed.evaluate();
+    assert_eq!(
+        preprocessed.layers.last().unwrap().output_vals,
+        relayed.layers.last().unwrap().output_vals
+    );
+}
+
+#[test]
+fn test_skip_sources_are_retained() {
+    let mut rng = test_rng();
+    let layer_num = 7;
+
+    let mut full = relay_circuit(layer_num);
+    full.skip_relays();
+    full.layers[0].input_vals = (0..2).map(|_| SimdF::random_unsafe(&mut rng)).collect();
+    let mut checkpointed = full.clone();
+    full.evaluate();
+
+    let retention = LayerRetention::Checkpoint { interval: 4 };
+    checkpointed.evaluate_with_retention(retention);
+    for i in (0..layer_num).rev() {
+        checkpointed.restore_layer_vals(i);
+        assert_eq!(checkpointed.layers[i].input_vals, full.layers[i].input_vals);
+        checkpointed.release_layer_vals(i, retention);
+    }
+}
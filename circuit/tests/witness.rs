@@ -0,0 +1,123 @@
+use std::{env, fs};
+
+use arith::{CanonicalField, Field};
+use ark_std::test_rng;
+use circuit::{Circuit, CircuitLayer, Witness};
+use gkr_engine::{FieldEngine, M31x16Config, MPIConfig};
+
+type C = M31x16Config;
+type F = <C as FieldEngine>::CircuitField;
+
+#[test]
+fn test_witness_shard_hash() {
+    let mut rng = test_rng();
+    let world_size = 2;
+    let shard_size = 3 * C::get_field_pack_size();
+
+    let mut witness = Witness::<C> {
+        num_witnesses: world_size * C::get_field_pack_size(),
+        num_private_inputs_per_witness: 2,
+        num_public_inputs_per_witness: 1,
+        values: (0..world_size * shard_size)
+            .map(|_| F::random_unsafe(&mut rng))
+            .collect(),
+    };
+    assert_eq!(witness.shard(1), &witness.values[shard_size..]);
+
+    let hashes = [witness.shard_hash(0), witness.shard_hash(1)];
+    assert_ne!(hashes[0], hashes[1]);
+
+    // a change in a shard only changes the hash of that shard
+    witness.values[shard_size] += F::ONE;
+    assert_eq!(witness.shard_hash(0), hashes[0]);
+    assert_ne!(witness.shard_hash(1), hashes[1]);
+}
+
+fn witness_file_bytes(witness: &Witness<C>) -> Vec<u8> {
+    let header = [
+        witness.num_witnesses,
+        witness.num_private_inputs_per_witness,
+        witness.num_public_inputs_per_witness,
+    ];
+    let mut bytes = header
+        .iter()
+        .flat_map(|n| (*n as u64).to_le_bytes())
+        .collect::<Vec<_>>();
+    bytes.extend([0u8; 32]);
+    witness
+        .values
+        .iter()
+        .for_each(|v| bytes.extend(v.to_canonical_bytes()));
+    bytes
+}
+
+#[test]
+fn test_stream_witness_file() {
+    let mut rng = test_rng();
+    let witness_len = 8 + 2;
+    // one more witness than proven, which is dropped
+    let num_witnesses = C::get_field_pack_size() + 1;
+    let witness = Witness::<C> {
+        num_witnesses,
+        num_private_inputs_per_witness: 8,
+        num_public_inputs_per_witness: 2,
+        values: (0..num_witnesses * witness_len)
+            .map(|_| F::random_unsafe(&mut rng))
+            .collect(),
+    };
+    let path = env::temp_dir().join("expander-streamed-witness.txt");
+    fs::write(&path, witness_file_bytes(&witness)).unwrap();
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 3,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    circuit
+        .try_prover_stream_witness_file(path.to_str().unwrap(), &mpi_config)
+        .unwrap();
+
+    // the same inputs as loading the whole witness
+    let (streamed_inputs, streamed_public_input) = (
+        circuit.layers[0].input_vals.clone(),
+        circuit.public_input.clone(),
+    );
+    let mut truncated = witness.clone();
+    truncated.num_witnesses -= 1;
+    truncated
+        .values
+        .truncate(truncated.num_witnesses * witness_len);
+    circuit.prover_process_witness(truncated, &mpi_config);
+    assert_eq!(streamed_inputs, circuit.layers[0].input_vals);
+    assert_eq!(streamed_public_input, circuit.public_input);
+
+    // the same inputs whether the lanes are read on demand or ahead
+    for prefetch_depth in [0, 1, 8] {
+        circuit
+            .try_prover_stream_witness_file_with_prefetch(
+                path.to_str().unwrap(),
+                &mpi_config,
+                prefetch_depth,
+            )
+            .unwrap();
+        assert_eq!(streamed_inputs, circuit.layers[0].input_vals);
+        assert_eq!(streamed_public_input, circuit.public_input);
+    }
+
+    // a value out of the field fails the load
+    let mut bytes = witness_file_bytes(&witness);
+    let value_start = bytes.len() - witness_len * F::CANONICAL_SIZE * 2;
+    bytes[value_start..value_start + F::CANONICAL_SIZE].fill(0xff);
+    fs::write(&path, bytes).unwrap();
+    assert!(circuit
+        .try_prover_stream_witness_file(path.to_str().unwrap(), &mpi_config)
+        .is_err());
+
+    fs::remove_file(&path).unwrap();
+    assert!(circuit
+        .try_prover_stream_witness_file(path.to_str().unwrap(), &mpi_config)
+        .is_err());
+}
@@ -1,7 +1,10 @@
+use std::path::Path;
+
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
+use sha2::{Digest, Sha256};
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{parse_macro_input, ExprPath, Ident, Token, Visibility};
+use syn::{parse_macro_input, ExprPath, Ident, LitStr, Token, Visibility};
 
 // Define a struct to parse our custom input format
 struct ConfigLit {
@@ -11,6 +14,7 @@ struct ConfigLit {
     fiat_shamir_hash_type_expr: ExprPath,
     polynomial_commitment_type: ExprPath,
     scheme_config: ExprPath,
+    mpi_engine_type: Option<ExprPath>,
 }
 
 // Implement parsing for our custom input format
@@ -27,6 +31,14 @@ impl Parse for ConfigLit {
         input.parse::<Token![,]>()?;
         let scheme_config: ExprPath = input.parse()?;
         let _ = input.parse::<Token![,]>(); // Optional trailing comma
+                                            // Optional MPI engine, MPIConfig unless given
+        let mpi_engine_type = if input.is_empty() {
+            None
+        } else {
+            let mpi_engine_type: ExprPath = input.parse()?;
+            let _ = input.parse::<Token![,]>();
+            Some(mpi_engine_type)
+        };
         Ok(ConfigLit {
             visibility,
             config_name,
@@ -34,10 +46,19 @@ impl Parse for ConfigLit {
             fiat_shamir_hash_type_expr,
             polynomial_commitment_type,
             scheme_config,
+            mpi_engine_type,
         })
     }
 }
 
+// Fail the expansion with a pointer to the missing cargo feature, rather than leaving the user
+// with an unresolved type deep inside the generated code
+fn require_feature(enabled: bool, feature: &str, variant: &str) {
+    if !enabled {
+        panic!("{variant} is not compiled in, enable the `{feature}` feature of `config_macros`");
+    }
+}
+
 // Check if the field type is one of the supported types and return the corresponding config type
 fn parse_field_type(field_expr: ExprPath) -> (String, String) {
     let field_enum = field_expr
@@ -45,7 +66,18 @@ fn parse_field_type(field_expr: ExprPath) -> (String, String) {
         .segments
         .last()
         .expect("Empty path for field");
-    match field_enum.ident.to_string().as_str() {
+    let field_type = field_enum.ident.to_string();
+    let feature = match field_type.as_str() {
+        "M31x1" | "M31x16" => ("m31", cfg!(feature = "m31")),
+        "BN254" => ("bn254", cfg!(feature = "bn254")),
+        "GF2Ext128" => ("gf2", cfg!(feature = "gf2")),
+        "Goldilocksx1" | "Goldilocksx8" => ("goldilocks", cfg!(feature = "goldilocks")),
+        "BabyBearx16" => ("babybear", cfg!(feature = "babybear")),
+        _ => panic!("Unknown field type"),
+    };
+    require_feature(feature.1, feature.0, &format!("FieldType::{field_type}"));
+
+    match field_type.as_str() {
         "M31x1" => ("M31x1".to_owned(), "M31x1Config".to_owned()),
         "M31x16" => ("M31x16".to_owned(), "M31x16Config".to_owned()),
         "BN254" => ("BN254".to_owned(), "BN254Config".to_owned()),
@@ -53,7 +85,7 @@ fn parse_field_type(field_expr: ExprPath) -> (String, String) {
         "Goldilocksx1" => ("Goldilocksx1".to_owned(), "Goldilocksx1Config".to_owned()),
         "Goldilocksx8" => ("Goldilocksx8".to_owned(), "Goldilocksx8Config".to_owned()),
         "BabyBearx16" => ("BabyBearx16".to_owned(), "BabyBearx16Config".to_owned()),
-        _ => panic!("Unknown field type"),
+        _ => unreachable!(),
     }
 }
 
@@ -72,6 +104,14 @@ fn parse_fiat_shamir_hash_type(
     let binding = hash_enum.ident.to_string();
     let hash_type_str = binding.as_str();
     let challenge_f = format!("<{field_config} as FieldEngine>::ChallengeField");
+    match hash_type_str {
+        "Keccak256" => require_feature(cfg!(feature = "keccak"), "keccak", hash_type_str),
+        "Poseidon" => require_feature(cfg!(feature = "poseidon"), "poseidon", hash_type_str),
+        "MIMC5" => require_feature(cfg!(feature = "mimc"), "mimc", hash_type_str),
+        "Anemoi" => require_feature(cfg!(feature = "anemoi"), "anemoi", hash_type_str),
+        "Griffin" => require_feature(cfg!(feature = "griffin"), "griffin", hash_type_str),
+        _ => (),
+    }
     match (hash_type_str, field_type) {
         ("SHA256", _) => (
             "SHA256".to_owned(),
@@ -89,6 +129,14 @@ fn parse_fiat_shamir_hash_type(
             "MIMC5".to_owned(),
             format!("BytesHashTranscript::<MiMC5FiatShamirHasher<{challenge_f}>>").to_owned(),
         ),
+        ("Anemoi", "BN254") => (
+            "Anemoi".to_owned(),
+            format!("BytesHashTranscript::<AnemoiFiatShamirHasher<{challenge_f}>>").to_owned(),
+        ),
+        ("Griffin", "BN254") => (
+            "Griffin".to_owned(),
+            format!("BytesHashTranscript::<GriffinFiatShamirHasher<{challenge_f}>>").to_owned(),
+        ),
         _ => panic!("Unknown hash type"),
     }
 }
@@ -105,13 +153,35 @@ fn parse_polynomial_commitment_type(
         .expect("Empty path for polynomial commitment type");
 
     let pcs_type_str = binding.ident.to_string();
+    match pcs_type_str.as_str() {
+        "Hyrax" => require_feature(cfg!(feature = "hyrax"), "hyrax", &pcs_type_str),
+        "KZG" | "Zeromorph" => require_feature(cfg!(feature = "kzg"), "kzg", &pcs_type_str),
+        "Orion" => require_feature(cfg!(feature = "orion"), "orion", &pcs_type_str),
+        "Basefold" => require_feature(cfg!(feature = "basefold"), "basefold", &pcs_type_str),
+        "Brakedown" => require_feature(cfg!(feature = "brakedown"), "brakedown", &pcs_type_str),
+        "Ligero" => require_feature(cfg!(feature = "ligero"), "ligero", &pcs_type_str),
+        "Whir" => require_feature(cfg!(feature = "whir"), "whir", &pcs_type_str),
+        "Dory" => require_feature(cfg!(feature = "dory"), "dory", &pcs_type_str),
+        "IPA" => require_feature(cfg!(feature = "ipa"), "ipa", &pcs_type_str),
+        _ => (),
+    }
     match (pcs_type_str.as_str(), field_type) {
         ("Raw", _) => (
             "Raw".to_owned(),
             format!("RawExpanderGKR::<{field_config}>").to_owned(),
         ),
+        ("RawDistributed", _) => (
+            "RawDistributed".to_owned(),
+            format!("RawExpanderGKRDistributed::<{field_config}>").to_owned(),
+        ),
         ("Hyrax", "BN254") => ("Hyrax".to_string(), "HyraxPCS::<G1Affine>".to_string()),
         ("KZG", "BN254") => ("KZG".to_owned(), "HyperBiKZGPCS::<Bn256>".to_string()),
+        ("Zeromorph", "BN254") => (
+            "Zeromorph".to_owned(),
+            "ZeromorphPCS::<Bn256>".to_string(),
+        ),
+        ("Dory", "BN254") => ("Dory".to_owned(), "DoryPCS::<Bn256>".to_string()),
+        ("IPA", "BN254") => ("IPA".to_owned(), "IpaPCS::<G1Affine>".to_string()),
         ("Orion", "GF2Ext128") => (
             "Orion".to_owned(),
             format!("OrionPCSForGKR::<{field_config}, GF2x128>").to_owned(),
@@ -124,13 +194,41 @@ fn parse_polynomial_commitment_type(
             "Orion".to_owned(),
             format!("OrionPCSForGKR::<{field_config}, Goldilocksx8>").to_owned(),
         ),
+        ("Orion", "BabyBearx16") => (
+            "Orion".to_owned(),
+            format!("OrionPCSForGKR::<{field_config}, BabyBearx16>").to_owned(),
+        ),
+        ("Brakedown", "GF2Ext128") => (
+            "Brakedown".to_owned(),
+            format!("BrakedownPCSForGKR::<{field_config}, GF2x128>").to_owned(),
+        ),
+        ("Brakedown", "M31x16") => (
+            "Brakedown".to_owned(),
+            format!("BrakedownPCSForGKR::<{field_config}, M31x16>").to_owned(),
+        ),
+        ("Brakedown", "Goldilocksx8") => (
+            "Brakedown".to_owned(),
+            format!("BrakedownPCSForGKR::<{field_config}, Goldilocksx8>").to_owned(),
+        ),
+        ("Ligero", "Goldilocksx1" | "Goldilocksx8" | "BN254") => (
+            "Ligero".to_owned(),
+            format!("LigeroPCSForGKR::<{field_config}>").to_owned(),
+        ),
+        ("Basefold", "M31x1" | "M31x16" | "Goldilocksx1" | "Goldilocksx8" | "BabyBearx16") => (
+            "Basefold".to_owned(),
+            format!("BasefoldPCSForGKR::<{field_config}>").to_owned(),
+        ),
+        ("Whir", "Goldilocksx1" | "Goldilocksx8" | "BabyBearx16") => (
+            "Whir".to_owned(),
+            format!("WhirPCSForGKR::<{field_config}>").to_owned(),
+        ),
         _ => panic!(
             "Unknown polynomial commitment type in config macro expansion. PCS: '{pcs_type_str}', Field: '{field_type}'"
         ),
     }
 }
 
-fn _parse_scheme_config(scheme_config: ExprPath) -> String {
+fn parse_scheme_config(scheme_config: &ExprPath) -> String {
     let binding = scheme_config
         .path
         .segments
@@ -139,6 +237,46 @@ fn _parse_scheme_config(scheme_config: ExprPath) -> String {
     binding.ident.to_string()
 }
 
+// The MPI engine type of the config. The engine does not show in the proof, which is the same
+// whether the processes are MPI ranks or threads, hence it is left out of the fingerprint.
+fn parse_mpi_engine_type(mpi_engine_type: Option<ExprPath>) -> TokenStream {
+    let Some(mpi_engine_type) = mpi_engine_type else {
+        return quote! { MPIConfig<'a> };
+    };
+    let binding = mpi_engine_type
+        .path
+        .segments
+        .last()
+        .expect("Empty path for MPI engine type");
+    match binding.ident.to_string().as_str() {
+        "MPI" => quote! { MPIConfig<'a> },
+        "Threaded" => quote! { ThreadedEngine },
+        other => panic!("Unknown MPI engine type in config macro expansion: '{other}'"),
+    }
+}
+
+// Number of SIMD lanes of the circuit field of each field type
+fn simd_width(field_type: &str) -> usize {
+    match field_type {
+        "M31x16" | "BabyBearx16" => 16,
+        "GF2Ext128" | "Goldilocksx8" => 8,
+        "M31x1" | "BN254" | "Goldilocksx1" => 1,
+        _ => unreachable!(),
+    }
+}
+
+// Bump whenever the proof format changes in a way the rest of the config does not capture
+const FINGERPRINT_VERSION: u32 = 1;
+
+// SHA-256 of a canonical description of the config, identical across builds and platforms
+fn config_fingerprint(field_type: &str, hash_type: &str, pcs_type: &str, scheme: &str) -> [u8; 32] {
+    let description = format!(
+        "expander-gkr-config/v{FINGERPRINT_VERSION}|field={field_type}|simd={}|hash={hash_type}|pcs={pcs_type}|scheme={scheme}",
+        simd_width(field_type)
+    );
+    Sha256::digest(description.as_bytes()).into()
+}
+
 /// Example usage:
 /// declare_gkr_config!(
 ///     pub MyFavoriateConfigName,
@@ -147,6 +285,16 @@ fn _parse_scheme_config(scheme_config: ExprPath) -> String {
 ///     PolynomialCommitmentType::Raw
 ///     GKRScheme::Vanilla,
 /// );
+/// An optional last argument selects the MPI engine, `MPIEngineType::MPI` by default, or
+/// `MPIEngineType::Threaded` for the ranks to be threads of a single process:
+/// declare_gkr_config!(
+///     pub MyThreadedConfigName,
+///     FieldType::M31,
+///     FiatShamirHashType::SHA256,
+///     PolynomialCommitmentType::Raw
+///     GKRScheme::Vanilla,
+///     MPIEngineType::Threaded,
+/// );
 #[proc_macro]
 pub fn declare_gkr_config(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     declare_gkr_config_impl(input)
@@ -161,13 +309,22 @@ fn declare_gkr_config_impl(input: proc_macro::TokenStream) -> proc_macro::TokenS
         fiat_shamir_hash_type_expr,
         polynomial_commitment_type,
         scheme_config,
+        mpi_engine_type,
     } = parse_macro_input!(input as ConfigLit);
 
     let (field_type, field_config) = parse_field_type(field_expr);
-    let (_fiat_shamir_hash_type, transcript_type) =
+    let (fiat_shamir_hash_type, transcript_type) =
         parse_fiat_shamir_hash_type(&field_type, &field_config, fiat_shamir_hash_type_expr);
-    let (_polynomial_commitment_enum, polynomial_commitment_type) =
+    let (polynomial_commitment_enum, polynomial_commitment_type) =
         parse_polynomial_commitment_type(&field_type, &field_config, polynomial_commitment_type);
+    let fingerprint = config_fingerprint(
+        &field_type,
+        &fiat_shamir_hash_type,
+        &polynomial_commitment_enum,
+        &parse_scheme_config(&scheme_config),
+    );
+
+    let mpi_engine_type = parse_mpi_engine_type(mpi_engine_type);
 
     let field_config = format_ident!("{field_config}");
     let transcript_type_expr = syn::parse_str::<syn::Type>(&transcript_type).unwrap();
@@ -182,12 +339,40 @@ fn declare_gkr_config_impl(input: proc_macro::TokenStream) -> proc_macro::TokenS
 
         impl<'a> GKREngine for #config_name<'a> {
             type FieldConfig = #field_config;
-            type MPIConfig = MPIConfig<'a>;
+            type MPIConfig = #mpi_engine_type;
             type TranscriptConfig = #transcript_type_expr;
             type PCSConfig = #polynomial_commitment_type_expr;
             const SCHEME: GKRScheme = #scheme_config;
+            const FINGERPRINT: [u8; 32] = [#(#fingerprint),*];
         }
     };
 
     ret.into()
 }
+
+/// Embed the serialized circuit at a path relative to the manifest directory of the crate, as
+/// an `EmbeddedCircuit` only deserialized on its first load. The circuit is embedded as it is
+/// serialized, uncompressed, and is not parsed at compile time: only its version header is
+/// checked, by a constant assertion failing the build wherever the macro is used. Example usage:
+/// static CIRCUIT: EmbeddedCircuit<M31x16Config> = include_circuit!("data/circuit_m31.txt");
+/// let circuit = CIRCUIT.verifier_load_circuit();
+#[proc_macro]
+pub fn include_circuit(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+    let path = Path::new(&manifest_dir).join(path.value());
+    // include_bytes! also rebuilds the crate whenever the circuit file changes
+    let path = path.to_str().expect("Non UTF-8 path for circuit");
+
+    let ret: TokenStream = quote! {
+        {
+            const _: () = assert!(
+                ::circuit::has_circuit_version(include_bytes!(#path)),
+                "not a circuit serialized in the format of CIRCUIT_VERSION"
+            );
+            ::circuit::EmbeddedCircuit::new(include_bytes!(#path))
+        }
+    };
+    ret.into()
+}
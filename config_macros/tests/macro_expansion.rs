@@ -1,15 +1,19 @@
 use std::any::type_name;
 
+use babybear::BabyBearx16;
 use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
     BN254Config, BabyBearx16Config, FieldEngine, GF2ExtConfig, GKREngine, GKRScheme,
-    Goldilocksx8Config, M31x16Config, MPIConfig,
+    Goldilocksx8Config, M31x16Config, MPIConfig, ThreadedEngine,
 };
 use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+use goldilocks::Goldilocksx8;
 use halo2curves::bn256::Bn256;
 use mersenne31::M31x16;
-use poly_commit::{HyperBiKZGPCS, OrionPCSForGKR, RawExpanderGKR};
+use poly_commit::{
+    BasefoldPCSForGKR, HyperBiKZGPCS, OrionPCSForGKR, RawExpanderGKR, RawExpanderGKRDistributed,
+};
 use transcript::BytesHashTranscript;
 
 fn print_type_name<Cfg: GKREngine>() {
@@ -74,6 +78,13 @@ fn main() {
         PolynomialCommitmentType::Raw,
         GKRScheme::Vanilla,
     );
+    declare_gkr_config!(
+        GoldilocksExtSHA256OrionConfig,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Orion,
+        GKRScheme::Vanilla,
+    );
     declare_gkr_config!(
         BabyBearExtSHA256Config,
         FieldType::BabyBearx16,
@@ -81,6 +92,29 @@ fn main() {
         PolynomialCommitmentType::Raw,
         GKRScheme::Vanilla,
     );
+    declare_gkr_config!(
+        BabyBearExtSHA256BasefoldConfig,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Basefold,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        BabyBearExtSHA256OrionConfig,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Orion,
+        GKRScheme::Vanilla,
+    );
+
+    declare_gkr_config!(
+        M31ExtSha256ThreadedConfig,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+        MPIEngineType::Threaded,
+    );
 
     print_type_name::<M31ExtSha256Config>();
     print_type_name::<M31ExtPoseidonRawConfig>();
@@ -90,5 +124,97 @@ fn main() {
     print_type_name::<GF2ExtKeccak256Config>();
     print_type_name::<GF2ExtKeccak256OrionConfig>();
     print_type_name::<GoldilocksExtSHA256Config>();
+    print_type_name::<GoldilocksExtSHA256OrionConfig>();
     print_type_name::<BabyBearExtSHA256Config>();
+    print_type_name::<BabyBearExtSHA256BasefoldConfig>();
+    print_type_name::<BabyBearExtSHA256OrionConfig>();
+    print_type_name::<M31ExtSha256ThreadedConfig>();
+
+    // the engine is not part of the proof format
+    assert_eq!(
+        type_name::<<M31ExtSha256ThreadedConfig as GKREngine>::MPIConfig>(),
+        type_name::<ThreadedEngine>()
+    );
+    assert_eq!(
+        M31ExtSha256ThreadedConfig::FINGERPRINT,
+        M31ExtSha256Config::FINGERPRINT
+    );
+}
+
+#[test]
+fn test_fingerprint() {
+    declare_gkr_config!(
+        M31ExtSha256RawConfig,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        M31ExtSha256RawConfigAgain,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        M31ExtKeccak256RawConfig,
+        FieldType::M31x16,
+        FiatShamirHashType::Keccak256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        M31ExtSha256OrionConfig,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Orion,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        M31ExtSha256RawDistributedConfig,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::RawDistributed,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        M31ExtSha256RawSquareConfig,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::GkrSquare,
+    );
+    declare_gkr_config!(
+        BabyBearExtSha256RawConfig,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+
+    // the fingerprint depends on the config only, not on the name it is declared under
+    assert_eq!(
+        M31ExtSha256RawConfig::FINGERPRINT,
+        M31ExtSha256RawConfigAgain::FINGERPRINT
+    );
+    // and must not change across releases unless the proof format does
+    assert_eq!(
+        M31ExtSha256RawConfig::FINGERPRINT[..8],
+        [124, 33, 222, 77, 181, 173, 149, 143]
+    );
+
+    let fingerprints = [
+        M31ExtSha256RawConfig::FINGERPRINT,
+        M31ExtKeccak256RawConfig::FINGERPRINT,
+        M31ExtSha256OrionConfig::FINGERPRINT,
+        M31ExtSha256RawDistributedConfig::FINGERPRINT,
+        M31ExtSha256RawSquareConfig::FINGERPRINT,
+        BabyBearExtSha256RawConfig::FINGERPRINT,
+    ];
+    for i in 0..fingerprints.len() {
+        for j in i + 1..fingerprints.len() {
+            assert_ne!(fingerprints[i], fingerprints[j]);
+        }
+    }
 }
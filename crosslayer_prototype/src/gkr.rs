@@ -19,7 +19,7 @@ pub fn prove_gkr<C: FieldEngine, T: Transcript>(
     let r_simd = transcript.generate_field_elements::<C::ChallengeField>(
         C::get_field_pack_size().trailing_zeros() as usize,
     );
-    let output_claim = C::eval_circuit_vals_at_challenge(final_layer_vals, &rz0, &mut sp.v_evals);
+    let output_claim = C::eval_circuit_vals_at_challenge(final_layer_vals, &rz0);
     let output_claim = MultiLinearPoly::<C::ChallengeField>::evaluate_with_buffer(
         &output_claim.unpack(),
         &r_simd,
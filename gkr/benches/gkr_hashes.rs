@@ -28,7 +28,9 @@ fn prover_run<Cfg: GKREngine>(
 {
     let mut prover = Prover::<Cfg>::new(mpi_config.clone());
     prover.prepare_mem(circuit);
-    prover.prove(circuit, pcs_params, pcs_proving_key, pcs_scratch);
+    prover
+        .prove(circuit, pcs_params, pcs_proving_key, pcs_scratch)
+        .unwrap();
 }
 
 fn benchmark_setup<Cfg: GKREngine>(
@@ -0,0 +1,208 @@
+//! A ledger of the evaluation claims made and discharged by the vanilla GKR prover and
+//! verifier, to audit that no claim is dropped as the protocol grows.
+//!
+//! The claim on the outputs of the circuit is made first. The sumcheck of layer `i` discharges
+//! the claims on its outputs, i.e., on the inputs of layer `i + 1`, and makes claims on its
+//! inputs. The skip inputs of layer `i` make claims on the layers they read, discharged with the
+//! claims on the inputs of those layers by the reduction of `crate::prover::skip_claims`. The
+//! claims left on the inputs of layer 0 are discharged by the opening of the PCS. Once a proof
+//! or a verification is done, no claim is to be left open, which debug builds assert.
+//!
+//! The ledger is only kept in debug builds, recording is a no-op otherwise.
+
+use std::fmt;
+
+use gkr_engine::{ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine};
+use serdes::ExpSerde;
+
+use crate::SkipClaim;
+
+/// The polynomial of a layer a claim is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ExpSerde)]
+pub enum ClaimedPoly {
+    /// The input values of the layer, those of the layer past the last being the outputs of the
+    /// circuit
+    Inputs,
+    /// The values the skip inputs of the given index add to the outputs of the layer
+    SkipInputs(usize),
+}
+
+/// A claim that a polynomial of a layer evaluates to `value` at `point`, the evaluations at
+/// `point.rz_0` and `point.rz_1` being combined with `alpha` if the claim is on both
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct EvalClaim<F: FieldEngine> {
+    pub layer: usize,
+    pub poly: ClaimedPoly,
+    pub point: ExpanderDualVarChallenge<F>,
+    pub alpha: Option<F::ChallengeField>,
+    pub value: F::ChallengeField,
+}
+
+impl<F: FieldEngine> EvalClaim<F> {
+    /// A claim on the inputs of `layer` at a single point
+    #[inline]
+    pub fn inputs(
+        layer: usize,
+        point: &ExpanderSingleVarChallenge<F>,
+        value: F::ChallengeField,
+    ) -> Self {
+        Self {
+            layer,
+            poly: ClaimedPoly::Inputs,
+            point: point.into(),
+            alpha: None,
+            value,
+        }
+    }
+
+    /// The claims on the inputs of `layer` at `rz_0` and at `rz_1` if any, with their values
+    pub fn inputs_at(
+        layer: usize,
+        point: &ExpanderDualVarChallenge<F>,
+        value_0: F::ChallengeField,
+        value_1: Option<F::ChallengeField>,
+    ) -> Vec<Self> {
+        let mut claims = vec![Self::inputs(layer, &point.challenge_x(), value_0)];
+        if let (Some(point_y), Some(value_1)) = (point.challenge_y(), value_1) {
+            claims.push(Self::inputs(layer, &point_y, value_1));
+        }
+        claims
+    }
+
+    /// The claim on the skip inputs of `skip_claim`
+    #[inline]
+    pub fn skip_inputs(skip_claim: &SkipClaim<F>, value: F::ChallengeField) -> Self {
+        Self {
+            layer: skip_claim.layer,
+            poly: ClaimedPoly::SkipInputs(skip_claim.skip),
+            point: skip_claim.challenge.clone(),
+            alpha: skip_claim.alpha,
+            value,
+        }
+    }
+
+    /// Whether the claim is on the same polynomial at the same point as `other`
+    #[inline]
+    fn is_at(&self, other: &Self) -> bool {
+        self.layer == other.layer
+            && self.poly == other.poly
+            && self.point == other.point
+            && self.alpha == other.alpha
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ExpSerde)]
+pub enum ClaimEvent {
+    Made,
+    Discharged,
+}
+
+/// The claims made and discharged so far, in order
+#[derive(Debug, Clone, Default, PartialEq, ExpSerde)]
+pub struct ClaimLedger<F: FieldEngine> {
+    pub entries: Vec<(ClaimEvent, EvalClaim<F>)>,
+}
+
+/// The outcome of `ClaimLedger::audit`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimAudit<'a, F: FieldEngine> {
+    /// the claims made and never discharged
+    pub open: Vec<&'a EvalClaim<F>>,
+    /// the claims discharged without being made before, with the same value
+    pub unmade: Vec<&'a EvalClaim<F>>,
+}
+
+impl<F: FieldEngine> ClaimAudit<'_, F> {
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.open.is_empty() && self.unmade.is_empty()
+    }
+}
+
+impl<F: FieldEngine> ClaimLedger<F> {
+    /// Whether the claims are recorded, i.e., in debug builds
+    pub const ENABLED: bool = cfg!(debug_assertions);
+
+    #[inline]
+    pub fn make(&mut self, claim: EvalClaim<F>) {
+        if Self::ENABLED {
+            self.entries.push((ClaimEvent::Made, claim));
+        }
+    }
+
+    #[inline]
+    pub fn discharge(&mut self, claim: EvalClaim<F>) {
+        if Self::ENABLED {
+            self.entries.push((ClaimEvent::Discharged, claim));
+        }
+    }
+
+    /// Discharge the open claim on the same polynomial at the same point as `claim`, whatever its
+    /// value, for the prover which does not carry the values of its claims from layer to layer.
+    /// Without such a claim, `claim` is discharged as is.
+    pub fn discharge_at(&mut self, claim: EvalClaim<F>) {
+        if !Self::ENABLED {
+            return;
+        }
+        let made = self
+            .audit()
+            .open
+            .into_iter()
+            .find(|open| open.is_at(&claim))
+            .cloned();
+        self.discharge(made.unwrap_or(claim));
+    }
+
+    /// The claims left open and the claims discharged without being made, a discharge matching
+    /// the earliest open claim equal to it
+    pub fn audit(&self) -> ClaimAudit<'_, F> {
+        let mut open: Vec<&EvalClaim<F>> = vec![];
+        let mut unmade = vec![];
+        for (event, claim) in &self.entries {
+            match event {
+                ClaimEvent::Made => open.push(claim),
+                ClaimEvent::Discharged => match open.iter().position(|c| *c == claim) {
+                    Some(idx) => {
+                        open.remove(idx);
+                    }
+                    None => unmade.push(claim),
+                },
+            }
+        }
+        ClaimAudit { open, unmade }
+    }
+}
+
+impl<F: FieldEngine> fmt::Display for ClaimLedger<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let write_claim = |f: &mut fmt::Formatter<'_>, prefix: &str, claim: &EvalClaim<F>| {
+            writeln!(
+                f,
+                "{prefix:<12} layer {:>4} {:<16} = {:?} at {:?}, alpha {:?}",
+                claim.layer,
+                format!("{:?}", claim.poly),
+                claim.value,
+                claim.point,
+                claim.alpha
+            )
+        };
+
+        for (event, claim) in &self.entries {
+            write_claim(f, &format!("{event:?}"), claim)?;
+        }
+        let audit = self.audit();
+        for claim in &audit.open {
+            write_claim(f, "OPEN", claim)?;
+        }
+        for claim in &audit.unmade {
+            write_claim(f, "UNMADE", claim)?;
+        }
+        writeln!(
+            f,
+            "{} claims, {} open, {} discharged without being made",
+            self.entries.len(),
+            audit.open.len(),
+            audit.unmade.len()
+        )
+    }
+}
@@ -0,0 +1,457 @@
+//! Proofs of circuits assembled from separately compiled components.
+//!
+//! A composite is made of components, each a circuit proven on its own or a composite itself,
+//! whose shared wires are linked: a `WireLink` states that an input of a circuit carries the same
+//! value as an input of another. The proof of a composite, a `CompositeProof`, nests the proofs
+//! of its components, and proves the links by the glue argument: both linked inputs are opened
+//! against the commitments to the inputs of their circuits, found in the proofs of the circuits,
+//! to the value the link carries.
+//!
+//! The components and the links are the statement, known to both the prover and the verifier,
+//! so the proof does not carry them. The glue transcript starts from the serialized proofs of
+//! the components, binding the openings to them.
+//!
+//! All the circuits of a composite are proven with the same config, and by the same MPI
+//! processes, under the current version of the protocol, see `ProofVersion`.
+
+use circuit::Circuit;
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, GKREngine, MPIEngine, MPIResult, Proof, StructuredReferenceString,
+    Transcript,
+};
+use poly_commit::{position_challenge, position_values};
+use polynomials::RefMultiLinearPoly;
+use serdes::ExpSerde;
+use transcript::{transcript_root_broadcast, transcript_verifier_sync};
+
+use crate::{Prover, Verifier};
+
+type ChallengeField<Cfg> = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField;
+type SimdCircuitField<Cfg> = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::SimdCircuitField;
+type PCSParams<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::Params;
+type PCSScratchPad<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::ScratchPad;
+type PCSCommitment<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::Commitment;
+type PCSOpening<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::Opening;
+type PCSSRS<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::SRS;
+type PCSProvingKey<Cfg> = <PCSSRS<Cfg> as StructuredReferenceString>::PKey;
+type PCSVerifyingKey<Cfg> = <PCSSRS<Cfg> as StructuredReferenceString>::VKey;
+
+const DOMAIN_SEPARATOR: &[u8] = b"expander composite proof";
+
+/// A wire of a composite: the input at `position` of the circuit reached through the components
+/// at `path`, from the composite down.
+///
+/// Positions index the circuit field elements of the global input of the circuit, LSB-first:
+/// the SIMD lane, then the local variables, then the MPI rank.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ExpSerde)]
+pub struct WireRef {
+    pub path: Vec<usize>,
+    pub position: usize,
+}
+
+impl WireRef {
+    #[inline]
+    pub fn new(path: Vec<usize>, position: usize) -> Self {
+        Self { path, position }
+    }
+}
+
+/// Two wires carrying the same value
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ExpSerde)]
+pub struct WireLink {
+    pub from: WireRef,
+    pub to: WireRef,
+}
+
+/// The proof of a component of a composite
+#[derive(Debug, Clone, ExpSerde)]
+pub enum ComponentProof<Cfg: GKREngine> {
+    /// The proof of a circuit, and the output claim it proves
+    Circuit {
+        claimed_v: ChallengeField<Cfg>,
+        proof: Proof,
+    },
+    Composite(CompositeProof<Cfg>),
+}
+
+/// The proof of a composite: the proofs of its components, and the glue argument of its links.
+#[derive(Debug, Clone, ExpSerde)]
+pub struct CompositeProof<Cfg: GKREngine> {
+    pub components: Vec<ComponentProof<Cfg>>,
+    /// the value carried by each link
+    pub values: Vec<ChallengeField<Cfg>>,
+    /// the openings of the `from` ends of the links
+    pub from_openings: Vec<PCSOpening<Cfg>>,
+    /// the openings of the `to` ends of the links
+    pub to_openings: Vec<PCSOpening<Cfg>>,
+}
+
+impl<Cfg: GKREngine> CompositeProof<Cfg> {
+    /// The proof of the circuit at `path`
+    pub fn circuit_proof(&self, path: &[usize]) -> Option<&Proof> {
+        let (first, rest) = path.split_first()?;
+        match self.components.get(*first)? {
+            ComponentProof::Circuit { proof, .. } if rest.is_empty() => Some(proof),
+            ComponentProof::Composite(composite) => composite.circuit_proof(rest),
+            _ => None,
+        }
+    }
+}
+
+/// A component of a composite, on the prover side
+pub enum ProverComponent<'a, Cfg: GKREngine> {
+    /// A circuit with its witness loaded, and the keys of its PCS
+    Circuit {
+        circuit: &'a mut Circuit<Cfg::FieldConfig>,
+        pcs_params: &'a PCSParams<Cfg>,
+        pcs_proving_key: &'a PCSProvingKey<Cfg>,
+    },
+    Composite {
+        components: Vec<ProverComponent<'a, Cfg>>,
+        links: Vec<WireLink>,
+    },
+}
+
+/// A component of a composite, on the verifier side
+pub enum VerifierComponent<'a, Cfg: GKREngine> {
+    /// A circuit, its public input gathered from all the processes, and the keys of its PCS
+    Circuit {
+        circuit: &'a mut Circuit<Cfg::FieldConfig>,
+        public_input: &'a [SimdCircuitField<Cfg>],
+        pcs_params: &'a PCSParams<Cfg>,
+        pcs_verification_key: &'a PCSVerifyingKey<Cfg>,
+    },
+    Composite {
+        components: Vec<VerifierComponent<'a, Cfg>>,
+        links: Vec<WireLink>,
+    },
+}
+
+/// The scratch pads of the PCS of the circuits once proven, kept for the glue openings
+enum ProvenComponent<Cfg: GKREngine> {
+    Circuit(PCSScratchPad<Cfg>),
+    Composite(Vec<ProvenComponent<Cfg>>),
+}
+
+/// The circuit at `path`, with the keys of its PCS and the scratch pad of its proof
+#[allow(clippy::type_complexity)]
+fn prover_circuit_at<'c, Cfg: GKREngine>(
+    components: &'c [ProverComponent<'_, Cfg>],
+    proven: &'c [ProvenComponent<Cfg>],
+    path: &[usize],
+) -> Option<(
+    &'c Circuit<Cfg::FieldConfig>,
+    &'c PCSParams<Cfg>,
+    &'c PCSProvingKey<Cfg>,
+    &'c PCSScratchPad<Cfg>,
+)> {
+    let (first, rest) = path.split_first()?;
+    match (components.get(*first)?, proven.get(*first)?) {
+        (
+            ProverComponent::Circuit {
+                circuit,
+                pcs_params,
+                pcs_proving_key,
+            },
+            ProvenComponent::Circuit(scratch_pad),
+        ) if rest.is_empty() => Some((&**circuit, *pcs_params, *pcs_proving_key, scratch_pad)),
+        (ProverComponent::Composite { components, .. }, ProvenComponent::Composite(proven)) => {
+            prover_circuit_at(components, proven, rest)
+        }
+        _ => None,
+    }
+}
+
+/// The circuit at `path`, with the keys of its PCS
+#[allow(clippy::type_complexity)]
+fn verifier_circuit_at<'c, Cfg: GKREngine>(
+    components: &'c [VerifierComponent<'_, Cfg>],
+    path: &[usize],
+) -> Option<(
+    &'c Circuit<Cfg::FieldConfig>,
+    &'c PCSParams<Cfg>,
+    &'c PCSVerifyingKey<Cfg>,
+)> {
+    let (first, rest) = path.split_first()?;
+    match components.get(*first)? {
+        VerifierComponent::Circuit {
+            circuit,
+            pcs_params,
+            pcs_verification_key,
+            ..
+        } if rest.is_empty() => Some((&**circuit, *pcs_params, *pcs_verification_key)),
+        VerifierComponent::Composite { components, .. } => verifier_circuit_at(components, rest),
+        _ => None,
+    }
+}
+
+/// The commitment to the inputs a proof of a circuit starts with, after the config fingerprint
+fn circuit_commitment<Cfg: GKREngine>(proof: &Proof) -> Option<PCSCommitment<Cfg>> {
+    let bytes = proof.bytes.strip_prefix(&Cfg::FINGERPRINT)?;
+    PCSCommitment::<Cfg>::deserialize_from(bytes).ok()
+}
+
+/// The number of variables of the global input of `circuit`, over the circuit field elements
+#[inline]
+fn global_input_num_vars<Cfg: GKREngine>(
+    circuit: &Circuit<Cfg::FieldConfig>,
+    world_size: usize,
+) -> usize {
+    circuit.log_input_size()
+        + <Cfg::FieldConfig as FieldEngine>::get_field_pack_size().ilog2() as usize
+        + world_size.next_power_of_two().ilog2() as usize
+}
+
+/// Bind the glue transcript to the proofs of the components, to the links and to their values.
+fn absorb_glue<Cfg: GKREngine>(
+    transcript: &mut impl Transcript,
+    components: &[ComponentProof<Cfg>],
+    links: &[WireLink],
+    values: &[ChallengeField<Cfg>],
+) {
+    transcript.append_domain_separator(DOMAIN_SEPARATOR);
+    let mut buffer = vec![];
+    components
+        .iter()
+        .for_each(|component| component.serialize_into(&mut buffer).unwrap());
+    links
+        .iter()
+        .for_each(|link| link.serialize_into(&mut buffer).unwrap());
+    transcript.append_u8_slice(&buffer);
+    values
+        .iter()
+        .for_each(|value| transcript.append_field_element(value));
+}
+
+impl<Cfg: GKREngine> Prover<'_, Cfg> {
+    /// Prove the composite of `components`, whose wires are linked by `links`: each circuit is
+    /// proven with `prove`, then the links of each composite by the glue argument.
+    ///
+    /// Only the proof on the root process is meaningful. Fails if the processes fail to
+    /// communicate.
+    ///
+    /// # Panics
+    /// If a link refers to no circuit, or to a position out of the inputs of its circuit.
+    pub fn prove_composite(
+        &mut self,
+        components: &mut [ProverComponent<'_, Cfg>],
+        links: &[WireLink],
+    ) -> MPIResult<CompositeProof<Cfg>> {
+        let (proof, _) = self.prove_composite_components(components, links)?;
+        Ok(proof)
+    }
+
+    fn prove_composite_components(
+        &mut self,
+        components: &mut [ProverComponent<'_, Cfg>],
+        links: &[WireLink],
+    ) -> MPIResult<(CompositeProof<Cfg>, Vec<ProvenComponent<Cfg>>)> {
+        let mut component_proofs = Vec::with_capacity(components.len());
+        let mut proven = Vec::with_capacity(components.len());
+        for component in components.iter_mut() {
+            match component {
+                ProverComponent::Circuit {
+                    circuit,
+                    pcs_params,
+                    pcs_proving_key,
+                } => {
+                    let mut scratch_pad =
+                        Cfg::PCSConfig::init_scratch_pad(pcs_params, &self.mpi_config);
+                    self.prepare_mem(circuit);
+                    let (claimed_v, proof) =
+                        self.prove(circuit, pcs_params, pcs_proving_key, &mut scratch_pad)?;
+                    component_proofs.push(ComponentProof::Circuit { claimed_v, proof });
+                    proven.push(ProvenComponent::Circuit(scratch_pad));
+                }
+                ProverComponent::Composite { components, links } => {
+                    let (proof, inner) = self.prove_composite_components(components, links)?;
+                    component_proofs.push(ComponentProof::Composite(proof));
+                    proven.push(ProvenComponent::Composite(inner));
+                }
+            }
+        }
+
+        // NOTE: the value of a link is read from its `from` end, the `to` end opens to it or the
+        // glue argument does not verify
+        let values = links
+            .iter()
+            .map(|link| {
+                let (circuit, ..) = prover_circuit_at(components, &proven, &link.from.path)
+                    .expect("link from no circuit");
+                let inputs = RefMultiLinearPoly::from_ref(&circuit.layers[0].input_vals);
+                Ok(position_values::<Cfg::FieldConfig>(
+                    &self.mpi_config,
+                    &inputs,
+                    &[link.from.position],
+                )?[0])
+            })
+            .collect::<MPIResult<Vec<_>>>()?;
+
+        let mut transcript = Cfg::TranscriptConfig::new();
+        absorb_glue::<Cfg>(&mut transcript, &component_proofs, links, &values);
+
+        let world_size = self.mpi_config.world_size();
+        let mut open_wire = |wire: &WireRef| -> MPIResult<PCSOpening<Cfg>> {
+            let (circuit, pcs_params, pcs_proving_key, scratch_pad) =
+                prover_circuit_at(components, &proven, &wire.path).expect("link to no circuit");
+            let num_vars = global_input_num_vars::<Cfg>(circuit, world_size);
+            assert!(wire.position < 1 << num_vars, "position out of the inputs");
+
+            transcript_root_broadcast(&mut transcript, &self.mpi_config)?;
+            let x = position_challenge::<Cfg::FieldConfig>(wire.position, num_vars, world_size);
+            transcript.lock_proof();
+            let opening = Cfg::PCSConfig::open(
+                pcs_params,
+                &self.mpi_config,
+                pcs_proving_key,
+                &RefMultiLinearPoly::from_ref(&circuit.layers[0].input_vals),
+                &x,
+                &mut transcript,
+                scratch_pad,
+            );
+            transcript.unlock_proof();
+            let opening = opening?.unwrap_or_default();
+
+            let mut buffer = vec![];
+            opening.serialize_into(&mut buffer).unwrap();
+            transcript.append_u8_slice(&buffer);
+            Ok(opening)
+        };
+
+        let mut from_openings = Vec::with_capacity(links.len());
+        let mut to_openings = Vec::with_capacity(links.len());
+        for link in links {
+            from_openings.push(open_wire(&link.from)?);
+            to_openings.push(open_wire(&link.to)?);
+        }
+
+        Ok((
+            CompositeProof {
+                components: component_proofs,
+                values,
+                from_openings,
+                to_openings,
+            },
+            proven,
+        ))
+    }
+}
+
+impl<Cfg: GKREngine> Verifier<'_, Cfg> {
+    /// Verify the proof of the composite of `components`, whose wires are linked by `links`: the
+    /// proof of each circuit with `verify`, and the glue argument of the links of each composite.
+    pub fn verify_composite(
+        &self,
+        components: &mut [VerifierComponent<'_, Cfg>],
+        links: &[WireLink],
+        proof: &CompositeProof<Cfg>,
+    ) -> bool {
+        if proof.components.len() != components.len() {
+            return false;
+        }
+
+        for (component, component_proof) in components.iter_mut().zip(&proof.components) {
+            let verified = match (component, component_proof) {
+                (
+                    VerifierComponent::Circuit {
+                        circuit,
+                        public_input,
+                        pcs_params,
+                        pcs_verification_key,
+                    },
+                    ComponentProof::Circuit { claimed_v, proof },
+                ) => self.verify(
+                    circuit,
+                    public_input,
+                    claimed_v,
+                    pcs_params,
+                    pcs_verification_key,
+                    proof,
+                ),
+                (
+                    VerifierComponent::Composite { components, links },
+                    ComponentProof::Composite(proof),
+                ) => self.verify_composite(components, links, proof),
+                _ => false,
+            };
+            if !verified {
+                return false;
+            }
+        }
+
+        self.verify_glue(components, links, proof)
+    }
+
+    fn verify_glue(
+        &self,
+        components: &[VerifierComponent<'_, Cfg>],
+        links: &[WireLink],
+        proof: &CompositeProof<Cfg>,
+    ) -> bool {
+        if proof.values.len() != links.len()
+            || proof.from_openings.len() != links.len()
+            || proof.to_openings.len() != links.len()
+        {
+            return false;
+        }
+
+        let mut transcript = Cfg::TranscriptConfig::new();
+        absorb_glue::<Cfg>(&mut transcript, &proof.components, links, &proof.values);
+
+        let proving_time_mpi_size = self.mpi_config.world_size();
+        let mut verify_wire = |wire: &WireRef, value, opening: &PCSOpening<Cfg>| {
+            let Some((circuit, pcs_params, pcs_verification_key)) =
+                verifier_circuit_at(components, &wire.path)
+            else {
+                return false;
+            };
+            let Some(commitment) = proof
+                .circuit_proof(&wire.path)
+                .and_then(circuit_commitment::<Cfg>)
+            else {
+                return false;
+            };
+            let num_vars = global_input_num_vars::<Cfg>(circuit, proving_time_mpi_size);
+            if wire.position >= 1 << num_vars {
+                return false;
+            }
+
+            transcript_verifier_sync(&mut transcript, proving_time_mpi_size);
+            let x = position_challenge::<Cfg::FieldConfig>(
+                wire.position,
+                num_vars,
+                proving_time_mpi_size,
+            );
+            transcript.lock_proof();
+            let verified = Cfg::PCSConfig::verify(
+                pcs_params,
+                pcs_verification_key,
+                &commitment,
+                &x,
+                value,
+                &mut transcript,
+                opening,
+            );
+            transcript.unlock_proof();
+
+            let mut buffer = vec![];
+            opening.serialize_into(&mut buffer).unwrap();
+            transcript.append_u8_slice(&buffer);
+            verified
+        };
+
+        links
+            .iter()
+            .zip(&proof.values)
+            .zip(proof.from_openings.iter().zip(&proof.to_openings))
+            .all(|((link, &value), (from_opening, to_opening))| {
+                verify_wire(&link.from, value, from_opening)
+                    && verify_wire(&link.to, value, to_opening)
+            })
+    }
+}
@@ -1,17 +1,65 @@
+#[cfg(all(feature = "babybear", feature = "orion"))]
+use babybear::BabyBearx16;
 use config_macros::declare_gkr_config;
+#[cfg(all(feature = "gf2", feature = "orion"))]
 use gf2::GF2x128;
-use gkr_engine::{
-    BN254Config, BabyBearx16Config, FieldEngine, GF2ExtConfig, GKREngine, GKRScheme,
-    Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x1Config, MPIConfig,
-};
-use gkr_hashers::{MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+#[cfg(feature = "bn254")]
+use gkr_engine::BN254Config;
+#[cfg(feature = "babybear")]
+use gkr_engine::BabyBearx16Config;
+#[cfg(any(feature = "anemoi", feature = "griffin", feature = "mimc"))]
+use gkr_engine::FieldEngine;
+#[cfg(feature = "gf2")]
+use gkr_engine::GF2ExtConfig;
+use gkr_engine::{GKREngine, GKRScheme, MPIConfig};
+#[cfg(feature = "goldilocks")]
+use gkr_engine::{Goldilocksx1Config, Goldilocksx8Config};
+#[cfg(feature = "m31")]
+use gkr_engine::{M31x16Config, M31x1Config};
+#[cfg(feature = "anemoi")]
+use gkr_hashers::AnemoiFiatShamirHasher;
+#[cfg(feature = "griffin")]
+use gkr_hashers::GriffinFiatShamirHasher;
+#[cfg(feature = "mimc")]
+use gkr_hashers::MiMC5FiatShamirHasher;
+#[cfg(feature = "poseidon")]
+use gkr_hashers::PoseidonFiatShamirHasher;
+use gkr_hashers::SHA256hasher;
+#[cfg(all(feature = "goldilocks", feature = "orion"))]
 use goldilocks::Goldilocksx8;
-use halo2curves::bn256::{Bn256, G1Affine};
+#[cfg(any(feature = "dory", feature = "kzg"))]
+use halo2curves::bn256::Bn256;
+#[cfg(any(feature = "hyrax", feature = "ipa"))]
+use halo2curves::bn256::G1Affine;
+#[cfg(all(feature = "m31", feature = "orion"))]
 use mersenne31::M31x16;
-use poly_commit::{raw::RawExpanderGKR, HyperBiKZGPCS, HyraxPCS, OrionPCSForGKR};
+use poly_commit::raw::RawExpanderGKR;
+#[cfg(feature = "raw-distributed")]
+use poly_commit::raw::RawExpanderGKRDistributed;
+#[cfg(feature = "basefold")]
+use poly_commit::BasefoldPCSForGKR;
+#[cfg(feature = "brakedown")]
+use poly_commit::BrakedownPCSForGKR;
+#[cfg(feature = "dory")]
+use poly_commit::DoryPCS;
+#[cfg(feature = "kzg")]
+use poly_commit::HyperBiKZGPCS;
+#[cfg(feature = "hyrax")]
+use poly_commit::HyraxPCS;
+#[cfg(feature = "ipa")]
+use poly_commit::IpaPCS;
+#[cfg(feature = "ligero")]
+use poly_commit::LigeroPCSForGKR;
+#[cfg(feature = "orion")]
+use poly_commit::OrionPCSForGKR;
+#[cfg(feature = "whir")]
+use poly_commit::WhirPCSForGKR;
+#[cfg(feature = "kzg")]
+use poly_commit::ZeromorphPCS;
 use transcript::BytesHashTranscript;
 
 // ============== M31 ==============
+#[cfg(feature = "m31")]
 declare_gkr_config!(
     pub M31x1ConfigSha2RawVanilla,
     FieldType::M31x1,
@@ -20,6 +68,7 @@ declare_gkr_config!(
     GKRScheme::Vanilla,
 );
 // ============== M31Ext3 ==============
+#[cfg(all(feature = "m31", feature = "poseidon"))]
 declare_gkr_config!(
     pub M31x16ConfigPoseidonRawVanilla,
     FieldType::M31x16,
@@ -27,6 +76,7 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "m31", feature = "poseidon"))]
 declare_gkr_config!(
     pub M31x16ConfigPoseidonRawSquare,
     FieldType::M31x16,
@@ -34,6 +84,7 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::GkrSquare,
 );
+#[cfg(all(feature = "m31", feature = "orion"))]
 declare_gkr_config!(
     pub M31x16ConfigSha2OrionVanilla,
     FieldType::M31x16,
@@ -41,6 +92,7 @@ declare_gkr_config!(
     PolynomialCommitmentType::Orion,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "m31", feature = "orion"))]
 declare_gkr_config!(
     pub M31x16ConfigSha2OrionSquare,
     FieldType::M31x16,
@@ -48,6 +100,23 @@ declare_gkr_config!(
     PolynomialCommitmentType::Orion,
     GKRScheme::GkrSquare,
 );
+#[cfg(all(feature = "m31", feature = "brakedown"))]
+declare_gkr_config!(
+    pub M31x16ConfigSha2BrakedownVanilla,
+    FieldType::M31x16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Brakedown,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "m31", feature = "basefold"))]
+declare_gkr_config!(
+    pub M31x16ConfigSha2BasefoldVanilla,
+    FieldType::M31x16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Basefold,
+    GKRScheme::Vanilla,
+);
+#[cfg(feature = "m31")]
 declare_gkr_config!(
     pub M31x16ConfigSha2RawVanilla,
     FieldType::M31x16,
@@ -55,6 +124,15 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "m31", feature = "raw-distributed"))]
+declare_gkr_config!(
+    pub M31x16ConfigSha2RawDistributedVanilla,
+    FieldType::M31x16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::RawDistributed,
+    GKRScheme::Vanilla,
+);
+#[cfg(feature = "m31")]
 declare_gkr_config!(
     pub M31x16ConfigSha2RawSquare,
     FieldType::M31x16,
@@ -64,6 +142,7 @@ declare_gkr_config!(
 );
 
 // ============== BN254 ==============
+#[cfg(all(feature = "bn254", feature = "mimc"))]
 declare_gkr_config!(
     pub BN254ConfigMIMC5Raw,
     FieldType::BN254,
@@ -71,6 +150,7 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+#[cfg(feature = "bn254")]
 declare_gkr_config!(
     pub BN254ConfigSha2Raw,
     FieldType::BN254,
@@ -78,6 +158,31 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "bn254", feature = "raw-distributed"))]
+declare_gkr_config!(
+    pub BN254ConfigSha2RawDistributed,
+    FieldType::BN254,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::RawDistributed,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "bn254", feature = "anemoi"))]
+declare_gkr_config!(
+    pub BN254ConfigAnemoiRaw,
+    FieldType::BN254,
+    FiatShamirHashType::Anemoi,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "bn254", feature = "griffin"))]
+declare_gkr_config!(
+    pub BN254ConfigGriffinRaw,
+    FieldType::BN254,
+    FiatShamirHashType::Griffin,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "bn254", feature = "hyrax"))]
 declare_gkr_config!(
     pub BN254ConfigSha2Hyrax,
     FieldType::BN254,
@@ -85,6 +190,7 @@ declare_gkr_config!(
     PolynomialCommitmentType::Hyrax,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "bn254", feature = "kzg"))]
 declare_gkr_config!(
     pub BN254ConfigSha2KZG,
     FieldType::BN254,
@@ -92,6 +198,7 @@ declare_gkr_config!(
     PolynomialCommitmentType::KZG,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "bn254", feature = "mimc", feature = "kzg"))]
 declare_gkr_config!(
     pub BN254ConfigMIMC5KZG,
     FieldType::BN254,
@@ -99,8 +206,33 @@ declare_gkr_config!(
     PolynomialCommitmentType::KZG,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "bn254", feature = "mimc", feature = "kzg"))]
+declare_gkr_config!(
+    pub BN254ConfigMIMC5Zeromorph,
+    FieldType::BN254,
+    FiatShamirHashType::MIMC5,
+    PolynomialCommitmentType::Zeromorph,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "bn254", feature = "dory"))]
+declare_gkr_config!(
+    pub BN254ConfigSha2Dory,
+    FieldType::BN254,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Dory,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "bn254", feature = "ipa"))]
+declare_gkr_config!(
+    pub BN254ConfigSha2IPA,
+    FieldType::BN254,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::IPA,
+    GKRScheme::Vanilla,
+);
 
 // ============== GF2 ==============
+#[cfg(all(feature = "gf2", feature = "orion"))]
 declare_gkr_config!(
     pub GF2ExtConfigSha2Orion,
     FieldType::GF2Ext128,
@@ -108,6 +240,7 @@ declare_gkr_config!(
     PolynomialCommitmentType::Orion,
     GKRScheme::Vanilla,
 );
+#[cfg(feature = "gf2")]
 declare_gkr_config!(
     pub GF2ExtConfigSha2Raw,
     FieldType::GF2Ext128,
@@ -115,8 +248,17 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "gf2", feature = "raw-distributed"))]
+declare_gkr_config!(
+    pub GF2ExtConfigSha2RawDistributed,
+    FieldType::GF2Ext128,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::RawDistributed,
+    GKRScheme::Vanilla,
+);
 
 // ============== Goldilocks ==============
+#[cfg(feature = "goldilocks")]
 declare_gkr_config!(
     pub Goldilocksx1ConfigSha2Raw,
     FieldType::Goldilocksx1,
@@ -126,6 +268,7 @@ declare_gkr_config!(
 );
 
 // ============== GoldilocksExt2 ==============
+#[cfg(feature = "goldilocks")]
 declare_gkr_config!(
     pub Goldilocksx8ConfigSha2Raw,
     FieldType::Goldilocksx8,
@@ -133,7 +276,16 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "goldilocks", feature = "raw-distributed"))]
+declare_gkr_config!(
+    pub Goldilocksx8ConfigSha2RawDistributed,
+    FieldType::Goldilocksx8,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::RawDistributed,
+    GKRScheme::Vanilla,
+);
 
+#[cfg(all(feature = "goldilocks", feature = "orion"))]
 declare_gkr_config!(
     pub Goldilocksx8ConfigSha2Orion,
     FieldType::Goldilocksx8,
@@ -142,7 +294,35 @@ declare_gkr_config!(
     GKRScheme::Vanilla,
 );
 
+#[cfg(all(feature = "goldilocks", feature = "basefold"))]
+declare_gkr_config!(
+    pub Goldilocksx8ConfigSha2Basefold,
+    FieldType::Goldilocksx8,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Basefold,
+    GKRScheme::Vanilla,
+);
+
+#[cfg(all(feature = "goldilocks", feature = "ligero"))]
+declare_gkr_config!(
+    pub Goldilocksx8ConfigSha2Ligero,
+    FieldType::Goldilocksx8,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Ligero,
+    GKRScheme::Vanilla,
+);
+
+#[cfg(all(feature = "goldilocks", feature = "whir"))]
+declare_gkr_config!(
+    pub Goldilocksx8ConfigSha2Whir,
+    FieldType::Goldilocksx8,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Whir,
+    GKRScheme::Vanilla,
+);
+
 // ============== Babybear ==============
+#[cfg(feature = "babybear")]
 declare_gkr_config!(
     pub BabyBearx16ConfigSha2Raw,
     FieldType::BabyBearx16,
@@ -150,3 +330,27 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+#[cfg(all(feature = "babybear", feature = "orion"))]
+declare_gkr_config!(
+    pub BabyBearx16ConfigSha2Orion,
+    FieldType::BabyBearx16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Orion,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "babybear", feature = "basefold"))]
+declare_gkr_config!(
+    pub BabyBearx16ConfigSha2Basefold,
+    FieldType::BabyBearx16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Basefold,
+    GKRScheme::Vanilla,
+);
+#[cfg(all(feature = "babybear", feature = "whir"))]
+declare_gkr_config!(
+    pub BabyBearx16ConfigSha2Whir,
+    FieldType::BabyBearx16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Whir,
+    GKRScheme::Vanilla,
+);
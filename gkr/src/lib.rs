@@ -6,6 +6,18 @@ pub use prover::*;
 pub mod verifier;
 pub use verifier::*;
 
+pub mod claim_ledger;
+pub use claim_ledger::*;
+
+pub mod composite;
+pub use composite::*;
+
+pub mod proof_session;
+pub use proof_session::*;
+
+pub mod small_proof;
+pub use small_proof::*;
+
 pub mod utils;
 
 pub mod gkr_configs;
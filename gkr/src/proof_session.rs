@@ -0,0 +1,200 @@
+//! Several proofs over one transcript.
+//!
+//! A `ProofSession` hosts a sequence of proofs, e.g., of the same circuit twice, whose
+//! transcripts are chained: the transcript of a proof starts from the state of the session,
+//! bound to the index of the proof and to the digest of its circuit, and the session absorbs the
+//! proof once it is done. A proof thus depends on all the proofs before it in the session, and a
+//! follow-up proof, or the protocol around the proofs, can draw challenges from the state the
+//! session was in right after an earlier proof with `ProofSession::challenges_of`.
+//!
+//! The prover and the verifier run the same session: the proofs are verified in the order they
+//! were proven, each with the digest it was proven with.
+
+use circuit::{Circuit, LayerRetention};
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, GKREngine, MPIEngine, MPIResult, Proof, StructuredReferenceString,
+    Transcript,
+};
+use gkr_hashers::{FiatShamirHasher, SHA256hasher};
+use transcript::{transcript_root_broadcast, transcript_verifier_sync};
+
+use crate::{Prover, Verifier};
+
+type ChallengeField<Cfg> = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField;
+
+const SESSION_DOMAIN_SEPARATOR: &[u8] = b"expander proof session";
+const PROOF_DOMAIN_SEPARATOR: &[u8] = b"expander session proof";
+const REFERENCE_DOMAIN_SEPARATOR: &[u8] = b"expander session reference";
+
+/// SHA256 of the serialized circuit, e.g., of the circuit file, to bind a proof of a session to
+pub fn circuit_digest(circuit_bytes: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    SHA256hasher.hash(&mut digest, circuit_bytes);
+    digest
+}
+
+/// A proof of a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionEntry {
+    pub circuit_digest: [u8; 32],
+    /// The state of the session right after it absorbed the proof
+    pub state: Vec<u8>,
+}
+
+/// A transcript hosting several proofs, see the module documentation.
+pub struct ProofSession<Cfg: GKREngine> {
+    transcript: Cfg::TranscriptConfig,
+    entries: Vec<SessionEntry>,
+}
+
+// NOTE: not derived, which would require the config itself to be `Clone`
+impl<Cfg: GKREngine> Clone for ProofSession<Cfg> {
+    fn clone(&self) -> Self {
+        Self {
+            transcript: self.transcript.clone(),
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<Cfg: GKREngine> ProofSession<Cfg> {
+    /// A session separated by `label` from the sessions of other protocols
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Cfg::TranscriptConfig::new();
+        transcript.append_domain_separator(SESSION_DOMAIN_SEPARATOR);
+        transcript.append_domain_separator(label);
+        Self {
+            transcript,
+            entries: vec![],
+        }
+    }
+
+    /// The number of proofs of the session so far, the index of the next one
+    #[inline]
+    pub fn num_proofs(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn entries(&self) -> &[SessionEntry] {
+        &self.entries
+    }
+
+    /// `n` challenges drawn from the state of the session right after the proof `index`, under
+    /// `label`, the same for the prover and the verifier. None if there is no such proof yet.
+    pub fn challenges_of(
+        &self,
+        index: usize,
+        label: &[u8],
+        n: usize,
+    ) -> Option<Vec<ChallengeField<Cfg>>> {
+        let entry = self.entries.get(index)?;
+        let mut transcript = Cfg::TranscriptConfig::new();
+        transcript.set_state(&entry.state);
+        transcript.append_domain_separator(REFERENCE_DOMAIN_SEPARATOR);
+        transcript.append_domain_separator(label);
+        Some(transcript.challenge_rlc(n))
+    }
+
+    /// The transcript of the next proof, of the circuit of `circuit_digest`
+    fn begin_proof(&mut self, circuit_digest: &[u8; 32]) -> Cfg::TranscriptConfig {
+        let mut transcript = Cfg::TranscriptConfig::new();
+        transcript.set_state(&self.transcript.hash_and_return_state());
+        transcript.append_domain_separator(PROOF_DOMAIN_SEPARATOR);
+        transcript.append_domain_separator(&(self.num_proofs() as u64).to_le_bytes());
+        transcript.append_domain_separator(circuit_digest);
+        transcript
+    }
+
+    /// Absorb the next proof, to be followed by `record_proof` once the session is in sync
+    fn absorb_proof(
+        &mut self,
+        circuit_digest: &[u8; 32],
+        claimed_v: &ChallengeField<Cfg>,
+        proof: &Proof,
+    ) {
+        self.transcript
+            .append_u8_slice(&(self.num_proofs() as u64).to_le_bytes());
+        self.transcript.append_u8_slice(circuit_digest);
+        self.transcript.append_field_element(claimed_v);
+        self.transcript.append_u8_slice(&proof.bytes);
+    }
+
+    fn record_proof(&mut self, circuit_digest: [u8; 32]) {
+        let state = self.transcript.hash_and_return_state();
+        self.entries.push(SessionEntry {
+            circuit_digest,
+            state,
+        });
+    }
+}
+
+impl<Cfg: GKREngine> Prover<'_, Cfg> {
+    /// Prove the evaluation of `c`, see `prove`, as the next proof of `session`, bound to the
+    /// digest `circuit_digest` of `c`, e.g., the `circuit_digest` of the circuit file.
+    ///
+    /// Every process must call it, the session being kept in sync with the root.
+    pub fn prove_in_session(
+        &mut self,
+        session: &mut ProofSession<Cfg>,
+        circuit_digest: [u8; 32],
+        c: &mut Circuit<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> MPIResult<(ChallengeField<Cfg>, Proof)> {
+        let transcript = session.begin_proof(&circuit_digest);
+        let (claimed_v, proof, _) = self.prove_from_transcript(
+            c,
+            LayerRetention::Full,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+            transcript,
+        )?;
+
+        // NOTE: the proof is that of the root, the session is synced up with it
+        session.absorb_proof(&circuit_digest, &claimed_v, &proof);
+        transcript_root_broadcast(&mut session.transcript, &self.mpi_config)?;
+        session.record_proof(circuit_digest);
+
+        Ok((claimed_v, proof))
+    }
+}
+
+impl<Cfg: GKREngine> Verifier<'_, Cfg> {
+    /// Verify `proof` as the next proof of `session`, see `verify` and `prove_in_session`. The
+    /// session is left as it was if the proof fails.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_in_session(
+        &self,
+        session: &mut ProofSession<Cfg>,
+        circuit_digest: [u8; 32],
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &ChallengeField<Cfg>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> bool {
+        let mut next = session.clone();
+        let mut transcript = next.begin_proof(&circuit_digest);
+        if !self.verify_with_transcript(
+            circuit,
+            public_input,
+            claimed_v,
+            pcs_params,
+            pcs_verification_key,
+            proof,
+            &mut transcript,
+        ) {
+            return false;
+        }
+
+        next.absorb_proof(&circuit_digest, claimed_v, proof);
+        transcript_verifier_sync(&mut next.transcript, self.mpi_config.world_size());
+        next.record_proof(circuit_digest);
+        *session = next;
+        true
+    }
+}
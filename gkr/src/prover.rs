@@ -6,3 +6,18 @@ pub use gkr_square::*;
 
 pub mod snark;
 pub use snark::*;
+
+pub mod draft;
+pub use draft::*;
+
+pub mod suspend;
+pub use suspend::*;
+
+pub mod checkpoint;
+pub use checkpoint::*;
+
+pub mod progress;
+pub use progress::*;
+
+pub mod skip_claims;
+pub use skip_claims::*;
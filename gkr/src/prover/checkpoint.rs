@@ -0,0 +1,280 @@
+//! Checkpointing a long proof to disk, to restart it after a process is preempted.
+//!
+//! Every `interval` layers of the vanilla GKR, each MPI process stores its `SuspendedProof` in
+//! its own file of the checkpoint directory, which must be reachable by every process, e.g., a
+//! shared file system. Checkpoints are numbered by generation, the commit phase being generation
+//! 0, and a process drops its older generations only once the processes have agreed that every
+//! one of them has stored the new one. Whenever a process is killed, the oldest of the latest
+//! generations of the processes is thus stored by all of them, and is where
+//! `Prover::resume_from_checkpoint` restarts from.
+//!
+//! Checkpointing waits for the slowest process at every checkpoint, and the circuit is evaluated
+//! and committed again on restart, so the interval is better set to many minutes of proving.
+//!
+//! The checkpoints are removed once the proof is finished.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use circuit::{Circuit, LayerRetention};
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, GKREngine, MPIEngine, Proof, StructuredReferenceString,
+};
+use serdes::{load_from_file, store_to_file, SerdeError, SerdeResult};
+
+use crate::{ProofProgress, Prover, SuspendedProof};
+
+/// Where and how often a proof is checkpointed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCheckpoints {
+    /// the directory of the checkpoint files, shared by all processes
+    pub dir: PathBuf,
+    /// the number of layers proven between two checkpoints
+    pub interval: usize,
+}
+
+impl ProofCheckpoints {
+    const EXTENSION: &'static str = "checkpoint";
+
+    pub fn new(dir: impl AsRef<Path>, interval: usize) -> Self {
+        assert!(interval > 0, "the checkpoint interval must be positive");
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            interval,
+        }
+    }
+
+    #[inline]
+    fn path(&self, rank: usize, generation: usize) -> PathBuf {
+        self.dir
+            .join(format!("{rank}.{generation}.{}", Self::EXTENSION))
+    }
+
+    /// The generations stored by process `rank`
+    fn generations(&self, rank: usize) -> io::Result<Vec<usize>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+
+        let mut generations = vec![];
+        for entry in entries {
+            let name = entry?.file_name();
+            let mut parts = name.to_str().unwrap_or_default().split('.');
+            if let (Some(r), Some(generation), Some(Self::EXTENSION), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            {
+                if let (Ok(r), Ok(generation)) = (r.parse::<usize>(), generation.parse()) {
+                    if r == rank {
+                        generations.push(generation);
+                    }
+                }
+            }
+        }
+        Ok(generations)
+    }
+
+    /// Drop the generations of this process older than `generation`
+    fn remove_older(&self, mpi_config: &impl MPIEngine, generation: usize) -> io::Result<()> {
+        let rank = mpi_config.world_rank();
+        self.generations(rank)?
+            .into_iter()
+            .filter(|&g| g < generation)
+            .try_for_each(|g| fs::remove_file(self.path(rank, g)))
+    }
+
+    /// Store the state of this process as checkpoint `generation`, collectively on all
+    /// processes, and drop the older generations once every process has stored it.
+    ///
+    /// Fails on every process if some process fails to store its state, keeping the older
+    /// generations.
+    pub fn store<F: FieldEngine>(
+        &self,
+        mpi_config: &impl MPIEngine,
+        generation: usize,
+        suspended: &SuspendedProof<F>,
+    ) -> SerdeResult<()> {
+        let stored = fs::create_dir_all(&self.dir)
+            .map_err(SerdeError::from)
+            .and_then(|_| store_to_file(suspended, self.path(mpi_config.world_rank(), generation)));
+
+        // the processes must agree on whether the generation is complete
+        let mut n_stored = vec![
+            0u8;
+            if mpi_config.is_root() {
+                mpi_config.world_size()
+            } else {
+                0
+            }
+        ];
+        mpi_config.gather_vec(&[stored.is_ok() as u8], &mut n_stored)?;
+        let mut all_stored = n_stored.iter().all(|&ok| ok == 1);
+        mpi_config.root_broadcast_f(&mut all_stored)?;
+
+        stored?;
+        if !all_stored {
+            return Err(io::Error::other(format!(
+                "checkpoint {generation} was not stored by every process"
+            ))
+            .into());
+        }
+        self.remove_older(mpi_config, generation)?;
+        Ok(())
+    }
+
+    /// The latest generation stored by every process and the state of this process in it,
+    /// collectively on all processes. Fails with `NotFound` if some process has no checkpoint.
+    pub fn load_latest<F: FieldEngine>(
+        &self,
+        mpi_config: &impl MPIEngine,
+    ) -> SerdeResult<(usize, SuspendedProof<F>)> {
+        const NONE: u64 = u64::MAX;
+
+        let latest = self
+            .generations(mpi_config.world_rank())?
+            .into_iter()
+            .max()
+            .map_or(NONE, |g| g as u64);
+        let mut all_latest = if mpi_config.is_root() {
+            vec![NONE; mpi_config.world_size()]
+        } else {
+            vec![]
+        };
+        mpi_config.gather_vec(&[latest], &mut all_latest)?;
+        let mut generation = all_latest.into_iter().min().unwrap_or(NONE);
+        mpi_config.root_broadcast_f(&mut generation)?;
+
+        if generation == NONE {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no checkpoint of every process in {}", self.dir.display()),
+            )
+            .into());
+        }
+        let generation = generation as usize;
+        let suspended = load_from_file(self.path(mpi_config.world_rank(), generation))?;
+        Ok((generation, suspended))
+    }
+
+    /// Remove the checkpoints of this process, collectively on all processes.
+    pub fn clear(&self, mpi_config: &impl MPIEngine) -> SerdeResult<()> {
+        mpi_config.barrier()?;
+        self.remove_older(mpi_config, usize::MAX)?;
+        Ok(())
+    }
+}
+
+impl<Cfg: GKREngine> Prover<'_, Cfg> {
+    /// Same as `prove_with_retention`, checkpointing the proof to `checkpoints` after the commit
+    /// phase and then every `checkpoints.interval` layers, see `resume_from_checkpoint`.
+    ///
+    /// Checkpointing between layers is supported by vanilla GKR only, other schemes are only
+    /// checkpointed after the commit phase. Fails if a checkpoint cannot be stored.
+    pub fn prove_with_checkpoints(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        checkpoints: &ProofCheckpoints,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> SerdeResult<(<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof)> {
+        let suspended = self.prove_until_commit(c, pcs_params, pcs_proving_key, pcs_scratch)?;
+        checkpoints.store(&self.mpi_config, 0, &suspended)?;
+
+        self.prove_from_checkpoint(
+            c,
+            retention,
+            checkpoints,
+            0,
+            suspended,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        )
+    }
+
+    /// Restart a proof checkpointed by `prove_with_checkpoints` from its latest checkpoint, on
+    /// the same circuit and witness, and run it to completion, checkpointing it as it goes.
+    ///
+    /// Fails if there is no checkpoint of every process, or if a checkpoint cannot be loaded or
+    /// stored.
+    pub fn resume_from_checkpoint(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        checkpoints: &ProofCheckpoints,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> SerdeResult<(<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof)> {
+        let (generation, suspended) = checkpoints.load_latest(&self.mpi_config)?;
+
+        self.prove_from_checkpoint(
+            c,
+            retention,
+            checkpoints,
+            generation,
+            suspended,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn prove_from_checkpoint(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        checkpoints: &ProofCheckpoints,
+        mut generation: usize,
+        suspended: SuspendedProof<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> SerdeResult<(<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof)> {
+        let mpi_config = self.mpi_config.clone();
+        let mut next_checkpoint = suspended
+            .claim
+            .as_ref()
+            .map_or(c.layers.len(), |claim| claim.next_layer)
+            .saturating_sub(checkpoints.interval);
+        let mut failure = None;
+
+        let progress = self.resume_proof_until(
+            c,
+            retention,
+            &suspended,
+            |next_layer, snapshot| {
+                if next_layer > next_checkpoint {
+                    return false;
+                }
+                generation += 1;
+                next_checkpoint = next_layer.saturating_sub(checkpoints.interval);
+                // a failed checkpoint fails on every process, which all pause here
+                failure = checkpoints
+                    .store(&mpi_config, generation, &snapshot())
+                    .err();
+                failure.is_some()
+            },
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        )?;
+        if let Some(e) = failure {
+            return Err(e);
+        }
+
+        match progress {
+            ProofProgress::Finished(claimed_v, proof) => {
+                checkpoints.clear(&self.mpi_config)?;
+                Ok((claimed_v, proof))
+            }
+            ProofProgress::Suspended(_) => unreachable!("the proof only pauses on a failure"),
+        }
+    }
+}
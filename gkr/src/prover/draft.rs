@@ -0,0 +1,66 @@
+//! Drafts, a fast but *unsound* check of a circuit and its witness.
+//!
+//! A draft evaluates the circuit on the witness and checks the outputs the circuit asserts to be
+//! zero. No sumcheck is run, nothing is committed and the PCS is skipped altogether, so a passing
+//! draft proves nothing: it is a quick signal for circuit developers before a full proving run.
+
+use std::fmt;
+
+use arith::Field;
+use circuit::Circuit;
+use gkr_engine::{GKREngine, Transcript};
+
+/// Outcome of `draft_check`. This is not a proof, see the module documentation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DraftReport {
+    /// number of outputs the circuit asserts to be zero
+    pub asserted_outputs: usize,
+    /// the asserted outputs that are not zero, in increasing order
+    pub nonzero_outputs: Vec<usize>,
+}
+
+impl DraftReport {
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.nonzero_outputs.is_empty()
+    }
+}
+
+impl fmt::Display for DraftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DRAFT, NOT A PROOF (unsound, no PCS): ")?;
+        if self.passed() {
+            write!(f, "the {} asserted outputs are zero", self.asserted_outputs)
+        } else {
+            write!(
+                f,
+                "{} of {} asserted outputs are nonzero, the first one is {}",
+                self.nonzero_outputs.len(),
+                self.asserted_outputs,
+                self.nonzero_outputs[0]
+            )
+        }
+    }
+}
+
+/// Evaluate `c` on its loaded witness and check the outputs it asserts to be zero.
+/// See the module documentation for what this does *not* check.
+pub fn draft_check<Cfg: GKREngine>(c: &mut Circuit<Cfg::FieldConfig>) -> DraftReport {
+    if c.rnd_coefs_identified {
+        c.fill_rnd_coefs(&mut Cfg::TranscriptConfig::new());
+    }
+    c.evaluate();
+
+    let asserted_outputs = c.expected_num_output_zeros;
+    let nonzero_outputs = c.layers.last().unwrap().output_vals[..asserted_outputs]
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| !v.is_zero())
+        .map(|(i, _)| i)
+        .collect();
+
+    DraftReport {
+        asserted_outputs,
+        nonzero_outputs,
+    }
+}
@@ -2,24 +2,32 @@
 //! This module implements the core GKR^2 IOP.
 
 use ark_std::{end_timer, start_timer};
-use circuit::Circuit;
+use circuit::{Circuit, LayerRetention};
 use gkr_engine::{
-    ExpanderSingleVarChallenge, FieldEngine, FieldType, MPIConfig, MPIEngine, Transcript,
+    ExpanderSingleVarChallenge, FieldEngine, FieldType, MPIConfig, MPIEngine, MPIResult, Transcript,
 };
 use sumcheck::{sumcheck_prove_gkr_square_layer, ProverScratchPad};
 
+/// Prove the layers of `circuit` from the output layer down, calling `before_layer(i)` right
+/// before proving layer `i`.
 #[allow(clippy::type_complexity)]
 pub fn gkr_square_prove<F: FieldEngine>(
-    circuit: &Circuit<F>,
+    circuit: &mut Circuit<F>,
+    retention: LayerRetention,
     sp: &mut ProverScratchPad<F>,
     transcript: &mut impl Transcript,
     mpi_config: &MPIConfig,
-) -> (F::ChallengeField, ExpanderSingleVarChallenge<F>) {
+    mut before_layer: impl FnMut(usize),
+) -> MPIResult<(F::ChallengeField, ExpanderSingleVarChallenge<F>)> {
     assert_ne!(
         F::FIELD_TYPE,
         FieldType::GF2Ext128,
         "GF2 is not supported in GKR^2"
     );
+    assert!(
+        !circuit.has_skip_inputs(),
+        "skip inputs are not supported in GKR^2"
+    );
     let timer = start_timer!(|| "gkr^2 prove");
     let layer_num = circuit.layers.len();
 
@@ -33,28 +41,32 @@ pub fn gkr_square_prove<F: FieldEngine>(
     let claimed_v = F::collectively_eval_circuit_vals_at_expander_challenge(
         output_vals,
         &challenge,
-        &mut sp.hg_evals,
-        &mut sp.eq_evals_first_half, // confusing name here..
         mpi_config,
-    );
+    )?;
 
     log::trace!("Claimed v: {claimed_v:?}");
 
     for i in (0..layer_num).rev() {
+        before_layer(i);
+        transcript.begin_phase(&format!("layer {i}"));
+        circuit.restore_layer_vals(i);
+
         sumcheck_prove_gkr_square_layer(
             &circuit.layers[i],
             &mut challenge,
             transcript,
             sp,
             mpi_config,
-        );
+        )?;
 
         log::trace!("Layer {i} proved");
         log::trace!("rz0.0: {:?}", challenge.rz[0]);
         log::trace!("rz0.1: {:?}", challenge.rz[1]);
         log::trace!("rz0.2: {:?}", challenge.rz[2]);
+
+        circuit.release_layer_vals(i, retention);
     }
 
     end_timer!(timer);
-    (claimed_v, challenge)
+    Ok((claimed_v, challenge))
 }
@@ -1,23 +1,73 @@
 //! This module implements the core GKR IOP.
 
-use circuit::Circuit;
+use arith::Field;
+use circuit::{Circuit, LayerRetention};
 use gkr_engine::{
     ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine,
-    Transcript,
+    MPIResult, Transcript,
 };
+use serdes::ExpSerde;
 use sumcheck::{sumcheck_prove_gkr_layer, ProverScratchPad};
 use utils::timer::Timer;
 
+use crate::{prove_skip_claims, prove_skip_reduction, ClaimLedger, EvalClaim, SkipClaim};
+
+/// The claim the GKR prover carries from one layer down to the next.
+#[derive(Debug, Clone, Default, PartialEq, ExpSerde)]
+pub struct GKRLayerClaim<F: FieldEngine> {
+    /// the layers `0..next_layer` are left to prove
+    pub next_layer: usize,
+    /// the claimed evaluation of the outputs, which is what the proof attests to
+    pub claimed_v: F::ChallengeField,
+    /// the challenge on the outputs of `next_layer - 1` the remaining layers are reduced from
+    pub challenge: ExpanderDualVarChallenge<F>,
+    pub alpha: Option<F::ChallengeField>,
+    /// the claims on skip inputs, pending until the layers they read are proven
+    pub skip_claims: Vec<SkipClaim<F>>,
+    /// the claims made and discharged so far, in debug builds
+    pub ledger: ClaimLedger<F>,
+}
+
+impl<F: FieldEngine> GKRLayerClaim<F> {
+    /// Discharge the claims on the inputs of `layer` at `self.challenge`, which the prover does
+    /// not keep the values of
+    pub(crate) fn discharge_input_claims(&mut self, layer: usize) {
+        let zero = F::ChallengeField::ZERO;
+        for input_claim in EvalClaim::inputs_at(layer, &self.challenge, zero, Some(zero)) {
+            self.ledger.discharge_at(input_claim);
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub fn gkr_prove<F: FieldEngine>(
-    circuit: &Circuit<F>,
+    circuit: &mut Circuit<F>,
+    retention: LayerRetention,
     sp: &mut ProverScratchPad<F>,
     transcript: &mut impl Transcript,
     mpi_config: &MPIConfig,
-) -> (F::ChallengeField, ExpanderDualVarChallenge<F>) {
-    let layer_num = circuit.layers.len();
+) -> MPIResult<(F::ChallengeField, ExpanderDualVarChallenge<F>)> {
+    let mut claim = gkr_prove_output_claim(circuit, transcript, mpi_config)?;
+    gkr_prove_layers(
+        circuit,
+        retention,
+        sp,
+        transcript,
+        mpi_config,
+        &mut claim,
+        |_, _| false,
+    )?;
+
+    Ok((claim.claimed_v, claim.challenge))
+}
 
-    let mut challenge: ExpanderDualVarChallenge<F> =
+/// Sample the challenge on the outputs of `circuit`, and evaluate the outputs there.
+pub fn gkr_prove_output_claim<F: FieldEngine>(
+    circuit: &Circuit<F>,
+    transcript: &mut impl Transcript,
+    mpi_config: &MPIConfig,
+) -> MPIResult<GKRLayerClaim<F>> {
+    let challenge: ExpanderDualVarChallenge<F> =
         ExpanderSingleVarChallenge::sample_from_transcript(
             transcript,
             circuit.layers.last().unwrap().output_var_num,
@@ -25,18 +75,52 @@ pub fn gkr_prove<F: FieldEngine>(
         )
         .into();
 
-    let mut alpha = None;
-
     let output_vals = &circuit.layers.last().unwrap().output_vals;
     let claimed_v = F::collectively_eval_circuit_vals_at_expander_challenge(
         output_vals,
         &challenge.challenge_x(),
-        &mut sp.hg_evals,
-        &mut sp.eq_evals_first_half, // confusing name here..
         mpi_config,
-    );
+    )?;
+
+    let mut ledger = ClaimLedger::default();
+    ledger.make(EvalClaim::inputs(
+        circuit.layers.len(),
+        &challenge.challenge_x(),
+        claimed_v,
+    ));
+
+    Ok(GKRLayerClaim {
+        next_layer: circuit.layers.len(),
+        claimed_v,
+        challenge,
+        alpha: None,
+        skip_claims: vec![],
+        ledger,
+    })
+}
+
+/// Prove the layers from `claim.next_layer - 1` down to the input layer, updating `claim` as
+/// layers are proven. Stops early, at a layer boundary, as soon as
+/// `pause_before(claim, transcript)` holds, which must then hold on every MPI process.
+pub fn gkr_prove_layers<F: FieldEngine, T: Transcript>(
+    circuit: &mut Circuit<F>,
+    retention: LayerRetention,
+    sp: &mut ProverScratchPad<F>,
+    transcript: &mut T,
+    mpi_config: &MPIConfig,
+    claim: &mut GKRLayerClaim<F>,
+    mut pause_before: impl FnMut(&GKRLayerClaim<F>, &T) -> bool,
+) -> MPIResult<()> {
+    let layer_num = circuit.layers.len();
+    assert!(claim.next_layer <= layer_num);
+
+    while claim.next_layer > 0 && !pause_before(claim, transcript) {
+        let i = claim.next_layer - 1;
+        transcript.begin_phase(&format!("layer {i}"));
+        circuit.restore_layer_vals(i);
+        claim.discharge_input_claims(i + 1);
+        prove_skip_claims(circuit, claim, transcript, mpi_config)?;
 
-    for i in (0..layer_num).rev() {
         let timer = Timer::new(
             &format!(
                 "Sumcheck Layer {}, n_vars {}, one phase only? {}",
@@ -47,26 +131,43 @@ pub fn gkr_prove<F: FieldEngine>(
             mpi_config.is_root(),
         );
 
-        (_, _) = sumcheck_prove_gkr_layer(
+        let (vx_claim, vy_claim) = sumcheck_prove_gkr_layer(
             &circuit.layers[i],
-            &mut challenge,
-            alpha,
+            &mut claim.challenge,
+            claim.alpha,
             transcript,
             sp,
             mpi_config,
             i == layer_num - 1,
-        );
+        )?;
+        for input_claim in EvalClaim::inputs_at(i, &claim.challenge, vx_claim, vy_claim) {
+            claim.ledger.make(input_claim);
+        }
 
-        if challenge.rz_1.is_some() {
+        // the skip claims reading the inputs leave a single claim on them, with no alpha
+        prove_skip_reduction(circuit, claim, transcript, mpi_config)?;
+        if claim.challenge.rz_1.is_some() {
             // TODO: try broadcast beta.unwrap directly
             let mut tmp = transcript.generate_field_element::<F::ChallengeField>();
-            mpi_config.root_broadcast_f(&mut tmp);
-            alpha = Some(tmp)
+            mpi_config.root_broadcast_f(&mut tmp)?;
+            claim.alpha = Some(tmp)
         } else {
-            alpha = None;
+            claim.alpha = None;
         }
         timer.stop();
+
+        circuit.release_layer_vals(i, retention);
+        claim.next_layer = i;
     }
 
-    (claimed_v, challenge)
+    if claim.next_layer == 0 && ClaimLedger::<F>::ENABLED {
+        // the claims on the inputs are left to the opening of the PCS
+        claim.discharge_input_claims(0);
+        debug_assert!(
+            claim.ledger.audit().is_clean(),
+            "GKR claims not discharged:\n{}",
+            claim.ledger
+        );
+    }
+    Ok(())
 }
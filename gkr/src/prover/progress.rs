@@ -0,0 +1,106 @@
+//! Progress reports of a proof, for a GUI or a service to show how far along a long proof is.
+//!
+//! A proof is counted in steps: the commitment to the inputs, every layer of the GKR, and the
+//! opening of the inputs. The processes of an MPI world prove the layers in lockstep, since every
+//! layer ends with collectives, so the progress of the root is that of the whole proof, and the
+//! sink is only invoked on the root.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use gkr_engine::{GKREngine, MPIEngine};
+
+use crate::Prover;
+
+/// The phase a proof is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingPhase {
+    Commit,
+    Gkr,
+    Opening,
+    Done,
+}
+
+/// The progress of a proof, as reported to a `ProgressSink`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressReport {
+    pub phase: ProvingPhase,
+    /// the number of layers proven so far
+    pub layers_done: usize,
+    pub total_layers: usize,
+}
+
+impl ProgressReport {
+    /// The share of the steps of the proof done, in percent
+    pub fn percent(&self) -> f64 {
+        let steps_done = match self.phase {
+            ProvingPhase::Commit => 0,
+            ProvingPhase::Gkr => 1 + self.layers_done,
+            ProvingPhase::Opening => 1 + self.total_layers,
+            ProvingPhase::Done => 2 + self.total_layers,
+        };
+        100.0 * steps_done as f64 / (2 + self.total_layers) as f64
+    }
+}
+
+/// A receiver of the progress of the proofs of a `Prover`, see `Prover::set_progress_sink`.
+pub trait ProgressSink: Send + Sync {
+    fn on_progress(&self, report: &ProgressReport);
+}
+
+impl<F: Fn(&ProgressReport) + Send + Sync> ProgressSink for F {
+    fn on_progress(&self, report: &ProgressReport) {
+        self(report)
+    }
+}
+
+/// Forwards the reports of a proof to a sink, at most one per `min_interval` within a phase.
+#[derive(Clone, Default)]
+pub(crate) struct ProgressReporter {
+    sink: Option<Arc<dyn ProgressSink>>,
+    min_interval: Duration,
+    /// the time and phase of the last forwarded report
+    last: Arc<Mutex<Option<(Instant, ProvingPhase)>>>,
+}
+
+impl ProgressReporter {
+    pub(crate) fn report(&self, phase: ProvingPhase, layers_done: usize, total_layers: usize) {
+        let Some(sink) = &self.sink else {
+            return;
+        };
+
+        // the first report of a phase always goes through, so that no phase is skipped
+        let now = Instant::now();
+        let mut last = self.last.lock().unwrap();
+        if let Some((time, last_phase)) = *last {
+            if last_phase == phase && now.duration_since(time) < self.min_interval {
+                return;
+            }
+        }
+        *last = Some((now, phase));
+        drop(last);
+
+        sink.on_progress(&ProgressReport {
+            phase,
+            layers_done,
+            total_layers,
+        });
+    }
+}
+
+impl<Cfg: GKREngine> Prover<'_, Cfg> {
+    /// Report the progress of the proofs to `sink`, at most once per `min_interval` except at the
+    /// start of each phase. Only the root reports, the sink is ignored on the other processes.
+    pub fn set_progress_sink(&mut self, sink: impl ProgressSink + 'static, min_interval: Duration) {
+        self.progress = ProgressReporter {
+            sink: self
+                .mpi_config
+                .is_root()
+                .then(|| Arc::new(sink) as Arc<dyn ProgressSink>),
+            min_interval,
+            last: Default::default(),
+        };
+    }
+}
@@ -0,0 +1,282 @@
+//! Claims on the skip inputs of a layer, carried down to the layer they read.
+//!
+//! Before layer `i` is proven, the prover sends the contribution of each of its skip inputs to
+//! the claim on its outputs, which is taken off the claim the sumcheck of layer `i` proves. The
+//! contribution of the skip inputs reading layer `j` is a claim on a weighted sum of the inputs
+//! of layer `j`, pending until layer `j` is proven. There, the claims of the sumcheck on the
+//! inputs of layer `j` and the pending skip claims on them are combined with the powers of a
+//! random `beta`, and reduced by a sumcheck over all the variables of the inputs, those of the
+//! SIMD lanes first, then those of the values, then those of the processes, to a single claim
+//! at a new challenge.
+
+use arith::{ExtensionField, Field};
+use circuit::Circuit;
+use gkr_engine::{
+    ExpanderDualVarChallenge, FieldEngine, FieldType, MPIEngine, MPIResult, Transcript,
+};
+use polynomials::EqPolynomial;
+use serdes::ExpSerde;
+use sumcheck::{transcript_io, unpack_and_combine};
+
+use crate::{EvalClaim, GKRLayerClaim};
+
+/// A claim on the skip inputs of a layer, pending until the layer they read is proven
+#[derive(Debug, Clone, Default, PartialEq, ExpSerde)]
+pub struct SkipClaim<F: FieldEngine> {
+    /// the layer of the skip inputs
+    pub layer: usize,
+    /// the index of the skip inputs among those of the layer
+    pub skip: usize,
+    /// the challenge on the outputs of the layer when the claim was made
+    pub challenge: ExpanderDualVarChallenge<F>,
+    pub alpha: Option<F::ChallengeField>,
+}
+
+impl<F: FieldEngine> SkipClaim<F> {
+    /// The layer the skip inputs read
+    #[inline]
+    pub fn source(&self, circuit: &Circuit<F>) -> usize {
+        circuit.layers[self.layer].skip_inputs[self.skip].layer
+    }
+
+    /// The weight of each input value of the source layer in the claim, that of the outputs,
+    /// `eq(rz_0, .) + alpha * eq(rz_1, .)`, carried through the gates
+    pub fn source_weights(&self, circuit: &Circuit<F>) -> Vec<F::ChallengeField> {
+        let skip = &circuit.layers[self.layer].skip_inputs[self.skip];
+        let mut output_weights = EqPolynomial::build_eq_x_r(&self.challenge.rz_0);
+        if let (Some(rz_1), Some(alpha)) = (&self.challenge.rz_1, self.alpha) {
+            output_weights
+                .iter_mut()
+                .zip(EqPolynomial::build_eq_x_r(rz_1))
+                .for_each(|(w, w_1)| *w += alpha * w_1);
+        }
+        skip.source_weights(
+            &output_weights,
+            1 << circuit.layers[skip.layer].input_var_num,
+        )
+    }
+}
+
+/// Send the contributions of the skip inputs of the next layer of `claim` to the claim on its
+/// outputs, and make the skip claims on the layers they read.
+pub fn prove_skip_claims<F: FieldEngine>(
+    circuit: &Circuit<F>,
+    claim: &mut GKRLayerClaim<F>,
+    transcript: &mut impl Transcript,
+    mpi_config: &impl MPIEngine,
+) -> MPIResult<()> {
+    let i = claim.next_layer - 1;
+    for skip in 0..circuit.layers[i].skip_inputs.len() {
+        let skip_claim = SkipClaim {
+            layer: i,
+            skip,
+            challenge: claim.challenge.clone(),
+            alpha: claim.alpha,
+        };
+
+        let vals = &circuit.layers[skip_claim.source(circuit)].input_vals;
+        let local_simd = skip_claim
+            .source_weights(circuit)
+            .iter()
+            .zip(vals)
+            .fold(F::Field::ZERO, |acc, (w, v)| acc + *w * *v);
+        let local_v = unpack_and_combine(
+            &local_simd,
+            &EqPolynomial::build_eq_x_r(&claim.challenge.r_simd),
+        );
+        let value = mpi_config.coef_combine_vec(
+            &[local_v],
+            &EqPolynomial::build_eq_x_r(&claim.challenge.r_mpi),
+        )?[0];
+        transcript.append_field_element(&value);
+
+        claim
+            .ledger
+            .make(EvalClaim::skip_inputs(&skip_claim, value));
+        claim.skip_claims.push(skip_claim);
+    }
+    Ok(())
+}
+
+/// Once the next layer of `claim` is proven, reduce the claims on its inputs, at
+/// `claim.challenge`, and the skip claims reading them to a single claim. Returns false if no
+/// skip claim reads the inputs, leaving `claim` as is, and fails if the processes fail to
+/// communicate.
+pub fn prove_skip_reduction<F: FieldEngine, T: Transcript>(
+    circuit: &Circuit<F>,
+    claim: &mut GKRLayerClaim<F>,
+    transcript: &mut T,
+    mpi_config: &impl MPIEngine,
+) -> MPIResult<bool> {
+    let i = claim.next_layer - 1;
+    let (pending, rest): (Vec<_>, Vec<_>) = claim
+        .skip_claims
+        .drain(..)
+        .partition(|skip_claim| skip_claim.source(circuit) == i);
+    claim.skip_claims = rest;
+    if pending.is_empty() {
+        return Ok(false);
+    }
+    for skip_claim in &pending {
+        let skip_inputs_claim = EvalClaim::skip_inputs(skip_claim, F::ChallengeField::ZERO);
+        claim.ledger.discharge_at(skip_inputs_claim);
+    }
+    claim.discharge_input_claims(i);
+
+    let mut beta = transcript.generate_field_element::<F::ChallengeField>();
+    mpi_config.root_broadcast_f(&mut beta)?;
+
+    let layer = &circuit.layers[i];
+    let pack_size = F::get_field_pack_size();
+    let simd_var_num = pack_size.trailing_zeros() as usize;
+    // this process, and the virtual party whose values are those of this process, if any
+    let parties = std::iter::once(mpi_config.world_rank())
+        .chain(mpi_config.virtual_party())
+        .collect::<Vec<_>>();
+
+    // the weights of the local values of each party, indexed by lane + pack_size * x
+    let mut weights =
+        vec![vec![F::ChallengeField::ZERO; pack_size << layer.input_var_num]; parties.len()];
+    let mut add_weights = |x_weights: &[F::ChallengeField],
+                           challenge: &ExpanderDualVarChallenge<F>,
+                           coef: F::ChallengeField| {
+        let eq_simd = EqPolynomial::build_eq_x_r(&challenge.r_simd);
+        for (party, party_weights) in parties.iter().zip(weights.iter_mut()) {
+            let scale = coef * EqPolynomial::ith_eq_vec_elem(&challenge.r_mpi, *party);
+            for (x, w_x) in x_weights.iter().enumerate() {
+                for (lane, eq_lane) in eq_simd.iter().enumerate() {
+                    party_weights[lane + pack_size * x] += scale * *eq_lane * *w_x;
+                }
+            }
+        }
+    };
+
+    let mut x_weights = EqPolynomial::build_eq_x_r(&claim.challenge.rz_0);
+    if let Some(ry) = &claim.challenge.rz_1 {
+        x_weights
+            .iter_mut()
+            .zip(EqPolynomial::build_eq_x_r(ry))
+            .for_each(|(w, w_y)| *w += beta * w_y);
+    }
+    add_weights(&x_weights, &claim.challenge, F::ChallengeField::ONE);
+    let mut coef = beta * beta;
+    for skip_claim in &pending {
+        add_weights(
+            &skip_claim.source_weights(circuit),
+            &skip_claim.challenge,
+            coef,
+        );
+        coef *= beta;
+    }
+
+    let mut vals = layer
+        .input_vals
+        .iter()
+        .flat_map(|v| v.unpack())
+        .map(F::ChallengeField::from)
+        .collect::<Vec<_>>();
+
+    let mut r = vec![];
+    for _ in 0..simd_var_num + layer.input_var_num {
+        let mut local_evals = [F::ChallengeField::ZERO; 3];
+        for party_weights in &weights {
+            let evals = product_evals::<F>(party_weights, &vals);
+            local_evals
+                .iter_mut()
+                .zip(evals)
+                .for_each(|(acc, e)| *acc += e);
+        }
+        let mut evals = mpi_config.sum_vec(&local_evals)?;
+        evals.resize(3, F::ChallengeField::ZERO);
+        let r_i = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
+        weights
+            .iter_mut()
+            .for_each(|party_weights| fold(party_weights, r_i));
+        fold(&mut vals, r_i);
+        r.push(r_i);
+    }
+
+    // the variables of the parties, on the root, the weight of a virtual party being sent by the
+    // process it mirrors along with its own
+    let (n_ranks, n_parties) = if mpi_config.is_root() {
+        (mpi_config.world_size(), mpi_config.n_parties())
+    } else {
+        (0, 0)
+    };
+    let local_weights = [
+        weights[0][0],
+        weights.get(1).map_or(F::ChallengeField::ZERO, |w| w[0]),
+    ];
+    let mut gathered_weights = vec![F::ChallengeField::ZERO; 2 * n_ranks];
+    mpi_config.gather_vec(&local_weights, &mut gathered_weights)?;
+    let mut party_weights = (0..n_parties)
+        .map(|party| {
+            let mirror = mpi_config.mirror_rank(party);
+            gathered_weights[2 * mirror + usize::from(party != mirror)]
+        })
+        .collect::<Vec<_>>();
+    let mut party_vals = vec![F::ChallengeField::ZERO; n_parties];
+    mpi_config.gather_padded_vec(&vals, &mut party_vals)?;
+    for _ in 0..mpi_config.n_mpi_vars()? {
+        let evals = if mpi_config.is_root() {
+            product_evals::<F>(&party_weights, &party_vals)
+        } else {
+            [F::ChallengeField::ZERO; 3]
+        };
+        let r_i = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
+        if mpi_config.is_root() {
+            fold(&mut party_weights, r_i);
+            fold(&mut party_vals, r_i);
+        }
+        r.push(r_i);
+    }
+    let claimed_v = party_vals
+        .first()
+        .copied()
+        .unwrap_or(F::ChallengeField::ZERO);
+    transcript.append_field_element(&claimed_v);
+
+    let (r_simd, r) = r.split_at(simd_var_num);
+    let (rz, r_mpi) = r.split_at(layer.input_var_num);
+    claim.challenge =
+        ExpanderDualVarChallenge::new(rz.to_vec(), None, r_simd.to_vec(), r_mpi.to_vec());
+    claim.alpha = None;
+    claim.ledger.make(EvalClaim::inputs(
+        i,
+        &claim.challenge.challenge_x(),
+        claimed_v,
+    ));
+    Ok(true)
+}
+
+/// The evaluations at 0, 1 and 2, or 0, 1 and X over GF2_128, of the sum over the other
+/// variables of the product of `weights` and `vals` in their lowest variable
+fn product_evals<F: FieldEngine>(
+    weights: &[F::ChallengeField],
+    vals: &[F::ChallengeField],
+) -> [F::ChallengeField; 3] {
+    let at_third_point = |v_0: F::ChallengeField, v_1: F::ChallengeField| {
+        if F::FIELD_TYPE == FieldType::GF2Ext128 {
+            (v_1 - v_0).mul_by_x() + v_0
+        } else {
+            v_1.double() - v_0
+        }
+    };
+
+    let mut evals = [F::ChallengeField::ZERO; 3];
+    for (w, v) in weights.chunks(2).zip(vals.chunks(2)) {
+        evals[0] += w[0] * v[0];
+        evals[1] += w[1] * v[1];
+        evals[2] += at_third_point(w[0], w[1]) * at_third_point(v[0], v[1]);
+    }
+    evals
+}
+
+/// Fix the lowest variable of `vals` to `r`
+fn fold<F: Field>(vals: &mut Vec<F>, r: F) {
+    let half = vals.len() / 2;
+    for i in 0..half {
+        vals[i] = vals[2 * i] + (vals[2 * i + 1] - vals[2 * i]) * r;
+    }
+    vals.truncate(half);
+}
@@ -1,10 +1,11 @@
 //! This module implements the whole GKR prover, including the IOP and PCS.
 
 use arith::Field;
-use circuit::Circuit;
+use circuit::{Circuit, LayerRetention};
 use gkr_engine::{
     ExpanderDualVarChallenge, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine,
-    GKRScheme, MPIConfig, MPIEngine, Proof, StructuredReferenceString, Transcript,
+    GKRScheme, MPIConfig, MPIEngine, MPIResult, Proof, ProofVersion, StructuredReferenceString,
+    Transcript,
 };
 use polynomials::{
     MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension, RefMultiLinearPoly,
@@ -14,7 +15,9 @@ use sumcheck::ProverScratchPad;
 use transcript::transcript_root_broadcast;
 use utils::timer::Timer;
 
-use crate::{gkr_prove, gkr_square_prove};
+use crate::{
+    gkr_prove_layers, gkr_prove_output_claim, gkr_square_prove, ProgressReporter, ProvingPhase,
+};
 
 #[cfg(feature = "grinding")]
 pub(crate) fn grind<Cfg: GKREngine>(transcript: &mut impl Transcript, mpi_config: &MPIConfig) {
@@ -52,7 +55,9 @@ pub(crate) fn grind<Cfg: GKREngine>(transcript: &mut impl Transcript, mpi_config
 #[derive(Default)]
 pub struct Prover<'a, Cfg: GKREngine> {
     pub mpi_config: MPIConfig<'a>,
-    sp: ProverScratchPad<Cfg::FieldConfig>,
+    pub(crate) sp: ProverScratchPad<Cfg::FieldConfig>,
+    pub(crate) progress: ProgressReporter,
+    proof_version: ProofVersion,
 }
 
 impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
@@ -60,9 +65,17 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
         Prover {
             mpi_config,
             sp: ProverScratchPad::default(),
+            progress: ProgressReporter::default(),
+            proof_version: ProofVersion::Current,
         }
     }
 
+    /// Generate the proofs of `version` of the protocol, e.g., `ProofVersion::Previous` while
+    /// the verifiers of a deployment are not upgraded yet. The current version by default.
+    pub fn set_proof_version(&mut self, version: ProofVersion) {
+        self.proof_version = version;
+    }
+
     pub fn prepare_mem(&mut self, c: &Circuit<Cfg::FieldConfig>) {
         let max_num_input_var = c
             .layers
@@ -83,18 +96,190 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
         );
     }
 
+    /// Prove the evaluation of `c`, whose input layer holds the witness. Fails, on every
+    /// process, if the processes fail to communicate.
     pub fn prove(
         &mut self,
         c: &mut Circuit<Cfg::FieldConfig>,
         pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
         pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
         pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
-    ) -> (<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof)
+    ) -> MPIResult<(<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof)>
+    where
+        Cfg::FieldConfig: FieldEngine,
+    {
+        self.prove_with_retention(
+            c,
+            LayerRetention::Full,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        )
+    }
+
+    /// Same as `prove`, with a custom policy on how many layer values are kept in memory, see
+    /// `LayerRetention`. The proof does not depend on the policy.
+    ///
+    /// With `LayerRetention::Checkpoint`, only the checkpointed layers of `c` hold their values
+    /// when this returns.
+    pub fn prove_with_retention(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> MPIResult<(<Cfg::FieldConfig as FieldEngine>::ChallengeField, Proof)>
+    where
+        Cfg::FieldConfig: FieldEngine,
+    {
+        let (claimed_v, proof, _) =
+            self.prove_with_transcript(c, retention, pcs_params, pcs_proving_key, pcs_scratch)?;
+        Ok((claimed_v, proof))
+    }
+
+    /// Same as `prove_with_retention`, also returning the transcript the proof was generated
+    /// with, e.g., the `RecordingTranscript` of a `Traced` config to debug a proof.
+    pub fn prove_with_transcript(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> MPIResult<(
+        <Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        Proof,
+        Cfg::TranscriptConfig,
+    )>
+    where
+        Cfg::FieldConfig: FieldEngine,
+    {
+        self.prove_from_transcript(
+            c,
+            retention,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+            Cfg::TranscriptConfig::new(),
+        )
+    }
+
+    /// Same as `prove_with_transcript`, running the prover transcript on `transcript` rather
+    /// than on a fresh one, e.g., one seeded by a `ProofSession`.
+    pub(crate) fn prove_from_transcript(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+        transcript: Cfg::TranscriptConfig,
+    ) -> MPIResult<(
+        <Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        Proof,
+        Cfg::TranscriptConfig,
+    )>
     where
         Cfg::FieldConfig: FieldEngine,
     {
         let proving_timer = Timer::new("prover", self.mpi_config.is_root());
-        let mut transcript = Cfg::TranscriptConfig::new();
+        let progress = self.progress.clone();
+        let n_layers = c.layers.len();
+        progress.report(ProvingPhase::Commit, 0, n_layers);
+        let mut transcript =
+            self.commit_inputs_to(c, pcs_params, pcs_proving_key, pcs_scratch, transcript)?;
+
+        if self.mpi_config.is_root() {
+            c.fill_rnd_coefs(&mut transcript);
+        }
+        self.mpi_config.barrier()?;
+        c.evaluate_with_retention(retention);
+
+        let gkr_prove_timer = Timer::new("gkr prove", self.mpi_config.is_root());
+        transcript_root_broadcast(&mut transcript, &self.mpi_config)?;
+        transcript.begin_phase("gkr");
+
+        let (claimed_v, challenge) = match Cfg::SCHEME {
+            GKRScheme::Vanilla => {
+                let mut claim = gkr_prove_output_claim(c, &mut transcript, &self.mpi_config)?;
+                gkr_prove_layers(
+                    c,
+                    retention,
+                    &mut self.sp,
+                    &mut transcript,
+                    &self.mpi_config,
+                    &mut claim,
+                    |claim, _| {
+                        progress.report(ProvingPhase::Gkr, n_layers - claim.next_layer, n_layers);
+                        false
+                    },
+                )?;
+                (claim.claimed_v, claim.challenge)
+            }
+            GKRScheme::GkrSquare => {
+                let (claimed_v, challenge_x) = gkr_square_prove(
+                    c,
+                    retention,
+                    &mut self.sp,
+                    &mut transcript,
+                    &self.mpi_config,
+                    |i| progress.report(ProvingPhase::Gkr, n_layers - i - 1, n_layers),
+                )?;
+                (claimed_v, ExpanderDualVarChallenge::from(&challenge_x))
+            }
+        };
+        gkr_prove_timer.stop();
+        progress.report(ProvingPhase::Opening, n_layers, n_layers);
+
+        let proof = self.open_inputs(
+            c,
+            &challenge,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+            &mut transcript,
+        )?;
+        proving_timer.print(&format!("Proof size {} bytes", proof.bytes.len()));
+        proving_timer.stop();
+        progress.report(ProvingPhase::Done, n_layers, n_layers);
+
+        Ok((claimed_v, proof, transcript))
+    }
+}
+
+impl<Cfg: GKREngine> Prover<'_, Cfg> {
+    /// Start the transcript and commit to the inputs of `c`, up to the grinding included.
+    pub(crate) fn commit_inputs(
+        &self,
+        c: &Circuit<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> MPIResult<Cfg::TranscriptConfig> {
+        self.commit_inputs_to(
+            c,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+            Cfg::TranscriptConfig::new(),
+        )
+    }
+
+    /// Same as `commit_inputs`, on the transcript `transcript` rather than on a fresh one.
+    pub(crate) fn commit_inputs_to(
+        &self,
+        c: &Circuit<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+        mut transcript: Cfg::TranscriptConfig,
+    ) -> MPIResult<Cfg::TranscriptConfig> {
+        transcript.begin_phase("commitment");
+        // the proofs of the previous version have no fingerprint
+        if self.proof_version == ProofVersion::Current {
+            transcript.append_u8_slice(&Cfg::FINGERPRINT);
+        }
 
         let pcs_commit_timer = Timer::new("pcs commit", self.mpi_config.is_root());
         // PC commit
@@ -104,7 +289,7 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
             pcs_proving_key,
             &RefMultiLinearPoly::from_ref(&c.layers[0].input_vals),
             pcs_scratch,
-        );
+        )?;
 
         if self.mpi_config.is_root() {
             let mut buffer = vec![];
@@ -116,26 +301,21 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
         #[cfg(feature = "grinding")]
         grind::<Cfg>(&mut transcript, &self.mpi_config);
 
-        if self.mpi_config.is_root() {
-            c.fill_rnd_coefs(&mut transcript);
-        }
-        self.mpi_config.barrier();
-        c.evaluate();
-
-        let gkr_prove_timer = Timer::new("gkr prove", self.mpi_config.is_root());
-        transcript_root_broadcast(&mut transcript, &self.mpi_config);
-
-        let (claimed_v, challenge) = match Cfg::SCHEME {
-            GKRScheme::Vanilla => gkr_prove(c, &mut self.sp, &mut transcript, &self.mpi_config),
-            GKRScheme::GkrSquare => {
-                let (claimed_v, challenge_x) =
-                    gkr_square_prove(c, &mut self.sp, &mut transcript, &self.mpi_config);
-                (claimed_v, ExpanderDualVarChallenge::from(&challenge_x))
-            }
-        };
-        gkr_prove_timer.stop();
+        Ok(transcript)
+    }
 
-        transcript_root_broadcast(&mut transcript, &self.mpi_config);
+    /// Open the inputs of `c` at the challenges GKR reduced to, and finalize the proof.
+    pub(crate) fn open_inputs(
+        &self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        challenge: &ExpanderDualVarChallenge<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+        transcript: &mut Cfg::TranscriptConfig,
+    ) -> MPIResult<Proof> {
+        transcript_root_broadcast(transcript, &self.mpi_config)?;
+        transcript.begin_phase("pcs opening");
 
         let pcs_open_timer = Timer::new("pcs open", self.mpi_config.is_root());
 
@@ -148,32 +328,26 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
             pcs_params,
             pcs_proving_key,
             pcs_scratch,
-            &mut transcript,
-        );
+            transcript,
+        )?;
 
         if let Some(mut challenge_y) = challenge.challenge_y() {
-            transcript_root_broadcast(&mut transcript, &self.mpi_config);
+            transcript_root_broadcast(transcript, &self.mpi_config)?;
             self.prove_input_layer_claim(
                 &mut mle_ref,
                 &mut challenge_y,
                 pcs_params,
                 pcs_proving_key,
                 pcs_scratch,
-                &mut transcript,
-            );
+                transcript,
+            )?;
         }
 
         pcs_open_timer.stop();
 
-        let proof = transcript.finalize_and_get_proof();
-        proving_timer.print(&format!("Proof size {} bytes", proof.bytes.len()));
-        proving_timer.stop();
-
-        (claimed_v, proof)
+        Ok(transcript.finalize_and_get_proof())
     }
-}
 
-impl<Cfg: GKREngine> Prover<'_, Cfg> {
     fn prove_input_layer_claim(
         &self,
         inputs: &mut MutRefMultiLinearPoly<<Cfg::FieldConfig as FieldEngine>::SimdCircuitField>,
@@ -182,7 +356,8 @@ impl<Cfg: GKREngine> Prover<'_, Cfg> {
         pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
         pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
         transcript: &mut impl Transcript,
-    ) where
+    ) -> MPIResult<()>
+    where
         Cfg::FieldConfig: FieldEngine,
     {
         let original_input_vars = inputs.num_vars();
@@ -198,6 +373,7 @@ impl<Cfg: GKREngine> Prover<'_, Cfg> {
             pcs_scratch,
         );
         transcript.unlock_proof();
+        let opening = opening?;
 
         inputs.lift_to_n_vars(original_input_vars);
         open_at.rz.resize(
@@ -210,5 +386,6 @@ impl<Cfg: GKREngine> Prover<'_, Cfg> {
             opening.unwrap().serialize_into(&mut buffer).unwrap(); // TODO: error propagation
             transcript.append_u8_slice(&buffer);
         }
+        Ok(())
     }
 }
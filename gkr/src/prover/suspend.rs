@@ -0,0 +1,268 @@
+//! Suspending a proof mid-protocol and resuming it later, possibly in another process.
+//!
+//! A proof can be suspended right after the commit phase, before the random coefficients of the
+//! circuit are drawn, or between two layers of the vanilla GKR. The suspended state holds the
+//! full transcript and the claim carried down to the next layer, and serializes to a blob that
+//! can be stored until the proof is resumed, e.g., once the event the next phase waits for has
+//! happened. A resumed proof is byte for byte the proof an uninterrupted run would produce, and
+//! is checked by the regular verifier.
+//!
+//! The layer values and the PCS scratch pad are not part of the state: they are recomputed on
+//! resumption from the circuit and its witness, which must be the ones the proof started with,
+//! keeping only the layers `LayerRetention` asks for. With MPI, every process suspends and
+//! resumes with its own state.
+//!
+//! On preemptible machines, `Prover::resume_proof_preemptible` proves until a `PauseSignal` is
+//! raised, e.g., by the handler of the reclamation notice, and pauses at the next layer
+//! boundary. The state is small enough to be persisted with `serdes::store_to_file` before the
+//! machine goes away. Without notice, `Prover::prove_with_checkpoints` persists the state
+//! periodically instead, see `checkpoint`.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use circuit::{Circuit, LayerRetention};
+use gkr_engine::{
+    ExpanderDualVarChallenge, ExpanderPCS, FieldEngine, GKREngine, GKRScheme, MPIEngine, MPIResult,
+    Proof, StructuredReferenceString, Transcript,
+};
+use polynomials::RefMultiLinearPoly;
+use serdes::{ExpSerde, SerdeResult};
+use transcript::transcript_root_broadcast;
+
+use crate::{gkr_prove_layers, gkr_prove_output_claim, gkr_square_prove, GKRLayerClaim, Prover};
+
+/// The state of a suspended proof.
+#[derive(Debug, Clone, Default, ExpSerde)]
+pub struct SuspendedProof<F: FieldEngine> {
+    /// the transcript, as exported by `Transcript::export_state`
+    pub transcript_state: Vec<u8>,
+    /// the random coefficients of the circuit, empty if suspended after the commit phase
+    pub rnd_coefs: Vec<F::CircuitField>,
+    /// the claim on the next layer to prove, `None` if suspended after the commit phase
+    pub claim: Option<GKRLayerClaim<F>>,
+}
+
+/// Outcome of `Prover::resume_proof`.
+#[derive(Debug, Clone)]
+pub enum ProofProgress<F: FieldEngine> {
+    Suspended(SuspendedProof<F>),
+    Finished(F::ChallengeField, Proof),
+}
+
+/// A flag to pause a proof resumed with `Prover::resume_proof_preemptible`, shared with the code
+/// that decides when to pause, e.g., a signal handler. With MPI, the flag of the root decides.
+#[derive(Debug, Clone, Default)]
+pub struct PauseSignal(Arc<AtomicBool>);
+
+impl PauseSignal {
+    /// Ask the proof to pause at the next layer boundary.
+    #[inline]
+    pub fn raise(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Withdraw a pause request, e.g., before resuming the proof again.
+    #[inline]
+    pub fn clear(&self) {
+        self.0.store(false, Ordering::Release);
+    }
+
+    #[inline]
+    pub fn is_raised(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+impl<Cfg: GKREngine> Prover<'_, Cfg> {
+    /// Commit to the inputs of `c` and suspend the proof, before any challenge is drawn. Fails if
+    /// the processes fail to communicate.
+    pub fn prove_until_commit(
+        &mut self,
+        c: &Circuit<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> MPIResult<SuspendedProof<Cfg::FieldConfig>> {
+        let transcript = self.commit_inputs(c, pcs_params, pcs_proving_key, pcs_scratch)?;
+
+        Ok(SuspendedProof {
+            transcript_state: transcript.export_state(),
+            rnd_coefs: vec![],
+            claim: None,
+        })
+    }
+
+    /// Resume a suspended proof on the same circuit and witness, and run it to completion, or
+    /// suspend it again right before proving layer `suspend_at_layer`.
+    ///
+    /// Suspending between layers is supported by vanilla GKR only. Fails if the state does not
+    /// deserialize into a transcript, or if the processes fail to communicate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn resume_proof(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        suspended: &SuspendedProof<Cfg::FieldConfig>,
+        suspend_at_layer: Option<usize>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> SerdeResult<ProofProgress<Cfg::FieldConfig>> {
+        assert!(
+            matches!(Cfg::SCHEME, GKRScheme::Vanilla) || suspend_at_layer.is_none(),
+            "suspending between layers supports vanilla GKR only"
+        );
+
+        self.resume_proof_until(
+            c,
+            retention,
+            suspended,
+            |next_layer, _| suspend_at_layer.is_some_and(|layer| next_layer <= layer),
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        )
+    }
+
+    /// Resume a suspended proof on the same circuit and witness, and run it to completion, or
+    /// pause it at the first layer boundary after `signal` is raised.
+    ///
+    /// Pausing is supported by vanilla GKR only, other schemes ignore `signal`. Fails if the
+    /// state does not deserialize into a transcript, or if the processes fail to communicate.
+    #[allow(clippy::too_many_arguments)]
+    pub fn resume_proof_preemptible(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        suspended: &SuspendedProof<Cfg::FieldConfig>,
+        signal: &PauseSignal,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> SerdeResult<ProofProgress<Cfg::FieldConfig>> {
+        let mpi_config = self.mpi_config.clone();
+        let mut failure = None;
+        let progress = self.resume_proof_until(
+            c,
+            retention,
+            suspended,
+            |_, _| {
+                // the processes must agree on the layer to pause at, a process failing to learn
+                // it pauses right away and fails
+                let mut paused = signal.is_raised();
+                failure = mpi_config.root_broadcast_f(&mut paused).err();
+                paused || failure.is_some()
+            },
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        )?;
+        match failure {
+            Some(e) => Err(e.into()),
+            None => Ok(progress),
+        }
+    }
+
+    /// Resume a suspended proof, pausing before layer `next_layer` if `pause_before(next_layer,
+    /// snapshot)` holds, where `snapshot` builds the state the proof would be suspended with.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn resume_proof_until(
+        &mut self,
+        c: &mut Circuit<Cfg::FieldConfig>,
+        retention: LayerRetention,
+        suspended: &SuspendedProof<Cfg::FieldConfig>,
+        mut pause_before: impl FnMut(usize, &dyn Fn() -> SuspendedProof<Cfg::FieldConfig>) -> bool,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+        pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+    ) -> SerdeResult<ProofProgress<Cfg::FieldConfig>> {
+        assert!(
+            matches!(Cfg::SCHEME, GKRScheme::Vanilla) || suspended.claim.is_none(),
+            "suspending between layers supports vanilla GKR only"
+        );
+
+        let mut transcript = Cfg::TranscriptConfig::import_state(&suspended.transcript_state)?;
+
+        // NOTE: the scratch pad is rebuilt by committing again, the commitment is already in the
+        // transcript
+        Cfg::PCSConfig::commit(
+            pcs_params,
+            &self.mpi_config,
+            pcs_proving_key,
+            &RefMultiLinearPoly::from_ref(&c.layers[0].input_vals),
+            pcs_scratch,
+        )?;
+
+        if self.mpi_config.is_root() {
+            match &suspended.claim {
+                Some(_) => c.set_rnd_coefs(&suspended.rnd_coefs),
+                None => c.fill_rnd_coefs(&mut transcript),
+            }
+        }
+        self.mpi_config.barrier()?;
+        c.evaluate_with_retention(retention);
+
+        let (claimed_v, challenge) = match Cfg::SCHEME {
+            GKRScheme::Vanilla => {
+                let mut claim = match &suspended.claim {
+                    Some(claim) => claim.clone(),
+                    None => {
+                        transcript_root_broadcast(&mut transcript, &self.mpi_config)?;
+                        transcript.begin_phase("gkr");
+                        gkr_prove_output_claim(c, &mut transcript, &self.mpi_config)?
+                    }
+                };
+                let rnd_coefs = c.rnd_coef_values();
+                gkr_prove_layers(
+                    c,
+                    retention,
+                    &mut self.sp,
+                    &mut transcript,
+                    &self.mpi_config,
+                    &mut claim,
+                    |claim, transcript| {
+                        pause_before(claim.next_layer, &|| SuspendedProof {
+                            transcript_state: transcript.export_state(),
+                            rnd_coefs: rnd_coefs.clone(),
+                            claim: Some(claim.clone()),
+                        })
+                    },
+                )?;
+
+                if claim.next_layer > 0 {
+                    return Ok(ProofProgress::Suspended(SuspendedProof {
+                        transcript_state: transcript.export_state(),
+                        rnd_coefs,
+                        claim: Some(claim),
+                    }));
+                }
+                (claim.claimed_v, claim.challenge)
+            }
+            GKRScheme::GkrSquare => {
+                transcript_root_broadcast(&mut transcript, &self.mpi_config)?;
+                let (claimed_v, challenge_x) = gkr_square_prove(
+                    c,
+                    retention,
+                    &mut self.sp,
+                    &mut transcript,
+                    &self.mpi_config,
+                    |_| {},
+                )?;
+                (claimed_v, ExpanderDualVarChallenge::from(&challenge_x))
+            }
+        };
+
+        let proof = self.open_inputs(
+            c,
+            &challenge,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+            &mut transcript,
+        )?;
+        Ok(ProofProgress::Finished(claimed_v, proof))
+    }
+}
@@ -0,0 +1,199 @@
+//! Proofs of tiny circuits, through a convenience wrapper of `Prover` and `Verifier`.
+//!
+//! A service proving many statements of a circuit of a few thousand gates sets up a
+//! `SmallProver` once, on a single process and over a config of a single SIMD lane, and proves
+//! each statement from its inputs only: the scratch pads are sized, the circuit is preprocessed
+//! and the PCS scratch pad is initialized once rather than on every proof, and the witness is
+//! neither read from a file nor checked against a sharding. The `SmallVerifier` likewise keeps
+//! the circuit and the verifying key of the PCS across the proofs.
+//!
+//! This is not a fast path of its own: each proof runs `Prover::prove`, with the sumchecks over
+//! the SIMD field of the config and its MPI engine, over a single process that does not
+//! communicate. The fixed costs of each proof, e.g., the PCS commitment and opening, are the ones
+//! of `Prover::prove`.
+//!
+//! The proofs are those of a single process, which `Verifier` verifies as well.
+
+use arith::SimdField;
+use circuit::{Circuit, CoefType};
+use gkr_engine::{
+    ExpErrors, ExpanderPCS, FieldEngine, GKREngine, MPIConfig, Proof, StructuredReferenceString,
+};
+
+use crate::{Prover, Verifier};
+
+type CircuitField<Cfg> = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::CircuitField;
+type ChallengeField<Cfg> = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField;
+type SimdCircuitField<Cfg> = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::SimdCircuitField;
+type PCSParams<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::Params;
+type PCSScratchPad<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::ScratchPad;
+type PCSSRS<Cfg> =
+    <<Cfg as GKREngine>::PCSConfig as ExpanderPCS<<Cfg as GKREngine>::FieldConfig>>::SRS;
+type PCSProvingKey<Cfg> = <PCSSRS<Cfg> as StructuredReferenceString>::PKey;
+type PCSVerifyingKey<Cfg> = <PCSSRS<Cfg> as StructuredReferenceString>::VKey;
+
+/// The number of gates up to which a circuit is proven by a `SmallProver`
+pub const SMALL_CIRCUIT_MAX_GATES: usize = 1 << 12;
+
+/// Whether `circuit` can be proven by a `SmallProver`: it has at most `SMALL_CIRCUIT_MAX_GATES`
+/// gates, over a field config of a single SIMD lane.
+pub fn is_small_circuit<C: FieldEngine>(circuit: &Circuit<C>) -> bool {
+    C::get_field_pack_size() == 1 && circuit.num_gates() <= SMALL_CIRCUIT_MAX_GATES
+}
+
+/// The number of public inputs the gates of `circuit` read
+fn num_public_inputs<C: FieldEngine>(circuit: &Circuit<C>) -> usize {
+    circuit
+        .layers
+        .iter()
+        .flat_map(|layer| &layer.const_)
+        .filter_map(|gate| match gate.coef_type {
+            CoefType::PublicInput(i) => Some(i + 1),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// The values of a single SIMD lane
+#[inline]
+fn pack_lane<Cfg: GKREngine>(values: &[CircuitField<Cfg>]) -> Vec<SimdCircuitField<Cfg>> {
+    values
+        .iter()
+        .map(<SimdCircuitField<Cfg> as SimdField>::pack_full)
+        .collect()
+}
+
+/// A prover of the statements of a small circuit, see the module documentation.
+pub struct SmallProver<Cfg: GKREngine> {
+    circuit: Circuit<Cfg::FieldConfig>,
+    num_public_inputs: usize,
+    prover: Prover<'static, Cfg>,
+    pcs_params: PCSParams<Cfg>,
+    pcs_proving_key: PCSProvingKey<Cfg>,
+    pcs_scratch: PCSScratchPad<Cfg>,
+}
+
+impl<Cfg: GKREngine> SmallProver<Cfg> {
+    /// A prover of `circuit`, with the PCS set up for its inputs on a single process.
+    ///
+    /// # Panics
+    /// If `circuit` is not small, see `is_small_circuit`.
+    pub fn new(
+        mut circuit: Circuit<Cfg::FieldConfig>,
+        pcs_params: PCSParams<Cfg>,
+        pcs_proving_key: PCSProvingKey<Cfg>,
+    ) -> Self {
+        assert!(
+            is_small_circuit(&circuit),
+            "{} gates over {} SIMD lanes is not a small circuit",
+            circuit.num_gates(),
+            <Cfg::FieldConfig as FieldEngine>::get_field_pack_size()
+        );
+        circuit.pre_process_gkr();
+
+        let mpi_config = MPIConfig::prover_new(None, None);
+        let pcs_scratch = Cfg::PCSConfig::init_scratch_pad(&pcs_params, &mpi_config);
+        let mut prover = Prover::new(mpi_config);
+        prover.prepare_mem(&circuit);
+
+        Self {
+            num_public_inputs: num_public_inputs(&circuit),
+            circuit,
+            prover,
+            pcs_params,
+            pcs_proving_key,
+            pcs_scratch,
+        }
+    }
+
+    /// Prove the evaluation of the circuit on `private_inputs` and `public_inputs`, returning
+    /// the claimed output value and the proof. Fails if the numbers of inputs are not those of
+    /// the circuit.
+    pub fn prove(
+        &mut self,
+        private_inputs: &[CircuitField<Cfg>],
+        public_inputs: &[CircuitField<Cfg>],
+    ) -> Result<(ChallengeField<Cfg>, Proof), ExpErrors> {
+        let num_private_inputs = 1 << self.circuit.log_input_size();
+        if private_inputs.len() != num_private_inputs {
+            return Err(ExpErrors::WitnessError(format!(
+                "expected {num_private_inputs} private inputs, got {}",
+                private_inputs.len()
+            )));
+        }
+        if public_inputs.len() != self.num_public_inputs {
+            return Err(ExpErrors::WitnessError(format!(
+                "expected {} public inputs, got {}",
+                self.num_public_inputs,
+                public_inputs.len()
+            )));
+        }
+
+        self.circuit.layers[0].input_vals = pack_lane::<Cfg>(private_inputs);
+        self.circuit.public_input = pack_lane::<Cfg>(public_inputs);
+        let (claimed_v, proof) = self.prover.prove(
+            &mut self.circuit,
+            &self.pcs_params,
+            &self.pcs_proving_key,
+            &mut self.pcs_scratch,
+        )?;
+        Ok((claimed_v, proof))
+    }
+}
+
+/// A verifier of the proofs of a `SmallProver`, see the module documentation.
+pub struct SmallVerifier<Cfg: GKREngine> {
+    circuit: Circuit<Cfg::FieldConfig>,
+    num_public_inputs: usize,
+    verifier: Verifier<'static, Cfg>,
+    pcs_params: PCSParams<Cfg>,
+    pcs_verification_key: PCSVerifyingKey<Cfg>,
+}
+
+impl<Cfg: GKREngine> SmallVerifier<Cfg> {
+    /// A verifier of the proofs of `circuit`, see `SmallProver::new`.
+    ///
+    /// # Panics
+    /// If `circuit` is not small, see `is_small_circuit`.
+    pub fn new(
+        mut circuit: Circuit<Cfg::FieldConfig>,
+        pcs_params: PCSParams<Cfg>,
+        pcs_verification_key: PCSVerifyingKey<Cfg>,
+    ) -> Self {
+        assert!(is_small_circuit(&circuit));
+        circuit.pre_process_gkr();
+
+        Self {
+            num_public_inputs: num_public_inputs(&circuit),
+            circuit,
+            verifier: Verifier::new(MPIConfig::verifier_new(1)),
+            pcs_params,
+            pcs_verification_key,
+        }
+    }
+
+    /// Verify `proof` of the evaluation of the circuit on some private inputs and on
+    /// `public_inputs` to `claimed_v`.
+    pub fn verify(
+        &mut self,
+        public_inputs: &[CircuitField<Cfg>],
+        claimed_v: &ChallengeField<Cfg>,
+        proof: &Proof,
+    ) -> bool {
+        if public_inputs.len() != self.num_public_inputs {
+            return false;
+        }
+
+        self.verifier.verify(
+            &mut self.circuit,
+            &pack_lane::<Cfg>(public_inputs),
+            claimed_v,
+            &self.pcs_params,
+            &self.pcs_verification_key,
+            proof,
+        )
+    }
+}
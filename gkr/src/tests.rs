@@ -1,2 +1,7 @@
+mod claim_ledger;
+mod composite;
 mod gkr_correctness;
+mod proof_session;
+mod protocol_version;
+mod small_proof;
 mod system;
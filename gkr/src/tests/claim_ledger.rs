@@ -0,0 +1,40 @@
+use arith::Field;
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, M31x16Config};
+
+use crate::{ClaimLedger, EvalClaim};
+
+type F = <M31x16Config as FieldEngine>::ChallengeField;
+
+#[test]
+fn test_claim_ledger() {
+    let point =
+        |x: u32| ExpanderSingleVarChallenge::<M31x16Config>::new(vec![F::from(x)], vec![], vec![]);
+    let mut ledger = ClaimLedger::<M31x16Config>::default();
+
+    ledger.make(EvalClaim::inputs(2, &point(1), F::from(10)));
+    ledger.make(EvalClaim::inputs(1, &point(2), F::from(20)));
+    ledger.make(EvalClaim::inputs(1, &point(3), F::from(30)));
+    ledger.discharge(EvalClaim::inputs(2, &point(1), F::from(10)));
+    // discharged with another value than the one claimed
+    ledger.discharge(EvalClaim::inputs(1, &point(2), F::from(21)));
+    // discharged whatever the value, as by the prover
+    ledger.discharge_at(EvalClaim::inputs(1, &point(3), F::ZERO));
+
+    if !ClaimLedger::<M31x16Config>::ENABLED {
+        assert!(ledger.entries.is_empty());
+        return;
+    }
+    let audit = ledger.audit();
+    assert!(!audit.is_clean());
+    assert_eq!(
+        audit.open,
+        vec![&EvalClaim::inputs(1, &point(2), F::from(20))]
+    );
+    assert_eq!(
+        audit.unmade,
+        vec![&EvalClaim::inputs(1, &point(2), F::from(21))]
+    );
+    assert!(ledger
+        .to_string()
+        .ends_with("6 claims, 1 open, 1 discharged without being made\n"));
+}
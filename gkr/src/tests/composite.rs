@@ -0,0 +1,106 @@
+use arith::{Field, SimdField};
+use circuit::Circuit;
+use gkr_engine::{FieldEngine, GKREngine, MPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+
+use crate::{
+    utils::*, CompositeProof, M31x16ConfigSha2RawVanilla, Prover, ProverComponent, Verifier,
+    VerifierComponent, WireLink, WireRef,
+};
+
+type Cfg = M31x16ConfigSha2RawVanilla;
+type FieldConfig = <Cfg as GKREngine>::FieldConfig;
+
+#[test]
+fn test_composite_proof() {
+    let mpi_config = MPIConfig::prover_new(None, None);
+
+    let mut circuit = Circuit::<FieldConfig>::single_thread_prover_load_circuit::<Cfg>(
+        &("../".to_owned() + KECCAK_M31_CIRCUIT),
+    );
+    circuit.load_witness_allow_padding_testing_only(
+        &("../".to_owned() + KECCAK_M31_WITNESS),
+        &mpi_config,
+    );
+    circuit.evaluate();
+    let (pcs_params, pcs_proving_key, pcs_verification_key, _) =
+        expander_pcs_init_testing_only::<FieldConfig, <Cfg as GKREngine>::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+
+    // the circuit field elements of the inputs, by position
+    let inputs: Vec<_> = circuit.layers[0]
+        .input_vals
+        .iter()
+        .flat_map(|v| v.unpack())
+        .collect();
+    let distinct = inputs.iter().position(|v| *v != inputs[0]).unwrap();
+
+    // a circuit linked to the same circuit nested one level down
+    let link = |to_position| WireLink {
+        from: WireRef::new(vec![0], 0),
+        to: WireRef::new(vec![1, 0], to_position),
+    };
+    let prove = |links: Vec<WireLink>| {
+        let (mut a, mut b) = (circuit.clone(), circuit.clone());
+        let mut components = vec![
+            ProverComponent::<Cfg>::Circuit {
+                circuit: &mut a,
+                pcs_params: &pcs_params,
+                pcs_proving_key: &pcs_proving_key,
+            },
+            ProverComponent::Composite {
+                components: vec![ProverComponent::Circuit {
+                    circuit: &mut b,
+                    pcs_params: &pcs_params,
+                    pcs_proving_key: &pcs_proving_key,
+                }],
+                links: vec![],
+            },
+        ];
+        Prover::<Cfg>::new(mpi_config.clone())
+            .prove_composite(&mut components, &links)
+            .unwrap()
+    };
+    let verify = |links: Vec<WireLink>, proof: &CompositeProof<Cfg>| {
+        let (mut a, mut b) = (circuit.clone(), circuit.clone());
+        let mut components = vec![
+            VerifierComponent::<Cfg>::Circuit {
+                circuit: &mut a,
+                public_input: &circuit.public_input,
+                pcs_params: &pcs_params,
+                pcs_verification_key: &pcs_verification_key,
+            },
+            VerifierComponent::Composite {
+                components: vec![VerifierComponent::Circuit {
+                    circuit: &mut b,
+                    public_input: &circuit.public_input,
+                    pcs_params: &pcs_params,
+                    pcs_verification_key: &pcs_verification_key,
+                }],
+                links: vec![],
+            },
+        ];
+        Verifier::<Cfg>::new(mpi_config.clone()).verify_composite(&mut components, &links, proof)
+    };
+
+    let proof = prove(vec![link(0)]);
+    let mut bytes = vec![];
+    proof.serialize_into(&mut bytes).unwrap();
+    let proof = CompositeProof::<Cfg>::deserialize_from(bytes.as_slice()).unwrap();
+    assert!(verify(vec![link(0)], &proof));
+
+    // the proof is of the links it was proven with
+    assert!(!verify(vec![link(distinct)], &proof));
+    assert!(!verify(vec![], &proof));
+
+    let mut tampered = proof.clone();
+    tampered.values[0] += <FieldConfig as FieldEngine>::ChallengeField::ONE;
+    assert!(!verify(vec![link(0)], &tampered));
+
+    // wires carrying different values are not proven equal
+    let proof = prove(vec![link(distinct)]);
+    assert!(!verify(vec![link(distinct)], &proof));
+}
@@ -1,29 +1,39 @@
-use std::io::Write;
 use std::panic::AssertUnwindSafe;
-use std::time::Instant;
-use std::{fs, panic};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{env, fs, mem, panic};
 
 use arith::Field;
-use circuit::Circuit;
+use babybear::BabyBearx16;
+use circuit::{Circuit, LayerRetention};
 use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
     root_println, BN254Config, BabyBearx16Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine,
     GKRScheme, Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x1Config, MPIConfig,
-    MPIEngine, MPISharedMemory,
+    MPIEngine, MPISharedMemory, Proof,
 };
-use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+use gkr_hashers::{
+    AnemoiFiatShamirHasher, GriffinFiatShamirHasher, Keccak256hasher, MiMC5FiatShamirHasher,
+    PoseidonFiatShamirHasher, SHA256hasher,
+};
+use goldilocks::Goldilocksx8;
 use halo2curves::bn256::{Bn256, G1Affine};
 use mersenne31::M31x16;
 use poly_commit::{
-    expander_pcs_init_testing_only, HyperBiKZGPCS, HyraxPCS, OrionPCSForGKR, RawExpanderGKR,
+    expander_pcs_init_testing_only, BasefoldPCSForGKR, BrakedownPCSForGKR, DoryPCS, HyperBiKZGPCS,
+    HyraxPCS, IpaPCS, LigeroPCSForGKR, OrionPCSForGKR, RawExpanderGKR, RawExpanderGKRDistributed,
+    WhirPCSForGKR, ZeromorphPCS,
 };
 use rand::Rng;
 use serdes::ExpSerde;
 use sha2::Digest;
 use transcript::BytesHashTranscript;
 
-use crate::{utils::*, Prover, Verifier};
+use crate::{
+    draft_check, utils::*, PauseSignal, ProgressReport, ProofCheckpoints, ProofProgress, Prover,
+    ProvingPhase, SuspendedProof, Verifier, VerifierSession,
+};
 
 #[test]
 fn test_gkr_correctness() {
@@ -145,6 +155,97 @@ fn test_gkr_correctness() {
         PolynomialCommitmentType::Raw,
         GKRScheme::Vanilla,
     );
+    declare_gkr_config!(
+        C16,
+        FieldType::BN254,
+        FiatShamirHashType::Anemoi,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C17,
+        FieldType::BN254,
+        FiatShamirHashType::Griffin,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C18,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Basefold,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C19,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Basefold,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C20,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Brakedown,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C21,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Ligero,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C22,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Whir,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C23,
+        FieldType::BN254,
+        FiatShamirHashType::MIMC5,
+        PolynomialCommitmentType::Zeromorph,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C24,
+        FieldType::BN254,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Dory,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C25,
+        FieldType::BN254,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::IPA,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C26,
+        FieldType::Goldilocksx8,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Orion,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C27,
+        FieldType::BabyBearx16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Orion,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C28,
+        FieldType::M31x16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::RawDistributed,
+        GKRScheme::Vanilla,
+    );
     test_gkr_correctness_helper::<C0>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C1>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C2>(mpi_config.clone(), None);
@@ -161,6 +262,19 @@ fn test_gkr_correctness() {
     test_gkr_correctness_helper::<C13>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C14>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C15>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C16>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C17>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C18>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C19>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C20>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C21>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C22>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C23>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C24>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C25>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C26>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C27>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C28>(mpi_config.clone(), None);
 }
 
 #[allow(unreachable_patterns)]
@@ -229,19 +343,46 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
     let mut prover = Prover::<Cfg>::new(mpi_config.clone());
     prover.prepare_mem(&circuit);
 
+    let report = draft_check::<Cfg>(&mut circuit.clone());
+    assert_eq!(report.asserted_outputs, circuit.expected_num_output_zeros);
+    assert!(report.passed());
+
     let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
         expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
             circuit.log_input_size(),
             &mpi_config,
         );
 
-    let proving_start = Instant::now();
-    let (claimed_v, proof) = prover.prove(
-        &mut circuit,
-        &pcs_params,
-        &pcs_proving_key,
-        &mut pcs_scratch,
+    let progress_reports = Arc::new(Mutex::new(vec![]));
+    let sink_reports = progress_reports.clone();
+    prover.set_progress_sink(
+        move |report: &ProgressReport| sink_reports.lock().unwrap().push(*report),
+        Duration::ZERO,
     );
+
+    let proving_start = Instant::now();
+    let (claimed_v, proof) = prover
+        .prove(
+            &mut circuit,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+
+    // every layer is reported, on the root only
+    let progress_reports = mem::take(&mut *progress_reports.lock().unwrap());
+    if mpi_config.is_root() {
+        assert_eq!(progress_reports.len(), circuit.layers.len() + 3);
+        assert_eq!(progress_reports[0].phase, ProvingPhase::Commit);
+        assert_eq!(progress_reports.last().unwrap().phase, ProvingPhase::Done);
+        assert_eq!(progress_reports.last().unwrap().percent(), 100.0);
+        assert!(progress_reports
+            .windows(2)
+            .all(|w| w[0].percent() < w[1].percent()));
+    } else {
+        assert!(progress_reports.is_empty());
+    }
     root_println!(
         mpi_config,
         "Proving time: {} μs",
@@ -261,6 +402,134 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
         .for_each(|b| print!("{} ", b));
     root_println!(mpi_config,);
 
+    // recomputing layer values from checkpoints must not change the proof
+    let mut checkpointed_circuit = circuit.clone();
+    let (checkpointed_claimed_v, checkpointed_proof) = prover
+        .prove_with_retention(
+            &mut checkpointed_circuit,
+            LayerRetention::Checkpoint { interval: 3 },
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+    assert!(checkpointed_circuit.layers[1].input_vals.is_empty());
+
+    // suspending after the commit phase and halfway through the layers, through serialized states
+    let mut suspended_circuit = circuit.clone();
+    let mut suspended = prover
+        .prove_until_commit(
+            &suspended_circuit,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+    if matches!(Cfg::SCHEME, GKRScheme::Vanilla) {
+        // a pause requested up front takes effect at the first layer boundary
+        let signal = PauseSignal::default();
+        signal.raise();
+        match prover
+            .resume_proof_preemptible(
+                &mut suspended_circuit,
+                LayerRetention::Full,
+                &suspended,
+                &signal,
+                &pcs_params,
+                &pcs_proving_key,
+                &mut pcs_scratch,
+            )
+            .unwrap()
+        {
+            ProofProgress::Suspended(next) => {
+                assert_eq!(
+                    next.claim.as_ref().unwrap().next_layer,
+                    circuit.layers.len()
+                );
+                suspended = next;
+            }
+            ProofProgress::Finished(..) => panic!("the raised pause signal was ignored"),
+        }
+    }
+    let mut suspend_at =
+        matches!(Cfg::SCHEME, GKRScheme::Vanilla).then(|| circuit.layers.len() / 2);
+    let (suspended_claimed_v, suspended_proof) = loop {
+        let mut state = vec![];
+        suspended.serialize_into(&mut state).unwrap();
+        suspended = SuspendedProof::deserialize_from(state.as_slice()).unwrap();
+
+        match prover
+            .resume_proof(
+                &mut suspended_circuit,
+                LayerRetention::Full,
+                &suspended,
+                suspend_at.take(),
+                &pcs_params,
+                &pcs_proving_key,
+                &mut pcs_scratch,
+            )
+            .unwrap()
+        {
+            ProofProgress::Suspended(next) => suspended = next,
+            ProofProgress::Finished(claimed_v, proof) => break (claimed_v, proof),
+        }
+    };
+
+    // checkpointing to disk, and restarting a proof preempted after a checkpoint
+    let checkpoints = ProofCheckpoints::new(env::temp_dir().join("expander-gkr-checkpoints"), 2);
+    checkpoints.clear(&mpi_config).unwrap();
+    let mut checkpointed_to_disk_circuit = circuit.clone();
+    let (checkpointed_to_disk_claimed_v, checkpointed_to_disk_proof) = prover
+        .prove_with_checkpoints(
+            &mut checkpointed_to_disk_circuit,
+            LayerRetention::Full,
+            &checkpoints,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+    assert!(checkpoints
+        .load_latest::<Cfg::FieldConfig>(&mpi_config)
+        .is_err());
+
+    let mut restarted_circuit = circuit.clone();
+    let preempted = prover
+        .prove_until_commit(
+            &restarted_circuit,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+    checkpoints.store(&mpi_config, 0, &preempted).unwrap();
+    let (restarted_claimed_v, restarted_proof) = prover
+        .resume_from_checkpoint(
+            &mut restarted_circuit,
+            LayerRetention::Full,
+            &checkpoints,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+
+    // reading the relayed values where they are computed, with the skip claims reduced into the
+    // claims on the layers they read
+    let mut skip_proof = matches!(Cfg::SCHEME, GKRScheme::Vanilla).then(|| {
+        let mut skip_circuit = circuit.clone();
+        assert!(skip_circuit.skip_relays() > 0);
+        let (claimed_v, proof) = prover
+            .prove(
+                &mut skip_circuit,
+                &pcs_params,
+                &pcs_proving_key,
+                &mut pcs_scratch,
+            )
+            .unwrap();
+        (skip_circuit, claimed_v, proof)
+    });
+
     let mut public_input_gathered = if mpi_config.is_root() {
         vec![
             <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ZERO;
@@ -269,7 +538,9 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
     } else {
         vec![]
     };
-    mpi_config.gather_vec(&circuit.public_input, &mut public_input_gathered);
+    mpi_config
+        .gather_vec(&circuit.public_input, &mut public_input_gathered)
+        .unwrap();
 
     // Verify
     if mpi_config.is_root() {
@@ -281,10 +552,21 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
                 .open(str)
                 .unwrap();
 
-            let mut buf = vec![];
-            proof.serialize_into(&mut buf).unwrap();
-            file.write_all(&buf).unwrap();
+            proof.serialize_into(&mut file).unwrap();
         }
+
+        // serializing into a caller-provided buffer of the exact size
+        let mut proof_bytes = vec![0u8; proof.serialized_size()];
+        assert_eq!(
+            proof.serialize_into_slice(&mut proof_bytes).unwrap(),
+            proof_bytes.len()
+        );
+        assert_eq!(
+            Proof::deserialize_from(proof_bytes.as_slice()).unwrap(),
+            proof
+        );
+        assert!(proof.serialize_into_slice(&mut proof_bytes[1..]).is_err());
+
         let verifier = Verifier::<Cfg>::new(mpi_config.clone());
         println!("Verifier created.");
         let verification_start = Instant::now();
@@ -314,6 +596,72 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
             "Multi-core Verification time: {} μs",
             par_verification_start.elapsed().as_micros()
         );
+
+        let mut session = VerifierSession::<Cfg>::new(
+            Verifier::<Cfg>::new(mpi_config.clone()),
+            circuit.clone(),
+            pcs_params.clone(),
+            pcs_verification_key.clone(),
+        );
+        let session_verification_start = Instant::now();
+        for _ in 0..2 {
+            assert!(session.verify(&public_input_gathered, &claimed_v, &proof));
+            assert!(session.par_verify(&public_input_gathered, &claimed_v, &proof));
+        }
+        println!(
+            "Session verification time (amortized): {} μs",
+            session_verification_start.elapsed().as_micros() / 4
+        );
+        assert!(verifier.verify(
+            &mut circuit,
+            &public_input_gathered,
+            &checkpointed_claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &checkpointed_proof
+        ));
+        assert!(verifier.verify(
+            &mut circuit,
+            &public_input_gathered,
+            &suspended_claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &suspended_proof
+        ));
+        assert!(verifier.verify(
+            &mut circuit,
+            &public_input_gathered,
+            &checkpointed_to_disk_claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &checkpointed_to_disk_proof
+        ));
+        assert!(verifier.verify(
+            &mut circuit,
+            &public_input_gathered,
+            &restarted_claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &restarted_proof
+        ));
+        if let Some((skip_circuit, skip_claimed_v, skip_proof)) = &mut skip_proof {
+            assert!(verifier.verify(
+                skip_circuit,
+                &public_input_gathered,
+                skip_claimed_v,
+                &pcs_params,
+                &pcs_verification_key,
+                skip_proof
+            ));
+            assert!(verifier.par_verify(
+                skip_circuit,
+                &public_input_gathered,
+                skip_claimed_v,
+                &pcs_params,
+                &pcs_verification_key,
+                skip_proof
+            ));
+        }
         println!("Correct proof verified.");
 
         let mut bad_proof = proof.clone();
@@ -349,10 +697,15 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
         let final_par_result = par_result.unwrap_or_default();
         assert!(!final_par_result,);
 
+        let session_result = panic::catch_unwind(AssertUnwindSafe(|| {
+            session.verify(&public_input_gathered, &claimed_v, &bad_proof)
+        }));
+        assert!(!session_result.unwrap_or_default());
+
         println!("Bad proof rejected.");
         println!("============== end ===============");
     }
 
     circuit.discard_control_of_shared_mem();
-    mpi_config.free_shared_mem(&mut window);
+    mpi_config.free_shared_mem(&mut window).unwrap();
 }
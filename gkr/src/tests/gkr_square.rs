@@ -140,7 +140,8 @@ fn gkr_square_correctness_test() {
         GKRScheme::GkrSquare
     );
     type GkrFieldConfigType = <GkrConfigType as GKREngine>::FieldConfig;
-let universe = MPIConfig::init().unwrap(); let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+    let universe = MPIConfig::init().unwrap();
+    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
     let mut circuit = gkr_square_test_circuit::<GkrFieldConfigType>();
     // Set input layers with N_2_0 = 3, N_2_1 = 5, N_2_2 = 7,
     // and N_2_3 varying from 0 to 15
@@ -153,7 +154,6 @@ let universe = MPIConfig::init().unwrap(); let mpi_config = MPIConfig::prover_ne
     circuit.public_input = vec![13.into()];
 
     do_prove_verify::<GkrConfigType>(&mpi_config, &mut circuit);
-   
 }
 
 fn do_prove_verify<Cfg: GKREngine>(
@@ -171,7 +171,9 @@ fn do_prove_verify<Cfg: GKREngine>(
     // Prove
     let mut prover = Prover::<Cfg>::new(mpi_config.clone());
     prover.prepare_mem(circuit);
-    let (claimed_v, proof) = prover.prove(circuit, &pcs_params, &pcs_proving_key, &mut pcs_scratch);
+    let (claimed_v, proof) = prover
+        .prove(circuit, &pcs_params, &pcs_proving_key, &mut pcs_scratch)
+        .unwrap();
 
     // Verify if root process
     if mpi_config.is_root() {
@@ -0,0 +1,107 @@
+use std::fs;
+
+use arith::Field;
+use circuit::Circuit;
+use gkr_engine::{FieldEngine, GKREngine, MPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+
+use crate::{circuit_digest, utils::*, M31x16ConfigSha2RawVanilla, ProofSession, Prover, Verifier};
+
+type Cfg = M31x16ConfigSha2RawVanilla;
+type FieldConfig = <Cfg as GKREngine>::FieldConfig;
+
+const SESSION_LABEL: &[u8] = b"test session";
+
+#[test]
+fn test_proof_session() {
+    let mpi_config = MPIConfig::prover_new(None, None);
+
+    let circuit_file = "../".to_owned() + KECCAK_M31_CIRCUIT;
+    let digest = circuit_digest(&fs::read(&circuit_file).unwrap());
+    let mut circuit =
+        Circuit::<FieldConfig>::single_thread_prover_load_circuit::<Cfg>(&circuit_file);
+    circuit.load_witness_allow_padding_testing_only(
+        &("../".to_owned() + KECCAK_M31_WITNESS),
+        &mpi_config,
+    );
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<FieldConfig, <Cfg as GKREngine>::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+
+    // the same circuit proven twice in one session
+    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let mut prover_session = ProofSession::<Cfg>::new(SESSION_LABEL);
+    let proofs: Vec<_> = (0..2)
+        .map(|_| {
+            prover
+                .prove_in_session(
+                    &mut prover_session,
+                    digest,
+                    &mut circuit.clone(),
+                    &pcs_params,
+                    &pcs_proving_key,
+                    &mut pcs_scratch,
+                )
+                .unwrap()
+        })
+        .collect();
+    // the proofs are bound to their index
+    assert_ne!(proofs[0].1.bytes, proofs[1].1.bytes);
+
+    let verifier = Verifier::<Cfg>::new(mpi_config.clone());
+    let verify = |session: &mut ProofSession<Cfg>, digest, index: usize| {
+        let (claimed_v, proof) = &proofs[index];
+        verifier.verify_in_session(
+            session,
+            digest,
+            &mut circuit.clone(),
+            &circuit.public_input,
+            claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            proof,
+        )
+    };
+
+    let mut verifier_session = ProofSession::<Cfg>::new(SESSION_LABEL);
+    assert!(verify(&mut verifier_session, digest, 0));
+    assert!(verify(&mut verifier_session, digest, 1));
+    assert_eq!(verifier_session.entries(), prover_session.entries());
+
+    // a follow-up refers to the challenges of an earlier proof
+    let challenges = prover_session.challenges_of(0, b"follow-up", 3).unwrap();
+    assert_eq!(
+        challenges,
+        verifier_session.challenges_of(0, b"follow-up", 3).unwrap()
+    );
+    assert_ne!(
+        challenges,
+        prover_session.challenges_of(1, b"follow-up", 3).unwrap()
+    );
+    assert!(prover_session.challenges_of(2, b"follow-up", 3).is_none());
+
+    // out of order, in another session, or for another circuit, the proofs fail
+    let mut verifier_session = ProofSession::<Cfg>::new(SESSION_LABEL);
+    assert!(!verify(&mut verifier_session, digest, 1));
+    assert_eq!(verifier_session.num_proofs(), 0);
+    assert!(!verify(&mut ProofSession::new(b"other session"), digest, 0));
+    assert!(!verify(&mut verifier_session, [0; 32], 0));
+
+    let mut tampered = verifier_session.clone();
+    assert!(verify(&mut tampered, digest, 0));
+    let mut claimed_v = proofs[1].0;
+    claimed_v += <FieldConfig as FieldEngine>::ChallengeField::ONE;
+    assert!(!verifier.verify_in_session(
+        &mut tampered,
+        digest,
+        &mut circuit.clone(),
+        &circuit.public_input,
+        &claimed_v,
+        &pcs_params,
+        &pcs_verification_key,
+        &proofs[1].1,
+    ));
+}
@@ -0,0 +1,78 @@
+use circuit::Circuit;
+use gkr_engine::{FieldEngine, GKREngine, MPIConfig, Proof, ProofVersion};
+use poly_commit::expander_pcs_init_testing_only;
+
+use crate::{utils::*, M31x16ConfigSha2RawVanilla, Prover, Verifier};
+
+type Cfg = M31x16ConfigSha2RawVanilla;
+type FieldConfig = <Cfg as GKREngine>::FieldConfig;
+
+#[test]
+fn test_verify_previous_version() {
+    let mpi_config = MPIConfig::prover_new(None, None);
+
+    let mut circuit = Circuit::<FieldConfig>::single_thread_prover_load_circuit::<Cfg>(
+        &("../".to_owned() + KECCAK_M31_CIRCUIT),
+    );
+    circuit.load_witness_allow_padding_testing_only(
+        &("../".to_owned() + KECCAK_M31_WITNESS),
+        &mpi_config,
+    );
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<FieldConfig, <Cfg as GKREngine>::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+
+    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (claimed_v, proof) = prover
+        .prove(
+            &mut circuit.clone(),
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+
+    prover.set_proof_version(ProofVersion::Previous);
+    let (previous_claimed_v, previous_proof) = prover
+        .prove(
+            &mut circuit.clone(),
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        )
+        .unwrap();
+    assert_eq!(previous_claimed_v, claimed_v);
+    assert_eq!(previous_proof.bytes.len() + 32, proof.bytes.len());
+    assert!(!previous_proof.bytes.starts_with(&Cfg::FINGERPRINT));
+
+    let verify = |verifier: &Verifier<Cfg>,
+                  claimed_v: &<FieldConfig as FieldEngine>::ChallengeField,
+                  proof: &Proof| {
+        verifier.verify(
+            &mut circuit.clone(),
+            &circuit.public_input,
+            claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            proof,
+        )
+    };
+
+    // the proofs of the previous version are rejected right away by default
+    let mut verifier = Verifier::<Cfg>::new(mpi_config.clone());
+    assert!(verify(&verifier, &claimed_v, &proof));
+    assert!(!verify(&verifier, &previous_claimed_v, &previous_proof));
+
+    // a verifier accepting them accepts the proofs of both versions
+    verifier.set_accept_previous_version(true);
+    assert!(verify(&verifier, &claimed_v, &proof));
+    assert!(verify(&verifier, &previous_claimed_v, &previous_proof));
+
+    // the version of a proof is bound to the transcript, it cannot be relabeled
+    let mut relabeled = previous_proof.clone();
+    relabeled.bytes.splice(..0, Cfg::FINGERPRINT);
+    assert!(!verify(&verifier, &previous_claimed_v, &relabeled));
+}
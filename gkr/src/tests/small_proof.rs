@@ -0,0 +1,128 @@
+use arith::{Field, SimdField};
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateConst, GateMul};
+use gkr_engine::{FieldEngine, GKREngine, M31x16Config, MPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+
+use crate::{is_small_circuit, M31x1ConfigSha2RawVanilla, SmallProver, SmallVerifier, Verifier};
+
+type Cfg = M31x1ConfigSha2RawVanilla;
+type FieldConfig = <Cfg as GKREngine>::FieldConfig;
+type CircuitField = <FieldConfig as FieldEngine>::CircuitField;
+
+/// A circuit checking a * b == c and d == PI[0], on the inputs a, b, c, d
+fn product_circuit<C: FieldEngine>() -> Circuit<C> {
+    let mut circuit = Circuit::default();
+
+    let mut l0 = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 2,
+        ..Default::default()
+    };
+    l0.mul.push(GateMul {
+        i_ids: [0, 1],
+        o_id: 0,
+        coef: C::CircuitField::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 0,
+    });
+    for i in [2, 3] {
+        l0.add.push(GateAdd {
+            i_ids: [i],
+            o_id: i - 1,
+            coef: C::CircuitField::ONE,
+            coef_type: CoefType::Constant,
+            gate_type: 0,
+        });
+    }
+    l0.const_.push(GateConst {
+        i_ids: [],
+        o_id: 3,
+        coef: C::CircuitField::ONE,
+        coef_type: CoefType::PublicInput(0),
+        gate_type: 0,
+    });
+    circuit.layers.push(l0);
+
+    let mut l1 = CircuitLayer {
+        input_var_num: 2,
+        output_var_num: 1,
+        ..Default::default()
+    };
+    for (i, coef) in [C::CircuitField::ONE, -C::CircuitField::ONE]
+        .into_iter()
+        .cycle()
+        .take(4)
+        .enumerate()
+    {
+        l1.add.push(GateAdd {
+            i_ids: [i],
+            o_id: i / 2,
+            coef,
+            coef_type: CoefType::Constant,
+            gate_type: 0,
+        });
+    }
+    circuit.layers.push(l1);
+    circuit.expected_num_output_zeros = 2;
+
+    circuit
+}
+
+fn values(values: &[u32]) -> Vec<CircuitField> {
+    values.iter().map(|v| CircuitField::from(*v)).collect()
+}
+
+#[test]
+fn test_small_proof() {
+    assert!(is_small_circuit(&product_circuit::<FieldConfig>()));
+    // the lanes of a SIMD field are proven by the usual prover
+    assert!(!is_small_circuit(&product_circuit::<M31x16Config>()));
+
+    let circuit = product_circuit::<FieldConfig>();
+    let (pcs_params, pcs_proving_key, pcs_verification_key, _) =
+        expander_pcs_init_testing_only::<FieldConfig, <Cfg as GKREngine>::PCSConfig>(
+            circuit.log_input_size(),
+            &MPIConfig::prover_new(None, None),
+        );
+    let mut prover = SmallProver::<Cfg>::new(circuit, pcs_params.clone(), pcs_proving_key);
+    let mut verifier = SmallVerifier::<Cfg>::new(
+        product_circuit(),
+        pcs_params.clone(),
+        pcs_verification_key.clone(),
+    );
+
+    // several statements with the same prover and verifier
+    let statements = [([3, 5, 15, 7], [7]), ([2, 9, 18, 4], [4])];
+    let proofs: Vec<_> = statements
+        .iter()
+        .map(|(private_inputs, public_inputs)| {
+            prover
+                .prove(&values(private_inputs), &values(public_inputs))
+                .unwrap()
+        })
+        .collect();
+    for ((_, public_inputs), (claimed_v, proof)) in statements.iter().zip(&proofs) {
+        assert!(claimed_v.is_zero());
+        assert!(verifier.verify(&values(public_inputs), claimed_v, proof));
+    }
+    assert!(!verifier.verify(&values(&[8]), &proofs[0].0, &proofs[0].1));
+    assert!(!verifier.verify(&values(&[7, 7]), &proofs[0].0, &proofs[0].1));
+
+    // the proofs are those of a single process
+    let mut circuit = product_circuit::<FieldConfig>();
+    circuit.pre_process_gkr();
+    let public_input = vec![<FieldConfig as FieldEngine>::SimdCircuitField::pack_full(
+        &CircuitField::from(7u32),
+    )];
+    assert!(Verifier::<Cfg>::new(MPIConfig::verifier_new(1)).verify(
+        &mut circuit,
+        &public_input,
+        &proofs[0].0,
+        &pcs_params,
+        &pcs_verification_key,
+        &proofs[0].1,
+    ));
+
+    assert!(prover.prove(&values(&[3, 5, 15]), &values(&[7])).is_err());
+    assert!(prover.prove(&values(&[3, 5, 15, 7]), &values(&[])).is_err());
+}
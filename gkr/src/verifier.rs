@@ -5,10 +5,15 @@ mod common;
 pub use common::*;
 
 mod gkr_vanilla;
-pub use gkr_vanilla::gkr_verify;
+pub use gkr_vanilla::{gkr_verify, gkr_verify_with_scratch_pad};
 
 mod gkr_square;
-pub use gkr_square::gkr_square_verify;
+pub use gkr_square::{gkr_square_verify, gkr_square_verify_with_scratch_pad};
 
 mod snark;
 pub use snark::Verifier;
+
+mod session;
+pub use session::VerifierSession;
+
+mod skip_claims;
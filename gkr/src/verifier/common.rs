@@ -114,7 +114,7 @@ pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
     }
     GKRVerifierHelper::set_r_simd_xy(&r_simd_xy, sp);
 
-    for _i_var in 0..proving_time_mpi_size.ilog2() {
+    for _i_var in 0..proving_time_mpi_size.next_power_of_two().ilog2() {
         verified &= verify_sumcheck_step::<F>(
             &mut proof_reader,
             SUMCHECK_GKR_SIMD_MPI_DEGREE,
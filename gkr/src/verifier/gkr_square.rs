@@ -11,21 +11,47 @@ use sumcheck::{GKRVerifierHelper, VerifierScratchPad, SUMCHECK_GKR_SQUARE_DEGREE
 
 #[allow(clippy::type_complexity)]
 pub fn gkr_square_verify<C: FieldEngine>(
+    proving_time_mpi_size: usize,
+    circuit: &Circuit<C>,
+    public_input: &[C::SimdCircuitField],
+    claimed_v: &C::ChallengeField,
+    transcript: &mut impl Transcript,
+    proof_reader: impl Read,
+) -> (bool, ExpanderSingleVarChallenge<C>, C::ChallengeField) {
+    let mut sp = VerifierScratchPad::<C>::new(circuit, proving_time_mpi_size);
+    gkr_square_verify_with_scratch_pad(
+        proving_time_mpi_size,
+        circuit,
+        public_input,
+        claimed_v,
+        transcript,
+        proof_reader,
+        &mut sp,
+    )
+}
+
+/// Same as `gkr_square_verify`, with a caller-provided scratch pad.
+#[allow(clippy::type_complexity)]
+pub fn gkr_square_verify_with_scratch_pad<C: FieldEngine>(
     proving_time_mpi_size: usize,
     circuit: &Circuit<C>,
     public_input: &[C::SimdCircuitField],
     claimed_v: &C::ChallengeField,
     transcript: &mut impl Transcript,
     mut proof_reader: impl Read,
+    sp: &mut VerifierScratchPad<C>,
 ) -> (bool, ExpanderSingleVarChallenge<C>, C::ChallengeField) {
     assert_ne!(
         C::FIELD_TYPE,
         FieldType::GF2Ext128,
         "GF2 is not supported in GKR^2"
     );
+    assert!(
+        !circuit.has_skip_inputs(),
+        "skip inputs are not supported in GKR^2"
+    );
 
     let timer = start_timer!(|| "gkr verify");
-    let mut sp = VerifierScratchPad::<C>::new(circuit, proving_time_mpi_size);
 
     let layer_num = circuit.layers.len();
 
@@ -43,6 +69,7 @@ pub fn gkr_square_verify<C: FieldEngine>(
     let mut current_claim = *claimed_v;
     log::trace!("Starting claim: {current_claim:?}",);
     for i in (0..layer_num).rev() {
+        transcript.begin_phase(&format!("layer {i}"));
         let cur_verified = sumcheck_verify_gkr_square_layer(
             proving_time_mpi_size,
             &circuit.layers[i],
@@ -51,7 +78,7 @@ pub fn gkr_square_verify<C: FieldEngine>(
             &mut current_claim,
             &mut proof_reader,
             transcript,
-            &mut sp,
+            sp,
             i == layer_num - 1,
             false,
         );
@@ -121,7 +148,7 @@ pub fn sumcheck_verify_gkr_square_layer<C: FieldEngine>(
     }
     GKRVerifierHelper::set_r_simd_xy(&challenge.r_simd, sp);
 
-    for _i_var in 0..proving_time_mpi_size.trailing_zeros() {
+    for _i_var in 0..proving_time_mpi_size.next_power_of_two().trailing_zeros() {
         verified &= verify_sumcheck_step::<C>(
             &mut proof_reader,
             degree,
@@ -138,7 +165,8 @@ pub fn sumcheck_verify_gkr_square_layer<C: FieldEngine>(
     log::trace!("v_claim: {v_claim:?}");
 
     sum -= v_claim * GKRVerifierHelper::eval_pow_1(&layer.uni, sp)
-        + v_claim.exp(5) * GKRVerifierHelper::eval_pow_5(&layer.uni, sp);
+        + v_claim.exp(5) * GKRVerifierHelper::eval_pow_5(&layer.uni, sp)
+        + GKRVerifierHelper::eval_custom_gates(layer, &v_claim, sp);
     transcript.append_field_element(&v_claim);
 
     verified &= sum == C::ChallengeField::ZERO;
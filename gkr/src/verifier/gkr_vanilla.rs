@@ -6,15 +6,46 @@ use sumcheck::VerifierScratchPad;
 use utils::timer::Timer;
 
 use super::common::sumcheck_verify_gkr_layer;
+use super::skip_claims::{verify_skip_claims, verify_skip_reduction};
+use crate::{ClaimLedger, EvalClaim};
 
 #[allow(clippy::type_complexity)]
 pub fn gkr_verify<F: FieldEngine>(
+    proving_time_mpi_size: usize,
+    circuit: &Circuit<F>,
+    public_input: &[F::SimdCircuitField],
+    claimed_v: &F::ChallengeField,
+    transcript: &mut impl Transcript,
+    proof_reader: impl Read,
+) -> (
+    bool,
+    ExpanderDualVarChallenge<F>,
+    F::ChallengeField,
+    Option<F::ChallengeField>,
+) {
+    let mut sp = VerifierScratchPad::<F>::new(circuit, proving_time_mpi_size);
+    gkr_verify_with_scratch_pad(
+        proving_time_mpi_size,
+        circuit,
+        public_input,
+        claimed_v,
+        transcript,
+        proof_reader,
+        &mut sp,
+    )
+}
+
+/// Same as `gkr_verify`, but reuses a scratch pad built for `circuit` and
+/// `proving_time_mpi_size`, so that repeated verifications skip the allocation.
+#[allow(clippy::type_complexity)]
+pub fn gkr_verify_with_scratch_pad<F: FieldEngine>(
     proving_time_mpi_size: usize,
     circuit: &Circuit<F>,
     public_input: &[F::SimdCircuitField],
     claimed_v: &F::ChallengeField,
     transcript: &mut impl Transcript,
     mut proof_reader: impl Read,
+    sp: &mut VerifierScratchPad<F>,
 ) -> (
     bool,
     ExpanderDualVarChallenge<F>,
@@ -22,7 +53,6 @@ pub fn gkr_verify<F: FieldEngine>(
     Option<F::ChallengeField>,
 ) {
     let timer = Timer::new("gkr_verify", true);
-    let mut sp = VerifierScratchPad::<F>::new(circuit, proving_time_mpi_size);
 
     let layer_num = circuit.layers.len();
 
@@ -36,9 +66,31 @@ pub fn gkr_verify<F: FieldEngine>(
     let mut alpha = None;
     let mut claimed_v0 = *claimed_v;
     let mut claimed_v1 = None;
+    let mut skip_claims = vec![];
+    let mut ledger = ClaimLedger::default();
+    ledger.make(EvalClaim::inputs(
+        layer_num,
+        &challenge.challenge_x(),
+        claimed_v0,
+    ));
 
     let mut verified = true;
     for i in (0..layer_num).rev() {
+        transcript.begin_phase(&format!("layer {i}"));
+        for input_claim in EvalClaim::inputs_at(i + 1, &challenge, claimed_v0, claimed_v1) {
+            ledger.discharge(input_claim);
+        }
+        verify_skip_claims(
+            circuit,
+            i,
+            &challenge,
+            alpha,
+            &mut claimed_v0,
+            &mut skip_claims,
+            &mut ledger,
+            &mut proof_reader,
+            transcript,
+        );
         let cur_verified = sumcheck_verify_gkr_layer(
             proving_time_mpi_size,
             &circuit.layers[i],
@@ -49,12 +101,31 @@ pub fn gkr_verify<F: FieldEngine>(
             alpha,
             &mut proof_reader,
             transcript,
-            &mut sp,
+            sp,
             i == layer_num - 1,
             false,
         );
 
         verified &= cur_verified;
+        for input_claim in EvalClaim::inputs_at(i, &challenge, claimed_v0, claimed_v1) {
+            ledger.make(input_claim);
+        }
+
+        // the skip claims reading the inputs leave a single claim on them, with no alpha
+        verified &= verify_skip_reduction(
+            proving_time_mpi_size,
+            circuit,
+            i,
+            &mut challenge,
+            &mut claimed_v0,
+            &mut claimed_v1,
+            &mut skip_claims,
+            &mut ledger,
+            &mut proof_reader,
+            transcript,
+            sp,
+        )
+        .unwrap_or(true);
         alpha = if challenge.rz_1.is_some() {
             Some(transcript.generate_field_element::<F::ChallengeField>())
         } else {
@@ -62,6 +133,17 @@ pub fn gkr_verify<F: FieldEngine>(
         };
     }
     timer.stop();
+
+    // the claims on the inputs are left to the opening of the PCS
+    for input_claim in EvalClaim::inputs_at(0, &challenge, claimed_v0, claimed_v1) {
+        ledger.discharge(input_claim);
+    }
+    debug_assert!(
+        ledger.audit().is_clean(),
+        "GKR claims not discharged:\n{ledger}"
+    );
+    log::debug!("GKR claims:\n{ledger}");
+
     let challenge = ExpanderDualVarChallenge::new(
         challenge.rz_0,
         challenge.rz_1,
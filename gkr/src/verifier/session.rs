@@ -0,0 +1,149 @@
+use std::io::Cursor;
+
+use circuit::Circuit;
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, GKREngine, MPIEngine, Proof, StructuredReferenceString, Transcript,
+};
+use sumcheck::VerifierScratchPad;
+use utils::timer::Timer;
+
+use super::Verifier;
+
+/// A verifier bound to a single circuit and PCS setup, for verifying many proofs that differ
+/// only in witness.
+///
+/// The circuit-dependent preparation (random coefficient slots, scratch pad sizing and the
+/// interpolation constants within) is done once in `new` and reused across `verify` calls.
+/// Everything derived from the transcript is still recomputed per proof.
+pub struct VerifierSession<'a, Cfg: GKREngine> {
+    pub verifier: Verifier<'a, Cfg>,
+    pub circuit: Circuit<Cfg::FieldConfig>,
+    pub pcs_params: <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+    pub pcs_verification_key:
+        <<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+
+    proving_time_mpi_size: usize,
+    sp: VerifierScratchPad<Cfg::FieldConfig>,
+}
+
+impl<'a, Cfg: GKREngine> VerifierSession<'a, Cfg> {
+    /// The circuit is expected to be pre-processed, e.g., loaded with
+    /// `Circuit::verifier_load_circuit`.
+    pub fn new(
+        verifier: Verifier<'a, Cfg>,
+        mut circuit: Circuit<Cfg::FieldConfig>,
+        pcs_params: <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_verification_key: <<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+    ) -> Self {
+        if !circuit.rnd_coefs_identified {
+            circuit.identify_rnd_coefs();
+        }
+
+        let proving_time_mpi_size = verifier.mpi_config.world_size();
+        let sp = VerifierScratchPad::<Cfg::FieldConfig>::new(&circuit, proving_time_mpi_size);
+
+        Self {
+            verifier,
+            circuit,
+            pcs_params,
+            pcs_verification_key,
+            proving_time_mpi_size,
+            sp,
+        }
+    }
+
+    /// Partially verify the proof, see `Verifier::verify`.
+    pub fn verify(
+        &mut self,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        proof: &Proof,
+    ) -> bool {
+        let timer = Timer::new("session verify", true);
+
+        let mut transcript = Cfg::TranscriptConfig::new();
+        let mut cursor = Cursor::new(&proof.bytes);
+
+        let Some(commitment) = self.verifier.pre_gkr(
+            &mut cursor,
+            &mut self.circuit,
+            &mut transcript,
+            self.proving_time_mpi_size,
+        ) else {
+            return false;
+        };
+
+        let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) = self.verifier.gkr(
+            &self.circuit,
+            public_input,
+            claimed_v,
+            self.proving_time_mpi_size,
+            &mut transcript,
+            &mut cursor,
+            &mut self.sp,
+        );
+
+        verified &= self.verifier.post_gkr(
+            &self.pcs_params,
+            &self.pcs_verification_key,
+            &commitment,
+            &mut challenge_x,
+            &claim_x,
+            &mut challenge_y,
+            &claim_y,
+            &mut transcript,
+            &mut cursor,
+        );
+
+        timer.stop();
+        verified
+    }
+
+    /// Multi-core version of `verify`, see `Verifier::par_verify`.
+    pub fn par_verify(
+        &mut self,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        proof: &Proof,
+    ) -> bool {
+        let timer = Timer::new("session par verify", true);
+
+        let mut transcript = Cfg::TranscriptConfig::new();
+        let mut cursor = Cursor::new(&proof.bytes);
+
+        let Some(commitment) = self.verifier.pre_gkr(
+            &mut cursor,
+            &mut self.circuit,
+            &mut transcript,
+            self.proving_time_mpi_size,
+        ) else {
+            return false;
+        };
+
+        let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) =
+            self.verifier.gkr_parallel(
+                &self.circuit,
+                public_input,
+                claimed_v,
+                self.proving_time_mpi_size,
+                &mut transcript,
+                &mut cursor,
+                &self.sp,
+            );
+
+        verified &= self.verifier.post_gkr(
+            &self.pcs_params,
+            &self.pcs_verification_key,
+            &commitment,
+            &mut challenge_x,
+            &claim_x,
+            &mut challenge_y,
+            &claim_y,
+            &mut transcript,
+            &mut cursor,
+        );
+
+        timer.stop();
+        verified
+    }
+}
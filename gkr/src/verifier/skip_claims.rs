@@ -0,0 +1,136 @@
+//! Verifier side of the skip claims, see `crate::prover::skip_claims`.
+
+use std::io::Read;
+
+use arith::Field;
+use circuit::Circuit;
+use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, Transcript};
+use polynomials::EqPolynomial;
+use serdes::ExpSerde;
+use sumcheck::{GKRVerifierHelper, VerifierScratchPad, SUMCHECK_GKR_DEGREE};
+
+use super::common::verify_sumcheck_step;
+use crate::{ClaimLedger, EvalClaim, SkipClaim};
+
+/// Read the contributions of the skip inputs of layer `i` to the claim on its outputs, take them
+/// off `claimed_v0`, and record the skip claims with their values.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_skip_claims<F: FieldEngine>(
+    circuit: &Circuit<F>,
+    i: usize,
+    challenge: &ExpanderDualVarChallenge<F>,
+    alpha: Option<F::ChallengeField>,
+    claimed_v0: &mut F::ChallengeField,
+    skip_claims: &mut Vec<(SkipClaim<F>, F::ChallengeField)>,
+    ledger: &mut ClaimLedger<F>,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+) {
+    for skip in 0..circuit.layers[i].skip_inputs.len() {
+        let value = F::ChallengeField::deserialize_from(&mut proof_reader).unwrap();
+        transcript.append_field_element(&value);
+        *claimed_v0 -= value;
+
+        let skip_claim = SkipClaim {
+            layer: i,
+            skip,
+            challenge: challenge.clone(),
+            alpha,
+        };
+        ledger.make(EvalClaim::skip_inputs(&skip_claim, value));
+        skip_claims.push((skip_claim, value));
+    }
+}
+
+/// Verify the reduction of the claims of the sumcheck of layer `i` on its inputs, at
+/// `challenge`, and of the skip claims reading them to a single claim, which replaces them.
+/// Returns None if no skip claim reads the inputs.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_skip_reduction<F: FieldEngine>(
+    proving_time_mpi_size: usize,
+    circuit: &Circuit<F>,
+    i: usize,
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    claimed_v0: &mut F::ChallengeField,
+    claimed_v1: &mut Option<F::ChallengeField>,
+    skip_claims: &mut Vec<(SkipClaim<F>, F::ChallengeField)>,
+    ledger: &mut ClaimLedger<F>,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+    sp: &mut VerifierScratchPad<F>,
+) -> Option<bool> {
+    let (pending, rest): (Vec<_>, Vec<_>) = skip_claims
+        .drain(..)
+        .partition(|(skip_claim, _)| skip_claim.source(circuit) == i);
+    *skip_claims = rest;
+    if pending.is_empty() {
+        return None;
+    }
+    for (skip_claim, value) in &pending {
+        ledger.discharge(EvalClaim::skip_inputs(skip_claim, *value));
+    }
+    for input_claim in EvalClaim::inputs_at(i, challenge, *claimed_v0, *claimed_v1) {
+        ledger.discharge(input_claim);
+    }
+
+    let beta = transcript.generate_field_element::<F::ChallengeField>();
+    let mut sum = *claimed_v0;
+    if let Some(v1) = claimed_v1 {
+        sum += beta * *v1;
+    }
+    let mut coef = beta * beta;
+    for (_, value) in &pending {
+        sum += coef * *value;
+        coef *= beta;
+    }
+
+    let var_num = circuit.layers[i].input_var_num;
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    let mpi_var_num = proving_time_mpi_size.next_power_of_two().ilog2() as usize;
+    let mut r = vec![];
+    let mut verified = true;
+    for _ in 0..simd_var_num + var_num + mpi_var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_DEGREE,
+            transcript,
+            &mut sum,
+            &mut r,
+            sp,
+        );
+    }
+    let v_claim = F::ChallengeField::deserialize_from(&mut proof_reader).unwrap();
+    transcript.append_field_element(&v_claim);
+
+    // the weight of the inputs at r in the combined claim
+    let (r_simd, r_rest) = r.split_at(simd_var_num);
+    let (rz, r_mpi) = r_rest.split_at(var_num);
+    let eq_simd_mpi = |challenge: &ExpanderDualVarChallenge<F>| {
+        EqPolynomial::eq_vec(&challenge.r_simd, r_simd)
+            * EqPolynomial::eq_vec(&challenge.r_mpi, r_mpi)
+    };
+    let mut x_weight = EqPolynomial::eq_vec(&challenge.rz_0, rz);
+    if let Some(ry) = &challenge.rz_1 {
+        x_weight += beta * EqPolynomial::eq_vec(ry, rz);
+    }
+    let mut weight = eq_simd_mpi(challenge) * x_weight;
+    let eq_rz = EqPolynomial::build_eq_x_r(rz);
+    let mut coef = beta * beta;
+    for (skip_claim, _) in &pending {
+        let x_weight = skip_claim
+            .source_weights(circuit)
+            .iter()
+            .zip(&eq_rz)
+            .fold(F::ChallengeField::ZERO, |acc, (w, eq)| acc + *w * *eq);
+        weight += coef * eq_simd_mpi(&skip_claim.challenge) * x_weight;
+        coef *= beta;
+    }
+    verified &= sum == weight * v_claim;
+
+    GKRVerifierHelper::set_rx(rz, sp);
+    *challenge = ExpanderDualVarChallenge::new(rz.to_vec(), None, r_simd.to_vec(), r_mpi.to_vec());
+    *claimed_v0 = v_claim;
+    *claimed_v1 = None;
+    ledger.make(EvalClaim::inputs(i, &challenge.challenge_x(), v_claim));
+    Some(verified)
+}
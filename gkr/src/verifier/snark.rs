@@ -1,5 +1,5 @@
 use std::{
-    io::{Cursor, Read},
+    io::{Cursor, Read, Seek, SeekFrom},
     marker::PhantomData,
     vec,
 };
@@ -8,7 +8,7 @@ use super::gkr_square::sumcheck_verify_gkr_square_layer;
 use circuit::Circuit;
 use gkr_engine::{
     ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine, GKRScheme, MPIConfig,
-    MPIEngine, Proof, StructuredReferenceString, Transcript,
+    MPIEngine, Proof, ProofVersion, StructuredReferenceString, Transcript,
 };
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
@@ -20,11 +20,15 @@ use utils::timer::Timer;
 
 #[cfg(feature = "grinding")]
 use crate::grind;
-use crate::{gkr_square_verify, gkr_verify, parse_proof, sumcheck_verify_gkr_layer};
+use crate::{
+    gkr_square_verify_with_scratch_pad, gkr_verify_with_scratch_pad, parse_proof,
+    sumcheck_verify_gkr_layer,
+};
 
 #[derive(Default)]
 pub struct Verifier<'a, Cfg: GKREngine> {
     pub mpi_config: MPIConfig<'a>,
+    accept_previous_version: bool,
     phantom: PhantomData<Cfg>,
 }
 
@@ -32,28 +36,72 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
     pub fn new(mpi_config: MPIConfig<'a>) -> Self {
         Self {
             mpi_config,
+            accept_previous_version: false,
             phantom: PhantomData,
         }
     }
 
+    /// Also accept the proofs of `ProofVersion::Previous`, e.g., while the provers of a
+    /// deployment are not upgraded yet. Without a fingerprint, the proofs of another config are
+    /// then only rejected when they fail to verify, rather than right away.
+    pub fn set_accept_previous_version(&mut self, accept: bool) {
+        self.accept_previous_version = accept;
+    }
+
     /// Prior to GKR, we need to do the following:
-    /// 1. Parse the commitment from the proof reader and use that to initialize the transcript.
-    /// 2. (Optionally) grinding.
-    /// 3. Fill the circuit with random coefficients.
+    /// 1. Check the config fingerprint the proof starts with against ours, if the proof is of the
+    ///    current version of the protocol.
+    /// 2. Parse the commitment from the proof reader and use that to initialize the transcript.
+    /// 3. (Optionally) grinding.
+    /// 4. Fill the circuit with random coefficients.
+    ///
+    /// Returns `None` if the proof was generated with another config, or with the previous
+    /// version of the protocol unless it is accepted.
     #[inline(always)]
     pub(crate) fn pre_gkr(
         &self,
-        mut proof_reader: impl Read,
+        mut proof_reader: impl Read + Seek,
         circuit: &mut Circuit<Cfg::FieldConfig>,
         transcript: &mut Cfg::TranscriptConfig,
         proving_time_mpi_size: usize,
-    ) -> <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Commitment {
+    ) -> Option<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Commitment> {
         let timer = Timer::new("pre_gkr", true);
-        let commitment =
+        let mut fingerprint = [0u8; 32];
+        let version = match proof_reader.read_exact(&mut fingerprint) {
+            Ok(()) if fingerprint == Cfg::FINGERPRINT => Some(ProofVersion::Current),
+            // the proofs of the previous version start with the commitment
+            Ok(()) if self.accept_previous_version => proof_reader
+                .seek(SeekFrom::Current(-(fingerprint.len() as i64)))
+                .ok()
+                .map(|_| ProofVersion::Previous),
+            _ => None,
+        };
+        transcript.begin_phase("commitment");
+        match version {
+            Some(ProofVersion::Current) => transcript.append_u8_slice(&fingerprint),
+            Some(ProofVersion::Previous) => {
+                log::warn!("Proof has no fingerprint, verifying it as of the previous version")
+            }
+            None => {
+                log::error!(
+                    "Proof was generated with another config or version: fingerprint {:02x?}, expected {:02x?}",
+                    fingerprint,
+                    Cfg::FINGERPRINT
+                );
+                timer.stop();
+                return None;
+            }
+        }
+
+        let Ok(commitment) =
             <<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Commitment as ExpSerde>::deserialize_from(
                 &mut proof_reader,
             )
-            .unwrap();
+        else {
+            log::error!("Proof does not start with a commitment of the config");
+            timer.stop();
+            return None;
+        };
         let mut buffer = vec![];
         commitment.serialize_into(&mut buffer).unwrap();
 
@@ -75,7 +123,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
 
         timer.stop();
 
-        commitment
+        Some(commitment)
     }
 
     /// Main body of the GKR verification.
@@ -93,6 +141,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         proving_time_mpi_size: usize,
         transcript: &mut Cfg::TranscriptConfig,
         mut proof_reader: impl Read,
+        sp: &mut VerifierScratchPad<Cfg::FieldConfig>,
     ) -> (
         bool,
         ExpanderSingleVarChallenge<Cfg::FieldConfig>,
@@ -101,15 +150,17 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
     ) {
         let timer = Timer::new("gkr", true);
+        transcript.begin_phase("gkr");
         let (verified, challenge_x, challenge_y, claim_x, claim_y) = match Cfg::SCHEME {
             GKRScheme::Vanilla => {
-                let (gkr_verified, challenge, claim_x, claim_y) = gkr_verify(
+                let (gkr_verified, challenge, claim_x, claim_y) = gkr_verify_with_scratch_pad(
                     proving_time_mpi_size,
                     circuit,
                     public_input,
                     claimed_v,
                     transcript,
                     &mut proof_reader,
+                    sp,
                 );
 
                 (
@@ -121,13 +172,14 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
                 )
             }
             GKRScheme::GkrSquare => {
-                let (gkr_verified, challenge_x, claim_x) = gkr_square_verify(
+                let (gkr_verified, challenge_x, claim_x) = gkr_square_verify_with_scratch_pad(
                     proving_time_mpi_size,
                     circuit,
                     public_input,
                     claimed_v,
                     transcript,
                     &mut proof_reader,
+                    sp,
                 );
 
                 (gkr_verified, challenge_x, None, claim_x, None)
@@ -153,6 +205,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         proving_time_mpi_size: usize,
         transcript: &mut Cfg::TranscriptConfig,
         mut proof_reader: impl Read,
+        sp: &VerifierScratchPad<Cfg::FieldConfig>,
     ) -> (
         bool,
         ExpanderSingleVarChallenge<Cfg::FieldConfig>,
@@ -160,7 +213,21 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         <Cfg::FieldConfig as FieldEngine>::ChallengeField,
         Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
     ) {
+        // the skip claims chain the layers, which are then verified one after the other
+        if circuit.has_skip_inputs() {
+            return self.gkr(
+                circuit,
+                public_input,
+                claimed_v,
+                proving_time_mpi_size,
+                transcript,
+                proof_reader,
+                &mut sp.clone(),
+            );
+        }
+
         let parse_proof_timer = Timer::new("parse_proof", true);
+        transcript.begin_phase("gkr");
         let xy_var_degree = match Cfg::SCHEME {
             GKRScheme::Vanilla => SUMCHECK_GKR_DEGREE,
             GKRScheme::GkrSquare => SUMCHECK_GKR_SQUARE_DEGREE,
@@ -177,7 +244,6 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         parse_proof_timer.stop();
 
         let gkr_parallel_timer = Timer::new("gkr_parallel", true);
-        let sp = VerifierScratchPad::<Cfg::FieldConfig>::new(circuit, proving_time_mpi_size);
         let (verified, challenge_x, challenge_y, claim_x, claim_y) = match Cfg::SCHEME {
             GKRScheme::Vanilla => {
                 let gkr_verified = verification_units
@@ -268,6 +334,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         mut proof_reader: impl Read,
     ) -> bool {
         let timer = Timer::new("post_gkr", true);
+        transcript.begin_phase("pcs opening");
         let mut verified = self.get_pcs_opening_from_proof_and_verify(
             pcs_params,
             pcs_verification_key,
@@ -305,22 +372,51 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
         pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
         proof: &Proof,
+    ) -> bool {
+        self.verify_with_transcript(
+            circuit,
+            public_input,
+            claimed_v,
+            pcs_params,
+            pcs_verification_key,
+            proof,
+            &mut Cfg::TranscriptConfig::new(),
+        )
+    }
+
+    /// Same as `verify`, running the verifier transcript on the fresh `transcript`, e.g., the
+    /// `RecordingTranscript` of a `Traced` config to debug a proof.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_transcript(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+        transcript: &mut Cfg::TranscriptConfig,
     ) -> bool {
         let timer = Timer::new("snark verify", true);
 
         let proving_time_mpi_size = self.mpi_config.world_size();
-        let mut transcript = Cfg::TranscriptConfig::new();
         let mut cursor = Cursor::new(&proof.bytes);
 
-        let commitment = self.pre_gkr(&mut cursor, circuit, &mut transcript, proving_time_mpi_size);
+        let Some(commitment) =
+            self.pre_gkr(&mut cursor, circuit, transcript, proving_time_mpi_size)
+        else {
+            return false;
+        };
 
+        let mut sp = VerifierScratchPad::<Cfg::FieldConfig>::new(circuit, proving_time_mpi_size);
         let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr(
             circuit,
             public_input,
             claimed_v,
             proving_time_mpi_size,
-            &mut transcript,
+            transcript,
             &mut cursor,
+            &mut sp,
         );
 
         verified &= self.post_gkr(
@@ -331,7 +427,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
             &claim_x,
             &mut challenge_y,
             &claim_y,
-            &mut transcript,
+            transcript,
             &mut cursor,
         );
 
@@ -355,8 +451,13 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         let mut transcript = Cfg::TranscriptConfig::new();
         let mut cursor = Cursor::new(&proof.bytes);
 
-        let commitment = self.pre_gkr(&mut cursor, circuit, &mut transcript, proving_time_mpi_size);
+        let Some(commitment) =
+            self.pre_gkr(&mut cursor, circuit, &mut transcript, proving_time_mpi_size)
+        else {
+            return false;
+        };
 
+        let sp = VerifierScratchPad::<Cfg::FieldConfig>::new(circuit, proving_time_mpi_size);
         let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr_parallel(
             circuit,
             public_input,
@@ -364,6 +465,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
             proving_time_mpi_size,
             &mut transcript,
             &mut cursor,
+            &sp,
         );
 
         verified &= self.post_gkr(
@@ -395,6 +497,11 @@ impl<Cfg: GKREngine> Verifier<'_, Cfg> {
         transcript: &mut impl Transcript,
         proof_reader: impl Read,
     ) -> bool {
+        if let Err(e) = open_at.check_shape(self.mpi_config.world_size()) {
+            log::warn!("rejecting the opening of the input layer: {e}");
+            return false;
+        }
+
         let opening = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Opening::deserialize_from(
             proof_reader,
         )
@@ -79,7 +79,7 @@ pub fn parse_proof<F: FieldEngine>(
         vec![SumcheckVerificationUnit::<F>::default(); circuit.layers.len()];
     let n_output_vars = circuit.layers.last().unwrap().output_var_num;
     let n_simd_vars = <F::SimdCircuitField as SimdField>::PACK_SIZE.trailing_zeros() as usize;
-    let n_mpi_vars = proving_time_mpi_size.trailing_zeros() as usize;
+    let n_mpi_vars = proving_time_mpi_size.next_power_of_two().trailing_zeros() as usize;
 
     let mut challenge: ExpanderDualVarChallenge<F> =
         ExpanderSingleVarChallenge::sample_from_transcript(
@@ -93,6 +93,7 @@ pub fn parse_proof<F: FieldEngine>(
     let mut claim_y = None;
 
     for i in (0..circuit.layers.len()).rev() {
+        transcript.begin_phase(&format!("layer {i}"));
         let verification_unit = &mut verification_units[i];
         verification_unit.claim = SumcheckClaim {
             challenge: challenge.clone(),
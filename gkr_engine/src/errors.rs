@@ -12,9 +12,99 @@ pub enum ExpErrors {
     #[error("Unknown string `{0}` for FiatShamir Hash Type")]
     FiatShamirHashTypeError(String),
 
+    #[error("Unknown string `{0}` for MPI chunk size")]
+    MPIChunkSizeError(String),
+
     #[error("field serde error: {0:?}")]
     SerdeError(#[from] SerdeError),
 
+    #[error("witness error: {0}")]
+    WitnessError(String),
+
+    #[error("MPI rank {rank} aborted: {reason}")]
+    MPIAbort { rank: usize, reason: String },
+
+    #[error(transparent)]
+    MPIError(#[from] MPIError),
+
+    #[error(transparent)]
+    ChallengeShapeError(#[from] ChallengeShapeError),
+
+    #[error(transparent)]
+    WorldSizeError(#[from] WorldSizeError),
+
     #[error("other error: {0:?}")]
     OtherError(#[from] std::io::Error),
 }
+
+/// A failed interaction of a process with the others, through an `MPIEngine`
+#[derive(Debug, Error)]
+#[error("MPI rank {rank} failed in {collective}: {reason}")]
+pub struct MPIError {
+    /// The rank of the process the interaction failed on
+    pub rank: usize,
+    /// The method of the engine that failed, e.g., `gather_vec`
+    pub collective: &'static str,
+    pub reason: String,
+}
+
+impl MPIError {
+    #[inline]
+    pub fn new(rank: usize, collective: &'static str, reason: impl ToString) -> Self {
+        Self {
+            rank,
+            collective,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+pub type MPIResult<T> = Result<T, MPIError>;
+
+/// A challenge whose variables do not match the SIMD lanes of the field config or the processes
+/// of the world, see `ExpanderSingleVarChallenge::check_shape`
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ChallengeShapeError {
+    #[error("expected {expected} SIMD variables for {n_lanes} lanes, got {got}")]
+    SimdVars {
+        n_lanes: usize,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("expected {expected} MPI variables for {world_size} processes, got {got}")]
+    MPIVars {
+        world_size: usize,
+        expected: usize,
+        got: usize,
+    },
+}
+
+/// A failed batch opening, see `ExpanderPCS::multi_points_batch_open`
+#[derive(Debug, Error)]
+pub enum BatchOpenError {
+    #[error(transparent)]
+    ChallengeShapeError(#[from] ChallengeShapeError),
+
+    #[error(transparent)]
+    MPIError(#[from] MPIError),
+}
+
+/// An MPI world the processes of which a PCS cannot be distributed over, see
+/// `ExpanderPCS::check_world_size`
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "{pcs} is distributed over the processes, the world size {world_size} must be a power of two"
+)]
+pub struct WorldSizeError {
+    pub pcs: &'static str,
+    pub world_size: usize,
+}
+
+/// For the loads and stores that communicate, e.g., the checkpoints of a proof
+impl From<MPIError> for SerdeError {
+    #[inline]
+    fn from(e: MPIError) -> Self {
+        SerdeError::IOError(std::io::Error::other(e))
+    }
+}
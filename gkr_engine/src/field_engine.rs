@@ -1,19 +1,39 @@
-mod babybear_x16;
-mod bn254;
-mod bn254_x_n;
 mod definition;
-mod gf2_ext128;
-mod goldilocks_x1;
-mod goldilocks_x8;
-mod m31_x1;
-mod m31_x16;
+pub use definition::*;
 
+#[cfg(feature = "babybear")]
+mod babybear_x16;
+#[cfg(feature = "babybear")]
 pub use babybear_x16::*;
+
+#[cfg(feature = "bn254")]
+mod bn254;
+#[cfg(feature = "bn254")]
+mod bn254_x_n;
+#[cfg(feature = "bn254")]
 pub use bn254::*;
+#[cfg(feature = "bn254")]
 pub use bn254_x_n::*;
-pub use definition::*;
+
+#[cfg(feature = "gf2")]
+mod gf2_ext128;
+#[cfg(feature = "gf2")]
 pub use gf2_ext128::*;
+
+#[cfg(feature = "goldilocks")]
+mod goldilocks_x1;
+#[cfg(feature = "goldilocks")]
+mod goldilocks_x8;
+#[cfg(feature = "goldilocks")]
 pub use goldilocks_x1::*;
+#[cfg(feature = "goldilocks")]
 pub use goldilocks_x8::*;
+
+#[cfg(feature = "m31")]
+mod m31_x1;
+#[cfg(feature = "m31")]
+mod m31_x16;
+#[cfg(feature = "m31")]
 pub use m31_x1::*;
+#[cfg(feature = "m31")]
 pub use m31_x16::*;
@@ -1,11 +1,10 @@
-use std::cmp;
 use std::fmt::Debug;
 use std::ops::{Add, Mul};
 
-use arith::{ExtensionField, Field, SimdField};
-use polynomials::MultiLinearPoly;
+use arith::{CanonicalField, ExtensionField, SimdField};
+use polynomials::EqPolynomial;
 
-use crate::{ExpanderSingleVarChallenge, MPIEngine};
+use crate::{ExpanderSingleVarChallenge, MPIEngine, MPIResult};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum FieldType {
@@ -27,7 +26,7 @@ pub trait FieldEngine: Default + Debug + Clone + Send + Sync + PartialEq + 'stat
     const SENTINEL: [u8; 32];
 
     /// Field type for the circuit, e.g., M31
-    type CircuitField: Field + Send + Sync;
+    type CircuitField: CanonicalField + Send + Sync;
 
     /// Field type for the challenge, e.g., M31Ext3
     type ChallengeField: ExtensionField<BaseField = Self::CircuitField>
@@ -64,32 +63,14 @@ pub trait FieldEngine: Default + Debug + Clone + Send + Sync + PartialEq + 'stat
     /// Take into consideration the simd challenge and the mpi challenge
     ///
     /// This is more efficient than the generic implementation by avoiding
-    /// unnecessary conversions between field types
+    /// unnecessary conversions between field types, and by weighting the evals with eq(x, .)
+    /// in a single pass rather than folding them variable by variable
     #[inline]
     fn eval_circuit_vals_at_challenge(
         evals: &[Self::SimdCircuitField],
         x: &[Self::ChallengeField],
-        scratch: &mut [Self::Field],
     ) -> Self::Field {
-        assert_eq!(1 << x.len(), evals.len());
-        assert!(scratch.len() >= evals.len());
-
-        if x.is_empty() {
-            evals[0].into()
-        } else {
-            for i in 0..(evals.len() >> 1) {
-                scratch[i] = x[0] * (evals[i * 2 + 1] - evals[i * 2]) + evals[i * 2];
-            }
-
-            let mut cur_eval_size = evals.len() >> 2;
-            for r in x.iter().skip(1) {
-                for i in 0..cur_eval_size {
-                    scratch[i] = scratch[i * 2] + (scratch[i * 2 + 1] - scratch[i * 2]).scale(r);
-                }
-                cur_eval_size >>= 1;
-            }
-            scratch[0]
-        }
+        EqPolynomial::eq_weighted_sum(x, evals)
     }
 
     /// This assumes each mpi core hold their own evals, and collectively
@@ -99,40 +80,22 @@ pub trait FieldEngine: Default + Debug + Clone + Send + Sync + PartialEq + 'stat
     fn collectively_eval_circuit_vals_at_expander_challenge(
         local_evals: &[Self::SimdCircuitField],
         challenge: &ExpanderSingleVarChallenge<Self>,
-
-        // x: &[Self::ChallengeField],
-        // x_simd: &[Self::ChallengeField],
-        // x_mpi: &[Self::ChallengeField],
-        scratch_field: &mut [Self::Field],
-        scratch_challenge_field: &mut [Self::ChallengeField],
         mpi_config: &impl MPIEngine,
-    ) -> Self::ChallengeField {
-        assert!(
-            scratch_challenge_field.len()
-                >= 1 << cmp::max(challenge.r_simd.len(), challenge.r_mpi.len())
-        );
-
-        let local_simd =
-            Self::eval_circuit_vals_at_challenge(local_evals, &challenge.rz, scratch_field);
-        let local_simd_unpacked = local_simd.unpack();
-        let local_v = MultiLinearPoly::evaluate_with_buffer(
-            &local_simd_unpacked,
-            &challenge.r_simd,
-            scratch_challenge_field,
-        );
+    ) -> MPIResult<Self::ChallengeField> {
+        let local_simd = Self::eval_circuit_vals_at_challenge(local_evals, &challenge.rz);
+        let local_v = EqPolynomial::eq_weighted_sum(&challenge.r_simd, &local_simd.unpack());
 
         if mpi_config.is_root() {
             let mut claimed_v_gathering_buffer =
-                vec![Self::ChallengeField::zero(); mpi_config.world_size()];
-            mpi_config.gather_vec(&[local_v], &mut claimed_v_gathering_buffer);
-            MultiLinearPoly::evaluate_with_buffer(
-                &claimed_v_gathering_buffer,
+                vec![Self::ChallengeField::zero(); mpi_config.n_parties()];
+            mpi_config.gather_padded_vec(&[local_v], &mut claimed_v_gathering_buffer)?;
+            Ok(EqPolynomial::eq_weighted_sum(
                 &challenge.r_mpi,
-                scratch_challenge_field,
-            )
+                &claimed_v_gathering_buffer,
+            ))
         } else {
-            mpi_config.gather_vec(&[local_v], &mut vec![]);
-            Self::ChallengeField::zero()
+            mpi_config.gather_padded_vec(&[local_v], &mut vec![])?;
+            Ok(Self::ChallengeField::zero())
         }
     }
 
@@ -146,30 +109,14 @@ pub trait FieldEngine: Default + Debug + Clone + Send + Sync + PartialEq + 'stat
         let local_poly_size = global_vals.len() >> challenge.r_mpi.len();
         assert_eq!(local_poly_size, 1 << challenge.rz.len());
 
-        let mut scratch_field = vec![Self::Field::default(); local_poly_size];
-        let mut scratch_challenge_field =
-            vec![
-                Self::ChallengeField::default();
-                1 << cmp::max(challenge.r_simd.len(), challenge.r_mpi.len())
-            ];
-        let local_evals = global_vals
-            .chunks(local_poly_size)
-            .map(|local_vals| {
-                let local_simd = Self::eval_circuit_vals_at_challenge(
-                    local_vals,
-                    &challenge.rz,
-                    &mut scratch_field,
-                );
-                let local_simd_unpacked = local_simd.unpack();
-                MultiLinearPoly::evaluate_with_buffer(
-                    &local_simd_unpacked,
-                    &challenge.r_simd,
-                    &mut scratch_challenge_field,
-                )
-            })
-            .collect::<Vec<Self::ChallengeField>>();
+        // fold the rz variables of all the processes at once
+        let mut local_simd = vec![Self::Field::default(); 1 << challenge.r_mpi.len()];
+        EqPolynomial::eq_weighted_fold(&challenge.rz, global_vals, &mut local_simd);
 
-        let mut scratch = vec![Self::ChallengeField::default(); local_evals.len()];
-        MultiLinearPoly::evaluate_with_buffer(&local_evals, &challenge.r_mpi, &mut scratch)
+        let local_evals = local_simd
+            .iter()
+            .map(|v| EqPolynomial::eq_weighted_sum(&challenge.r_simd, &v.unpack()))
+            .collect::<Vec<Self::ChallengeField>>();
+        EqPolynomial::eq_weighted_sum(&challenge.r_mpi, &local_evals)
     }
 }
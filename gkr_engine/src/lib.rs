@@ -59,6 +59,7 @@ pub use transcript::*;
 ///     type TranscriptConfig = BytesHashTranscript<M31Ext3, Sha2hasher>;
 ///     type PCSConfig = RawPCS<M31Ext3>;
 ///     const SCHEME: GKRScheme = GKRScheme::Vanilla;
+///     const FINGERPRINT: [u8; 32] = [0u8; 32];
 /// }
 /// ```
 pub trait GKREngine: Send + Sync {
@@ -76,4 +77,23 @@ pub trait GKREngine: Send + Sync {
 
     /// GKR scheme
     const SCHEME: GKRScheme;
+
+    /// Digest of the field, hasher, PCS, scheme and SIMD width of the config, generated by
+    /// `declare_gkr_config!`. It is the first thing absorbed into the transcript, so a proof
+    /// is rejected right away by a verifier with a different config.
+    const FINGERPRINT: [u8; 32];
+}
+
+/// The version of the protocol a proof was generated with, told apart by the envelope the proof
+/// starts with. The provers and the verifiers of a deployment can be upgraded one after the
+/// other, see `Prover::set_proof_version` and `Verifier::set_accept_previous_version`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProofVersion {
+    /// The proofs start with the `GKREngine::FINGERPRINT` of their config, which the transcript
+    /// absorbs first.
+    #[default]
+    Current,
+    /// The proofs predating the fingerprints start with the commitment, the transcript absorbing
+    /// nothing before it. The rest of the proof is as in the current version.
+    Previous,
 }
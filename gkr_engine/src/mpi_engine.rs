@@ -1,10 +1,28 @@
+mod auto;
+mod bytes;
+mod comm_stats;
 mod definition;
+#[cfg(feature = "cuda-mpi")]
+mod device;
 mod engine;
+mod node_topology;
 mod shared_mem;
+mod simulated;
+mod tcp;
+mod threaded;
 
+pub use auto::{Launcher, MPIEnvironment};
+pub(crate) use comm_stats::CommRecord;
+pub use comm_stats::{CollectiveStats, CommStats};
 pub use definition::*;
+#[cfg(feature = "cuda-mpi")]
+pub use device::{DeviceSlice, DeviceSliceMut};
 pub use engine::*;
+pub use node_topology::{NodePlacement, NodeTopology};
 pub use shared_mem::MPISharedMemory;
+pub use simulated::SimulatedMPIEngine;
+pub use tcp::TcpCommunicator;
+pub use threaded::ThreadedEngine;
 
 #[cfg(test)]
 mod tests;
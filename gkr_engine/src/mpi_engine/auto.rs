@@ -0,0 +1,145 @@
+//! The MPI engine of a binary, set up from the environment it is launched in.
+//!
+//! A binary started by `mpirun` or by SLURM's `srun` finds the size of its world in the
+//! environment variables of the launcher, and joins the MPI world with its node topology. A
+//! binary started directly runs as a single process without initializing MPI, so that it does
+//! not need an MPI runtime to be reachable.
+//!
+//! The processes of a node share its cores: unless `RAYON_NUM_THREADS` says otherwise, each
+//! process runs the cores SLURM gives a task, or its share of the cores of the node.
+
+use std::{num::NonZeroUsize, thread::available_parallelism};
+
+use mpi::{environment::Universe, topology::SimpleCommunicator};
+
+use super::{MPIConfig, MPIEngine, NodePlacement, NodeTopology};
+use crate::root_println;
+
+/// How the processes of a run were started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Launcher {
+    /// `mpirun` or `mpiexec`, of OpenMPI or of an MPICH derivative
+    MpiRun,
+    /// A SLURM job step, i.e., `srun`
+    Slurm,
+    /// Directly, as a single process
+    Standalone,
+}
+
+impl Launcher {
+    /// Read the launcher from the environment variables it sets in the processes it starts,
+    /// `env` returning the value of a variable
+    pub fn detect(env: impl Fn(&str) -> Option<String>) -> Self {
+        if env("OMPI_COMM_WORLD_SIZE").is_some() || env("PMI_SIZE").is_some() {
+            Launcher::MpiRun
+        } else if env("SLURM_STEP_ID").is_some() && env("SLURM_PROCID").is_some() {
+            Launcher::Slurm
+        } else {
+            Launcher::Standalone
+        }
+    }
+
+    /// The number of processes the launcher started on the node of this process, if it says
+    fn local_size(&self, env: impl Fn(&str) -> Option<String>) -> Option<usize> {
+        let var = match self {
+            Launcher::MpiRun => {
+                env("OMPI_COMM_WORLD_LOCAL_SIZE").or_else(|| env("MPI_LOCALNRANKS"))
+            }
+            Launcher::Slurm => env("SLURM_NTASKS_PER_NODE"),
+            Launcher::Standalone => Some("1".to_string()),
+        };
+        var.and_then(|v| v.parse().ok())
+    }
+
+    /// The number of threads of each process: `RAYON_NUM_THREADS` if set, otherwise the cores of
+    /// a SLURM task, otherwise the `n_cores` of the node split between its processes
+    pub fn threads_per_process(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+        n_cores: usize,
+    ) -> usize {
+        let parse = |var: &str| env(var).and_then(|v| v.parse::<usize>().ok());
+        parse("RAYON_NUM_THREADS")
+            .filter(|&n| n > 0)
+            .or_else(|| parse("SLURM_CPUS_PER_TASK"))
+            .unwrap_or_else(|| n_cores / self.local_size(&env).unwrap_or(1).max(1))
+            .max(1)
+    }
+}
+
+/// The MPI world of a binary with its node topology, set up by `MPIEnvironment::auto`
+pub struct MPIEnvironment {
+    launcher: Launcher,
+    n_threads: usize,
+    node_topology: Option<NodeTopology>,
+    node_placement: Option<NodePlacement>,
+    world: Option<SimpleCommunicator>,
+    /// Dropped last, finalizing MPI once the communicators are freed
+    universe: Option<Universe>,
+}
+
+impl MPIEnvironment {
+    /// Set up the engine from the environment of this process, and print the setup on the root.
+    ///
+    /// Under a launcher, MPI is initialized and the world split into nodes, collectively on all
+    /// processes of the world. The global thread pool is sized to the threads of a process,
+    /// unless it was built before.
+    ///
+    /// # Panics
+    /// If a launcher is detected but MPI was initialized before
+    pub fn auto() -> Self {
+        let env = |var: &str| std::env::var(var).ok();
+        let launcher = Launcher::detect(env);
+        let n_cores = available_parallelism().map_or(1, NonZeroUsize::get);
+        let n_threads = launcher.threads_per_process(env, n_cores);
+        // the pool may have been built by the binary already, which then keeps its size
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(n_threads)
+            .build_global();
+
+        let universe = match launcher {
+            Launcher::Standalone => None,
+            _ => Some(MPIConfig::init().expect("MPI was initialized before")),
+        };
+        let world = universe.as_ref().map(|universe| universe.world());
+        let node_topology = world.as_ref().and_then(NodeTopology::split);
+        let node_placement = world.as_ref().map(NodePlacement::query);
+
+        let mpi_env = Self {
+            launcher,
+            n_threads: rayon::current_num_threads(),
+            node_topology,
+            node_placement,
+            world,
+            universe,
+        };
+        let config = mpi_env.config();
+        root_println!(
+            config,
+            "MPI engine: launched by {:?}, {} processes on {} nodes, {} threads per process",
+            mpi_env.launcher,
+            config.world_size(),
+            config.n_nodes(),
+            mpi_env.n_threads
+        );
+        mpi_env
+    }
+
+    #[inline]
+    pub fn launcher(&self) -> Launcher {
+        self.launcher
+    }
+
+    #[inline]
+    pub fn n_threads(&self) -> usize {
+        self.n_threads
+    }
+
+    /// The engine of this process
+    #[inline]
+    pub fn config(&self) -> MPIConfig<'_> {
+        MPIConfig::prover_new(self.universe.as_ref(), self.world.as_ref())
+            .with_node_topology(self.node_topology.as_ref())
+            .with_node_placement(self.node_placement.as_ref())
+    }
+}
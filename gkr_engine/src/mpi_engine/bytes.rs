@@ -0,0 +1,27 @@
+//! The byte views of the buffers the communicators send.
+//!
+//! A buffer of `Pod` values is sent as its memory, which is sound as a `Pod` value owns no heap
+//! memory and is rebuilt by a byte copy: a `Vec` or an `Arc` sent this way would arrive as a
+//! dangling pointer, and is rejected by the bound instead. Values that are not `Pod` go through
+//! `ExpSerde`, see `MPIEngine::gather_serde_vec`.
+//!
+//! The values further have no padding bytes, which would be sent uninitialized: the layout of the
+//! type is checked at compile time by the byte views below, see `Pod::NO_PADDING`.
+
+use std::slice;
+
+use arith::Pod;
+
+/// The memory of `values` as bytes
+#[inline(always)]
+pub(crate) fn as_bytes<F: Pod>(values: &[F]) -> &[u8] {
+    let () = F::NO_PADDING;
+    unsafe { slice::from_raw_parts(values.as_ptr() as *const u8, size_of_val(values)) }
+}
+
+/// The memory of `values` as bytes, overwriting the bytes overwrites the values
+#[inline(always)]
+pub(crate) fn as_bytes_mut<F: Pod>(values: &mut [F]) -> &mut [u8] {
+    let () = F::NO_PADDING;
+    unsafe { slice::from_raw_parts_mut(values.as_mut_ptr() as *mut u8, size_of_val(values)) }
+}
@@ -0,0 +1,123 @@
+//! Call counts, payload sizes and wall time of the collectives of an `MPIConfig`.
+//!
+//! Every collective of the engine records itself when it returns, under its name. A collective
+//! built on another one, e.g., `gather_varlen_vec` gathering the lengths with `gather_vec`, is
+//! recorded once, as the outer collective. The collectives the trait provides on top of the
+//! others, e.g., `sum_vec`, are recorded as the collectives they call. The time of a
+//! non-blocking collective includes the work overlapped with it.
+
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The calls to a collective
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectiveStats {
+    pub n_calls: u64,
+    /// The bytes of the payloads of this process, sent or received
+    pub n_bytes: u64,
+    pub time: Duration,
+}
+
+impl CollectiveStats {
+    #[inline]
+    fn add(&mut self, other: &Self) {
+        self.n_calls += other.n_calls;
+        self.n_bytes += other.n_bytes;
+        self.time += other.time;
+    }
+}
+
+/// The calls to the collectives of an engine, by the name of the collective
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommStats {
+    pub collectives: BTreeMap<&'static str, CollectiveStats>,
+}
+
+impl CommStats {
+    /// The calls to all collectives
+    pub fn total(&self) -> CollectiveStats {
+        let mut total = CollectiveStats::default();
+        self.collectives.values().for_each(|stats| total.add(stats));
+        total
+    }
+}
+
+impl fmt::Display for CommStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        let rows = self
+            .collectives
+            .iter()
+            .map(|(name, stats)| (*name, stats))
+            .chain([("total", &total)]);
+        for (name, stats) in rows {
+            writeln!(
+                f,
+                "{name:<28} {:>8} calls {:>14} bytes {:>12.3?}",
+                stats.n_calls, stats.n_bytes, stats.time
+            )?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// The number of recorded collectives running on this thread, the outer one included
+    static N_RUNNING: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Records a call to a collective when dropped, unless it is called by another collective
+pub(crate) struct CommRecord<'a> {
+    stats: &'a Arc<Mutex<CommStats>>,
+    collective: &'static str,
+    n_bytes: usize,
+    start: Instant,
+    is_outer: bool,
+}
+
+impl<'a> CommRecord<'a> {
+    #[inline]
+    pub(crate) fn start(
+        stats: &'a Arc<Mutex<CommStats>>,
+        collective: &'static str,
+        n_bytes: usize,
+    ) -> Self {
+        let is_outer = N_RUNNING.with(|n| n.replace(n.get() + 1)) == 0;
+        Self {
+            stats,
+            collective,
+            n_bytes,
+            start: Instant::now(),
+            is_outer,
+        }
+    }
+}
+
+impl Drop for CommRecord<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        N_RUNNING.with(|n| n.set(n.get() - 1));
+        if !self.is_outer {
+            return;
+        }
+
+        let call = CollectiveStats {
+            n_calls: 1,
+            n_bytes: self.n_bytes as u64,
+            time: self.start.elapsed(),
+        };
+        // a collective panicking elsewhere is not recorded
+        if let Ok(mut stats) = self.stats.lock() {
+            stats
+                .collectives
+                .entry(self.collective)
+                .or_default()
+                .add(&call);
+        }
+    }
+}
@@ -1,14 +1,27 @@
-use arith::Field;
-use mpi::{ffi::ompi_win_t, topology::Process};
+use std::os::raw::c_int;
+
+use arith::{Field, Pod};
+use mpi::ffi::ompi_win_t;
 use serdes::ExpSerde;
 
-use super::MPISharedMemory;
+use super::{
+    bytes::{as_bytes, as_bytes_mut},
+    MPISharedMemory,
+};
+use crate::{ExpErrors, MPIError, MPIResult};
 
 /// MPI APIs for distributed computing operations
+///
+/// The interactions with the other processes return an `MPIError` naming the rank and the
+/// method that failed, rather than panicking or assuming success.
 pub trait MPIEngine {
-    /// The rank of the root process (always 0)
+    /// The rank of the root process, unless the engine is set up with another root
     const ROOT_RANK: i32 = 0;
 
+    /// The largest number of bytes sent by a single broadcast of `root_broadcast_varlen_vec`,
+    /// below the 2^31 element limit of MPI
+    const BROADCAST_CHUNK_SIZE: usize = 1 << 30;
+
     /// Gather vectors from all processes into the root process
     ///
     /// # Arguments
@@ -18,7 +31,80 @@ pub trait MPIEngine {
     /// # Behavior
     /// - Root process receives all vectors
     /// - Non-root processes send their vectors but don't modify global_vec
-    fn gather_vec<F: Sized + Clone>(&self, local_vec: &[F], global_vec: &mut Vec<F>);
+    ///
+    /// # NOTE
+    /// The elements are sent as their memory, hence the `Pod` bound: an element owning heap
+    /// memory would arrive as a dangling pointer, and the padding bytes of an element as garbage.
+    /// Such elements go through `gather_serde_vec`.
+    fn gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()>;
+
+    /// Gather vectors of elements that are not `Pod` from all processes into the root process
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local vector to be gathered from this process
+    /// * `global_vec` - Overwritten in root process by all gathered vectors, in rank order
+    ///
+    /// # Behavior
+    /// - Same as `gather_vec`, except that `global_vec` need not be allocated beforehand
+    ///
+    /// # Implementation
+    /// Each process serializes its local vector, the bytes are gathered with
+    /// `gather_varlen_vec`, and the root process deserializes the vectors of all processes.
+    fn gather_serde_vec<F: ExpSerde>(
+        &self,
+        local_vec: &[F],
+        global_vec: &mut Vec<F>,
+    ) -> MPIResult<()> {
+        // serialize the same way as a Vec<F>, so that it is deserialized as one
+        let mut local_bytes = vec![];
+        local_vec.len().serialize_into(&mut local_bytes).unwrap();
+        local_vec
+            .iter()
+            .for_each(|elem| elem.serialize_into(&mut local_bytes).unwrap());
+
+        let mut global_bytes = vec![];
+        if self.is_single_process() {
+            global_bytes.push(local_bytes);
+        } else {
+            self.gather_varlen_vec(&local_bytes, &mut global_bytes)?;
+        }
+
+        if self.is_root() {
+            global_vec.clear();
+            for bytes in &global_bytes {
+                let elems = Vec::<F>::deserialize_from(bytes.as_slice())
+                    .map_err(|e| self.mpi_error("gather_serde_vec", e))?;
+                global_vec.extend(elems);
+            }
+        }
+        Ok(())
+    }
+
+    /// Gather vectors from all processes into all processes
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local vector to be gathered from this process
+    /// * `global_vec` - Buffer in every process to store all gathered vectors, of length
+    ///   `local_vec.len() * world_size`
+    ///
+    /// # Behavior
+    /// - Every process receives all vectors, in rank order
+    ///
+    /// # Implementation
+    /// Defaults to `gather_vec` followed by a broadcast of the gathered vectors from the root.
+    fn all_gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+        self.check_global_len("all_gather_vec", local_vec.len(), global_vec.len())?;
+
+        self.gather_vec(local_vec, global_vec)?;
+        let mut global_bytes = as_bytes(global_vec).to_vec();
+        self.root_broadcast_bytes(&mut global_bytes)?;
+        as_bytes_mut(global_vec).copy_from_slice(&global_bytes);
+        Ok(())
+    }
 
     /// Scatter vector from root process into all processes
     ///
@@ -29,7 +115,7 @@ pub trait MPIEngine {
     /// # Behavior
     /// - Root process sends vector segments into all vectors
     /// - Non-root processes receive their segment share but not modifying send_vec
-    fn scatter_vec<F: Sized + Clone>(&self, send_vec: &[F], receive_vec: &mut [F]);
+    fn scatter_vec<F: Pod>(&self, send_vec: &[F], receive_vec: &mut [F]) -> MPIResult<()>;
 
     /// Broadcast a field element from root process to all processes
     ///
@@ -39,7 +125,7 @@ pub trait MPIEngine {
     /// # Behavior
     /// - Root process broadcasts its value
     /// - All other processes receive the value
-    fn root_broadcast_f<F: Copy>(&self, f: &mut F);
+    fn root_broadcast_f<F: Pod>(&self, f: &mut F) -> MPIResult<()>;
 
     /// Broadcast a vector of bytes from root process to all processes
     ///
@@ -49,7 +135,229 @@ pub trait MPIEngine {
     /// # Behavior
     /// - Root process broadcasts its bytes
     /// - All other processes receive the bytes
-    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>);
+    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) -> MPIResult<()>;
+
+    /// Broadcast a vector of any length from root process to all processes
+    ///
+    /// # Arguments
+    /// * `vec` - The vector to broadcast, overwritten in non-root processes
+    ///
+    /// # Behavior
+    /// - Root process broadcasts its vector
+    /// - All other processes receive the vector, whatever the length of theirs was
+    ///
+    /// # Implementation
+    /// The root process serializes the vector and broadcasts the number of bytes, then the
+    /// bytes in chunks of at most `BROADCAST_CHUNK_SIZE` bytes.
+    fn root_broadcast_varlen_vec<F: ExpSerde>(&self, vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        let mut bytes = vec![];
+        if self.is_root() {
+            vec.serialize_into(&mut bytes).unwrap();
+        }
+        let mut n_bytes = bytes.len();
+        self.root_broadcast_f(&mut n_bytes)?;
+        bytes.resize(n_bytes, 0);
+
+        let mut chunk = vec![];
+        for start in (0..n_bytes).step_by(Self::BROADCAST_CHUNK_SIZE) {
+            let end = n_bytes.min(start + Self::BROADCAST_CHUNK_SIZE);
+            chunk.clear();
+            chunk.extend_from_slice(&bytes[start..end]);
+            self.root_broadcast_bytes(&mut chunk)?;
+            bytes[start..end].copy_from_slice(&chunk);
+        }
+
+        if !self.is_root() {
+            *vec = Vec::deserialize_from(bytes.as_slice())
+                .map_err(|e| self.mpi_error("root_broadcast_varlen_vec", e))?;
+        }
+        Ok(())
+    }
+
+    /// Gather vectors from all the parties of the hypercube into the root process, the virtual
+    /// parties sending the vectors of the processes they mirror, see `mirror_rank`
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local vector to be gathered from this process
+    /// * `global_vec` - Buffer in root process to store all gathered vectors, of length
+    ///   `local_vec.len() * n_parties`
+    ///
+    /// # Behavior
+    /// - Same as `gather_vec` when the world size is a power of two
+    /// - Only valid for vectors computed from the witness of the process and the public challenges
+    ///   alone, which a virtual party would compute the same
+    fn gather_padded_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.n_parties() == self.world_size() {
+            return self.gather_vec(local_vec, global_vec);
+        }
+        if !self.is_root() {
+            return self.gather_vec(local_vec, global_vec);
+        }
+
+        let local_len = local_vec.len();
+        global_vec.truncate(local_len * self.world_size());
+        self.gather_vec(local_vec, global_vec)?;
+        for party in self.world_size()..self.n_parties() {
+            let start = self.mirror_rank(party) * local_len;
+            global_vec.extend_from_within(start..start + local_len);
+        }
+        Ok(())
+    }
+
+    /// Non-blocking counterpart of `gather_vec`, overlapping the gather with local computation
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local vector to be gathered from this process
+    /// * `global_vec` - Buffer in root process to store all gathered vectors
+    /// * `overlap` - Computation run while the gather is in flight, its output is returned
+    ///
+    /// # Behavior
+    /// - Same as `gather_vec`, `global_vec` is filled when the call returns
+    /// - `overlap` must not touch the buffers of the gather, which the borrow checker enforces
+    /// - `overlap` is run even if the gather fails, whose error is returned instead of its output
+    ///
+    /// # Implementation
+    /// The request of a non-blocking operation borrows its buffers, so instead of handing the
+    /// request out, the operation is started, `overlap` is run, and the request is waited for.
+    /// Defaults to the blocking operation followed by `overlap`.
+    fn igather_vec<F: Pod, R>(
+        &self,
+        local_vec: &[F],
+        global_vec: &mut Vec<F>,
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let gathered = self.gather_vec(local_vec, global_vec);
+        let output = overlap();
+        gathered.map(|_| output)
+    }
+
+    /// Non-blocking counterpart of `root_broadcast_f`, overlapping the broadcast with local
+    /// computation the same way as `igather_vec`
+    fn ibroadcast_f<F: Pod, R>(&self, f: &mut F, overlap: impl FnOnce() -> R) -> MPIResult<R> {
+        let broadcast = self.root_broadcast_f(f);
+        let output = overlap();
+        broadcast.map(|_| output)
+    }
+
+    /// Non-blocking counterpart of `scatter_vec`, overlapping the scatter with local computation
+    /// the same way as `igather_vec`
+    fn iscatter_vec<F: Pod, R>(
+        &self,
+        send_vec: &[F],
+        receive_vec: &mut [F],
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let scattered = self.scatter_vec(send_vec, receive_vec);
+        let output = overlap();
+        scattered.map(|_| output)
+    }
+
+    /// Send a vector to the process of rank `rank`, which receives it with `recv_from`
+    ///
+    /// # Arguments
+    /// * `rank` - The rank of the process to send to, other than this one
+    /// * `vec` - The vector to be sent
+    ///
+    /// # Behavior
+    /// - The vectors sent from a process to another arrive in the order they were sent
+    /// - May block until the other process receives the vector, so the processes must not wait on
+    ///   each other in a cycle, e.g., by sending to each other first
+    ///
+    /// # NOTE
+    /// A vector sent has to be received before the two processes enter a collective operation, as
+    /// some engines carry the collectives over the same connections
+    fn send_to<F: Pod>(&self, rank: usize, vec: &[F]) -> MPIResult<()>;
+
+    /// Receive a vector sent by the process of rank `rank` with `send_to`
+    ///
+    /// # Arguments
+    /// * `rank` - The rank of the process to receive from, other than this one
+    /// * `vec` - Buffer of the length of the vector sent, overwritten with it
+    ///
+    /// # Behavior
+    /// Fails if the vector sent does not have the length of `vec`
+    fn recv_from<F: Pod>(&self, rank: usize, vec: &mut [F]) -> MPIResult<()>;
+
+    /// Non-blocking counterpart of `send_to`, overlapping the send with local computation the
+    /// same way as `igather_vec`
+    fn isend_to<F: Pod, R>(
+        &self,
+        rank: usize,
+        vec: &[F],
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let sent = self.send_to(rank, vec);
+        let output = overlap();
+        sent.map(|_| output)
+    }
+
+    /// Non-blocking counterpart of `recv_from`, overlapping the receive with local computation
+    /// the same way as `igather_vec`
+    fn irecv_from<F: Pod, R>(
+        &self,
+        rank: usize,
+        vec: &mut [F],
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let received = self.recv_from(rank, vec);
+        let output = overlap();
+        received.map(|_| output)
+    }
+
+    /// Scatter a vector from the root process chunk by chunk, as the root produces it
+    ///
+    /// # Arguments
+    /// * `n_chunks` - The number of chunks
+    /// * `chunk_len` - The number of elements of a chunk sent to each process
+    /// * `produce` - Called on the root process only, returns chunk `i`, the `chunk_len` elements
+    ///   of each process one process after the other
+    /// * `consume` - Called on all processes with `i` and the share of chunk `i` of the process
+    ///
+    /// # Behavior
+    /// - Chunks are produced and consumed in order
+    /// - Chunk `i + 1` is produced and chunk `i - 1` consumed while chunk `i` is scattered, so that
+    ///   the root reading its input, the network and the processes unpacking their shares are busy
+    ///   at once
+    ///
+    /// # Implementation
+    /// A pipeline of `iscatter_vec`, one chunk ahead on the root and one behind on all processes.
+    fn scatter_vec_streaming<F: Pod + Default>(
+        &self,
+        n_chunks: usize,
+        chunk_len: usize,
+        mut produce: impl FnMut(usize) -> Vec<F>,
+        mut consume: impl FnMut(usize, &[F]),
+    ) -> MPIResult<()> {
+        let mut produce_chunk = |i: usize| {
+            if !self.is_root() || i >= n_chunks {
+                return vec![];
+            }
+            let chunk = produce(i);
+            assert_eq!(chunk.len(), chunk_len * self.world_size());
+            chunk
+        };
+
+        let mut send = produce_chunk(0);
+        let mut received: Option<(usize, Vec<F>)> = None;
+        for i in 0..n_chunks {
+            let mut receive = vec![F::default(); chunk_len];
+            send = self.iscatter_vec(&send, &mut receive, || {
+                if let Some((j, share)) = received.take() {
+                    consume(j, &share);
+                }
+                produce_chunk(i + 1)
+            })?;
+            received = Some((i, receive));
+        }
+        if let Some((j, share)) = received {
+            consume(j, &share);
+        }
+        Ok(())
+    }
 
     /// Sum up field elements across all processes
     ///
@@ -58,23 +366,105 @@ pub trait MPIEngine {
     ///
     /// # Returns
     /// A vector containing the sum of corresponding elements from all processes
-    fn sum_vec<F: Field>(&self, local_vec: &[F]) -> Vec<F>;
+    fn sum_vec<F: Field>(&self, local_vec: &[F]) -> MPIResult<Vec<F>> {
+        if self.is_single_process() {
+            Ok(local_vec.to_vec())
+        } else if self.is_root() {
+            let mut global_vec = vec![F::ZERO; local_vec.len() * self.world_size()];
+            self.gather_vec(local_vec, &mut global_vec)?;
+            for i in 0..local_vec.len() {
+                for j in 1..self.world_size() {
+                    global_vec[i] = global_vec[i] + global_vec[j * local_vec.len() + i];
+                }
+            }
+            global_vec.truncate(local_vec.len());
+            Ok(global_vec)
+        } else {
+            self.gather_vec(local_vec, &mut vec![])?;
+            Ok(vec![])
+        }
+    }
+
+    /// Sum up field elements across all processes, with the sum available on every process
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local vector of field elements to sum, of the same length everywhere
+    ///
+    /// # Returns
+    /// A vector containing the sum of corresponding elements from all processes, on every
+    /// process, unlike `sum_vec` which only returns it on the root
+    ///
+    /// # Implementation
+    /// Defaults to `sum_vec` followed by a broadcast of the sum from the root.
+    fn allreduce_vec<F: Field>(&self, local_vec: &[F]) -> MPIResult<Vec<F>> {
+        if self.is_single_process() {
+            return Ok(local_vec.to_vec());
+        }
 
-    /// Combines vectors from all MPI processes using weighted coefficients
+        let mut sum = self.sum_vec(local_vec)?;
+        self.root_broadcast_varlen_vec(&mut sum)?;
+        Ok(sum)
+    }
+
+    /// Sum up field elements across all processes, with each process receiving its own segment
+    /// of the sum
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local vector of field elements to sum, of the same length everywhere,
+    ///   divisible by world_size
+    ///
+    /// # Returns
+    /// The segment of rank i, i.e., elements i * n..(i + 1) * n of the sum where
+    /// n = local_vec.len() / world_size
+    ///
+    /// # Implementation
+    /// Defaults to `sum_vec` followed by `scatter_vec` of the sum from the root.
+    fn reduce_scatter_vec<F: Field>(&self, local_vec: &[F]) -> MPIResult<Vec<F>> {
+        self.check_split_len("reduce_scatter_vec", local_vec.len())?;
+        if self.is_single_process() {
+            return Ok(local_vec.to_vec());
+        }
+
+        let global_vec = self.sum_vec(local_vec)?;
+        let mut segment = vec![F::ZERO; local_vec.len() / self.world_size()];
+        self.scatter_vec(&global_vec, &mut segment)?;
+        Ok(segment)
+    }
+
+    /// Combines vectors from all the parties of the hypercube using weighted coefficients
     ///
     /// # Arguments
     /// * `local_vec` - The local vector from the current process
-    /// * `coef` - Array of coefficients, with length equal to world_size
+    /// * `coef` - Array of coefficients, with length equal to n_parties
     ///
     /// # Returns
     /// * For single process: Returns local_vec.clone()
-    /// * For root process: Returns weighted combination Σ(coef[j] * process_j_vector[i])
+    /// * For root process: Returns weighted combination Σ(coef[j] * party_j_vector[i])
     /// * For other processes: Returns zero vector of same length
     ///
     /// # Implementation
-    /// Root process gathers all vectors and computes the weighted sum.
+    /// Root process gathers all vectors with `gather_padded_vec` and computes the weighted sum.
     /// Non-root processes participate in gathering but return zero vectors.
-    fn coef_combine_vec<F: Field>(&self, local_vec: &[F], coef: &[F]) -> Vec<F>;
+    fn coef_combine_vec<F: Field>(&self, local_vec: &[F], coef: &[F]) -> MPIResult<Vec<F>> {
+        if self.is_single_process() {
+            // Warning: literally, it should be coef[0] * local_vec
+            // but coef[0] is always one in our use case of self.world_size = 1
+            Ok(local_vec.to_vec())
+        } else if self.is_root() {
+            let mut global_vec = vec![F::ZERO; local_vec.len() * self.n_parties()];
+            let mut ret = vec![F::ZERO; local_vec.len()];
+            self.gather_padded_vec(local_vec, &mut global_vec)?;
+            for i in 0..local_vec.len() {
+                for j in 0..self.n_parties() {
+                    ret[i] += global_vec[j * local_vec.len() + i] * coef[j];
+                }
+            }
+            Ok(ret)
+        } else {
+            self.gather_vec(local_vec, &mut vec![])?;
+            Ok(vec![F::ZERO; local_vec.len()])
+        }
+    }
 
     /// Perform matrix transpose with other MPI processes through MPI all-to-all transpose
     ///
@@ -85,7 +475,7 @@ pub trait MPIEngine {
     /// - Each process exchanges chunks of data with every other process
     /// - Resulting data layout on each process swaps one dimension of distribution with another
     ///   (e.g., rows to columns in a distributed matrix)
-    fn all_to_all_transpose<F: Sized>(&self, row: &mut [F]);
+    fn all_to_all_transpose<F: Pod>(&self, row: &mut [F]) -> MPIResult<()>;
 
     /// Gather *variable length* vectors from all processes into the root process
     ///
@@ -112,7 +502,53 @@ pub trait MPIEngine {
     /// flattening all the Merkle paths into a final flattened vector of opened Merkle paths with
     /// agreeing order from the indices sampled from the Fiat-Shamir RO.
     #[allow(clippy::ptr_arg)]
-    fn gather_varlen_vec<F: ExpSerde>(&self, local_vec: &Vec<F>, global_vec: &mut Vec<Vec<F>>);
+    fn gather_varlen_vec<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        global_vec: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()>;
+
+    /// Gather *variable length* vectors from all processes, handing them to `consume` on the root
+    /// process one at a time
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local variable length vector to be gathered from this process
+    /// * `consume` - Called by the root process with each process's rank and vector
+    ///
+    /// # Behavior
+    /// - Root process calls `consume` once per process, in rank order
+    /// - Non-root processes send their vectors and never call `consume`
+    ///
+    /// # Implementation
+    /// Unlike `gather_varlen_vec`, the root process receives the serialized bytes of one process
+    /// at a time, point to point, into a buffer reused across processes.  The memory of the root
+    /// process is then bounded by the largest payload instead of the sum of all payloads, at the
+    /// cost of non-root processes waiting for their turn.
+    #[allow(clippy::ptr_arg)]
+    fn gather_varlen_vec_streaming<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        consume: impl FnMut(usize, Vec<F>),
+    ) -> MPIResult<()>;
+
+    /// Gather *variable length* vectors from all processes into all processes
+    ///
+    /// # Arguments
+    /// * `local_vec` - The local variable length vector to be gathered from this process
+    /// * `global_vec` - Overwritten in every process by the vectors of all processes, in rank order
+    ///
+    /// # Implementation
+    /// Defaults to `gather_varlen_vec` followed by `root_broadcast_varlen_vec` of the gathered
+    /// vectors.
+    #[allow(clippy::ptr_arg)]
+    fn all_gather_varlen_vec<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        global_vec: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()> {
+        self.gather_varlen_vec(local_vec, global_vec)?;
+        self.root_broadcast_varlen_vec(global_vec)
+    }
 
     /// Check if there is only one process in the MPI world
     fn is_single_process(&self) -> bool;
@@ -123,49 +559,262 @@ pub trait MPIEngine {
     /// Get the rank of the current process
     fn world_rank(&self) -> usize;
 
+    /// The number of parties of the hypercube the MPI variables of the challenges range over,
+    /// i.e., the world size rounded up to a power of two. The parties past the processes are
+    /// virtual, each proving the witness of the process it mirrors, see `mirror_rank`, so that a
+    /// world of any size is proven without idling processes.
+    #[inline(always)]
+    fn n_parties(&self) -> usize {
+        self.world_size().next_power_of_two()
+    }
+
+    /// The rank of the process proving the witness of `party`: the process of that rank, or for
+    /// a virtual party, the process with the highest MPI variable of the party cleared
+    #[inline(always)]
+    fn mirror_rank(&self, party: usize) -> usize {
+        assert!(party < self.n_parties());
+        if party < self.world_size() {
+            party
+        } else {
+            party - self.n_parties() / 2
+        }
+    }
+
+    /// The virtual party whose witness is that of this process, if any
+    #[inline(always)]
+    fn virtual_party(&self) -> Option<usize> {
+        let party = self.world_rank() + self.n_parties() / 2;
+        (party >= self.world_size() && party < self.n_parties()).then_some(party)
+    }
+
+    /// The rank of the root process, which the gathers collect into and the broadcasts and
+    /// scatters send from. Defaults to `ROOT_RANK`.
+    #[inline(always)]
+    fn root_rank(&self) -> usize {
+        Self::ROOT_RANK as usize
+    }
+
     /// Check if the current process is the root process
     #[inline(always)]
     fn is_root(&self) -> bool {
-        self.world_rank() == Self::ROOT_RANK as usize
+        self.world_rank() == self.root_rank()
     }
 
-    /// Get the root process handle
-    fn root_process(&self) -> Process;
+    /// The number of shared memory nodes the processes are placed on. Without a known placement,
+    /// every process is taken to be on a node of its own.
+    #[inline]
+    fn n_nodes(&self) -> usize {
+        self.world_size()
+    }
+
+    /// The node of the process of `rank`, the nodes being numbered in the order of their lowest
+    /// rank
+    #[inline]
+    fn node_of_rank(&self, rank: usize) -> usize {
+        rank
+    }
+
+    /// The ranks of the processes on the node of this process, in increasing order, which are
+    /// reached through shared memory rather than the network
+    #[inline]
+    fn ranks_on_my_node(&self) -> Vec<usize> {
+        (0..self.world_size())
+            .filter(|&rank| self.is_intra_node(rank))
+            .collect()
+    }
+
+    /// Whether the process of `rank` is on the node of this process
+    #[inline]
+    fn is_intra_node(&self, rank: usize) -> bool {
+        self.node_of_rank(rank) == self.node_of_rank(self.world_rank())
+    }
 
     /// Synchronize all processes at this point
-    fn barrier(&self);
+    fn barrier(&self) -> MPIResult<()>;
+
+    /// The error of this process failing in the method `collective` of the engine
+    #[inline]
+    fn mpi_error(&self, collective: &'static str, reason: impl ToString) -> MPIError {
+        MPIError::new(self.world_rank(), collective, reason)
+    }
+
+    /// Check that a buffer of `global_len` elements holds the buffers of `local_len` elements of
+    /// all processes, in rank order. The product is checked rather than computed, as the sizes of
+    /// the buffers come from the callers and may overflow, e.g., on 32-bit targets.
+    #[inline]
+    fn check_global_len(
+        &self,
+        collective: &'static str,
+        local_len: usize,
+        global_len: usize,
+    ) -> MPIResult<()> {
+        match local_len.checked_mul(self.world_size()) {
+            Some(len) if len == global_len => Ok(()),
+            Some(len) => Err(self.mpi_error(
+                collective,
+                format!("expected a buffer of {len} elements, got {global_len}"),
+            )),
+            None => Err(self.mpi_error(
+                collective,
+                format!(
+                    "{local_len} elements from each of {} processes overflow",
+                    self.world_size()
+                ),
+            )),
+        }
+    }
+
+    /// Check that a buffer of `len` elements splits into equal segments, one per process
+    #[inline]
+    fn check_split_len(&self, collective: &'static str, len: usize) -> MPIResult<()> {
+        if len % self.world_size() != 0 {
+            return Err(self.mpi_error(
+                collective,
+                format!(
+                    "{len} elements do not split between {} processes",
+                    self.world_size()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that `rank` is a process of the world other than this one, to send to or receive
+    /// from
+    #[inline]
+    fn check_peer(&self, collective: &'static str, rank: usize) -> MPIResult<()> {
+        if rank >= self.world_size() || rank == self.world_rank() {
+            return Err(self.mpi_error(
+                collective,
+                format!(
+                    "rank {rank} is not another process of a world of {} processes",
+                    self.world_size()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The number of MPI variables of the challenges, i.e., `log2(n_parties)`
+    #[inline]
+    fn n_mpi_vars(&self) -> MPIResult<usize> {
+        Ok(self.n_parties().trailing_zeros() as usize)
+    }
+
+    /// Agree on the outcome of a fallible step across all processes.
+    ///
+    /// Every process must call this function, with either the successful result of its local
+    /// step or the error it ran into. If any process failed, all processes return an error:
+    /// the failing process keeps its own error, while the others receive
+    /// `ExpErrors::MPIAbort` naming the lowest failing rank, instead of hanging in the next
+    /// collective operation.
+    fn sync_result<T>(&self, local: Result<T, ExpErrors>) -> Result<T, ExpErrors> {
+        if self.is_single_process() {
+            return local;
+        }
+
+        // an empty message means the step succeeded locally
+        let local_msg = match &local {
+            Ok(_) => vec![],
+            Err(e) => {
+                let msg = e.to_string().into_bytes();
+                if msg.is_empty() {
+                    b"unknown error".to_vec()
+                } else {
+                    msg
+                }
+            }
+        };
+        let mut all_msgs = vec![];
+        self.gather_varlen_vec(&local_msg, &mut all_msgs)?;
+
+        // root decides on the first failure, if any, and broadcasts it as (rank, reason)
+        let (mut failed_rank, mut reason) = if self.is_root() {
+            match all_msgs.iter().position(|msg| !msg.is_empty()) {
+                Some(rank) => (rank, all_msgs[rank].clone()),
+                None => (usize::MAX, vec![]),
+            }
+        } else {
+            (usize::MAX, vec![])
+        };
+        self.root_broadcast_f(&mut failed_rank)?;
+        if failed_rank == usize::MAX {
+            return local;
+        }
+
+        self.root_broadcast_varlen_vec(&mut reason)?;
+
+        match local {
+            Err(e) => Err(e),
+            Ok(_) => Err(ExpErrors::MPIAbort {
+                rank: failed_rank,
+                reason: String::from_utf8_lossy(&reason).into_owned(),
+            }),
+        }
+    }
+
+    /// Tear down all processes in the MPI world with the given error code.
+    /// Use this for unrecoverable failures, where not every process can reach `sync_result`.
+    fn abort(&self, error_code: i32) -> !;
 
     /// Create a shared memory segment for inter-process communication
-    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, *mut ompi_win_t);
+    fn create_shared_mem(&self, n_bytes: usize) -> MPIResult<(*mut u8, *mut ompi_win_t)>;
 
     /// Consume the shared memory segment and create a new shared memory object
     fn consume_obj_and_create_shared<T: MPISharedMemory>(
         &self,
         obj: Option<T>,
-    ) -> (T, *mut ompi_win_t) {
+    ) -> MPIResult<(T, *mut ompi_win_t)> {
         assert!(!self.is_root() || obj.is_some());
 
         if self.is_root() {
             let obj = obj.unwrap();
             let n_bytes = obj.bytes_size();
-            let (mut ptr, window) = self.create_shared_mem(n_bytes);
+            let (mut ptr, window) = self.create_shared_mem(n_bytes)?;
             let mut ptr_copy = ptr;
             obj.to_memory(&mut ptr_copy);
-            self.barrier();
-            (T::new_from_memory(&mut ptr), window)
+            self.barrier()?;
+            Ok((T::new_from_memory(&mut ptr), window))
         } else {
-            let (mut ptr, window) = self.create_shared_mem(0);
-            self.barrier(); // wait for root to write data
-            (T::new_from_memory(&mut ptr), window)
+            let (mut ptr, window) = self.create_shared_mem(0)?;
+            self.barrier()?; // wait for root to write data
+            Ok((T::new_from_memory(&mut ptr), window))
         }
     }
 
     /// Discard the control of shared memory segment
-    fn free_shared_mem(&self, window: &mut *mut ompi_win_t) {
-        unsafe {
+    fn free_shared_mem(&self, window: &mut *mut ompi_win_t) -> MPIResult<()> {
+        let code = unsafe {
             // Reconstruct an MPI_Win handle from the raw pointer and let MPI release it.
             let mut win_handle = mpi::ffi::MPI_Win(*window);
-            mpi::ffi::MPI_Win_free(&mut win_handle as *mut mpi::ffi::MPI_Win);
-        }
+            mpi::ffi::MPI_Win_free(&mut win_handle as *mut mpi::ffi::MPI_Win)
+        };
+        check_mpi_code(self, "free_shared_mem", "MPI_Win_free", code)
+    }
+}
+
+/// The engine the processes of a config declared by `declare_gkr_config!` communicate through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MPIEngineType {
+    /// Processes launched by an MPI runtime, through `MPIConfig`
+    #[default]
+    MPI,
+    /// Threads of a single process, through `ThreadedEngine`
+    Threaded,
+}
+
+/// Turn the return code of the MPI function `function`, called by the method `collective` of
+/// `engine`, into an error if it is not `MPI_SUCCESS`. The codes are only returned to the caller
+/// if the error handler of the communicator returns them, the default one aborting the job.
+pub(crate) fn check_mpi_code(
+    engine: &(impl MPIEngine + ?Sized),
+    collective: &'static str,
+    function: &str,
+    code: c_int,
+) -> MPIResult<()> {
+    if code == mpi::ffi::MPI_SUCCESS as c_int {
+        Ok(())
+    } else {
+        Err(engine.mpi_error(collective, format!("{function} returned error code {code}")))
     }
 }
@@ -0,0 +1,195 @@
+//! Collectives over buffers in the memory of a GPU, for the CUDA provers.
+//!
+//! A CUDA-aware MPI library, e.g., OpenMPI built with CUDA support, moves buffers of device
+//! memory itself, over GPUDirect where the fabric allows, so that a prover whose field vectors
+//! live on the GPU hands them to the collectives as they are instead of copying them to the host
+//! and back. The buffers are passed as raw device pointers, MPI recognizing device memory through
+//! the unified virtual address space, and host pointers work the same.
+//!
+//! The device collectives send single messages of field elements rather than chunks of bytes,
+//! splitting a payload in device memory would take a copy on the device, and bypass the node
+//! topology of the engine. Reductions are not offered: the reduction of `allreduce_vec` runs on
+//! the host and cannot read device memory.
+
+use std::{ffi::c_void, marker::PhantomData, os::raw::c_int};
+
+use arith::Pod;
+use mpi::{
+    datatype::{MutView, Pointer, PointerMut, UserDatatype, View},
+    traits::*,
+    Count,
+};
+
+use super::MPIConfig;
+
+extern "C" {
+    /// OpenMPI extension, 1 if the library is built with CUDA support
+    fn MPIX_Query_cuda_support() -> c_int;
+}
+
+/// `len` values of type `F` in device memory, borrowed for `'a`
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceSlice<'a, F> {
+    ptr: *const F,
+    len: usize,
+    _marker: PhantomData<&'a [F]>,
+}
+
+impl<F> DeviceSlice<'_, F> {
+    /// # Safety
+    /// `ptr` points to `len` values of type `F` in device or host memory, valid and not written
+    /// to for the lifetime of the slice
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *const F, len: usize) -> Self {
+        Self {
+            ptr,
+            len,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+unsafe impl<F> Pointer for DeviceSlice<'_, F> {
+    fn pointer(&self) -> *const c_void {
+        self.ptr as *const c_void
+    }
+}
+
+/// Mutable counterpart of `DeviceSlice`
+#[derive(Debug)]
+pub struct DeviceSliceMut<'a, F> {
+    ptr: *mut F,
+    len: usize,
+    _marker: PhantomData<&'a mut [F]>,
+}
+
+impl<F> DeviceSliceMut<'_, F> {
+    /// # Safety
+    /// `ptr` points to `len` values of type `F` in device or host memory, valid and not accessed
+    /// otherwise for the lifetime of the slice
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *mut F, len: usize) -> Self {
+        Self {
+            ptr,
+            len,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+unsafe impl<F> Pointer for DeviceSliceMut<'_, F> {
+    fn pointer(&self) -> *const c_void {
+        self.ptr as *const c_void
+    }
+}
+
+unsafe impl<F> PointerMut for DeviceSliceMut<'_, F> {
+    fn pointer_mut(&mut self) -> *mut c_void {
+        self.ptr as *mut c_void
+    }
+}
+
+/// The datatype of a value of type `F`, opaque to MPI, which counts the values of a message
+/// rather than its bytes so that a single message carries more than the communication limit
+fn device_elem_datatype<F: Pod>() -> UserDatatype {
+    let () = F::NO_PADDING;
+    UserDatatype::contiguous(size_of::<F>() as Count, &u8::equivalent_datatype())
+}
+
+#[inline]
+fn device_count(len: usize) -> Count {
+    Count::try_from(len).expect("device buffer exceeds the MPI count limit")
+}
+
+impl MPIConfig<'_> {
+    /// Whether the MPI library moves device memory itself, to be checked before handing device
+    /// buffers to the device collectives, the same on all processes
+    #[inline]
+    pub fn is_cuda_aware() -> bool {
+        unsafe { MPIX_Query_cuda_support() == 1 }
+    }
+
+    /// Device memory counterpart of `gather_vec`: gather `local_vec` of every process into
+    /// `global_vec` on the root, ordered by rank. `global_vec` is only touched on the root.
+    ///
+    /// # Panics
+    /// If the engine has no MPI world, or the length of `global_vec` on the root is not that of
+    /// `local_vec` times the world size
+    pub fn gather_device_vec<F: Pod>(
+        &self,
+        local_vec: &DeviceSlice<F>,
+        global_vec: &mut DeviceSliceMut<F>,
+    ) {
+        assert!(self.world.is_some(), "device collectives need an MPI world");
+        let datatype = device_elem_datatype::<F>();
+        let send =
+            View::with_count_and_datatype(local_vec, device_count(local_vec.len()), &datatype);
+        if self.is_root() {
+            assert_eq!(global_vec.len(), local_vec.len() * self.world_size());
+            let n_elems = device_count(global_vec.len());
+            let mut recv = MutView::with_count_and_datatype(global_vec, n_elems, &datatype);
+            self.root_process().gather_into_root(&send, &mut recv);
+        } else {
+            self.root_process().gather_into(&send);
+        }
+    }
+
+    /// Device memory counterpart of `scatter_vec`: the root sends the segment of `send_vec` of
+    /// every process into its `recv_vec`. `send_vec` is only read on the root.
+    ///
+    /// # Panics
+    /// If the engine has no MPI world, or the length of `send_vec` on the root is not that of
+    /// `recv_vec` times the world size
+    pub fn scatter_device_vec<F: Pod>(
+        &self,
+        send_vec: &DeviceSlice<F>,
+        recv_vec: &mut DeviceSliceMut<F>,
+    ) {
+        assert!(self.world.is_some(), "device collectives need an MPI world");
+        let datatype = device_elem_datatype::<F>();
+        let n_elems = device_count(recv_vec.len());
+        if self.is_root() {
+            assert_eq!(send_vec.len(), recv_vec.len() * self.world_size());
+            let send =
+                View::with_count_and_datatype(send_vec, device_count(send_vec.len()), &datatype);
+            let mut recv = MutView::with_count_and_datatype(recv_vec, n_elems, &datatype);
+            self.root_process().scatter_into_root(&send, &mut recv);
+        } else {
+            let mut recv = MutView::with_count_and_datatype(recv_vec, n_elems, &datatype);
+            self.root_process().scatter_into(&mut recv);
+        }
+    }
+
+    /// Device memory counterpart of `root_broadcast_bytes`: overwrite `vec` with that of the
+    /// root, whose length is the same on all processes
+    ///
+    /// # Panics
+    /// If the engine has no MPI world
+    pub fn root_broadcast_device_vec<F: Pod>(&self, vec: &mut DeviceSliceMut<F>) {
+        assert!(self.world.is_some(), "device collectives need an MPI world");
+        let datatype = device_elem_datatype::<F>();
+        let n_elems = device_count(vec.len());
+        let mut buffer = MutView::with_count_and_datatype(vec, n_elems, &datatype);
+        self.root_process().broadcast_into(&mut buffer);
+    }
+}
@@ -1,18 +1,31 @@
 use std::os::raw::c_void;
-use std::{cmp, fmt::Debug, slice};
+use std::{
+    cmp,
+    fmt::Debug,
+    slice,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
-use arith::Field;
+use arith::{Field, Pod};
 use itertools::izip;
 use mpi::environment::Universe;
 use mpi::{
-    datatype::PartitionMut,
+    collective::UserOperation,
+    datatype::{MutView, PartitionMut, UserDatatype, View},
     ffi::*,
-    topology::{Process, SimpleCommunicator},
+    topology::{Color, Process, SimpleCommunicator},
     traits::*,
+    Address, Count,
 };
 use serdes::ExpSerde;
 
-use super::MPIEngine;
+use super::{
+    bytes::{as_bytes, as_bytes_mut},
+    definition::check_mpi_code,
+    CommRecord, CommStats, MPIEngine, NodePlacement, NodeTopology,
+};
+use crate::{ExpErrors, MPIResult};
 
 #[macro_export]
 macro_rules! root_println {
@@ -23,12 +36,55 @@ macro_rules! root_println {
     };
 }
 
+/// The size of the chunks the payloads of the collectives are split into, to stay below the
+/// communication limit of MPI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MPIChunkSize {
+    /// Chunks of the given number of bytes per process
+    Fixed(usize),
+    /// As few chunks as the communication limit allows for the world size and the payload, which
+    /// suits high bandwidth fabrics where each message costs a round trip rather than bandwidth
+    Auto,
+}
+
+impl Default for MPIChunkSize {
+    fn default() -> Self {
+        MPIChunkSize::Fixed(MPIConfig::CHUNK_SIZE)
+    }
+}
+
+impl FromStr for MPIChunkSize {
+    type Err = ExpErrors;
+
+    /// Either "auto", or a positive number of bytes
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(MPIChunkSize::Auto),
+            _ => match s.parse::<usize>() {
+                Ok(n_bytes) if n_bytes > 0 => Ok(MPIChunkSize::Fixed(n_bytes)),
+                _ => Err(ExpErrors::MPIChunkSizeError(s.to_string())),
+            },
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct MPIConfig<'a> {
     pub universe: Option<&'a Universe>,
     pub world: Option<&'a SimpleCommunicator>,
     pub world_size: i32,
     pub world_rank: i32,
+    /// The split of the world into nodes, routing gathers and broadcasts through the node
+    /// leaders if set
+    pub node_topology: Option<&'a NodeTopology>,
+    /// The node of every process, each process taken to be on a node of its own if unset
+    pub node_placement: Option<&'a NodePlacement>,
+    /// The chunking of the payloads of the collectives, the same on all processes
+    pub chunk_size: MPIChunkSize,
+    /// The rank of the root of the collectives, the same on all processes
+    pub root: i32,
+    /// The calls to the collectives, shared by the clones of the engine
+    comm_stats: Arc<Mutex<CommStats>>,
 }
 
 impl<'a> Default for MPIConfig<'a> {
@@ -38,6 +94,11 @@ impl<'a> Default for MPIConfig<'a> {
             world: None,
             world_size: 1,
             world_rank: 0,
+            node_topology: None,
+            node_placement: None,
+            chunk_size: MPIChunkSize::default(),
+            root: Self::ROOT_RANK,
+            comm_stats: Arc::default(),
         }
     }
 }
@@ -61,6 +122,12 @@ impl<'a> Debug for MPIConfig<'a> {
             .field("world", &world_fmt)
             .field("world_size", &self.world_size)
             .field("world_rank", &self.world_rank)
+            .field(
+                "n_nodes",
+                &self.node_topology.map(|topology| topology.n_nodes()),
+            )
+            .field("chunk_size", &self.chunk_size)
+            .field("root", &self.root)
             .finish()
     }
 }
@@ -73,7 +140,10 @@ impl<'a> PartialEq for MPIConfig<'a> {
 }
 
 impl<'a> MPIConfig<'a> {
-    /// The communication limit for MPI is 2^30. Save 10 bits for #parties here.
+    /// The communication limit for MPI, on the bytes of a message of the root
+    pub const COMM_LIMIT: usize = 1usize << 30;
+
+    /// The default chunk size. Save 10 bits of the communication limit for #parties here.
     pub const CHUNK_SIZE: usize = 1usize << 20;
 
     /// Initialize the MPI environment.
@@ -82,6 +152,53 @@ impl<'a> MPIConfig<'a> {
         mpi::initialize()
     }
 
+    /// Relabel the ranks of `world` node after node, collectively on all processes of the world.
+    ///
+    /// A launcher placing the ranks round robin over the nodes leaves every block of consecutive
+    /// ranks spread over all nodes. Relabeled, the ranks of a shared memory node are consecutive,
+    /// the nodes being ordered by their lowest rank in `world`, so that the hierarchical
+    /// algorithms of the MPI library for `all_to_all_transpose` and the gathers see whole nodes.
+    /// The node layout is read from MPI rather than measured, and the relabeling is the identity
+    /// if the ranks already are node after node.
+    ///
+    /// Opt-in, the returned communicator is passed to `prover_new` in place of `world`. The rank
+    /// of a process, and hence its share of the witness, may change.
+    pub fn optimize_topology(world: &SimpleCommunicator) -> SimpleCommunicator {
+        let node = world.split_shared(world.rank());
+        let mut leader = world.rank();
+        node.process_at_rank(0).broadcast_into(&mut leader);
+
+        // ranks with the same key keep their order in world
+        world
+            .split_by_color_with_key(Color::with_value(0), leader)
+            .unwrap()
+    }
+
+    /// Split the processes of this engine into sub-worlds, one per `color`, collectively on all
+    /// processes. The processes of a sub-world are ranked by `key`, then by their rank here.
+    ///
+    /// A sub-world proves on its own, e.g., a 64 process world split with `color = rank / 16`
+    /// proves four independent circuits on 16 processes each, the engine of each sub-world
+    /// being passed to the prover and the PCS in place of this one.
+    ///
+    /// The sub-worlds keep the chunk size of this engine, but not the node topology and the node
+    /// placement, which are read from the world of this engine.
+    ///
+    /// # Panics
+    /// If `color` is negative
+    pub fn split(&self, color: i32, key: i32) -> MPISubWorld<'a> {
+        let communicator = self.world.map(|world| {
+            world
+                .split_by_color_with_key(Color::with_value(color), key)
+                .unwrap()
+        });
+        MPISubWorld {
+            universe: self.universe,
+            communicator,
+            chunk_size: self.chunk_size,
+        }
+    }
+
     /// Create a new MPI engine for the prover
     pub fn prover_new(
         universe: Option<&'a Universe>,
@@ -98,6 +215,76 @@ impl<'a> MPIConfig<'a> {
             world,
             world_size,
             world_rank,
+            node_topology: None,
+            node_placement: None,
+            chunk_size: MPIChunkSize::default(),
+            root: Self::ROOT_RANK,
+            comm_stats: Arc::default(),
+        }
+    }
+
+    /// Route the gathers and broadcasts through the node leaders of `node_topology`, which has
+    /// to be split from the world of this engine, and take the node placement from it
+    #[inline]
+    pub fn with_node_topology(mut self, node_topology: Option<&'a NodeTopology>) -> Self {
+        self.node_topology = node_topology;
+        if let Some(topology) = node_topology {
+            self.node_placement = Some(topology.placement());
+        }
+        self
+    }
+
+    /// Answer the topology queries, e.g., `ranks_on_my_node`, from `node_placement`, which has
+    /// to be queried from the world of this engine
+    #[inline]
+    pub fn with_node_placement(mut self, node_placement: Option<&'a NodePlacement>) -> Self {
+        self.node_placement = node_placement;
+        self
+    }
+
+    /// Split the payloads of the collectives into chunks of `chunk_size`, which has to be the
+    /// same on all processes. `gather_vec` and `scatter_vec` are not chunked, their payloads
+    /// being sent at once as elements of a derived datatype.
+    ///
+    /// # Panics
+    /// If a fixed chunk size is zero, or gathered from all processes exceeds the communication
+    /// limit
+    #[inline]
+    pub fn with_chunk_size(mut self, chunk_size: MPIChunkSize) -> Self {
+        if let MPIChunkSize::Fixed(n_bytes) = chunk_size {
+            assert!(n_bytes > 0 && n_bytes <= Self::COMM_LIMIT / self.world_size());
+        }
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Root the collectives at the process of rank `root`, which has to be the same on all
+    /// processes, e.g., to spread the work of the root over the processes, or to run the
+    /// collectives of several clones of the engine rooted at different processes.
+    ///
+    /// The gathers and broadcasts are routed through the node leaders only if rooted at rank 0,
+    /// the leader of the first node.
+    ///
+    /// # Panics
+    /// If `root` is not a rank of the world
+    #[inline]
+    pub fn with_root(mut self, root: usize) -> Self {
+        assert!(root < self.world_size());
+        self.root = root as i32;
+        self
+    }
+
+    /// The size of the chunks a payload of `n_bytes` per process is split into
+    #[inline]
+    pub fn chunk_size_for(&self, n_bytes: usize) -> usize {
+        match self.chunk_size {
+            MPIChunkSize::Fixed(chunk_size) => chunk_size,
+            MPIChunkSize::Auto => {
+                // the fewest chunks that fit in the limit once gathered, of even sizes
+                let max_chunk_size = Self::COMM_LIMIT / self.world_size();
+                let n_chunks = n_bytes.div_ceil(max_chunk_size).max(1);
+                n_bytes.div_ceil(n_chunks).max(1)
+            }
         }
     }
 
@@ -112,7 +299,99 @@ impl<'a> MPIConfig<'a> {
             world: None,
             world_size,
             world_rank: 0,
+            node_topology: None,
+            node_placement: None,
+            chunk_size: MPIChunkSize::default(),
+            root: Self::ROOT_RANK,
+            comm_stats: Arc::default(),
+        }
+    }
+
+    /// Get the root process handle
+    #[inline(always)]
+    pub fn root_process(&self) -> Process {
+        self.world.unwrap().process_at_rank(self.root)
+    }
+
+    /// The calls to the collectives of this engine and of its clones so far, on this process
+    #[inline]
+    pub fn comm_stats(&self) -> CommStats {
+        self.comm_stats.lock().unwrap().clone()
+    }
+
+    /// Forget the calls to the collectives recorded so far
+    #[inline]
+    pub fn reset_comm_stats(&self) {
+        *self.comm_stats.lock().unwrap() = CommStats::default();
+    }
+
+    /// Record the collective returning before the returned record is dropped
+    #[inline]
+    fn record(&self, collective: &'static str, n_bytes: usize) -> CommRecord<'_> {
+        CommRecord::start(&self.comm_stats, collective, n_bytes)
+    }
+
+    /// The split of the world the gathers and broadcasts are routed through, if any, whose
+    /// leaders are rooted at rank 0
+    #[inline]
+    fn node_routing(&self) -> Option<&'a NodeTopology> {
+        self.node_topology.filter(|_| self.root == Self::ROOT_RANK)
+    }
+
+    /// Gather equal length payloads into the root, through the node leaders if the world is
+    /// split into nodes, each as a single element of `payload_datatype` so that MPI moves
+    /// payloads past the communication limit itself. `global_u8s` is only touched on the root.
+    #[inline]
+    fn gather_u8s(&self, local_u8s: &[u8], global_u8s: &mut [u8]) {
+        if let Some(topology) = self.node_routing() {
+            return topology.gather_u8s(local_u8s, global_u8s);
+        }
+
+        let datatype = payload_datatype(local_u8s.len());
+        let send = View::with_count_and_datatype(local_u8s, 1, &datatype);
+        if self.is_root() {
+            let n_payloads = self.world_size() as Count;
+            let mut recv = MutView::with_count_and_datatype(global_u8s, n_payloads, &datatype);
+            self.root_process().gather_into_root(&send, &mut recv);
+        } else {
+            self.root_process().gather_into(&send);
+        }
+    }
+
+    /// Check that a receive of one payload of `n_bytes` from `rank` got a whole payload, i.e.,
+    /// that the message was not shorter than the buffer. A longer message fails in MPI itself.
+    #[inline]
+    fn check_received(
+        &self,
+        collective: &'static str,
+        rank: usize,
+        n_bytes: usize,
+        n_payloads: Count,
+    ) -> MPIResult<()> {
+        if n_bytes > 0 && n_payloads != 1 {
+            return Err(self.mpi_error(
+                collective,
+                format!("expected a message of {n_bytes} bytes from rank {rank}"),
+            ));
         }
+        Ok(())
+    }
+}
+
+/// The processes of an `MPIConfig` of the same color, see `MPIConfig::split`
+pub struct MPISubWorld<'a> {
+    universe: Option<&'a Universe>,
+    /// None if split from an engine without communicator, i.e., of a single process
+    communicator: Option<SimpleCommunicator>,
+    chunk_size: MPIChunkSize,
+}
+
+impl MPISubWorld<'_> {
+    /// The engine of this process in the sub-world
+    #[inline]
+    pub fn config(&self) -> MPIConfig<'_> {
+        MPIConfig::prover_new(self.universe, self.communicator.as_ref())
+            .with_chunk_size(self.chunk_size)
     }
 }
 
@@ -120,205 +399,320 @@ impl<'a> MPIConfig<'a> {
 impl<'a> MPIEngine for MPIConfig<'a> {
     const ROOT_RANK: i32 = 0;
 
-    #[allow(clippy::collapsible_else_if)]
-    fn gather_vec<F: Sized + Clone>(&self, local_vec: &[F], global_vec: &mut Vec<F>) {
-        unsafe {
-            if self.world_size == 1 {
-                *global_vec = local_vec.to_vec()
-            } else {
-                assert!(!self.is_root() || global_vec.len() == local_vec.len() * self.world_size());
-
-                let local_vec_u8 = transmute_vec_to_u8_bytes(local_vec);
-                let local_n_bytes = local_vec_u8.len();
-                let n_chunks = (local_n_bytes + Self::CHUNK_SIZE - 1) / Self::CHUNK_SIZE;
-                if n_chunks == 1 {
-                    if self.world_rank == Self::ROOT_RANK {
-                        let mut global_vec_u8 = transmute_vec_to_u8_bytes(global_vec);
-                        self.root_process()
-                            .gather_into_root(&local_vec_u8, &mut global_vec_u8);
-                        global_vec_u8.leak(); // discard control of the memory
-                    } else {
-                        self.root_process().gather_into(&local_vec_u8);
-                    }
-                } else {
-                    if self.world_rank == Self::ROOT_RANK {
-                        let mut chunk_buffer_u8 = vec![0u8; Self::CHUNK_SIZE * self.world_size()];
-                        let mut global_vec_u8 = transmute_vec_to_u8_bytes(global_vec);
-                        for i in 0..n_chunks {
-                            let local_start = i * Self::CHUNK_SIZE;
-                            let local_end = cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
-                            let actual_chunk_size = local_end - local_start;
-                            if actual_chunk_size < Self::CHUNK_SIZE {
-                                chunk_buffer_u8.resize(actual_chunk_size * self.world_size(), 0u8);
-                            }
-
-                            self.root_process().gather_into_root(
-                                &local_vec_u8[local_start..local_end],
-                                &mut chunk_buffer_u8,
-                            );
-
-                            // distribute the data to where they belong to in global vec
-                            for j in 0..self.world_size() {
-                                let global_start = j * local_n_bytes + local_start;
-                                let global_end = global_start + actual_chunk_size;
-                                global_vec_u8[global_start..global_end].copy_from_slice(
-                                    &chunk_buffer_u8
-                                        [j * actual_chunk_size..(j + 1) * actual_chunk_size],
-                                );
-                            }
-                        }
-                        global_vec_u8.leak(); // discard control of the memory
-                    } else {
-                        for i in 0..n_chunks {
-                            let local_start = i * Self::CHUNK_SIZE;
-                            let local_end = cmp::min(local_start + Self::CHUNK_SIZE, local_n_bytes);
-                            self.root_process()
-                                .gather_into(&local_vec_u8[local_start..local_end]);
-                        }
-                    }
-                }
-                local_vec_u8.leak(); // discard control of the memory
+    fn gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        let _record = self.record("gather_vec", size_of_val(local_vec));
+        if self.world_size == 1 {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+        if self.is_root() {
+            self.check_global_len("gather_vec", local_vec.len(), global_vec.len())?;
+        }
+
+        if self.is_root() {
+            self.gather_u8s(as_bytes(local_vec), as_bytes_mut(global_vec));
+        } else {
+            self.gather_u8s(as_bytes(local_vec), &mut []);
+        }
+        Ok(())
+    }
+
+    /// gather all local values into all processes with MPI_Allgather, chunk by chunk as
+    /// `gather_vec`
+    fn all_gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        let _record = self.record("all_gather_vec", size_of_val(local_vec));
+        if self.world_size == 1 {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+        self.check_global_len("all_gather_vec", local_vec.len(), global_vec.len())?;
+
+        let world = self.world.unwrap();
+        let local_vec_u8 = as_bytes(local_vec);
+        let local_n_bytes = local_vec_u8.len();
+        let chunk_size = self.chunk_size_for(local_n_bytes);
+        let global_vec_u8 = as_bytes_mut(global_vec);
+        if local_n_bytes <= chunk_size {
+            world.all_gather_into(local_vec_u8, global_vec_u8);
+            return Ok(());
+        }
+
+        let mut chunk_buffer_u8 = vec![0u8; chunk_size * self.world_size()];
+        for (i, local_chunk) in local_vec_u8.chunks(chunk_size).enumerate() {
+            let actual_chunk_size = local_chunk.len();
+            chunk_buffer_u8.truncate(actual_chunk_size * self.world_size());
+            world.all_gather_into(local_chunk, &mut chunk_buffer_u8[..]);
+
+            // distribute the data to where they belong to in global vec
+            for (j, chunk) in chunk_buffer_u8.chunks(actual_chunk_size).enumerate() {
+                let global_start = j * local_n_bytes + i * chunk_size;
+                global_vec_u8[global_start..global_start + actual_chunk_size]
+                    .copy_from_slice(chunk);
             }
         }
+        Ok(())
     }
 
     #[inline]
-    fn scatter_vec<F: Sized + Clone>(&self, send_vec: &[F], recv_vec: &mut [F]) {
+    fn scatter_vec<F: Pod>(&self, send_vec: &[F], recv_vec: &mut [F]) -> MPIResult<()> {
+        let _record = self.record("scatter_vec", size_of_val(recv_vec));
         if self.world_size() == 1 {
-            recv_vec.clone_from_slice(send_vec);
-            return;
+            recv_vec.copy_from_slice(send_vec);
+            return Ok(());
+        }
+        if self.is_root() {
+            self.check_global_len("scatter_vec", recv_vec.len(), send_vec.len())?;
+        }
+
+        // a single element of the payload datatype per process, however large the payloads
+        let recv_u8s = as_bytes_mut(recv_vec);
+        let datatype = payload_datatype(recv_u8s.len());
+        let mut recv = MutView::with_count_and_datatype(recv_u8s, 1, &datatype);
+        if self.is_root() {
+            let send = View::with_count_and_datatype(
+                as_bytes(send_vec),
+                self.world_size() as Count,
+                &datatype,
+            );
+            self.root_process().scatter_into_root(&send, &mut recv);
+        } else {
+            self.root_process().scatter_into(&mut recv);
         }
+        Ok(())
+    }
 
-        let send_buf_u8_len = std::mem::size_of_val(send_vec);
-        let send_u8s: &[u8] =
-            unsafe { slice::from_raw_parts(send_vec.as_ptr() as *const u8, send_buf_u8_len) };
+    /// Root process broadcast a value f into all the processes
+    #[inline]
+    fn root_broadcast_f<F: Pod>(&self, f: &mut F) -> MPIResult<()> {
+        let _record = self.record("root_broadcast_f", size_of::<F>());
+        if self.world_size > 1 {
+            self.root_process()
+                .broadcast_into(as_bytes_mut(slice::from_mut(f)));
+        }
+        Ok(())
+    }
 
-        let recv_buf_u8_len = std::mem::size_of_val(recv_vec);
-        let recv_u8s: &mut [u8] =
-            unsafe { slice::from_raw_parts_mut(recv_vec.as_mut_ptr() as *mut u8, recv_buf_u8_len) };
+    #[inline]
+    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) -> MPIResult<()> {
+        let _record = self.record("root_broadcast_bytes", bytes.len());
+        if self.world_size == 1 {
+            return Ok(());
+        }
+        match self.node_routing() {
+            Some(topology) => topology.broadcast_bytes(bytes),
+            None => self.root_process().broadcast_into(bytes),
+        }
+        Ok(())
+    }
 
-        let n_chunks = recv_buf_u8_len.div_ceil(Self::CHUNK_SIZE);
+    fn igather_vec<F: Pod, R>(
+        &self,
+        local_vec: &[F],
+        global_vec: &mut Vec<F>,
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let _record = self.record("igather_vec", size_of_val(local_vec));
+        // NOTE: a single non-blocking gather is bounded by the MPI communication limit, larger
+        // payloads go through the blocking gather of derived datatypes
+        let local_n_bytes = size_of_val(local_vec);
+        if self.world_size == 1 || local_n_bytes > self.chunk_size_for(local_n_bytes) {
+            let gathered = self.gather_vec(local_vec, global_vec);
+            let output = overlap();
+            return gathered.map(|_| output);
+        }
+        if self.is_root() {
+            self.check_global_len("igather_vec", local_vec.len(), global_vec.len())?;
+        }
 
-        if n_chunks == 1 {
+        let local_u8s = as_bytes(local_vec);
+        mpi::request::scope(|scope| {
             if self.is_root() {
-                self.root_process().scatter_into_root(send_u8s, recv_u8s);
+                let global_u8s = as_bytes_mut(global_vec);
+                let request = self
+                    .root_process()
+                    .immediate_gather_into_root(scope, local_u8s, global_u8s);
+                let output = overlap();
+                request.wait();
+                Ok(output)
             } else {
-                self.root_process().scatter_into(recv_u8s);
+                let request = self.root_process().immediate_gather_into(scope, local_u8s);
+                let output = overlap();
+                request.wait();
+                Ok(output)
             }
+        })
+    }
 
-            return;
+    fn ibroadcast_f<F: Pod, R>(&self, f: &mut F, overlap: impl FnOnce() -> R) -> MPIResult<R> {
+        let _record = self.record("ibroadcast_f", size_of::<F>());
+        if self.world_size == 1 {
+            return Ok(overlap());
         }
 
-        if !self.is_root() {
-            recv_u8s.chunks_mut(Self::CHUNK_SIZE).for_each(|c| {
-                self.root_process().scatter_into(c);
-            });
+        let f_u8s = as_bytes_mut(slice::from_mut(f));
+        mpi::request::scope(|scope| {
+            let request = self.root_process().immediate_broadcast_into(scope, f_u8s);
+            let output = overlap();
+            request.wait();
+            Ok(output)
+        })
+    }
 
-            return;
+    fn iscatter_vec<F: Pod, R>(
+        &self,
+        send_vec: &[F],
+        recv_vec: &mut [F],
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let _record = self.record("iscatter_vec", size_of_val(recv_vec));
+        // NOTE: as in `igather_vec`, larger payloads go through the blocking scatter
+        let local_n_bytes = size_of_val(recv_vec);
+        if self.world_size == 1 || local_n_bytes > self.chunk_size_for(local_n_bytes) {
+            let scattered = self.scatter_vec(send_vec, recv_vec);
+            let output = overlap();
+            return scattered.map(|_| output);
+        }
+        if self.is_root() {
+            self.check_global_len("iscatter_vec", recv_vec.len(), send_vec.len())?;
         }
 
-        let mut send_buf = vec![0u8; Self::CHUNK_SIZE * self.world_size()];
-
-        izip!(0..n_chunks, recv_u8s.chunks_mut(Self::CHUNK_SIZE)).for_each(|(i, recv_c)| {
-            let copy_srt = i * Self::CHUNK_SIZE;
-            let copy_end = copy_srt + recv_c.len();
-
-            if recv_c.len() < Self::CHUNK_SIZE {
-                send_buf.resize(recv_c.len() * self.world_size(), 0u8);
+        let recv_u8s = as_bytes_mut(recv_vec);
+        mpi::request::scope(|scope| {
+            if self.is_root() {
+                let send_u8s = as_bytes(send_vec);
+                let request = self
+                    .root_process()
+                    .immediate_scatter_into_root(scope, send_u8s, recv_u8s);
+                let output = overlap();
+                request.wait();
+                Ok(output)
+            } else {
+                let request = self.root_process().immediate_scatter_into(scope, recv_u8s);
+                let output = overlap();
+                request.wait();
+                Ok(output)
             }
+        })
+    }
 
-            izip!(0..self.world_size(), send_buf.chunks_mut(recv_c.len())).for_each(
-                |(world_i, send_c)| {
-                    let world_starts = recv_buf_u8_len * world_i;
+    /// send the vector as a single element of `payload_datatype`, as the collectives do
+    fn send_to<F: Pod>(&self, rank: usize, vec: &[F]) -> MPIResult<()> {
+        let _record = self.record("send_to", size_of_val(vec));
+        self.check_peer("send_to", rank)?;
 
-                    let local_srt = world_starts + copy_srt;
-                    let local_end = world_starts + copy_end;
+        let datatype = payload_datatype(size_of_val(vec));
+        let send = View::with_count_and_datatype(as_bytes(vec), 1, &datatype);
+        self.world.unwrap().process_at_rank(rank as i32).send(&send);
+        Ok(())
+    }
 
-                    send_c.copy_from_slice(&send_u8s[local_srt..local_end]);
-                },
-            );
+    fn recv_from<F: Pod>(&self, rank: usize, vec: &mut [F]) -> MPIResult<()> {
+        let _record = self.record("recv_from", size_of_val(vec));
+        self.check_peer("recv_from", rank)?;
+
+        let n_bytes = size_of_val(vec);
+        let datatype = payload_datatype(n_bytes);
+        let mut recv = MutView::with_count_and_datatype(as_bytes_mut(vec), 1, &datatype);
+        let status = self
+            .world
+            .unwrap()
+            .process_at_rank(rank as i32)
+            .receive_into(&mut recv);
+        self.check_received("recv_from", rank, n_bytes, status.count(&datatype))
+    }
 
-            self.root_process().scatter_into_root(&send_buf, recv_c);
+    fn isend_to<F: Pod, R>(
+        &self,
+        rank: usize,
+        vec: &[F],
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let _record = self.record("isend_to", size_of_val(vec));
+        self.check_peer("isend_to", rank)?;
+
+        let datatype = payload_datatype(size_of_val(vec));
+        let send = View::with_count_and_datatype(as_bytes(vec), 1, &datatype);
+        let process = self.world.unwrap().process_at_rank(rank as i32);
+        mpi::request::scope(|scope| {
+            let request = process.immediate_send(scope, &send);
+            let output = overlap();
+            request.wait();
+            Ok(output)
         })
     }
 
-    /// Root process broadcast a value f into all the processes
-    #[inline]
-    fn root_broadcast_f<F: Copy>(&self, f: &mut F) {
-        unsafe {
-            if self.world_size == 1 {
-            } else {
-                let mut vec_u8 = transmute_elem_to_u8_bytes(f, std::mem::size_of::<F>());
-                self.root_process().broadcast_into(&mut vec_u8);
-                vec_u8.leak();
-            }
-        }
+    fn irecv_from<F: Pod, R>(
+        &self,
+        rank: usize,
+        vec: &mut [F],
+        overlap: impl FnOnce() -> R,
+    ) -> MPIResult<R> {
+        let _record = self.record("irecv_from", size_of_val(vec));
+        self.check_peer("irecv_from", rank)?;
+
+        let n_bytes = size_of_val(vec);
+        let datatype = payload_datatype(n_bytes);
+        let mut recv = MutView::with_count_and_datatype(as_bytes_mut(vec), 1, &datatype);
+        let process = self.world.unwrap().process_at_rank(rank as i32);
+        let (output, status) = mpi::request::scope(|scope| {
+            let request = process.immediate_receive_into(scope, &mut recv);
+            let output = overlap();
+            (output, request.wait())
+        });
+        self.check_received("irecv_from", rank, n_bytes, status.count(&datatype))?;
+        Ok(output)
     }
 
+    /// sum up all local values with MPI_Allreduce, so that every process gets the sum
     #[inline]
-    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) {
+    fn allreduce_vec<F: Field>(&self, local_vec: &[F]) -> MPIResult<Vec<F>> {
+        let _record = self.record("allreduce_vec", size_of_val(local_vec));
         if self.world_size == 1 {
-            return;
+            return Ok(local_vec.to_vec());
         }
-        self.root_process().broadcast_into(bytes);
-    }
 
-    /// sum up all local values
-    #[inline]
-    fn sum_vec<F: Field>(&self, local_vec: &[F]) -> Vec<F> {
-        if self.world_size == 1 {
-            local_vec.to_vec()
-        } else if self.world_rank == Self::ROOT_RANK {
-            let mut global_vec = vec![F::ZERO; local_vec.len() * (self.world_size as usize)];
-            self.gather_vec(local_vec, &mut global_vec);
-            for i in 0..local_vec.len() {
-                for j in 1..(self.world_size as usize) {
-                    global_vec[i] = global_vec[i] + global_vec[j * local_vec.len() + i];
-                }
-            }
-            global_vec.truncate(local_vec.len());
-            global_vec
-        } else {
-            self.gather_vec(local_vec, &mut vec![]);
-            vec![]
-        }
+        let elem_datatype = field_elem_datatype::<F>();
+        let mut global_vec = vec![F::ZERO; local_vec.len()];
+        let send = field_elems_view(local_vec, &elem_datatype);
+        let mut recv = field_elems_mut_view(&mut global_vec, &elem_datatype);
+        self.world
+            .unwrap()
+            .all_reduce_into(&send, &mut recv, &field_add_op::<F>());
+        Ok(global_vec)
     }
 
-    /// coef has a length of mpi_world_size
+    /// sum up all local values with MPI_Reduce_scatter_block, so that each process gets its
+    /// segment of the sum without the sum going through the root
     #[inline]
-    fn coef_combine_vec<F: Field>(&self, local_vec: &[F], coef: &[F]) -> Vec<F> {
+    fn reduce_scatter_vec<F: Field>(&self, local_vec: &[F]) -> MPIResult<Vec<F>> {
+        let _record = self.record("reduce_scatter_vec", size_of_val(local_vec));
+        self.check_split_len("reduce_scatter_vec", local_vec.len())?;
         if self.world_size == 1 {
-            // Warning: literally, it should be coef[0] * local_vec
-            // but coef[0] is always one in our use case of self.world_size = 1
-            local_vec.to_vec()
-        } else if self.world_rank == Self::ROOT_RANK {
-            let mut global_vec = vec![F::ZERO; local_vec.len() * (self.world_size as usize)];
-            let mut ret = vec![F::ZERO; local_vec.len()];
-            self.gather_vec(local_vec, &mut global_vec);
-            for i in 0..local_vec.len() {
-                for j in 0..(self.world_size as usize) {
-                    ret[i] += global_vec[j * local_vec.len() + i] * coef[j];
-                }
-            }
-            ret
-        } else {
-            self.gather_vec(local_vec, &mut vec![]);
-            vec![F::ZERO; local_vec.len()]
+            return Ok(local_vec.to_vec());
         }
+
+        let elem_datatype = field_elem_datatype::<F>();
+        let mut segment = vec![F::ZERO; local_vec.len() / self.world_size()];
+        let send = field_elems_view(local_vec, &elem_datatype);
+        let mut recv = field_elems_mut_view(&mut segment, &elem_datatype);
+        self.world
+            .unwrap()
+            .reduce_scatter_block_into(&send, &mut recv, &field_add_op::<F>());
+        Ok(segment)
     }
 
     /// perform an all to all transpose,
     /// supposing the current party holds a row in a matrix with row number being MPI parties.
     #[inline(always)]
-    fn all_to_all_transpose<F: Sized>(&self, row: &mut [F]) {
-        assert_eq!(row.len() % self.world_size(), 0);
+    fn all_to_all_transpose<F: Pod>(&self, row: &mut [F]) -> MPIResult<()> {
+        let _record = self.record("all_to_all_transpose", size_of_val(row));
+        self.check_split_len("all_to_all_transpose", row.len())?;
 
         // NOTE(HS) MPI has some upper limit for send buffer size, pre declare here and use later
         const SEND_BUFFER_MAX: usize = 1 << 22;
 
-        let row_as_u8_len = size_of_val(row);
-        let row_u8s: &mut [u8] =
-            unsafe { slice::from_raw_parts_mut(row.as_mut_ptr() as *mut u8, row_as_u8_len) };
+        let row_u8s = as_bytes_mut(row);
+        let row_as_u8_len = row_u8s.len();
 
         let num_of_bytes_per_world = row_as_u8_len / self.world_size();
         let num_of_transposes = row_as_u8_len.div_ceil(SEND_BUFFER_MAX);
@@ -359,15 +753,21 @@ impl<'a> MPIEngine for MPIConfig<'a> {
 
             copy_starts += send_buffer_size_per_world;
         });
+        Ok(())
     }
 
     #[inline(always)]
-    fn gather_varlen_vec<F: ExpSerde>(&self, elems: &Vec<F>, global_elems: &mut Vec<Vec<F>>) {
+    fn gather_varlen_vec<F: ExpSerde>(
+        &self,
+        elems: &Vec<F>,
+        global_elems: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()> {
         let mut elems_bytes: Vec<u8> = Vec::new();
         elems.serialize_into(&mut elems_bytes).unwrap();
+        let _record = self.record("gather_varlen_vec", elems_bytes.len());
 
         let mut byte_lengths = vec![0i32; self.world_size()];
-        self.gather_vec(&[elems_bytes.len() as i32], &mut byte_lengths);
+        self.gather_vec(&[elems_bytes.len() as i32], &mut byte_lengths)?;
 
         let all_elems_bytes_len = byte_lengths.iter().sum::<i32>() as usize;
         let mut all_elems_bytes: Vec<u8> = vec![0u8; all_elems_bytes_len];
@@ -391,9 +791,100 @@ impl<'a> MPIEngine for MPIConfig<'a> {
 
             *global_elems = displs
                 .iter()
-                .map(|&srt| Vec::deserialize_from(&all_elems_bytes[srt as usize..]).unwrap())
-                .collect();
+                .map(|&srt| Vec::deserialize_from(&all_elems_bytes[srt as usize..]))
+                .collect::<Result<_, _>>()
+                .map_err(|e| self.mpi_error("gather_varlen_vec", e))?;
+        }
+        Ok(())
+    }
+
+    /// gather the serialized vectors into all processes with MPI_Allgatherv, after the numbers
+    /// of bytes of all processes
+    fn all_gather_varlen_vec<F: ExpSerde>(
+        &self,
+        elems: &Vec<F>,
+        global_elems: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()> {
+        let mut elems_bytes: Vec<u8> = Vec::new();
+        elems.serialize_into(&mut elems_bytes).unwrap();
+        let _record = self.record("all_gather_varlen_vec", elems_bytes.len());
+        if self.world_size == 1 {
+            *global_elems = vec![Vec::deserialize_from(elems_bytes.as_slice()).unwrap()];
+            return Ok(());
+        }
+
+        let mut byte_lengths = vec![0i32; self.world_size()];
+        self.all_gather_vec(&[elems_bytes.len() as i32], &mut byte_lengths)?;
+
+        let all_elems_bytes_len = byte_lengths.iter().sum::<i32>() as usize;
+        let mut all_elems_bytes: Vec<u8> = vec![0u8; all_elems_bytes_len];
+        let displs = byte_lengths
+            .iter()
+            .scan(0, |s, i| {
+                let srt = *s;
+                *s += i;
+                Some(srt)
+            })
+            .collect::<Vec<_>>();
+
+        let mut partition = PartitionMut::new(&mut all_elems_bytes, byte_lengths, &displs[..]);
+        self.world
+            .unwrap()
+            .all_gather_varcount_into(&elems_bytes[..], &mut partition);
+
+        *global_elems = displs
+            .iter()
+            .map(|&srt| Vec::deserialize_from(&all_elems_bytes[srt as usize..]))
+            .collect::<Result<_, _>>()
+            .map_err(|e| self.mpi_error("all_gather_varlen_vec", e))?;
+        Ok(())
+    }
+
+    fn gather_varlen_vec_streaming<F: ExpSerde>(
+        &self,
+        elems: &Vec<F>,
+        mut consume: impl FnMut(usize, Vec<F>),
+    ) -> MPIResult<()> {
+        let mut buffer: Vec<u8> = Vec::new();
+        elems.serialize_into(&mut buffer).unwrap();
+        let _record = self.record("gather_varlen_vec_streaming", buffer.len());
+
+        if !self.is_root() {
+            let root = self.root_process();
+            root.send(&(buffer.len() as u64));
+            buffer
+                .chunks(self.chunk_size_for(buffer.len()))
+                .for_each(|chunk| root.send(chunk));
+            return Ok(());
+        }
+
+        // the root deserializes its own vector as well, which spares a Clone bound on F
+        let mut own = Some(Vec::deserialize_from(buffer.as_slice()).unwrap());
+        for rank in 0..self.world_size {
+            if rank == self.root {
+                consume(rank as usize, own.take().unwrap());
+                continue;
+            }
+
+            let process = self.world.unwrap().process_at_rank(rank);
+            let (byte_len, _) = process.receive::<u64>();
+
+            buffer.clear();
+            buffer.resize(byte_len as usize, 0);
+            let chunk_size = self.chunk_size_for(buffer.len());
+            buffer.chunks_mut(chunk_size).for_each(|chunk| {
+                process.receive_into(chunk);
+            });
+
+            let elems = Vec::deserialize_from(buffer.as_slice()).map_err(|e| {
+                self.mpi_error(
+                    "gather_varlen_vec_streaming",
+                    format!("bad payload from rank {rank}: {e}"),
+                )
+            })?;
+            consume(rank as usize, elems);
         }
+        Ok(())
     }
 
     #[inline(always)]
@@ -412,21 +903,51 @@ impl<'a> MPIEngine for MPIConfig<'a> {
     }
 
     #[inline(always)]
-    fn root_process(&self) -> Process {
-        self.world.unwrap().process_at_rank(Self::ROOT_RANK)
+    fn root_rank(&self) -> usize {
+        self.root as usize
+    }
+
+    #[inline]
+    fn n_nodes(&self) -> usize {
+        self.node_placement
+            .map_or(self.world_size(), |placement| placement.n_nodes())
+    }
+
+    #[inline]
+    fn node_of_rank(&self, rank: usize) -> usize {
+        self.node_placement
+            .map_or(rank, |placement| placement.node_of(rank))
+    }
+
+    #[inline]
+    fn ranks_on_my_node(&self) -> Vec<usize> {
+        match self.node_placement {
+            Some(placement) => placement.ranks_on_node(placement.node_of(self.world_rank())),
+            None => vec![self.world_rank()],
+        }
     }
 
     // Barrier is designed for mpi use only
     // There might be some issues if used with multi-threading
     #[inline(always)]
-    fn barrier(&self) {
+    fn barrier(&self) -> MPIResult<()> {
+        let _record = self.record("barrier", 0);
         if self.world_size > 1 {
             self.world.unwrap().barrier();
         }
+        Ok(())
+    }
+
+    #[inline]
+    fn abort(&self, error_code: i32) -> ! {
+        match self.world {
+            Some(world) => world.abort(error_code),
+            None => std::process::exit(error_code),
+        }
     }
 
     #[inline]
-    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, *mut ompi_win_t) {
+    fn create_shared_mem(&self, n_bytes: usize) -> MPIResult<(*mut u8, *mut ompi_win_t)> {
         let window_size = if self.is_root() { n_bytes } else { 0 };
         let mut baseptr: *mut c_void = std::ptr::null_mut();
 
@@ -434,7 +955,7 @@ impl<'a> MPIEngine for MPIConfig<'a> {
         let mut window_handle: MPI_Win = MPI_Win(std::ptr::null_mut());
 
         unsafe {
-            MPI_Win_allocate_shared(
+            let code = MPI_Win_allocate_shared(
                 window_size as isize,
                 1,
                 RSMPI_INFO_NULL,
@@ -442,39 +963,87 @@ impl<'a> MPIEngine for MPIConfig<'a> {
                 &mut baseptr as *mut *mut c_void as *mut c_void,
                 &mut window_handle as *mut MPI_Win,
             );
-            self.barrier();
+            check_mpi_code(
+                self,
+                "create_shared_mem",
+                &format!("MPI_Win_allocate_shared of {window_size} bytes"),
+                code,
+            )?;
+            self.barrier()?;
 
             if !self.is_root() {
                 let mut size: MPI_Aint = 0;
                 let mut disp_unit: ::std::os::raw::c_int = 0;
                 let mut query_baseptr: *mut c_void = std::ptr::null_mut();
-                MPI_Win_shared_query(
+                let code = MPI_Win_shared_query(
                     window_handle,
                     0,
                     &mut size as *mut MPI_Aint,
                     &mut disp_unit as *mut ::std::os::raw::c_int,
                     &mut query_baseptr as *mut *mut c_void as *mut c_void,
                 );
+                check_mpi_code(self, "create_shared_mem", "MPI_Win_shared_query", code)?;
                 baseptr = query_baseptr;
             }
         }
 
-        (baseptr as *mut u8, window_handle.0)
+        Ok((baseptr as *mut u8, window_handle.0))
     }
 }
 
-/// Return an u8 vector sharing THE SAME MEMORY SLOT with the input.
-#[inline]
-unsafe fn transmute_elem_to_u8_bytes<V: Sized>(elem: &V, byte_size: usize) -> Vec<u8> {
-    Vec::<u8>::from_raw_parts((elem as *const V) as *mut u8, byte_size, byte_size)
+/// The datatype of a payload of `n_bytes`, opaque to MPI, so that a collective moves one element
+/// per process whatever the size of the payloads, instead of splitting them into messages below
+/// the communication limit. The payload is made of blocks of at most `MPIConfig::COMM_LIMIT`
+/// bytes, at displacements of the address width, hence of up to `2^31` blocks.
+pub(super) fn payload_datatype(n_bytes: usize) -> UserDatatype {
+    let (block_lengths, displacements) = payload_blocks(n_bytes);
+    UserDatatype::heterogeneous_indexed(&block_lengths, &displacements, &u8::equivalent_datatype())
+}
+
+/// The lengths and the displacements of the blocks of bytes of `payload_datatype`
+pub(super) fn payload_blocks(n_bytes: usize) -> (Vec<Count>, Vec<Address>) {
+    (0..n_bytes)
+        .step_by(MPIConfig::COMM_LIMIT)
+        .map(|start| {
+            let length = cmp::min(MPIConfig::COMM_LIMIT, n_bytes - start);
+            (length as Count, start as Address)
+        })
+        .unzip()
+}
+
+/// The datatype of a field element, opaque to MPI: a contiguous datatype keeps MPI from
+/// splitting the elements in a reduction
+fn field_elem_datatype<F: Field>() -> UserDatatype {
+    UserDatatype::contiguous(size_of::<F>() as Count, &u8::equivalent_datatype())
+}
+
+/// The reduction adding up buffers of field elements of the datatype `field_elem_datatype`
+fn field_add_op<F: Field>() -> UserOperation<'static> {
+    UserOperation::commutative(|x, mut acc| {
+        let x_u8s = x.as_bytes();
+        let acc_u8s = acc.as_bytes_mut();
+        // NOTE: the buffers of MPI are not necessarily aligned for F
+        (0..x_u8s.len() / size_of::<F>()).for_each(|i| unsafe {
+            let x_i = (x_u8s.as_ptr() as *const F).add(i).read_unaligned();
+            let acc_i = (acc_u8s.as_mut_ptr() as *mut F).add(i);
+            acc_i.write_unaligned(acc_i.read_unaligned() + x_i);
+        });
+    })
+}
+
+/// View the field elements as elements of the datatype `field_elem_datatype`
+fn field_elems_view<'a, F: Field>(
+    elems: &'a [F],
+    datatype: &'a UserDatatype,
+) -> View<'a, 'a, UserDatatype, [u8]> {
+    View::with_count_and_datatype(as_bytes(elems), elems.len() as Count, datatype)
 }
 
-/// Return an u8 vector sharing THE SAME MEMORY SLOT with the input.
-#[inline]
-unsafe fn transmute_vec_to_u8_bytes<F: Sized>(vec: &[F]) -> Vec<u8> {
-    Vec::<u8>::from_raw_parts(
-        vec.as_ptr() as *mut u8,
-        std::mem::size_of_val(vec),
-        std::mem::size_of_val(vec),
-    )
+/// Mutable counterpart of `field_elems_view`
+fn field_elems_mut_view<'a, F: Field>(
+    elems: &'a mut [F],
+    datatype: &'a UserDatatype,
+) -> MutView<'a, 'a, UserDatatype, [u8]> {
+    let n_elems = elems.len();
+    MutView::with_count_and_datatype(as_bytes_mut(elems), n_elems as Count, datatype)
 }
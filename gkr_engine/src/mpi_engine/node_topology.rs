@@ -0,0 +1,215 @@
+//! Two level collectives for processes spread over several multi-socket nodes.
+//!
+//! The world is split into one communicator per shared memory node with
+//! `MPI_Comm_split_type(MPI_COMM_TYPE_SHARED)`, and a communicator of the node leaders, the
+//! lowest world rank on each node. A gather first collects the payloads of a node at its leader,
+//! which MPI does through the shared memory of the node, and then only the leaders gather over
+//! the network, one message per node instead of one per process. A broadcast goes the other way.
+//!
+//! The root is the lowest world rank, hence the leader of its node and the root of the leaders.
+
+use mpi::{
+    datatype::{MutView, PartitionMut, View},
+    topology::{Color, Process, SimpleCommunicator},
+    traits::*,
+    Count, Rank,
+};
+
+use super::engine::payload_datatype;
+
+/// The shared memory node of every process of a world, the nodes being numbered in the order of
+/// their lowest rank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePlacement {
+    node_of: Vec<usize>,
+}
+
+impl NodePlacement {
+    /// Read the node of every process from MPI, collectively on all processes of the world
+    pub fn query(world: &SimpleCommunicator) -> Self {
+        let node = world.split_shared(world.rank());
+        Self::from_leaders(&leaders_of(world, &node))
+    }
+
+    /// The placement of the processes given the leader of each, the lowest rank on its node
+    pub(crate) fn from_leaders(leader_of: &[Rank]) -> Self {
+        let mut node_of_leader = vec![usize::MAX; leader_of.len()];
+        let mut n_nodes = 0;
+        let node_of = leader_of
+            .iter()
+            .map(|&leader| {
+                let node = &mut node_of_leader[leader as usize];
+                if *node == usize::MAX {
+                    *node = n_nodes;
+                    n_nodes += 1;
+                }
+                *node
+            })
+            .collect();
+        Self { node_of }
+    }
+
+    #[inline]
+    pub fn n_nodes(&self) -> usize {
+        self.node_of.iter().max().map_or(0, |&node| node + 1)
+    }
+
+    /// The node of the process of `rank`
+    #[inline]
+    pub fn node_of(&self, rank: usize) -> usize {
+        self.node_of[rank]
+    }
+
+    /// The ranks of the processes on `node`, in increasing order
+    #[inline]
+    pub fn ranks_on_node(&self, node: usize) -> Vec<usize> {
+        (0..self.node_of.len())
+            .filter(|&rank| self.node_of[rank] == node)
+            .collect()
+    }
+}
+
+/// The leader of every process of `world`, the lowest world rank on its `node`
+fn leaders_of(world: &SimpleCommunicator, node: &SimpleCommunicator) -> Vec<Rank> {
+    let mut leader: Rank = world.rank();
+    node.process_at_rank(0).broadcast_into(&mut leader);
+    let mut leader_of = vec![0 as Rank; world.size() as usize];
+    world.all_gather_into(&leader, &mut leader_of[..]);
+    leader_of
+}
+
+pub struct NodeTopology {
+    /// The processes on the node of this process, ordered by world rank
+    node: SimpleCommunicator,
+    /// The node leaders ordered by world rank, only on the leaders
+    leaders: Option<SimpleCommunicator>,
+    /// The number of processes on each node, in the order of the leaders
+    node_sizes: Vec<usize>,
+    /// The world ranks of the processes, node after node in the order of the leaders
+    ranks_by_node: Vec<usize>,
+    placement: NodePlacement,
+}
+
+impl NodeTopology {
+    /// Split the world into nodes, collectively on all processes of the world.
+    ///
+    /// Returns None if the world is on a single node, or on a process per node, where the two
+    /// level collectives would only add a step to the flat ones.
+    pub fn split(world: &SimpleCommunicator) -> Option<Self> {
+        let node = world.split_shared(world.rank());
+        let is_leader = node.rank() == 0;
+        let color = if is_leader {
+            Color::with_value(0)
+        } else {
+            Color::undefined()
+        };
+        let leaders = world.split_by_color_with_key(color, world.rank());
+
+        // every process learns the leader of every process, to agree on the split
+        let leader_of = leaders_of(world, &node);
+
+        let mut ranks_by_node = (0..leader_of.len()).collect::<Vec<_>>();
+        ranks_by_node.sort_by_key(|&rank| (leader_of[rank], rank));
+        let mut node_sizes = vec![];
+        ranks_by_node.iter().enumerate().for_each(|(i, &rank)| {
+            if i == 0 || leader_of[rank] != leader_of[ranks_by_node[i - 1]] {
+                node_sizes.push(0);
+            }
+            *node_sizes.last_mut().unwrap() += 1;
+        });
+
+        if node_sizes.len() == 1 || node_sizes.len() == ranks_by_node.len() {
+            return None;
+        }
+        Some(Self {
+            node,
+            leaders,
+            node_sizes,
+            ranks_by_node,
+            placement: NodePlacement::from_leaders(&leader_of),
+        })
+    }
+
+    /// The number of nodes of the world
+    #[inline]
+    pub fn n_nodes(&self) -> usize {
+        self.node_sizes.len()
+    }
+
+    /// The node of every process of the world
+    #[inline]
+    pub fn placement(&self) -> &NodePlacement {
+        &self.placement
+    }
+
+    #[inline]
+    fn node_leader(&self) -> Process {
+        self.node.process_at_rank(0)
+    }
+
+    /// Gather the equal length payloads of all processes into `global_u8s` on the root, in
+    /// world rank order, each as a single element of `payload_datatype`. `global_u8s` is only
+    /// touched on the root.
+    pub(crate) fn gather_u8s(&self, local_u8s: &[u8], global_u8s: &mut [u8]) {
+        let n_bytes = local_u8s.len();
+        let datatype = payload_datatype(n_bytes);
+        let send = View::with_count_and_datatype(local_u8s, 1, &datatype);
+        if self.node.rank() != 0 {
+            self.node_leader().gather_into(&send);
+            return;
+        }
+
+        let node_size = self.node.size();
+        let mut node_u8s = vec![0u8; n_bytes * node_size as usize];
+        self.node_leader().gather_into_root(
+            &send,
+            &mut MutView::with_count_and_datatype(&mut node_u8s[..], node_size, &datatype),
+        );
+
+        let leaders = self.leaders.as_ref().unwrap();
+        let node_send = View::with_count_and_datatype(&node_u8s[..], node_size, &datatype);
+        if leaders.rank() != 0 {
+            leaders.process_at_rank(0).gather_varcount_into(&node_send);
+            return;
+        }
+
+        // the counts and the displacements are in payloads
+        let counts = self
+            .node_sizes
+            .iter()
+            .map(|&size| size as Count)
+            .collect::<Vec<_>>();
+        let displs = counts
+            .iter()
+            .scan(0, |acc, &count| {
+                let displ = *acc;
+                *acc += count;
+                Some(displ)
+            })
+            .collect::<Vec<_>>();
+
+        // the payloads arrive node after node, and are put back into world rank order
+        let mut by_node_u8s = vec![0u8; global_u8s.len()];
+        let n_payloads = self.ranks_by_node.len() as Count;
+        let mut by_node =
+            MutView::with_count_and_datatype(&mut by_node_u8s[..], n_payloads, &datatype);
+        let mut partition = PartitionMut::new(&mut by_node, counts, &displs[..]);
+        leaders
+            .process_at_rank(0)
+            .gather_varcount_into_root(&node_send, &mut partition);
+        self.ranks_by_node
+            .iter()
+            .zip(by_node_u8s.chunks(n_bytes.max(1)))
+            .for_each(|(&rank, payload)| {
+                global_u8s[rank * n_bytes..(rank + 1) * n_bytes].copy_from_slice(payload)
+            });
+    }
+
+    /// Broadcast `bytes` from the root to all processes, of the same length everywhere.
+    pub(crate) fn broadcast_bytes(&self, bytes: &mut Vec<u8>) {
+        if let Some(leaders) = &self.leaders {
+            leaders.process_at_rank(0).broadcast_into(bytes);
+        }
+        self.node_leader().broadcast_into(bytes);
+    }
+}
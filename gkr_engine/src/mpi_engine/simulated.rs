@@ -0,0 +1,360 @@
+//! A world of processes simulated by the threads of a single process, for testing the
+//! distributed code paths with `cargo test`, without `mpirun` nor sockets.
+//!
+//! Every pair of ranks is connected by a channel in each direction, so that the all to all
+//! transposes and the all gathers are sent directly from rank to rank, unlike the star of
+//! `TcpCommunicator`. Every message is checked against the length the receiver expects, so that
+//! ranks running out of step fail on the first mismatching message. A rank that panics drops its
+//! channels, and the ranks waiting on it fail in turn instead of hanging.
+//!
+//! The ranks do not share memory through the engine, so `create_shared_mem` is not supported.
+
+use std::{
+    mem, slice,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+};
+
+use arith::Pod;
+use mpi::ffi::ompi_win_t;
+use serdes::ExpSerde;
+
+use super::{
+    bytes::{as_bytes, as_bytes_mut},
+    MPIEngine,
+};
+use crate::MPIResult;
+
+#[derive(Debug)]
+pub struct SimulatedMPIEngine {
+    world_size: usize,
+    world_rank: usize,
+    root: usize,
+    /// Indexed by the rank sent to
+    senders: Vec<Sender<Vec<u8>>>,
+    /// Indexed by the rank received from, behind a lock for the engine to be `Sync`
+    receivers: Vec<Mutex<Receiver<Vec<u8>>>>,
+}
+
+impl SimulatedMPIEngine {
+    /// The engines of the ranks of a world of `world_size` processes, in rank order, each to be
+    /// moved to its own thread.
+    pub fn world(world_size: usize) -> Vec<Self> {
+        assert!(world_size > 0);
+
+        // channels[from][to]
+        let (senders, receivers): (Vec<Vec<_>>, Vec<Vec<_>>) = (0..world_size)
+            .map(|_| (0..world_size).map(|_| channel::<Vec<u8>>()).unzip())
+            .unzip();
+
+        let mut receivers_to: Vec<Vec<_>> = (0..world_size).map(|_| vec![]).collect();
+        for receivers_from in receivers {
+            receivers_from
+                .into_iter()
+                .zip(receivers_to.iter_mut())
+                .for_each(|(receiver, to)| to.push(Mutex::new(receiver)));
+        }
+
+        senders
+            .into_iter()
+            .zip(receivers_to)
+            .enumerate()
+            .map(|(world_rank, (senders, receivers))| Self {
+                world_size,
+                world_rank,
+                root: Self::ROOT_RANK as usize,
+                senders,
+                receivers,
+            })
+            .collect()
+    }
+
+    /// Run `f` on every rank of a world of `world_size` processes, each on its own thread, and
+    /// return the outputs in rank order. Panics if any rank does.
+    pub fn run<R: Send>(world_size: usize, f: impl Fn(&Self) -> R + Sync) -> Vec<R> {
+        let f = &f;
+        thread::scope(|s| {
+            let handles: Vec<_> = Self::world(world_size)
+                .into_iter()
+                .map(|engine| s.spawn(move || f(&engine)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    /// Root the collectives at the process of rank `root`, which has to be the same on all ranks
+    ///
+    /// # Panics
+    /// If `root` is not a rank of the world
+    #[inline]
+    pub fn with_root(mut self, root: usize) -> Self {
+        assert!(root < self.world_size);
+        self.root = root;
+        self
+    }
+
+    #[inline]
+    fn send(&self, collective: &'static str, rank: usize, bytes: &[u8]) -> MPIResult<()> {
+        self.senders[rank]
+            .send(bytes.to_vec())
+            .map_err(|_| self.mpi_error(collective, format!("rank {rank} hung up")))
+    }
+
+    #[inline]
+    fn receive_into(
+        &self,
+        collective: &'static str,
+        rank: usize,
+        bytes: &mut [u8],
+    ) -> MPIResult<()> {
+        let message = self.receive_vec(collective, rank)?;
+        if message.len() != bytes.len() {
+            return Err(self.mpi_error(
+                collective,
+                format!(
+                    "expected a message of {} bytes from rank {rank}, got {}",
+                    bytes.len(),
+                    message.len()
+                ),
+            ));
+        }
+        bytes.copy_from_slice(&message);
+        Ok(())
+    }
+
+    #[inline]
+    fn receive_vec(&self, collective: &'static str, rank: usize) -> MPIResult<Vec<u8>> {
+        self.receivers[rank]
+            .lock()
+            .unwrap()
+            .recv()
+            .map_err(|_| self.mpi_error(collective, format!("rank {rank} hung up")))
+    }
+
+    /// The ranks other than this one
+    #[inline]
+    fn others(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.world_size).filter(|&rank| rank != self.world_rank)
+    }
+}
+
+impl MPIEngine for SimulatedMPIEngine {
+    fn gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+
+        if !self.is_root() {
+            return self.send("gather_vec", self.root, as_bytes(local_vec));
+        }
+
+        self.check_global_len("gather_vec", local_vec.len(), global_vec.len())?;
+        let local_n_bytes = size_of_val(local_vec);
+        let global_bytes = as_bytes_mut(global_vec);
+        global_bytes[self.root * local_n_bytes..(self.root + 1) * local_n_bytes]
+            .copy_from_slice(as_bytes(local_vec));
+        self.others().try_for_each(|rank| {
+            self.receive_into(
+                "gather_vec",
+                rank,
+                &mut global_bytes[rank * local_n_bytes..(rank + 1) * local_n_bytes],
+            )
+        })
+    }
+
+    fn all_gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+
+        self.check_global_len("all_gather_vec", local_vec.len(), global_vec.len())?;
+        let local_bytes = as_bytes(local_vec);
+        self.others()
+            .try_for_each(|rank| self.send("all_gather_vec", rank, local_bytes))?;
+
+        let local_n_bytes = local_bytes.len();
+        let global_bytes = as_bytes_mut(global_vec);
+        global_bytes[self.world_rank * local_n_bytes..(self.world_rank + 1) * local_n_bytes]
+            .copy_from_slice(local_bytes);
+        self.others().try_for_each(|rank| {
+            self.receive_into(
+                "all_gather_vec",
+                rank,
+                &mut global_bytes[rank * local_n_bytes..(rank + 1) * local_n_bytes],
+            )
+        })
+    }
+
+    fn scatter_vec<F: Pod>(&self, send_vec: &[F], recv_vec: &mut [F]) -> MPIResult<()> {
+        if self.is_single_process() {
+            recv_vec.copy_from_slice(send_vec);
+            return Ok(());
+        }
+
+        if !self.is_root() {
+            return self.receive_into("scatter_vec", self.root, as_bytes_mut(recv_vec));
+        }
+
+        self.check_global_len("scatter_vec", recv_vec.len(), send_vec.len())?;
+        let share = |rank: usize| rank * recv_vec.len()..(rank + 1) * recv_vec.len();
+        self.others().try_for_each(|rank| {
+            self.send("scatter_vec", rank, as_bytes(&send_vec[share(rank)]))
+        })?;
+        recv_vec.copy_from_slice(&send_vec[share(self.root)]);
+        Ok(())
+    }
+
+    fn root_broadcast_f<F: Pod>(&self, f: &mut F) -> MPIResult<()> {
+        self.root_broadcast_slice("root_broadcast_f", slice::from_mut(f))
+    }
+
+    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) -> MPIResult<()> {
+        self.root_broadcast_slice("root_broadcast_bytes", bytes)
+    }
+
+    fn all_to_all_transpose<F: Pod>(&self, row: &mut [F]) -> MPIResult<()> {
+        self.check_split_len("all_to_all_transpose", row.len())?;
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        // block i of this row goes to process i, and block i of the transposed row comes from
+        // process i, the block of this process staying in place
+        let row_bytes = as_bytes_mut(row);
+        let block_n_bytes = row_bytes.len() / self.world_size;
+        let block = |rank: usize| rank * block_n_bytes..(rank + 1) * block_n_bytes;
+        self.others().try_for_each(|rank| {
+            self.send("all_to_all_transpose", rank, &row_bytes[block(rank)])
+        })?;
+        self.others().try_for_each(|rank| {
+            self.receive_into("all_to_all_transpose", rank, &mut row_bytes[block(rank)])
+        })
+    }
+
+    fn send_to<F: Pod>(&self, rank: usize, vec: &[F]) -> MPIResult<()> {
+        self.check_peer("send_to", rank)?;
+        self.send("send_to", rank, as_bytes(vec))
+    }
+
+    fn recv_from<F: Pod>(&self, rank: usize, vec: &mut [F]) -> MPIResult<()> {
+        self.check_peer("recv_from", rank)?;
+        self.receive_into("recv_from", rank, as_bytes_mut(vec))
+    }
+
+    fn gather_varlen_vec<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        global_vec: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()> {
+        let mut gathered = vec![];
+        self.gather_varlen_vec_streaming(local_vec, |_, elems| gathered.push(elems))?;
+        if self.is_root() {
+            *global_vec = gathered;
+        }
+        Ok(())
+    }
+
+    fn gather_varlen_vec_streaming<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        mut consume: impl FnMut(usize, Vec<F>),
+    ) -> MPIResult<()> {
+        const COLLECTIVE: &str = "gather_varlen_vec_streaming";
+        let mut buffer: Vec<u8> = Vec::new();
+        local_vec.serialize_into(&mut buffer).unwrap();
+
+        if !self.is_root() {
+            return self.send(COLLECTIVE, self.root, &buffer);
+        }
+
+        for rank in 0..self.world_size {
+            let buffer = if rank == self.root {
+                mem::take(&mut buffer)
+            } else {
+                self.receive_vec(COLLECTIVE, rank)?
+            };
+            let elems = Vec::deserialize_from(buffer.as_slice()).map_err(|e| {
+                self.mpi_error(COLLECTIVE, format!("bad payload from rank {rank}: {e}"))
+            })?;
+            consume(rank, elems);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn is_single_process(&self) -> bool {
+        self.world_size == 1
+    }
+
+    #[inline(always)]
+    fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    #[inline(always)]
+    fn world_rank(&self) -> usize {
+        self.world_rank
+    }
+
+    #[inline(always)]
+    fn root_rank(&self) -> usize {
+        self.root
+    }
+
+    fn barrier(&self) -> MPIResult<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        if self.is_root() {
+            self.others()
+                .try_for_each(|rank| self.receive_into("barrier", rank, &mut []))?;
+            self.others()
+                .try_for_each(|rank| self.send("barrier", rank, &[]))
+        } else {
+            self.send("barrier", self.root, &[])?;
+            self.receive_into("barrier", self.root, &mut [])
+        }
+    }
+
+    fn abort(&self, error_code: i32) -> ! {
+        // the thread of this rank unwinds and drops its channels, failing the other ranks on
+        // their next message to or from this one, without exiting the test binary
+        panic!("rank {} aborted with code {error_code}", self.world_rank)
+    }
+
+    fn create_shared_mem(&self, _n_bytes: usize) -> MPIResult<(*mut u8, *mut ompi_win_t)> {
+        Err(self.mpi_error(
+            "create_shared_mem",
+            "ranks of a simulated world do not share memory",
+        ))
+    }
+}
+
+impl SimulatedMPIEngine {
+    fn root_broadcast_slice<F: Pod>(
+        &self,
+        collective: &'static str,
+        values: &mut [F],
+    ) -> MPIResult<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        let bytes = as_bytes_mut(values);
+        if self.is_root() {
+            self.others()
+                .try_for_each(|rank| self.send(collective, rank, bytes))
+        } else {
+            self.receive_into(collective, self.root, bytes)
+        }
+    }
+}
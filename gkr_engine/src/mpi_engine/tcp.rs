@@ -0,0 +1,426 @@
+//! A communicator over TCP, for processes that are not started together by `mpirun`, e.g., the
+//! pods or VMs of a cloud deployment.
+//!
+//! The processes rendezvous at the address of the root: the root listens on it, and every other
+//! process connects to it and announces its rank. The connections form a star around the root,
+//! which relays the all to all transposes, and point-to-point messages are only sent to or from
+//! the root. Every message is prefixed with its length, so that processes running out of step
+//! fail on the first mismatching message instead of reading garbage.
+//!
+//! The processes of a TCP communicator do not share memory, so `create_shared_mem` is not
+//! supported, and the circuit has to be loaded by every process.
+//!
+//! NOTE: the PCS and the transcript take any `MPIEngine`, but the sumcheck and the GKR prover
+//! still take an `MPIConfig`, and are to be made generic over the communicator as well.
+
+use std::{
+    io::{self, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs},
+    slice,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use arith::Pod;
+use mpi::ffi::ompi_win_t;
+use serdes::ExpSerde;
+
+use super::{
+    bytes::{as_bytes, as_bytes_mut},
+    MPIEngine,
+};
+use crate::MPIResult;
+
+#[derive(Debug)]
+pub struct TcpCommunicator {
+    world_size: usize,
+    world_rank: usize,
+    /// Indexed by rank. The root holds a connection to every other process, the other processes
+    /// only to the root.
+    peers: Vec<Option<TcpStream>>,
+}
+
+impl TcpCommunicator {
+    /// Interval between two attempts of a process to connect to the root.
+    const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Join the processes meeting at `addr`: process 0 listens on `addr` as the root, the others
+    /// connect to it, waiting up to `timeout` for the root to come up.
+    pub fn rendezvous(
+        addr: impl ToSocketAddrs,
+        world_size: usize,
+        world_rank: usize,
+        timeout: Duration,
+    ) -> io::Result<Self> {
+        if world_rank == Self::ROOT_RANK as usize {
+            Self::accept(&TcpListener::bind(addr)?, world_size)
+        } else {
+            Self::connect(addr, world_size, world_rank, timeout)
+        }
+    }
+
+    /// As the root, wait for the other `world_size - 1` processes to connect to `listener`.
+    pub fn accept(listener: &TcpListener, world_size: usize) -> io::Result<Self> {
+        let mut peers: Vec<Option<TcpStream>> = (0..world_size).map(|_| None).collect();
+
+        for _ in 1..world_size {
+            let (stream, remote) = listener.accept()?;
+            stream.set_nodelay(true)?;
+
+            let mut header = [0u64; 2];
+            read_message(&stream, as_bytes_mut(&mut header))?;
+            let [rank, remote_world_size] = header.map(|v| v as usize);
+
+            if remote_world_size != world_size {
+                return Err(invalid_data(format!(
+                    "{remote} joined a world of size {remote_world_size}, expected {world_size}"
+                )));
+            }
+            if rank == 0 || rank >= world_size || peers[rank].is_some() {
+                return Err(invalid_data(format!(
+                    "{remote} joined as rank {rank}, which is taken or out of range"
+                )));
+            }
+            peers[rank] = Some(stream);
+        }
+
+        // everyone is here, let the other processes go on
+        for stream in peers.iter().flatten() {
+            write_message(stream, &[])?;
+        }
+
+        Ok(Self {
+            world_size,
+            world_rank: Self::ROOT_RANK as usize,
+            peers,
+        })
+    }
+
+    /// Connect to the root at `root_addr` as process `world_rank`, retrying until `timeout` has
+    /// elapsed as the root may not be listening yet. Returns once all processes have joined.
+    pub fn connect(
+        root_addr: impl ToSocketAddrs,
+        world_size: usize,
+        world_rank: usize,
+        timeout: Duration,
+    ) -> io::Result<Self> {
+        assert!(world_rank > 0 && world_rank < world_size);
+
+        let deadline = Instant::now() + timeout;
+        let stream = loop {
+            match TcpStream::connect(&root_addr) {
+                Ok(stream) => break stream,
+                Err(e) if Instant::now() >= deadline => return Err(e),
+                Err(_) => sleep(Self::RETRY_INTERVAL),
+            }
+        };
+        stream.set_nodelay(true)?;
+
+        write_message(&stream, as_bytes(&[world_rank as u64, world_size as u64]))?;
+        read_message(&stream, &mut [])?;
+
+        let mut peers: Vec<Option<TcpStream>> = (0..world_size).map(|_| None).collect();
+        peers[Self::ROOT_RANK as usize] = Some(stream);
+        Ok(Self {
+            world_size,
+            world_rank,
+            peers,
+        })
+    }
+
+    #[inline]
+    fn peer(&self, rank: usize) -> &TcpStream {
+        self.peers[rank]
+            .as_ref()
+            .unwrap_or_else(|| panic!("rank {} has no connection to rank {rank}", self.world_rank))
+    }
+
+    /// Check that this process is connected to `rank`, i.e., that one of them is the root: the
+    /// root would have to take part to relay the messages between two other processes
+    fn check_connected(&self, collective: &'static str, rank: usize) -> MPIResult<()> {
+        self.check_peer(collective, rank)?;
+        if self.peers[rank].is_none() {
+            return Err(self.mpi_error(
+                collective,
+                format!(
+                    "ranks {} and {rank} are not connected, only to the root",
+                    self.world_rank
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn send(&self, collective: &'static str, rank: usize, bytes: &[u8]) -> MPIResult<()> {
+        write_message(self.peer(rank), bytes)
+            .map_err(|e| self.mpi_error(collective, format!("failed to send to rank {rank}: {e}")))
+    }
+
+    #[inline]
+    fn receive_into(
+        &self,
+        collective: &'static str,
+        rank: usize,
+        bytes: &mut [u8],
+    ) -> MPIResult<()> {
+        read_message(self.peer(rank), bytes).map_err(|e| {
+            self.mpi_error(
+                collective,
+                format!("failed to receive from rank {rank}: {e}"),
+            )
+        })
+    }
+
+    #[inline]
+    fn receive_vec(&self, collective: &'static str, rank: usize) -> MPIResult<Vec<u8>> {
+        read_message_vec(self.peer(rank)).map_err(|e| {
+            self.mpi_error(
+                collective,
+                format!("failed to receive from rank {rank}: {e}"),
+            )
+        })
+    }
+}
+
+impl MPIEngine for TcpCommunicator {
+    fn gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+
+        if !self.is_root() {
+            return self.send("gather_vec", Self::ROOT_RANK as usize, as_bytes(local_vec));
+        }
+
+        self.check_global_len("gather_vec", local_vec.len(), global_vec.len())?;
+        global_vec[..local_vec.len()].copy_from_slice(local_vec);
+        let global_bytes = as_bytes_mut(global_vec);
+        let local_n_bytes = global_bytes.len() / self.world_size;
+        (1..self.world_size).try_for_each(|rank| {
+            self.receive_into(
+                "gather_vec",
+                rank,
+                &mut global_bytes[rank * local_n_bytes..(rank + 1) * local_n_bytes],
+            )
+        })
+    }
+
+    fn scatter_vec<F: Pod>(&self, send_vec: &[F], recv_vec: &mut [F]) -> MPIResult<()> {
+        if self.is_single_process() {
+            recv_vec.copy_from_slice(send_vec);
+            return Ok(());
+        }
+
+        if !self.is_root() {
+            return self.receive_into(
+                "scatter_vec",
+                Self::ROOT_RANK as usize,
+                as_bytes_mut(recv_vec),
+            );
+        }
+
+        self.check_global_len("scatter_vec", recv_vec.len(), send_vec.len())?;
+        recv_vec.copy_from_slice(&send_vec[..recv_vec.len()]);
+        as_bytes(send_vec)
+            .chunks(size_of_val(recv_vec))
+            .enumerate()
+            .skip(1)
+            .try_for_each(|(rank, chunk)| self.send("scatter_vec", rank, chunk))
+    }
+
+    fn root_broadcast_f<F: Pod>(&self, f: &mut F) -> MPIResult<()> {
+        self.root_broadcast_slice("root_broadcast_f", slice::from_mut(f))
+    }
+
+    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) -> MPIResult<()> {
+        self.root_broadcast_slice("root_broadcast_bytes", bytes)
+    }
+
+    fn all_to_all_transpose<F: Pod>(&self, row: &mut [F]) -> MPIResult<()> {
+        const COLLECTIVE: &str = "all_to_all_transpose";
+        self.check_split_len(COLLECTIVE, row.len())?;
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        let row_bytes = as_bytes_mut(row);
+        if !self.is_root() {
+            self.send(COLLECTIVE, Self::ROOT_RANK as usize, row_bytes)?;
+            return self.receive_into(COLLECTIVE, Self::ROOT_RANK as usize, row_bytes);
+        }
+
+        // the root gathers the whole matrix, and sends back column i of blocks to process i
+        let block_n_bytes = row_bytes.len() / self.world_size;
+        let mut matrix = vec![0u8; row_bytes.len() * self.world_size];
+        matrix[..row_bytes.len()].copy_from_slice(row_bytes);
+        matrix
+            .chunks_mut(row_bytes.len())
+            .skip(1)
+            .enumerate()
+            .try_for_each(|(i, r)| self.receive_into(COLLECTIVE, i + 1, r))?;
+
+        let mut column = vec![0u8; row_bytes.len()];
+        for rank in 0..self.world_size {
+            column
+                .chunks_mut(block_n_bytes)
+                .zip(matrix.chunks(row_bytes.len()))
+                .for_each(|(block, r)| {
+                    block.copy_from_slice(&r[rank * block_n_bytes..(rank + 1) * block_n_bytes])
+                });
+
+            if rank == Self::ROOT_RANK as usize {
+                row_bytes.copy_from_slice(&column);
+            } else {
+                self.send(COLLECTIVE, rank, &column)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_to<F: Pod>(&self, rank: usize, vec: &[F]) -> MPIResult<()> {
+        self.check_connected("send_to", rank)?;
+        self.send("send_to", rank, as_bytes(vec))
+    }
+
+    fn recv_from<F: Pod>(&self, rank: usize, vec: &mut [F]) -> MPIResult<()> {
+        self.check_connected("recv_from", rank)?;
+        self.receive_into("recv_from", rank, as_bytes_mut(vec))
+    }
+
+    fn gather_varlen_vec<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        global_vec: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()> {
+        let mut gathered = vec![];
+        self.gather_varlen_vec_streaming(local_vec, |_, elems| gathered.push(elems))?;
+        if self.is_root() {
+            *global_vec = gathered;
+        }
+        Ok(())
+    }
+
+    fn gather_varlen_vec_streaming<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        mut consume: impl FnMut(usize, Vec<F>),
+    ) -> MPIResult<()> {
+        const COLLECTIVE: &str = "gather_varlen_vec_streaming";
+        let mut buffer: Vec<u8> = Vec::new();
+        local_vec.serialize_into(&mut buffer).unwrap();
+
+        if !self.is_root() {
+            return self.send(COLLECTIVE, Self::ROOT_RANK as usize, &buffer);
+        }
+
+        consume(
+            Self::ROOT_RANK as usize,
+            Vec::deserialize_from(buffer.as_slice()).unwrap(),
+        );
+        for rank in 1..self.world_size {
+            let buffer = self.receive_vec(COLLECTIVE, rank)?;
+            let elems = Vec::deserialize_from(buffer.as_slice()).map_err(|e| {
+                self.mpi_error(COLLECTIVE, format!("bad payload from rank {rank}: {e}"))
+            })?;
+            consume(rank, elems);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn is_single_process(&self) -> bool {
+        self.world_size == 1
+    }
+
+    #[inline(always)]
+    fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    #[inline(always)]
+    fn world_rank(&self) -> usize {
+        self.world_rank
+    }
+
+    fn barrier(&self) -> MPIResult<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        if self.is_root() {
+            (1..self.world_size)
+                .try_for_each(|rank| self.receive_into("barrier", rank, &mut []))?;
+            (1..self.world_size).try_for_each(|rank| self.send("barrier", rank, &[]))
+        } else {
+            self.send("barrier", Self::ROOT_RANK as usize, &[])?;
+            self.receive_into("barrier", Self::ROOT_RANK as usize, &mut [])
+        }
+    }
+
+    fn abort(&self, error_code: i32) -> ! {
+        // the other processes fail on their next message to or from this one
+        self.peers.iter().flatten().for_each(|stream| {
+            let _ = stream.shutdown(Shutdown::Both);
+        });
+        std::process::exit(error_code)
+    }
+
+    fn create_shared_mem(&self, _n_bytes: usize) -> MPIResult<(*mut u8, *mut ompi_win_t)> {
+        Err(self.mpi_error(
+            "create_shared_mem",
+            "processes of a TCP communicator do not share memory",
+        ))
+    }
+}
+
+impl TcpCommunicator {
+    fn root_broadcast_slice<F: Pod>(
+        &self,
+        collective: &'static str,
+        values: &mut [F],
+    ) -> MPIResult<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        let bytes = as_bytes_mut(values);
+        if self.is_root() {
+            (1..self.world_size).try_for_each(|rank| self.send(collective, rank, bytes))
+        } else {
+            self.receive_into(collective, Self::ROOT_RANK as usize, bytes)
+        }
+    }
+}
+
+#[inline]
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn write_message(mut stream: &TcpStream, bytes: &[u8]) -> io::Result<()> {
+    stream.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    stream.write_all(bytes)
+}
+
+fn read_message(mut stream: &TcpStream, bytes: &mut [u8]) -> io::Result<()> {
+    let mut len = [0u8; 8];
+    stream.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len) as usize;
+    if len != bytes.len() {
+        return Err(invalid_data(format!(
+            "expected a message of {} bytes, got {len}",
+            bytes.len()
+        )));
+    }
+    stream.read_exact(bytes)
+}
+
+fn read_message_vec(mut stream: &TcpStream) -> io::Result<Vec<u8>> {
+    let mut len = [0u8; 8];
+    stream.read_exact(&mut len)?;
+    let mut bytes = vec![0u8; u64::from_le_bytes(len) as usize];
+    stream.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
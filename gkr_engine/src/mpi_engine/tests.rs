@@ -1,12 +1,18 @@
-use arith::Field;
+use std::{array, fmt::Debug, net::TcpListener, thread, time::Duration};
+
+use arith::{impl_pod, Field, Fr, Pod};
 use ark_std::test_rng;
 use gf2::{GF2x128, GF2x64, GF2x8};
 use itertools::izip;
 use mersenne31::{M31Ext3, M31x16, M31};
 
-use crate::{MPIConfig, MPIEngine};
+use super::engine::payload_blocks;
+use crate::{
+    ExpErrors, Launcher, MPIChunkSize, MPIConfig, MPIEngine, MPIError, NodePlacement, NodeTopology,
+    SimulatedMPIEngine, TcpCommunicator, ThreadedEngine,
+};
 
-fn test_gather_vec_helper(mpi_config: &MPIConfig) {
+fn test_gather_vec_helper(mpi_config: &impl MPIEngine) {
     const TEST_SIZE: usize = (1 << 10) + 1;
 
     let mut local_vec = vec![M31::ZERO; TEST_SIZE];
@@ -20,7 +26,7 @@ fn test_gather_vec_helper(mpi_config: &MPIConfig) {
         vec![]
     };
 
-    mpi_config.gather_vec(&local_vec, &mut global_vec);
+    mpi_config.gather_vec(&local_vec, &mut global_vec).unwrap();
     if mpi_config.is_root() {
         for (i, v) in global_vec.iter().enumerate() {
             assert_eq!(M31::from(i as u32), *v);
@@ -28,11 +34,108 @@ fn test_gather_vec_helper(mpi_config: &MPIConfig) {
     }
 }
 
-fn test_varlen_gather_vec_helper(mpi_config: &MPIConfig) {
+fn test_all_gather_vec_helper(mpi_config: &impl MPIEngine) {
+    const TEST_SIZE: usize = (1 << 10) + 1;
+
+    let local_vec: Vec<_> = (0..TEST_SIZE)
+        .map(|i| M31::from((mpi_config.world_rank() * TEST_SIZE + i) as u32))
+        .collect();
+    let mut global_vec = vec![M31::ZERO; TEST_SIZE * mpi_config.world_size()];
+
+    // every process gets the vectors of all processes
+    mpi_config
+        .all_gather_vec(&local_vec, &mut global_vec)
+        .unwrap();
+    for (i, v) in global_vec.iter().enumerate() {
+        assert_eq!(M31::from(i as u32), *v);
+    }
+}
+
+fn test_gather_serde_vec_helper(mpi_config: &impl MPIEngine) {
+    // elements owning heap memory, of a different length on each process
+    let local_vec: Vec<Vec<usize>> = (0..3)
+        .map(|i| (0..=mpi_config.world_rank() + i).collect())
+        .collect();
+    let mut global_vec = vec![];
+
+    mpi_config
+        .gather_serde_vec(&local_vec, &mut global_vec)
+        .unwrap();
+    if mpi_config.is_root() {
+        assert_eq!(global_vec.len(), 3 * mpi_config.world_size());
+        global_vec.iter().enumerate().for_each(|(i, elem)| {
+            let (rank, j) = (i / 3, i % 3);
+            assert_eq!(*elem, (0..=rank + j).collect::<Vec<_>>());
+        });
+    }
+}
+
+fn test_root_broadcast_varlen_vec_helper(mpi_config: &impl MPIEngine) {
+    const TEST_SIZE: usize = (1 << 10) + 1;
+
+    // only the root knows the length, the other processes start with junk of another length
+    let mut vec: Vec<Vec<u64>> = if mpi_config.is_root() {
+        (0..TEST_SIZE).map(|i| vec![i as u64; i % 4]).collect()
+    } else {
+        vec![vec![u64::MAX; mpi_config.world_rank()]; mpi_config.world_rank()]
+    };
+    mpi_config.root_broadcast_varlen_vec(&mut vec).unwrap();
+
+    assert_eq!(vec.len(), TEST_SIZE);
+    vec.iter()
+        .enumerate()
+        .for_each(|(i, elem)| assert_eq!(*elem, vec![i as u64; i % 4]));
+}
+
+fn test_allreduce_vec_helper(mpi_config: &impl MPIEngine) {
+    const TEST_SIZE: usize = (1 << 10) + 1;
+
+    let local_vec: Vec<_> = (0..TEST_SIZE)
+        .map(|i| M31Ext3::from((mpi_config.world_rank() * TEST_SIZE + i) as u32))
+        .collect();
+    let sum = mpi_config.allreduce_vec(&local_vec).unwrap();
+
+    // every process gets the sum, and it agrees with the one of the root
+    assert_eq!(sum.len(), TEST_SIZE);
+    let root_sum = mpi_config.sum_vec(&local_vec).unwrap();
+    if mpi_config.is_root() {
+        assert_eq!(sum, root_sum);
+    }
+    sum.iter().enumerate().for_each(|(i, v)| {
+        let expected: M31Ext3 = (0..mpi_config.world_size())
+            .map(|rank| M31Ext3::from((rank * TEST_SIZE + i) as u32))
+            .sum();
+        assert_eq!(*v, expected);
+    });
+}
+
+fn test_reduce_scatter_vec_helper(mpi_config: &impl MPIEngine) {
+    const SEGMENT_SIZE: usize = (1 << 8) + 1;
+    let total_size = SEGMENT_SIZE * mpi_config.world_size();
+
+    let local_vec: Vec<_> = (0..total_size)
+        .map(|i| M31Ext3::from((mpi_config.world_rank() * total_size + i) as u32))
+        .collect();
+    let segment = mpi_config.reduce_scatter_vec(&local_vec).unwrap();
+
+    // each process gets its own segment of the sum
+    assert_eq!(segment.len(), SEGMENT_SIZE);
+    let segment_start = mpi_config.world_rank() * SEGMENT_SIZE;
+    segment.iter().enumerate().for_each(|(i, v)| {
+        let expected: M31Ext3 = (0..mpi_config.world_size())
+            .map(|rank| M31Ext3::from((rank * total_size + segment_start + i) as u32))
+            .sum();
+        assert_eq!(*v, expected);
+    });
+}
+
+fn test_varlen_gather_vec_helper(mpi_config: &impl MPIEngine) {
     let msg: Vec<_> = (0..=mpi_config.world_rank()).collect();
     let mut global_elems: Vec<Vec<usize>> = Vec::new();
 
-    mpi_config.gather_varlen_vec(&msg, &mut global_elems);
+    mpi_config
+        .gather_varlen_vec(&msg, &mut global_elems)
+        .unwrap();
 
     dbg!(&global_elems);
 
@@ -43,7 +146,45 @@ fn test_varlen_gather_vec_helper(mpi_config: &MPIConfig) {
     });
 }
 
-fn test_all_to_all_transpose_helper<F: Field>(mpi_config: &MPIConfig) {
+fn test_all_gather_varlen_vec_helper(mpi_config: &impl MPIEngine) {
+    let msg: Vec<_> = (0..=mpi_config.world_rank()).collect();
+    let mut global_elems: Vec<Vec<usize>> = Vec::new();
+
+    mpi_config
+        .all_gather_varlen_vec(&msg, &mut global_elems)
+        .unwrap();
+
+    assert_eq!(global_elems.len(), mpi_config.world_size());
+    global_elems
+        .iter()
+        .enumerate()
+        .for_each(|(i, elems)| assert_eq!(*elems, (0..=i).collect::<Vec<_>>()));
+}
+
+fn test_streaming_varlen_gather_vec_helper(mpi_config: &impl MPIEngine) {
+    // payloads crossing the chunk size are received in several messages
+    let msg: Vec<_> = (0..=mpi_config.world_rank() * MPIConfig::CHUNK_SIZE / 4).collect();
+    let mut next_rank = 0;
+
+    mpi_config
+        .gather_varlen_vec_streaming(&msg, |rank, elems: Vec<usize>| {
+            assert!(mpi_config.is_root());
+            assert_eq!(rank, next_rank);
+            assert_eq!(elems.len(), rank * MPIConfig::CHUNK_SIZE / 4 + 1);
+            elems
+                .iter()
+                .enumerate()
+                .for_each(|(i, e)| assert_eq!(i, *e));
+            next_rank += 1;
+        })
+        .unwrap();
+
+    if mpi_config.is_root() {
+        assert_eq!(next_rank, mpi_config.world_size());
+    }
+}
+
+fn test_all_to_all_transpose_helper<F: Field>(mpi_config: &impl MPIEngine) {
     const TEST_MATRIX_LEN: usize = 1 << 23;
 
     dbg!(F::NAME);
@@ -62,7 +203,7 @@ fn test_all_to_all_transpose_helper<F: Field>(mpi_config: &MPIConfig) {
 
     dbg!(local_share_starts, local_length);
 
-    mpi_config.all_to_all_transpose(&mut local_shares);
+    mpi_config.all_to_all_transpose(&mut local_shares).unwrap();
 
     let transpose_slice_len = local_length / mpi_config.world_size();
     izip!(
@@ -80,7 +221,7 @@ fn test_all_to_all_transpose_helper<F: Field>(mpi_config: &MPIConfig) {
     });
 }
 
-fn test_scatter_vec_helper(mpi_config: &MPIConfig) {
+fn test_scatter_vec_helper(mpi_config: &impl MPIEngine) {
     const TEST_SIZE: usize = MPIConfig::CHUNK_SIZE + 1;
 
     let send_vec: Vec<_> = if mpi_config.is_root() {
@@ -99,7 +240,7 @@ fn test_scatter_vec_helper(mpi_config: &MPIConfig) {
 
     let mut local_vec = vec![0u8; TEST_SIZE];
 
-    mpi_config.scatter_vec(&send_vec, &mut local_vec);
+    mpi_config.scatter_vec(&send_vec, &mut local_vec).unwrap();
 
     let expected = local_vec
         .iter()
@@ -108,6 +249,234 @@ fn test_scatter_vec_helper(mpi_config: &MPIConfig) {
     assert!(expected);
 }
 
+fn test_streaming_scatter_vec_helper(mpi_config: &impl MPIEngine) {
+    const N_CHUNKS: usize = 5;
+    const CHUNK_LEN: usize = 1 << 10;
+
+    let value =
+        |chunk: usize, rank: usize, k: usize| (((chunk << 8) + rank) << 16) as u64 + k as u64;
+    let mut produced = vec![];
+    let mut consumed = vec![];
+    mpi_config
+        .scatter_vec_streaming(
+            N_CHUNKS,
+            CHUNK_LEN,
+            |i| {
+                produced.push(i);
+                (0..mpi_config.world_size())
+                    .flat_map(|rank| (0..CHUNK_LEN).map(move |k| value(i, rank, k)))
+                    .collect()
+            },
+            |i, share: &[u64]| {
+                let rank = mpi_config.world_rank();
+                assert!(share
+                    .iter()
+                    .enumerate()
+                    .all(|(k, v)| *v == value(i, rank, k)));
+                consumed.push(i);
+            },
+        )
+        .unwrap();
+
+    // every chunk is consumed in order, and only the root produces them
+    assert_eq!(consumed, (0..N_CHUNKS).collect::<Vec<_>>());
+    if mpi_config.is_root() {
+        assert_eq!(produced, consumed);
+    } else {
+        assert!(produced.is_empty());
+    }
+}
+
+fn test_send_recv_helper(mpi_config: &impl MPIEngine) {
+    const LEN: usize = 8;
+    let rank = mpi_config.world_rank();
+
+    // a pipeline along the ranks, each adding its share to the partial sums of the previous one
+    let mut partial_sums = [0usize; LEN];
+    let share: [usize; LEN] = if rank > 0 {
+        mpi_config
+            .irecv_from(rank - 1, &mut partial_sums, || array::from_fn(|i| rank * i))
+            .unwrap()
+    } else {
+        [0; LEN]
+    };
+    izip!(&mut partial_sums, share).for_each(|(sum, v)| *sum += v);
+    if rank + 1 < mpi_config.world_size() {
+        mpi_config.isend_to(rank + 1, &partial_sums, || ()).unwrap();
+    }
+    let expected: [usize; LEN] = array::from_fn(|i| rank * (rank + 1) / 2 * i);
+    assert_eq!(partial_sums, expected);
+
+    // and back to the first rank, blocking
+    if rank + 1 < mpi_config.world_size() {
+        mpi_config.recv_from(rank + 1, &mut partial_sums).unwrap();
+    }
+    if rank > 0 {
+        mpi_config.send_to(rank - 1, &partial_sums).unwrap();
+    }
+    let last = mpi_config.world_size() - 1;
+    let expected: [usize; LEN] = array::from_fn(|i| last * (last + 1) / 2 * i);
+    assert_eq!(partial_sums, expected);
+
+    // a process does not send to itself
+    assert_eq!(
+        mpi_config
+            .send_to(rank, &partial_sums)
+            .unwrap_err()
+            .collective,
+        "send_to"
+    );
+    mpi_config.barrier().unwrap();
+}
+
+fn test_sync_result_helper(mpi_config: &impl MPIEngine) {
+    // all processes succeed
+    let res = mpi_config.sync_result(Ok(mpi_config.world_rank()));
+    assert_eq!(res.unwrap(), mpi_config.world_rank());
+
+    // the last process fails, everyone learns about it
+    let failing_rank = mpi_config.world_size() - 1;
+    let local = if mpi_config.world_rank() == failing_rank {
+        Err(ExpErrors::WitnessError("bad shard".to_owned()))
+    } else {
+        Ok(())
+    };
+    match mpi_config.sync_result(local) {
+        Err(ExpErrors::WitnessError(reason)) => {
+            assert_eq!(mpi_config.world_rank(), failing_rank);
+            assert_eq!(reason, "bad shard");
+        }
+        Err(ExpErrors::MPIAbort { rank, reason }) => {
+            assert_ne!(mpi_config.world_rank(), failing_rank);
+            assert_eq!(rank, failing_rank);
+            assert_eq!(reason, "witness error: bad shard");
+        }
+        _ => panic!("failure on rank {failing_rank} was not propagated"),
+    }
+}
+
+#[cfg(feature = "cuda-mpi")]
+fn test_device_collectives_helper(mpi_config: &MPIConfig) {
+    use crate::{DeviceSlice, DeviceSliceMut};
+
+    // host memory goes through the device collectives as well
+    const TEST_SIZE: usize = 1 << 10;
+    let (rank, world_size) = (mpi_config.world_rank(), mpi_config.world_size());
+
+    let local_vec = (0..TEST_SIZE)
+        .map(|i| M31::from((rank * TEST_SIZE + i) as u32))
+        .collect::<Vec<_>>();
+    let mut global_vec = vec![M31::ZERO; TEST_SIZE * world_size];
+    unsafe {
+        mpi_config.gather_device_vec(
+            &DeviceSlice::from_raw_parts(local_vec.as_ptr(), local_vec.len()),
+            &mut DeviceSliceMut::from_raw_parts(global_vec.as_mut_ptr(), global_vec.len()),
+        );
+    }
+    if mpi_config.is_root() {
+        assert!(izip!(0.., &global_vec).all(|(i, v)| *v == M31::from(i as u32)));
+    }
+
+    let mut recv_vec = vec![M31::ZERO; TEST_SIZE];
+    unsafe {
+        mpi_config.scatter_device_vec(
+            &DeviceSlice::from_raw_parts(global_vec.as_ptr(), global_vec.len()),
+            &mut DeviceSliceMut::from_raw_parts(recv_vec.as_mut_ptr(), recv_vec.len()),
+        );
+    }
+    assert_eq!(recv_vec, local_vec);
+
+    let mut broadcast_vec = local_vec.clone();
+    unsafe {
+        mpi_config.root_broadcast_device_vec(&mut DeviceSliceMut::from_raw_parts(
+            broadcast_vec.as_mut_ptr(),
+            broadcast_vec.len(),
+        ));
+    }
+    assert!(izip!(0.., &broadcast_vec).all(|(i, v)| *v == M31::from(i as u32)));
+}
+
+/// Round trip `values`, `2 * world_size` values per process, through the collectives sending
+/// values as their memory
+fn test_pod_collectives_helper<T: Pod + Debug + PartialEq>(
+    mpi_config: &impl MPIEngine,
+    values: &[T],
+) {
+    assert_eq!(size_of::<T>(), T::FIELDS_SIZE);
+
+    let world_size = mpi_config.world_size();
+    let rank = mpi_config.world_rank();
+    let local_len = 2 * world_size;
+    assert_eq!(values.len(), local_len * world_size);
+    let local_vec = &values[rank * local_len..(rank + 1) * local_len];
+
+    let mut global_vec = if mpi_config.is_root() {
+        vec![values[values.len() - 1]; values.len()]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(local_vec, &mut global_vec).unwrap();
+    if mpi_config.is_root() {
+        assert_eq!(global_vec, values);
+    }
+
+    let send_vec = if mpi_config.is_root() {
+        values.to_vec()
+    } else {
+        vec![]
+    };
+    let mut recv_vec = vec![values[values.len() - 1]; local_len];
+    mpi_config.scatter_vec(&send_vec, &mut recv_vec).unwrap();
+    assert_eq!(recv_vec, local_vec);
+
+    let mut f = if mpi_config.is_root() {
+        values[1]
+    } else {
+        values[0]
+    };
+    mpi_config.root_broadcast_f(&mut f).unwrap();
+    assert_eq!(f, values[1]);
+
+    // the process receives its 2 columns of the row of each process
+    let mut row = local_vec.to_vec();
+    mpi_config.all_to_all_transpose(&mut row).unwrap();
+    izip!(values.chunks(local_len), row.chunks(2))
+        .for_each(|(c, r)| assert_eq!(r, &c[2 * rank..2 * rank + 2]));
+}
+
+/// A share of a claim of a process, with fields of different sizes and no padding in between
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+struct ClaimShare {
+    rank: u32,
+    lane: u16,
+    flags: [u8; 2],
+    value: M31Ext3,
+}
+
+impl_pod!(ClaimShare {
+    rank: u32,
+    lane: u16,
+    flags: [u8; 2],
+    value: M31Ext3,
+});
+
+fn test_node_placement_helper(mpi_config: &impl MPIEngine) {
+    let rank = mpi_config.world_rank();
+    let ranks_on_my_node = mpi_config.ranks_on_my_node();
+    assert!(ranks_on_my_node.contains(&rank));
+    assert!(ranks_on_my_node.windows(2).all(|w| w[0] < w[1]));
+    assert!(ranks_on_my_node
+        .iter()
+        .all(|&r| mpi_config.is_intra_node(r)));
+
+    let n_on_my_node = (0..mpi_config.world_size())
+        .filter(|&r| mpi_config.node_of_rank(r) == mpi_config.node_of_rank(rank))
+        .count();
+    assert_eq!(n_on_my_node, ranks_on_my_node.len());
+    assert!(mpi_config.n_nodes() >= 1 && mpi_config.n_nodes() <= mpi_config.world_size());
+}
+
 #[test]
 fn test_mpi_engine() {
     let universe = MPIConfig::init().unwrap();
@@ -115,6 +484,11 @@ fn test_mpi_engine() {
     let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
 
     test_gather_vec_helper(&mpi_config);
+    test_all_gather_vec_helper(&mpi_config);
+    test_gather_serde_vec_helper(&mpi_config);
+    test_root_broadcast_varlen_vec_helper(&mpi_config);
+    test_allreduce_vec_helper(&mpi_config);
+    test_reduce_scatter_vec_helper(&mpi_config);
 
     test_all_to_all_transpose_helper::<GF2x128>(&mpi_config);
     test_all_to_all_transpose_helper::<GF2x64>(&mpi_config);
@@ -125,6 +499,506 @@ fn test_mpi_engine() {
     test_all_to_all_transpose_helper::<M31Ext3>(&mpi_config);
 
     test_varlen_gather_vec_helper(&mpi_config);
+    test_all_gather_varlen_vec_helper(&mpi_config);
+    test_streaming_varlen_gather_vec_helper(&mpi_config);
 
     test_scatter_vec_helper(&mpi_config);
+    test_streaming_scatter_vec_helper(&mpi_config);
+
+    test_sync_result_helper(&mpi_config);
+    test_send_recv_helper(&mpi_config);
+
+    #[cfg(feature = "cuda-mpi")]
+    test_device_collectives_helper(&mpi_config);
+
+    // the chunked collectives with chunks smaller than the payloads, and with auto-tuned chunks
+    for chunk_size in [MPIChunkSize::Fixed(1 << 12), MPIChunkSize::Auto] {
+        let mpi_config = mpi_config.clone().with_chunk_size(chunk_size);
+        test_gather_vec_helper(&mpi_config);
+        test_all_gather_vec_helper(&mpi_config);
+        test_scatter_vec_helper(&mpi_config);
+        test_streaming_varlen_gather_vec_helper(&mpi_config);
+    }
+
+    // the collectives rooted at the last process
+    let last_rooted_config = mpi_config.clone().with_root(mpi_config.world_size() - 1);
+    test_gather_vec_helper(&last_rooted_config);
+    test_root_broadcast_varlen_vec_helper(&last_rooted_config);
+    test_streaming_varlen_gather_vec_helper(&last_rooted_config);
+    test_scatter_vec_helper(&last_rooted_config);
+    test_sync_result_helper(&last_rooted_config);
+
+    // the same collectives through the node leaders, if the processes span several nodes
+    let node_topology = NodeTopology::split(&world);
+    let mpi_config = mpi_config.with_node_topology(node_topology.as_ref());
+    test_gather_vec_helper(&mpi_config);
+    test_sync_result_helper(&mpi_config);
+
+    // the placement agrees with the node topology, and every node is a block of whole processes
+    let node_placement = NodePlacement::query(&world);
+    if let Some(topology) = &node_topology {
+        assert_eq!(topology.placement(), &node_placement);
+    }
+    let placed_config = mpi_config
+        .clone()
+        .with_node_placement(Some(&node_placement));
+    test_node_placement_helper(&placed_config);
+
+    // the same collectives over the ranks relabeled node after node
+    let optimized_world = MPIConfig::optimize_topology(&world);
+    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&optimized_world));
+    test_gather_vec_helper(&mpi_config);
+    test_all_to_all_transpose_helper::<M31>(&mpi_config);
+    test_scatter_vec_helper(&mpi_config);
+
+    // the collectives within halves of the world, in reverse order of the ranks
+    let rank = mpi_config.world_rank();
+    let sub_world = mpi_config.split((rank % 2) as i32, -(rank as i32));
+    let sub_config = sub_world.config();
+    let parity_size = (mpi_config.world_size() + 1 - rank % 2) / 2;
+    assert_eq!(sub_config.world_size(), parity_size);
+    assert_eq!(sub_config.world_rank(), parity_size - 1 - rank / 2);
+    test_gather_vec_helper(&sub_config);
+    test_root_broadcast_varlen_vec_helper(&sub_config);
+    test_allreduce_vec_helper(&sub_config);
+    test_all_to_all_transpose_helper::<M31>(&sub_config);
+    test_sync_result_helper(&sub_config);
+}
+
+#[test]
+fn test_mpi_chunk_size() {
+    assert_eq!("auto".parse::<MPIChunkSize>().unwrap(), MPIChunkSize::Auto);
+    assert_eq!(
+        "4096".parse::<MPIChunkSize>().unwrap(),
+        MPIChunkSize::Fixed(4096)
+    );
+    assert!("0".parse::<MPIChunkSize>().is_err());
+    assert!("1MB".parse::<MPIChunkSize>().is_err());
+
+    // a payload within the limit is sent at once, a larger one in as few even chunks as possible
+    let mpi_config = MPIConfig::verifier_new(8).with_chunk_size(MPIChunkSize::Auto);
+    let max_chunk_size = MPIConfig::COMM_LIMIT / 8;
+    assert_eq!(mpi_config.chunk_size_for(0), 1);
+    assert_eq!(mpi_config.chunk_size_for(1000), 1000);
+    assert_eq!(mpi_config.chunk_size_for(max_chunk_size), max_chunk_size);
+    assert_eq!(
+        mpi_config.chunk_size_for(max_chunk_size + 2),
+        max_chunk_size / 2 + 1
+    );
+}
+
+#[test]
+fn test_payload_blocks() {
+    // a payload within the limit is a single block, a larger one has 64-bit displacements
+    assert_eq!(payload_blocks(0), (vec![], vec![]));
+    assert_eq!(payload_blocks(1000), (vec![1000], vec![0]));
+
+    let limit = MPIConfig::COMM_LIMIT;
+    let (block_lengths, displacements) = payload_blocks(4 * limit + 3);
+    assert_eq!(
+        block_lengths,
+        [
+            limit as mpi::Count,
+            limit as mpi::Count,
+            limit as mpi::Count,
+            limit as mpi::Count,
+            3
+        ]
+    );
+    assert_eq!(
+        displacements,
+        (0..5)
+            .map(|i| (i * limit) as mpi::Address)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_node_placement() {
+    // ranks 0, 2 and 3 on the node of rank 0, ranks 1 and 4 on that of rank 1, rank 5 alone
+    let placement = NodePlacement::from_leaders(&[0, 1, 0, 0, 1, 5]);
+    assert_eq!(placement.n_nodes(), 3);
+    assert_eq!(
+        (0..6)
+            .map(|rank| placement.node_of(rank))
+            .collect::<Vec<_>>(),
+        [0, 1, 0, 0, 1, 2]
+    );
+    assert_eq!(placement.ranks_on_node(0), [0, 2, 3]);
+    assert_eq!(placement.ranks_on_node(1), [1, 4]);
+    assert_eq!(placement.ranks_on_node(2), [5]);
+
+    // without a placement, every process is on a node of its own
+    let mpi_config = MPIConfig::verifier_new(4);
+    assert_eq!(mpi_config.n_nodes(), 4);
+    assert_eq!(mpi_config.ranks_on_my_node(), [0]);
+    assert!(mpi_config.is_intra_node(0));
+    assert!(!mpi_config.is_intra_node(1));
+
+    let mpi_config = mpi_config.with_node_placement(Some(&placement));
+    assert_eq!(mpi_config.n_nodes(), 3);
+    assert_eq!(mpi_config.ranks_on_my_node(), [0, 2, 3]);
+    assert!(mpi_config.is_intra_node(3));
+    assert!(!mpi_config.is_intra_node(4));
+}
+
+#[test]
+fn test_launcher_detection() {
+    let env_of = |vars: &'static [(&'static str, &'static str)]| {
+        move |var: &str| {
+            vars.iter()
+                .find(|(key, _)| *key == var)
+                .map(|(_, value)| value.to_string())
+        }
+    };
+
+    let mpirun = env_of(&[
+        ("OMPI_COMM_WORLD_SIZE", "8"),
+        ("OMPI_COMM_WORLD_LOCAL_SIZE", "4"),
+        ("SLURM_STEP_ID", "0"),
+        ("SLURM_PROCID", "0"),
+    ]);
+    assert_eq!(Launcher::detect(mpirun), Launcher::MpiRun);
+    assert_eq!(Launcher::MpiRun.threads_per_process(mpirun, 64), 16);
+
+    let srun = env_of(&[
+        ("SLURM_STEP_ID", "0"),
+        ("SLURM_PROCID", "3"),
+        ("SLURM_CPUS_PER_TASK", "12"),
+    ]);
+    assert_eq!(Launcher::detect(srun), Launcher::Slurm);
+    assert_eq!(Launcher::Slurm.threads_per_process(srun, 64), 12);
+
+    // a SLURM batch script starting the binary without srun runs it standalone
+    let batch = env_of(&[("SLURM_JOB_ID", "42")]);
+    assert_eq!(Launcher::detect(batch), Launcher::Standalone);
+    assert_eq!(Launcher::Standalone.threads_per_process(batch, 64), 64);
+
+    // more local processes than cores still leaves a thread to each, and the thread count set
+    // by the user wins
+    let crowded = env_of(&[("PMI_SIZE", "128"), ("MPI_LOCALNRANKS", "128")]);
+    assert_eq!(Launcher::MpiRun.threads_per_process(crowded, 64), 1);
+    let pinned = env_of(&[("PMI_SIZE", "2"), ("RAYON_NUM_THREADS", "5")]);
+    assert_eq!(Launcher::MpiRun.threads_per_process(pinned, 64), 5);
+}
+
+#[test]
+fn test_comm_stats() {
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let local_vec = vec![M31::ONE; 16];
+    let mut global_vec = vec![];
+
+    mpi_config.gather_vec(&local_vec, &mut global_vec).unwrap();
+    mpi_config.gather_vec(&local_vec, &mut global_vec).unwrap();
+    // the gather of the non-blocking gather is not recorded on its own
+    mpi_config
+        .igather_vec(&local_vec, &mut global_vec, || ())
+        .unwrap();
+    mpi_config.allreduce_vec(&local_vec).unwrap();
+    // the clones of the engine record into the same stats
+    mpi_config.clone().barrier().unwrap();
+
+    let stats = mpi_config.comm_stats();
+    assert_eq!(stats.collectives.len(), 4);
+    assert_eq!(stats.collectives["gather_vec"].n_calls, 2);
+    assert_eq!(stats.collectives["gather_vec"].n_bytes, 2 * 16 * 4);
+    assert_eq!(stats.collectives["igather_vec"].n_calls, 1);
+    assert_eq!(stats.collectives["allreduce_vec"].n_calls, 1);
+    assert_eq!(stats.collectives["barrier"].n_calls, 1);
+    assert_eq!(stats.total().n_calls, 5);
+    assert!(stats.to_string().contains("gather_vec"));
+
+    mpi_config.reset_comm_stats();
+    assert_eq!(mpi_config.comm_stats(), Default::default());
+}
+
+#[test]
+fn test_tcp_communicator() {
+    const WORLD_SIZE: usize = 4;
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let run = |comm: TcpCommunicator| {
+        test_gather_vec_helper(&comm);
+        test_gather_serde_vec_helper(&comm);
+        test_root_broadcast_varlen_vec_helper(&comm);
+        test_allreduce_vec_helper(&comm);
+        test_reduce_scatter_vec_helper(&comm);
+        test_all_to_all_transpose_helper::<M31>(&comm);
+        test_all_gather_vec_helper(&comm);
+        test_varlen_gather_vec_helper(&comm);
+        test_all_gather_varlen_vec_helper(&comm);
+        test_streaming_varlen_gather_vec_helper(&comm);
+        test_scatter_vec_helper(&comm);
+        test_streaming_scatter_vec_helper(&comm);
+        test_sync_result_helper(&comm);
+
+        // point-to-point messages go to and from the root only
+        let rank = comm.world_rank();
+        if comm.is_root() {
+            for peer in 1..WORLD_SIZE {
+                let mut received = [0];
+                comm.recv_from(peer, &mut received).unwrap();
+                assert_eq!(received, [peer]);
+            }
+        } else {
+            comm.send_to(0, &[rank]).unwrap();
+            let peer = rank % (WORLD_SIZE - 1) + 1;
+            assert!(comm.send_to(peer, &[rank]).is_err());
+        }
+        comm.barrier().unwrap();
+    };
+
+    thread::scope(|s| {
+        for rank in 1..WORLD_SIZE {
+            s.spawn(
+                move || run(TcpCommunicator::connect(addr, WORLD_SIZE, rank, TIMEOUT).unwrap()),
+            );
+        }
+        run(TcpCommunicator::accept(&listener, WORLD_SIZE).unwrap());
+    });
+}
+
+#[test]
+fn test_gather_padded_vec() {
+    for world_size in [1, 2, 3, 5, 6] {
+        SimulatedMPIEngine::run(world_size, |engine| {
+            let local_vec = vec![engine.world_rank() as u32; 2];
+            let mut global_vec = vec![0u32; 2 * engine.n_parties()];
+            engine
+                .gather_padded_vec(&local_vec, &mut global_vec)
+                .unwrap();
+
+            if engine.is_root() {
+                let expected = (0..engine.n_parties())
+                    .flat_map(|party| [engine.mirror_rank(party) as u32; 2])
+                    .collect::<Vec<_>>();
+                assert_eq!(global_vec, expected);
+            }
+            let hosted = (0..engine.n_parties()).filter(|&party| {
+                party >= world_size && engine.mirror_rank(party) == engine.world_rank()
+            });
+            assert_eq!(engine.virtual_party(), hosted.max());
+        });
+    }
+}
+
+#[test]
+fn test_simulated_mpi_engine() {
+    for world_size in [1, 2, 4] {
+        SimulatedMPIEngine::run(world_size, |engine| {
+            test_gather_vec_helper(engine);
+            test_gather_serde_vec_helper(engine);
+            test_root_broadcast_varlen_vec_helper(engine);
+            test_allreduce_vec_helper(engine);
+            test_reduce_scatter_vec_helper(engine);
+            test_all_to_all_transpose_helper::<M31>(engine);
+            test_all_gather_vec_helper(engine);
+            test_varlen_gather_vec_helper(engine);
+            test_all_gather_varlen_vec_helper(engine);
+            test_streaming_varlen_gather_vec_helper(engine);
+            test_scatter_vec_helper(engine);
+            test_streaming_scatter_vec_helper(engine);
+            test_sync_result_helper(engine);
+            test_send_recv_helper(engine);
+            engine.barrier().unwrap();
+        });
+    }
+}
+
+#[test]
+fn test_threaded_engine() {
+    for world_size in [1, 2, 4] {
+        ThreadedEngine::run(world_size, |engine| {
+            test_gather_vec_helper(engine);
+            test_gather_serde_vec_helper(engine);
+            test_root_broadcast_varlen_vec_helper(engine);
+            test_allreduce_vec_helper(engine);
+            test_reduce_scatter_vec_helper(engine);
+            test_all_to_all_transpose_helper::<M31>(engine);
+            test_all_gather_vec_helper(engine);
+            test_varlen_gather_vec_helper(engine);
+            test_all_gather_varlen_vec_helper(engine);
+            test_streaming_varlen_gather_vec_helper(engine);
+            test_scatter_vec_helper(engine);
+            test_streaming_scatter_vec_helper(engine);
+            test_sync_result_helper(engine);
+            test_send_recv_helper(engine);
+            engine.barrier().unwrap();
+        });
+    }
+
+    // a rank aborting breaks the barrier, the other rank fails instead of hanging
+    let mut engines = ThreadedEngine::world(2);
+    let other = engines.pop().unwrap();
+    let root = engines.pop().unwrap();
+    thread::scope(|s| {
+        let aborted = s.spawn(move || root.abort(1));
+        let waiting = s.spawn(move || other.barrier());
+        assert!(aborted.join().is_err());
+        let error = waiting.join().unwrap().unwrap_err();
+        assert_eq!((error.rank, error.collective), (1, "barrier"));
+    });
+}
+
+#[test]
+fn test_collectives_with_another_root() {
+    const WORLD_SIZE: usize = 4;
+
+    for root in 1..WORLD_SIZE {
+        thread::scope(|s| {
+            for engine in SimulatedMPIEngine::world(WORLD_SIZE) {
+                s.spawn(move || {
+                    let engine = engine.with_root(root);
+                    assert_eq!(engine.is_root(), engine.world_rank() == root);
+
+                    test_gather_vec_helper(&engine);
+                    test_gather_serde_vec_helper(&engine);
+                    test_root_broadcast_varlen_vec_helper(&engine);
+                    test_allreduce_vec_helper(&engine);
+                    test_varlen_gather_vec_helper(&engine);
+                    test_streaming_varlen_gather_vec_helper(&engine);
+                    test_scatter_vec_helper(&engine);
+                    test_streaming_scatter_vec_helper(&engine);
+                    test_sync_result_helper(&engine);
+                    engine.barrier().unwrap();
+                });
+            }
+        });
+    }
+}
+
+#[test]
+fn test_mpi_error() {
+    // the root leaves the world, the broadcast of the other rank fails instead of hanging
+    let results = SimulatedMPIEngine::run(2, |engine| {
+        if engine.is_root() {
+            return None;
+        }
+        let mut value = 0usize;
+        Some(engine.root_broadcast_f(&mut value))
+    });
+    let error = results[1].as_ref().unwrap().as_ref().unwrap_err();
+    assert_eq!(error.rank, 1);
+    assert_eq!(error.collective, "root_broadcast_f");
+    assert_eq!(
+        error.to_string(),
+        "MPI rank 1 failed in root_broadcast_f: rank 0 hung up"
+    );
+
+    // the ranks of a simulated world do not share memory, and the error converts into the
+    // errors of the prover
+    let engine = SimulatedMPIEngine::world(1).pop().unwrap();
+    let error = ExpErrors::from(engine.create_shared_mem(8).unwrap_err());
+    assert!(matches!(
+        error,
+        ExpErrors::MPIError(MPIError {
+            rank: 0,
+            collective: "create_shared_mem",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_mpi_size_checks() {
+    let mpi_config = MPIConfig::verifier_new(6);
+    assert!(mpi_config.check_global_len("gather_vec", 4, 24).is_ok());
+    assert_eq!(
+        mpi_config
+            .check_global_len("gather_vec", 4, 20)
+            .unwrap_err()
+            .to_string(),
+        "MPI rank 0 failed in gather_vec: expected a buffer of 24 elements, got 20"
+    );
+    // the product of the sizes overflows rather than wrapping around to a matching length
+    assert!(mpi_config
+        .check_global_len(
+            "gather_vec",
+            usize::MAX / 3,
+            (usize::MAX / 3).wrapping_mul(6)
+        )
+        .is_err());
+    assert!(mpi_config
+        .check_split_len("all_to_all_transpose", 12)
+        .is_ok());
+    assert!(mpi_config
+        .check_split_len("all_to_all_transpose", 8)
+        .is_err());
+
+    // six processes are padded with two virtual parties, mirroring ranks 2 and 3
+    assert_eq!(mpi_config.n_parties(), 8);
+    assert_eq!(mpi_config.n_mpi_vars().unwrap(), 3);
+    assert_eq!(mpi_config.mirror_rank(5), 5);
+    assert_eq!(mpi_config.mirror_rank(6), 2);
+    assert_eq!(mpi_config.mirror_rank(7), 3);
+    assert_eq!(mpi_config.virtual_party(), None);
+    assert_eq!(MPIConfig::verifier_new(8).n_mpi_vars().unwrap(), 3);
+
+    // a scatter of a buffer of the wrong length fails on the root, and on the other ranks once
+    // the root leaves the world
+    let results = SimulatedMPIEngine::run(2, |engine| {
+        let send_vec = vec![0u32; if engine.is_root() { 3 } else { 0 }];
+        let mut recv_vec = [0u32; 2];
+        engine.scatter_vec(&send_vec, &mut recv_vec)
+    });
+    assert_eq!(results[0].as_ref().unwrap_err().collective, "scatter_vec");
+    assert!(results[1].is_err());
+}
+
+#[test]
+fn test_pod_collectives() {
+    fn all_types(engine: &impl MPIEngine) {
+        let n = 2 * engine.world_size() * engine.world_size();
+        let mut rng = test_rng();
+
+        test_pod_collectives_helper(engine, &(0..n).map(|i| i as u8).collect::<Vec<_>>());
+        test_pod_collectives_helper(engine, &(0..n).map(|i| i % 3 == 0).collect::<Vec<_>>());
+        test_pod_collectives_helper(engine, &(0..n).map(|i| (i << 20) | i).collect::<Vec<_>>());
+        test_pod_collectives_helper(
+            engine,
+            &(0..n).map(|i| [i as u16, !i as u16, 3]).collect::<Vec<_>>(),
+        );
+        test_pod_collectives_helper(
+            engine,
+            &(0..n).map(|i| (i as u64, [i as u8; 8])).collect::<Vec<_>>(),
+        );
+        test_pod_collectives_helper(
+            engine,
+            &(0..n)
+                .map(|i| ClaimShare {
+                    rank: i as u32,
+                    lane: !i as u16,
+                    flags: [i as u8, 1],
+                    value: M31Ext3::random_unsafe(&mut rng),
+                })
+                .collect::<Vec<_>>(),
+        );
+        test_pod_collectives_helper(
+            engine,
+            &(0..n)
+                .map(|_| M31x16::random_unsafe(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        test_pod_collectives_helper(
+            engine,
+            &(0..n)
+                .map(|_| GF2x128::random_unsafe(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        test_pod_collectives_helper(
+            engine,
+            &(0..n)
+                .map(|_| Fr::random_unsafe(&mut rng))
+                .collect::<Vec<_>>(),
+        );
+        engine.barrier().unwrap();
+    }
+
+    for world_size in [1, 2, 4] {
+        ThreadedEngine::run(world_size, |engine| all_types(engine));
+        SimulatedMPIEngine::run(world_size, |engine| all_types(engine));
+    }
 }
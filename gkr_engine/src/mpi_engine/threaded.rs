@@ -0,0 +1,458 @@
+//! A world of processes run as the threads of a single process, for data-parallel proving on
+//! one machine without an MPI runtime.
+//!
+//! Unlike `SimulatedMPIEngine`, the collectives do not send messages: every rank has a slot in
+//! memory shared by the whole world, and a collective is a write of the own slot, a barrier, the
+//! reads of the slots of the other ranks, and a second barrier before the slots are written
+//! again. The checks of the buffer sizes happen after the first barrier, so that a rank failing
+//! a check still takes part in the collective and the others do not hang on it. A rank that
+//! panics breaks the barrier, and the ranks waiting on it fail instead of hanging.
+//!
+//! Each rank runs on a thread of its own rather than on the rayon pool, the barriers blocking
+//! the threads they run on; the rayon pool stays free for the parallel loops of the ranks.
+
+use std::{
+    slice,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Condvar, Mutex, RwLock,
+    },
+    thread,
+};
+
+use arith::Pod;
+use mpi::ffi::ompi_win_t;
+use serdes::ExpSerde;
+
+use super::{
+    bytes::{as_bytes, as_bytes_mut},
+    MPIEngine,
+};
+use crate::MPIResult;
+
+/// A barrier that can be broken by a rank leaving the world, failing the waits on it
+#[derive(Debug, Default)]
+struct WorldBarrier {
+    // (number of ranks waiting, generation, broken)
+    state: Mutex<(usize, usize, bool)>,
+    cvar: Condvar,
+}
+
+impl WorldBarrier {
+    /// Wait for `world_size` ranks, returns false if the barrier is broken
+    fn wait(&self, world_size: usize) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.2 {
+            return false;
+        }
+
+        let generation = state.1;
+        state.0 += 1;
+        if state.0 == world_size {
+            *state = (0, generation.wrapping_add(1), false);
+            self.cvar.notify_all();
+            return true;
+        }
+
+        let state = self
+            .cvar
+            .wait_while(state, |state| state.1 == generation && !state.2)
+            .unwrap();
+        state.1 != generation
+    }
+
+    fn break_barrier(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.2 = true;
+        self.cvar.notify_all();
+    }
+}
+
+/// The memory shared by the ranks of a world
+#[derive(Debug)]
+struct ThreadedWorld {
+    barrier: WorldBarrier,
+    /// Indexed by the rank writing the slot
+    slots: Vec<RwLock<Vec<u8>>>,
+}
+
+#[derive(Debug)]
+pub struct ThreadedEngine {
+    world_size: usize,
+    world_rank: usize,
+    world: Arc<ThreadedWorld>,
+    /// Indexed by the rank sent to, for the point-to-point messages
+    senders: Vec<Sender<Vec<u8>>>,
+    /// Indexed by the rank received from, behind a lock for the engine to be `Sync`
+    receivers: Vec<Mutex<Receiver<Vec<u8>>>>,
+}
+
+impl ThreadedEngine {
+    /// The engines of the ranks of a world of `world_size` threads, in rank order, each to be
+    /// moved to its own thread.
+    pub fn world(world_size: usize) -> Vec<Self> {
+        assert!(world_size > 0);
+
+        let world = Arc::new(ThreadedWorld {
+            barrier: WorldBarrier::default(),
+            slots: (0..world_size).map(|_| RwLock::new(vec![])).collect(),
+        });
+
+        // channels[from][to]
+        let (senders, receivers): (Vec<Vec<_>>, Vec<Vec<_>>) = (0..world_size)
+            .map(|_| (0..world_size).map(|_| channel::<Vec<u8>>()).unzip())
+            .unzip();
+
+        let mut receivers_to: Vec<Vec<_>> = (0..world_size).map(|_| vec![]).collect();
+        for receivers_from in receivers {
+            receivers_from
+                .into_iter()
+                .zip(receivers_to.iter_mut())
+                .for_each(|(receiver, to)| to.push(Mutex::new(receiver)));
+        }
+
+        senders
+            .into_iter()
+            .zip(receivers_to)
+            .enumerate()
+            .map(|(world_rank, (senders, receivers))| Self {
+                world_size,
+                world_rank,
+                world: world.clone(),
+                senders,
+                receivers,
+            })
+            .collect()
+    }
+
+    /// Run `f` on every rank of a world of `world_size` threads and return the outputs in rank
+    /// order. Panics if any rank does.
+    pub fn run<R: Send>(world_size: usize, f: impl Fn(&Self) -> R + Sync) -> Vec<R> {
+        let f = &f;
+        thread::scope(|s| {
+            let handles: Vec<_> = Self::world(world_size)
+                .into_iter()
+                .map(|engine| s.spawn(move || f(&engine)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    #[inline]
+    fn wait(&self, collective: &'static str) -> MPIResult<()> {
+        if self.world.barrier.wait(self.world_size) {
+            Ok(())
+        } else {
+            Err(self.mpi_error(collective, "a rank left the world"))
+        }
+    }
+
+    /// Write `bytes` into the slot of this rank, and run `read` on the slots of all ranks once
+    /// every rank has written its own
+    fn exchange<R>(
+        &self,
+        collective: &'static str,
+        bytes: &[u8],
+        read: impl FnOnce(&[&[u8]]) -> MPIResult<R>,
+    ) -> MPIResult<R> {
+        {
+            let mut slot = self.world.slots[self.world_rank].write().unwrap();
+            slot.clear();
+            slot.extend_from_slice(bytes);
+        }
+        self.wait(collective)?;
+
+        let output = {
+            let guards: Vec<_> = self
+                .world
+                .slots
+                .iter()
+                .map(|slot| slot.read().unwrap())
+                .collect();
+            let slots: Vec<&[u8]> = guards.iter().map(|slot| slot.as_slice()).collect();
+            read(&slots)
+        };
+
+        // no rank writes its slot again before all ranks are done reading
+        self.wait(collective)?;
+        output
+    }
+
+    /// Copy the slot of `rank` into `bytes`, checking it has the length expected
+    #[inline]
+    fn copy_slot(
+        &self,
+        collective: &'static str,
+        rank: usize,
+        slot: &[u8],
+        bytes: &mut [u8],
+    ) -> MPIResult<()> {
+        if slot.len() != bytes.len() {
+            return Err(self.mpi_error(
+                collective,
+                format!(
+                    "expected {} bytes from rank {rank}, got {}",
+                    bytes.len(),
+                    slot.len()
+                ),
+            ));
+        }
+        bytes.copy_from_slice(slot);
+        Ok(())
+    }
+
+    fn root_broadcast_slice<F: Pod>(
+        &self,
+        collective: &'static str,
+        values: &mut [F],
+    ) -> MPIResult<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        let root = self.root_rank();
+        let is_root = self.is_root();
+        let bytes = as_bytes_mut(values);
+        let sent = if is_root { bytes.to_vec() } else { vec![] };
+        self.exchange(collective, &sent, |slots| {
+            if is_root {
+                return Ok(());
+            }
+            self.copy_slot(collective, root, slots[root], bytes)
+        })
+    }
+}
+
+impl Drop for ThreadedEngine {
+    fn drop(&mut self) {
+        // a rank unwinding out of the world fails the collectives the other ranks wait in
+        if thread::panicking() {
+            self.world.barrier.break_barrier();
+        }
+    }
+}
+
+impl MPIEngine for ThreadedEngine {
+    fn gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+
+        let is_root = self.is_root();
+        self.exchange("gather_vec", as_bytes(local_vec), |slots| {
+            if !is_root {
+                return Ok(());
+            }
+            self.check_global_len("gather_vec", local_vec.len(), global_vec.len())?;
+            let local_n_bytes = size_of_val(local_vec);
+            as_bytes_mut(global_vec)
+                .chunks_exact_mut(local_n_bytes.max(1))
+                .zip(slots)
+                .enumerate()
+                .try_for_each(|(rank, (bytes, slot))| {
+                    self.copy_slot("gather_vec", rank, slot, bytes)
+                })
+        })
+    }
+
+    fn all_gather_vec<F: Pod>(&self, local_vec: &[F], global_vec: &mut Vec<F>) -> MPIResult<()> {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+
+        self.exchange("all_gather_vec", as_bytes(local_vec), |slots| {
+            self.check_global_len("all_gather_vec", local_vec.len(), global_vec.len())?;
+            let local_n_bytes = size_of_val(local_vec);
+            as_bytes_mut(global_vec)
+                .chunks_exact_mut(local_n_bytes.max(1))
+                .zip(slots)
+                .enumerate()
+                .try_for_each(|(rank, (bytes, slot))| {
+                    self.copy_slot("all_gather_vec", rank, slot, bytes)
+                })
+        })
+    }
+
+    fn scatter_vec<F: Pod>(&self, send_vec: &[F], recv_vec: &mut [F]) -> MPIResult<()> {
+        if self.is_single_process() {
+            recv_vec.copy_from_slice(send_vec);
+            return Ok(());
+        }
+
+        let (root, is_root) = (self.root_rank(), self.is_root());
+        let sent = if is_root { as_bytes(send_vec) } else { &[] };
+        self.exchange("scatter_vec", sent, |slots| {
+            if is_root {
+                self.check_global_len("scatter_vec", recv_vec.len(), send_vec.len())?;
+            }
+            let share_n_bytes = size_of_val(recv_vec);
+            let start = self.world_rank * share_n_bytes;
+            let share = slots[root]
+                .get(start..start + share_n_bytes)
+                .ok_or_else(|| {
+                    self.mpi_error(
+                        "scatter_vec",
+                        format!(
+                            "expected {} bytes from rank {root}, got {}",
+                            share_n_bytes * self.world_size,
+                            slots[root].len()
+                        ),
+                    )
+                })?;
+            as_bytes_mut(recv_vec).copy_from_slice(share);
+            Ok(())
+        })
+    }
+
+    fn root_broadcast_f<F: Pod>(&self, f: &mut F) -> MPIResult<()> {
+        self.root_broadcast_slice("root_broadcast_f", slice::from_mut(f))
+    }
+
+    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) -> MPIResult<()> {
+        self.root_broadcast_slice("root_broadcast_bytes", bytes)
+    }
+
+    fn all_to_all_transpose<F: Pod>(&self, row: &mut [F]) -> MPIResult<()> {
+        self.check_split_len("all_to_all_transpose", row.len())?;
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        // block i of the transposed row is block `world_rank` of the row of process i
+        let row_bytes = as_bytes_mut(row);
+        let block_n_bytes = row_bytes.len() / self.world_size;
+        let block = |rank: usize| rank * block_n_bytes..(rank + 1) * block_n_bytes;
+        let sent = row_bytes.to_vec();
+        self.exchange("all_to_all_transpose", &sent, |slots| {
+            slots.iter().enumerate().try_for_each(|(rank, slot)| {
+                let received = slot.get(block(self.world_rank)).ok_or_else(|| {
+                    self.mpi_error(
+                        "all_to_all_transpose",
+                        format!(
+                            "expected {} bytes from rank {rank}, got {}",
+                            sent.len(),
+                            slot.len()
+                        ),
+                    )
+                })?;
+                row_bytes[block(rank)].copy_from_slice(received);
+                Ok(())
+            })
+        })
+    }
+
+    fn send_to<F: Pod>(&self, rank: usize, vec: &[F]) -> MPIResult<()> {
+        self.check_peer("send_to", rank)?;
+        self.senders[rank]
+            .send(as_bytes(vec).to_vec())
+            .map_err(|_| self.mpi_error("send_to", format!("rank {rank} hung up")))
+    }
+
+    fn recv_from<F: Pod>(&self, rank: usize, vec: &mut [F]) -> MPIResult<()> {
+        self.check_peer("recv_from", rank)?;
+        let message = self.receivers[rank]
+            .lock()
+            .unwrap()
+            .recv()
+            .map_err(|_| self.mpi_error("recv_from", format!("rank {rank} hung up")))?;
+        self.copy_slot("recv_from", rank, &message, as_bytes_mut(vec))
+    }
+
+    fn gather_varlen_vec<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        global_vec: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()> {
+        let mut gathered = vec![];
+        self.gather_varlen_vec_streaming(local_vec, |_, elems| gathered.push(elems))?;
+        if self.is_root() {
+            *global_vec = gathered;
+        }
+        Ok(())
+    }
+
+    fn gather_varlen_vec_streaming<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        mut consume: impl FnMut(usize, Vec<F>),
+    ) -> MPIResult<()> {
+        const COLLECTIVE: &str = "gather_varlen_vec_streaming";
+        let mut buffer: Vec<u8> = Vec::new();
+        local_vec.serialize_into(&mut buffer).unwrap();
+
+        let is_root = self.is_root();
+        self.exchange(COLLECTIVE, &buffer, |slots| {
+            if !is_root {
+                return Ok(());
+            }
+            slots.iter().enumerate().try_for_each(|(rank, slot)| {
+                let elems = Vec::deserialize_from(*slot).map_err(|e| {
+                    self.mpi_error(COLLECTIVE, format!("bad payload from rank {rank}: {e}"))
+                })?;
+                consume(rank, elems);
+                Ok(())
+            })
+        })
+    }
+
+    fn all_gather_varlen_vec<F: ExpSerde>(
+        &self,
+        local_vec: &Vec<F>,
+        global_vec: &mut Vec<Vec<F>>,
+    ) -> MPIResult<()> {
+        const COLLECTIVE: &str = "all_gather_varlen_vec";
+        let mut buffer: Vec<u8> = Vec::new();
+        local_vec.serialize_into(&mut buffer).unwrap();
+
+        // every rank deserializes the payloads of all ranks, without going through the root
+        *global_vec = self.exchange(COLLECTIVE, &buffer, |slots| {
+            slots
+                .iter()
+                .enumerate()
+                .map(|(rank, slot)| {
+                    Vec::deserialize_from(*slot).map_err(|e| {
+                        self.mpi_error(COLLECTIVE, format!("bad payload from rank {rank}: {e}"))
+                    })
+                })
+                .collect()
+        })?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn is_single_process(&self) -> bool {
+        self.world_size == 1
+    }
+
+    #[inline(always)]
+    fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    #[inline(always)]
+    fn world_rank(&self) -> usize {
+        self.world_rank
+    }
+
+    fn barrier(&self) -> MPIResult<()> {
+        self.wait("barrier")
+    }
+
+    fn abort(&self, error_code: i32) -> ! {
+        // the thread of this rank unwinds and breaks the barrier of the world, failing the
+        // other ranks in their next collective, without exiting the process
+        panic!("rank {} aborted with code {error_code}", self.world_rank)
+    }
+
+    fn create_shared_mem(&self, _n_bytes: usize) -> MPIResult<(*mut u8, *mut ompi_win_t)> {
+        Err(self.mpi_error(
+            "create_shared_mem",
+            "threads of a world share the memory of their process, not MPI windows",
+        ))
+    }
+}
@@ -1,9 +1,13 @@
 use polynomials::MultilinearExtension;
-use rand::RngCore;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use serdes::ExpSerde;
 use std::{fmt::Debug, str::FromStr};
 
-use crate::{ExpErrors, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, Transcript};
+use crate::{
+    BatchOpenError, ExpErrors, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    Transcript, WorldSizeError,
+};
 
 pub trait StructuredReferenceString {
     type PKey: Clone + Debug + ExpSerde + Send + Sync + 'static;
@@ -30,6 +34,14 @@ pub trait ExpanderPCS<F: FieldEngine> {
 
     const PCS_TYPE: PolynomialCommitmentType;
 
+    /// How the distributed commitment lays out the shares of the processes, see `CommitLayout`.
+    const COMMIT_LAYOUT: CommitLayout = CommitLayout::Global;
+
+    /// Whether the PCS is distributed over the processes themselves rather than over the parties,
+    /// i.e., does not commit to the witnesses of the virtual parties of a world that is not a
+    /// power of two, and so requires a power of two of processes.
+    const POWER_OF_TWO_WORLD: bool = false;
+
     type Params: PCSParams;
     type ScratchPad: Clone + Debug + Default + Send + ExpSerde + Sync;
 
@@ -61,25 +73,9 @@ pub trait ExpanderPCS<F: FieldEngine> {
         path: Option<&str>,
     ) -> Self::SRS {
         match path {
-            Some(path) => {
-                match std::fs::File::open(path) {
-                    Ok(mut file) => {
-                        // file exists; deserialize SRS from file
-                        Self::SRS::deserialize_from(&mut file).unwrap_or_else(|_| {
-                            panic!("Failed to deserialize SRS for {} PCS", Self::NAME)
-                        })
-                    }
-                    Err(_e) => {
-                        // file does not exist; generate SRS and store to file
-                        let srs = Self::gen_srs(params, mpi_engine, rng);
-                        let mut file =
-                            std::fs::File::create(path).expect("Failed to create SRS file");
-                        srs.serialize_into(&mut file)
-                            .expect("Failed to serialize SRS to file");
-                        srs
-                    }
-                }
-            }
+            // the SRS file is memory mapped, see `serdes::load_or_compute`
+            Some(path) => serdes::load_or_compute(path, || Self::gen_srs(params, mpi_engine, rng))
+                .unwrap_or_else(|e| panic!("Failed to load SRS for {} PCS: {e}", Self::NAME)),
 
             None => {
                 // no path provided; generate SRS
@@ -91,8 +87,35 @@ pub trait ExpanderPCS<F: FieldEngine> {
     /// The actual function to generate the SRS.
     fn gen_srs(params: &Self::Params, mpi_engine: &impl MPIEngine, rng: impl RngCore) -> Self::SRS;
 
+    /// Generate the SRS from `seed`, the same for the same seed on every machine.
+    ///
+    /// Insecure, for tests only: whoever knows the seed knows the trapdoor of the SRS.
+    fn gen_srs_from_seed(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        seed: u64,
+    ) -> Self::SRS {
+        Self::gen_srs(params, mpi_engine, ChaCha20Rng::seed_from_u64(seed))
+    }
+
+    /// Check that the PCS can be distributed over `world_size` processes, see
+    /// `POWER_OF_TWO_WORLD`. Callers check it before setting up the PCS.
+    fn check_world_size(world_size: usize) -> Result<(), WorldSizeError> {
+        if Self::POWER_OF_TWO_WORLD && !world_size.is_power_of_two() {
+            return Err(WorldSizeError {
+                pcs: Self::NAME,
+                world_size,
+            });
+        }
+        Ok(())
+    }
+
     /// n_input_vars is with respect to the multilinear poly on each machine in MPI,
     /// also ignore the number of variables stacked in the SIMD field.
+    ///
+    /// # Panics
+    ///
+    /// If `check_world_size` rejects `world_size`.
     fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params;
 
     /// Initialize the scratch pad.
@@ -100,17 +123,18 @@ pub trait ExpanderPCS<F: FieldEngine> {
     fn init_scratch_pad(params: &Self::Params, mpi_engine: &impl MPIEngine) -> Self::ScratchPad;
 
     /// Commit to a polynomial. Root process returns the commitment, other processes can return
-    /// arbitrary value.
+    /// arbitrary value. Fails if the processes fail to communicate.
     fn commit(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
         poly: &impl MultilinearExtension<F::SimdCircuitField>,
         scratch_pad: &mut Self::ScratchPad,
-    ) -> Option<Self::Commitment>;
+    ) -> MPIResult<Option<Self::Commitment>>;
 
     /// Open the polynomial at a point.
     /// Root process returns the opening, other processes can return arbitrary value.
+    /// Fails if the processes fail to communicate.
     ///
     /// Note(ZF): In GKR, We'll add the opening proof to the transcript after
     /// calling this function.
@@ -138,7 +162,7 @@ pub trait ExpanderPCS<F: FieldEngine> {
         x: &ExpanderSingleVarChallenge<F>,
         transcript: &mut impl Transcript,
         scratch_pad: &Self::ScratchPad,
-    ) -> Option<Self::Opening>;
+    ) -> MPIResult<Option<Self::Opening>>;
 
     /// Verify the opening of a polynomial at a point.
     /// This should only be called on the root process.
@@ -157,6 +181,8 @@ pub trait ExpanderPCS<F: FieldEngine> {
     ) -> bool;
 
     /// Open a set of polynomials at a set of points.
+    /// Fails if a point does not match the SIMD lanes of `F` or the processes of `mpi_engine`,
+    /// see `ExpanderSingleVarChallenge::check_shape`, or if the processes fail to communicate.
     fn multi_points_batch_open(
         _params: &Self::Params,
         _mpi_engine: &impl MPIEngine,
@@ -165,11 +191,12 @@ pub trait ExpanderPCS<F: FieldEngine> {
         _x: &[ExpanderSingleVarChallenge<F>],
         _scratch_pad: &Self::ScratchPad,
         _transcript: &mut impl Transcript,
-    ) -> (Vec<F::ChallengeField>, Self::BatchOpening) {
+    ) -> Result<(Vec<F::ChallengeField>, Self::BatchOpening), BatchOpenError> {
         unimplemented!("Batch opening is not implemented for this PCS type")
     }
 
     /// Verify the opening of a set of polynomials at a set of points.
+    /// Rejects the points that do not match the SIMD lanes of `F`, on a single process.
     fn multi_points_batch_verify(
         _params: &Self::Params,
         _verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
@@ -192,14 +219,39 @@ impl StructuredReferenceString for () {
     }
 }
 
+/// The layout a PCS commits to a polynomial distributed over the processes in.
+///
+/// The GKR prover holds the polynomial row per rank: each process holds the evaluations over
+/// the local variables for its own assignment of the MPI variables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommitLayout {
+    /// The commitment is to the matrix of the global polynomial, the processes reorganize their
+    /// shares on commit, e.g., with an `all_to_all_transpose`.
+    #[default]
+    Global,
+    /// Each process commits to its own share as it is held, the data of the queried columns is
+    /// gathered across the processes in the opening instead.
+    RankLocal,
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum PolynomialCommitmentType {
     #[default]
     Raw,
+    /// The distributed raw PCS, which is not binding, for the benchmarks only: its configs are
+    /// built with the `raw-distributed` feature
+    RawDistributed,
     KZG,
     Hyrax,
     Orion,
     FRI,
+    Basefold,
+    Brakedown,
+    Ligero,
+    Whir,
+    Zeromorph,
+    Dory,
+    IPA,
 }
 
 impl FromStr for PolynomialCommitmentType {
@@ -208,10 +260,18 @@ impl FromStr for PolynomialCommitmentType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "Raw" => Ok(PolynomialCommitmentType::Raw),
+            "RawDistributed" => Ok(PolynomialCommitmentType::RawDistributed),
             "KZG" => Ok(PolynomialCommitmentType::KZG),
             "Hyrax" => Ok(PolynomialCommitmentType::Hyrax),
             "Orion" => Ok(PolynomialCommitmentType::Orion),
             "FRI" => Ok(PolynomialCommitmentType::FRI),
+            "Basefold" => Ok(PolynomialCommitmentType::Basefold),
+            "Brakedown" => Ok(PolynomialCommitmentType::Brakedown),
+            "Ligero" => Ok(PolynomialCommitmentType::Ligero),
+            "Whir" => Ok(PolynomialCommitmentType::Whir),
+            "Zeromorph" => Ok(PolynomialCommitmentType::Zeromorph),
+            "Dory" => Ok(PolynomialCommitmentType::Dory),
+            "IPA" => Ok(PolynomialCommitmentType::IPA),
             _ => Err(ExpErrors::PCSTypeError(s.to_string())),
         }
     }
@@ -1,7 +1,9 @@
 mod challenge;
 mod definition;
 mod proof;
+mod queries;
 
 pub use challenge::*;
 pub use definition::*;
 pub use proof::*;
+pub use queries::*;
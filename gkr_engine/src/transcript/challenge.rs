@@ -1,7 +1,8 @@
 use arith::SimdField;
+use polynomials::HypercubeLayout;
 use serdes::ExpSerde;
 
-use crate::FieldEngine;
+use crate::{ChallengeShapeError, FieldEngine};
 
 use super::Transcript;
 
@@ -27,6 +28,42 @@ pub struct ExpanderSingleVarChallenge<F: FieldEngine> {
     pub r_mpi: Vec<F::ChallengeField>,
 }
 
+/// The number of variables of the SIMD lanes of `F`, i.e., the expected length of `r_simd`
+#[inline]
+fn n_simd_vars<F: FieldEngine>() -> usize {
+    <F::SimdCircuitField as SimdField>::PACK_SIZE.trailing_zeros() as usize
+}
+
+/// The number of variables of the parties of `world_size` processes, i.e., the expected length of
+/// `r_mpi`, see `MPIEngine::n_parties`
+#[inline]
+fn n_mpi_vars(world_size: usize) -> usize {
+    world_size.next_power_of_two().trailing_zeros() as usize
+}
+
+/// Check the lengths of `r_simd` and `r_mpi` against the SIMD lanes of `F` and `world_size`
+fn check_shape<F: FieldEngine>(
+    r_simd: &[F::ChallengeField],
+    r_mpi: &[F::ChallengeField],
+    world_size: usize,
+) -> Result<(), ChallengeShapeError> {
+    if r_simd.len() != n_simd_vars::<F>() {
+        return Err(ChallengeShapeError::SimdVars {
+            n_lanes: <F::SimdCircuitField as SimdField>::PACK_SIZE,
+            expected: n_simd_vars::<F>(),
+            got: r_simd.len(),
+        });
+    }
+    if r_mpi.len() != n_mpi_vars(world_size) {
+        return Err(ChallengeShapeError::MPIVars {
+            world_size,
+            expected: n_mpi_vars(world_size),
+            got: r_mpi.len(),
+        });
+    }
+    Ok(())
+}
+
 impl<F: FieldEngine> ExpanderSingleVarChallenge<F> {
     #[inline]
     pub fn new(
@@ -37,6 +74,40 @@ impl<F: FieldEngine> ExpanderSingleVarChallenge<F> {
         Self { rz, r_simd, r_mpi }
     }
 
+    /// Counterpart of `new` checking the challenge against the SIMD lanes of `F` and the
+    /// `world_size` processes, see `check_shape`
+    #[inline]
+    pub fn try_new(
+        rz: Vec<F::ChallengeField>,
+        r_simd: Vec<F::ChallengeField>,
+        r_mpi: Vec<F::ChallengeField>,
+        world_size: usize,
+    ) -> Result<Self, ChallengeShapeError> {
+        let challenge = Self::new(rz, r_simd, r_mpi);
+        challenge.check_shape(world_size)?;
+        Ok(challenge)
+    }
+
+    /// The number of SIMD lanes of `F`, merged by `r_simd`
+    #[inline]
+    pub fn n_simd_lanes() -> usize {
+        <F::SimdCircuitField as SimdField>::PACK_SIZE
+    }
+
+    /// The number of processes merged by `r_mpi`
+    #[inline]
+    pub fn n_mpi_parties(&self) -> usize {
+        1 << self.r_mpi.len()
+    }
+
+    /// Check that `r_simd` has a variable per doubling of the SIMD lanes of `F`, and `r_mpi` one
+    /// per doubling of the parties of the `world_size` processes, before the challenge is evaluated
+    /// at: a mismatch would shift the variables of the polynomial and evaluate it elsewhere.
+    #[inline]
+    pub fn check_shape(&self, world_size: usize) -> Result<(), ChallengeShapeError> {
+        check_shape::<F>(&self.r_simd, &self.r_mpi, world_size)
+    }
+
     #[inline]
     pub fn local_xs(&self) -> Vec<F::ChallengeField> {
         [self.r_simd.as_slice(), self.rz.as_slice()].concat()
@@ -57,6 +128,47 @@ impl<F: FieldEngine> ExpanderSingleVarChallenge<F> {
         self.rz.len() + self.r_simd.len() + self.r_mpi.len()
     }
 
+    /// Build a challenge from a point over all the variables of the global polynomial, given in
+    /// `layout`. The point is split into `[r_simd, rz, r_mpi]` after conversion to Expander's
+    /// layout.
+    #[inline]
+    pub fn from_point(
+        point: &[F::ChallengeField],
+        layout: HypercubeLayout,
+        world_size: usize,
+    ) -> Self {
+        let n_simd_vars = n_simd_vars::<F>();
+        let n_mpi_vars = n_mpi_vars(world_size);
+        assert!(point.len() >= n_simd_vars + n_mpi_vars);
+
+        let xs = layout.convert_point(point, HypercubeLayout::LsbFirst);
+        let (r_simd, rest) = xs.split_at(n_simd_vars);
+        let (rz, r_mpi) = rest.split_at(rest.len() - n_mpi_vars);
+        Self::new(rz.to_vec(), r_simd.to_vec(), r_mpi.to_vec())
+    }
+
+    /// Inverse of `from_point`, i.e., `global_xs` in the requested layout.
+    #[inline]
+    pub fn to_point(&self, layout: HypercubeLayout) -> Vec<F::ChallengeField> {
+        HypercubeLayout::LsbFirst.convert_point(&self.global_xs(), layout)
+    }
+
+    /// Split off the copy dimension of a data-parallel circuit, whose copy index occupies the
+    /// lowest `log_n_copies` circuit variables. Returns `(r_copy, challenge of a single copy)`.
+    #[inline]
+    pub fn split_copy_vars(&self, log_n_copies: usize) -> (Vec<F::ChallengeField>, Self) {
+        assert!(log_n_copies <= self.rz.len());
+        let (r_copy, rz) = self.rz.split_at(log_n_copies);
+        (
+            r_copy.to_vec(),
+            Self {
+                rz: rz.to_vec(),
+                r_simd: self.r_simd.clone(),
+                r_mpi: self.r_mpi.clone(),
+            },
+        )
+    }
+
     #[inline]
     pub fn sample_from_transcript(
         transcript: &mut impl Transcript,
@@ -65,12 +177,9 @@ impl<F: FieldEngine> ExpanderSingleVarChallenge<F> {
     ) -> Self {
         let rz = transcript.generate_field_elements::<F::ChallengeField>(num_circuit_var);
 
-        let r_simd = transcript.generate_field_elements::<F::ChallengeField>(
-            <F::SimdCircuitField as SimdField>::PACK_SIZE.trailing_zeros() as usize,
-        );
+        let r_simd = transcript.generate_field_elements::<F::ChallengeField>(n_simd_vars::<F>());
 
-        let r_mpi = transcript
-            .generate_field_elements::<F::ChallengeField>(world_size.trailing_zeros() as usize);
+        let r_mpi = transcript.generate_field_elements::<F::ChallengeField>(n_mpi_vars(world_size));
 
         Self { rz, r_simd, r_mpi }
     }
@@ -92,6 +201,12 @@ impl<F: FieldEngine> ExpanderDualVarChallenge<F> {
         }
     }
 
+    /// See `ExpanderSingleVarChallenge::check_shape`
+    #[inline]
+    pub fn check_shape(&self, world_size: usize) -> Result<(), ChallengeShapeError> {
+        check_shape::<F>(&self.r_simd, &self.r_mpi, world_size)
+    }
+
     #[inline]
     pub fn challenge_x(&self) -> ExpanderSingleVarChallenge<F> {
         ExpanderSingleVarChallenge {
@@ -118,12 +233,9 @@ impl<F: FieldEngine> ExpanderDualVarChallenge<F> {
     ) -> Self {
         let rz_0 = transcript.generate_field_elements::<F::ChallengeField>(num_circuit_var);
 
-        let r_simd = transcript.generate_field_elements::<F::ChallengeField>(
-            <F::SimdCircuitField as SimdField>::PACK_SIZE.trailing_zeros() as usize,
-        );
+        let r_simd = transcript.generate_field_elements::<F::ChallengeField>(n_simd_vars::<F>());
 
-        let r_mpi = transcript
-            .generate_field_elements::<F::ChallengeField>(world_size.trailing_zeros() as usize);
+        let r_mpi = transcript.generate_field_elements::<F::ChallengeField>(n_mpi_vars(world_size));
 
         Self {
             rz_0,
@@ -1,11 +1,15 @@
 use std::{fmt::Debug, str::FromStr};
 
 use arith::Field;
-use serdes::ExpSerde;
+use serdes::{ExpSerde, SerdeResult};
 
 use crate::ExpErrors;
 
-use super::Proof;
+use super::{sample_queries, Proof};
+
+const CHALLENGE_POINT_LABEL: &[u8] = b"expander challenge point";
+const CHALLENGE_RLC_LABEL: &[u8] = b"expander challenge rlc";
+const CHALLENGE_QUERIES_LABEL: &[u8] = b"expander challenge queries";
 
 /// A trait for transcript generation over the challenge field
 /// The associated field is the challenge field, i.e., M31Ext3
@@ -68,6 +72,50 @@ pub trait Transcript: Clone + Debug {
         res
     }
 
+    /// Absorb a domain separation label into the transcript.
+    /// Subsequent challenges depend on the label, but the label is not meant to be part of the
+    /// proof, i.e., both the prover and the verifier add the same label at the same point.
+    ///
+    /// By default, the label is appended as plain bytes.
+    #[inline]
+    fn append_domain_separator(&mut self, label: &[u8]) {
+        self.append_u8_slice(label);
+    }
+
+    /// Mark the start of a phase of the protocol, e.g., a layer of GKR, for the transcripts
+    /// recording their operations. The prover and the verifier mark the same phases at the same
+    /// points. Does nothing by default, and never affects the challenges.
+    #[inline]
+    fn begin_phase(&mut self, _phase: &str) {}
+
+    /// Generate a random evaluation point with `n_vars` coordinates.
+    #[inline]
+    fn challenge_point<F: Field>(&mut self, n_vars: usize) -> Vec<F> {
+        self.append_domain_separator(CHALLENGE_POINT_LABEL);
+        self.generate_field_elements(n_vars)
+    }
+
+    /// Generate `n` independent coefficients for a random linear combination.
+    #[inline]
+    fn challenge_rlc<F: Field>(&mut self, n: usize) -> Vec<F> {
+        self.append_domain_separator(CHALLENGE_RLC_LABEL);
+        self.generate_field_elements(n)
+    }
+
+    /// Generate `k` query indices in the range `[0, domain_size)`, uniformly at random.
+    ///
+    /// If `dedup` is set, the returned indices are pairwise distinct, and they keep the order in
+    /// which they were first sampled. The domain size, `k` and `dedup` are appended after the
+    /// label, so that queries drawn for another domain are not the same indices reduced.
+    fn challenge_queries(&mut self, domain_size: usize, k: usize, dedup: bool) -> Vec<usize> {
+        self.append_domain_separator(CHALLENGE_QUERIES_LABEL);
+        self.append_u8_slice(&(domain_size as u64).to_le_bytes());
+        self.append_u8_slice(&(k as u64).to_le_bytes());
+        self.append_u8_slice(&[dedup as u8]);
+
+        sample_queries(domain_size, k, dedup, |n| self.generate_usize_vector(n))
+    }
+
     /// Produce the proof
     /// It is not recommended to append/challenge after calling this function
     fn finalize_and_get_proof(&mut self) -> Proof;
@@ -101,6 +149,13 @@ pub trait Transcript: Clone + Debug {
     fn unlock_proof(&mut self);
 
     fn refresh_digest(&mut self);
+
+    /// Export the full state of the transcript, including the proof so far, e.g., to suspend a
+    /// proof and resume it later with `import_state`
+    fn export_state(&self) -> Vec<u8>;
+
+    /// Restore a transcript from the output of `export_state`
+    fn import_state(state: &[u8]) -> SerdeResult<Self>;
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -109,8 +164,9 @@ pub enum FiatShamirHashType {
     SHA256,
     Keccak256,
     Poseidon,
-    Animoe,
-    MIMC5, // Note: use MIMC5 for bn254 ONLY
+    Anemoi,  // Note: use Anemoi for bn254 ONLY
+    Griffin, // Note: use Griffin for bn254 ONLY
+    MIMC5,   // Note: use MIMC5 for bn254 ONLY
 }
 
 impl FromStr for FiatShamirHashType {
@@ -121,7 +177,9 @@ impl FromStr for FiatShamirHashType {
             "SHA256" => Ok(FiatShamirHashType::SHA256),
             "Keccak256" => Ok(FiatShamirHashType::Keccak256),
             "Poseidon" => Ok(FiatShamirHashType::Poseidon),
-            "Animoe" => Ok(FiatShamirHashType::Animoe),
+            // "Animoe" is kept for configs written against the earlier misspelled variant
+            "Anemoi" | "Animoe" => Ok(FiatShamirHashType::Anemoi),
+            "Griffin" => Ok(FiatShamirHashType::Griffin),
             "MIMC5" => Ok(FiatShamirHashType::MIMC5),
             _ => Err(ExpErrors::FiatShamirHashTypeError(s.to_string())),
         }
@@ -1,4 +1,4 @@
-use serdes::ExpSerde;
+use serdes::{ExpSerde, SerdeError, SerdeResult};
 
 /// Proof. In the serialized mode.
 #[derive(Debug, Clone, Default, PartialEq, ExpSerde)]
@@ -11,4 +11,26 @@ impl Proof {
     pub fn append_u8_slice(&mut self, buffer: &[u8], size: usize) {
         self.bytes.extend_from_slice(&buffer[..size]);
     }
+
+    /// The exact number of bytes written by `serialize_into`: a length prefix and the proof bytes.
+    #[inline(always)]
+    pub fn serialized_size(&self) -> usize {
+        std::mem::size_of::<u64>() + self.bytes.len()
+    }
+
+    /// Serialize the proof directly into a caller-provided buffer, avoiding an intermediate
+    /// allocation of the whole serialized proof.
+    ///
+    /// The buffer should hold at least `serialized_size` bytes.
+    /// Returns the number of bytes written.
+    #[inline]
+    pub fn serialize_into_slice(&self, buffer: &mut [u8]) -> SerdeResult<usize> {
+        let size = self.serialized_size();
+        if buffer.len() < size {
+            return Err(SerdeError::IOError(std::io::ErrorKind::WriteZero.into()));
+        }
+
+        self.serialize_into(&mut buffer[..size])?;
+        Ok(size)
+    }
 }
@@ -0,0 +1,45 @@
+//! Sampling of query indices from uniformly random words, shared by the hash-based PCSes.
+//!
+//! A word reduced modulo a domain size that does not divide `2^64` favors the low indices. The
+//! words past the largest multiple of the domain size are rejected instead, so that every index
+//! of the domain is equally likely, and more words are drawn until `k` indices are accepted.
+
+use std::collections::HashSet;
+
+/// The index of the domain `[0, domain_size)` a uniformly random word stands for, or None if
+/// the word is rejected, with probability below `domain_size / 2^64`
+#[inline]
+pub fn unbiased_index(word: usize, domain_size: usize) -> Option<usize> {
+    assert!(domain_size > 0);
+    // the number of words mapped to each index is the same below the limit
+    let limit = usize::MAX - (usize::MAX % domain_size + 1) % domain_size;
+    (word <= limit).then_some(word % domain_size)
+}
+
+/// Sample `k` indices in `[0, domain_size)`, pairwise distinct if `dedup` is set, from the
+/// words returned by `draw(n)`, `n` at a time, rejecting the words `unbiased_index` rejects.
+/// The indices keep the order in which they were first sampled.
+pub fn sample_queries(
+    domain_size: usize,
+    k: usize,
+    dedup: bool,
+    mut draw: impl FnMut(usize) -> Vec<usize>,
+) -> Vec<usize> {
+    assert!(domain_size > 0);
+    assert!(!dedup || k <= domain_size);
+
+    let mut queries = Vec::with_capacity(k);
+    let mut sampled = HashSet::with_capacity(if dedup { k } else { 0 });
+    while queries.len() < k {
+        let remaining = k - queries.len();
+        draw(remaining)
+            .into_iter()
+            .filter_map(|word| unbiased_index(word, domain_size))
+            .for_each(|q| {
+                if queries.len() < k && (!dedup || sampled.insert(q)) {
+                    queries.push(q);
+                }
+            });
+    }
+    queries
+}
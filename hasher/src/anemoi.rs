@@ -0,0 +1,108 @@
+use arith::Field;
+
+use crate::{
+    field_hash_utils::{
+        bytes_to_field_elems, get_constants, inv_5_exponent, pow_limbs, Alpha5Field,
+    },
+    FiatShamirHasher,
+};
+
+/// Anemoi over a two-cell state (x, y), with the open Flystel S-box and alpha = 5.
+///
+/// The hasher is a sponge of rate 1 and capacity 1, the capacity cell being initialized with
+/// the input length in bytes. The round constants are derived from a keccak chain, the same way
+/// as for MiMC5 and Poseidon.
+#[derive(Debug, Clone, Default)]
+pub struct AnemoiFiatShamirHasher<F: Field> {
+    round_constants_c: Vec<F>,
+    round_constants_d: Vec<F>,
+
+    /// generator g, the Flystel uses beta = g, gamma = 0 and delta = g^{-1}
+    g: F,
+    g_inv: F,
+    alpha_inv: [u64; 4],
+}
+
+const ANEMOI_SEED_C: &str = "anemoi_seed_c";
+const ANEMOI_SEED_D: &str = "anemoi_seed_d";
+
+impl<F: Field> AnemoiFiatShamirHasher<F> {
+    /// Number of rounds for 128-bit security with alpha = 5 and a single column.
+    pub const ROUNDS: usize = 21;
+
+    /// Multiplicative generator of the BN254 scalar field.
+    const GENERATOR: u32 = 7;
+
+    #[inline(always)]
+    fn linear_layer(&self, state: &mut [F; 2]) {
+        // M = [[1, g], [g, g^2 + 1]]
+        state[0] += self.g * state[1];
+        state[1] += self.g * state[0];
+
+        // pseudo-Hadamard transform
+        state[1] += state[0];
+        state[0] += state[1];
+    }
+
+    #[inline(always)]
+    fn flystel(&self, state: &mut [F; 2]) {
+        state[0] -= self.g * state[1].square();
+        state[1] -= pow_limbs(&state[0], &self.alpha_inv);
+        state[0] += self.g * state[1].square() + self.g_inv;
+    }
+
+    pub fn permute(&self, state: &mut [F; 2]) {
+        self.round_constants_c
+            .iter()
+            .zip(self.round_constants_d.iter())
+            .for_each(|(c, d)| {
+                state[0] += c;
+                state[1] += d;
+                self.linear_layer(state);
+                self.flystel(state);
+            });
+        self.linear_layer(state);
+    }
+
+    fn hash_u8_to_state(&self, input: &[u8]) -> F {
+        let mut state = [F::ZERO, F::from(input.len() as u32)];
+        let elems = bytes_to_field_elems::<F>(input);
+        if elems.is_empty() {
+            self.permute(&mut state);
+        }
+        elems.iter().for_each(|x| {
+            state[0] += x;
+            self.permute(&mut state);
+        });
+        state[0]
+    }
+}
+
+impl<F: Alpha5Field> FiatShamirHasher for AnemoiFiatShamirHasher<F> {
+    const NAME: &'static str = "Anemoi_Field_Hasher";
+
+    const DIGEST_SIZE: usize = F::SIZE;
+
+    fn new() -> Self {
+        let g = F::from(Self::GENERATOR);
+        Self {
+            round_constants_c: get_constants(ANEMOI_SEED_C, Self::ROUNDS),
+            round_constants_d: get_constants(ANEMOI_SEED_D, Self::ROUNDS),
+            g,
+            g_inv: g.inv().unwrap(),
+            alpha_inv: inv_5_exponent::<F>(),
+        }
+    }
+
+    fn hash(&self, output: &mut [u8], input: &[u8]) {
+        assert!(output.len() == F::SIZE);
+        let res = self.hash_u8_to_state(input);
+        res.to_bytes(output);
+    }
+
+    fn hash_inplace(&self, buffer: &mut [u8]) {
+        assert!(buffer.len() == F::SIZE);
+        let res = self.hash_u8_to_state(buffer);
+        res.to_bytes(buffer);
+    }
+}
@@ -0,0 +1,49 @@
+use arith::{Field, Fr};
+use ark_std::test_rng;
+
+use crate::{
+    field_hash_utils::{inv_5_exponent, is_square, pow_limbs},
+    AnemoiFiatShamirHasher, FiatShamirHasher, GriffinFiatShamirHasher,
+};
+
+#[test]
+fn check_bn254_inv_5_exponent() {
+    let mut rng = test_rng();
+    let inv_5 = inv_5_exponent::<Fr>();
+    for _ in 0..10 {
+        let x = Fr::random_unsafe(&mut rng);
+        let y = pow_limbs(&x, &inv_5);
+        assert_eq!(y.exp(5), x);
+        assert!(is_square(&x.square()));
+    }
+    // 7 generates the multiplicative group, hence is not a square
+    assert!(!is_square(&Fr::from(7u32)));
+}
+
+fn hasher_sanity_helper<H: FiatShamirHasher>() {
+    let hasher = H::new();
+    let hash = |input: &[u8]| {
+        let mut output = vec![0u8; H::DIGEST_SIZE];
+        hasher.hash(&mut output, input);
+        output
+    };
+
+    assert_eq!(hash(b"expander"), hash(b"expander"));
+    assert_ne!(hash(b"expander"), hash(b"expandes"));
+    // zero padding of the last chunk must not collide
+    assert_ne!(hash(&[1u8]), hash(&[1u8, 0u8]));
+    assert_ne!(hash(&[]), hash(&[0u8]));
+    // multi-chunk inputs
+    assert_ne!(hash(&[3u8; 100]), hash(&[3u8; 101]));
+
+    let mut buffer = hash(b"expander");
+    let expected = hash(&buffer);
+    hasher.hash_inplace(&mut buffer);
+    assert_eq!(buffer, expected);
+}
+
+#[test]
+fn check_anemoi_griffin_sanity() {
+    hasher_sanity_helper::<AnemoiFiatShamirHasher<Fr>>();
+    hasher_sanity_helper::<GriffinFiatShamirHasher<Fr>>();
+}
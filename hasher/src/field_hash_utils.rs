@@ -0,0 +1,98 @@
+use arith::{Field, Fr};
+use tiny_keccak::{Hasher, Keccak};
+
+/// (p - 1) / 2 for the BN254 scalar field, little endian limbs.
+pub const BN254_FR_HALF_ORDER: [u64; 4] = [
+    0xa1f0fac9f8000000,
+    0x9419f4243cdcb848,
+    0xdc2822db40c0ac2e,
+    0x183227397098d014,
+];
+
+/// 5^{-1} mod (p - 1) for the BN254 scalar field, little endian limbs,
+/// i.e., x -> x^{BN254_FR_INV_5} inverts the x -> x^5 S-box.
+pub const BN254_FR_INV_5: [u64; 4] = [
+    0xcfe7f7a98ccccccd,
+    0x535cb9d394945a0d,
+    0x93736af8679aad17,
+    0x26b6a528b427b354,
+];
+
+/// Exponentiation by an exponent wider than 128 bits, given as little endian limbs.
+#[inline]
+pub fn pow_limbs<F: Field>(base: &F, exp: &[u64]) -> F {
+    let mut res = F::ONE;
+    for limb in exp.iter().rev() {
+        for i in (0..64).rev() {
+            res = res.square();
+            if (limb >> i) & 1 == 1 {
+                res *= base;
+            }
+        }
+    }
+    res
+}
+
+/// A field where x -> x^5 is a permutation, as the S-boxes of Anemoi and Griffin require, with
+/// the exponents inverting it and computing the Legendre symbol. Only the BN254 scalar field is
+/// supported, so the hashers over any other field fail to build rather than to run.
+pub trait Alpha5Field: Field {
+    /// 5^{-1} mod (p - 1), little endian limbs
+    const INV_5: [u64; 4];
+
+    /// (p - 1) / 2, little endian limbs
+    const HALF_ORDER: [u64; 4];
+}
+
+impl Alpha5Field for Fr {
+    const INV_5: [u64; 4] = BN254_FR_INV_5;
+
+    const HALF_ORDER: [u64; 4] = BN254_FR_HALF_ORDER;
+}
+
+/// Exponent inverting the x -> x^5 S-box of the field, used by Anemoi and Griffin.
+#[inline]
+pub fn inv_5_exponent<F: Alpha5Field>() -> [u64; 4] {
+    F::INV_5
+}
+
+/// Legendre symbol check, true if x is a nonzero square.
+#[inline]
+pub fn is_square<F: Alpha5Field>(x: &F) -> bool {
+    pow_limbs(x, &F::HALF_ORDER) == F::ONE
+}
+
+/// Derive field constants from a keccak chain seeded by `seed`.
+pub fn get_constants<F: Field>(seed: &str, n_rounds: usize) -> Vec<F> {
+    let mut keccak = Keccak::v256();
+    let mut h = [0u8; 32];
+    keccak.update(seed.as_bytes());
+    keccak.finalize(&mut h);
+
+    (0..n_rounds)
+        .map(|_| {
+            let mut keccak = Keccak::v256();
+            keccak.update(&h);
+            keccak.finalize(&mut h);
+
+            // big endian -> little endian, in order to match the one in gnark
+            // or probably we can change the implementation there
+            let mut h_reverse = h;
+            h_reverse.reverse();
+
+            F::from_uniform_bytes(&h_reverse)
+        })
+        .collect()
+}
+
+/// Split the input bytes into field elements, zero padding the last chunk.
+pub fn bytes_to_field_elems<F: Field>(input: &[u8]) -> Vec<F> {
+    input
+        .chunks(F::SIZE)
+        .map(|chunk| {
+            let mut buf = chunk.to_vec();
+            buf.resize(F::SIZE, 0);
+            F::from_uniform_bytes(&buf)
+        })
+        .collect()
+}
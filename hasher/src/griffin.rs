@@ -0,0 +1,125 @@
+use arith::Field;
+
+use crate::{
+    field_hash_utils::{
+        bytes_to_field_elems, get_constants, inv_5_exponent, is_square, pow_limbs, Alpha5Field,
+    },
+    FiatShamirHasher,
+};
+
+/// Griffin over a three-cell state with d = 5.
+///
+/// The hasher is a sponge of rate 2 and capacity 1, the capacity cell being initialized with
+/// the input length in bytes. The round constants and (alpha, beta) are derived from a keccak
+/// chain, the same way as for MiMC5 and Poseidon.
+#[derive(Debug, Clone, Default)]
+pub struct GriffinFiatShamirHasher<F: Field> {
+    /// constants added after every round except for the last one
+    round_constants: Vec<[F; 3]>,
+
+    /// alpha^2 - 4 * beta is a non-square, so that the third cell's multiplier never vanishes
+    alpha: F,
+    beta: F,
+    d_inv: [u64; 4],
+}
+
+const GRIFFIN_SEED_ROUND_CONSTANTS: &str = "griffin_seed_round_constants";
+const GRIFFIN_SEED_ALPHA_BETA: &str = "griffin_seed_alpha_beta";
+
+impl<F: Field> GriffinFiatShamirHasher<F> {
+    /// Number of rounds for 128-bit security with t = 3 and d = 5.
+    pub const ROUNDS: usize = 14;
+
+    pub const STATE_WIDTH: usize = 3;
+
+    pub const RATE: usize = 2;
+
+    #[inline(always)]
+    fn linear_layer(state: &mut [F; 3]) {
+        // circ(2, 1, 1)
+        let sum = state[0] + state[1] + state[2];
+        state.iter_mut().for_each(|x| *x += sum);
+    }
+
+    #[inline(always)]
+    fn non_linear_layer(&self, state: &mut [F; 3]) {
+        state[0] = pow_limbs(&state[0], &self.d_inv);
+        state[1] = state[1].square().square() * state[1];
+
+        // L_2(y_0, y_1, 0) = gamma_2 * y_0 + y_1 with gamma_2 = 1
+        let l = state[0] + state[1];
+        state[2] *= l.square() + self.alpha * l + self.beta;
+    }
+
+    pub fn permute(&self, state: &mut [F; 3]) {
+        Self::linear_layer(state);
+        for round in 0..Self::ROUNDS {
+            self.non_linear_layer(state);
+            Self::linear_layer(state);
+            if let Some(rc) = self.round_constants.get(round) {
+                state.iter_mut().zip(rc.iter()).for_each(|(x, c)| *x += c);
+            }
+        }
+    }
+
+    fn hash_u8_to_state(&self, input: &[u8]) -> F {
+        let mut state = [F::ZERO, F::ZERO, F::from(input.len() as u32)];
+        let elems = bytes_to_field_elems::<F>(input);
+        if elems.is_empty() {
+            self.permute(&mut state);
+        }
+        elems.chunks(Self::RATE).for_each(|chunk| {
+            state
+                .iter_mut()
+                .zip(chunk.iter())
+                .for_each(|(x, e)| *x += e);
+            self.permute(&mut state);
+        });
+        state[0]
+    }
+}
+
+impl<F: Alpha5Field> FiatShamirHasher for GriffinFiatShamirHasher<F> {
+    const NAME: &'static str = "Griffin_Field_Hasher";
+
+    const DIGEST_SIZE: usize = F::SIZE;
+
+    fn new() -> Self {
+        let round_constants = get_constants::<F>(
+            GRIFFIN_SEED_ROUND_CONSTANTS,
+            (Self::ROUNDS - 1) * Self::STATE_WIDTH,
+        )
+        .chunks_exact(Self::STATE_WIDTH)
+        .map(|c| [c[0], c[1], c[2]])
+        .collect();
+
+        // the first candidate pair that makes alpha^2 - 4 * beta a non-square
+        let (alpha, beta) = get_constants::<F>(GRIFFIN_SEED_ALPHA_BETA, 128)
+            .chunks_exact(2)
+            .map(|c| (c[0], c[1]))
+            .find(|(alpha, beta)| {
+                let disc = alpha.square() - F::from(4u32) * beta;
+                disc != F::ZERO && !is_square(&disc)
+            })
+            .expect("failed to sample Griffin alpha and beta");
+
+        Self {
+            round_constants,
+            alpha,
+            beta,
+            d_inv: inv_5_exponent::<F>(),
+        }
+    }
+
+    fn hash(&self, output: &mut [u8], input: &[u8]) {
+        assert!(output.len() == F::SIZE);
+        let res = self.hash_u8_to_state(input);
+        res.to_bytes(output);
+    }
+
+    fn hash_inplace(&self, buffer: &mut [u8]) {
+        assert!(buffer.len() == F::SIZE);
+        let res = self.hash_u8_to_state(buffer);
+        res.to_bytes(buffer);
+    }
+}
@@ -5,10 +5,27 @@ pub use traits::{FiatShamirHasher, PoseidonStateTrait};
 
 // field hashers
 
+#[cfg(any(feature = "anemoi", feature = "griffin", feature = "mimc"))]
+pub mod field_hash_utils;
+
+#[cfg(feature = "anemoi")]
+mod anemoi;
+#[cfg(feature = "anemoi")]
+pub use anemoi::AnemoiFiatShamirHasher;
+
+#[cfg(feature = "griffin")]
+mod griffin;
+#[cfg(feature = "griffin")]
+pub use griffin::GriffinFiatShamirHasher;
+
+#[cfg(feature = "mimc")]
 mod mimc;
+#[cfg(feature = "mimc")]
 pub use mimc::MiMC5FiatShamirHasher;
 
+#[cfg(feature = "poseidon")]
 pub mod poseidon;
+#[cfg(feature = "poseidon")]
 pub use poseidon::PoseidonFiatShamirHasher;
 
 // byte hashers
@@ -16,8 +33,13 @@ pub use poseidon::PoseidonFiatShamirHasher;
 pub mod sha2_256;
 pub use sha2_256::SHA256hasher;
 
+#[cfg(feature = "keccak")]
 pub mod keccak_256;
+#[cfg(feature = "keccak")]
 pub use keccak_256::Keccak256hasher;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "mimc"))]
 mod mimc_test;
+
+#[cfg(all(test, feature = "anemoi", feature = "griffin"))]
+mod anemoi_griffin_test;
@@ -1,8 +1,7 @@
 use arith::Field;
 use halo2curves::bn256::Fr;
-use tiny_keccak::{Hasher, Keccak};
 
-use crate::FiatShamirHasher;
+use crate::{field_hash_utils::get_constants, FiatShamirHasher};
 
 #[derive(Debug, Clone, Default)]
 pub struct MiMC5FiatShamirHasher<F: Field> {
@@ -83,25 +82,3 @@ pub fn generate_mimc_constants<F: Field>() -> Vec<F> {
     };
     get_constants(SEED, n_rounds)
 }
-
-pub fn get_constants<F: Field>(seed: &str, n_rounds: usize) -> Vec<F> {
-    let mut keccak = Keccak::v256();
-    let mut h = [0u8; 32];
-    keccak.update(seed.as_bytes());
-    keccak.finalize(&mut h);
-
-    (0..n_rounds)
-        .map(|_| {
-            let mut keccak = Keccak::v256();
-            keccak.update(&h);
-            keccak.finalize(&mut h);
-
-            // big endian -> little endian, in order to match the one in gnark
-            // or probably we can change the implementation there
-            let mut h_reverse = h;
-            h_reverse.reverse();
-
-            F::from_uniform_bytes(&h_reverse)
-        })
-        .collect()
-}
@@ -0,0 +1,77 @@
+use std::hint::black_box;
+
+use arith::{Field, SimdField};
+use ark_std::test_rng;
+use babybear::{BabyBear, BabyBearx16};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use gf2::{GF2x128, GF2x8, GF2};
+use goldilocks::{Goldilocks, Goldilocksx8};
+use mersenne31::{M31x16, M31};
+use poly_commit::*;
+use polynomials::MultiLinearPoly;
+use tynm::type_name;
+
+/// The commitments of Brakedown and of Orion to the same polynomials, side by side
+fn simd_field_committing_comparison_helper<F, SimdF, ComPackF>(
+    c: &mut Criterion,
+    lowest_num_vars: usize,
+    highest_num_vars: usize,
+) where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+    ComPackF: SimdField<Scalar = F>,
+{
+    let mut group = c.benchmark_group(format!(
+        "Brakedown vs Orion PCS SIMD field committing: F = {}, SIMD-F = {}, ComPackF = {}",
+        type_name::<F>(),
+        type_name::<SimdF>(),
+        type_name::<ComPackF>(),
+    ));
+
+    let mut rng = test_rng();
+    let mut scratch_pad = OrionScratchPad::default();
+
+    for num_vars in lowest_num_vars..=highest_num_vars {
+        let packed_num_vars = num_vars - SimdF::PACK_SIZE.ilog2() as usize;
+        let poly = MultiLinearPoly::<SimdF>::random(packed_num_vars, &mut rng);
+
+        for (name, code_instance) in [
+            ("Brakedown", BRAKEDOWN_CODE_PARAMETER_INSTANCE),
+            ("Orion", ORION_CODE_PARAMETER_INSTANCE),
+        ] {
+            let (srs, _) = OrionSRS::from_random(
+                1,
+                num_vars,
+                F::FIELD_SIZE,
+                ComPackF::PACK_SIZE,
+                code_instance,
+                &mut rng,
+            );
+
+            group
+                .bench_function(BenchmarkId::new(name, num_vars), |b| {
+                    b.iter(|| {
+                        _ = black_box(
+                            orion_commit_simd_field::<_, SimdF, ComPackF>(
+                                &srs,
+                                &poly,
+                                &mut scratch_pad,
+                            )
+                            .unwrap(),
+                        )
+                    })
+                })
+                .sample_size(10);
+        }
+    }
+}
+
+fn brakedown_orion_committing_comparison(c: &mut Criterion) {
+    simd_field_committing_comparison_helper::<GF2, GF2x8, GF2x128>(c, 19, 30);
+    simd_field_committing_comparison_helper::<M31, M31x16, M31x16>(c, 19, 25);
+    simd_field_committing_comparison_helper::<Goldilocks, Goldilocksx8, Goldilocksx8>(c, 19, 25);
+    simd_field_committing_comparison_helper::<BabyBear, BabyBearx16, BabyBearx16>(c, 19, 25);
+}
+
+criterion_group!(bench, brakedown_orion_committing_comparison);
+criterion_main!(bench);
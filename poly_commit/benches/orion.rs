@@ -2,6 +2,7 @@ use std::hint::black_box;
 
 use arith::{ExtensionField, Field, SimdField};
 use ark_std::test_rng;
+use babybear::{BabyBear, BabyBearExt3, BabyBearx16};
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use gf2::{GF2x128, GF2x8, GF2};
 use gf2_128::GF2_128;
@@ -75,6 +76,7 @@ fn orion_simd_field_committing_benchmark(c: &mut Criterion) {
     simd_field_committing_benchmark_helper::<GF2, GF2x8, GF2x128>(c, 19, 32);
     simd_field_committing_benchmark_helper::<M31, M31x16, M31x16>(c, 19, 27);
     simd_field_committing_benchmark_helper::<Goldilocks, Goldilocksx8, Goldilocksx8>(c, 19, 27);
+    simd_field_committing_benchmark_helper::<BabyBear, BabyBearx16, BabyBearx16>(c, 19, 27);
 }
 
 fn simd_field_opening_benchmark_helper<F, SimdF, EvalF, ComPackF, T>(
@@ -164,6 +166,13 @@ fn orion_simd_field_opening_benchmark(c: &mut Criterion) {
         Goldilocksx8,
         BytesHashTranscript<Keccak256hasher>,
     >(c, 19, 27);
+    simd_field_opening_benchmark_helper::<
+        BabyBear,
+        BabyBearx16,
+        BabyBearExt3,
+        BabyBearx16,
+        BytesHashTranscript<Keccak256hasher>,
+    >(c, 19, 27);
 }
 
 criterion_group!(
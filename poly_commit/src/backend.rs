@@ -0,0 +1,703 @@
+//! The curve operations of the KZG, Hyrax and Dory commitments that call into the curve library.
+//!
+//! The commitments are otherwise written against the `ff`, `group` and `pairing` traits. The
+//! multi-scalar multiplications and the pairing checks, where nearly all the time goes, are only
+//! ever run through the functions of this module, which run them with the `CurveBackend` of
+//! `SelectedBackend`.
+//!
+//! `Halo2curvesBackend` is the default. With the `arkworks` feature, the MSMs and the pairing
+//! checks over BN254 run on `ark-bn254` instead, through `ArkworksBackend`.
+//!
+//! This is a backend for these two operations only, not a swap of the curve library:
+//! - `halo2curves` stays a dependency, and the points, scalars and SRS of the public API of the
+//!   commitments stay the `halo2curves` types, which the scalar fields of the BN254 configs in
+//!   `arith` and `gkr_engine` are. Users of `arkworks` convert their types at the API.
+//! - Each MSM and pairing check converts all of its points and scalars to `ark-bn254` and back, in
+//!   O(n) on top of the operation itself.
+//! - The MSMs on `FixedBaseTables` and the Hyrax windows stay on `halo2curves`, or are bypassed for
+//!   the backend MSM, see below.
+//! - The Miller loops of Dory, whose results are kept before the final exponentiation, stay on
+//!   `halo2curves`.
+//!
+//! The commitments to many vectors against the same bases run on the `FixedBaseTables` of the
+//! bases, see `coeff_form_uni_kzg_commit_with_tables` for the KZG commitments. The Hyrax
+//! commitments always do, from the windows of `fixed_base_msm_precompute` in their parameters,
+//! except with the `arkworks` feature, where they run on the backend MSM.
+//!
+//! With the `oblivious` feature, the MSMs of the prover are run by `oblivious_msm_serial`, whose
+//! memory accesses and curve operations do not depend on the scalars, unlike the buckets of the
+//! Pippenger MSM indexed by the digits of the scalars.
+use halo2curves::{pairing::MultiMillerLoop, CurveAffine};
+
+/// The curve library running the MSMs and the pairing checks of the commitments.
+pub trait CurveBackend {
+    /// sum_i scalars[i] * bases[i], on the rayon thread pool
+    fn msm<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C]) -> C::Curve;
+
+    /// acc += sum_i scalars[i] * bases[i], on the current thread
+    fn msm_serial<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C], acc: &mut C::Curve);
+
+    /// Whether the product of the pairings e(g1_i, g2_i) is the identity
+    fn pairing_check<E: MultiMillerLoop>(terms: &[(E::G1Affine, E::G2Affine)]) -> bool;
+}
+
+/// The MSMs and pairings of `halo2curves`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Halo2curvesBackend;
+
+impl CurveBackend for Halo2curvesBackend {
+    #[inline(always)]
+    fn msm<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C]) -> C::Curve {
+        halo2curves::msm::best_multiexp(scalars, bases)
+    }
+
+    #[inline(always)]
+    fn msm_serial<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C], acc: &mut C::Curve) {
+        halo2curves::msm::multiexp_serial(scalars, bases, acc)
+    }
+
+    fn pairing_check<E: MultiMillerLoop>(terms: &[(E::G1Affine, E::G2Affine)]) -> bool {
+        use halo2curves::pairing::MillerLoopResult;
+
+        let g2s: Vec<E::G2Prepared> = terms
+            .iter()
+            .map(|(_, g2)| E::G2Prepared::from(*g2))
+            .collect();
+        let terms: Vec<_> = terms.iter().map(|(g1, _)| g1).zip(&g2s).collect();
+        E::multi_miller_loop(&terms)
+            .final_exponentiation()
+            .is_identity()
+            .into()
+    }
+}
+
+/// The MSMs and pairings of `ark-bn254` over BN254, and of `halo2curves` over the other curves
+#[cfg(feature = "arkworks")]
+pub use ark::ArkworksBackend;
+
+/// The backend of the commitments, `ArkworksBackend` with the `arkworks` feature, or else
+/// `Halo2curvesBackend`
+#[cfg(not(feature = "arkworks"))]
+pub type SelectedBackend = Halo2curvesBackend;
+
+/// The backend of the commitments, `ArkworksBackend` with the `arkworks` feature, or else
+/// `Halo2curvesBackend`
+#[cfg(feature = "arkworks")]
+pub type SelectedBackend = ArkworksBackend;
+
+/// `values` as a slice of `T`, if `S` is `T`
+#[cfg(feature = "arkworks")]
+fn cast_slice<S: 'static, T: 'static>(values: &[S]) -> Option<&[T]> {
+    (std::any::TypeId::of::<S>() == std::any::TypeId::of::<T>()).then(|| {
+        // SAFETY: S and T are the same type
+        unsafe { std::slice::from_raw_parts(values.as_ptr() as *const T, values.len()) }
+    })
+}
+
+#[cfg(feature = "arkworks")]
+mod ark {
+    use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+    use ark_ff::{BigInt, BigInteger, PrimeField as _};
+    use ark_std::Zero;
+    use halo2curves::{
+        bn256::{Fq, Fq2, Fr, G1Affine, G2Affine, G1},
+        ff::PrimeField,
+        group::prime::PrimeCurveAffine,
+        pairing::MultiMillerLoop,
+        CurveAffine,
+    };
+
+    use super::{cast_slice, CurveBackend, Halo2curvesBackend};
+
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct ArkworksBackend;
+
+    fn to_ark_fq(x: &Fq) -> ark_bn254::Fq {
+        ark_bn254::Fq::from_le_bytes_mod_order(x.to_repr().as_ref())
+    }
+
+    fn from_ark_fq(x: &ark_bn254::Fq) -> Fq {
+        let mut repr = <Fq as PrimeField>::Repr::default();
+        repr.as_mut()
+            .copy_from_slice(&x.into_bigint().to_bytes_le());
+        Fq::from_repr(repr).unwrap()
+    }
+
+    fn to_ark_fq2(x: &Fq2) -> ark_bn254::Fq2 {
+        ark_bn254::Fq2::new(to_ark_fq(&x.c0), to_ark_fq(&x.c1))
+    }
+
+    /// The little endian limbs of the canonical form of `scalar`
+    fn to_ark_bigint(scalar: &Fr) -> BigInt<4> {
+        let repr = scalar.to_repr();
+        let mut limbs = [0u64; 4];
+        limbs
+            .iter_mut()
+            .zip(repr.as_ref().chunks_exact(8))
+            .for_each(|(limb, bytes)| *limb = u64::from_le_bytes(bytes.try_into().unwrap()));
+        BigInt(limbs)
+    }
+
+    fn to_ark_g1(p: &G1Affine) -> ark_bn254::G1Affine {
+        if bool::from(p.is_identity()) {
+            return ark_bn254::G1Affine::zero();
+        }
+        ark_bn254::G1Affine::new_unchecked(to_ark_fq(&p.x), to_ark_fq(&p.y))
+    }
+
+    fn to_ark_g2(p: &G2Affine) -> ark_bn254::G2Affine {
+        if bool::from(p.is_identity()) {
+            return ark_bn254::G2Affine::zero();
+        }
+        ark_bn254::G2Affine::new_unchecked(to_ark_fq2(&p.x), to_ark_fq2(&p.y))
+    }
+
+    fn from_ark_g1(p: &ark_bn254::G1Projective) -> G1 {
+        match p.into_affine().xy() {
+            Some((x, y)) => G1Affine {
+                x: from_ark_fq(x),
+                y: from_ark_fq(y),
+            }
+            .to_curve(),
+            None => G1Affine::identity().to_curve(),
+        }
+    }
+
+    /// The MSM on `ark-bn254`, if `C` is the G1 of BN254
+    fn bn254_msm<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C]) -> Option<C::Curve> {
+        let bases = cast_slice::<C, G1Affine>(bases)?;
+        let scalars = cast_slice::<C::Scalar, Fr>(scalars)?;
+
+        let n_terms = scalars.len().min(bases.len());
+        let bases: Vec<_> = bases[..n_terms].iter().map(to_ark_g1).collect();
+        let scalars: Vec<_> = scalars[..n_terms].iter().map(to_ark_bigint).collect();
+        let res = from_ark_g1(&ark_bn254::G1Projective::msm_bigint(&bases, &scalars));
+        cast_slice::<G1, C::Curve>(&[res]).map(|res| res[0])
+    }
+
+    impl CurveBackend for ArkworksBackend {
+        fn msm<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C]) -> C::Curve {
+            bn254_msm(scalars, bases).unwrap_or_else(|| Halo2curvesBackend::msm(scalars, bases))
+        }
+
+        fn msm_serial<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C], acc: &mut C::Curve) {
+            match bn254_msm(scalars, bases) {
+                Some(res) => *acc += res,
+                None => Halo2curvesBackend::msm_serial(scalars, bases, acc),
+            }
+        }
+
+        fn pairing_check<E: MultiMillerLoop>(terms: &[(E::G1Affine, E::G2Affine)]) -> bool {
+            let Some(bn254_terms) = cast_slice::<_, (G1Affine, G2Affine)>(terms) else {
+                return Halo2curvesBackend::pairing_check::<E>(terms);
+            };
+
+            let g1s: Vec<_> = bn254_terms.iter().map(|(g1, _)| to_ark_g1(g1)).collect();
+            let g2s: Vec<_> = bn254_terms.iter().map(|(_, g2)| to_ark_g2(g2)).collect();
+            ark_bn254::Bn254::multi_pairing(g1s, g2s).is_zero()
+        }
+    }
+}
+
+/// sum_i scalars[i] * bases[i], on the rayon thread pool
+#[inline(always)]
+pub(crate) fn msm<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C]) -> C::Curve {
+    #[cfg(feature = "oblivious")]
+    {
+        oblivious_msm(scalars, bases)
+    }
+    #[cfg(not(feature = "oblivious"))]
+    {
+        SelectedBackend::msm(scalars, bases)
+    }
+}
+
+/// acc += sum_i scalars[i] * bases[i], on the current thread
+#[cfg(any(feature = "kzg", feature = "hyrax"))]
+#[inline(always)]
+pub(crate) fn msm_serial<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C], acc: &mut C::Curve) {
+    #[cfg(feature = "oblivious")]
+    {
+        oblivious_msm_serial(scalars, bases, acc)
+    }
+    #[cfg(not(feature = "oblivious"))]
+    {
+        SelectedBackend::msm_serial(scalars, bases, acc)
+    }
+}
+
+/// The affine points of `proj`, normalized in batches on the rayon thread pool, each batch with
+/// a single field inversion. Curve points are accumulated in projective coordinates, and
+/// converted once at the end with this, rather than one by one with `to_affine`.
+pub fn batch_to_affine<C: CurveAffine>(proj: &[C::Curve]) -> Vec<C> {
+    use halo2curves::group::Curve;
+    use rayon::prelude::*;
+
+    let mut affine = vec![C::default(); proj.len()];
+    let chunk_len = proj.len().div_ceil(rayon::current_num_threads()).max(1);
+    affine
+        .par_chunks_mut(chunk_len)
+        .zip(proj.par_chunks(chunk_len))
+        .for_each(|(affine, proj)| C::Curve::batch_normalize(proj, affine));
+    affine
+}
+
+/// The windows of the fixed-base MSM over `bases`, see `fixed_base_msm_serial`.
+#[cfg(feature = "hyrax")]
+#[inline(always)]
+pub(crate) fn fixed_base_msm_precompute<C>(bases: &[C], window_bits: usize) -> Vec<C::Curve>
+where
+    C: CurveAffine,
+    C::Scalar: halo2curves::ff::PrimeField,
+{
+    halo2curves::msm::multiexp_precompute(bases, window_bits)
+}
+
+/// acc += sum_i scalars[i] * bases[i], from the windows `fixed_base_msm_precompute` computed
+/// over `bases`
+#[cfg(feature = "hyrax")]
+#[inline(always)]
+pub(crate) fn fixed_base_msm_serial<C>(
+    scalars: &[C::Scalar],
+    bases: &[C],
+    pre_bases: &[C::Curve],
+    window_bits: usize,
+    acc: &mut C::Curve,
+) where
+    C: CurveAffine,
+    C::Scalar: halo2curves::ff::PrimeField,
+{
+    // the windows are looked up at the digits of the scalars, and are not those of the MSMs of
+    // the arkworks backend
+    #[cfg(any(feature = "oblivious", feature = "arkworks"))]
+    {
+        let _ = (pre_bases, window_bits);
+        msm_serial(scalars, bases, acc)
+    }
+    #[cfg(not(any(feature = "oblivious", feature = "arkworks")))]
+    {
+        let _ = bases;
+        halo2curves::msm::multiexp_precompute_serial::<C>(scalars, pre_bases, window_bits, acc)
+    }
+}
+
+/// Windowed tables of fixed bases, built once to commit to many vectors against the same SRS.
+///
+/// The table of a base holds its multiples by the powers of 2^window_bits, so an MSM over the
+/// bases is a single pass of bucket additions over the digits of all the scalars, without the
+/// doublings and the per window bucket sums of the Pippenger MSM. It pays off when the vectors
+/// are short relative to the window, as for the folded polynomials of the KZG openings, at the
+/// cost of a table of `C::Scalar::NUM_BITS / window_bits` points for each base.
+///
+/// The MSMs over a prefix of the bases, e.g., of the powers of tau for a polynomial shorter than
+/// the SRS, read the prefix of the tables.
+#[derive(Clone, Debug)]
+pub struct FixedBaseTables<C: CurveAffine> {
+    window_bits: usize,
+    num_windows: usize,
+    /// 2^(window_bits * j) * bases[i] at i * num_windows + j
+    points: Vec<C>,
+}
+
+impl<C: CurveAffine> FixedBaseTables<C> {
+    /// The tables of `bases`, of digits of `window_bits` bits, between 1 and 20.
+    pub fn new(bases: &[C], window_bits: usize) -> Self {
+        use halo2curves::{ff::PrimeField, group::Group};
+        use rayon::prelude::*;
+
+        assert!((1..=20).contains(&window_bits));
+        let num_windows = (C::Scalar::NUM_BITS as usize).div_ceil(window_bits);
+
+        let proj: Vec<C::Curve> = bases
+            .par_iter()
+            .flat_map_iter(|base| {
+                let mut multiple = base.to_curve();
+                (0..num_windows).map(move |_| {
+                    let current = multiple;
+                    (0..window_bits).for_each(|_| multiple = multiple.double());
+                    current
+                })
+            })
+            .collect();
+
+        Self {
+            window_bits,
+            num_windows,
+            points: batch_to_affine(&proj),
+        }
+    }
+
+    /// The number of bases
+    pub fn len(&self) -> usize {
+        self.points.len() / self.num_windows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// sum_i scalars[i] * bases[i], on the rayon thread pool, for at most `len` scalars
+    pub fn msm(&self, scalars: &[C::Scalar]) -> C::Curve {
+        assert!(scalars.len() <= self.len());
+
+        #[cfg(feature = "oblivious")]
+        {
+            // the buckets are indexed by the digits of the scalars
+            let bases: Vec<C> = self
+                .points
+                .iter()
+                .step_by(self.num_windows)
+                .take(scalars.len())
+                .copied()
+                .collect();
+            oblivious_msm(scalars, &bases)
+        }
+        #[cfg(not(feature = "oblivious"))]
+        {
+            use halo2curves::group::Group;
+            use rayon::prelude::*;
+
+            // as many terms in a chunk as the additions summing its buckets
+            let min_chunk_len = (2usize << self.window_bits).div_ceil(self.num_windows);
+            let chunk_len = scalars
+                .len()
+                .div_ceil(rayon::current_num_threads())
+                .max(min_chunk_len);
+            scalars
+                .par_chunks(chunk_len)
+                .zip(self.points.par_chunks(chunk_len * self.num_windows))
+                .map(|(scalars, points)| self.msm_serial(scalars, points))
+                .reduce(C::Curve::identity, |a, b| a + b)
+        }
+    }
+
+    /// sum_i scalars[i] * bases[i], from the tables `points` of the bases
+    #[cfg(not(feature = "oblivious"))]
+    fn msm_serial(&self, scalars: &[C::Scalar], points: &[C]) -> C::Curve {
+        use halo2curves::{ff::PrimeField, group::Group};
+
+        let mut buckets = vec![C::Curve::identity(); (1 << self.window_bits) - 1];
+        for (scalar, table) in scalars.iter().zip(points.chunks(self.num_windows)) {
+            let repr = scalar.to_repr();
+            for (window, point) in table.iter().enumerate() {
+                let digit =
+                    window_digit(repr.as_ref(), window * self.window_bits, self.window_bits);
+                if digit != 0 {
+                    buckets[digit - 1] += *point;
+                }
+            }
+        }
+
+        // sum_d d * buckets[d - 1], as the sum of the running sums from the top bucket
+        let mut running_sum = C::Curve::identity();
+        let mut sum = C::Curve::identity();
+        for bucket in buckets.iter().rev() {
+            running_sum += bucket;
+            sum += running_sum;
+        }
+        sum
+    }
+}
+
+/// The digit of `window_bits` bits at `bit_offset` of the little endian bytes `repr`
+#[cfg(not(feature = "oblivious"))]
+#[inline(always)]
+fn window_digit(repr: &[u8], bit_offset: usize, window_bits: usize) -> usize {
+    let num_bytes = (bit_offset % 8 + window_bits).div_ceil(8);
+    let bytes = repr[bit_offset / 8..].iter().take(num_bytes);
+    let word = bytes.enumerate().fold(0usize, |word, (k, byte)| {
+        word | ((*byte as usize) << (8 * k))
+    });
+    (word >> (bit_offset % 8)) & ((1 << window_bits) - 1)
+}
+
+/// The number of bits of the digits of the scalars in `oblivious_msm_serial`
+#[cfg(feature = "oblivious")]
+const OBLIVIOUS_WINDOW_BITS: usize = 4;
+
+/// sum_i scalars[i] * bases[i], with `oblivious_msm_serial` on chunks of the terms spread over
+/// the rayon thread pool
+#[cfg(feature = "oblivious")]
+fn oblivious_msm<C: CurveAffine>(scalars: &[C::Scalar], bases: &[C]) -> C::Curve {
+    use halo2curves::group::Group;
+    use rayon::prelude::*;
+
+    let n_terms = scalars.len().min(bases.len());
+    let chunk_len = n_terms.div_ceil(rayon::current_num_threads()).max(1);
+    scalars[..n_terms]
+        .par_chunks(chunk_len)
+        .zip(bases[..n_terms].par_chunks(chunk_len))
+        .map(|(scalars, bases)| {
+            let mut acc = C::Curve::identity();
+            oblivious_msm_serial(scalars, bases, &mut acc);
+            acc
+        })
+        .reduce(C::Curve::identity, |a, b| a + b)
+}
+
+/// acc += sum_i scalars[i] * bases[i], by fixed windows of `OBLIVIOUS_WINDOW_BITS` bits.
+///
+/// The multiple of a base for the digit of a window is selected by a scan of all the multiples
+/// of the base, in constant time, and added even when the digit is zero. The same memory is
+/// then read and the same curve operations run whatever the scalars, at the cost of about
+/// twice the additions of the Pippenger MSM, and the scan of the tables.
+#[cfg(feature = "oblivious")]
+pub(crate) fn oblivious_msm_serial<C: CurveAffine>(
+    scalars: &[C::Scalar],
+    bases: &[C],
+    acc: &mut C::Curve,
+) {
+    use halo2curves::{
+        ff::PrimeField,
+        group::{prime::PrimeCurveAffine, Curve, Group},
+    };
+    use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+    const TABLE_LEN: usize = 1 << OBLIVIOUS_WINDOW_BITS;
+
+    // 0 * base, 1 * base, ..., (TABLE_LEN - 1) * base, of each base
+    let tables: Vec<Vec<C>> = bases
+        .iter()
+        .take(scalars.len())
+        .map(|base| {
+            let mut multiples = vec![C::Curve::identity()];
+            (1..TABLE_LEN).for_each(|j| multiples.push(multiples[j - 1] + *base));
+            let mut table = vec![C::identity(); TABLE_LEN];
+            C::Curve::batch_normalize(&multiples, &mut table);
+            table
+        })
+        .collect();
+
+    // the little endian bytes of the scalars, a byte holding two digits
+    let reprs: Vec<_> = scalars.iter().map(|scalar| scalar.to_repr()).collect();
+    let n_windows = (C::Scalar::NUM_BITS as usize).div_ceil(OBLIVIOUS_WINDOW_BITS);
+
+    let mut sum = C::Curve::identity();
+    for window in (0..n_windows).rev() {
+        (0..OBLIVIOUS_WINDOW_BITS).for_each(|_| sum = sum.double());
+
+        let bit_offset = window * OBLIVIOUS_WINDOW_BITS;
+        for (repr, table) in reprs.iter().zip(&tables) {
+            let byte = repr.as_ref()[bit_offset / 8];
+            let digit = (byte >> (bit_offset % 8)) & (TABLE_LEN as u8 - 1);
+
+            let mut multiple = C::identity();
+            table
+                .iter()
+                .enumerate()
+                .for_each(|(j, entry)| multiple.conditional_assign(entry, (j as u8).ct_eq(&digit)));
+            sum += multiple;
+        }
+    }
+    *acc += sum;
+}
+
+/// Whether the product of the pairings e(g1_i, g2_i) is the identity.
+#[cfg(feature = "kzg")]
+#[inline(always)]
+pub(crate) fn pairing_check<E: MultiMillerLoop>(terms: &[(E::G1Affine, E::G2Affine)]) -> bool {
+    SelectedBackend::pairing_check::<E>(terms)
+}
+
+/// prod_i e(g1s[i], g2s[i]) without the final exponentiation, on the rayon thread pool
+#[cfg(feature = "dory")]
+pub(crate) fn multi_miller_loop<E: MultiMillerLoop>(
+    g1s: &[E::G1Affine],
+    g2s: &[E::G2Affine],
+) -> E::Result {
+    use rayon::prelude::*;
+
+    assert_eq!(g1s.len(), g2s.len());
+    let chunk_len = g1s.len().div_ceil(rayon::current_num_threads()).max(1);
+    g1s.par_chunks(chunk_len)
+        .zip(g2s.par_chunks(chunk_len))
+        .map(|(g1s, g2s)| {
+            let g2s: Vec<E::G2Prepared> = g2s.iter().map(|g2| E::G2Prepared::from(*g2)).collect();
+            let terms: Vec<_> = g1s.iter().zip(&g2s).collect();
+            E::multi_miller_loop(&terms)
+        })
+        .reduce(|| E::multi_miller_loop(&[]), |a, b| a + b)
+}
+
+/// Pairing checks collected to be verified with a single multi-pairing.
+///
+/// A check is that the product of the pairings e(g1_i, g2_i) of its terms is the identity, e.g.,
+/// e(A, B) = e(C, D) is the check of (A, B) and (-C, D). The checks are combined with the powers
+/// of a challenge drawn from the digest of all their terms, and the terms sharing a G2 point are
+/// merged, so that verifying the checks takes one Miller loop per distinct G2 point and a single
+/// final exponentiation. The KZG verifiers add their checks to an accumulator with their
+/// `_deferred` variants, and aggregators verify many openings at once this way.
+#[cfg(feature = "kzg")]
+#[derive(Debug, Clone)]
+pub struct PairingAccumulator<E: halo2curves::pairing::Engine> {
+    checks: Vec<Vec<(E::G1Affine, E::G2Affine)>>,
+}
+
+/// The accumulator of the pairing checks over BN254
+#[cfg(feature = "kzg")]
+pub type Bn254PairingAccumulator = PairingAccumulator<halo2curves::bn256::Bn256>;
+
+#[cfg(feature = "kzg")]
+impl<E: halo2curves::pairing::Engine> Default for PairingAccumulator<E> {
+    fn default() -> Self {
+        Self { checks: vec![] }
+    }
+}
+
+#[cfg(feature = "kzg")]
+impl<E: halo2curves::pairing::Engine> PairingAccumulator<E> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the check that the product of the pairings of `terms` is the identity
+    #[inline]
+    pub fn add_check(&mut self, terms: &[(E::G1Affine, E::G2Affine)]) {
+        self.checks.push(terms.to_vec());
+    }
+
+    /// Add the check e(a, b) = e(c, d)
+    #[inline]
+    pub fn add_equality(&mut self, a: E::G1Affine, b: E::G2Affine, c: E::G1Affine, d: E::G2Affine) {
+        self.checks.push(vec![(a, b), (-c, d)]);
+    }
+
+    /// Take over the checks of `other`
+    #[inline]
+    pub fn append(&mut self, other: &mut Self) {
+        self.checks.append(&mut other.checks);
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Whether all the checks hold, up to the soundness error of the random combination
+    pub fn check(&self) -> bool
+    where
+        E: MultiMillerLoop,
+        E::Fr: arith::Field,
+    {
+        use halo2curves::group::{Curve, GroupEncoding};
+        use tiny_keccak::{Hasher, Keccak};
+
+        let coefs = match self.checks.len() {
+            0 => return true,
+            1 => vec![<E::Fr as arith::Field>::ONE],
+            n => {
+                let mut hasher = Keccak::v256();
+                for terms in &self.checks {
+                    hasher.update(&(terms.len() as u64).to_le_bytes());
+                    for (g1, g2) in terms {
+                        hasher.update(g1.to_bytes().as_ref());
+                        hasher.update(g2.to_bytes().as_ref());
+                    }
+                }
+                let mut digest = [0u8; 32];
+                hasher.finalize(&mut digest);
+
+                let challenge = <E::Fr as arith::Field>::from_uniform_bytes(&digest);
+                crate::powers_series(&challenge, n)
+            }
+        };
+
+        // the G1 points of the terms sharing a G2 point are summed
+        let mut merged: Vec<(E::G2Affine, E::G1)> = vec![];
+        for (terms, coef) in self.checks.iter().zip(&coefs) {
+            for (g1, g2) in terms {
+                let g1 = *g1 * *coef;
+                match merged.iter_mut().find(|(merged_g2, _)| merged_g2 == g2) {
+                    Some((_, merged_g1)) => *merged_g1 += g1,
+                    None => merged.push((*g2, g1)),
+                }
+            }
+        }
+
+        let terms = merged
+            .iter()
+            .map(|(g2, g1)| (g1.to_affine(), *g2))
+            .collect::<Vec<_>>();
+        pairing_check::<E>(&terms)
+    }
+}
+
+#[cfg(all(test, any(feature = "oblivious", feature = "arkworks")))]
+mod test {
+    use halo2curves::{
+        bn256::{Fr, G1Affine, G1},
+        ff::Field,
+        group::{Curve, Group},
+    };
+    use rand::thread_rng;
+
+    #[cfg(feature = "oblivious")]
+    #[test]
+    fn test_oblivious_msm() {
+        use super::oblivious_msm_serial;
+
+        let mut rng = thread_rng();
+        let bases: Vec<G1Affine> = (0..37).map(|_| G1::random(&mut rng).to_affine()).collect();
+        let mut scalars: Vec<Fr> = (0..37).map(|_| Fr::random(&mut rng)).collect();
+        scalars[0] = Fr::ZERO;
+        scalars[1] = Fr::ONE;
+        scalars[2] = -Fr::ONE;
+
+        let expected: G1 = scalars.iter().zip(&bases).map(|(s, b)| *b * s).sum();
+
+        let mut acc = G1::generator();
+        oblivious_msm_serial(&scalars, &bases, &mut acc);
+        assert_eq!(acc, expected + G1::generator());
+        assert_eq!(super::msm(&scalars, &bases), expected);
+    }
+
+    #[cfg(feature = "arkworks")]
+    #[test]
+    fn test_arkworks_backend() {
+        use halo2curves::{
+            bn256::{Bn256, G2Affine, G2},
+            group::prime::PrimeCurveAffine,
+        };
+
+        use super::{ArkworksBackend, CurveBackend, Halo2curvesBackend};
+
+        let mut rng = thread_rng();
+        let bases: Vec<G1Affine> = (0..37).map(|_| G1::random(&mut rng).to_affine()).collect();
+        let mut scalars: Vec<Fr> = (0..37).map(|_| Fr::random(&mut rng)).collect();
+        scalars[0] = Fr::ZERO;
+        scalars[1] = -Fr::ONE;
+
+        let expected = Halo2curvesBackend::msm(&scalars, &bases);
+        assert_eq!(ArkworksBackend::msm(&scalars, &bases), expected);
+        assert_eq!(ArkworksBackend::msm(&[Fr::ZERO], &bases), G1::identity());
+
+        let mut acc = G1::generator();
+        ArkworksBackend::msm_serial(&scalars, &bases, &mut acc);
+        assert_eq!(acc, expected + G1::generator());
+
+        // e(a * g1, g2) = e(g1, a * g2)
+        let a = Fr::random(&mut rng);
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let a_g1 = (g1 * a).to_affine();
+        let a_g2 = (G2::generator() * a).to_affine();
+        assert!(ArkworksBackend::pairing_check::<Bn256>(&[
+            (a_g1, g2),
+            (-g1, a_g2)
+        ]));
+        assert!(!ArkworksBackend::pairing_check::<Bn256>(&[
+            (a_g1, g2),
+            (g1, a_g2)
+        ]));
+        assert!(ArkworksBackend::pairing_check::<Bn256>(&[(
+            G1Affine::identity(),
+            g2
+        )]));
+    }
+}
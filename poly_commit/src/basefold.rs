@@ -0,0 +1,11 @@
+mod utils;
+pub use utils::{BasefoldCommitment, BasefoldProof, BasefoldSRS, BasefoldScratchPad};
+
+mod basefold_impl;
+pub use basefold_impl::{basefold_commit, basefold_open, basefold_verify};
+
+mod pcs_trait_impl;
+pub use pcs_trait_impl::BasefoldPCS;
+
+mod expander_api;
+pub use expander_api::BasefoldPCSForGKR;
@@ -0,0 +1,209 @@
+use arith::{ExtensionField, Field};
+use gkr_engine::Transcript;
+use polynomials::EqPolynomial;
+use tree::{Node, Tree};
+
+use crate::{
+    basefold::{
+        utils::{basefold_query_complexity, leaf_pair, pair_tree},
+        BasefoldCommitment, BasefoldProof, BasefoldSRS, BasefoldScratchPad,
+    },
+    utils::{fold_evals, interpolate_at, sumcheck_round_message},
+    PCS_SOUNDNESS_BITS,
+};
+
+/// Commit to the evaluations of a multilinear polynomial over the hypercube, by the root of the
+/// tree over the pairs of entries of its codeword
+pub fn basefold_commit<F: Field>(
+    srs: &BasefoldSRS<F>,
+    evals: &[F],
+    scratch_pad: &mut BasefoldScratchPad,
+) -> BasefoldCommitment {
+    assert_eq!(evals.len(), 1 << srs.num_vars);
+
+    let codeword = srs.encode(evals);
+    scratch_pad.codeword_tree = pair_tree(&codeword);
+    scratch_pad.codeword_tree.root()
+}
+
+/// Open the committed polynomial at `point`, by a sumcheck of `f(x) * eq(x, point)` whose
+/// challenges also fold the codeword, down to the codeword of the polynomial evaluated at the
+/// challenges.
+pub fn basefold_open<F, EvalF>(
+    srs: &BasefoldSRS<F>,
+    evals: &[F],
+    point: &[EvalF],
+    transcript: &mut impl Transcript,
+    scratch_pad: &BasefoldScratchPad,
+) -> (EvalF, BasefoldProof<EvalF>)
+where
+    F: Field,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let num_vars = srs.num_vars;
+    assert_eq!(evals.len(), 1 << num_vars);
+    assert_eq!(point.len(), num_vars);
+
+    let mut sumcheck_messages = Vec::with_capacity(num_vars);
+    let mut fold_trees: Vec<Tree> = Vec::with_capacity(num_vars - 1);
+
+    let mut evals: Vec<EvalF> = evals.iter().map(|&e| EvalF::from(e)).collect();
+    let mut eq_evals = EqPolynomial::build_eq_x_r(point);
+    let eval = evals.iter().zip(&eq_evals).map(|(f, eq)| *f * eq).sum();
+
+    // NOTE: the committed codeword is read back from the leaves, lifted to the extension field
+    let (mut codeword, codeword_hi): (Vec<EvalF>, Vec<EvalF>) = scratch_pad
+        .codeword_tree
+        .leaves
+        .iter()
+        .map(|leaf| {
+            let (lo, hi) = leaf_pair::<F>(leaf).unwrap();
+            (EvalF::from(lo), EvalF::from(hi))
+        })
+        .unzip();
+    codeword.extend(codeword_hi);
+
+    for round in 0..num_vars {
+        let message = sumcheck_round_message(&evals, &eq_evals);
+        message
+            .iter()
+            .for_each(|h| transcript.append_field_element(h));
+        sumcheck_messages.push(message);
+
+        let r = transcript.generate_field_element::<EvalF>();
+        evals = fold_evals(&evals, r);
+        eq_evals = fold_evals(&eq_evals, r);
+        codeword = srs.fold(num_vars - round, &codeword, r);
+
+        if round < num_vars - 1 {
+            let tree = pair_tree(&codeword);
+            transcript.append_u8_slice(tree.root().as_bytes());
+            fold_trees.push(tree);
+        }
+    }
+
+    let final_eval = evals[0];
+    transcript.append_field_element(&final_eval);
+
+    let query_num = basefold_query_complexity(PCS_SOUNDNESS_BITS);
+    let query_indices = transcript.challenge_queries(srs.codeword_len() / 2, query_num, false);
+
+    let query_paths = query_indices
+        .iter()
+        .map(|&index| {
+            std::iter::once(&scratch_pad.codeword_tree)
+                .chain(&fold_trees)
+                .map(|tree| tree.index_query(index % tree.leaves.len()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    (
+        eval,
+        BasefoldProof {
+            sumcheck_messages,
+            fold_roots: fold_trees.iter().map(Tree::root).collect(),
+            final_eval,
+            query_paths,
+        },
+    )
+}
+
+/// Verify the opening of the committed polynomial at `point` to `evaluation`
+pub fn basefold_verify<F, EvalF>(
+    srs: &BasefoldSRS<F>,
+    commitment: &BasefoldCommitment,
+    point: &[EvalF],
+    evaluation: EvalF,
+    transcript: &mut impl Transcript,
+    proof: &BasefoldProof<EvalF>,
+) -> bool
+where
+    F: Field,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let num_vars = srs.num_vars;
+    let query_num = basefold_query_complexity(PCS_SOUNDNESS_BITS);
+    if point.len() != num_vars
+        || proof.sumcheck_messages.len() != num_vars
+        || proof.fold_roots.len() != num_vars - 1
+        || proof.query_paths.len() != query_num
+    {
+        return false;
+    }
+
+    // NOTE: replay the sumcheck, drawing the folding challenges along the way
+    let mut claim = evaluation;
+    let mut challenges = Vec::with_capacity(num_vars);
+    for (round, message) in proof.sumcheck_messages.iter().enumerate() {
+        if message[0] + message[1] != claim {
+            return false;
+        }
+
+        message
+            .iter()
+            .for_each(|h| transcript.append_field_element(h));
+        let r = transcript.generate_field_element::<EvalF>();
+        claim = interpolate_at(message, r);
+        challenges.push(r);
+
+        if round < num_vars - 1 {
+            transcript.append_u8_slice(proof.fold_roots[round].as_bytes());
+        }
+    }
+
+    if claim != proof.final_eval * EqPolynomial::eq_vec(point, &challenges) {
+        return false;
+    }
+    transcript.append_field_element(&proof.final_eval);
+
+    let query_indices = transcript.challenge_queries(srs.codeword_len() / 2, query_num, false);
+
+    // NOTE: check each query along the folds, from the commitment down to the final evaluation
+    let roots: Vec<&Node> = std::iter::once(commitment)
+        .chain(&proof.fold_roots)
+        .collect();
+    query_indices
+        .iter()
+        .zip(&proof.query_paths)
+        .all(|(&index, paths)| {
+            if paths.len() != num_vars {
+                return false;
+            }
+
+            // the entry of the codeword of the next level, folded from the queried pair
+            let mut folded: Option<(usize, EvalF)> = None;
+            for (round, (path, root)) in paths.iter().zip(&roots).enumerate() {
+                let level = num_vars - round;
+                let half_len = BasefoldSRS::<F>::codeword_len_at(level) / 2;
+                let leaf_index = index % half_len;
+                if path.index != leaf_index || path.root() != **root {
+                    return false;
+                }
+
+                let pair = if round == 0 {
+                    leaf_pair::<F>(&path.leaf).map(|(lo, hi)| (EvalF::from(lo), EvalF::from(hi)))
+                } else {
+                    leaf_pair::<EvalF>(&path.leaf)
+                };
+                let Some((lo, hi)) = pair else {
+                    return false;
+                };
+
+                if let Some((position, entry)) = folded {
+                    let queried = if position < half_len { lo } else { hi };
+                    if queried != entry {
+                        return false;
+                    }
+                }
+
+                folded = Some((
+                    leaf_index,
+                    srs.fold_pair(level, leaf_index, lo, hi, challenges[round]),
+                ));
+            }
+
+            // the codeword of a constant repeats it
+            matches!(folded, Some((_, entry)) if entry == proof.final_eval)
+        })
+}
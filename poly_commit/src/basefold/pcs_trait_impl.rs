@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+
+use arith::{ExtensionField, Field};
+use gkr_engine::{StructuredReferenceString, Transcript};
+use polynomials::MultiLinearPoly;
+
+use crate::{
+    basefold::{
+        basefold_impl::{basefold_commit, basefold_open, basefold_verify},
+        BasefoldCommitment, BasefoldProof, BasefoldSRS, BasefoldScratchPad,
+    },
+    PolynomialCommitmentScheme,
+};
+
+impl<F: Field> StructuredReferenceString for BasefoldSRS<F> {
+    type PKey = BasefoldSRS<F>;
+    type VKey = BasefoldSRS<F>;
+
+    fn into_keys(self) -> (Self::PKey, Self::VKey) {
+        (self.clone(), self)
+    }
+}
+
+pub struct BasefoldPCS<F, EvalF>
+where
+    F: Field,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    _marker_f: PhantomData<F>,
+    _marker_eval_f: PhantomData<EvalF>,
+}
+
+impl<F, EvalF> PolynomialCommitmentScheme<EvalF> for BasefoldPCS<F, EvalF>
+where
+    F: Field,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    const NAME: &'static str = "BasefoldPCS";
+
+    type Params = usize;
+    type Poly = MultiLinearPoly<F>;
+    type EvalPoint = Vec<EvalF>;
+    type ScratchPad = BasefoldScratchPad;
+
+    type SRS = BasefoldSRS<F>;
+    type Commitment = BasefoldCommitment;
+    type Opening = BasefoldProof<EvalF>;
+
+    fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
+        (BasefoldSRS::from_random(*params, rng), *params)
+    }
+
+    fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {
+        BasefoldScratchPad::default()
+    }
+
+    fn commit(
+        params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> Self::Commitment {
+        assert_eq!(*params, proving_key.num_vars);
+        basefold_commit(proving_key, &poly.coeffs, scratch_pad)
+    }
+
+    fn open(
+        params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        x: &Self::EvalPoint,
+        scratch_pad: &Self::ScratchPad,
+        transcript: &mut impl Transcript,
+    ) -> (EvalF, Self::Opening) {
+        assert_eq!(*params, proving_key.num_vars);
+        basefold_open(proving_key, &poly.coeffs, x, transcript, scratch_pad)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        vk: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &Self::EvalPoint,
+        v: EvalF,
+        opening: &Self::Opening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        assert_eq!(*params, vk.num_vars);
+        basefold_verify(vk, commitment, x, v, transcript, opening)
+    }
+}
@@ -0,0 +1,239 @@
+use arith::{ExtensionField, Field};
+use rand::RngCore;
+use rayon::join;
+use serdes::ExpSerde;
+use tree::{Leaf, Node, Path, Tree, LEAF_BYTES};
+
+/// The codewords are `2^BASEFOLD_LOG_INV_RATE` times as long as the messages.
+pub const BASEFOLD_LOG_INV_RATE: usize = 3;
+
+/// Below this many message elements the encoding of the two halves stays on the current thread.
+const ENCODE_PAR_THRESHOLD: usize = 1 << 12;
+
+/*
+ * RELEVANT TYPES SETUP
+ */
+
+/// A random foldable code (ZCF24, section 3), shared by the prover and the verifier.
+///
+/// The code of level `0` repeats the message, and a message of level `i + 1`, i.e., the
+/// evaluations `m` of a multilinear polynomial over `i + 1` variables, is encoded as
+/// `(a + t * b, a - t * b)` with `a` the codeword of level `i` of `m(0, .)`, `b` the one of
+/// `m(1, .) - m(0, .)` and `t = twiddles[i]`. Folding the codeword of `m` with `r` thus yields the
+/// codeword of `m(r, .)`, one variable at a time from the lowest one, in step with the sumcheck.
+///
+/// The code is public, there is no trapdoor: any source of randomness makes a good code with
+/// overwhelming probability.
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct BasefoldSRS<F: Field> {
+    pub num_vars: usize,
+    /// `twiddles[i]` has the length of the codewords of level `i`, none of them zero
+    pub twiddles: Vec<Vec<F>>,
+    pub inv_twiddles: Vec<Vec<F>>,
+}
+
+impl<F: Field> BasefoldSRS<F> {
+    /// Sample the code of the polynomials over `num_vars` variables.
+    ///
+    /// # Panics
+    /// If the field has characteristic 2, where the entries `a + t * b` and `a - t * b` coincide,
+    /// or if `num_vars` is zero, as a constant would be committed to without any folding.
+    pub fn from_random(num_vars: usize, mut rng: impl RngCore) -> Self {
+        assert_ne!(
+            F::ONE.double(),
+            F::ZERO,
+            "BaseFold needs an odd characteristic"
+        );
+        assert!(num_vars > 0);
+
+        let twiddles: Vec<Vec<F>> = (0..num_vars)
+            .map(|level| {
+                (0..Self::codeword_len_at(level))
+                    .map(|_| loop {
+                        let t = F::random_unsafe(&mut rng);
+                        if !t.is_zero() {
+                            break t;
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let inv_twiddles = twiddles.iter().map(|ts| batch_inverse(ts)).collect();
+
+        Self {
+            num_vars,
+            twiddles,
+            inv_twiddles,
+        }
+    }
+
+    /// The length of the codewords of the messages over `level` variables
+    #[inline(always)]
+    pub fn codeword_len_at(level: usize) -> usize {
+        1 << (level + BASEFOLD_LOG_INV_RATE)
+    }
+
+    #[inline(always)]
+    pub fn codeword_len(&self) -> usize {
+        Self::codeword_len_at(self.num_vars)
+    }
+
+    /// Encode the evaluations of a multilinear polynomial over the hypercube
+    pub fn encode(&self, msg: &[F]) -> Vec<F> {
+        assert!(msg.len().is_power_of_two() && msg.len() <= 1 << self.num_vars);
+
+        let mut codeword = vec![F::ZERO; msg.len() << BASEFOLD_LOG_INV_RATE];
+        self.encode_into(msg, &mut codeword);
+        codeword
+    }
+
+    fn encode_into(&self, msg: &[F], codeword: &mut [F]) {
+        if msg.len() == 1 {
+            codeword.fill(msg[0]);
+            return;
+        }
+
+        let at_zero: Vec<F> = msg.iter().step_by(2).copied().collect();
+        let slopes: Vec<F> = msg.chunks(2).map(|pair| pair[1] - pair[0]).collect();
+        let (left, right) = codeword.split_at_mut(codeword.len() / 2);
+        if msg.len() >= ENCODE_PAR_THRESHOLD {
+            join(
+                || self.encode_into(&at_zero, left),
+                || self.encode_into(&slopes, right),
+            );
+        } else {
+            self.encode_into(&at_zero, left);
+            self.encode_into(&slopes, right);
+        }
+
+        let level = msg.len().ilog2() as usize - 1;
+        left.iter_mut()
+            .zip(right.iter_mut())
+            .zip(&self.twiddles[level])
+            .for_each(|((a, b), t)| {
+                let tb = *b * t;
+                *b = *a - tb;
+                *a += tb;
+            });
+    }
+
+    /// Fold the pair of entries `index` and `index + len / 2` of a codeword of `level` variables
+    /// with `r`, into the entry `index` of the codeword of `level - 1` variables
+    #[inline(always)]
+    pub fn fold_pair<EvalF>(
+        &self,
+        level: usize,
+        index: usize,
+        lo: EvalF,
+        hi: EvalF,
+        r: EvalF,
+    ) -> EvalF
+    where
+        EvalF: ExtensionField<BaseField = F>,
+    {
+        // lo = a + t * b and hi = a - t * b, folded into a + r * b
+        let slope = (lo - hi).mul_by_base_field(&self.inv_twiddles[level - 1][index]);
+        (lo + hi + r * slope) * EvalF::INV_2
+    }
+
+    /// Fold a codeword of `level` variables with `r`
+    pub fn fold<EvalF>(&self, level: usize, codeword: &[EvalF], r: EvalF) -> Vec<EvalF>
+    where
+        EvalF: ExtensionField<BaseField = F>,
+    {
+        let (lo, hi) = codeword.split_at(codeword.len() / 2);
+        lo.iter()
+            .zip(hi)
+            .enumerate()
+            .map(|(i, (&lo, &hi))| self.fold_pair(level, i, lo, hi, r))
+            .collect()
+    }
+}
+
+pub type BasefoldCommitment = Node;
+
+/// The Merkle tree over the committed codeword, kept by the prover between commit and open
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct BasefoldScratchPad {
+    pub codeword_tree: Tree,
+}
+
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct BasefoldProof<EvalF: Field> {
+    /// The round polynomials of the sumcheck of `f(x) * eq(x, point)`, evaluated at 0, 1 and 2
+    pub sumcheck_messages: Vec<[EvalF; 3]>,
+    /// The roots of the trees of the folded codewords, but the last one
+    pub fold_roots: Vec<Node>,
+    /// The last folded codeword repeats the polynomial evaluated at the sumcheck challenges
+    pub final_eval: EvalF,
+    /// For each query, the paths to the queried pairs of entries of the committed codeword and
+    /// of the folded ones
+    pub query_paths: Vec<Vec<Path>>,
+}
+
+/// The number of queries for `soundness_bits` bits of security, from the relative distance of the
+/// code in the unique decoding regime, see `TensorCodeIOPPCS::query_complexity`
+#[inline(always)]
+pub(crate) fn basefold_query_complexity(soundness_bits: usize) -> usize {
+    let rate = 1f64 / (1 << BASEFOLD_LOG_INV_RATE) as f64;
+    let avg_case_dist = (1f64 - rate) / 2f64;
+    let sec_bits = -(1f64 - avg_case_dist).log2();
+
+    (soundness_bits as f64 / sec_bits).ceil() as usize
+}
+
+/// The tree over the pairs of entries of a codeword that are folded together, i.e., with leaf
+/// `i` holding the entries `i` and `i + len / 2`
+pub(crate) fn pair_tree<V: Field>(codeword: &[V]) -> Tree {
+    let (lo, hi) = codeword.split_at(codeword.len() / 2);
+    Tree::new_with_leaves(
+        lo.iter()
+            .zip(hi)
+            .map(|(lo, hi)| pair_leaf(lo, hi))
+            .collect(),
+    )
+}
+
+#[inline(always)]
+pub(crate) fn pair_leaf<V: Field>(lo: &V, hi: &V) -> Leaf {
+    assert!(2 * V::SIZE <= LEAF_BYTES);
+
+    let mut data = [0u8; LEAF_BYTES];
+    let mut writer = &mut data[..];
+    lo.serialize_into(&mut writer).unwrap();
+    hi.serialize_into(&mut writer).unwrap();
+    Leaf::new(data)
+}
+
+/// The pair of entries in a leaf of `pair_tree`, or None if the leaf does not hold two field
+/// elements
+#[inline(always)]
+pub(crate) fn leaf_pair<V: Field>(leaf: &Leaf) -> Option<(V, V)> {
+    let mut reader = &leaf.data[..];
+    let lo = V::deserialize_from(&mut reader).ok()?;
+    let hi = V::deserialize_from(&mut reader).ok()?;
+    Some((lo, hi))
+}
+
+/// The inverses of non-zero field elements, with a single inversion
+fn batch_inverse<F: Field>(elems: &[F]) -> Vec<F> {
+    let mut prefix_products = Vec::with_capacity(elems.len());
+    let mut acc = F::ONE;
+    elems.iter().for_each(|e| {
+        prefix_products.push(acc);
+        acc *= e;
+    });
+
+    let mut inv_acc = acc.inv().unwrap();
+    let mut inverses = vec![F::ZERO; elems.len()];
+    elems
+        .iter()
+        .zip(prefix_products)
+        .zip(inverses.iter_mut())
+        .rev()
+        .for_each(|((e, prefix_product), inv)| {
+            *inv = inv_acc * prefix_product;
+            inv_acc *= e;
+        });
+    inverses
+}
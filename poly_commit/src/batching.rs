@@ -1,10 +1,10 @@
 //! Multi-points batch opening
 //! Uses Rayon to parallelize the computation.
+use std::sync::Arc;
+
 use arith::{ExtensionField, Field};
 use ark_std::log2;
 use gkr_engine::Transcript;
-use halo2curves::group::Curve;
-use halo2curves::msm::best_multiexp;
 use halo2curves::{ff::PrimeField, CurveAffine};
 use polynomials::{EqPolynomial, MultilinearExtension};
 use polynomials::{MultiLinearPoly, SumOfProductsPoly};
@@ -13,6 +13,8 @@ use serdes::ExpSerde;
 use sumcheck::{IOPProof, SumCheck};
 use utils::timer::Timer;
 
+use crate::{backend, batch_to_affine};
+
 /// Merge a list of polynomials and its corresponding points into a single polynomial
 /// Returns
 /// - the new point for evaluation
@@ -38,7 +40,7 @@ where
     let ell = log2(k) as usize;
 
     // challenge point t
-    let t = transcript.generate_field_elements::<C::Scalar>(ell);
+    let t = transcript.challenge_point::<C::Scalar>(ell);
 
     // eq(t, i) for i in [0..k]
     let eq_t_i = EqPolynomial::build_eq_x_r(&t);
@@ -55,9 +57,9 @@ where
                 tilde_g_eval[j] = f_i_eval * eq_t_i[index];
             }
 
-            MultiLinearPoly {
+            Arc::new(MultiLinearPoly {
                 coeffs: tilde_g_eval,
-            }
+            })
         })
         .collect::<Vec<_>>();
     timer.stop();
@@ -78,7 +80,7 @@ where
     let timer = Timer::new("Sumcheck merging points", true);
     let mut sumcheck_poly = SumOfProductsPoly::new();
     for (tilde_g, tilde_eq) in tilde_gs.iter().zip(tilde_eqs.into_iter()) {
-        sumcheck_poly.add_pair(tilde_g.clone(), tilde_eq);
+        sumcheck_poly.add_pair(Arc::clone(tilde_g), tilde_eq);
     }
     let proof = SumCheck::<C::Scalar>::prove(&sumcheck_poly, transcript);
     timer.stop();
@@ -137,7 +139,7 @@ where
     let a2 = sumcheck_proof.export_point_to_expander();
 
     // challenge point t
-    let t = transcript.generate_field_elements::<C::Scalar>(ell);
+    let t = transcript.challenge_point::<C::Scalar>(ell);
 
     let eq_t_i = EqPolynomial::build_eq_x_r(&t);
 
@@ -159,11 +161,10 @@ where
 
     let g_prime_commit_elems = bases_transposed
         .iter()
-        .map(|base| best_multiexp(&scalars, base))
+        .map(|base| backend::msm(&scalars, base))
         .collect::<Vec<_>>();
 
-    let mut g_prime_commit_affine = vec![C::default(); padded_commitments[0].len()];
-    C::Curve::batch_normalize(&g_prime_commit_elems, &mut g_prime_commit_affine);
+    let g_prime_commit_affine: Vec<C> = batch_to_affine(&g_prime_commit_elems);
 
     // ensure \sum_i eq(t, <i>) * f_i_evals matches the sum via SumCheck
     let mut sum = C::Scalar::zero();
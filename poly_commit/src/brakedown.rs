@@ -0,0 +1,86 @@
+//! Brakedown (GLSTW21): the tensor code PCS of Orion, without the proof composition, over a
+//! higher rate instance of the same Spielman code. The commitment encodes the rows into shorter
+//! codewords, through denser graphs, and the lower distance of the code is paid for with more
+//! column queries in the opening, hence larger proofs.
+//!
+//! The commitment, the openings and the verification, single process or distributed, are the
+//! ones of Orion, the code instance being carried by the SRS. The `brakedown` bench compares the
+//! commitment times of both on the same polynomials.
+
+use crate::orion::{OrionCodeParameter, OrionGraphDegrees};
+
+mod expander_api;
+pub use expander_api::{BrakedownPCSForGKR, BrakedownSIMDFieldPCS};
+
+pub type BrakedownSRS = crate::orion::OrionSRS;
+
+pub type BrakedownScratchPad = crate::orion::OrionScratchPad;
+
+pub type BrakedownCommitment = crate::orion::OrionCommitment;
+
+pub type BrakedownProof<EvalF> = crate::orion::OrionProof<EvalF>;
+
+// NOTE: the instance of Brakedown (GLSTW21) Figure 2: rate 1 / 1.42 and relative distance
+// beta / r = 0.0175, with the degrees c_n and d_n of the figure at each recursion level.
+// Brakedown encodes a message x of length n into (x, z, v), z the codeword of x * A of length
+// r alpha n, and v = z * B of length (r - 1 - r alpha) n, hence the ratio alpha_g1 of v to z.
+// The recursion stops at the length threshold of Orion, the code of the shorter messages being
+// the identity, of relative distance 1 / 12 above beta / r, as the figure requires of the base
+// code.  The evaluation shape of Orion relies on that threshold as well.
+const BRAKEDOWN_ALPHA: f64 = 0.1195;
+
+const BRAKEDOWN_BETA: f64 = 0.0248;
+
+const BRAKEDOWN_RATE: f64 = 1.42;
+
+pub const BRAKEDOWN_CODE_PARAMETER_INSTANCE: OrionCodeParameter = OrionCodeParameter {
+    alpha_g0: BRAKEDOWN_ALPHA,
+
+    length_threshold_g0s: 12,
+
+    alpha_g1: (BRAKEDOWN_RATE - 1.0 - BRAKEDOWN_RATE * BRAKEDOWN_ALPHA)
+        / (BRAKEDOWN_RATE * BRAKEDOWN_ALPHA),
+
+    degrees: OrionGraphDegrees::Brakedown {
+        beta: BRAKEDOWN_BETA,
+        rate: BRAKEDOWN_RATE,
+    },
+
+    hamming_weight: BRAKEDOWN_BETA / BRAKEDOWN_RATE,
+};
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+
+    use super::{BrakedownSRS, BRAKEDOWN_CODE_PARAMETER_INSTANCE};
+    use crate::{traits::TensorCodeIOPPCS, PCS_SOUNDNESS_BITS};
+
+    #[test]
+    fn test_brakedown_degrees() {
+        let params = BRAKEDOWN_CODE_PARAMETER_INSTANCE;
+        assert_eq!(params.degrees(100), (7, 211));
+        assert_eq!(params.degrees(1 << 10), (3, 52));
+        assert_eq!(params.degrees(1 << 16), (2, 35));
+        assert_eq!(params.degrees(1 << 30), (2, 35));
+    }
+
+    #[test]
+    fn test_brakedown_code_rate_and_queries() {
+        let mut rng = test_rng();
+        let (srs, _) =
+            BrakedownSRS::from_random(1, 26, 1, 1, BRAKEDOWN_CODE_PARAMETER_INSTANCE, &mut rng);
+        assert!(srs.message_len() >= 1 << 10);
+
+        let rate = srs.codeword_len() as f64 / srs.message_len() as f64;
+        assert!((1.39..1.43).contains(&rate), "rate 1 / {rate}");
+
+        // each query passes a codeword at relative distance over half the minimum distance with
+        // probability at most 1 - d / 2
+        let query_num = srs.query_complexity(PCS_SOUNDNESS_BITS);
+        let bits_per_query = -(1f64 - srs.minimum_hamming_weight() / 2f64).log2();
+        assert!(query_num as f64 * bits_per_query >= PCS_SOUNDNESS_BITS as f64);
+        assert!((query_num - 1) as f64 * bits_per_query < PCS_SOUNDNESS_BITS as f64);
+        assert_eq!(query_num, 10116);
+    }
+}
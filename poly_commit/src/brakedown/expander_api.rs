@@ -0,0 +1,227 @@
+use std::marker::PhantomData;
+
+use arith::{ExtensionField, Field, SimdField};
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
+};
+use polynomials::MultilinearExtension;
+
+use crate::{
+    brakedown::{
+        BrakedownCommitment, BrakedownProof, BrakedownSRS, BrakedownScratchPad,
+        BRAKEDOWN_CODE_PARAMETER_INSTANCE,
+    },
+    orion::{
+        orion_commit_simd_field, orion_eval_shape, orion_mpi_commit_simd_field,
+        orion_mpi_open_simd_field, orion_open_simd_field, orion_verify, OrionPCSError,
+    },
+    utils::{
+        lift_expander_challenge_to_n_vars, lift_poly_and_expander_challenge_to_n_vars,
+        lift_poly_to_n_vars,
+    },
+};
+
+pub struct BrakedownSIMDFieldPCS<F, SimdF, EvalF, ComPackF>
+where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+    EvalF: ExtensionField<BaseField = F>,
+    ComPackF: SimdField<Scalar = F>,
+{
+    _marker_f: PhantomData<F>,
+    _marker_simd_f: PhantomData<SimdF>,
+    _marker_eval_f: PhantomData<EvalF>,
+    _marker_commit_f: PhantomData<ComPackF>,
+}
+
+impl<C, ComPackF> ExpanderPCS<C>
+    for BrakedownSIMDFieldPCS<C::CircuitField, C::SimdCircuitField, C::ChallengeField, ComPackF>
+where
+    C: FieldEngine,
+    ComPackF: SimdField<Scalar = C::CircuitField>,
+{
+    const NAME: &'static str = "BrakedownPCSForExpanderGKR";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::Brakedown;
+
+    const POWER_OF_TWO_WORLD: bool = true;
+
+    type Params = usize;
+    type ScratchPad = BrakedownScratchPad;
+
+    type Commitment = BrakedownCommitment;
+    type Opening = BrakedownProof<C::ChallengeField>;
+    type BatchOpening = ();
+    type SRS = BrakedownSRS;
+
+    /// NOTE(HS): this is the number of variables for local polynomial w.r.t. SIMD field elements.
+    fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params {
+        Self::check_world_size(world_size).unwrap_or_else(|e| panic!("{e}"));
+        let num_vars_each_core = n_input_vars + C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+        let (_num_leaves_per_mt_query, scaled_num_local_vars, _msg_size) = orion_eval_shape(
+            world_size,
+            num_vars_each_core,
+            C::CircuitField::FIELD_SIZE,
+            C::SimdCircuitField::PACK_SIZE,
+        );
+
+        scaled_num_local_vars - C::SimdCircuitField::PACK_SIZE.ilog2() as usize
+    }
+
+    fn gen_srs(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        rng: impl rand::RngCore,
+    ) -> Self::SRS {
+        let num_vars_each_core = *params + C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+        let (srs, calibrated_num_vars_each_core) = BrakedownSRS::from_random(
+            mpi_engine.world_size(),
+            num_vars_each_core,
+            C::CircuitField::FIELD_SIZE,
+            ComPackF::PACK_SIZE,
+            BRAKEDOWN_CODE_PARAMETER_INSTANCE,
+            rng,
+        );
+        assert_eq!(num_vars_each_core, calibrated_num_vars_each_core);
+        srs
+    }
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {
+        Self::ScratchPad::default()
+    }
+
+    fn commit(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Commitment>> {
+        if poly.num_vars() < *params {
+            let poly = lift_poly_to_n_vars(poly, *params);
+            return <Self as ExpanderPCS<C>>::commit(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                scratch_pad,
+            );
+        }
+
+        let num_vars_each_core = *params + C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+        assert_eq!(num_vars_each_core, proving_key.num_vars);
+
+        if mpi_engine.is_single_process() {
+            return Ok(orion_commit_simd_field::<_, C::SimdCircuitField, ComPackF>(
+                proving_key,
+                poly,
+                scratch_pad,
+            )
+            .ok());
+        }
+
+        // the failures to communicate are reported, the other ones leave no commitment as before
+        match orion_mpi_commit_simd_field::<_, C::SimdCircuitField, ComPackF>(
+            mpi_engine,
+            proving_key,
+            poly,
+            scratch_pad,
+            <Self as ExpanderPCS<C>>::COMMIT_LAYOUT,
+        ) {
+            Err(OrionPCSError::MPIError(e)) => Err(e),
+            commitment => Ok(commitment.ok()),
+        }
+    }
+
+    fn open(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        transcript: &mut impl Transcript,
+        scratch_pad: &Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Opening>> {
+        if poly.num_vars() < *params {
+            let (poly, eval_point) =
+                lift_poly_and_expander_challenge_to_n_vars(poly, eval_point, *params);
+            return <Self as ExpanderPCS<C>>::open(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                &eval_point,
+                transcript,
+                scratch_pad,
+            );
+        }
+
+        let num_vars_each_core = *params + C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+        assert_eq!(num_vars_each_core, proving_key.num_vars);
+
+        if mpi_engine.is_single_process() {
+            let (_, opening) = orion_open_simd_field::<_, C::SimdCircuitField, _, ComPackF>(
+                proving_key,
+                poly,
+                &eval_point.local_xs(),
+                transcript,
+                scratch_pad,
+            );
+            return Ok(opening.into());
+        }
+
+        orion_mpi_open_simd_field::<_, C::SimdCircuitField, _, ComPackF>(
+            mpi_engine,
+            proving_key,
+            poly,
+            &eval_point.local_xs(),
+            &eval_point.r_mpi,
+            transcript,
+            scratch_pad,
+            <Self as ExpanderPCS<C>>::COMMIT_LAYOUT,
+        )
+    }
+
+    fn verify(
+        params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        eval: C::ChallengeField,
+        transcript: &mut impl Transcript, /* add transcript here to allow
+                                           * interactive arguments */
+        opening: &Self::Opening,
+    ) -> bool {
+        if eval_point.num_vars() < *params {
+            let eval_point = lift_expander_challenge_to_n_vars(eval_point, *params);
+            return <Self as ExpanderPCS<C>>::verify(
+                params,
+                verifying_key,
+                commitment,
+                &eval_point,
+                eval,
+                transcript,
+                opening,
+            );
+        }
+
+        orion_verify::<_, C::SimdCircuitField, _, ComPackF>(
+            verifying_key,
+            commitment,
+            &eval_point.local_xs(),
+            &eval_point.r_mpi,
+            eval,
+            transcript,
+            opening,
+            <Self as ExpanderPCS<C>>::COMMIT_LAYOUT,
+        )
+    }
+}
+
+pub type BrakedownPCSForGKR<C, ComPack> = BrakedownSIMDFieldPCS<
+    <C as FieldEngine>::CircuitField,
+    <C as FieldEngine>::SimdCircuitField,
+    <C as FieldEngine>::ChallengeField,
+    ComPack,
+>;
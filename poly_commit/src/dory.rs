@@ -0,0 +1,12 @@
+mod setup;
+pub use setup::{DorySRS, DoryVerifierParams};
+
+mod dory_impl;
+pub use dory_impl::{
+    dory_commit, dory_open, dory_verify, DoryCommitment, DoryOpening, DoryReduceRound,
+};
+
+mod pcs_trait_impl;
+pub use pcs_trait_impl::DoryPCS;
+
+mod expander_api;
@@ -0,0 +1,480 @@
+use std::ops::Mul;
+
+use arith::ExtensionField;
+use derivative::Derivative;
+use gkr_engine::Transcript;
+use halo2curves::{
+    ff::Field,
+    group::{Curve, GroupEncoding},
+    pairing::{MillerLoopResult, MultiMillerLoop},
+    CurveAffine,
+};
+use itertools::izip;
+use polynomials::{EqPolynomial, MatrixView};
+use rayon::prelude::*;
+use serdes::ExpSerde;
+
+use crate::{
+    backend, batch_to_affine,
+    dory::{setup::dory_num_vars_per_side, DorySRS, DoryVerifierParams},
+};
+
+/// The commitment to the matrix of the evaluations of a multilinear polynomial: the inner pairing
+/// product of the Pedersen commitments to its rows with \Gamma_2.
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct DoryCommitment<E: MultiMillerLoop>(pub E::Result);
+
+impl<E: MultiMillerLoop> AsRef<DoryCommitment<E>> for DoryCommitment<E> {
+    fn as_ref(&self) -> &DoryCommitment<E> {
+        self
+    }
+}
+
+// Derive macros does not work for associated types
+impl<E: MultiMillerLoop> ExpSerde for DoryCommitment<E>
+where
+    E::Result: ExpSerde,
+{
+    fn serialize_into<W: std::io::Write>(&self, writer: W) -> serdes::SerdeResult<()> {
+        self.0.serialize_into(writer)
+    }
+
+    fn deserialize_from<R: std::io::Read>(reader: R) -> serdes::SerdeResult<Self> {
+        Ok(Self(<E::Result as ExpSerde>::deserialize_from(reader)?))
+    }
+}
+
+/// The messages of a round of Dory-Reduce, halving the vectors of the inner pairing product.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct DoryReduceRound<E: MultiMillerLoop>
+where
+    E::G1Affine: ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    /// <v_{1,L}, \Gamma_2'>, <v_{1,R}, \Gamma_2'>, <\Gamma_1', v_{2,L}>, <\Gamma_1', v_{2,R}>
+    pub d1_left: E::Result,
+    pub d1_right: E::Result,
+    pub d2_left: E::Result,
+    pub d2_right: E::Result,
+    /// <\Gamma_1, s_2> and <s_1, \Gamma_2>
+    pub e1_beta: E::G1Affine,
+    pub e2_beta: E::G2Affine,
+    /// The cross terms <v_{1,L}, v_{2,R}> and <v_{1,R}, v_{2,L}>, after the bases are added
+    pub c_plus: E::Result,
+    pub c_minus: E::Result,
+    /// The cross terms <v_{1,L}, s_{2,R}>, <v_{1,R}, s_{2,L}>, <s_{1,L}, v_{2,R}> and
+    /// <s_{1,R}, v_{2,L}>
+    pub e1_plus: E::G1Affine,
+    pub e1_minus: E::G1Affine,
+    pub e2_plus: E::G2Affine,
+    pub e2_minus: E::G2Affine,
+}
+
+/// Dory opening of the matrix M of the evaluations at a point (u_col, u_row), with the row
+/// commitments T = M \Gamma_1, the row weights L = eq(u_row) and the column weights
+/// R = eq(u_col). The opening proves the knowledge of v_1 = T and v_2 = (L^T M) h_2 such that
+/// D_1 = <v_1, \Gamma_2> is the commitment, E_1 = <v_1, L>, e(E_1, h_2) = <\Gamma_1, v_2> and
+/// <R, v_2> = y h_2, for the evaluation y.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct DoryOpening<E: MultiMillerLoop>
+where
+    E::G1Affine: ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    /// <v_1, v_2>
+    pub c: E::Result,
+    /// <v_1, L>, the commitment to the row L^T M
+    pub e1: E::G1Affine,
+    /// The rounds, from the full matrix down to vectors of a single element
+    pub rounds: Vec<DoryReduceRound<E>>,
+    /// The folded v_1 and v_2
+    pub v1: E::G1Affine,
+    pub v2: E::G2Affine,
+}
+
+#[inline(always)]
+fn append_gt<E: MultiMillerLoop>(fs_transcript: &mut impl Transcript, gt: &E::Result)
+where
+    E::Result: ExpSerde,
+{
+    let mut bytes = vec![];
+    gt.serialize_into(&mut bytes).unwrap();
+    fs_transcript.append_u8_slice(&bytes);
+}
+
+#[inline(always)]
+fn append_point<C: GroupEncoding>(fs_transcript: &mut impl Transcript, point: &C) {
+    fs_transcript.append_u8_slice(point.to_bytes().as_ref());
+}
+
+/// v_i + scalar * bases_i
+#[inline(always)]
+fn add_scaled_bases<C: CurveAffine>(v: &[C], bases: &[C], scalar: C::Scalar) -> Vec<C> {
+    let proj: Vec<C::Curve> = v
+        .par_iter()
+        .zip(bases)
+        .map(|(v_i, base)| *base * scalar + *v_i)
+        .collect();
+    batch_to_affine(&proj)
+}
+
+/// scalar * v_L + v_R
+#[inline(always)]
+fn fold_points<C: CurveAffine>(v: &[C], scalar: C::Scalar) -> Vec<C> {
+    let (left, right) = v.split_at(v.len() / 2);
+    let proj: Vec<C::Curve> = left
+        .par_iter()
+        .zip(right)
+        .map(|(l, r)| *l * scalar + *r)
+        .collect();
+    batch_to_affine(&proj)
+}
+
+/// scalar * s_L + s_R
+#[inline(always)]
+fn fold_scalars<F: Field>(s: &[F], scalar: F) -> Vec<F> {
+    let (left, right) = s.split_at(s.len() / 2);
+    izip!(left, right).map(|(l, r)| *l * scalar + *r).collect()
+}
+
+/// The evaluations over an even number of variables, the top one fixed to 0 if the polynomial
+/// has an odd number of variables.
+#[inline(always)]
+fn pad_to_square<F: Field>(evals: &[F]) -> Vec<F> {
+    let num_vars = 2 * dory_num_vars_per_side(evals.len().ilog2() as usize);
+    let mut evals = evals.to_vec();
+    evals.resize(1 << num_vars, F::ZERO);
+    evals
+}
+
+/// The Pedersen commitments over \Gamma_1 to the rows of the matrix of `evals`
+fn dory_row_commitments<E>(srs: &DorySRS<E>, evals: &[E::Fr]) -> Vec<E::G1Affine>
+where
+    E: MultiMillerLoop,
+    E::G1Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    let side = 1 << dory_num_vars_per_side(evals.len().ilog2() as usize);
+    assert!(
+        srs.g1_bases.len() >= side,
+        "the SRS commits to matrices of {} columns, not {side}",
+        srs.g1_bases.len()
+    );
+
+    let rows: Vec<E::G1> = MatrixView::new(evals, side.ilog2() as usize)
+        .par_rows()
+        .map(|row| backend::msm(row, &srs.g1_bases[..side]))
+        .collect();
+    batch_to_affine(&rows)
+}
+
+/// Commit to the multilinear polynomial of evaluations `evals` over the hypercube, as a matrix
+/// whose columns are indexed by the lower half of the variables.
+pub fn dory_commit<E>(srs: &DorySRS<E>, evals: &[E::Fr]) -> DoryCommitment<E>
+where
+    E: MultiMillerLoop,
+    E::G1Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+    E::Result: ExpSerde,
+{
+    let rows = dory_row_commitments(srs, &pad_to_square(evals));
+    DoryCommitment(backend::multi_miller_loop::<E>(
+        &rows,
+        &srs.g2_bases[..rows.len()],
+    ))
+}
+
+/// Open the multilinear polynomial of evaluations `evals` over the hypercube at `point`, the
+/// variables being LSB first.
+pub fn dory_open<E, T>(
+    srs: &DorySRS<E>,
+    evals: &[E::Fr],
+    point: &[E::Fr],
+    fs_transcript: &mut T,
+) -> (E::Fr, DoryOpening<E>)
+where
+    E: MultiMillerLoop,
+    E::Fr: ExtensionField,
+    E::G1Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+    E::Result: ExpSerde,
+    T: Transcript,
+{
+    assert_eq!(evals.len(), 1 << point.len());
+
+    let num_vars_per_side = dory_num_vars_per_side(point.len());
+    let evals = pad_to_square(evals);
+    let mut point = point.to_vec();
+    point.resize(2 * num_vars_per_side, E::Fr::ZERO);
+    let side = 1 << num_vars_per_side;
+    let h2 = srs.verifier_params.h2;
+
+    let rows = dory_row_commitments(srs, &evals);
+    let row_weights = EqPolynomial::build_eq_x_r(&point[num_vars_per_side..]);
+    let col_weights = EqPolynomial::build_eq_x_r(&point[..num_vars_per_side]);
+
+    // NOTE: the row L^T M, and the evaluation <L^T M, R>
+    let mut row = vec![E::Fr::ZERO; side];
+    izip!(
+        MatrixView::new(&evals, num_vars_per_side).rows(),
+        &row_weights
+    )
+    .for_each(|(m_i, l_i)| {
+        izip!(&mut row, m_i).for_each(|(r_j, m_ij)| *r_j += *l_i * *m_ij);
+    });
+    let eval: E::Fr = izip!(&row, &col_weights)
+        .map(|(r_j, c_j)| *r_j * *c_j)
+        .sum();
+
+    let mut v1 = rows;
+    let v2_proj: Vec<E::G2> = row.par_iter().map(|r_j| h2 * *r_j).collect();
+    let mut v2: Vec<E::G2Affine> = batch_to_affine(&v2_proj);
+    let mut s1 = col_weights;
+    let mut s2 = row_weights;
+
+    let c = backend::multi_miller_loop::<E>(&[backend::msm(&row, &v1).to_affine()], &[h2]);
+    let e1 = backend::msm(&s2, &v1).to_affine();
+    append_gt::<E>(fs_transcript, &c);
+    append_point(fs_transcript, &e1);
+
+    let rounds = (1..=num_vars_per_side)
+        .rev()
+        .map(|k| {
+            let (half, len) = (1 << (k - 1), 1 << k);
+            let (g1_bases, g2_bases) = (&srs.g1_bases[..len], &srs.g2_bases[..len]);
+
+            let d1_left = backend::multi_miller_loop::<E>(&v1[..half], &g2_bases[..half]);
+            let d1_right = backend::multi_miller_loop::<E>(&v1[half..], &g2_bases[..half]);
+            let d2_left = backend::multi_miller_loop::<E>(&g1_bases[..half], &v2[..half]);
+            let d2_right = backend::multi_miller_loop::<E>(&g1_bases[..half], &v2[half..]);
+            let e1_beta = backend::msm(&s2, g1_bases).to_affine();
+            let e2_beta = backend::msm(&s1, g2_bases).to_affine();
+            [&d1_left, &d1_right, &d2_left, &d2_right]
+                .into_iter()
+                .for_each(|d| append_gt::<E>(fs_transcript, d));
+            append_point(fs_transcript, &e1_beta);
+            append_point(fs_transcript, &e2_beta);
+
+            let beta = fs_transcript.generate_field_element::<E::Fr>();
+            let beta_inv = beta.invert().unwrap();
+            v1 = add_scaled_bases(&v1, g1_bases, beta);
+            v2 = add_scaled_bases(&v2, g2_bases, beta_inv);
+
+            let c_plus = backend::multi_miller_loop::<E>(&v1[..half], &v2[half..]);
+            let c_minus = backend::multi_miller_loop::<E>(&v1[half..], &v2[..half]);
+            let e1_plus = backend::msm(&s2[half..], &v1[..half]).to_affine();
+            let e1_minus = backend::msm(&s2[..half], &v1[half..]).to_affine();
+            let e2_plus = backend::msm(&s1[..half], &v2[half..]).to_affine();
+            let e2_minus = backend::msm(&s1[half..], &v2[..half]).to_affine();
+            append_gt::<E>(fs_transcript, &c_plus);
+            append_gt::<E>(fs_transcript, &c_minus);
+            [&e1_plus, &e1_minus]
+                .into_iter()
+                .for_each(|e| append_point(fs_transcript, e));
+            [&e2_plus, &e2_minus]
+                .into_iter()
+                .for_each(|e| append_point(fs_transcript, e));
+
+            let alpha = fs_transcript.generate_field_element::<E::Fr>();
+            let alpha_inv = alpha.invert().unwrap();
+            v1 = fold_points(&v1, alpha);
+            v2 = fold_points(&v2, alpha_inv);
+            s1 = fold_scalars(&s1, alpha);
+            s2 = fold_scalars(&s2, alpha_inv);
+
+            DoryReduceRound {
+                d1_left,
+                d1_right,
+                d2_left,
+                d2_right,
+                e1_beta,
+                e2_beta,
+                c_plus,
+                c_minus,
+                e1_plus,
+                e1_minus,
+                e2_plus,
+                e2_minus,
+            }
+        })
+        .collect();
+
+    append_point(fs_transcript, &v1[0]);
+    append_point(fs_transcript, &v2[0]);
+
+    (
+        eval,
+        DoryOpening {
+            c,
+            e1,
+            rounds,
+            v1: v1[0],
+            v2: v2[0],
+        },
+    )
+}
+
+/// Verify the Dory opening of the commitment `comm` at `point` to `eval`, in a number of pairings
+/// and exponentiations in the target group logarithmic in the size of the polynomial.
+pub fn dory_verify<E, T>(
+    vk: &DoryVerifierParams<E>,
+    comm: &DoryCommitment<E>,
+    point: &[E::Fr],
+    eval: E::Fr,
+    opening: &DoryOpening<E>,
+    fs_transcript: &mut T,
+) -> bool
+where
+    E: MultiMillerLoop,
+    E::Fr: ExtensionField,
+    E::G1Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+    E::Result: ExpSerde + Mul<E::Fr, Output = E::Result>,
+    T: Transcript,
+{
+    let num_vars_per_side = dory_num_vars_per_side(point.len());
+    if num_vars_per_side > vk.num_vars_per_side() || opening.rounds.len() != num_vars_per_side {
+        return false;
+    }
+    let mut point = point.to_vec();
+    point.resize(2 * num_vars_per_side, E::Fr::ZERO);
+    let (col_point, row_point) = point.split_at(num_vars_per_side);
+
+    append_gt::<E>(fs_transcript, &opening.c);
+    append_point(fs_transcript, &opening.e1);
+
+    let mut c = opening.c;
+    let mut d1 = comm.0;
+    let mut d2 = backend::multi_miller_loop::<E>(&[opening.e1], &[vk.h2]);
+    let mut e1: E::G1 = opening.e1.into();
+    let mut e2: E::G2 = vk.h2 * eval;
+    // NOTE: the folded s_1 = R and s_2 = L, the folds binding their top variables
+    let mut s1 = E::Fr::ONE;
+    let mut s2 = E::Fr::ONE;
+
+    for (k, round) in (1..=num_vars_per_side).rev().zip(&opening.rounds) {
+        [
+            &round.d1_left,
+            &round.d1_right,
+            &round.d2_left,
+            &round.d2_right,
+        ]
+        .into_iter()
+        .for_each(|d| append_gt::<E>(fs_transcript, d));
+        append_point(fs_transcript, &round.e1_beta);
+        append_point(fs_transcript, &round.e2_beta);
+        let beta = fs_transcript.generate_field_element::<E::Fr>();
+        let beta_inv = beta.invert().unwrap();
+
+        append_gt::<E>(fs_transcript, &round.c_plus);
+        append_gt::<E>(fs_transcript, &round.c_minus);
+        [&round.e1_plus, &round.e1_minus]
+            .into_iter()
+            .for_each(|e| append_point(fs_transcript, e));
+        [&round.e2_plus, &round.e2_minus]
+            .into_iter()
+            .for_each(|e| append_point(fs_transcript, e));
+        let alpha = fs_transcript.generate_field_element::<E::Fr>();
+        let alpha_inv = alpha.invert().unwrap();
+
+        let (chi, chi_half) = (vk.chi[k], vk.chi[k - 1]);
+        let (delta_1_right, delta_2_right) = (vk.delta_1_right[k - 1], vk.delta_2_right[k - 1]);
+
+        c = c + chi + d2 * beta + d1 * beta_inv + round.c_plus * alpha + round.c_minus * alpha_inv;
+        d1 = round.d1_left * alpha
+            + round.d1_right
+            + chi_half * (alpha * beta)
+            + delta_1_right * beta;
+        d2 = round.d2_left * alpha_inv
+            + round.d2_right
+            + chi_half * (alpha_inv * beta_inv)
+            + delta_2_right * beta_inv;
+        e1 = e1 + round.e1_beta * beta + round.e1_plus * alpha + round.e1_minus * alpha_inv;
+        e2 = e2 + round.e2_beta * beta_inv + round.e2_plus * alpha + round.e2_minus * alpha_inv;
+
+        let (u_col, u_row) = (col_point[k - 1], row_point[k - 1]);
+        s1 *= alpha * (E::Fr::ONE - u_col) + u_col;
+        s2 *= alpha_inv * (E::Fr::ONE - u_row) + u_row;
+    }
+
+    append_point(fs_transcript, &opening.v1);
+    append_point(fs_transcript, &opening.v2);
+
+    if e1 != opening.v1 * s2 || e2 != opening.v2 * s1 {
+        return false;
+    }
+
+    // NOTE: C + d D_1 + d^{-1} D_2 + \chi_0 = e(v_1 + d^{-1} \Gamma_1, v_2 + d \Gamma_2)
+    let d = fs_transcript.generate_field_element::<E::Fr>();
+    let d_inv = d.invert().unwrap();
+    let lhs = c + d1 * d + d2 * d_inv + vk.chi[0];
+    let rhs = backend::multi_miller_loop::<E>(
+        &[(vk.g1 * d_inv + opening.v1).to_affine()],
+        &[(vk.g2 * d + opening.v2).to_affine()],
+    );
+
+    lhs.final_exponentiation() == rhs.final_exponentiation()
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use gkr_engine::Transcript;
+    use gkr_hashers::Keccak256hasher;
+    use halo2curves::{
+        bn256::{Bn256, Fr},
+        ff::Field,
+    };
+    use polynomials::MultiLinearPoly;
+    use transcript::BytesHashTranscript;
+
+    use crate::dory::setup::dory_setup;
+
+    use super::*;
+
+    #[test]
+    fn test_dory_e2e() {
+        type T = BytesHashTranscript<Keccak256hasher>;
+        let mut rng = test_rng();
+
+        for num_vars in [0, 1, 4, 5] {
+            let srs = dory_setup::<Bn256>(num_vars, &mut rng);
+            let vk = srs.verifier_params.clone();
+
+            let evals: Vec<Fr> = (0..1 << num_vars).map(|_| Fr::random(&mut rng)).collect();
+            let point: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+            let comm = dory_commit(&srs, &evals);
+
+            let (eval, opening) = dory_open(&srs, &evals, &point, &mut T::new());
+            let expected = MultiLinearPoly::evaluate_with_buffer(
+                &evals,
+                &point,
+                &mut vec![Fr::ZERO; evals.len()],
+            );
+            assert_eq!(eval, expected);
+
+            assert!(dory_verify(
+                &vk,
+                &comm,
+                &point,
+                eval,
+                &opening,
+                &mut T::new()
+            ));
+            assert!(!dory_verify(
+                &vk,
+                &comm,
+                &point,
+                eval + Fr::ONE,
+                &opening,
+                &mut T::new()
+            ));
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::ops::Mul;
+
+use arith::ExtensionField;
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
+};
+use halo2curves::{ff::PrimeField, pairing::MultiMillerLoop, CurveAffine};
+use polynomials::MultilinearExtension;
+use serdes::ExpSerde;
+
+use crate::{
+    dory::setup::dory_setup, dory_commit, dory_open, dory_verify, utils::gather_evals,
+    DoryCommitment, DoryOpening, DoryPCS, DorySRS,
+};
+
+impl<G, E> ExpanderPCS<G> for DoryPCS<E>
+where
+    G: FieldEngine<ChallengeField = E::Fr, SimdCircuitField = E::Fr>,
+    E: MultiMillerLoop,
+    E::Fr: ExtensionField + PrimeField,
+    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+    E::Result: ExpSerde + Mul<E::Fr, Output = E::Result>,
+{
+    const NAME: &'static str = "DoryForExpander";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::Dory;
+
+    type Commitment = DoryCommitment<E>;
+    type Opening = DoryOpening<E>;
+    type Params = usize;
+    type SRS = DorySRS<E>;
+    type ScratchPad = ();
+    type BatchOpening = ();
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
+
+    fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
+        n_input_vars
+    }
+
+    /// The setup of the global polynomial, opened by the root alone.
+    fn gen_srs(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        rng: impl rand::RngCore,
+    ) -> Self::SRS {
+        let global_num_vars = *params + mpi_engine.n_parties().ilog2() as usize;
+        dory_setup(global_num_vars, rng)
+    }
+
+    fn commit(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<E::Fr>,
+        _scratch_pad: &mut Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Commitment>> {
+        let Some(evals) = gather_evals(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        Ok(Some(dory_commit(proving_key, &evals)))
+    }
+
+    fn open(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<E::Fr>,
+        x: &ExpanderSingleVarChallenge<G>,
+        transcript: &mut impl Transcript,
+        _scratch_pad: &Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Opening>> {
+        // NOTE: the opening is run on the root alone, over the gathered polynomial
+        let Some(evals) = gather_evals(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        let (_eval, opening) = dory_open(proving_key, &evals, &x.global_xs(), transcript);
+
+        Ok(Some(opening))
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &ExpanderSingleVarChallenge<G>,
+        v: <G as FieldEngine>::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        dory_verify(
+            verifying_key,
+            commitment,
+            &x.global_xs(),
+            v,
+            opening,
+            transcript,
+        )
+    }
+}
@@ -0,0 +1,81 @@
+use std::{marker::PhantomData, ops::Mul};
+
+use arith::ExtensionField;
+use gkr_engine::{StructuredReferenceString, Transcript};
+use halo2curves::{ff::PrimeField, pairing::MultiMillerLoop, CurveAffine};
+use polynomials::MultiLinearPoly;
+use serdes::ExpSerde;
+
+use crate::{
+    dory::{setup::dory_setup, DoryCommitment, DoryOpening, DorySRS},
+    dory_commit, dory_open, dory_verify, PolynomialCommitmentScheme,
+};
+
+/// Dory (Lee21) over a pairing-friendly curve: the multilinear polynomial is committed to as the
+/// square matrix of its evaluations, with a transparent setup, and opened with a logarithmic
+/// number of rounds, each verified with a constant number of operations in the target group.
+pub struct DoryPCS<E>
+where
+    E: MultiMillerLoop,
+    E::Fr: ExtensionField,
+{
+    _marker_e: PhantomData<E>,
+}
+
+impl<E> PolynomialCommitmentScheme<E::Fr> for DoryPCS<E>
+where
+    E: MultiMillerLoop,
+    E::Fr: ExtensionField + PrimeField,
+    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+    E::Result: ExpSerde + Mul<E::Fr, Output = E::Result>,
+{
+    const NAME: &'static str = "DoryPCS";
+
+    type Params = usize;
+    type Poly = MultiLinearPoly<E::Fr>;
+    type EvalPoint = Vec<E::Fr>;
+    type ScratchPad = ();
+
+    type SRS = DorySRS<E>;
+    type Commitment = DoryCommitment<E>;
+    type Opening = DoryOpening<E>;
+
+    fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
+
+    fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
+        (dory_setup(*params, rng), *params)
+    }
+
+    fn commit(
+        _params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        _scratch_pad: &mut Self::ScratchPad,
+    ) -> Self::Commitment {
+        dory_commit(proving_key, &poly.coeffs)
+    }
+
+    fn open(
+        _params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        x: &Self::EvalPoint,
+        _scratch_pad: &Self::ScratchPad,
+        transcript: &mut impl Transcript,
+    ) -> (E::Fr, Self::Opening) {
+        dory_open(proving_key, &poly.coeffs, x, transcript)
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &Self::EvalPoint,
+        v: E::Fr,
+        opening: &Self::Opening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        dory_verify(verifying_key, commitment, x, v, opening, transcript)
+    }
+}
@@ -0,0 +1,157 @@
+use derivative::Derivative;
+use gkr_engine::StructuredReferenceString;
+use halo2curves::{
+    group::{Curve, Group},
+    pairing::MultiMillerLoop,
+    CurveAffine,
+};
+use serdes::ExpSerde;
+
+use crate::backend;
+
+/// The public parameters of Dory over a matrix of `2^num_vars_per_side` rows and columns.
+///
+/// The bases are sampled from the random source, e.g., a public seed, as random points of the
+/// groups: there is no trapdoor to the setup. The bases of the rounds of the opening are the
+/// prefixes of the bases of the commitment, so the parameters of a matrix commit to any smaller
+/// one as well.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct DorySRS<E: MultiMillerLoop>
+where
+    E::G1Affine: ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    /// \Gamma_1, the bases of the Pedersen commitments to the rows
+    pub g1_bases: Vec<E::G1Affine>,
+    /// \Gamma_2, the bases paired with the commitments to the rows
+    pub g2_bases: Vec<E::G2Affine>,
+    /// The parameters of the verifier, logarithmic in the number of columns
+    pub verifier_params: DoryVerifierParams<E>,
+}
+
+/// The parameters of the Dory verifier: the inner pairing products of the prefixes of the bases,
+/// and the G2 base the evaluations are committed over.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct DoryVerifierParams<E: MultiMillerLoop>
+where
+    E::G1Affine: ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    /// \Gamma_1\[0\]
+    pub g1: E::G1Affine,
+    /// \Gamma_2\[0\]
+    pub g2: E::G2Affine,
+    /// The base of the commitments to the scalars, e.g., the evaluation
+    pub h2: E::G2Affine,
+    /// \chi_k = <\Gamma_1\[..2^k\], \Gamma_2\[..2^k\]>, for k from 0
+    pub chi: Vec<E::Result>,
+    /// <\Gamma_1\[2^{k-1}..2^k\], \Gamma_2\[..2^{k-1}\]>, for k from 1
+    pub delta_1_right: Vec<E::Result>,
+    /// <\Gamma_1\[..2^{k-1}\], \Gamma_2\[2^{k-1}..2^k\]>, for k from 1
+    pub delta_2_right: Vec<E::Result>,
+}
+
+impl<E: MultiMillerLoop> DoryVerifierParams<E>
+where
+    E::G1Affine: ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    /// The number of variables of a side of the largest matrix the parameters commit to
+    pub fn num_vars_per_side(&self) -> usize {
+        self.chi.len() - 1
+    }
+}
+
+impl<E: MultiMillerLoop> From<&DorySRS<E>> for DoryVerifierParams<E>
+where
+    E::G1Affine: ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    fn from(value: &DorySRS<E>) -> Self {
+        value.verifier_params.clone()
+    }
+}
+
+impl<E: MultiMillerLoop> StructuredReferenceString for DorySRS<E>
+where
+    E::G1Affine: ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Result: ExpSerde,
+{
+    type PKey = DorySRS<E>;
+    type VKey = DoryVerifierParams<E>;
+
+    fn into_keys(self) -> (Self::PKey, Self::VKey) {
+        let vk: Self::VKey = From::from(&self);
+        (self, vk)
+    }
+}
+
+/// The number of variables of a side of the matrix a polynomial of `num_vars` variables is
+/// committed as, the row variables being the higher ones
+#[inline(always)]
+pub(crate) fn dory_num_vars_per_side(num_vars: usize) -> usize {
+    num_vars.div_ceil(2)
+}
+
+pub(crate) fn dory_setup<E>(num_vars: usize, mut rng: impl rand::RngCore) -> DorySRS<E>
+where
+    E: MultiMillerLoop,
+    E::G1Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+    E::Result: ExpSerde,
+{
+    let num_vars_per_side = dory_num_vars_per_side(num_vars);
+    let side = 1 << num_vars_per_side;
+
+    let g1_proj: Vec<E::G1> = (0..side).map(|_| E::G1::random(&mut rng)).collect();
+    let mut g1_bases = vec![E::G1Affine::default(); side];
+    E::G1::batch_normalize(&g1_proj, &mut g1_bases);
+
+    let g2_proj: Vec<E::G2> = (0..side + 1).map(|_| E::G2::random(&mut rng)).collect();
+    let mut g2_bases = vec![E::G2Affine::default(); side + 1];
+    E::G2::batch_normalize(&g2_proj, &mut g2_bases);
+    let h2 = g2_bases.pop().unwrap();
+
+    // NOTE: \chi_k is \chi_{k-1} and the pairings of the upper halves of the prefixes
+    let mut chi = vec![backend::multi_miller_loop::<E>(
+        &g1_bases[..1],
+        &g2_bases[..1],
+    )];
+    let mut delta_1_right = Vec::with_capacity(num_vars_per_side);
+    let mut delta_2_right = Vec::with_capacity(num_vars_per_side);
+    (1..=num_vars_per_side).for_each(|k| {
+        let (half, len) = (1 << (k - 1), 1 << k);
+        let upper = backend::multi_miller_loop::<E>(&g1_bases[half..len], &g2_bases[half..len]);
+        chi.push(chi[k - 1] + upper);
+        delta_1_right.push(backend::multi_miller_loop::<E>(
+            &g1_bases[half..len],
+            &g2_bases[..half],
+        ));
+        delta_2_right.push(backend::multi_miller_loop::<E>(
+            &g1_bases[..half],
+            &g2_bases[half..len],
+        ));
+    });
+
+    let verifier_params = DoryVerifierParams {
+        g1: g1_bases[0],
+        g2: g2_bases[0],
+        h2,
+        chi,
+        delta_1_right,
+        delta_2_right,
+    };
+
+    DorySRS {
+        g1_bases,
+        g2_bases,
+        verifier_params,
+    }
+}
@@ -2,7 +2,7 @@ mod pedersen;
 pub use pedersen::PedersenParams;
 
 mod hyrax_impl;
-pub use hyrax_impl::{HyraxCommitment, HyraxOpening};
+pub use hyrax_impl::{HyraxCommitment, HyraxOpening, HyraxShape};
 
 mod pcs_trait_impl;
 pub use pcs_trait_impl::HyraxPCS;
@@ -1,22 +1,20 @@
-use arith::ExtensionField;
+use arith::{ExtensionField, Pod};
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
-    StructuredReferenceString, Transcript,
+    BatchOpenError, CommitLayout, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine,
+    MPIResult, PolynomialCommitmentType, StructuredReferenceString, Transcript,
 };
-use halo2curves::{ff::PrimeField, group::UncompressedEncoding, msm, CurveAffine};
+use halo2curves::{ff::PrimeField, group::UncompressedEncoding, CurveAffine};
 use polynomials::{
     EqPolynomial, MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension,
-    RefMultiLinearPoly,
 };
 use serdes::ExpSerde;
 
 use crate::{
-    hyrax::{
-        hyrax_impl::{hyrax_commit, hyrax_open, hyrax_setup, hyrax_verify},
-        pedersen::pedersen_commit,
+    hyrax::hyrax_impl::{
+        hyrax_aggregated_verify, hyrax_commit, hyrax_open, hyrax_setup, hyrax_verify,
     },
     traits::BatchOpening,
-    HyraxCommitment, HyraxOpening, HyraxPCS, PedersenParams,
+    HyraxCommitment, HyraxOpening, HyraxPCS, HyraxShape, PedersenParams,
 };
 
 use super::hyrax_impl::{
@@ -26,7 +24,7 @@ use super::hyrax_impl::{
 impl<G, C> ExpanderPCS<G> for HyraxPCS<C>
 where
     G: FieldEngine<ChallengeField = C::Scalar, SimdCircuitField = C::Scalar>,
-    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C: CurveAffine + ExpSerde + UncompressedEncoding + Pod,
     C::Scalar: ExtensionField + PrimeField,
     C::ScalarExt: ExtensionField + PrimeField,
     C::Base: PrimeField<Repr = [u8; 32]>,
@@ -35,6 +33,9 @@ where
 
     const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::Hyrax;
 
+    // NOTE: each process commits to the rows of its own share, the commitment gathers them
+    const COMMIT_LAYOUT: CommitLayout = CommitLayout::RankLocal;
+
     type Params = usize;
     type ScratchPad = ();
 
@@ -55,9 +56,9 @@ where
         mpi_engine: &impl MPIEngine,
         rng: impl rand::RngCore,
     ) -> Self::SRS {
-        let mpi_vars = mpi_engine.world_size().ilog2() as usize;
+        let mpi_vars = mpi_engine.n_parties().ilog2() as usize;
 
-        hyrax_setup(*params, mpi_vars, rng)
+        hyrax_setup(*params, mpi_vars, HyraxShape::Auto, rng)
     }
 
     fn commit(
@@ -66,25 +67,25 @@ where
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
         poly: &impl polynomials::MultilinearExtension<C::Scalar>,
         _scratch_pad: &mut Self::ScratchPad,
-    ) -> Option<Self::Commitment> {
+    ) -> MPIResult<Option<Self::Commitment>> {
         let local_commit = hyrax_commit(proving_key, poly);
 
         if mpi_engine.is_single_process() {
-            return local_commit.into();
+            return Ok(local_commit.into());
         }
 
         let mut global_commit: Vec<C> = if mpi_engine.is_root() {
-            vec![C::default(); mpi_engine.world_size() * local_commit.0.len()]
+            vec![C::default(); mpi_engine.n_parties() * local_commit.0.len()]
         } else {
             vec![]
         };
 
-        mpi_engine.gather_vec(&local_commit.0, &mut global_commit);
+        mpi_engine.gather_padded_vec(&local_commit.0, &mut global_commit)?;
         if !mpi_engine.is_root() {
-            return None;
+            return Ok(None);
         }
 
-        HyraxCommitment(global_commit).into()
+        Ok(HyraxCommitment(global_commit).into())
     }
 
     fn open(
@@ -95,10 +96,10 @@ where
         x: &ExpanderSingleVarChallenge<G>,
         _transcript: &mut impl Transcript,
         _scratch_pad: &Self::ScratchPad,
-    ) -> Option<Self::Opening> {
+    ) -> MPIResult<Option<Self::Opening>> {
         if mpi_engine.is_single_process() {
             let (_, open) = hyrax_open(proving_key, poly, &x.local_xs());
-            return open.into();
+            return Ok(open.into());
         }
 
         let pedersen_len = proving_key.msm_len();
@@ -110,13 +111,13 @@ where
         local_mle.fix_variables(&local_vars[pedersen_vars..]);
 
         let eq_mpi_vars = EqPolynomial::build_eq_x_r(&x.r_mpi);
-        let combined_coeffs = mpi_engine.coef_combine_vec(&local_basis, &eq_mpi_vars);
+        let combined_coeffs = mpi_engine.coef_combine_vec(&local_basis, &eq_mpi_vars)?;
 
         if !mpi_engine.is_root() {
-            return None;
+            return Ok(None);
         }
 
-        HyraxOpening(combined_coeffs).into()
+        Ok(HyraxOpening(combined_coeffs).into())
     }
 
     fn verify(
@@ -132,24 +133,27 @@ where
             return hyrax_verify(verifying_key, commitment, &x.local_xs(), evals, opening);
         }
 
-        let pedersen_len = verifying_key.msm_len();
-        let pedersen_vars = pedersen_len.ilog2() as usize;
-
-        let local_vars = x.local_xs();
-        let mut non_row_vars = local_vars[pedersen_vars..].to_vec();
-        non_row_vars.extend_from_slice(&x.r_mpi);
-
-        let eq_combination: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&non_row_vars);
-        let row_comm = msm::best_multiexp(&eq_combination, &commitment.0);
-
-        if pedersen_commit(verifying_key, &opening.0) != row_comm.into() {
+        // the global commitment gathers the row commitments of each process, aggregated with
+        // eq(r_mpi, .) into the commitment to the polynomial combined by the root in the opening
+        let n_parties = 1 << x.r_mpi.len();
+        if commitment.0.is_empty() || commitment.0.len() % n_parties != 0 {
             return false;
         }
+        let shards: Vec<HyraxCommitment<C>> = commitment
+            .0
+            .chunks(commitment.0.len() / n_parties)
+            .map(|rows| HyraxCommitment(rows.to_vec()))
+            .collect();
+        let eq_mpi_vars = EqPolynomial::build_eq_x_r(&x.r_mpi);
 
-        let mut scratch = vec![C::Scalar::default(); opening.0.len()];
-        evals
-            == RefMultiLinearPoly::from_ref(&opening.0)
-                .evaluate_with_buffer(&local_vars[..pedersen_vars], &mut scratch)
+        hyrax_aggregated_verify(
+            verifying_key,
+            &shards,
+            &eq_mpi_vars,
+            &x.local_xs(),
+            evals,
+            opening,
+        )
     }
 
     /// Open a set of polynomials at a set of points.
@@ -161,11 +165,19 @@ where
         eval_points: &[ExpanderSingleVarChallenge<G>],
         _scratch_pad: &Self::ScratchPad,
         transcript: &mut impl Transcript,
-    ) -> (Vec<C::Scalar>, Self::BatchOpening) {
+    ) -> Result<(Vec<C::Scalar>, Self::BatchOpening), BatchOpenError> {
         if mpi_engine.is_single_process() || mpi_engine.is_root() {
+            for x in eval_points {
+                x.check_shape(mpi_engine.world_size())?;
+            }
             let points = eval_points.iter().map(|x| x.local_xs()).collect::<Vec<_>>();
 
-            hyrax_multi_points_batch_open_internal(proving_key, mle_poly_list, &points, transcript)
+            Ok(hyrax_multi_points_batch_open_internal(
+                proving_key,
+                mle_poly_list,
+                &points,
+                transcript,
+            ))
         } else {
             // todo: handle this case?
             panic!("Hyrax PCS does not support multi-points batch opening in non-root processes");
@@ -182,11 +194,9 @@ where
         batch_opening: &Self::BatchOpening,
         transcript: &mut impl Transcript,
     ) -> bool {
-        for x_i in x {
-            assert!(
-                x_i.r_mpi.is_empty(),
-                "Hyrax PCS does not support multi-points batch verification with MPI challenges"
-            );
+        // NOTE: Hyrax does not support multi-points batch verification with MPI challenges
+        if x.iter().any(|x_i| x_i.check_shape(1).is_err()) {
+            return false;
         }
         let points = x.iter().map(|x| x.local_xs()).collect::<Vec<_>>();
 
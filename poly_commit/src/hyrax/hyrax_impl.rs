@@ -1,19 +1,22 @@
 use arith::{ExtensionField, Field};
 use gkr_engine::Transcript;
-use halo2curves::{ff::PrimeField, group::UncompressedEncoding, msm, CurveAffine};
+use halo2curves::{ff::PrimeField, group::UncompressedEncoding, CurveAffine};
 use polynomials::{
     EqPolynomial, MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension,
     RefMultiLinearPoly,
 };
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
 use serdes::ExpSerde;
 use utils::timer::Timer;
 
 use crate::batching::{prover_merge_points, verifier_merge_points};
 use crate::traits::BatchOpening;
 use crate::{
+    backend, batch_to_affine,
     hyrax::{
-        pedersen::{pedersen_commit, pedersen_setup},
+        pedersen::{pedersen_commit, pedersen_commit_projective, pedersen_setup},
         PedersenParams,
     },
     powers_series,
@@ -21,27 +24,46 @@ use crate::{
 
 use super::HyraxPCS;
 
+/// The shape of the matrix a polynomial is committed as, one Pedersen commitment per row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HyraxShape {
+    /// Rows of half the variables rounded up, balancing the row commitments against the row
+    /// sent in an opening, unless a process holds fewer variables than that
+    #[default]
+    Auto,
+    /// Rows of `2^row_vars` values, e.g., long rows to shorten the commitment, or short rows to
+    /// shorten the opening. Each process commits whole rows of its own values.
+    RowVars(usize),
+}
+
+impl HyraxShape {
+    /// The number of variables of a row, for polynomials of `local_vars` variables on each of the
+    /// `2^mpi_vars` processes
+    pub fn row_vars(&self, local_vars: usize, mpi_vars: usize) -> usize {
+        match *self {
+            HyraxShape::Auto => (mpi_vars + local_vars).div_ceil(2).min(local_vars),
+            HyraxShape::RowVars(row_vars) => {
+                assert!(
+                    row_vars <= local_vars,
+                    "rows of {row_vars} variables are longer than the {local_vars} local variables"
+                );
+                row_vars
+            }
+        }
+    }
+}
+
 pub(crate) fn hyrax_setup<C: CurveAffine + ExpSerde>(
     local_vars: usize,
     mpi_vars: usize,
+    shape: HyraxShape,
     rng: impl rand::RngCore,
 ) -> PedersenParams<C>
 where
     C::Scalar: PrimeField,
     C::Base: PrimeField<Repr = [u8; 32]>,
 {
-    let pedersen_vars = {
-        let total_vars = mpi_vars + local_vars;
-        let squared_row_var = total_vars.div_ceil(2);
-
-        if mpi_vars + squared_row_var > total_vars {
-            total_vars - mpi_vars
-        } else {
-            squared_row_var
-        }
-    };
-
-    let pedersen_length = 1 << pedersen_vars;
+    let pedersen_length = 1 << shape.row_vars(local_vars, mpi_vars);
 
     pedersen_setup(pedersen_length, rng)
 }
@@ -64,6 +86,40 @@ where
     }
 }
 
+impl<C> HyraxCommitment<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+{
+    /// The commitment to `sum_i scalars[i] f_i`, given the commitments to the f_i under the same
+    /// parameters, e.g., the witness shards of the MPI processes. The Pedersen commitments being
+    /// homomorphic, the rows of the commitments are combined, and the aggregated polynomial is
+    /// opened with a single opening of the length of a row.
+    ///
+    /// Panics if the numbers of commitments and scalars differ, or the commitments are of
+    /// different numbers of rows.
+    pub fn aggregate(commitments: &[Self], scalars: &[C::Scalar]) -> Self {
+        assert_eq!(
+            commitments.len(),
+            scalars.len(),
+            "the numbers of commitments and scalars differ"
+        );
+        let num_rows = commitments.first().map_or(0, |c| c.0.len());
+        assert!(
+            commitments.iter().all(|c| c.0.len() == num_rows),
+            "the commitments are of different numbers of rows"
+        );
+
+        let rows: Vec<C::Curve> = (0..num_rows)
+            .map(|i| {
+                let column: Vec<C> = commitments.iter().map(|c| c.0[i]).collect();
+                backend::msm(scalars, &column)
+            })
+            .collect();
+
+        Self(batch_to_affine(&rows))
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct HyraxOpening<C>(pub Vec<C::Scalar>)
 where
@@ -139,13 +195,14 @@ where
         return HyraxCommitment(vec![commitment]);
     }
 
-    let commitments: Vec<C> = mle_poly
-        .hypercube_basis_ref()
-        .chunks(params.msm_len())
-        .map(|sub_hypercube| pedersen_commit(params, sub_hypercube))
+    // the rows are committed in projective coordinates, and normalized at once
+    let commitments: Vec<C::Curve> = mle_poly
+        .as_matrix(params.msm_len().ilog2() as usize)
+        .rows()
+        .map(|sub_hypercube| pedersen_commit_projective(params, sub_hypercube))
         .collect();
 
-    HyraxCommitment(commitments)
+    HyraxCommitment(batch_to_affine(&commitments))
 }
 
 // NOTE(HS) the hyrax opening returns an eval and an opening against the eval_point on input.
@@ -190,19 +247,16 @@ where
     let pedersen_vars = pedersen_len.ilog2() as usize;
 
     let eq_combination: Vec<C::Scalar> = EqPolynomial::build_eq_x_r(&eval_point[pedersen_vars..]);
-    let row_comm = msm::best_multiexp(&eq_combination, &comm.0);
+    let row_comm = backend::msm(&eq_combination, &comm.0);
 
-    let pedersen_commitment = pedersen_commit(params, &proof.0);
+    let pedersen_commitment = pedersen_commit_projective(params, &proof.0);
 
-    if pedersen_commitment != row_comm.into() {
+    if pedersen_commitment != row_comm {
         eprintln!("pedersen commitment not match",);
         return false;
     }
 
-    let mut scratch = vec![C::Scalar::default(); proof.0.len()];
-    let res = eval
-        == RefMultiLinearPoly::from_ref(&proof.0)
-            .evaluate_with_buffer(&eval_point[..pedersen_vars], &mut scratch);
+    let res = eval == EqPolynomial::eq_weighted_sum(&eval_point[..pedersen_vars], &proof.0);
     if !res {
         eprintln!("evaluation does not match");
     }
@@ -210,6 +264,70 @@ where
     res
 }
 
+/// Open `sum_i scalars[i] f_i` at `eval_point`, the f_i being of the same number of variables,
+/// against the commitment `HyraxCommitment::aggregate` computes from the commitments to the f_i.
+pub(crate) fn hyrax_aggregated_open<C>(
+    params: &PedersenParams<C>,
+    mle_poly_list: &[impl MultilinearExtension<C::Scalar>],
+    scalars: &[C::Scalar],
+    eval_point: &[C::Scalar],
+) -> (C::Scalar, HyraxOpening<C>)
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::ScalarExt: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    assert_eq!(mle_poly_list.len(), scalars.len());
+
+    let mut aggregated = vec![C::Scalar::zero(); 1 << eval_point.len()];
+    mle_poly_list
+        .iter()
+        .zip(scalars.iter())
+        .for_each(|(mle_poly, scalar)| {
+            let basis = mle_poly.hypercube_basis_ref();
+            assert_eq!(basis.len(), aggregated.len());
+            aggregated
+                .par_iter_mut()
+                .zip(basis.par_iter())
+                .for_each(|(a, c)| *a += *scalar * *c);
+        });
+
+    hyrax_open(
+        params,
+        &RefMultiLinearPoly::from_ref(&aggregated),
+        eval_point,
+    )
+}
+
+/// Verify the opening of `sum_i scalars[i] f_i` at `eval_point` to `eval`, given the commitments
+/// to the f_i, aggregated by `HyraxCommitment::aggregate`.
+pub(crate) fn hyrax_aggregated_verify<C>(
+    params: &PedersenParams<C>,
+    comm_list: &[HyraxCommitment<C>],
+    scalars: &[C::Scalar],
+    eval_point: &[C::Scalar],
+    eval: C::Scalar,
+    proof: &HyraxOpening<C>,
+) -> bool
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::ScalarExt: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    if comm_list.len() != scalars.len()
+        || comm_list
+            .iter()
+            .any(|comm| comm.0.len() != comm_list[0].0.len())
+    {
+        return false;
+    }
+
+    let aggregated = HyraxCommitment::aggregate(comm_list, scalars);
+    hyrax_verify(params, &aggregated, eval_point, eval, proof)
+}
+
 // batch open a set of mle_polys at the same point
 // returns a set of eval points and a signle opening
 // NOTE: random linear combination is used to merge polynomials
@@ -294,9 +412,9 @@ where
         scalars.extend_from_slice(scale(&eq_combination, c).as_ref());
     }
 
-    let row_comm = msm::best_multiexp(&scalars, &bases);
+    let row_comm = backend::msm(&scalars, &bases);
 
-    if pedersen_commit(params, &batch_proof.0) != row_comm.into() {
+    if pedersen_commit_projective(params, &batch_proof.0) != row_comm {
         eprintln!("commitment not matching");
         return false;
     }
@@ -308,10 +426,7 @@ where
         .map(|(eval, challenge)| *eval * *challenge)
         .sum::<C::Scalar>();
 
-    let mut scratch = vec![C::Scalar::default(); batch_proof.0.len()];
-    eval_sum
-        == RefMultiLinearPoly::from_ref(&batch_proof.0)
-            .evaluate_with_buffer(&eval_point[..pedersen_vars], &mut scratch)
+    eval_sum == EqPolynomial::eq_weighted_sum(&eval_point[..pedersen_vars], &batch_proof.0)
 }
 
 #[inline(always)]
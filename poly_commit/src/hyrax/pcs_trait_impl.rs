@@ -10,12 +10,14 @@ use crate::traits::BatchOpening;
 use crate::{
     hyrax::hyrax_impl::{hyrax_commit, hyrax_open, hyrax_setup, hyrax_verify},
     traits::BatchOpeningPCS,
-    HyraxCommitment, HyraxOpening, PedersenParams, PolynomialCommitmentScheme,
+    HyraxCommitment, HyraxOpening, HyraxShape, PedersenParams, PolynomialCommitmentScheme,
 };
 
 use super::hyrax_impl::hyrax_multi_points_batch_open_internal;
 use super::hyrax_impl::hyrax_multi_points_batch_verify_internal;
-use super::hyrax_impl::{hyrax_batch_open, hyrax_batch_verify};
+use super::hyrax_impl::{
+    hyrax_aggregated_open, hyrax_aggregated_verify, hyrax_batch_open, hyrax_batch_verify,
+};
 
 pub struct HyraxPCS<C>
 where
@@ -26,6 +28,52 @@ where
     _phantom_c: PhantomData<C>,
 }
 
+impl<C> HyraxPCS<C>
+where
+    C: CurveAffine + ExpSerde + UncompressedEncoding,
+    C::Scalar: ExtensionField + PrimeField,
+    C::ScalarExt: ExtensionField + PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    /// The SRS committing to polynomials of `local_vars` variables on each of `2^mpi_vars`
+    /// processes as matrices of the given shape, instead of the `HyraxShape::Auto` shape of
+    /// `gen_srs_for_testing` and `ExpanderPCS::gen_srs`. The shape is that of the rows of the
+    /// SRS, which the commitments and openings follow.
+    pub fn gen_srs_with_shape(
+        local_vars: usize,
+        mpi_vars: usize,
+        shape: HyraxShape,
+        rng: impl rand::RngCore,
+    ) -> PedersenParams<C> {
+        hyrax_setup(local_vars, mpi_vars, shape, rng)
+    }
+
+    /// Open `sum_i scalars[i] polys[i]` at `x` with a single opening, e.g., the witness shards of
+    /// several processes, rather than opening each of them. Returns the evaluation of the
+    /// aggregated polynomial, i.e., `sum_i scalars[i] polys[i](x)`.
+    pub fn open_aggregated(
+        proving_key: &PedersenParams<C>,
+        polys: &[MultiLinearPoly<C::Scalar>],
+        scalars: &[C::Scalar],
+        x: &[C::Scalar],
+    ) -> (C::Scalar, HyraxOpening<C>) {
+        hyrax_aggregated_open(proving_key, polys, scalars, x)
+    }
+
+    /// Verify an opening of `open_aggregated` against the commitments to the polynomials, which
+    /// are aggregated by `HyraxCommitment::aggregate`.
+    pub fn verify_aggregated(
+        verifying_key: &PedersenParams<C>,
+        commitments: &[HyraxCommitment<C>],
+        scalars: &[C::Scalar],
+        x: &[C::Scalar],
+        v: C::Scalar,
+        opening: &HyraxOpening<C>,
+    ) -> bool {
+        hyrax_aggregated_verify(verifying_key, commitments, scalars, x, v, opening)
+    }
+}
+
 impl<C> PolynomialCommitmentScheme<C::Scalar> for HyraxPCS<C>
 where
     C: CurveAffine + ExpSerde + UncompressedEncoding,
@@ -47,7 +95,7 @@ where
     fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
 
     fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
-        (hyrax_setup(*params, 0, rng), *params)
+        (hyrax_setup(*params, 0, HyraxShape::Auto, rng), *params)
     }
 
     fn commit(
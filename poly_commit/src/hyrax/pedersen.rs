@@ -1,10 +1,15 @@
+use std::sync::Arc;
+
 use gkr_engine::StructuredReferenceString;
 use halo2curves::{
     ff::{Field, PrimeField},
     group::Curve,
-    msm, CurveAffine,
+    CurveAffine,
 };
-use serdes::ExpSerde;
+use rayon::prelude::*;
+use serdes::{ExpSerde, Mmap};
+
+use crate::{backend, SrsPoints};
 
 #[derive(Clone, Debug, Default)]
 pub struct PedersenParams<C>
@@ -12,76 +17,46 @@ where
     C: CurveAffine,
     C::Base: PrimeField<Repr = [u8; 32]>,
 {
-    pub bases: Vec<C>,
+    pub bases: SrsPoints<C>,
     pub pre_bases: Vec<C::Curve>,
 }
 
+impl<C> PedersenParams<C>
+where
+    C: CurveAffine,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    /// The pre-computed bases, written as affine points
+    fn pre_bases_from_points(points: SrsPoints<C>) -> Vec<C::Curve> {
+        points.par_iter().map(|p| p.to_curve()).collect()
+    }
+}
+
 impl<C> ExpSerde for PedersenParams<C>
 where
     C: CurveAffine,
     C::Base: PrimeField<Repr = [u8; 32]>,
 {
     fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> serdes::SerdeResult<()> {
-        // basis
-        {
-            self.bases.len().serialize_into(&mut writer)?;
-            for curve_element in self.bases.iter() {
-                // We want to write both x and y coordinates.
-                let coord = curve_element.coordinates().unwrap();
-                // todo: this incurrs a cost to convert from Montogomery form.
-                coord.x().to_repr().serialize_into(&mut writer)?;
-                coord.y().to_repr().serialize_into(&mut writer)?;
-            }
-        }
-        // pre-computed bases
-        {
-            self.pre_bases.len().serialize_into(&mut writer)?;
-
-            let mut normalized_bases = vec![C::default(); self.pre_bases.len()];
-            C::Curve::batch_normalize(&self.pre_bases, &mut normalized_bases);
-
-            for curve_element in normalized_bases.iter() {
-                // We want to write both x and y coordinates.
-                let coord = curve_element.coordinates().unwrap();
-                // todo: this incurrs a cost to convert from Montogomery form.
-                coord.x().to_repr().serialize_into(&mut writer)?;
-                coord.y().to_repr().serialize_into(&mut writer)?;
-            }
-        }
-        Ok(())
+        self.bases.serialize_into(&mut writer)?;
+
+        let mut normalized_bases = vec![C::default(); self.pre_bases.len()];
+        C::Curve::batch_normalize(&self.pre_bases, &mut normalized_bases);
+        SrsPoints::from(normalized_bases).serialize_into(&mut writer)
     }
 
     fn deserialize_from<R: std::io::Read>(mut reader: R) -> serdes::SerdeResult<Self> {
-        let mut buf = [0u8; 32];
-
-        // bases
-        let bases = {
-            let bases_len = usize::deserialize_from(&mut reader)?;
-            let mut bases = Vec::with_capacity(bases_len);
-            for _ in 0..bases_len {
-                reader.read_exact(&mut buf)?;
-                let x = C::Base::from_repr(buf).unwrap();
-                reader.read_exact(&mut buf)?;
-                let y = C::Base::from_repr(buf).unwrap();
-                bases.push(C::from_xy(x, y).unwrap())
-            }
-            bases
-        };
-
-        // pre-computed bases
-        let pre_bases = {
-            let pre_bases_len = usize::deserialize_from(&mut reader)?;
-            let mut pre_bases = Vec::with_capacity(pre_bases_len);
-            for _ in 0..pre_bases_len {
-                reader.read_exact(&mut buf)?;
-                let x = C::Base::from_repr(buf).unwrap();
-                reader.read_exact(&mut buf)?;
-                let y = C::Base::from_repr(buf).unwrap();
-                pre_bases.push(C::from_xy(x, y).unwrap().to_curve())
-            }
-            pre_bases
-        };
+        let bases = SrsPoints::deserialize_from(&mut reader)?;
+        let pre_bases = Self::pre_bases_from_points(SrsPoints::deserialize_from(&mut reader)?);
+        Ok(Self { bases, pre_bases })
+    }
 
+    /// The bases are decoded on first use, the pre-computed bases, used by every commitment, as
+    /// they are loaded.
+    fn deserialize_from_mmap(mmap: &Arc<Mmap>, offset: &mut usize) -> serdes::SerdeResult<Self> {
+        let bases = SrsPoints::deserialize_from_mmap(mmap, offset)?;
+        let pre_bases =
+            Self::pre_bases_from_points(SrsPoints::deserialize_from_mmap(mmap, offset)?);
         Ok(Self { bases, pre_bases })
     }
 }
@@ -114,12 +89,29 @@ where
 
     let mut bases = vec![C::default(); length];
     C::Curve::batch_normalize(&proj_bases, &mut bases);
-    let pre_bases = msm::multiexp_precompute(&bases, 12);
+    let pre_bases = backend::fixed_base_msm_precompute(&bases, 12);
 
-    PedersenParams { bases, pre_bases }
+    PedersenParams {
+        bases: bases.into(),
+        pre_bases,
+    }
 }
 
 pub(crate) fn pedersen_commit<C>(params: &PedersenParams<C>, coeffs: &[C::Scalar]) -> C
+where
+    C: CurveAffine,
+    C::Scalar: PrimeField,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    pedersen_commit_projective(params, coeffs).to_affine()
+}
+
+/// The Pedersen commitment in projective coordinates, for the commitments of the rows to be
+/// normalized in a batch, or compared without normalization
+pub(crate) fn pedersen_commit_projective<C>(
+    params: &PedersenParams<C>,
+    coeffs: &[C::Scalar],
+) -> C::Curve
 where
     C: CurveAffine,
     C::Scalar: PrimeField,
@@ -127,9 +119,9 @@ where
 {
     let mut what = C::default().to_curve();
 
-    msm::multiexp_precompute_serial::<C>(coeffs, &params.pre_bases, 12, &mut what);
+    backend::fixed_base_msm_serial::<C>(coeffs, &params.bases, &params.pre_bases, 12, &mut what);
 
-    what.to_affine()
+    what
 }
 
 impl<C> PedersenParams<C>
@@ -0,0 +1,10 @@
+mod setup;
+pub use setup::IpaSRS;
+
+mod ipa_impl;
+pub use ipa_impl::{ipa_commit, ipa_open, ipa_verify, IpaCommitment, IpaOpening, IpaRound};
+
+mod pcs_trait_impl;
+pub use pcs_trait_impl::IpaPCS;
+
+mod expander_api;
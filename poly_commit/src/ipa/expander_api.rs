@@ -0,0 +1,97 @@
+use arith::ExtensionField;
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
+};
+use halo2curves::{ff::PrimeField, CurveAffine};
+use polynomials::MultilinearExtension;
+use serdes::ExpSerde;
+
+use crate::{
+    ipa::setup::ipa_setup, ipa_commit, ipa_open, ipa_verify, utils::gather_evals, IpaCommitment,
+    IpaOpening, IpaPCS, IpaSRS,
+};
+
+impl<G, C> ExpanderPCS<G> for IpaPCS<C>
+where
+    G: FieldEngine<ChallengeField = C::Scalar, SimdCircuitField = C::Scalar>,
+    C: CurveAffine + ExpSerde,
+    C::Scalar: ExtensionField + PrimeField,
+{
+    const NAME: &'static str = "IpaForExpander";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::IPA;
+
+    type Commitment = IpaCommitment<C>;
+    type Opening = IpaOpening<C>;
+    type Params = usize;
+    type SRS = IpaSRS<C>;
+    type ScratchPad = ();
+    type BatchOpening = ();
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
+
+    fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
+        n_input_vars
+    }
+
+    /// The setup of the global polynomial, opened by the root alone.
+    fn gen_srs(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        rng: impl rand::RngCore,
+    ) -> Self::SRS {
+        let global_num_vars = *params + mpi_engine.n_parties().ilog2() as usize;
+        ipa_setup(global_num_vars, rng)
+    }
+
+    fn commit(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::Scalar>,
+        _scratch_pad: &mut Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Commitment>> {
+        let Some(evals) = gather_evals(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        Ok(Some(ipa_commit(proving_key, &evals)))
+    }
+
+    fn open(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::Scalar>,
+        x: &ExpanderSingleVarChallenge<G>,
+        transcript: &mut impl Transcript,
+        _scratch_pad: &Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Opening>> {
+        // NOTE: the opening is run on the root alone, over the gathered polynomial
+        let Some(evals) = gather_evals(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        let (_eval, opening) = ipa_open(proving_key, &evals, &x.global_xs(), transcript);
+
+        Ok(Some(opening))
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &ExpanderSingleVarChallenge<G>,
+        v: <G as FieldEngine>::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        ipa_verify(
+            verifying_key,
+            commitment,
+            &x.global_xs(),
+            v,
+            opening,
+            transcript,
+        )
+    }
+}
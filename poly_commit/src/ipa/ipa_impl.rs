@@ -0,0 +1,297 @@
+use arith::ExtensionField;
+use derivative::Derivative;
+use gkr_engine::Transcript;
+use halo2curves::{
+    ff::Field,
+    group::{prime::PrimeCurveAffine, Curve, GroupEncoding},
+    CurveAffine,
+};
+use itertools::izip;
+use polynomials::EqPolynomial;
+use rayon::prelude::*;
+use serdes::ExpSerde;
+
+use crate::{backend, batch_to_affine, ipa::IpaSRS};
+
+/// The Pedersen commitment over the bases G to the evaluations of a multilinear polynomial over
+/// the hypercube.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IpaCommitment<C: CurveAffine>(pub C);
+
+impl<C: CurveAffine> AsRef<IpaCommitment<C>> for IpaCommitment<C> {
+    fn as_ref(&self) -> &IpaCommitment<C> {
+        self
+    }
+}
+
+// Derive macros does not work for tuple structs
+impl<C: CurveAffine + ExpSerde> ExpSerde for IpaCommitment<C> {
+    fn serialize_into<W: std::io::Write>(&self, writer: W) -> serdes::SerdeResult<()> {
+        self.0.serialize_into(writer)
+    }
+
+    fn deserialize_from<R: std::io::Read>(reader: R) -> serdes::SerdeResult<Self> {
+        Ok(Self(C::deserialize_from(reader)?))
+    }
+}
+
+/// The messages of a round of the inner product argument, halving the vectors.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct IpaRound<C>
+where
+    C: CurveAffine + ExpSerde,
+{
+    /// <a_L, G_R> + <a_L, b_R> U and <a_R, G_L> + <a_R, b_L> U
+    pub left: C,
+    pub right: C,
+}
+
+/// Opening of the evaluations a at a point u by the inner product argument (BCC+16, BBB+18),
+/// without the zero knowledge blinding: the rounds fold a, the bases G and the weights
+/// b = eq(u) down to a single element, proving the knowledge of a such that the commitment is
+/// <a, G> and the evaluation is <a, b>.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct IpaOpening<C>
+where
+    C: CurveAffine + ExpSerde,
+    C::Scalar: ExpSerde,
+{
+    /// The rounds, binding the variables from the top one down
+    pub rounds: Vec<IpaRound<C>>,
+    /// The folded a
+    pub a: C::Scalar,
+}
+
+#[inline(always)]
+fn append_point<C: GroupEncoding>(fs_transcript: &mut impl Transcript, point: &C) {
+    fs_transcript.append_u8_slice(point.to_bytes().as_ref());
+}
+
+#[inline(always)]
+fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    izip!(a, b).map(|(a_i, b_i)| *a_i * *b_i).sum()
+}
+
+/// x * s_L + x_inv * s_R
+#[inline(always)]
+fn fold_scalars<F: Field>(s: &[F], x: F, x_inv: F) -> Vec<F> {
+    let (left, right) = s.split_at(s.len() / 2);
+    izip!(left, right)
+        .map(|(l, r)| *l * x + *r * x_inv)
+        .collect()
+}
+
+/// x * g_L + x_inv * g_R
+#[inline(always)]
+fn fold_points<C: CurveAffine>(g: &[C], x: C::Scalar, x_inv: C::Scalar) -> Vec<C> {
+    let (left, right) = g.split_at(g.len() / 2);
+    let proj: Vec<C::Curve> = left
+        .par_iter()
+        .zip(right)
+        .map(|(l, r)| *l * x + *r * x_inv)
+        .collect();
+    batch_to_affine(&proj)
+}
+
+/// The base the inner products are committed over, scaled by a challenge drawn after the
+/// evaluation, so that the prover cannot choose the evaluation against U.
+#[inline(always)]
+fn ipa_inner_product_base<C, T>(srs: &IpaSRS<C>, eval: C::Scalar, fs_transcript: &mut T) -> C
+where
+    C: CurveAffine + ExpSerde,
+    C::Scalar: ExtensionField,
+    T: Transcript,
+{
+    fs_transcript.append_field_element(&eval);
+    let alpha = fs_transcript.generate_field_element::<C::Scalar>();
+    (srs.u * alpha).to_affine()
+}
+
+/// Commit to the multilinear polynomial of evaluations `evals` over the hypercube.
+pub fn ipa_commit<C>(srs: &IpaSRS<C>, evals: &[C::Scalar]) -> IpaCommitment<C>
+where
+    C: CurveAffine + ExpSerde,
+{
+    assert!(
+        srs.bases.len() >= evals.len(),
+        "the SRS commits to {} evaluations, not {}",
+        srs.bases.len(),
+        evals.len()
+    );
+    IpaCommitment(backend::msm(evals, &srs.bases[..evals.len()]).to_affine())
+}
+
+/// Open the multilinear polynomial of evaluations `evals` over the hypercube at `point`, the
+/// variables being LSB first.
+pub fn ipa_open<C, T>(
+    srs: &IpaSRS<C>,
+    evals: &[C::Scalar],
+    point: &[C::Scalar],
+    fs_transcript: &mut T,
+) -> (C::Scalar, IpaOpening<C>)
+where
+    C: CurveAffine + ExpSerde,
+    C::Scalar: ExtensionField + ExpSerde,
+    T: Transcript,
+{
+    assert_eq!(evals.len(), 1 << point.len());
+    assert!(srs.bases.len() >= evals.len());
+
+    let mut a = evals.to_vec();
+    let mut b = EqPolynomial::build_eq_x_r(point);
+    let mut g = srs.bases[..evals.len()].to_vec();
+    let eval = inner_product(&a, &b);
+    let u = ipa_inner_product_base(srs, eval, fs_transcript);
+
+    let rounds = (0..point.len())
+        .map(|_| {
+            let half = a.len() / 2;
+            let (a_left, a_right) = a.split_at(half);
+            let (b_left, b_right) = b.split_at(half);
+            let (g_left, g_right) = g.split_at(half);
+
+            let left =
+                (backend::msm(a_left, g_right) + u * inner_product(a_left, b_right)).to_affine();
+            let right =
+                (backend::msm(a_right, g_left) + u * inner_product(a_right, b_left)).to_affine();
+            append_point(fs_transcript, &left);
+            append_point(fs_transcript, &right);
+            let x = fs_transcript.generate_field_element::<C::Scalar>();
+            let x_inv = x.invert().unwrap();
+
+            a = fold_scalars(&a, x, x_inv);
+            b = fold_scalars(&b, x_inv, x);
+            g = fold_points(&g, x_inv, x);
+
+            IpaRound { left, right }
+        })
+        .collect();
+
+    (eval, IpaOpening { rounds, a: a[0] })
+}
+
+/// Verify the opening of the commitment `comm` at `point` to `eval`.
+///
+/// NOTE: the verifier folds the bases with a multi-scalar multiplication as long as the
+/// polynomial, unlike the logarithmic checks of the rest of the argument.
+pub fn ipa_verify<C, T>(
+    srs: &IpaSRS<C>,
+    comm: &IpaCommitment<C>,
+    point: &[C::Scalar],
+    eval: C::Scalar,
+    opening: &IpaOpening<C>,
+    fs_transcript: &mut T,
+) -> bool
+where
+    C: CurveAffine + ExpSerde,
+    C::Scalar: ExtensionField + ExpSerde,
+    T: Transcript,
+{
+    let num_vars = point.len();
+    if srs.bases.len() < 1 << num_vars || opening.rounds.len() != num_vars {
+        return false;
+    }
+
+    let u = ipa_inner_product_base(srs, eval, fs_transcript);
+
+    // NOTE: P = <a, G> + <a, b> U, and x^2 L + P + x^{-2} R after each round
+    let mut p = comm.0.to_curve() + u * eval;
+    let challenges: Vec<(C::Scalar, C::Scalar)> = opening
+        .rounds
+        .iter()
+        .map(|round| {
+            append_point(fs_transcript, &round.left);
+            append_point(fs_transcript, &round.right);
+            let x = fs_transcript.generate_field_element::<C::Scalar>();
+            let x_inv = x.invert().unwrap();
+            p += round.left * (x * x) + round.right * (x_inv * x_inv);
+            (x, x_inv)
+        })
+        .collect();
+
+    // the round binding variable k folds the halves of b by (1 - u_k) x^{-1} + u_k x, and those
+    // of G by x^{-1} and x, the rounds binding the variables from the top one down
+    let b: C::Scalar = izip!(point.iter().rev(), &challenges)
+        .map(|(u_k, (x, x_inv))| (C::Scalar::ONE - u_k) * x_inv + *u_k * x)
+        .product();
+    let mut s = vec![C::Scalar::ONE];
+    challenges.iter().rev().for_each(|(x, x_inv)| {
+        s = s
+            .iter()
+            .map(|s_i| *s_i * x_inv)
+            .chain(s.iter().map(|s_i| *s_i * x))
+            .collect();
+    });
+    let g = backend::msm(&s, &srs.bases[..s.len()]);
+
+    p == g * opening.a + u * (opening.a * b)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use gkr_engine::Transcript;
+    use gkr_hashers::Keccak256hasher;
+    use halo2curves::{
+        bn256::{Fr, G1Affine},
+        ff::Field,
+    };
+    use polynomials::MultiLinearPoly;
+    use transcript::BytesHashTranscript;
+
+    use crate::ipa::setup::ipa_setup;
+
+    use super::*;
+
+    #[test]
+    fn test_ipa_e2e() {
+        type T = BytesHashTranscript<Keccak256hasher>;
+        let mut rng = test_rng();
+
+        for num_vars in [0, 1, 4, 5] {
+            let srs = ipa_setup::<G1Affine>(num_vars, &mut rng);
+
+            let evals: Vec<Fr> = (0..1 << num_vars).map(|_| Fr::random(&mut rng)).collect();
+            let point: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+            let comm = ipa_commit(&srs, &evals);
+
+            let (eval, opening) = ipa_open(&srs, &evals, &point, &mut T::new());
+            let expected = MultiLinearPoly::evaluate_with_buffer(
+                &evals,
+                &point,
+                &mut vec![Fr::ZERO; evals.len()],
+            );
+            assert_eq!(eval, expected);
+
+            assert!(ipa_verify(
+                &srs,
+                &comm,
+                &point,
+                eval,
+                &opening,
+                &mut T::new()
+            ));
+            assert!(!ipa_verify(
+                &srs,
+                &comm,
+                &point,
+                eval + Fr::ONE,
+                &opening,
+                &mut T::new()
+            ));
+
+            let mut bad_opening = opening.clone();
+            bad_opening.a += Fr::ONE;
+            assert!(!ipa_verify(
+                &srs,
+                &comm,
+                &point,
+                eval,
+                &bad_opening,
+                &mut T::new()
+            ));
+        }
+    }
+}
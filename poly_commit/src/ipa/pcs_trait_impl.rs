@@ -0,0 +1,79 @@
+use std::marker::PhantomData;
+
+use arith::ExtensionField;
+use gkr_engine::{StructuredReferenceString, Transcript};
+use halo2curves::{ff::PrimeField, CurveAffine};
+use polynomials::MultiLinearPoly;
+use serdes::ExpSerde;
+
+use crate::{
+    ipa::{setup::ipa_setup, IpaCommitment, IpaOpening, IpaSRS},
+    ipa_commit, ipa_open, ipa_verify, PolynomialCommitmentScheme,
+};
+
+/// The inner product argument over a prime order curve: the multilinear polynomial is committed
+/// to by the Pedersen commitment to its evaluations, with a transparent setup, and opened with a
+/// logarithmic number of rounds. No pairing is involved, so the curve may be one of a cycle,
+/// e.g., Grumpkin, for the proofs to be verified in Halo2-style recursion.
+pub struct IpaPCS<C>
+where
+    C: CurveAffine,
+    C::Scalar: ExtensionField,
+{
+    _marker_c: PhantomData<C>,
+}
+
+impl<C> PolynomialCommitmentScheme<C::Scalar> for IpaPCS<C>
+where
+    C: CurveAffine + ExpSerde,
+    C::Scalar: ExtensionField + PrimeField,
+{
+    const NAME: &'static str = "IpaPCS";
+
+    type Params = usize;
+    type Poly = MultiLinearPoly<C::Scalar>;
+    type EvalPoint = Vec<C::Scalar>;
+    type ScratchPad = ();
+
+    type SRS = IpaSRS<C>;
+    type Commitment = IpaCommitment<C>;
+    type Opening = IpaOpening<C>;
+
+    fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
+
+    fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
+        (ipa_setup(*params, rng), *params)
+    }
+
+    fn commit(
+        _params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        _scratch_pad: &mut Self::ScratchPad,
+    ) -> Self::Commitment {
+        ipa_commit(proving_key, &poly.coeffs)
+    }
+
+    fn open(
+        _params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        x: &Self::EvalPoint,
+        _scratch_pad: &Self::ScratchPad,
+        transcript: &mut impl Transcript,
+    ) -> (C::Scalar, Self::Opening) {
+        ipa_open(proving_key, &poly.coeffs, x, transcript)
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &Self::EvalPoint,
+        v: C::Scalar,
+        opening: &Self::Opening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        ipa_verify(verifying_key, commitment, x, v, opening, transcript)
+    }
+}
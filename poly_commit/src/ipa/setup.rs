@@ -0,0 +1,62 @@
+use derivative::Derivative;
+use gkr_engine::StructuredReferenceString;
+use halo2curves::{
+    group::{Curve, Group},
+    CurveAffine,
+};
+use serdes::ExpSerde;
+
+/// The public parameters of the inner product argument over `2^num_vars` evaluations.
+///
+/// The bases are sampled from the random source, e.g., a public seed, as random points of the
+/// curve: there is no trapdoor to the setup, and no pairing is needed, so any prime order curve
+/// will do, e.g., a curve of a cycle for recursion. The prefixes of the bases commit to the
+/// smaller polynomials as well.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct IpaSRS<C>
+where
+    C: CurveAffine + ExpSerde,
+{
+    /// G, the bases of the Pedersen commitments to the evaluations
+    pub bases: Vec<C>,
+    /// U, the base the inner products are committed over
+    pub u: C,
+}
+
+impl<C> IpaSRS<C>
+where
+    C: CurveAffine + ExpSerde,
+{
+    /// The number of variables of the largest polynomial the parameters commit to
+    pub fn num_vars(&self) -> usize {
+        self.bases.len().ilog2() as usize
+    }
+}
+
+impl<C> StructuredReferenceString for IpaSRS<C>
+where
+    C: CurveAffine + ExpSerde,
+{
+    type PKey = Self;
+    type VKey = Self;
+
+    /// NOTE: the verifier folds the bases, so it needs all of them.
+    fn into_keys(self) -> (Self::PKey, Self::VKey) {
+        (self.clone(), self)
+    }
+}
+
+pub(crate) fn ipa_setup<C>(num_vars: usize, mut rng: impl rand::RngCore) -> IpaSRS<C>
+where
+    C: CurveAffine + ExpSerde,
+{
+    let proj: Vec<C::Curve> = (0..(1 << num_vars) + 1)
+        .map(|_| C::Curve::random(&mut rng))
+        .collect();
+    let mut bases = vec![C::default(); proj.len()];
+    C::Curve::batch_normalize(&proj, &mut bases);
+    let u = bases.pop().unwrap();
+
+    IpaSRS { bases, u }
+}
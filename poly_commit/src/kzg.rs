@@ -4,5 +4,11 @@ pub use uni_kzg::*;
 mod bi_kzg;
 pub use bi_kzg::*;
 
+mod zeromorph;
+pub use zeromorph::*;
+
+mod ptau;
+pub use ptau::*;
+
 mod utils;
 pub(crate) use utils::*;
@@ -1,8 +1,7 @@
 use halo2curves::{
     ff::Field,
     group::{prime::PrimeCurveAffine, Curve, Group},
-    msm,
-    pairing::{MillerLoopResult, MultiMillerLoop},
+    pairing::MultiMillerLoop,
     CurveAffine,
 };
 use itertools::izip;
@@ -46,7 +45,7 @@ where
     };
 
     let tau_x_srs = CoefFormUniKZGSRS::<E> {
-        powers_of_tau: x_coeff_bases,
+        powers_of_tau: x_coeff_bases.into(),
         tau_g2: (E::G2Affine::generator() * tau_x).into(),
     };
 
@@ -62,7 +61,7 @@ where
     };
 
     let tau_y_srs = CoefFormUniKZGSRS::<E> {
-        powers_of_tau: y_coeff_bases,
+        powers_of_tau: y_coeff_bases.into(),
         tau_g2: (E::G2Affine::generator() * tau_y).into(),
     };
 
@@ -90,7 +89,7 @@ where
     let (div, eval) = univariate_degree_one_quotient(&gammas, beta);
 
     let mut y_open = E::G1::generator() * E::Fr::ZERO;
-    msm::multiexp_serial(&div, &srs.tau_y_srs.powers_of_tau[..div.len()], &mut y_open);
+    backend::msm_serial(&div, &srs.tau_y_srs.powers_of_tau[..div.len()], &mut y_open);
 
     (
         eval,
@@ -113,24 +112,42 @@ pub fn coeff_form_bi_kzg_verify<E: MultiMillerLoop>(
 where
     E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
     E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
+    E::Fr: arith::Field,
+{
+    let mut pairing_acc = PairingAccumulator::new();
+    coeff_form_bi_kzg_verify_deferred(&vk, comm, alpha, beta, eval, opening, &mut pairing_acc);
+    pairing_acc.check()
+}
+
+/// Add the pairing check of `coeff_form_bi_kzg_verify` to `pairing_acc` rather than running it
+#[inline(always)]
+pub fn coeff_form_bi_kzg_verify_deferred<E: MultiMillerLoop>(
+    vk: &BiKZGVerifierParam<E>,
+    comm: E::G1Affine,
+    alpha: E::Fr,
+    beta: E::Fr,
+    eval: E::Fr,
+    opening: BiKZGProof<E>,
+    pairing_acc: &mut PairingAccumulator<E>,
+) where
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
 {
     let g1_eval: E::G1Affine = (E::G1Affine::generator() * eval).into();
     let g2_alpha: E::G2 = E::G2Affine::generator() * alpha;
     let g2_beta: E::G2 = E::G2Affine::generator() * beta;
 
-    let gt_result = E::multi_miller_loop(&[
+    pairing_acc.add_check(&[
         (
-            &opening.quotient_x,
-            &(vk.tau_x_g2.to_curve() - g2_alpha).to_affine().into(),
+            opening.quotient_x,
+            (vk.tau_x_g2.to_curve() - g2_alpha).to_affine(),
         ),
         (
-            &opening.quotient_y,
-            &(vk.tau_y_g2.to_curve() - g2_beta).to_affine().into(),
+            opening.quotient_y,
+            (vk.tau_y_g2.to_curve() - g2_beta).to_affine(),
         ),
-        (&(g1_eval - comm).into(), &E::G2Affine::generator().into()),
+        ((g1_eval - comm).into(), E::G2Affine::generator()),
     ]);
-
-    gt_result.final_exponentiation().is_identity().into()
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
-use arith::ExtensionField;
+use arith::{ExtensionField, Pod};
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
-    StructuredReferenceString, Transcript,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
 };
 use halo2curves::{
     ff::PrimeField,
@@ -25,13 +25,16 @@ where
     G: FieldEngine<ChallengeField = E::Fr, SimdCircuitField = E::Fr>,
     E: Engine + MultiMillerLoop,
     E::Fr: ExtensionField + PrimeField,
-    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G1: Pod,
+    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + Pod,
     E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
 {
     const NAME: &'static str = "HyperBiKZGForExpander";
 
     const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::KZG;
 
+    const POWER_OF_TWO_WORLD: bool = true;
+
     type Commitment = BiKZGCommitment<E>;
     type Opening = HyperBiKZGOpening<E>;
     type Params = usize;
@@ -41,7 +44,8 @@ where
 
     fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
 
-    fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
+    fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params {
+        Self::check_world_size(world_size).unwrap_or_else(|e| panic!("{e}"));
         std::cmp::max(n_input_vars, Self::MINIMUM_SUPPORTED_NUM_VARS)
     }
 
@@ -65,7 +69,7 @@ where
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
         poly: &impl polynomials::MultilinearExtension<E::Fr>,
         _scratch_pad: &mut Self::ScratchPad,
-    ) -> Option<Self::Commitment> {
+    ) -> MPIResult<Option<Self::Commitment>> {
         // The minimum supported number of variables is 1.
         // If the polynomial has no variables, we lift it to a polynomial with 1 variable.
         if poly.num_vars() < Self::MINIMUM_SUPPORTED_NUM_VARS {
@@ -83,20 +87,20 @@ where
             coeff_form_uni_kzg_commit(&proving_key.tau_x_srs, poly.hypercube_basis_ref());
 
         if mpi_engine.is_single_process() {
-            return BiKZGCommitment(local_commitment).into();
+            return Ok(BiKZGCommitment(local_commitment).into());
         }
 
         let local_g1 = local_commitment.to_curve();
         let mut root_gathering_commits: Vec<E::G1> = vec![local_g1; mpi_engine.world_size()];
-        mpi_engine.gather_vec(&[local_g1], &mut root_gathering_commits);
+        mpi_engine.gather_vec(&[local_g1], &mut root_gathering_commits)?;
 
         if !mpi_engine.is_root() {
-            return None;
+            return Ok(None);
         }
 
         let final_commit = root_gathering_commits.iter().sum::<E::G1>().into();
 
-        BiKZGCommitment(final_commit).into()
+        Ok(BiKZGCommitment(final_commit).into())
     }
 
     fn open(
@@ -107,7 +111,7 @@ where
         x: &ExpanderSingleVarChallenge<G>,
         transcript: &mut impl Transcript,
         _scratch_pad: &Self::ScratchPad,
-    ) -> Option<Self::Opening> {
+    ) -> MPIResult<Option<Self::Opening>> {
         if poly.num_vars() < Self::MINIMUM_SUPPORTED_NUM_VARS {
             let (poly, x) = lift_poly_and_expander_challenge_to_n_vars(
                 poly,
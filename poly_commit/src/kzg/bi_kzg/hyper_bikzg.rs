@@ -3,8 +3,8 @@
 
 use std::{io::Cursor, iter};
 
-use arith::ExtensionField;
-use gkr_engine::{MPIEngine, Transcript};
+use arith::{ExtensionField, Pod};
+use gkr_engine::{MPIEngine, MPIResult, Transcript};
 use halo2curves::{
     ff::Field,
     group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding},
@@ -25,10 +25,11 @@ pub fn coeff_form_hyper_bikzg_open<E>(
     local_alphas: &[E::Fr],
     mpi_alphas: &[E::Fr],
     fs_transcript: &mut impl Transcript,
-) -> Option<HyperBiKZGOpening<E>>
+) -> MPIResult<Option<HyperBiKZGOpening<E>>>
 where
     E: MultiMillerLoop,
-    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G1: Pod,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde + Pod,
     E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
     E::Fr: ExtensionField,
 {
@@ -43,7 +44,7 @@ where
         );
 
         let hyper_bikzg_opening: HyperBiKZGOpening<E> = hyperkzg_opening.into();
-        return hyper_bikzg_opening.into();
+        return Ok(hyper_bikzg_opening.into());
     }
 
     //
@@ -74,8 +75,8 @@ where
     mpi_engine.gather_vec(
         &local_folded_x_oracle_commits,
         &mut root_gathering_folded_oracle_commits,
-    );
-    mpi_engine.gather_vec(&[local_final_eval_at_x], &mut final_evals_at_x);
+    )?;
+    mpi_engine.gather_vec(&[local_final_eval_at_x], &mut final_evals_at_x)?;
 
     //
     // Leader party collect oracle commitments, sum them up for folded oracles
@@ -135,7 +136,7 @@ where
         .for_each(|f| fs_transcript.append_u8_slice(f.to_bytes().as_ref()));
     }
 
-    transcript_root_broadcast(fs_transcript, mpi_engine);
+    transcript_root_broadcast(fs_transcript, mpi_engine)?;
 
     let beta_x = fs_transcript.generate_field_element::<E::Fr>();
     let beta_y = fs_transcript.generate_field_element::<E::Fr>();
@@ -172,7 +173,7 @@ where
         let mut gathering_buffer =
             vec![0u8; mpi_engine.world_size() * local_exported_folded_x_evals_bytes.len()];
 
-        mpi_engine.gather_vec(&local_exported_folded_x_evals_bytes, &mut gathering_buffer);
+        mpi_engine.gather_vec(&local_exported_folded_x_evals_bytes, &mut gathering_buffer)?;
 
         if mpi_engine.is_root() {
             izip!(
@@ -215,7 +216,7 @@ where
         root_folded_y_evals.append_to_transcript(fs_transcript);
     }
 
-    transcript_root_broadcast(fs_transcript, mpi_engine);
+    transcript_root_broadcast(fs_transcript, mpi_engine)?;
 
     let gamma = fs_transcript.generate_field_element::<E::Fr>();
 
@@ -247,7 +248,7 @@ where
             .serialize_into(&mut serialized_y_coeffs)
             .unwrap();
 
-        mpi_engine.root_broadcast_bytes(&mut serialized_y_coeffs);
+        mpi_engine.root_broadcast_bytes(&mut serialized_y_coeffs)?;
         leader_gamma_aggregated_y_coeffs = {
             let mut cursor = Cursor::new(serialized_y_coeffs);
             Vec::deserialize_from(&mut cursor).unwrap()
@@ -299,7 +300,7 @@ where
     mpi_engine.gather_vec(
         &[local_gamma_aggregated_x_quotient_commitment_g1],
         &mut root_gathering_gamma_aggregated_x_quotient_commitment_g1s,
-    );
+    )?;
 
     let mut gamma_aggregated_x_quotient_commitment: E::G1Affine = E::G1Affine::default();
 
@@ -313,7 +314,7 @@ where
         fs_transcript.append_u8_slice(gamma_aggregated_x_quotient_commitment.to_bytes().as_ref());
     }
 
-    transcript_root_broadcast(fs_transcript, mpi_engine);
+    transcript_root_broadcast(fs_transcript, mpi_engine)?;
 
     let delta_x = fs_transcript.generate_field_element::<E::Fr>();
 
@@ -330,7 +331,7 @@ where
     mpi_engine.gather_vec(
         &[local_degree2_eval_at_delta_x],
         &mut degree2_evals_at_delta_x,
-    );
+    )?;
 
     //
     // Leader does similar thing - quotient at beta_y, -beta_y, beta_y^2,
@@ -373,7 +374,7 @@ where
         fs_transcript.append_u8_slice(leader_quotient_y_commitment.to_bytes().as_ref());
     }
 
-    transcript_root_broadcast(fs_transcript, mpi_engine);
+    transcript_root_broadcast(fs_transcript, mpi_engine)?;
 
     let delta_y = fs_transcript.generate_field_element::<E::Fr>();
 
@@ -388,7 +389,7 @@ where
             .serialize_into(&mut serialized_y_quotient_coeffs)
             .unwrap();
 
-        mpi_engine.root_broadcast_bytes(&mut serialized_y_quotient_coeffs);
+        mpi_engine.root_broadcast_bytes(&mut serialized_y_quotient_coeffs)?;
         leader_quotient_y_coeffs = {
             let mut cursor = Cursor::new(serialized_y_quotient_coeffs);
             Vec::deserialize_from(&mut cursor).unwrap()
@@ -422,15 +423,15 @@ where
     let local_eval_open =
         coeff_form_uni_kzg_open_eval(&srs.tau_x_srs, &local_gamma_aggregated_x_coeffs, delta_x);
 
-    mpi_engine.gather_vec(&[local_eval_open], &mut gathered_eval_opens);
+    mpi_engine.gather_vec(&[local_eval_open], &mut gathered_eval_opens)?;
 
     if !mpi_engine.is_root() {
-        return None;
+        return Ok(None);
     }
 
     let (_, final_opening) = coeff_form_bi_kzg_open_leader(srs, &gathered_eval_opens, delta_y);
 
-    HyperBiKZGOpening {
+    Ok(HyperBiKZGOpening {
         folded_oracle_commitments,
         aggregated_evals: root_aggregated_x_evals,
         leader_evals: root_folded_y_evals.into(),
@@ -439,7 +440,7 @@ where
         quotient_delta_x_commitment: final_opening.quotient_x,
         quotient_delta_y_commitment: final_opening.quotient_y,
     }
-    .into()
+    .into())
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -452,6 +453,39 @@ pub fn coeff_form_hyper_bikzg_verify<E, T>(
     opening: &HyperBiKZGOpening<E>,
     fs_transcript: &mut T,
 ) -> bool
+where
+    E: MultiMillerLoop,
+    T: Transcript,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
+    E::Fr: ExtensionField,
+{
+    let mut pairing_acc = PairingAccumulator::new();
+    coeff_form_hyper_bikzg_verify_deferred(
+        vk,
+        local_alphas,
+        mpi_alphas,
+        eval,
+        commitment,
+        opening,
+        fs_transcript,
+        &mut pairing_acc,
+    ) && pairing_acc.check()
+}
+
+/// `coeff_form_hyper_bikzg_verify` adding the final pairing check to `pairing_acc` rather than
+/// running it. Returns false if the other checks of the opening fail.
+#[allow(clippy::too_many_arguments)]
+pub fn coeff_form_hyper_bikzg_verify_deferred<E, T>(
+    vk: &BiKZGVerifierParam<E>,
+    local_alphas: &[E::Fr],
+    mpi_alphas: &[E::Fr],
+    eval: E::Fr,
+    commitment: E::G1Affine,
+    opening: &HyperBiKZGOpening<E>,
+    fs_transcript: &mut T,
+    pairing_acc: &mut PairingAccumulator<E>,
+) -> bool
 where
     E: MultiMillerLoop,
     T: Transcript,
@@ -464,13 +498,14 @@ where
         let hyper_bikzg_opening = opening.clone();
         let hyper_kzg_opening: HyperUniKZGOpening<E> = hyper_bikzg_opening.into();
 
-        let what = coeff_form_uni_hyperkzg_verify(
+        let what = coeff_form_uni_hyperkzg_verify_deferred(
             &vk.into(),
             commitment,
             local_alphas,
             eval,
             &hyper_kzg_opening,
             fs_transcript,
+            pairing_acc,
         );
 
         return what;
@@ -639,12 +674,15 @@ where
         quotient_y: opening.quotient_delta_y_commitment,
     };
 
-    coeff_form_bi_kzg_verify(
-        vk.clone(),
+    coeff_form_bi_kzg_verify_deferred(
+        vk,
         com_r.to_affine(),
         delta_x,
         delta_y,
         degree_2_final_eval,
         final_opening,
-    )
+        pairing_acc,
+    );
+
+    true
 }
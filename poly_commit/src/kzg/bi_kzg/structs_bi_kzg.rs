@@ -29,7 +29,7 @@ where
 #[derivative(Default(bound = ""))]
 pub struct CoefFormBiKZGLocalSRS<E: Engine>
 where
-    E::G1Affine: ExpSerde,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
     E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
 {
     pub tau_x_srs: CoefFormUniKZGSRS<E>,
@@ -51,7 +51,7 @@ where
 
 impl<E: Engine> From<&CoefFormBiKZGLocalSRS<E>> for BiKZGVerifierParam<E>
 where
-    E::G1Affine: ExpSerde,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
     E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
 {
     fn from(srs: &CoefFormBiKZGLocalSRS<E>) -> Self {
@@ -0,0 +1,538 @@
+//! Import of the powers of tau of a ceremony, e.g., the perpetual powers of tau, from the
+//! `.ptau` files of snarkjs, as the SRS of the KZG commitments over BN254.
+//!
+//! A `.ptau` file is a list of sections, each of an id and a length. The ones read here are the
+//! header (1), tau^i over G1 (2) and over G2 (3), and the contributions (7), the points being
+//! written as their affine coordinates, little endian and in Montgomery form.
+//!
+//! The import is UNVERIFIED: the hashes of the contributions are read as the file records them,
+//! not recomputed from the accumulator, and the proofs of knowledge of the contributions are not
+//! checked, hence the names of `Ptau::read_unverified` and `Ptau::compare_unverified_hashes`. A
+//! file is only known to be the accumulator of the ceremony once `snarkjs powersoftau verify`
+//! accepts it.
+
+use std::io::{Read, Seek, SeekFrom};
+
+use halo2curves::{
+    bn256::{Bn256, Fq, Fq2, Fr, G1Affine, G2Affine},
+    ff::Field,
+    group::{prime::PrimeCurveAffine, Group},
+    CurveAffine,
+};
+use rayon::prelude::*;
+use thiserror::Error;
+
+use crate::{backend, powers_series, CoefFormBiKZGLocalSRS, CoefFormUniKZGSRS, PairingAccumulator};
+
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+
+const SECTION_HEADER: u32 = 1;
+const SECTION_TAU_G1: u32 = 2;
+const SECTION_TAU_G2: u32 = 3;
+const SECTION_CONTRIBUTIONS: u32 = 7;
+
+const FQ_BYTES: usize = 32;
+const G1_BYTES: usize = 2 * FQ_BYTES;
+const G2_BYTES: usize = 4 * FQ_BYTES;
+
+/// The length of the blake2b state of snarkjs, hashing the contribution up to its public key
+const PARTIAL_HASH_BYTES: usize = 216;
+
+#[derive(Debug, Error)]
+pub enum PtauError {
+    #[error("ptau io error")]
+    IOError(#[from] std::io::Error),
+
+    #[error("malformed ptau file: {0}")]
+    FormatError(&'static str),
+
+    #[error("the ptau file is not over BN254")]
+    UnsupportedCurve,
+
+    #[error("the ptau file has {available} powers of tau, {required} are required")]
+    NotEnoughPowers { available: usize, required: usize },
+
+    #[error("a point of the ptau file is not in the prime order subgroup")]
+    InvalidPoint,
+
+    #[error("the ptau file has no contribution")]
+    NoContribution,
+
+    #[error("the recorded hash of the contribution {index} is not the expected one")]
+    ContributionHashMismatch { index: usize },
+
+    #[error("the powers of tau are not consistent")]
+    InconsistentPowers,
+
+    #[error("no univariate ceremony provides the bivariate SRS of {parties} parties")]
+    DistributedSRS { parties: usize },
+}
+
+pub type PtauResult<T> = std::result::Result<T, PtauError>;
+
+/// A contribution to the ceremony, as recorded in the `.ptau` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PtauContribution {
+    /// The name the contributor gave, if any
+    pub name: Option<String>,
+    /// Whether the contribution is a random beacon rather than a contributor's
+    pub is_beacon: bool,
+    /// tau over G1 after the contribution
+    pub tau_g1: G1Affine,
+    /// tau over G2 after the contribution
+    pub tau_g2: G2Affine,
+    /// The hash of the accumulator after the contribution, the one snarkjs prints and the
+    /// attestations of the ceremony publish, as recorded by the file
+    pub next_challenge: [u8; 64],
+}
+
+/// The powers of tau read from a `.ptau` file, not verified to be the ones of the ceremony, see
+/// the module documentation.
+#[derive(Clone, Debug)]
+pub struct Ptau {
+    /// The file has 2^power powers of tau over G2, and twice as many over G1
+    pub power: usize,
+    /// The first powers of tau over G1, tau^0 being the generator
+    pub tau_g1: Vec<G1Affine>,
+    /// The generator and tau over G2
+    pub tau_g2: [G2Affine; 2],
+    /// The contributions to the ceremony, in order
+    pub contributions: Vec<PtauContribution>,
+}
+
+impl Ptau {
+    /// Read the first `num_powers` powers of tau over G1 from a `.ptau` file, the points being
+    /// checked to be on the curve, see `check_consistency` for the checks of the points. The file
+    /// is not verified to be the accumulator of the ceremony.
+    pub fn read_unverified(mut reader: impl Read + Seek, num_powers: usize) -> PtauResult<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != PTAU_MAGIC {
+            return Err(PtauError::FormatError("not a ptau file"));
+        }
+        let _version = read_u32(&mut reader)?;
+        let num_sections = read_u32(&mut reader)?;
+
+        // the sections are indexed by their ids, and read in the order required below
+        let mut sections = vec![];
+        for _ in 0..num_sections {
+            let id = read_u32(&mut reader)?;
+            let len = read_u64(&mut reader)?;
+            let offset = reader.stream_position()?;
+            sections.push((id, offset, len));
+            reader.seek(SeekFrom::Current(len as i64))?;
+        }
+
+        seek_section(&mut reader, &sections, SECTION_HEADER)?;
+        let n8 = read_u32(&mut reader)? as usize;
+        let mut modulus = vec![0u8; n8];
+        reader.read_exact(&mut modulus)?;
+        if n8 != FQ_BYTES || !is_fq_modulus(&modulus) {
+            return Err(PtauError::UnsupportedCurve);
+        }
+        let power = read_u32(&mut reader)? as usize;
+        let _ceremony_power = read_u32(&mut reader)?;
+
+        let available = (1 << (power + 1)) - 1;
+        if num_powers > available {
+            return Err(PtauError::NotEnoughPowers {
+                available,
+                required: num_powers,
+            });
+        }
+        let num_powers = num_powers.max(2);
+
+        let len = seek_section(&mut reader, &sections, SECTION_TAU_G1)?;
+        if len != (available * G1_BYTES) as u64 {
+            return Err(PtauError::FormatError("tau over G1 of the wrong length"));
+        }
+        let r_inv = montgomery_r_inv();
+        let mut buf = vec![0u8; num_powers * G1_BYTES];
+        reader.read_exact(&mut buf)?;
+        let tau_g1 = buf
+            .par_chunks(G1_BYTES)
+            .map(|bytes| decode_g1(bytes, &r_inv))
+            .collect::<PtauResult<Vec<_>>>()?;
+
+        let len = seek_section(&mut reader, &sections, SECTION_TAU_G2)?;
+        if len != ((1 << power) * G2_BYTES) as u64 {
+            return Err(PtauError::FormatError("tau over G2 of the wrong length"));
+        }
+        let mut buf = [0u8; G2_BYTES];
+        reader.read_exact(&mut buf)?;
+        let g2 = decode_g2(&buf, &r_inv)?;
+        reader.read_exact(&mut buf)?;
+        let tau_g2 = [g2, decode_g2(&buf, &r_inv)?];
+
+        seek_section(&mut reader, &sections, SECTION_CONTRIBUTIONS)?;
+        let num_contributions = read_u32(&mut reader)?;
+        let contributions = (0..num_contributions)
+            .map(|_| read_contribution(&mut reader, &r_inv))
+            .collect::<PtauResult<Vec<_>>>()?;
+
+        Ok(Self {
+            power,
+            tau_g1,
+            tau_g2,
+            contributions,
+        })
+    }
+
+    /// Compare the hashes the file records for the contributions with the ones the attestations
+    /// of the ceremony publish, `expected_hashes[i]` being the hash of the accumulator after the
+    /// contribution i, and check that the last contribution is the one the powers of the file
+    /// end at.
+    ///
+    /// This does NOT verify the file: the recorded hashes are not recomputed from the
+    /// accumulator, so a match only tells the file claims to be the one of the ceremony.
+    pub fn compare_unverified_hashes(&self, expected_hashes: &[[u8; 64]]) -> PtauResult<()> {
+        let last = self.contributions.last().ok_or(PtauError::NoContribution)?;
+        if self.contributions.len() != expected_hashes.len() {
+            return Err(PtauError::ContributionHashMismatch {
+                index: self.contributions.len().min(expected_hashes.len()),
+            });
+        }
+        if let Some(index) = self
+            .contributions
+            .iter()
+            .zip(expected_hashes)
+            .position(|(contribution, hash)| contribution.next_challenge != *hash)
+        {
+            return Err(PtauError::ContributionHashMismatch { index });
+        }
+        if last.tau_g1 != self.tau_g1[1] || last.tau_g2 != self.tau_g2[1] {
+            return Err(PtauError::InconsistentPowers);
+        }
+        Ok(())
+    }
+
+    /// Check that the points over G2 are in the prime order subgroup, the one of G1 being the
+    /// whole curve, and that the points over G1 are the powers of the same tau as over G2, by a
+    /// random linear combination of the powers checked with the pairing.
+    pub fn check_consistency(&self, mut rng: impl rand::RngCore) -> PtauResult<()> {
+        if self.tau_g1[0] != G1Affine::generator() || self.tau_g2[0] != G2Affine::generator() {
+            return Err(PtauError::InconsistentPowers);
+        }
+        // [r]P = [r - 1]P + P
+        if self
+            .tau_g2
+            .iter()
+            .any(|p| !bool::from((*p * -Fr::ONE + p.to_curve()).is_identity()))
+        {
+            return Err(PtauError::InvalidPoint);
+        }
+
+        // sum_i rho^i tau^(i + 1) G1 = tau * sum_i rho^i tau^i G1
+        let n = self.tau_g1.len() - 1;
+        let rho = Fr::random(&mut rng);
+        let rho_powers = powers_series(&rho, n);
+        let lower: G1Affine = backend::msm(&rho_powers, &self.tau_g1[..n]).into();
+        let upper: G1Affine = backend::msm(&rho_powers, &self.tau_g1[1..]).into();
+
+        let mut pairing_acc = PairingAccumulator::<Bn256>::new();
+        pairing_acc.add_equality(
+            self.tau_g1[1],
+            self.tau_g2[0],
+            self.tau_g1[0],
+            self.tau_g2[1],
+        );
+        pairing_acc.add_equality(upper, self.tau_g2[0], lower, self.tau_g2[1]);
+        match pairing_acc.check() {
+            true => Ok(()),
+            false => Err(PtauError::InconsistentPowers),
+        }
+    }
+
+    /// The SRS of the univariate KZG over the first `length` powers of tau.
+    pub fn uni_kzg_srs(&self, length: usize) -> PtauResult<CoefFormUniKZGSRS<Bn256>> {
+        if length > self.tau_g1.len() {
+            return Err(PtauError::NotEnoughPowers {
+                available: self.tau_g1.len(),
+                required: length,
+            });
+        }
+        Ok(CoefFormUniKZGSRS {
+            powers_of_tau: self.tau_g1[..length].to_vec().into(),
+            tau_g2: self.tau_g2[1],
+        })
+    }
+
+    /// The SRS of the HyperBiKZG of a single party, over the first `local_length` powers of tau.
+    ///
+    /// The SRS of several parties has the powers tau_x^i tau_y^j over G1 for two independent
+    /// taus, which a univariate ceremony does not provide: tau_y being a power of tau, say
+    /// tau_x^`local_length`, the openings of the bivariate KZG could be forged.
+    pub fn bi_kzg_srs(
+        &self,
+        local_length: usize,
+        distributed_parties: usize,
+    ) -> PtauResult<CoefFormBiKZGLocalSRS<Bn256>> {
+        if distributed_parties != 1 {
+            return Err(PtauError::DistributedSRS {
+                parties: distributed_parties,
+            });
+        }
+        Ok(CoefFormBiKZGLocalSRS {
+            tau_x_srs: self.uni_kzg_srs(local_length)?,
+            // NOTE: a single party opens with the univariate HyperKZG, tau_y is never used
+            tau_y_srs: CoefFormUniKZGSRS {
+                powers_of_tau: vec![G1Affine::generator()].into(),
+                tau_g2: G2Affine::default(),
+            },
+        })
+    }
+}
+
+#[inline(always)]
+fn read_u32(reader: &mut impl Read) -> PtauResult<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+#[inline(always)]
+fn read_u64(reader: &mut impl Read) -> PtauResult<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+#[inline(always)]
+fn read_bytes(reader: &mut impl Read, len: usize) -> PtauResult<Vec<u8>> {
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Seek the section `id`, returning its length
+fn seek_section(reader: &mut impl Seek, sections: &[(u32, u64, u64)], id: u32) -> PtauResult<u64> {
+    let &(_, offset, len) = sections
+        .iter()
+        .find(|(section_id, _, _)| *section_id == id)
+        .ok_or(PtauError::FormatError("missing section"))?;
+    reader.seek(SeekFrom::Start(offset))?;
+    Ok(len)
+}
+
+/// Whether the little endian `modulus` is the one of Fq, i.e., (q - 1) + 1 with q - 1 even
+fn is_fq_modulus(modulus: &[u8]) -> bool {
+    let q_minus_one = (-Fq::ONE).to_bytes();
+    modulus[0] == q_minus_one[0] + 1 && modulus[1..] == q_minus_one[1..]
+}
+
+/// R^-1, the Montgomery form of x being x * R with R = 2^256
+fn montgomery_r_inv() -> Fq {
+    Fq::from(2u64).pow_vartime([256]).invert().unwrap()
+}
+
+/// The element of Fq of the little endian Montgomery form `bytes`
+fn decode_fq(bytes: &[u8], r_inv: &Fq) -> PtauResult<Fq> {
+    let mut repr = [0u8; FQ_BYTES];
+    repr.copy_from_slice(bytes);
+    let montgomery = Option::<Fq>::from(Fq::from_bytes(&repr))
+        .ok_or(PtauError::FormatError("coordinate out of range"))?;
+    Ok(montgomery * r_inv)
+}
+
+fn decode_g1(bytes: &[u8], r_inv: &Fq) -> PtauResult<G1Affine> {
+    let x = decode_fq(&bytes[..FQ_BYTES], r_inv)?;
+    let y = decode_fq(&bytes[FQ_BYTES..], r_inv)?;
+    Option::from(G1Affine::from_xy(x, y)).ok_or(PtauError::InvalidPoint)
+}
+
+/// The coordinates over Fq2 being written c0 first
+fn decode_g2(bytes: &[u8], r_inv: &Fq) -> PtauResult<G2Affine> {
+    let fq2 = |i: usize| -> PtauResult<Fq2> {
+        let coord = |j: usize| decode_fq(&bytes[j * FQ_BYTES..(j + 1) * FQ_BYTES], r_inv);
+        Ok(Fq2 {
+            c0: coord(2 * i)?,
+            c1: coord(2 * i + 1)?,
+        })
+    };
+    Option::from(G2Affine::from_xy(fq2(0)?, fq2(1)?)).ok_or(PtauError::InvalidPoint)
+}
+
+/// The next `len` bytes of `params` from `pos`
+fn take_bytes<'a>(params: &'a [u8], pos: &mut usize, len: usize) -> PtauResult<&'a [u8]> {
+    let bytes = params
+        .get(*pos..*pos + len)
+        .ok_or(PtauError::FormatError("truncated contribution parameter"))?;
+    *pos += len;
+    Ok(bytes)
+}
+
+fn read_contribution(reader: &mut impl Read, r_inv: &Fq) -> PtauResult<PtauContribution> {
+    let tau_g1 = decode_g1(&read_bytes(reader, G1_BYTES)?, r_inv)?;
+    let tau_g2 = decode_g2(&read_bytes(reader, G2_BYTES)?, r_inv)?;
+    // alpha and beta over G1, beta over G2, the public key of six points over G1 and three over
+    // G2, and the partial hash
+    read_bytes(reader, 3 * G1_BYTES + G2_BYTES)?;
+    read_bytes(reader, 6 * G1_BYTES + 3 * G2_BYTES)?;
+    read_bytes(reader, PARTIAL_HASH_BYTES)?;
+
+    let mut next_challenge = [0u8; 64];
+    reader.read_exact(&mut next_challenge)?;
+    let is_beacon = read_u32(reader)? == 1;
+
+    // the parameters, each of a type byte: the name (1), the number of iterations of the
+    // beacon (2) and the beacon hash (3)
+    let params = read_bytes(reader, read_u32(reader)? as usize)?;
+    let mut name = None;
+    let mut pos = 0;
+    while pos < params.len() {
+        let param_type = take_bytes(&params, &mut pos, 1)?[0];
+        match param_type {
+            1 | 3 => {
+                let len = take_bytes(&params, &mut pos, 1)?[0] as usize;
+                let bytes = take_bytes(&params, &mut pos, len)?;
+                if param_type == 1 {
+                    name = Some(String::from_utf8_lossy(bytes).into_owned());
+                }
+            }
+            2 => {
+                take_bytes(&params, &mut pos, 1)?;
+            }
+            _ => return Err(PtauError::FormatError("unknown contribution parameter")),
+        }
+    }
+
+    Ok(PtauContribution {
+        name,
+        is_beacon,
+        tau_g1,
+        tau_g2,
+        next_challenge,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use ark_std::test_rng;
+    use halo2curves::group::Curve;
+
+    use super::*;
+
+    fn encode_fq(x: &Fq, buf: &mut Vec<u8>) {
+        let r = Fq::from(2u64).pow_vartime([256]);
+        buf.extend_from_slice(&(*x * r).to_bytes());
+    }
+
+    fn encode_g1(p: &G1Affine, buf: &mut Vec<u8>) {
+        let coords = p.coordinates().unwrap();
+        encode_fq(coords.x(), buf);
+        encode_fq(coords.y(), buf);
+    }
+
+    fn encode_g2(p: &G2Affine, buf: &mut Vec<u8>) {
+        let coords = p.coordinates().unwrap();
+        for fq2 in [coords.x(), coords.y()] {
+            encode_fq(&fq2.c0, buf);
+            encode_fq(&fq2.c1, buf);
+        }
+    }
+
+    fn section(id: u32, data: Vec<u8>, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&id.to_le_bytes());
+        buf.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        buf.extend_from_slice(&data);
+    }
+
+    /// A ptau file of the powers of `tau`, with a single named contribution
+    fn ptau_file(tau: Fr, power: usize, next_challenge: [u8; 64]) -> Vec<u8> {
+        let tau_g1: Vec<G1Affine> = powers_series(&tau, (1 << (power + 1)) - 1)
+            .iter()
+            .map(|tau_i| (G1Affine::generator() * *tau_i).to_affine())
+            .collect();
+        let tau_g2: Vec<G2Affine> = powers_series(&tau, 1 << power)
+            .iter()
+            .map(|tau_i| (G2Affine::generator() * *tau_i).to_affine())
+            .collect();
+
+        let mut buf = PTAU_MAGIC.to_vec();
+        buf.extend_from_slice(&1u32.to_le_bytes());
+        buf.extend_from_slice(&4u32.to_le_bytes());
+
+        let mut header = (FQ_BYTES as u32).to_le_bytes().to_vec();
+        let mut modulus = (-Fq::ONE).to_bytes();
+        modulus[0] += 1;
+        header.extend_from_slice(&modulus);
+        header.extend_from_slice(&(power as u32).to_le_bytes());
+        header.extend_from_slice(&(power as u32).to_le_bytes());
+        section(SECTION_HEADER, header, &mut buf);
+
+        let mut data = vec![];
+        tau_g1.iter().for_each(|p| encode_g1(p, &mut data));
+        section(SECTION_TAU_G1, data, &mut buf);
+
+        let mut data = vec![];
+        tau_g2.iter().for_each(|p| encode_g2(p, &mut data));
+        section(SECTION_TAU_G2, data, &mut buf);
+
+        let mut data = 1u32.to_le_bytes().to_vec();
+        encode_g1(&tau_g1[1], &mut data);
+        encode_g2(&tau_g2[1], &mut data);
+        (0..3).for_each(|_| encode_g1(&tau_g1[0], &mut data));
+        encode_g2(&tau_g2[0], &mut data);
+        (0..6).for_each(|_| encode_g1(&tau_g1[0], &mut data));
+        (0..3).for_each(|_| encode_g2(&tau_g2[0], &mut data));
+        data.extend_from_slice(&[0u8; PARTIAL_HASH_BYTES]);
+        data.extend_from_slice(&next_challenge);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        let params = [&[1u8, 5][..], b"alice", &[2u8, 10]].concat();
+        data.extend_from_slice(&(params.len() as u32).to_le_bytes());
+        data.extend_from_slice(&params);
+        section(SECTION_CONTRIBUTIONS, data, &mut buf);
+
+        buf
+    }
+
+    #[test]
+    fn test_ptau_import() {
+        let mut rng = test_rng();
+        let tau = Fr::random(&mut rng);
+        let hash = [7u8; 64];
+        let file = ptau_file(tau, 3, hash);
+
+        let ptau = Ptau::read_unverified(Cursor::new(&file), 8).unwrap();
+        assert_eq!(ptau.power, 3);
+        assert_eq!(ptau.contributions.len(), 1);
+        assert_eq!(ptau.contributions[0].name.as_deref(), Some("alice"));
+        assert!(!ptau.contributions[0].is_beacon);
+
+        ptau.compare_unverified_hashes(&[hash]).unwrap();
+        assert!(matches!(
+            ptau.compare_unverified_hashes(&[[0u8; 64]]),
+            Err(PtauError::ContributionHashMismatch { index: 0 })
+        ));
+        ptau.check_consistency(&mut rng).unwrap();
+
+        let srs = ptau.bi_kzg_srs(8, 1).unwrap();
+        let expected: Vec<G1Affine> = powers_series(&tau, 8)
+            .iter()
+            .map(|tau_i| (G1Affine::generator() * *tau_i).to_affine())
+            .collect();
+        assert_eq!(*srs.tau_x_srs.powers_of_tau, expected[..]);
+        assert_eq!(
+            srs.tau_x_srs.tau_g2,
+            (G2Affine::generator() * tau).to_affine()
+        );
+        assert!(matches!(
+            ptau.bi_kzg_srs(8, 2),
+            Err(PtauError::DistributedSRS { parties: 2 })
+        ));
+        assert!(matches!(
+            Ptau::read_unverified(Cursor::new(&file), 16),
+            Err(PtauError::NotEnoughPowers { .. })
+        ));
+
+        // a power of another tau
+        let mut tampered = ptau.clone();
+        tampered.tau_g1[5] = (G1Affine::generator() * Fr::random(&mut rng)).to_affine();
+        assert!(matches!(
+            tampered.check_consistency(&mut rng),
+            Err(PtauError::InconsistentPowers)
+        ));
+    }
+}
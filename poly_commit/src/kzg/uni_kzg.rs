@@ -10,6 +10,9 @@ pub use hyper_kzg::*;
 mod univariate;
 pub use univariate::*;
 
+mod shplonk;
+pub use shplonk::*;
+
 mod pcs_trait_impl;
 pub use pcs_trait_impl::*;
 
@@ -1,13 +1,13 @@
 use arith::{ExtensionField, Field};
 use gkr_engine::Transcript;
 use halo2curves::group::Group;
-use halo2curves::{group::Curve, msm::multiexp_serial, pairing::MultiMillerLoop, CurveAffine};
+use halo2curves::{group::Curve, pairing::MultiMillerLoop, CurveAffine};
 use polynomials::MultiLinearPoly;
 use serdes::ExpSerde;
 
 use crate::{
-    coeff_form_uni_hyperkzg_open, coeff_form_uni_hyperkzg_verify, powers_series, CoefFormUniKZGSRS,
-    HyperUniKZGOpening, UniKZGVerifierParams,
+    backend, coeff_form_uni_hyperkzg_open, coeff_form_uni_hyperkzg_verify, powers_series,
+    CoefFormUniKZGSRS, HyperUniKZGOpening, UniKZGVerifierParams,
 };
 
 pub(crate) fn kzg_single_point_batch_open<E>(
@@ -74,7 +74,7 @@ where
 
     // stay with single thread as the num_poly is usually small
     let mut merged_commitment = E::G1::identity();
-    multiexp_serial(&rlcs, commitments, &mut merged_commitment);
+    backend::msm_serial(&rlcs, commitments, &mut merged_commitment);
 
     let merged_eval = evals
         .iter()
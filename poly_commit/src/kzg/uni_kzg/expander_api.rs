@@ -1,7 +1,7 @@
 use arith::ExtensionField;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
-    StructuredReferenceString, Transcript,
+    BatchOpenError, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
 };
 use halo2curves::{
     ff::PrimeField,
@@ -69,7 +69,7 @@ where
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
         poly: &impl polynomials::MultilinearExtension<E::Fr>,
         scratch_pad: &mut Self::ScratchPad,
-    ) -> Option<Self::Commitment> {
+    ) -> MPIResult<Option<Self::Commitment>> {
         if poly.num_vars() < Self::MINIMUM_SUPPORTED_NUM_VARS {
             assert_eq!(*params, Self::MINIMUM_SUPPORTED_NUM_VARS);
             let poly = lift_poly_to_n_vars(poly, *params);
@@ -83,7 +83,7 @@ where
         }
 
         let commitment = coeff_form_uni_kzg_commit(proving_key, poly.hypercube_basis_ref());
-        Some(UniKZGCommitment(commitment))
+        Ok(Some(UniKZGCommitment(commitment)))
     }
 
     fn open(
@@ -94,7 +94,7 @@ where
         x: &ExpanderSingleVarChallenge<G>,
         transcript: &mut impl Transcript,
         scratch_pad: &Self::ScratchPad,
-    ) -> Option<Self::Opening> {
+    ) -> MPIResult<Option<Self::Opening>> {
         if poly.num_vars() < Self::MINIMUM_SUPPORTED_NUM_VARS {
             assert_eq!(*params, Self::MINIMUM_SUPPORTED_NUM_VARS);
             let (poly, x) = lift_poly_and_expander_challenge_to_n_vars(poly, x, *params);
@@ -116,7 +116,7 @@ where
             transcript,
         );
 
-        Some(open)
+        Ok(Some(open))
     }
 
     fn verify(
@@ -154,16 +154,24 @@ where
     /// Open a set of polynomials at a point.
     fn multi_points_batch_open(
         _params: &Self::Params,
-        _mpi_engine: &impl MPIEngine,
+        mpi_engine: &impl MPIEngine,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
         polys: &[impl MultilinearExtension<E::Fr>],
         x: &[ExpanderSingleVarChallenge<G>],
         _scratch_pad: &Self::ScratchPad,
         transcript: &mut impl Transcript,
-    ) -> (Vec<E::Fr>, Self::BatchOpening) {
+    ) -> Result<(Vec<E::Fr>, Self::BatchOpening), BatchOpenError> {
+        for p in x {
+            p.check_shape(mpi_engine.world_size())?;
+        }
         let points: Vec<Vec<E::Fr>> = x.iter().map(|p| p.local_xs()).collect();
 
-        multiple_points_batch_open_impl(proving_key, polys, points.as_ref(), transcript)
+        Ok(multiple_points_batch_open_impl(
+            proving_key,
+            polys,
+            points.as_ref(),
+            transcript,
+        ))
     }
 
     fn multi_points_batch_verify(
@@ -175,6 +183,9 @@ where
         batch_opening: &Self::BatchOpening,
         transcript: &mut impl Transcript,
     ) -> bool {
+        if x.iter().any(|p| p.check_shape(1).is_err()) {
+            return false;
+        }
         let points: Vec<Vec<E::Fr>> = x.iter().map(|p| p.local_xs()).collect();
 
         multiple_points_batch_verify_impl(
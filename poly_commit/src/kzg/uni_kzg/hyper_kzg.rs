@@ -187,6 +187,37 @@ pub fn coeff_form_uni_hyperkzg_verify<E, T>(
     opening: &HyperUniKZGOpening<E>,
     fs_transcript: &mut T,
 ) -> bool
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Fr: ExtensionField + ExpSerde,
+    T: Transcript,
+{
+    let mut pairing_acc = PairingAccumulator::new();
+    coeff_form_uni_hyperkzg_verify_deferred(
+        vk,
+        comm,
+        alphas,
+        eval,
+        opening,
+        fs_transcript,
+        &mut pairing_acc,
+    ) && pairing_acc.check()
+}
+
+/// `coeff_form_uni_hyperkzg_verify` adding the final pairing check to `pairing_acc` rather than
+/// running it. Returns false if the other checks of the opening fail.
+#[inline(always)]
+pub fn coeff_form_uni_hyperkzg_verify_deferred<E, T>(
+    vk: &UniKZGVerifierParams<E>,
+    comm: E::G1Affine,
+    alphas: &[E::Fr],
+    eval: E::Fr,
+    opening: &HyperUniKZGOpening<E>,
+    fs_transcript: &mut T,
+    pairing_acc: &mut PairingAccumulator<E>,
+) -> bool
 where
     E: MultiMillerLoop,
     E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
@@ -234,12 +265,13 @@ where
     let lagrange_eval =
         lagrange_degree2[0] + lagrange_degree2[1] * tau + lagrange_degree2[2] * tau * tau;
 
-    coeff_form_uni_kzg_verify(
+    coeff_form_uni_kzg_verify_deferred(
         vk,
         (commitment_agg_g1 - opening.beta_x_commitment.to_curve() * q_weight).into(),
         tau,
         lagrange_eval,
         opening.quotient_delta_x_commitment,
+        pairing_acc,
     );
 
     true
@@ -11,7 +11,7 @@ use polynomials::MultiLinearPoly;
 use serdes::ExpSerde;
 
 use crate::{
-    traits::{BatchOpening, BatchOpeningPCS},
+    traits::{BatchOpening, BatchOpeningPCS, UnivariateBatchOpeningPCS},
     *,
 };
 
@@ -33,6 +33,31 @@ where
     pub const MINIMUM_SUPPORTED_NUM_VARS: usize = 1;
 }
 
+impl<E> HyperUniKZGPCS<E>
+where
+    E: Engine + MultiMillerLoop,
+    E::Fr: ExtensionField + PrimeField,
+    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+{
+    /// The tables of the powers of tau of `proving_key` for `commit_with_tables`, built once for
+    /// all the polynomials to commit to, see `FixedBaseTables` for the choice of `window_bits`.
+    pub fn precompute_tables(
+        proving_key: &CoefFormUniKZGSRS<E>,
+        window_bits: usize,
+    ) -> FixedBaseTables<E::G1Affine> {
+        FixedBaseTables::new(&proving_key.powers_of_tau, window_bits)
+    }
+
+    /// The commitment `commit` computes, from the tables `precompute_tables` built.
+    pub fn commit_with_tables(
+        tables: &FixedBaseTables<E::G1Affine>,
+        poly: &MultiLinearPoly<E::Fr>,
+    ) -> UniKZGCommitment<E> {
+        UniKZGCommitment(coeff_form_uni_kzg_commit_with_tables(tables, &poly.coeffs))
+    }
+}
+
 impl<E> PolynomialCommitmentScheme<E::Fr> for HyperUniKZGPCS<E>
 where
     E: Engine + MultiMillerLoop,
@@ -185,3 +210,46 @@ where
         )
     }
 }
+
+/// The polynomials are the coefficients committed to, i.e., the evaluations over the hypercube of
+/// the multilinear polynomials, opened as univariate ones by Shplonk.
+impl<E> UnivariateBatchOpeningPCS<E::Fr> for HyperUniKZGPCS<E>
+where
+    E: Engine + MultiMillerLoop,
+    E::Fr: ExtensionField + PrimeField,
+    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+{
+    type UnivariateBatchOpening = ShplonkOpening<E>;
+
+    fn batch_open(
+        _params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        polys: &[Vec<E::Fr>],
+        point_sets: &[Vec<E::Fr>],
+        transcript: &mut impl Transcript,
+    ) -> (Vec<Vec<E::Fr>>, Self::UnivariateBatchOpening) {
+        coeff_form_uni_kzg_shplonk_open(proving_key, polys, point_sets, transcript)
+    }
+
+    fn batch_verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitments: &[Self::Commitment],
+        point_sets: &[Vec<E::Fr>],
+        evals: &[Vec<E::Fr>],
+        opening: &Self::UnivariateBatchOpening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        let commitment_unwrapped = commitments.iter().map(|c| c.0).collect::<Vec<_>>();
+
+        coeff_form_uni_kzg_shplonk_verify(
+            verifying_key,
+            &commitment_unwrapped,
+            point_sets,
+            evals,
+            opening,
+            transcript,
+        )
+    }
+}
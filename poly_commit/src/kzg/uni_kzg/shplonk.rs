@@ -0,0 +1,336 @@
+use arith::ExtensionField;
+use gkr_engine::Transcript;
+use halo2curves::{
+    ff::Field,
+    group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding},
+    pairing::MultiMillerLoop,
+    CurveAffine,
+};
+use itertools::izip;
+use rayon::prelude::*;
+use serdes::ExpSerde;
+
+use crate::*;
+
+/// Horner's evaluation of the univariate polynomial of coefficients `coeffs` at `x`
+#[inline(always)]
+fn univariate_eval<F: Field>(coeffs: &[F], x: F) -> F {
+    coeffs.iter().rev().fold(F::ZERO, |acc, c| acc * x + c)
+}
+
+/// The vanishing polynomial of `points` at `x`
+#[inline(always)]
+fn vanishing_eval<F: Field>(points: &[F], x: F) -> F {
+    points.iter().map(|p| x - p).product()
+}
+
+/// The polynomial of degree less than |`points`| interpolating `evals` over `points`, at `x`,
+/// or None if the points are not distinct
+fn lagrange_eval<F: Field>(points: &[F], evals: &[F], x: F) -> Option<F> {
+    let mut acc = F::ZERO;
+    for (j, (p_j, e_j)) in izip!(points, evals).enumerate() {
+        let (num, denom) = points
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| *k != j)
+            .fold((F::ONE, F::ONE), |(num, denom), (_, p_k)| {
+                (num * (x - p_k), denom * (*p_j - p_k))
+            });
+        acc += *e_j * num * Option::<F>::from(denom.invert())?;
+    }
+    Some(acc)
+}
+
+/// The union T of the point sets, each point once
+fn union_of_points<F: Field>(point_sets: &[impl AsRef<[F]>]) -> Vec<F> {
+    let mut union: Vec<F> = vec![];
+    point_sets
+        .iter()
+        .flat_map(|points| points.as_ref().iter())
+        .for_each(|p| {
+            if !union.contains(p) {
+                union.push(*p)
+            }
+        });
+    union
+}
+
+/// The weights gamma^i Z_{T \ S_i}(z) of the polynomials in the linearization, as
+/// gamma^i Z_T(z) / Z_{S_i}(z), and Z_T(z). None if z is one of the points.
+fn linearization_weights<F: Field>(
+    point_sets: &[impl AsRef<[F]>],
+    gamma: F,
+    z: F,
+) -> Option<(Vec<F>, F)> {
+    let z_t = vanishing_eval(&union_of_points(point_sets), z);
+    let z_t_inv = Option::<F>::from(z_t.invert())?;
+
+    let weights = izip!(point_sets, powers_series(&gamma, point_sets.len()))
+        .map(|(points, gamma_i)| {
+            let z_s_over_z_t = vanishing_eval(points.as_ref(), z) * z_t_inv;
+            Option::<F>::from(z_s_over_z_t.invert()).map(|z_t_over_z_s| gamma_i * z_t_over_z_s)
+        })
+        .collect::<Option<Vec<F>>>()?;
+    Some((weights, z_t))
+}
+
+#[inline(always)]
+fn append_evals<F: ExtensionField>(evals: &[Vec<F>], fs_transcript: &mut impl Transcript) {
+    evals
+        .iter()
+        .flatten()
+        .for_each(|e| fs_transcript.append_field_element(e));
+}
+
+/// Open the univariate polynomials of coefficients `polys`, each at all the points of its set in
+/// `point_sets`, with two commitments in all (BDFG20, section 4):
+/// - for gamma drawn from the transcript, W commits to h = sum_i gamma^i (f_i - r_i) / Z_{S_i},
+///   with r_i interpolating the evaluations of f_i over S_i, and Z_S vanishing over S;
+/// - for z drawn after W, W' commits to L / (X - z), with the linearization of h at z L = sum_i
+///   gamma^i Z_{T \ S_i}(z) (f_i - r_i(z)) - Z_T(z) h, T the union of the S_i.
+///
+/// Returns the evaluations of each polynomial at the points of its set.
+pub fn coeff_form_uni_kzg_shplonk_open<E, T>(
+    srs: &CoefFormUniKZGSRS<E>,
+    polys: &[impl AsRef<[E::Fr]> + Sync],
+    point_sets: &[impl AsRef<[E::Fr]> + Sync],
+    fs_transcript: &mut T,
+) -> (Vec<Vec<E::Fr>>, ShplonkOpening<E>)
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
+    E::Fr: ExtensionField,
+    T: Transcript,
+{
+    assert_eq!(polys.len(), point_sets.len());
+    assert!(!polys.is_empty());
+
+    let evals: Vec<Vec<E::Fr>> = polys
+        .par_iter()
+        .zip(point_sets.par_iter())
+        .map(|(coeffs, points)| {
+            points
+                .as_ref()
+                .iter()
+                .map(|p| univariate_eval(coeffs.as_ref(), *p))
+                .collect()
+        })
+        .collect();
+    append_evals(&evals, fs_transcript);
+
+    let gamma = fs_transcript.generate_field_element::<E::Fr>();
+    let gamma_powers = powers_series(&gamma, polys.len());
+
+    // the quotient of f_i by Z_{S_i} is that of f_i - r_i, the remainders dropped
+    let quotients: Vec<Vec<E::Fr>> = polys
+        .par_iter()
+        .zip(point_sets.par_iter())
+        .map(|(coeffs, points)| {
+            points
+                .as_ref()
+                .iter()
+                .fold(coeffs.as_ref().to_vec(), |acc, p| {
+                    univariate_degree_one_quotient(&acc, *p).0
+                })
+        })
+        .collect();
+    let mut h = vec![];
+    izip!(&gamma_powers, &quotients).for_each(|(gamma_i, q)| polynomial_add(&mut h, *gamma_i, q));
+    let quotient_commitment = coeff_form_uni_kzg_commit(srs, &h);
+    fs_transcript.append_u8_slice(quotient_commitment.to_bytes().as_ref());
+
+    let z = fs_transcript.generate_field_element::<E::Fr>();
+    let (weights, z_t) =
+        linearization_weights(point_sets, gamma, z).expect("the challenge is an opening point");
+
+    let mut linearization = vec![];
+    let mut constant = E::Fr::ZERO;
+    izip!(polys, point_sets, &evals, &weights).for_each(|(coeffs, points, evals, weight)| {
+        polynomial_add(&mut linearization, *weight, coeffs.as_ref());
+        constant += *weight * lagrange_eval(points.as_ref(), evals, z).expect("repeated point");
+    });
+    polynomial_add(&mut linearization, -z_t, &h);
+    linearization[0] -= constant;
+
+    let (linearization_quotient, remainder) = univariate_degree_one_quotient(&linearization, z);
+    assert_eq!(remainder, E::Fr::ZERO);
+    let linearization_quotient_commitment = coeff_form_uni_kzg_commit(srs, &linearization_quotient);
+
+    (
+        evals,
+        ShplonkOpening {
+            quotient_commitment,
+            linearization_quotient_commitment,
+        },
+    )
+}
+
+#[inline(always)]
+pub fn coeff_form_uni_kzg_shplonk_verify<E, T>(
+    vk: &UniKZGVerifierParams<E>,
+    commitments: &[E::G1Affine],
+    point_sets: &[impl AsRef<[E::Fr]>],
+    evals: &[Vec<E::Fr>],
+    opening: &ShplonkOpening<E>,
+    fs_transcript: &mut T,
+) -> bool
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Fr: ExtensionField,
+    T: Transcript,
+{
+    let mut pairing_acc = PairingAccumulator::new();
+    coeff_form_uni_kzg_shplonk_verify_deferred(
+        vk,
+        commitments,
+        point_sets,
+        evals,
+        opening,
+        fs_transcript,
+        &mut pairing_acc,
+    ) && pairing_acc.check()
+}
+
+/// `coeff_form_uni_kzg_shplonk_verify` adding its single pairing check to `pairing_acc` rather
+/// than running it. Returns false if the other checks of the opening fail.
+pub fn coeff_form_uni_kzg_shplonk_verify_deferred<E, T>(
+    vk: &UniKZGVerifierParams<E>,
+    commitments: &[E::G1Affine],
+    point_sets: &[impl AsRef<[E::Fr]>],
+    evals: &[Vec<E::Fr>],
+    opening: &ShplonkOpening<E>,
+    fs_transcript: &mut T,
+    pairing_acc: &mut PairingAccumulator<E>,
+) -> bool
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Fr: ExtensionField,
+    T: Transcript,
+{
+    if commitments.is_empty()
+        || commitments.len() != point_sets.len()
+        || commitments.len() != evals.len()
+        || izip!(point_sets, evals).any(|(points, evals)| points.as_ref().len() != evals.len())
+    {
+        return false;
+    }
+    append_evals(evals, fs_transcript);
+
+    let gamma = fs_transcript.generate_field_element::<E::Fr>();
+    fs_transcript.append_u8_slice(opening.quotient_commitment.to_bytes().as_ref());
+
+    let z = fs_transcript.generate_field_element::<E::Fr>();
+    let Some((weights, z_t)) = linearization_weights(point_sets, gamma, z) else {
+        return false;
+    };
+    let Some(constant) = izip!(point_sets, evals, &weights)
+        .map(|(points, evals, weight)| {
+            lagrange_eval(points.as_ref(), evals, z).map(|r_i_at_z| *weight * r_i_at_z)
+        })
+        .sum::<Option<E::Fr>>()
+    else {
+        return false;
+    };
+
+    // [L(tau)] = sum_i gamma^i Z_{T \ S_i}(z) (C_i - r_i(z) G) - Z_T(z) W, and
+    // L(tau) + z W'(tau) = tau W'(tau)
+    let mut scalars = weights;
+    scalars.extend([-constant, -z_t, z]);
+    let mut bases = commitments.to_vec();
+    bases.extend([
+        E::G1Affine::generator(),
+        opening.quotient_commitment,
+        opening.linearization_quotient_commitment,
+    ]);
+    let mut lhs = E::G1::identity();
+    backend::msm_serial(&scalars, &bases, &mut lhs);
+
+    pairing_acc.add_equality(
+        lhs.to_affine(),
+        E::G2Affine::generator(),
+        opening.linearization_quotient_commitment,
+        vk.tau_g2,
+    );
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use gkr_hashers::Keccak256hasher;
+    use halo2curves::bn256::{Bn256, Fr};
+    use transcript::BytesHashTranscript;
+
+    use super::*;
+
+    #[test]
+    fn test_coefficient_form_univariate_kzg_shplonk_e2e() {
+        type T = BytesHashTranscript<Keccak256hasher>;
+        let mut rng = test_rng();
+        let srs = generate_coef_form_uni_kzg_srs_for_testing::<Bn256>(16, &mut rng);
+        let vk: UniKZGVerifierParams<Bn256> = From::from(&srs);
+
+        // polynomials of several degrees, at overlapping sets of points of several sizes
+        let shared = Fr::random(&mut rng);
+        let polys: Vec<Vec<Fr>> = [16, 3, 8, 1, 16]
+            .iter()
+            .map(|len| (0..*len).map(|_| Fr::random(&mut rng)).collect())
+            .collect();
+        let point_sets: Vec<Vec<Fr>> = [1, 2, 3, 1, 4]
+            .iter()
+            .map(|num_points| {
+                let mut points: Vec<Fr> = (1..*num_points).map(|_| Fr::random(&mut rng)).collect();
+                points.push(shared);
+                points
+            })
+            .collect();
+        let commitments: Vec<_> = polys
+            .iter()
+            .map(|poly| coeff_form_uni_kzg_commit(&srs, poly))
+            .collect();
+
+        let (evals, opening) =
+            coeff_form_uni_kzg_shplonk_open(&srs, &polys, &point_sets, &mut T::new());
+        izip!(&polys, &point_sets, &evals).for_each(|(poly, points, evals)| {
+            izip!(points, evals).for_each(|(p, e)| {
+                assert_eq!(univariate_degree_one_quotient(poly, *p).1, *e);
+            })
+        });
+
+        assert!(coeff_form_uni_kzg_shplonk_verify(
+            &vk,
+            &commitments,
+            &point_sets,
+            &evals,
+            &opening,
+            &mut T::new()
+        ));
+
+        let mut wrong_evals = evals.clone();
+        wrong_evals[2][1] += Fr::ONE;
+        assert!(!coeff_form_uni_kzg_shplonk_verify(
+            &vk,
+            &commitments,
+            &point_sets,
+            &wrong_evals,
+            &opening,
+            &mut T::new()
+        ));
+
+        let mut wrong_commitments = commitments.clone();
+        wrong_commitments.swap(0, 4);
+        assert!(!coeff_form_uni_kzg_shplonk_verify(
+            &vk,
+            &wrong_commitments,
+            &point_sets,
+            &evals,
+            &opening,
+            &mut T::new()
+        ));
+    }
+}
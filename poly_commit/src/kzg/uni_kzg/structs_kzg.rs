@@ -3,6 +3,8 @@ use gkr_engine::StructuredReferenceString;
 use halo2curves::{pairing::Engine, CurveAffine};
 use serdes::{ExpSerde, SerdeResult};
 
+use crate::SrsPoints;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Derivative)]
 #[derivative(Default(bound = ""))]
 pub struct UniKZGCommitment<E: Engine>(pub E::G1Affine)
@@ -38,12 +40,12 @@ where
 #[derivative(Default(bound = ""))]
 pub struct CoefFormUniKZGSRS<E: Engine>
 where
-    E::G1Affine: ExpSerde,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
     E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
 {
     /// power of \tau times the generators of G1, yielding
     /// \tau^i over G1 with i ranging in \[ 0, 2^n - 1 \]
-    pub powers_of_tau: Vec<E::G1Affine>,
+    pub powers_of_tau: SrsPoints<E::G1Affine>,
     /// \tau over G2
     pub tau_g2: E::G2Affine,
 }
@@ -74,7 +76,7 @@ where
 
 impl<E: Engine> From<&CoefFormUniKZGSRS<E>> for UniKZGVerifierParams<E>
 where
-    E::G1Affine: ExpSerde,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
     E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
 {
     fn from(value: &CoefFormUniKZGSRS<E>) -> Self {
@@ -83,3 +85,17 @@ where
         }
     }
 }
+
+/// Opening of many univariate polynomials, each at its own set of points, see
+/// `coeff_form_uni_kzg_shplonk_open`.
+#[derive(Clone, Debug, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct ShplonkOpening<E: Engine>
+where
+    E::G1Affine: ExpSerde,
+{
+    /// W, the commitment to the combination of the quotients by the vanishing polynomials
+    pub quotient_commitment: E::G1Affine,
+    /// W', the commitment to the quotient of the linearization at z by X - z
+    pub linearization_quotient_commitment: E::G1Affine,
+}
@@ -1,8 +1,7 @@
 use halo2curves::{
     ff::Field,
-    group::{prime::PrimeCurveAffine, Curve, Group},
-    msm,
-    pairing::{MillerLoopResult, MultiMillerLoop},
+    group::{prime::PrimeCurveAffine, Curve},
+    pairing::MultiMillerLoop,
     CurveAffine,
 };
 use rayon::prelude::*;
@@ -42,7 +41,7 @@ where
     };
 
     CoefFormUniKZGSRS {
-        powers_of_tau: coeff_bases,
+        powers_of_tau: coeff_bases.into(),
         tau_g2: (E::G2Affine::generator() * tau).into(),
     }
 }
@@ -58,11 +57,24 @@ where
 {
     assert!(srs.powers_of_tau.len() >= coeffs.len());
 
-    let com = msm::best_multiexp(coeffs, &srs.powers_of_tau[..coeffs.len()]);
+    let com = backend::msm(coeffs, &srs.powers_of_tau[..coeffs.len()]);
 
     com.into()
 }
 
+/// Same as `coeff_form_uni_kzg_commit`, from the `FixedBaseTables` of the powers of tau of the
+/// SRS, for the many commitments against the same SRS.
+#[inline(always)]
+pub fn coeff_form_uni_kzg_commit_with_tables<E: MultiMillerLoop>(
+    tables: &FixedBaseTables<E::G1Affine>,
+    coeffs: &[E::Fr],
+) -> E::G1Affine
+where
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+{
+    tables.msm(coeffs).into()
+}
+
 #[inline(always)]
 pub fn coeff_form_uni_kzg_open_eval<E: MultiMillerLoop>(
     srs: &CoefFormUniKZGSRS<E>,
@@ -76,13 +88,13 @@ where
     assert!(srs.powers_of_tau.len() >= coeffs.len());
 
     let (div, eval) = univariate_degree_one_quotient(coeffs, alpha);
-    let opening = msm::best_multiexp(&div, &srs.powers_of_tau[..div.len()]);
+    let opening = backend::msm(&div, &srs.powers_of_tau[..div.len()]);
 
     (eval, opening.into())
 }
 
 #[inline(always)]
-pub(crate) fn coeff_form_uni_kzg_verify<E: MultiMillerLoop>(
+pub fn coeff_form_uni_kzg_verify<E: MultiMillerLoop>(
     vk: &UniKZGVerifierParams<E>,
     comm: E::G1Affine,
     alpha: E::Fr,
@@ -92,25 +104,42 @@ pub(crate) fn coeff_form_uni_kzg_verify<E: MultiMillerLoop>(
 where
     E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
     E::G2Affine: ExpSerde,
+    E::Fr: arith::Field,
+{
+    let mut pairing_acc = PairingAccumulator::new();
+    coeff_form_uni_kzg_verify_deferred(vk, comm, alpha, eval, opening, &mut pairing_acc);
+    pairing_acc.check()
+}
+
+/// Add the pairing check of `coeff_form_uni_kzg_verify` to `pairing_acc` rather than running it
+#[inline(always)]
+pub fn coeff_form_uni_kzg_verify_deferred<E: MultiMillerLoop>(
+    vk: &UniKZGVerifierParams<E>,
+    comm: E::G1Affine,
+    alpha: E::Fr,
+    eval: E::Fr,
+    opening: E::G1Affine,
+    pairing_acc: &mut PairingAccumulator<E>,
+) where
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde,
 {
     let g1_eval: E::G1Affine = (E::G1Affine::generator() * eval).into();
     let g2_alpha: E::G2 = E::G2Affine::generator() * alpha;
 
-    let gt_result = E::multi_miller_loop(&[
-        (
-            &opening,
-            &(vk.tau_g2.to_curve() - g2_alpha).to_affine().into(),
-        ),
-        (&(g1_eval - comm).into(), &E::G2Affine::generator().into()),
+    pairing_acc.add_check(&[
+        (opening, (vk.tau_g2.to_curve() - g2_alpha).to_affine()),
+        ((g1_eval - comm).into(), E::G2Affine::generator()),
     ]);
-
-    gt_result.final_exponentiation().is_identity().into()
 }
 
 #[cfg(test)]
 mod tests {
     use ark_std::test_rng;
-    use halo2curves::bn256::{Bn256, Fr};
+    use halo2curves::{
+        bn256::{Bn256, Fr},
+        ff::Field,
+    };
 
     use crate::*;
 
@@ -157,4 +186,48 @@ mod tests {
 
         assert!(coeff_form_uni_kzg_verify(&vk, com, alpha, eval, opening))
     }
+
+    #[test]
+    fn test_coefficient_form_univariate_kzg_batched_pairing_checks() {
+        let mut rng = test_rng();
+        let srs = generate_coef_form_uni_kzg_srs_for_testing::<Bn256>(8, &mut rng);
+        let vk: UniKZGVerifierParams<Bn256> = From::from(&srs);
+
+        let openings: Vec<_> = (0..4)
+            .map(|_| {
+                let poly: Vec<Fr> = (0..8).map(|_| Fr::random(&mut rng)).collect();
+                let alpha = Fr::random(&mut rng);
+                let com = coeff_form_uni_kzg_commit(&srs, &poly);
+                let (eval, opening) = coeff_form_uni_kzg_open_eval(&srs, &poly, alpha);
+                (com, alpha, eval, opening)
+            })
+            .collect();
+
+        let mut pairing_acc = Bn254PairingAccumulator::new();
+        for (com, alpha, eval, opening) in &openings {
+            coeff_form_uni_kzg_verify_deferred(
+                &vk,
+                *com,
+                *alpha,
+                *eval,
+                *opening,
+                &mut pairing_acc,
+            );
+        }
+        assert_eq!(pairing_acc.len(), openings.len());
+        assert!(pairing_acc.check());
+
+        // a single wrong evaluation fails the batch
+        let mut wrong_acc = Bn254PairingAccumulator::new();
+        for (i, (com, alpha, eval, opening)) in openings.iter().enumerate() {
+            let eval = if i == 2 { *eval + Fr::ONE } else { *eval };
+            coeff_form_uni_kzg_verify_deferred(&vk, *com, *alpha, eval, *opening, &mut wrong_acc);
+        }
+        assert!(!wrong_acc.check());
+
+        // the checks of two accumulators are verified together
+        pairing_acc.append(&mut wrong_acc);
+        assert!(wrong_acc.is_empty());
+        assert!(!pairing_acc.check());
+    }
 }
@@ -0,0 +1,10 @@
+mod structs_zeromorph;
+pub use structs_zeromorph::*;
+
+mod zeromorph_impl;
+pub use zeromorph_impl::*;
+
+mod pcs_trait_impl;
+pub use pcs_trait_impl::ZeromorphPCS;
+
+mod expander_api;
@@ -0,0 +1,154 @@
+use arith::ExtensionField;
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
+};
+use halo2curves::{
+    ff::PrimeField,
+    pairing::{Engine, MultiMillerLoop},
+    CurveAffine,
+};
+use polynomials::MultilinearExtension;
+use serdes::ExpSerde;
+
+use crate::{
+    utils::{
+        gather_evals, lift_expander_challenge_to_n_vars,
+        lift_poly_and_expander_challenge_to_n_vars, lift_poly_to_n_vars,
+    },
+    *,
+};
+
+impl<G, E> ExpanderPCS<G> for ZeromorphPCS<E>
+where
+    G: FieldEngine<ChallengeField = E::Fr, SimdCircuitField = E::Fr>,
+    E: Engine + MultiMillerLoop,
+    E::Fr: ExtensionField + PrimeField,
+    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+{
+    const NAME: &'static str = "ZeromorphForExpander";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::Zeromorph;
+
+    type Commitment = UniKZGCommitment<E>;
+    type Opening = ZeromorphOpening<E>;
+    type Params = usize;
+    type SRS = CoefFormUniKZGSRS<E>;
+    type ScratchPad = ();
+    type BatchOpening = ();
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
+
+    /// NOTE: the number of local variables, such that the global polynomial, over the local
+    /// variables and the processes, has at least a variable.
+    fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params {
+        if n_input_vars + world_size.next_power_of_two().ilog2() as usize
+            >= Self::MINIMUM_SUPPORTED_NUM_VARS
+        {
+            n_input_vars
+        } else {
+            Self::MINIMUM_SUPPORTED_NUM_VARS
+        }
+    }
+
+    /// The SRS of the global polynomial, opened by the root alone.
+    fn gen_srs(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        rng: impl rand::RngCore,
+    ) -> Self::SRS {
+        let global_num_vars = *params + mpi_engine.n_parties().ilog2() as usize;
+        generate_coef_form_uni_kzg_srs_for_testing(1 << global_num_vars, rng)
+    }
+
+    fn commit(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<E::Fr>,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Commitment>> {
+        if poly.num_vars() < *params {
+            let poly = lift_poly_to_n_vars(poly, *params);
+            return <Self as ExpanderPCS<G>>::commit(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                scratch_pad,
+            );
+        }
+
+        let Some(evals) = gather_evals(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        Ok(Some(UniKZGCommitment(coeff_form_uni_kzg_commit(
+            proving_key,
+            &evals,
+        ))))
+    }
+
+    fn open(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<E::Fr>,
+        x: &ExpanderSingleVarChallenge<G>,
+        transcript: &mut impl Transcript,
+        scratch_pad: &Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Opening>> {
+        if poly.num_vars() < *params {
+            let (poly, x) = lift_poly_and_expander_challenge_to_n_vars(poly, x, *params);
+            return <Self as ExpanderPCS<G>>::open(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                &x,
+                transcript,
+                scratch_pad,
+            );
+        }
+
+        // NOTE: the opening is run on the root alone, over the gathered polynomial
+        let Some(evals) = gather_evals(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        let (_eval, opening) = zeromorph_open(proving_key, &evals, &x.global_xs(), transcript);
+
+        Ok(Some(opening))
+    }
+
+    fn verify(
+        params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &ExpanderSingleVarChallenge<G>,
+        v: <G as FieldEngine>::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        if x.rz.len() < *params {
+            let x = lift_expander_challenge_to_n_vars(x, *params);
+            return <Self as ExpanderPCS<G>>::verify(
+                params,
+                verifying_key,
+                commitment,
+                &x,
+                v,
+                transcript,
+                opening,
+            );
+        }
+
+        zeromorph_verify(
+            verifying_key,
+            commitment.0,
+            &x.global_xs(),
+            v,
+            opening,
+            transcript,
+        )
+    }
+}
@@ -0,0 +1,93 @@
+use std::marker::PhantomData;
+
+use arith::ExtensionField;
+use gkr_engine::{StructuredReferenceString, Transcript};
+use halo2curves::{
+    ff::PrimeField,
+    pairing::{Engine, MultiMillerLoop},
+    CurveAffine,
+};
+use polynomials::MultiLinearPoly;
+use serdes::ExpSerde;
+
+use crate::*;
+
+/// Zeromorph (KT23) over the univariate KZG: the multilinear polynomial is committed to as the
+/// univariate polynomial of its evaluations over the hypercube, with the same SRS and the same
+/// commitment as `HyperUniKZGPCS`, and opened with a KZG opening of a single polynomial.
+pub struct ZeromorphPCS<E>
+where
+    E: Engine,
+    E::Fr: ExtensionField,
+{
+    _marker_e: PhantomData<E>,
+}
+
+impl<E> ZeromorphPCS<E>
+where
+    E: Engine,
+    E::Fr: ExtensionField,
+{
+    pub const MINIMUM_SUPPORTED_NUM_VARS: usize = 1;
+}
+
+impl<E> PolynomialCommitmentScheme<E::Fr> for ZeromorphPCS<E>
+where
+    E: Engine + MultiMillerLoop,
+    E::Fr: ExtensionField + PrimeField,
+    E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+    E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
+{
+    const NAME: &'static str = "ZeromorphPCS";
+
+    type Params = usize;
+    type Poly = MultiLinearPoly<E::Fr>;
+    type EvalPoint = Vec<E::Fr>;
+    type ScratchPad = ();
+
+    type SRS = CoefFormUniKZGSRS<E>;
+    type Commitment = UniKZGCommitment<E>;
+    type Opening = ZeromorphOpening<E>;
+
+    fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
+
+    fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
+        let local_num_vars = std::cmp::max(*params, Self::MINIMUM_SUPPORTED_NUM_VARS);
+
+        let length = 1 << local_num_vars;
+        let srs = generate_coef_form_uni_kzg_srs_for_testing(length, rng);
+        (srs, local_num_vars)
+    }
+
+    fn commit(
+        _params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        _scratch_pad: &mut Self::ScratchPad,
+    ) -> Self::Commitment {
+        UniKZGCommitment(coeff_form_uni_kzg_commit(proving_key, &poly.coeffs))
+    }
+
+    fn open(
+        _params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        x: &Self::EvalPoint,
+        _scratch_pad: &Self::ScratchPad,
+        transcript: &mut impl Transcript,
+    ) -> (E::Fr, Self::Opening) {
+        zeromorph_open(proving_key, &poly.coeffs, x, transcript)
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &Self::EvalPoint,
+        v: E::Fr,
+        opening: &Self::Opening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        zeromorph_verify(verifying_key, commitment.0, x, v, opening, transcript)
+    }
+}
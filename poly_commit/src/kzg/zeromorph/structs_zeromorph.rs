@@ -0,0 +1,20 @@
+use derivative::Derivative;
+use halo2curves::pairing::Engine;
+use serdes::ExpSerde;
+
+/// Zeromorph opening of a multilinear polynomial, committed to as the univariate polynomial of
+/// its evaluations over the hypercube, i.e., as a `UniKZGCommitment`.
+#[derive(Debug, Clone, Derivative, ExpSerde)]
+#[derivative(Default(bound = ""))]
+pub struct ZeromorphOpening<E: Engine>
+where
+    E::G1Affine: Default + ExpSerde,
+{
+    /// The commitments to the quotients q_k, over the lowest k variables, of
+    /// f(X) - v = \sum_k (X_k - u_k) q_k(X_0, ..., X_{k - 1})
+    pub quotient_commitments: Vec<E::G1Affine>,
+    /// The commitment to \sum_k y^k X^{2^n - 2^k} q_k(X), bounding the degrees of the quotients
+    pub batched_quotient_commitment: E::G1Affine,
+    /// The KZG opening of the polynomial vanishing at x that ties the quotients to the commitment
+    pub opening: E::G1Affine,
+}
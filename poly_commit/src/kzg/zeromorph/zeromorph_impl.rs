@@ -0,0 +1,304 @@
+use arith::ExtensionField;
+use gkr_engine::Transcript;
+use halo2curves::{
+    ff::Field,
+    group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding},
+    pairing::MultiMillerLoop,
+    CurveAffine,
+};
+use itertools::izip;
+use serdes::ExpSerde;
+
+use crate::*;
+
+/// \Phi_m(a) = \sum_{i < 2^m} a^i = \prod_{j < m} (1 + a^{2^j})
+#[inline(always)]
+fn zeromorph_phi<F: Field>(a: F, m: usize) -> F {
+    (0..m)
+        .fold((F::ONE, a), |(acc, a_pow), _| {
+            (acc * (F::ONE + a_pow), a_pow.square())
+        })
+        .0
+}
+
+/// The quotients q_k, over the lowest k variables, of
+/// f(X) - f(u) = \sum_k (X_k - u_k) q_k(X_0, ..., X_{k - 1}), along with f(u).
+/// The variables are LSB first, the quotients are given by their evaluations over the hypercube.
+#[inline(always)]
+pub(crate) fn zeromorph_quotients<F: Field>(evals: &[F], point: &[F]) -> (Vec<Vec<F>>, F) {
+    assert_eq!(evals.len(), 1 << point.len());
+
+    let mut quotients = vec![vec![]; point.len()];
+    let mut folded = evals.to_vec();
+    point.iter().enumerate().rev().for_each(|(k, u_k)| {
+        let (lo, hi) = folded.split_at(folded.len() / 2);
+        let quotient: Vec<F> = izip!(lo, hi).map(|(l, h)| *h - *l).collect();
+        folded = izip!(lo, &quotient).map(|(l, q)| *l + *u_k * *q).collect();
+        quotients[k] = quotient;
+    });
+
+    (quotients, folded[0])
+}
+
+/// The weights of the commitments to the quotients in the commitment to
+/// \zeta_x(X) + z Z_x(X), the polynomial vanishing at x, where
+/// \zeta_x(X) = \hat{q}(X) - \sum_k y^k x^{2^n - 2^k} q_k(X) and
+/// Z_x(X) = f(X) - v \Phi_n(x) - \sum_k (x^{2^k} \Phi_{n-k-1}(x^{2^{k+1}}) - u_k
+/// \Phi_{n-k}(x^{2^k})) q_k(X)
+#[inline(always)]
+fn zeromorph_quotient_weights<F: Field>(point: &[F], x: F, y: F, z: F) -> Vec<F> {
+    let num_vars = point.len();
+    let y_pow_series = powers_series(&y, num_vars);
+
+    let mut x_pow_2k = x;
+    izip!(point, y_pow_series)
+        .enumerate()
+        .map(|(k, (u_k, y_k))| {
+            let x_pow_2k_plus_1 = x_pow_2k.square();
+            let degree_shift = x.pow_vartime([((1u64 << num_vars) - (1u64 << k))]);
+            let z_weight = x_pow_2k * zeromorph_phi(x_pow_2k_plus_1, num_vars - k - 1)
+                - *u_k * zeromorph_phi(x_pow_2k, num_vars - k);
+            x_pow_2k = x_pow_2k_plus_1;
+
+            -(y_k * degree_shift + z * z_weight)
+        })
+        .collect()
+}
+
+/// Open the multilinear polynomial of evaluations `evals` over the hypercube at `point` with
+/// Zeromorph (KT23), the polynomial being committed to as the univariate polynomial of
+/// coefficients `evals`.
+///
+/// NOTE: the degrees of the quotients are bounded by the length of the SRS, that should be
+/// exactly the number of evaluations.
+pub fn zeromorph_open<E, T>(
+    srs: &CoefFormUniKZGSRS<E>,
+    evals: &[E::Fr],
+    point: &[E::Fr],
+    fs_transcript: &mut T,
+) -> (E::Fr, ZeromorphOpening<E>)
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
+    E::Fr: ExtensionField,
+    T: Transcript,
+{
+    assert!(!point.is_empty());
+
+    let (quotients, eval) = zeromorph_quotients(evals, point);
+    let quotient_commitments: Vec<E::G1Affine> = quotients
+        .iter()
+        .map(|q| coeff_form_uni_kzg_commit(srs, q))
+        .collect();
+    quotient_commitments
+        .iter()
+        .for_each(|c| fs_transcript.append_u8_slice(c.to_bytes().as_ref()));
+
+    // NOTE: q_k shifted to the top of the SRS, so that its degree is at most 2^k - 1
+    let y = fs_transcript.generate_field_element::<E::Fr>();
+    let y_pow_series = powers_series(&y, point.len());
+    let mut batched_quotient = vec![E::Fr::ZERO; evals.len()];
+    izip!(&quotients, &y_pow_series).for_each(|(q, y_k)| {
+        let offset = evals.len() - q.len();
+        izip!(&mut batched_quotient[offset..], q).for_each(|(c, q_i)| *c += *y_k * *q_i);
+    });
+    let batched_quotient_commitment = coeff_form_uni_kzg_commit(srs, &batched_quotient);
+    fs_transcript.append_u8_slice(batched_quotient_commitment.to_bytes().as_ref());
+
+    let x = fs_transcript.generate_field_element::<E::Fr>();
+    let z = fs_transcript.generate_field_element::<E::Fr>();
+
+    let mut vanishing_at_x = batched_quotient;
+    polynomial_add(&mut vanishing_at_x, z, evals);
+    vanishing_at_x[0] -= z * eval * zeromorph_phi(x, point.len());
+    izip!(&quotients, zeromorph_quotient_weights(point, x, y, z))
+        .for_each(|(q, weight)| polynomial_add(&mut vanishing_at_x, weight, q));
+
+    let (quotient_at_x, remainder) = univariate_degree_one_quotient(&vanishing_at_x, x);
+    assert_eq!(remainder, E::Fr::ZERO);
+    let opening = coeff_form_uni_kzg_commit(srs, &quotient_at_x);
+
+    (
+        eval,
+        ZeromorphOpening {
+            quotient_commitments,
+            batched_quotient_commitment,
+            opening,
+        },
+    )
+}
+
+#[inline(always)]
+pub fn zeromorph_verify<E, T>(
+    vk: &UniKZGVerifierParams<E>,
+    comm: E::G1Affine,
+    point: &[E::Fr],
+    eval: E::Fr,
+    opening: &ZeromorphOpening<E>,
+    fs_transcript: &mut T,
+) -> bool
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Fr: ExtensionField + ExpSerde,
+    T: Transcript,
+{
+    let mut pairing_acc = PairingAccumulator::new();
+    zeromorph_verify_deferred(
+        vk,
+        comm,
+        point,
+        eval,
+        opening,
+        fs_transcript,
+        &mut pairing_acc,
+    ) && pairing_acc.check()
+}
+
+/// `zeromorph_verify` adding the final pairing check to `pairing_acc` rather than running it.
+/// Returns false if the opening is malformed.
+#[inline(always)]
+pub fn zeromorph_verify_deferred<E, T>(
+    vk: &UniKZGVerifierParams<E>,
+    comm: E::G1Affine,
+    point: &[E::Fr],
+    eval: E::Fr,
+    opening: &ZeromorphOpening<E>,
+    fs_transcript: &mut T,
+    pairing_acc: &mut PairingAccumulator<E>,
+) -> bool
+where
+    E: MultiMillerLoop,
+    E::G1Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1> + ExpSerde,
+    E::G2Affine: ExpSerde,
+    E::Fr: ExtensionField + ExpSerde,
+    T: Transcript,
+{
+    if point.is_empty() || opening.quotient_commitments.len() != point.len() {
+        return false;
+    }
+
+    opening
+        .quotient_commitments
+        .iter()
+        .for_each(|c| fs_transcript.append_u8_slice(c.to_bytes().as_ref()));
+    let y = fs_transcript.generate_field_element::<E::Fr>();
+
+    fs_transcript.append_u8_slice(opening.batched_quotient_commitment.to_bytes().as_ref());
+    let x = fs_transcript.generate_field_element::<E::Fr>();
+    let z = fs_transcript.generate_field_element::<E::Fr>();
+
+    // NOTE: the commitment to \zeta_x(X) + z Z_x(X), which is opened to 0 at x
+    let mut bases = vec![
+        opening.batched_quotient_commitment,
+        comm,
+        E::G1Affine::generator(),
+    ];
+    bases.extend_from_slice(&opening.quotient_commitments);
+    let mut scalars = vec![E::Fr::ONE, z, -z * eval * zeromorph_phi(x, point.len())];
+    scalars.extend(zeromorph_quotient_weights(point, x, y, z));
+
+    let mut vanishing_commitment = E::G1::identity();
+    backend::msm_serial(&scalars, &bases, &mut vanishing_commitment);
+
+    coeff_form_uni_kzg_verify_deferred(
+        vk,
+        vanishing_commitment.to_affine(),
+        x,
+        E::Fr::ZERO,
+        opening.opening,
+        pairing_acc,
+    );
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use halo2curves::{
+        bn256::{Bn256, Fr},
+        ff::Field,
+    };
+    use polynomials::MultiLinearPoly;
+    use transcript::BytesHashTranscript;
+
+    use gkr_engine::Transcript;
+    use gkr_hashers::Keccak256hasher;
+
+    use crate::*;
+
+    use super::{zeromorph_phi, zeromorph_quotients};
+
+    fn evaluate(evals: &[Fr], point: &[Fr]) -> Fr {
+        MultiLinearPoly::evaluate_with_buffer(evals, point, &mut vec![Fr::ZERO; evals.len()])
+    }
+
+    #[test]
+    fn test_zeromorph_quotients() {
+        let mut rng = test_rng();
+        let num_vars = 5;
+        let evals: Vec<Fr> = (0..1 << num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let point: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let (quotients, eval) = zeromorph_quotients(&evals, &point);
+        assert_eq!(eval, evaluate(&evals, &point));
+
+        // f(X) - f(u) = \sum_k (X_k - u_k) q_k(X_0, ..., X_{k - 1}) at a random point
+        let at: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let rhs: Fr = quotients
+            .iter()
+            .enumerate()
+            .map(|(k, q)| (at[k] - point[k]) * evaluate(q, &at[..k]))
+            .sum();
+        assert_eq!(evaluate(&evals, &at) - eval, rhs);
+
+        let x = Fr::random(&mut rng);
+        let phi: Fr = (0..1u64 << num_vars).map(|i| x.pow_vartime([i])).sum();
+        assert_eq!(zeromorph_phi(x, num_vars), phi);
+    }
+
+    #[test]
+    fn test_zeromorph_e2e() {
+        type T = BytesHashTranscript<Keccak256hasher>;
+        let mut rng = test_rng();
+
+        let num_vars = 6;
+        let srs = generate_coef_form_uni_kzg_srs_for_testing::<Bn256>(1 << num_vars, &mut rng);
+        let vk: UniKZGVerifierParams<Bn256> = From::from(&srs);
+
+        let evals: Vec<Fr> = (0..1 << num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let point: Vec<Fr> = (0..num_vars).map(|_| Fr::random(&mut rng)).collect();
+        let comm = coeff_form_uni_kzg_commit(&srs, &evals);
+
+        let (eval, opening) = zeromorph_open(&srs, &evals, &point, &mut T::new());
+        assert!(zeromorph_verify(
+            &vk,
+            comm,
+            &point,
+            eval,
+            &opening,
+            &mut T::new()
+        ));
+        assert!(!zeromorph_verify(
+            &vk,
+            comm,
+            &point,
+            eval + Fr::ONE,
+            &opening,
+            &mut T::new()
+        ));
+
+        let mut bad_opening = opening.clone();
+        bad_opening.quotient_commitments.swap(0, 1);
+        assert!(!zeromorph_verify(
+            &vk,
+            comm,
+            &point,
+            eval,
+            &bad_opening,
+            &mut T::new()
+        ));
+    }
+}
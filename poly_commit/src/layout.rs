@@ -0,0 +1,63 @@
+//! Layout adapters for openings at points given in a foreign hypercube layout.
+//!
+//! Expander lays out the evaluations of a polynomial LSB-first.  A polynomial imported from a
+//! system indexing the hypercube MSB-first can be committed as is: evaluating it at `point` in
+//! the foreign layout is evaluating the committed evaluations at the converted point.  These
+//! helpers do the conversion at the PCS boundary, so neither the polynomial nor the claims need
+//! to be permuted.
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    StructuredReferenceString, Transcript,
+};
+use polynomials::{HypercubeLayout, MultilinearExtension};
+
+/// Open `poly` at `point`, a point over all the variables of the global polynomial in `layout`.
+/// See `ExpanderPCS::open`.
+#[allow(clippy::too_many_arguments)]
+pub fn open_in_layout<C: FieldEngine, PCS: ExpanderPCS<C>>(
+    params: &PCS::Params,
+    mpi_engine: &impl MPIEngine,
+    proving_key: &<PCS::SRS as StructuredReferenceString>::PKey,
+    poly: &impl MultilinearExtension<C::SimdCircuitField>,
+    point: &[C::ChallengeField],
+    layout: HypercubeLayout,
+    transcript: &mut impl Transcript,
+    scratch_pad: &PCS::ScratchPad,
+) -> MPIResult<Option<PCS::Opening>> {
+    let x = ExpanderSingleVarChallenge::<C>::from_point(point, layout, mpi_engine.world_size());
+    PCS::open(
+        params,
+        mpi_engine,
+        proving_key,
+        poly,
+        &x,
+        transcript,
+        scratch_pad,
+    )
+}
+
+/// Verify the opening of a polynomial at `point`, given in `layout`.
+/// `world_size` is the number of MPI processes at proving time. See `ExpanderPCS::verify`.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_in_layout<C: FieldEngine, PCS: ExpanderPCS<C>>(
+    params: &PCS::Params,
+    verifying_key: &<PCS::SRS as StructuredReferenceString>::VKey,
+    commitment: &PCS::Commitment,
+    point: &[C::ChallengeField],
+    layout: HypercubeLayout,
+    world_size: usize,
+    v: C::ChallengeField,
+    transcript: &mut impl Transcript,
+    opening: &PCS::Opening,
+) -> bool {
+    let x = ExpanderSingleVarChallenge::<C>::from_point(point, layout, world_size);
+    PCS::verify(
+        params,
+        verifying_key,
+        commitment,
+        &x,
+        v,
+        transcript,
+        opening,
+    )
+}
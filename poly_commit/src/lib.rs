@@ -1,23 +1,83 @@
 #![allow(clippy::manual_div_ceil)]
 
 mod traits;
-pub use traits::{BatchOpeningPCS, PolynomialCommitmentScheme};
+pub use traits::{BatchOpeningPCS, PolynomialCommitmentScheme, UnivariateBatchOpeningPCS};
 
 pub const PCS_SOUNDNESS_BITS: usize = 128;
 
 mod utils;
-pub use utils::expander_pcs_init_testing_only;
+
+mod backend;
+pub use backend::{batch_to_affine, FixedBaseTables};
+#[cfg(feature = "kzg")]
+pub use backend::{Bn254PairingAccumulator, PairingAccumulator};
+pub use utils::{
+    expander_pcs_init_from_seed_testing_only, expander_pcs_init_testing_only,
+    expander_pcs_init_with_srs_file_testing_only,
+};
+
+#[cfg(any(feature = "hyrax", feature = "kzg"))]
+mod srs_points;
+#[cfg(any(feature = "hyrax", feature = "kzg"))]
+pub use srs_points::SrsPoints;
 
 pub mod raw;
 pub use raw::RawExpanderGKR;
+#[cfg(feature = "raw-distributed")]
+pub use raw::RawExpanderGKRDistributed;
+
+#[cfg(feature = "basefold")]
+pub mod basefold;
+#[cfg(feature = "basefold")]
+pub use basefold::*;
 
+#[cfg(feature = "orion")]
 pub mod orion;
+#[cfg(feature = "orion")]
 pub use orion::*;
 
+#[cfg(feature = "brakedown")]
+pub mod brakedown;
+#[cfg(feature = "brakedown")]
+pub use brakedown::*;
+
+#[cfg(feature = "ligero")]
+pub mod ligero;
+#[cfg(feature = "ligero")]
+pub use ligero::*;
+
+#[cfg(feature = "whir")]
+pub mod whir;
+#[cfg(feature = "whir")]
+pub use whir::*;
+
+#[cfg(feature = "dory")]
+pub mod dory;
+#[cfg(feature = "dory")]
+pub use dory::*;
+
+#[cfg(feature = "ipa")]
+pub mod ipa;
+#[cfg(feature = "ipa")]
+pub use ipa::*;
+
+#[cfg(feature = "hyrax")]
 pub mod hyrax;
+#[cfg(feature = "hyrax")]
 pub use hyrax::*;
 
+#[cfg(feature = "kzg")]
 pub mod kzg;
+#[cfg(feature = "kzg")]
 pub use kzg::*;
 
 pub mod batching;
+
+pub mod layout;
+pub use layout::{open_in_layout, verify_in_layout};
+
+pub mod shared_state;
+pub use shared_state::{
+    position_challenge, position_values, state_root, SharedState, SharedStateVerifier,
+    StateOpenings, StateRoot,
+};
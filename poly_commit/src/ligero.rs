@@ -0,0 +1,35 @@
+//! Ligero (AHIV17): the tensor code PCS over a Reed-Solomon code.
+//!
+//! The evaluations of the polynomial are laid out as a matrix whose rows are encoded as the
+//! evaluations, over a multiplicative subgroup four times their length, of the polynomials with
+//! the rows as coefficients. The columns of the encoded matrix are committed to by a Merkle tree.
+//! An opening is the combination of the rows by the eq polynomial of the row variables of the
+//! point, together with random combinations of the rows for the proximity test, checked against
+//! the same combinations of the columns at the queried positions.
+//!
+//! The code reaches the distance 3/4 of the Singleton bound, fewer queries than for the expander
+//! codes of Orion and Brakedown, at the cost of FFTs, hence a base field with large enough
+//! subgroups of two-adic order, i.e., Goldilocks or the scalar field of BN254.
+//!
+//! With MPI, the processes encode their own rows, and the root gathers the columns of all the
+//! processes into the columns of the global matrix before committing to them.
+
+mod utils;
+pub use utils::{
+    LigeroBatchProof, LigeroCommitment, LigeroProof, LigeroSRS, LigeroScratchPad,
+    LIGERO_LOG_INV_RATE,
+};
+
+mod ligero_impl;
+pub use ligero_impl::{
+    ligero_batch_open, ligero_batch_verify, ligero_commit, ligero_open, ligero_verify,
+};
+
+mod mpi_impl;
+pub use mpi_impl::{ligero_mpi_commit, ligero_mpi_open};
+
+mod pcs_trait_impl;
+pub use pcs_trait_impl::LigeroPCS;
+
+mod expander_api;
+pub use expander_api::LigeroPCSForGKR;
@@ -0,0 +1,274 @@
+use arith::{FFTField, Field, SimdField};
+use gkr_engine::{
+    BatchOpenError, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
+};
+use polynomials::MultilinearExtension;
+
+use crate::{
+    ligero::{
+        ligero_batch_open, ligero_batch_verify, ligero_commit, ligero_mpi_commit, ligero_mpi_open,
+        ligero_open, ligero_verify, LigeroBatchProof, LigeroCommitment, LigeroPCS, LigeroProof,
+        LigeroSRS, LigeroScratchPad,
+    },
+    utils::{
+        lift_expander_challenge_to_n_vars, lift_poly_and_expander_challenge_to_n_vars,
+        lift_poly_to_n_vars,
+    },
+};
+
+/// The number of local variables over the SIMD field elements of the polynomials of the key,
+/// which the smaller polynomials are lifted to
+#[inline(always)]
+fn key_num_vars<C: FieldEngine>(srs: &LigeroSRS<C::CircuitField>) -> usize
+where
+    C::CircuitField: FFTField,
+{
+    srs.num_vars - C::SimdCircuitField::PACK_SIZE.ilog2() as usize
+}
+
+/// The evaluations over the hypercube of the local polynomial, unpacked
+#[inline(always)]
+fn unpack_circuit_vals<C: FieldEngine>(
+    poly: &impl MultilinearExtension<C::SimdCircuitField>,
+) -> Vec<C::CircuitField> {
+    // NOTE: the SIMD lanes are the lowest variables
+    poly.hypercube_basis_ref()
+        .iter()
+        .flat_map(SimdField::unpack)
+        .collect()
+}
+
+/// The evaluations of the global polynomial over the hypercube, unpacked on the root, None on the
+/// other processes
+fn gather_circuit_vals<C: FieldEngine>(
+    mpi_engine: &impl MPIEngine,
+    poly: &impl MultilinearExtension<C::SimdCircuitField>,
+) -> MPIResult<Option<Vec<C::CircuitField>>> {
+    let mut buffer = if mpi_engine.is_root() {
+        vec![C::SimdCircuitField::ZERO; poly.hypercube_size() * mpi_engine.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_engine.gather_vec(poly.hypercube_basis_ref(), &mut buffer)?;
+
+    if !mpi_engine.is_root() {
+        return Ok(None);
+    }
+
+    Ok(buffer
+        .iter()
+        .flat_map(SimdField::unpack)
+        .collect::<Vec<_>>()
+        .into())
+}
+
+impl<C> ExpanderPCS<C> for LigeroPCS<C::CircuitField, C::ChallengeField>
+where
+    C: FieldEngine,
+    C::CircuitField: FFTField,
+{
+    const NAME: &'static str = "LigeroPCSForExpanderGKR";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::Ligero;
+
+    const POWER_OF_TWO_WORLD: bool = true;
+
+    type Params = usize;
+    type ScratchPad = LigeroScratchPad;
+
+    type Commitment = LigeroCommitment;
+    type Opening = LigeroProof<C::ChallengeField>;
+    type BatchOpening = LigeroBatchProof<C::ChallengeField>;
+    type SRS = LigeroSRS<C::CircuitField>;
+
+    /// NOTE: this is the number of variables for local polynomial w.r.t. SIMD field elements.
+    fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params {
+        Self::check_world_size(world_size).unwrap_or_else(|e| panic!("{e}"));
+        n_input_vars
+    }
+
+    /// The code is public, the random source is left unused.
+    fn gen_srs(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        _rng: impl rand::RngCore,
+    ) -> Self::SRS {
+        let num_vars_each_core = *params + C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+        LigeroSRS::new(num_vars_each_core, mpi_engine.world_size())
+    }
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {
+        Self::ScratchPad::default()
+    }
+
+    /// The polynomials smaller than the ones of the key are lifted to the size of the key.
+    fn commit(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Commitment>> {
+        let num_vars = key_num_vars::<C>(proving_key);
+        if poly.num_vars() < num_vars {
+            let poly = lift_poly_to_n_vars(poly, num_vars);
+            return <Self as ExpanderPCS<C>>::commit(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                scratch_pad,
+            );
+        }
+        assert_eq!(poly.num_vars(), num_vars);
+
+        let evals = unpack_circuit_vals::<C>(poly);
+        if mpi_engine.is_single_process() {
+            return Ok(ligero_commit(proving_key, &evals, scratch_pad).into());
+        }
+
+        ligero_mpi_commit(mpi_engine, proving_key, &evals, scratch_pad)
+    }
+
+    fn open(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        transcript: &mut impl Transcript,
+        scratch_pad: &Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Opening>> {
+        let num_vars = key_num_vars::<C>(proving_key);
+        if poly.num_vars() < num_vars {
+            let (poly, eval_point) =
+                lift_poly_and_expander_challenge_to_n_vars(poly, eval_point, num_vars);
+            return <Self as ExpanderPCS<C>>::open(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                &eval_point,
+                transcript,
+                scratch_pad,
+            );
+        }
+        assert_eq!(poly.num_vars(), num_vars);
+
+        let evals = unpack_circuit_vals::<C>(poly);
+        if mpi_engine.is_single_process() {
+            let (_, opening) = ligero_open(
+                proving_key,
+                &evals,
+                &eval_point.local_xs(),
+                transcript,
+                scratch_pad,
+            );
+            return Ok(opening.into());
+        }
+
+        ligero_mpi_open(
+            mpi_engine,
+            proving_key,
+            &evals,
+            &eval_point.local_xs(),
+            &eval_point.r_mpi,
+            transcript,
+            scratch_pad,
+        )
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        eval: C::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        let num_vars = key_num_vars::<C>(verifying_key);
+        let eval_point = if eval_point.rz.len() < num_vars {
+            lift_expander_challenge_to_n_vars(eval_point, num_vars)
+        } else {
+            eval_point.clone()
+        };
+
+        ligero_verify(
+            verifying_key,
+            commitment,
+            &eval_point.global_xs(),
+            eval,
+            transcript,
+            opening,
+        )
+    }
+
+    /// The polynomials are gathered on the root, which opens them all, the other processes
+    /// return no evaluations and an empty opening.
+    fn multi_points_batch_open(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        polys: &[impl MultilinearExtension<C::SimdCircuitField>],
+        eval_points: &[ExpanderSingleVarChallenge<C>],
+        _scratch_pad: &Self::ScratchPad,
+        transcript: &mut impl Transcript,
+    ) -> Result<(Vec<C::ChallengeField>, Self::BatchOpening), BatchOpenError> {
+        for x in eval_points {
+            x.check_shape(mpi_engine.world_size())?;
+        }
+
+        let num_vars = key_num_vars::<C>(proving_key);
+        let mut evals_list = Vec::with_capacity(polys.len());
+        let mut points = Vec::with_capacity(polys.len());
+        for (poly, x) in polys.iter().zip(eval_points) {
+            let (poly, x) = lift_poly_and_expander_challenge_to_n_vars(poly, x, num_vars);
+            let Some(evals) = gather_circuit_vals::<C>(mpi_engine, &poly)? else {
+                continue;
+            };
+            evals_list.push(evals);
+            points.push(x.global_xs());
+        }
+
+        if !mpi_engine.is_root() {
+            return Ok((Vec::new(), LigeroBatchProof::default()));
+        }
+
+        Ok(ligero_batch_open(
+            proving_key,
+            &evals_list,
+            &points,
+            transcript,
+        ))
+    }
+
+    fn multi_points_batch_verify(
+        _params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitments: &[impl AsRef<Self::Commitment>],
+        x: &[ExpanderSingleVarChallenge<C>],
+        evals: &[C::ChallengeField],
+        batch_opening: &Self::BatchOpening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        let num_vars = key_num_vars::<C>(verifying_key);
+        let points: Vec<_> = x
+            .iter()
+            .map(|x_i| lift_expander_challenge_to_n_vars(x_i, num_vars).global_xs())
+            .collect();
+
+        ligero_batch_verify(
+            verifying_key,
+            commitments,
+            &points,
+            evals,
+            transcript,
+            batch_opening,
+        )
+    }
+}
+
+pub type LigeroPCSForGKR<C> =
+    LigeroPCS<<C as FieldEngine>::CircuitField, <C as FieldEngine>::ChallengeField>;
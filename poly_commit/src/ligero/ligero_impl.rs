@@ -0,0 +1,260 @@
+use std::iter;
+
+use arith::{ExtensionField, FFTField};
+use gkr_engine::Transcript;
+use itertools::{chain, izip};
+use polynomials::{EqPolynomial, MatrixView};
+
+use crate::ligero::{
+    utils::{
+        codeword_check, column_openings, column_tree, encode_columns, proximity_coeffs,
+        query_indices, verify_column_openings,
+    },
+    LigeroBatchProof, LigeroCommitment, LigeroProof, LigeroSRS, LigeroScratchPad,
+};
+use crate::traits::TensorCodeIOPPCS;
+
+/// Commit to the evaluations of a multilinear polynomial over the hypercube, by the root of the
+/// tree over the columns of the encoded matrix
+pub fn ligero_commit<F: FFTField>(
+    srs: &LigeroSRS<F>,
+    evals: &[F],
+    scratch_pad: &mut LigeroScratchPad,
+) -> LigeroCommitment {
+    assert_eq!(evals.len(), 1 << srs.num_global_vars());
+
+    let columns = encode_columns(srs, evals);
+    scratch_pad.column_tree = column_tree(srs, &columns);
+    LigeroCommitment(scratch_pad.column_tree.root())
+}
+
+/// Open the committed polynomial at `point`, whose lowest variables are the ones of the rows
+pub fn ligero_open<F, EvalF>(
+    srs: &LigeroSRS<F>,
+    evals: &[F],
+    point: &[EvalF],
+    transcript: &mut impl Transcript,
+    scratch_pad: &LigeroScratchPad,
+) -> (EvalF, LigeroProof<EvalF>)
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    assert_eq!(evals.len(), 1 << srs.num_global_vars());
+    assert_eq!(point.len(), srs.num_global_vars());
+
+    let (col_point, row_point) = point.split_at(srs.log_msg_len);
+    let matrix = MatrixView::new(evals, srs.log_msg_len);
+
+    let random_row_coeffs = proximity_coeffs(srs, row_point.len(), transcript);
+    let proximity_rows: Vec<_> = random_row_coeffs
+        .iter()
+        .map(|coeffs| matrix.combine_rows(coeffs))
+        .collect();
+
+    let eq_row_coeffs = EqPolynomial::build_eq_x_r(row_point);
+    let eval_row = matrix.combine_rows(&eq_row_coeffs);
+    let eval = EqPolynomial::eq_weighted_sum(col_point, &eval_row);
+
+    let query_indices = query_indices(
+        srs,
+        chain!(&proximity_rows, iter::once(&eval_row)),
+        transcript,
+    );
+    let query_openings = column_openings(srs, &scratch_pad.column_tree, &query_indices);
+
+    (
+        eval,
+        LigeroProof {
+            eval_row,
+            proximity_rows,
+            query_openings,
+        },
+    )
+}
+
+pub fn ligero_verify<F, EvalF>(
+    srs: &LigeroSRS<F>,
+    commitment: &LigeroCommitment,
+    point: &[EvalF],
+    eval: EvalF,
+    transcript: &mut impl Transcript,
+    proof: &LigeroProof<EvalF>,
+) -> bool
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    if point.len() != srs.num_global_vars() || proof.eval_row.len() != srs.message_len() {
+        return false;
+    }
+
+    let (col_point, row_point) = point.split_at(srs.log_msg_len);
+    if EqPolynomial::eq_weighted_sum::<EvalF, EvalF>(col_point, &proof.eval_row) != eval {
+        return false;
+    }
+
+    let random_row_coeffs = proximity_coeffs(srs, row_point.len(), transcript);
+    if proof.proximity_rows.len() != random_row_coeffs.len() {
+        return false;
+    }
+
+    let query_indices = query_indices(
+        srs,
+        chain!(&proof.proximity_rows, iter::once(&proof.eval_row)),
+        transcript,
+    );
+    let Some(columns) =
+        verify_column_openings(srs, commitment, &query_indices, &proof.query_openings)
+    else {
+        return false;
+    };
+
+    let eq_row_coeffs = EqPolynomial::build_eq_x_r(row_point);
+    izip!(
+        chain!(&random_row_coeffs, iter::once(&eq_row_coeffs)),
+        chain!(&proof.proximity_rows, iter::once(&proof.eval_row))
+    )
+    .all(|(coeffs, row)| codeword_check(srs, &query_indices, &columns, coeffs, row))
+}
+
+/// Open the polynomials of `evals_list`, committed to with `ligero_commit`, each at its point.
+///
+/// The trees over the columns are computed again from the evaluations, the commitment being
+/// deterministic. The proximity test combines the rows of all the polynomials at once, whose
+/// columns are then queried at the same positions.
+pub fn ligero_batch_open<F, EvalF>(
+    srs: &LigeroSRS<F>,
+    evals_list: &[impl AsRef<[F]>],
+    points: &[impl AsRef<[EvalF]>],
+    transcript: &mut impl Transcript,
+) -> (Vec<EvalF>, LigeroBatchProof<EvalF>)
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    assert_eq!(evals_list.len(), points.len());
+    evals_list
+        .iter()
+        .for_each(|evals| assert_eq!(evals.as_ref().len(), 1 << srs.num_global_vars()));
+    points
+        .iter()
+        .for_each(|point| assert_eq!(point.as_ref().len(), srs.num_global_vars()));
+
+    let num_row_vars = srs.num_global_vars() - srs.log_msg_len;
+    let num_poly_vars = evals_list.len().next_power_of_two().ilog2() as usize;
+
+    // NOTE: the rows of all the matrices, stacked, are combined at random
+    let random_row_coeffs = proximity_coeffs(srs, num_row_vars + num_poly_vars, transcript);
+    let proximity_rows: Vec<_> = random_row_coeffs
+        .iter()
+        .map(|coeffs| {
+            let mut row = vec![EvalF::ZERO; srs.message_len()];
+            izip!(evals_list, coeffs.chunks(srs.num_rows)).for_each(|(evals, coeffs)| {
+                let poly_row =
+                    MatrixView::new(evals.as_ref(), srs.log_msg_len).combine_rows(coeffs);
+                izip!(&mut row, &poly_row).for_each(|(r, p)| *r += p);
+            });
+            row
+        })
+        .collect();
+
+    let (evals, eval_rows): (Vec<EvalF>, Vec<Vec<EvalF>>) = izip!(evals_list, points)
+        .map(|(evals, point)| {
+            let (col_point, row_point) = point.as_ref().split_at(srs.log_msg_len);
+            let eq_row_coeffs = EqPolynomial::build_eq_x_r(row_point);
+            let eval_row =
+                MatrixView::new(evals.as_ref(), srs.log_msg_len).combine_rows(&eq_row_coeffs);
+            (
+                EqPolynomial::eq_weighted_sum(col_point, &eval_row),
+                eval_row,
+            )
+        })
+        .unzip();
+
+    let query_indices = query_indices(srs, chain!(&proximity_rows, &eval_rows), transcript);
+    let query_openings = evals_list
+        .iter()
+        .map(|evals| {
+            let tree = column_tree(srs, &encode_columns(srs, evals.as_ref()));
+            column_openings(srs, &tree, &query_indices)
+        })
+        .collect();
+
+    (
+        evals,
+        LigeroBatchProof {
+            eval_rows,
+            proximity_rows,
+            query_openings,
+        },
+    )
+}
+
+pub fn ligero_batch_verify<F, EvalF>(
+    srs: &LigeroSRS<F>,
+    commitments: &[impl AsRef<LigeroCommitment>],
+    points: &[impl AsRef<[EvalF]>],
+    evals: &[EvalF],
+    transcript: &mut impl Transcript,
+    proof: &LigeroBatchProof<EvalF>,
+) -> bool
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let num_polys = commitments.len();
+    if points.len() != num_polys
+        || evals.len() != num_polys
+        || proof.eval_rows.len() != num_polys
+        || proof.query_openings.len() != num_polys
+    {
+        return false;
+    }
+
+    let evals_match = izip!(points, evals, &proof.eval_rows).all(|(point, &eval, eval_row)| {
+        let point = point.as_ref();
+        point.len() == srs.num_global_vars()
+            && eval_row.len() == srs.message_len()
+            && EqPolynomial::eq_weighted_sum::<EvalF, EvalF>(&point[..srs.log_msg_len], eval_row)
+                == eval
+    });
+    if !evals_match {
+        return false;
+    }
+
+    let num_row_vars = srs.num_global_vars() - srs.log_msg_len;
+    let num_poly_vars = num_polys.next_power_of_two().ilog2() as usize;
+
+    let random_row_coeffs = proximity_coeffs(srs, num_row_vars + num_poly_vars, transcript);
+    if proof.proximity_rows.len() != random_row_coeffs.len() {
+        return false;
+    }
+
+    let query_indices = query_indices(
+        srs,
+        chain!(&proof.proximity_rows, &proof.eval_rows),
+        transcript,
+    );
+    let Some(columns_list) = izip!(commitments, &proof.query_openings)
+        .map(|(commitment, openings)| {
+            verify_column_openings(srs, commitment.as_ref(), &query_indices, openings)
+        })
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+
+    // NOTE: the queried columns of all the matrices, stacked, for the proximity test
+    let stacked_columns: Vec<Vec<F>> = (0..query_indices.len())
+        .map(|q| columns_list.iter().flat_map(|c| c[q].clone()).collect())
+        .collect();
+    let proximity_ok = izip!(&random_row_coeffs, &proof.proximity_rows)
+        .all(|(coeffs, row)| codeword_check(srs, &query_indices, &stacked_columns, coeffs, row));
+
+    proximity_ok
+        && izip!(points, &proof.eval_rows, &columns_list).all(|(point, eval_row, columns)| {
+            let eq_row_coeffs = EqPolynomial::build_eq_x_r(&point.as_ref()[srs.log_msg_len..]);
+            codeword_check(srs, &query_indices, columns, &eq_row_coeffs, eval_row)
+        })
+}
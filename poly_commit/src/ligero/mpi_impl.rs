@@ -0,0 +1,121 @@
+use std::iter;
+
+use arith::{ExtensionField, FFTField};
+use gkr_engine::{MPIEngine, MPIResult, Transcript};
+use itertools::chain;
+use polynomials::{EqPolynomial, MatrixView};
+
+use crate::ligero::{
+    utils::{column_openings, column_tree, encode_columns, proximity_coeffs, query_indices},
+    LigeroCommitment, LigeroProof, LigeroSRS, LigeroScratchPad,
+};
+use crate::traits::TensorCodeIOPPCS;
+
+/// Commit to the polynomial whose evaluations are spread over the processes, in rank order.
+///
+/// Each process encodes its own rows, and the root gathers the columns of the processes, the
+/// column of the global matrix being the columns of the processes one after another. The root
+/// returns the commitment, the other processes None.
+pub fn ligero_mpi_commit<F: FFTField>(
+    mpi_engine: &impl MPIEngine,
+    srs: &LigeroSRS<F>,
+    local_evals: &[F],
+    scratch_pad: &mut LigeroScratchPad,
+) -> MPIResult<Option<LigeroCommitment>> {
+    assert_eq!(local_evals.len(), 1 << srs.num_vars);
+
+    let local_columns = encode_columns(srs, local_evals);
+
+    let mut global_columns = if mpi_engine.is_root() {
+        vec![F::ZERO; local_columns.len() * mpi_engine.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_engine.gather_vec(&local_columns, &mut global_columns)?;
+
+    if !mpi_engine.is_root() {
+        return Ok(None);
+    }
+
+    // NOTE: the gathered columns are in rank order, rearrange them in column order
+    let rank_columns: Vec<_> = MatrixView::new(&global_columns, srs.num_vars - srs.log_msg_len)
+        .row_blocks(mpi_engine.world_size())
+        .collect();
+    let columns: Vec<F> = (0..srs.codeword_len())
+        .flat_map(|j| rank_columns.iter().flat_map(move |columns| columns.row(j)))
+        .copied()
+        .collect();
+
+    scratch_pad.column_tree = column_tree(srs, &columns);
+    Ok(LigeroCommitment(scratch_pad.column_tree.root()).into())
+}
+
+/// Open the polynomial committed to with `ligero_mpi_commit` at the point `point` of the local
+/// variables and `mpi_point` of the processes.
+///
+/// Each process combines its own rows, weighted by the eq polynomial of the processes, and the
+/// combinations are summed up on all the processes, which keeps the transcripts in step. The
+/// root opens the queried columns and returns the opening, the other processes None.
+pub fn ligero_mpi_open<F, EvalF>(
+    mpi_engine: &impl MPIEngine,
+    srs: &LigeroSRS<F>,
+    local_evals: &[F],
+    point: &[EvalF],
+    mpi_point: &[EvalF],
+    transcript: &mut impl Transcript,
+    scratch_pad: &LigeroScratchPad,
+) -> MPIResult<Option<LigeroProof<EvalF>>>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    assert_eq!(local_evals.len(), 1 << srs.num_vars);
+    assert_eq!(point.len(), srs.num_vars);
+
+    let local_row_point = &point[srs.log_msg_len..];
+    let rank = mpi_engine.world_rank();
+    let local_matrix = MatrixView::new(local_evals, srs.log_msg_len);
+
+    // NOTE: draw randomness over the global rows, and keep the share of the local rows
+    let random_row_coeffs: Vec<Vec<EvalF>> =
+        proximity_coeffs(srs, local_row_point.len() + mpi_point.len(), transcript);
+    let local_proximity_rows: Vec<_> = random_row_coeffs
+        .iter()
+        .map(|coeffs| {
+            let local_coeffs = &coeffs[rank * srs.num_local_rows()..][..srs.num_local_rows()];
+            local_matrix.combine_rows(local_coeffs)
+        })
+        .collect();
+
+    let local_eval_row = {
+        let mut coeffs = EqPolynomial::build_eq_x_r(local_row_point);
+        let mpi_weight = EqPolynomial::ith_eq_vec_elem(mpi_point, rank);
+        coeffs.iter_mut().for_each(|c| *c *= mpi_weight);
+        local_matrix.combine_rows(&coeffs)
+    };
+
+    // NOTE: MPI sum up local weighed rows, on every process
+    let eval_row = mpi_engine.allreduce_vec(&local_eval_row)?;
+    let proximity_rows: Vec<_> = local_proximity_rows
+        .iter()
+        .map(|r| mpi_engine.allreduce_vec(r))
+        .collect::<MPIResult<_>>()?;
+
+    let query_indices = query_indices(
+        srs,
+        chain!(&proximity_rows, iter::once(&eval_row)),
+        transcript,
+    );
+
+    if !mpi_engine.is_root() {
+        return Ok(None);
+    }
+
+    let query_openings = column_openings(srs, &scratch_pad.column_tree, &query_indices);
+    Ok(LigeroProof {
+        eval_row,
+        proximity_rows,
+        query_openings,
+    }
+    .into())
+}
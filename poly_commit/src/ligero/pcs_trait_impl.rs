@@ -0,0 +1,92 @@
+use std::marker::PhantomData;
+
+use arith::{ExtensionField, FFTField};
+use gkr_engine::{StructuredReferenceString, Transcript};
+use polynomials::MultiLinearPoly;
+
+use crate::{
+    ligero::{
+        ligero_commit, ligero_open, ligero_verify, LigeroCommitment, LigeroProof, LigeroSRS,
+        LigeroScratchPad,
+    },
+    PolynomialCommitmentScheme,
+};
+
+impl<F: FFTField> StructuredReferenceString for LigeroSRS<F> {
+    type PKey = LigeroSRS<F>;
+    type VKey = LigeroSRS<F>;
+
+    fn into_keys(self) -> (Self::PKey, Self::VKey) {
+        (self.clone(), self)
+    }
+}
+
+pub struct LigeroPCS<F, EvalF>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    _marker_f: PhantomData<F>,
+    _marker_eval_f: PhantomData<EvalF>,
+}
+
+impl<F, EvalF> PolynomialCommitmentScheme<EvalF> for LigeroPCS<F, EvalF>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    const NAME: &'static str = "LigeroPCS";
+
+    type Params = usize;
+    type Poly = MultiLinearPoly<F>;
+    type EvalPoint = Vec<EvalF>;
+    type ScratchPad = LigeroScratchPad;
+
+    type SRS = LigeroSRS<F>;
+    type Commitment = LigeroCommitment;
+    type Opening = LigeroProof<EvalF>;
+
+    /// The code is public, the random source is left unused.
+    fn gen_srs_for_testing(params: &Self::Params, _rng: impl rand::RngCore) -> (Self::SRS, usize) {
+        (LigeroSRS::new(*params, 1), *params)
+    }
+
+    fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {
+        LigeroScratchPad::default()
+    }
+
+    fn commit(
+        params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> Self::Commitment {
+        assert_eq!(*params, proving_key.num_vars);
+        ligero_commit(proving_key, &poly.coeffs, scratch_pad)
+    }
+
+    fn open(
+        params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        x: &Self::EvalPoint,
+        scratch_pad: &Self::ScratchPad,
+        transcript: &mut impl Transcript,
+    ) -> (EvalF, Self::Opening) {
+        assert_eq!(*params, proving_key.num_vars);
+        ligero_open(proving_key, &poly.coeffs, x, transcript, scratch_pad)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &Self::EvalPoint,
+        v: EvalF,
+        opening: &Self::Opening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        assert_eq!(*params, verifying_key.num_vars);
+        ligero_verify(verifying_key, commitment, x, v, transcript, opening)
+    }
+}
@@ -0,0 +1,322 @@
+use arith::{ExtensionField, FFTField, FFTTwiddles, Field};
+use gkr_engine::Transcript;
+use itertools::izip;
+use polynomials::{EqPolynomial, MatrixView};
+use rayon::prelude::*;
+use serdes::ExpSerde;
+use tree::{Leaf, Node, RangePath, Tree, LEAF_BYTES};
+
+use crate::{traits::TensorCodeIOPPCS, PCS_SOUNDNESS_BITS};
+
+/// The codewords are `2^LIGERO_LOG_INV_RATE` times as long as the rows.
+pub const LIGERO_LOG_INV_RATE: usize = 2;
+
+/*
+ * RELEVANT TYPES SETUP
+ */
+
+/// The shape of the matrix of the evaluations and the Reed-Solomon code of its rows.
+///
+/// The code is public, there is no trapdoor: the SRS is only the shape of the polynomials and the
+/// twiddle factors of the FFTs.
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct LigeroSRS<F: FFTField> {
+    /// the number of variables of the local polynomial of each process, over the base field
+    pub num_vars: usize,
+    /// the number of rows of the global matrix, over all the processes
+    pub num_rows: usize,
+    /// the rows are `2^log_msg_len` long, and hold the lowest variables
+    pub log_msg_len: usize,
+    /// the twiddle factors of the FFTs of the length of the codewords
+    pub twiddles: FFTTwiddles<F>,
+}
+
+impl<F: FFTField> TensorCodeIOPPCS for LigeroSRS<F> {
+    fn message_len(&self) -> usize {
+        1 << self.log_msg_len
+    }
+
+    fn codeword_len(&self) -> usize {
+        1 << (self.log_msg_len + LIGERO_LOG_INV_RATE)
+    }
+
+    fn minimum_hamming_weight(&self) -> f64 {
+        1f64 - 1f64 / (1 << LIGERO_LOG_INV_RATE) as f64
+    }
+
+    fn num_leaves_per_mt_query(&self) -> usize {
+        (self.num_rows * F::SIZE)
+            .div_ceil(LEAF_BYTES)
+            .next_power_of_two()
+    }
+}
+
+impl<F: FFTField> LigeroSRS<F> {
+    /// The code of the polynomials over `num_vars` local variables on each of `world_size`
+    /// processes, with a matrix as square as possible such that each process holds whole rows.
+    ///
+    /// # Panics
+    /// If the codewords are longer than the largest subgroup of two-adic order of the field.
+    pub fn new(num_vars: usize, world_size: usize) -> Self {
+        let num_global_vars = num_vars + world_size.ilog2() as usize;
+        let log_msg_len = num_global_vars.div_ceil(2).min(num_vars);
+
+        let log_codeword_len = log_msg_len + LIGERO_LOG_INV_RATE;
+        assert!(log_codeword_len <= F::TWO_ADICITY);
+
+        Self {
+            num_vars,
+            num_rows: 1 << (num_global_vars - log_msg_len),
+            log_msg_len,
+            twiddles: FFTTwiddles::new(log_codeword_len),
+        }
+    }
+
+    /// The number of variables of the global polynomial, over all the processes
+    #[inline(always)]
+    pub fn num_global_vars(&self) -> usize {
+        self.log_msg_len + self.num_rows.ilog2() as usize
+    }
+
+    /// The number of rows of the matrix held by each process
+    #[inline(always)]
+    pub fn num_local_rows(&self) -> usize {
+        1 << (self.num_vars - self.log_msg_len)
+    }
+
+    /// Encode a row as the evaluations over the subgroup of the polynomial with the row as its
+    /// coefficients
+    #[inline(always)]
+    pub fn encode_in_place(&self, msg: &[F], codeword: &mut [F]) {
+        assert_eq!(msg.len(), self.message_len());
+        assert_eq!(codeword.len(), self.codeword_len());
+
+        codeword[..msg.len()].copy_from_slice(msg);
+        codeword[msg.len()..].fill(F::ZERO);
+        self.twiddles.fft_in_place(codeword);
+    }
+
+    /// Encode a row over an extension field, limb by limb, the code being linear over the base
+    /// field
+    pub fn encode_ext<EvalF>(&self, msg: &[EvalF]) -> Vec<EvalF>
+    where
+        EvalF: ExtensionField<BaseField = F>,
+    {
+        let limbs: Vec<Vec<F>> = msg.iter().map(|e| e.to_limbs()).collect();
+        let encoded_limbs: Vec<Vec<F>> = (0..EvalF::DEGREE)
+            .map(|i| {
+                let limb_msg: Vec<F> = limbs.iter().map(|l| l[i]).collect();
+                let mut codeword = vec![F::ZERO; self.codeword_len()];
+                self.encode_in_place(&limb_msg, &mut codeword);
+                codeword
+            })
+            .collect();
+
+        (0..self.codeword_len())
+            .map(|j| {
+                let limbs: Vec<F> = encoded_limbs.iter().map(|c| c[j]).collect();
+                EvalF::from_limbs(&limbs)
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ExpSerde)]
+pub struct LigeroCommitment(pub Node);
+
+/// See `HyraxCommitment` for why `AsRef` is implemented on the commitment itself.
+impl AsRef<LigeroCommitment> for LigeroCommitment {
+    fn as_ref(&self) -> &LigeroCommitment {
+        self
+    }
+}
+
+/// The Merkle tree over the columns of the encoded matrix, kept by the root between commit and
+/// open
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct LigeroScratchPad {
+    pub column_tree: Tree,
+}
+
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct LigeroProof<EvalF: Field> {
+    /// the rows combined by the eq polynomial of the row variables of the point
+    pub eval_row: Vec<EvalF>,
+    /// the rows combined at random, for the proximity test
+    pub proximity_rows: Vec<Vec<EvalF>>,
+    /// the queried columns, with their Merkle paths
+    pub query_openings: Vec<RangePath>,
+}
+
+/// The opening of several committed polynomials, each at its own point.
+///
+/// The proximity test runs once over the rows of all the matrices, and the columns of all the
+/// matrices are queried at the same positions.
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct LigeroBatchProof<EvalF: Field> {
+    /// for each polynomial, its rows combined by the eq polynomial of the row variables of its
+    /// point
+    pub eval_rows: Vec<Vec<EvalF>>,
+    /// the rows of all the polynomials combined at random, for the proximity test
+    pub proximity_rows: Vec<Vec<EvalF>>,
+    /// for each polynomial, its queried columns, with their Merkle paths
+    pub query_openings: Vec<Vec<RangePath>>,
+}
+
+/*
+ * COMMITMENT TO THE COLUMNS
+ */
+
+/// The codewords of the rows of `evals`, laid out by columns, i.e., with the entries of a column
+/// next to each other
+pub(crate) fn encode_columns<F: FFTField>(srs: &LigeroSRS<F>, evals: &[F]) -> Vec<F> {
+    let rows = MatrixView::new(evals, srs.log_msg_len);
+
+    let mut codewords = vec![F::ZERO; rows.num_rows() * srs.codeword_len()];
+    codewords
+        .par_chunks_mut(srs.codeword_len())
+        .zip(rows.par_rows())
+        .for_each(|(codeword, row)| srs.encode_in_place(row, codeword));
+
+    MatrixView::new(&codewords, srs.log_msg_len + LIGERO_LOG_INV_RATE).transpose()
+}
+
+/// The tree whose leaves hold the columns in order, each column taking the same number of leaves
+pub(crate) fn column_tree<F: FFTField>(srs: &LigeroSRS<F>, columns: &[F]) -> Tree {
+    let leaves_per_column = srs.num_leaves_per_mt_query();
+    let leaves = columns
+        .chunks(srs.num_rows)
+        .flat_map(|column| {
+            let mut bytes = Vec::with_capacity(leaves_per_column * LEAF_BYTES);
+            column
+                .iter()
+                .for_each(|e| e.serialize_into(&mut bytes).unwrap());
+            bytes.resize(leaves_per_column * LEAF_BYTES, 0);
+
+            bytes
+                .chunks(LEAF_BYTES)
+                .map(|data| Leaf::new(data.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Tree::new_with_leaves(leaves)
+}
+
+/// The column of `num_rows` entries in the leaves of an opening, or None if they do not hold one
+#[inline(always)]
+pub(crate) fn leaves_column<F: Field>(leaves: &[Leaf], num_rows: usize) -> Option<Vec<F>> {
+    let bytes: Vec<u8> = leaves.iter().flat_map(|leaf| leaf.data).collect();
+    let mut reader = bytes.as_slice();
+    (0..num_rows)
+        .map(|_| F::deserialize_from(&mut reader).ok())
+        .collect()
+}
+
+/*
+ * OPENING HELPERS, SHARED BY THE PROVER AND THE VERIFIER
+ */
+
+/// The coefficients of the rows in the random combinations of the proximity test, over
+/// `num_row_vars` row variables
+pub(crate) fn proximity_coeffs<F, EvalF>(
+    srs: &LigeroSRS<F>,
+    num_row_vars: usize,
+    transcript: &mut impl Transcript,
+) -> Vec<Vec<EvalF>>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    (0..srs.proximity_repetitions::<EvalF>(PCS_SOUNDNESS_BITS))
+        .map(|_| {
+            let rand = transcript.challenge_point::<EvalF>(num_row_vars);
+            EqPolynomial::build_eq_x_r(&rand)
+        })
+        .collect()
+}
+
+/// Absorb the combined rows, then draw the queried columns
+pub(crate) fn query_indices<'a, F, EvalF>(
+    srs: &LigeroSRS<F>,
+    rows: impl Iterator<Item = &'a Vec<EvalF>>,
+    transcript: &mut impl Transcript,
+) -> Vec<usize>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F> + 'a,
+{
+    rows.flatten()
+        .for_each(|e| transcript.append_field_element(e));
+
+    let query_num = srs.query_complexity(PCS_SOUNDNESS_BITS);
+    transcript.challenge_queries(srs.codeword_len(), query_num, false)
+}
+
+/// The Merkle paths to the queried columns
+pub(crate) fn column_openings<F: FFTField>(
+    srs: &LigeroSRS<F>,
+    tree: &Tree,
+    query_indices: &[usize],
+) -> Vec<RangePath> {
+    let leaves_per_column = srs.num_leaves_per_mt_query();
+    query_indices
+        .iter()
+        .map(|&index| {
+            let left = index * leaves_per_column;
+            tree.range_query(left, left + leaves_per_column - 1)
+        })
+        .collect()
+}
+
+/// The queried columns, or None if an opening is not the path from the commitment to the
+/// queried column
+pub(crate) fn verify_column_openings<F: FFTField>(
+    srs: &LigeroSRS<F>,
+    commitment: &LigeroCommitment,
+    query_indices: &[usize],
+    query_openings: &[RangePath],
+) -> Option<Vec<Vec<F>>> {
+    if query_openings.len() != query_indices.len() {
+        return None;
+    }
+
+    let leaves_per_column = srs.num_leaves_per_mt_query();
+    izip!(query_indices, query_openings)
+        .map(|(&index, path)| {
+            let is_column = path.leaves.len() == leaves_per_column
+                && path.left == index * leaves_per_column
+                && path.verify(&commitment.0);
+            if !is_column {
+                return None;
+            }
+            leaves_column(&path.leaves, srs.num_rows)
+        })
+        .collect()
+}
+
+/// Check that the row, encoded, agrees at the queried positions with the same combination of
+/// the queried columns
+pub(crate) fn codeword_check<F, EvalF>(
+    srs: &LigeroSRS<F>,
+    query_indices: &[usize],
+    columns: &[Vec<F>],
+    coeffs: &[EvalF],
+    row: &[EvalF],
+) -> bool
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    if row.len() != srs.message_len() {
+        return false;
+    }
+
+    let codeword = srs.encode_ext(row);
+    izip!(query_indices, columns).all(|(&index, column)| {
+        let combined: EvalF = izip!(coeffs, column)
+            .map(|(c, e)| c.mul_by_base_field(e))
+            .sum();
+        combined == codeword[index]
+    })
+}
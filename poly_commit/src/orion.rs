@@ -1,11 +1,12 @@
 mod utils;
+pub(crate) use utils::orion_eval_shape;
 pub use utils::{
     OrionCommitment, OrionPCSError, OrionProof, OrionResult, OrionSRS, OrionScratchPad,
     SubsetSumLUTs,
 };
 
 mod linear_code;
-pub use linear_code::{OrionCodeParameter, ORION_CODE_PARAMETER_INSTANCE};
+pub use linear_code::{OrionCodeParameter, OrionGraphDegrees, ORION_CODE_PARAMETER_INSTANCE};
 
 #[cfg(test)]
 mod linear_code_tests;
@@ -25,4 +26,9 @@ mod pcs_trait_impl;
 pub use pcs_trait_impl::{OrionBaseFieldPCS, OrionSIMDFieldPCS};
 
 mod expander_api;
-pub use expander_api::OrionPCSForGKR;
+pub use expander_api::{OrionPCSForGKR, OrionRankLocalPCSForGKR};
+
+mod data_availability;
+pub use data_availability::{
+    orion_da_commit, orion_da_sample, orion_da_setup, orion_da_verify, OrionDASamples,
+};
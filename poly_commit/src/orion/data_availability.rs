@@ -0,0 +1,215 @@
+//! Orion's column opening machinery as a standalone data availability commitment.
+//!
+//! The data is laid out as a matrix whose rows are encoded with the Orion expander code, and the
+//! columns of the encoded matrix are committed to by a Merkle tree, exactly as in an Orion
+//! commitment. A sampling round then checks both that the sampled columns are under the root, and
+//! that the committed matrix is close to a matrix of codewords, i.e., that the data can be
+//! recovered from a large enough subset of the columns: the prover sends random linear
+//! combinations of the data rows, which the verifier encodes and checks against the same linear
+//! combinations of the sampled columns.
+//!
+//! The linear combinations and the sampled columns are drawn from the transcript. A sampler that
+//! wants its own samples absorbs some fresh randomness into the transcript before a round.
+
+use arith::{ExtensionField, Field, SimdField};
+use gf2::GF2;
+use gkr_engine::Transcript;
+use polynomials::{EqPolynomial, RefMultiLinearPoly};
+use serdes::ExpSerde;
+
+use crate::{
+    orion::{
+        simd_field_impl::orion_commit_simd_field,
+        utils::{
+            lut_open_linear_combine, orion_mt_openings, orion_mt_verify, simd_open_linear_combine,
+        },
+        verify::{orion_codeword_check, orion_interleaved_alphabets},
+        OrionCommitment, OrionPCSError, OrionResult, OrionSRS, OrionScratchPad,
+        ORION_CODE_PARAMETER_INSTANCE,
+    },
+    traits::TensorCodeIOPPCS,
+    PCS_SOUNDNESS_BITS,
+};
+
+/// Response of the prover to a round of data availability sampling.
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct OrionDASamples<EvalF: Field> {
+    /// random linear combinations of the data rows
+    pub proximity_rows: Vec<Vec<EvalF>>,
+    /// the sampled columns of the encoded data, with their Merkle paths
+    pub query_openings: Vec<tree::RangePath>,
+}
+
+/// Sample the SRS for committing to up to `num_elems` data elements.
+pub fn orion_da_setup<F, ComPackF>(num_elems: usize, rng: impl rand::RngCore) -> OrionSRS
+where
+    F: Field,
+    ComPackF: SimdField<Scalar = F>,
+{
+    let num_vars = num_elems.next_power_of_two().ilog2() as usize;
+    let (srs, _) = OrionSRS::from_random(
+        1,
+        num_vars,
+        F::FIELD_SIZE,
+        ComPackF::PACK_SIZE,
+        ORION_CODE_PARAMETER_INSTANCE,
+        rng,
+    );
+    srs
+}
+
+/// Commit to `data`, padded with zeros to the size of the SRS.
+pub fn orion_da_commit<F, SimdF, ComPackF>(
+    pk: &OrionSRS,
+    data: &[F],
+    scratch_pad: &mut OrionScratchPad,
+) -> OrionResult<OrionCommitment>
+where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+    ComPackF: SimdField<Scalar = F>,
+{
+    let packed_data = pack_padded_data::<F, SimdF>(pk, data)?;
+    orion_commit_simd_field::<_, SimdF, ComPackF>(
+        pk,
+        &RefMultiLinearPoly::from_ref(&packed_data),
+        scratch_pad,
+    )
+}
+
+/// Respond to a round of sampling on the data committed to with `orion_da_commit`.
+pub fn orion_da_sample<F, SimdF, EvalF, ComPackF>(
+    pk: &OrionSRS,
+    data: &[F],
+    transcript: &mut impl Transcript,
+    scratch_pad: &OrionScratchPad,
+) -> OrionResult<OrionDASamples<EvalF>>
+where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+    EvalF: ExtensionField<BaseField = F>,
+    ComPackF: SimdField<Scalar = F>,
+{
+    let packed_data = pack_padded_data::<F, SimdF>(pk, data)?;
+
+    let random_col_coeffs = draw_row_combinations::<EvalF>(pk, transcript);
+    let mut proximity_rows = vec![vec![EvalF::ZERO; pk.message_len()]; random_col_coeffs.len()];
+
+    // NOTE: the linear combination routines take the evaluation row of a PCS opening apart,
+    // a sampling round has proximity rows only, the first of which takes its place
+    let (first_row, other_rows) = proximity_rows.split_first_mut().unwrap();
+    // NOTE: the look up tables are indexed by the committed bits, the oblivious build sums
+    // the columns with the SIMD inner products instead
+    match F::NAME {
+        GF2::NAME if !cfg!(feature = "oblivious") => lut_open_linear_combine(
+            ComPackF::PACK_SIZE,
+            &packed_data,
+            &random_col_coeffs[0],
+            first_row,
+            &random_col_coeffs[1..],
+            other_rows,
+        ),
+        _ => simd_open_linear_combine(
+            ComPackF::PACK_SIZE,
+            &packed_data,
+            &random_col_coeffs[0],
+            first_row,
+            &random_col_coeffs[1..],
+            other_rows,
+        ),
+    }
+
+    // NOTE: the sampled columns are only drawn once the rows are fixed
+    proximity_rows
+        .iter()
+        .flatten()
+        .for_each(|e| transcript.append_field_element(e));
+    let query_openings = orion_mt_openings(pk, transcript, scratch_pad);
+
+    Ok(OrionDASamples {
+        proximity_rows,
+        query_openings,
+    })
+}
+
+/// Verify a round of sampling against the commitment root.
+pub fn orion_da_verify<F, SimdF, EvalF>(
+    vk: &OrionSRS,
+    commitment: &OrionCommitment,
+    transcript: &mut impl Transcript,
+    samples: &OrionDASamples<EvalF>,
+) -> bool
+where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let random_col_coeffs = draw_row_combinations::<EvalF>(vk, transcript);
+    if samples.proximity_rows.len() != random_col_coeffs.len()
+        || samples
+            .proximity_rows
+            .iter()
+            .any(|row| row.len() != vk.message_len())
+    {
+        return false;
+    }
+
+    samples
+        .proximity_rows
+        .iter()
+        .flatten()
+        .for_each(|e| transcript.append_field_element(e));
+    let query_num = vk.query_complexity(PCS_SOUNDNESS_BITS);
+    let query_indices = transcript.challenge_queries(vk.codeword_len(), query_num, false);
+    if samples.query_openings.len() != query_num {
+        return false;
+    }
+
+    if !orion_mt_verify(vk, &query_indices, &samples.query_openings, &[*commitment]) {
+        return false;
+    }
+
+    let Some(packed_interleaved_alphabets) =
+        orion_interleaved_alphabets::<SimdF>(vk, &samples.query_openings)
+    else {
+        return false;
+    };
+
+    orion_codeword_check::<F, SimdF, EvalF>(
+        vk,
+        &query_indices,
+        &packed_interleaved_alphabets,
+        random_col_coeffs.iter().zip(&samples.proximity_rows),
+    )
+}
+
+#[inline(always)]
+fn pack_padded_data<F, SimdF>(pk: &OrionSRS, data: &[F]) -> OrionResult<Vec<SimdF>>
+where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+{
+    let padded_len = 1 << pk.num_vars;
+    if data.len() > padded_len || padded_len % SimdF::PACK_SIZE != 0 {
+        return Err(OrionPCSError::ParameterUnmatchError);
+    }
+
+    let mut padded_data = data.to_vec();
+    padded_data.resize(padded_len, F::ZERO);
+    Ok(padded_data
+        .chunks(SimdF::PACK_SIZE)
+        .map(SimdF::pack)
+        .collect())
+}
+
+// The coefficients of the random linear combinations of the rows of the data matrix
+#[inline(always)]
+fn draw_row_combinations<EvalF: Field>(
+    pk: &OrionSRS,
+    transcript: &mut impl Transcript,
+) -> Vec<Vec<EvalF>> {
+    let num_vars = pk.num_vars - pk.message_len().ilog2() as usize;
+    (0..pk.proximity_repetitions::<EvalF>(PCS_SOUNDNESS_BITS))
+        .map(|_| EqPolynomial::build_eq_x_r(&transcript.challenge_point::<EvalF>(num_vars)))
+        .collect()
+}
@@ -1,7 +1,7 @@
 use arith::{Field, SimdField};
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
-    StructuredReferenceString, Transcript,
+    CommitLayout, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
 };
 use polynomials::MultilinearExtension;
 
@@ -10,7 +10,7 @@ use crate::{
         simd_field_impl::{orion_commit_simd_field, orion_open_simd_field},
         simd_field_mpi_impl::{orion_mpi_commit_simd_field, orion_mpi_open_simd_field},
         verify::orion_verify,
-        OrionCommitment, OrionProof, OrionSIMDFieldPCS, OrionSRS, OrionScratchPad,
+        OrionCommitment, OrionPCSError, OrionProof, OrionSIMDFieldPCS, OrionSRS, OrionScratchPad,
         ORION_CODE_PARAMETER_INSTANCE,
     },
     utils::{
@@ -21,16 +21,34 @@ use crate::{
 
 use super::utils::orion_eval_shape;
 
-impl<C, ComPackF> ExpanderPCS<C>
-    for OrionSIMDFieldPCS<C::CircuitField, C::SimdCircuitField, C::ChallengeField, ComPackF>
+impl<C, ComPackF, const RANK_LOCAL: bool> ExpanderPCS<C>
+    for OrionSIMDFieldPCS<
+        C::CircuitField,
+        C::SimdCircuitField,
+        C::ChallengeField,
+        ComPackF,
+        RANK_LOCAL,
+    >
 where
     C: FieldEngine,
     ComPackF: SimdField<Scalar = C::CircuitField>,
 {
-    const NAME: &'static str = "OrionPCSForExpanderGKR";
+    const NAME: &'static str = if RANK_LOCAL {
+        "OrionRankLocalPCSForExpanderGKR"
+    } else {
+        "OrionPCSForExpanderGKR"
+    };
 
     const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::Orion;
 
+    const COMMIT_LAYOUT: CommitLayout = if RANK_LOCAL {
+        CommitLayout::RankLocal
+    } else {
+        CommitLayout::Global
+    };
+
+    const POWER_OF_TWO_WORLD: bool = true;
+
     type Params = usize;
     type ScratchPad = OrionScratchPad;
 
@@ -41,6 +59,7 @@ where
 
     /// NOTE(HS): this is the number of variables for local polynomial w.r.t. SIMD field elements.
     fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params {
+        Self::check_world_size(world_size).unwrap_or_else(|e| panic!("{e}"));
         let num_vars_each_core = n_input_vars + C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
         let (_num_leaves_per_mt_query, scaled_num_local_vars, _msg_size) = orion_eval_shape(
             world_size,
@@ -80,7 +99,7 @@ where
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
         poly: &impl MultilinearExtension<C::SimdCircuitField>,
         scratch_pad: &mut Self::ScratchPad,
-    ) -> Option<Self::Commitment> {
+    ) -> MPIResult<Option<Self::Commitment>> {
         if poly.num_vars() < *params {
             let poly = lift_poly_to_n_vars(poly, *params);
             return <Self as ExpanderPCS<C>>::commit(
@@ -96,21 +115,25 @@ where
         assert_eq!(num_vars_each_core, proving_key.num_vars);
 
         if mpi_engine.is_single_process() {
-            return orion_commit_simd_field::<_, C::SimdCircuitField, ComPackF>(
+            return Ok(orion_commit_simd_field::<_, C::SimdCircuitField, ComPackF>(
                 proving_key,
                 poly,
                 scratch_pad,
             )
-            .ok();
+            .ok());
         }
 
-        orion_mpi_commit_simd_field::<_, C::SimdCircuitField, ComPackF>(
+        // the failures to communicate are reported, the other ones leave no commitment as before
+        match orion_mpi_commit_simd_field::<_, C::SimdCircuitField, ComPackF>(
             mpi_engine,
             proving_key,
             poly,
             scratch_pad,
-        )
-        .ok()
+            <Self as ExpanderPCS<C>>::COMMIT_LAYOUT,
+        ) {
+            Err(OrionPCSError::MPIError(e)) => Err(e),
+            commitment => Ok(commitment.ok()),
+        }
     }
 
     fn open(
@@ -121,7 +144,7 @@ where
         eval_point: &ExpanderSingleVarChallenge<C>,
         transcript: &mut impl Transcript,
         scratch_pad: &Self::ScratchPad,
-    ) -> Option<Self::Opening> {
+    ) -> MPIResult<Option<Self::Opening>> {
         if poly.num_vars() < *params {
             let (poly, eval_point) =
                 lift_poly_and_expander_challenge_to_n_vars(poly, eval_point, *params);
@@ -147,7 +170,7 @@ where
                 transcript,
                 scratch_pad,
             );
-            return opening.into();
+            return Ok(opening.into());
         }
 
         orion_mpi_open_simd_field::<_, C::SimdCircuitField, _, ComPackF>(
@@ -158,6 +181,7 @@ where
             &eval_point.r_mpi,
             transcript,
             scratch_pad,
+            <Self as ExpanderPCS<C>>::COMMIT_LAYOUT,
         )
     }
 
@@ -192,6 +216,7 @@ where
             eval,
             transcript,
             opening,
+            <Self as ExpanderPCS<C>>::COMMIT_LAYOUT,
         )
     }
 }
@@ -202,3 +227,14 @@ pub type OrionPCSForGKR<C, ComPack> = OrionSIMDFieldPCS<
     <C as FieldEngine>::ChallengeField,
     ComPack,
 >;
+
+/// Orion committing in the `CommitLayout::RankLocal` layout: each process encodes and commits to
+/// its own share, with no all-to-all transpose of the codewords, and the opening gathers the
+/// shares of the queried columns, at the cost of an MT path per process in each query.
+pub type OrionRankLocalPCSForGKR<C, ComPack> = OrionSIMDFieldPCS<
+    <C as FieldEngine>::CircuitField,
+    <C as FieldEngine>::SimdCircuitField,
+    <C as FieldEngine>::ChallengeField,
+    ComPack,
+    true,
+>;
@@ -82,7 +82,6 @@ pub struct OrionCodeParameter {
     // parameter for graph g0, that maps n -> (\alpha_g0 n)
     // \alpha_g0 should be ranging in (0, 1)
     pub alpha_g0: f64,
-    pub degree_g0: usize,
 
     // parameter regarding graph generation for the code:
     // stopping condition when message is too short for the recursive code
@@ -92,21 +91,80 @@ pub struct OrionCodeParameter {
     // parameter for graph g1, let the message in the middle has length L,
     // then the graph g1 maps L -> (\alpha_g1 L)
     pub alpha_g1: f64,
-    pub degree_g1: usize,
+
+    // degrees of the graphs g0 and g1
+    pub degrees: OrionGraphDegrees,
 
     // code's relateive distance
     pub hamming_weight: f64,
 }
 
+/// The degrees of the expander graphs of a code, i.e., the number of outputs each input of a
+/// graph is added to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrionGraphDegrees {
+    /// The same degrees for the graphs of all the message lengths
+    Fixed { g0: usize, g1: usize },
+
+    /// The degrees c_n of g0 and d_n of g1 of Brakedown (GLSTW21) Figure 2, for the graphs of
+    /// the recursion level encoding a message of length n, with `alpha_g0` as alpha
+    Brakedown { beta: f64, rate: f64 },
+}
+
+impl OrionCodeParameter {
+    /// The degrees of the graphs g0 and g1 of the recursion level encoding a message of length
+    /// `msg_len`, before they are capped by the output lengths of the graphs
+    pub fn degrees(&self, msg_len: usize) -> (usize, usize) {
+        match self.degrees {
+            OrionGraphDegrees::Fixed { g0, g1 } => (g0, g1),
+            OrionGraphDegrees::Brakedown { beta, rate } => {
+                brakedown_degrees(self.alpha_g0, beta, rate, msg_len)
+            }
+        }
+    }
+}
+
+/// The binary entropy of `x` in (0, 1)
+fn binary_entropy(x: f64) -> f64 {
+    -x * x.log2() - (1f64 - x) * (1f64 - x).log2()
+}
+
+/// c_n and d_n of Brakedown (GLSTW21) Figure 2 for a message of length n:
+///
+/// c_n = ceil(min(max(1.28 beta n, beta n + 4),
+///                (110 / n + H(beta) + alpha H(1.28 beta / alpha)) / (beta log2(e / beta))))
+/// d_n = ceil((r alpha H(beta / r) + mu H(nu / mu) + 110 / n) / (alpha beta log2(mu / nu)))
+///
+/// with mu = r - 1 - r alpha and nu = beta + alpha beta + 0.03. The other bound of d_n of the
+/// figure, (2 beta + (r - 1 + 110 / n) / log2(q)) n, depends on the size q of the field, which the
+/// code is not specific to, and is left out, the degree being then no smaller.
+fn brakedown_degrees(alpha: f64, beta: f64, rate: f64, n: usize) -> (usize, usize) {
+    let n = n as f64;
+    let mu = rate - 1f64 - rate * alpha;
+    let nu = beta + alpha * beta + 0.03;
+    assert!(0f64 < nu && nu < mu);
+
+    let c_n = f64::min(
+        f64::max(1.28 * beta * n, beta * n + 4f64),
+        (110f64 / n + binary_entropy(beta) + alpha * binary_entropy(1.28 * beta / alpha))
+            / (beta * (std::f64::consts::E / beta).log2()),
+    );
+    let d_n =
+        (rate * alpha * binary_entropy(beta / rate) + mu * binary_entropy(nu / mu) + 110f64 / n)
+            / (alpha * beta * (mu / nu).log2());
+
+    (c_n.ceil() as usize, d_n.ceil() as usize)
+}
+
 // NOTE: This instance of code derives from Orion paper Section 5.
 pub const ORION_CODE_PARAMETER_INSTANCE: OrionCodeParameter = OrionCodeParameter {
     alpha_g0: 0.33,
-    degree_g0: 6,
 
     length_threshold_g0s: 12,
 
     alpha_g1: 0.337,
-    degree_g1: 6,
+
+    degrees: OrionGraphDegrees::Fixed { g0: 6, g1: 6 },
 
     hamming_weight: 0.055,
 };
@@ -194,12 +252,14 @@ impl OrionCode {
         // as was part of Druk-Ishai-14 distance proof by induction
         assert!(1f64 / (params.length_threshold_g0s as f64) > params.hamming_weight);
 
-        // NOTE: sanity check for both alpha_g0 and alpha_g1
+        // NOTE: sanity check for both alpha_g0 and alpha_g1, g1 may expand the codeword of the
+        // recursion into a longer one, as the higher rate codes of Brakedown do
         assert!(0f64 < params.alpha_g0 && params.alpha_g0 < 1f64);
-        assert!(0f64 < params.alpha_g1 && params.alpha_g1 < 1f64);
+        assert!(0f64 < params.alpha_g1);
 
         // NOTE: the real deal of code instance generation starts here
-        let mut recursive_g0_output_starts: Vec<usize> = Vec::new();
+        // (output starts of g0, message length) of each recursion level
+        let mut recursive_g0_output_starts: Vec<(usize, usize)> = Vec::new();
 
         let mut g0s: Vec<OrionExpanderGraphPositioned> = Vec::new();
         let mut g1s: Vec<OrionExpanderGraphPositioned> = Vec::new();
@@ -210,7 +270,7 @@ impl OrionCode {
         while g0_output_starts - g0_input_starts > params.length_threshold_g0s {
             let n = g0_output_starts - g0_input_starts;
             let g0_output_len = (n as f64 * params.alpha_g0).round() as usize;
-            let degree_g0 = cmp::min(params.degree_g0, g0_output_len);
+            let degree_g0 = cmp::min(params.degrees(n).0, g0_output_len);
 
             g0s.push(OrionExpanderGraphPositioned::new(
                 g0_input_starts,
@@ -220,7 +280,7 @@ impl OrionCode {
                 &mut rng,
             ));
 
-            recursive_g0_output_starts.push(g0_output_starts);
+            recursive_g0_output_starts.push((g0_output_starts, n));
 
             (g0_input_starts, g0_output_starts) =
                 (g0_output_starts, g0_output_starts + g0_output_len);
@@ -229,10 +289,10 @@ impl OrionCode {
         // After g0s are generated, we generate g1s
         let mut g1_output_starts = g0_output_starts;
 
-        while let Some(g1_input_starts) = recursive_g0_output_starts.pop() {
+        while let Some((g1_input_starts, level_msg_len)) = recursive_g0_output_starts.pop() {
             let n = g1_output_starts - g1_input_starts;
             let g1_output_len = (n as f64 * params.alpha_g1).round() as usize;
-            let degree_g1 = cmp::min(params.degree_g1, g1_output_len);
+            let degree_g1 = cmp::min(params.degrees(level_msg_len).1, g1_output_len);
 
             g1s.push(OrionExpanderGraphPositioned::new(
                 g1_input_starts,
@@ -1,11 +1,11 @@
 use std::collections::VecDeque;
 
 use arith::SimdField;
-use gkr_engine::{MPIEngine, Transcript};
+use gkr_engine::{MPIEngine, MPIResult, Transcript};
 use itertools::izip;
 use serdes::ExpSerde;
 use transpose::transpose_inplace;
-use tree::{RangePath, Tree};
+use tree::{RangePath, Tree, LEAF_BYTES};
 
 use crate::{
     orion::{OrionCommitment, OrionResult, OrionSRS, OrionScratchPad},
@@ -103,13 +103,10 @@ eventually, a final transpose each row lead to results of ordering by *WHOLE* in
 After all these, we can go onwards to MT commitment, and later open alphabets lies in one of the parties.
  */
 
+/// Encode the rows of the packed evaluations of this process, and transpose the codewords s.t.
+/// the columns of the codewords, i.e., the local interleaved alphabets, lie one after the other.
 #[inline(always)]
-pub(crate) fn mpi_commit_encoded<PackF>(
-    mpi_engine: &impl MPIEngine,
-    pk: &OrionSRS,
-    packed_evals: &[PackF],
-    scratch_pad: &mut OrionScratchPad,
-) -> OrionResult<OrionCommitment>
+fn encode_interleaved<PackF>(pk: &OrionSRS, packed_evals: &[PackF]) -> OrionResult<Vec<PackF>>
 where
     PackF: SimdField,
 {
@@ -130,6 +127,54 @@ where
         drop(scratch)
     }
 
+    Ok(codewords)
+}
+
+/// Gather the roots of the local trees of the processes into the merkle cap, and return the MT
+/// root over the merkle cap as the commitment.
+#[inline(always)]
+fn gather_merkle_cap(
+    mpi_engine: &impl MPIEngine,
+    scratch_pad: &mut OrionScratchPad,
+) -> OrionResult<OrionCommitment> {
+    let local_commitment = scratch_pad.interleaved_alphabet_commitment.root();
+    let mut leaves = vec![tree::Node::default(); mpi_engine.world_size()];
+    mpi_engine.gather_vec(&[local_commitment], &mut leaves)?;
+
+    {
+        let mut leaves_bytes: Vec<u8> = Vec::new();
+        leaves.serialize_into(&mut leaves_bytes)?;
+        mpi_engine.root_broadcast_bytes(&mut leaves_bytes)?;
+
+        if !mpi_engine.is_root() {
+            leaves = Vec::deserialize_from(leaves_bytes.as_slice())?;
+        }
+    }
+
+    scratch_pad.merkle_cap = leaves.clone();
+
+    let root = {
+        let height = 1 + leaves.len().ilog2();
+        let internal = tree::Tree::new_with_leaf_nodes(&leaves, height);
+        internal[0]
+    };
+
+    Ok(root)
+}
+
+#[inline(always)]
+pub(crate) fn mpi_commit_encoded<PackF>(
+    mpi_engine: &impl MPIEngine,
+    pk: &OrionSRS,
+    packed_evals: &[PackF],
+    scratch_pad: &mut OrionScratchPad,
+) -> OrionResult<OrionCommitment>
+where
+    PackF: SimdField,
+{
+    let packed_rows = pk.local_num_fs_per_query() / PackF::PACK_SIZE;
+    let mut codewords = encode_interleaved(pk, packed_evals)?;
+
     // NOTE: commit the interleaved codeword
     // we just directly commit to the packed field elements to leaves
     // Also note, when codeword is not power of 2 length, pad to nearest po2
@@ -140,7 +185,7 @@ where
     }
 
     // NOTE: ALL-TO-ALL transpose go get other world's slice of codeword
-    mpi_engine.all_to_all_transpose(&mut codewords);
+    mpi_engine.all_to_all_transpose(&mut codewords)?;
 
     let codeword_po2_len = pk.codeword_len().next_power_of_two();
     let codeword_this_world_len = packed_rows * codeword_po2_len;
@@ -175,29 +220,72 @@ where
         Tree::compact_new_with_packed_field_elems(codewords);
 
     // NOTE: gather local roots and compute the final MT root
-    let local_commitment = scratch_pad.interleaved_alphabet_commitment.root();
-    let mut leaves = vec![tree::Node::default(); mpi_engine.world_size()];
-    mpi_engine.gather_vec(&[local_commitment], &mut leaves);
+    gather_merkle_cap(mpi_engine, scratch_pad)
+}
 
-    {
-        let mut leaves_bytes: Vec<u8> = Vec::new();
-        leaves.serialize_into(&mut leaves_bytes)?;
-        mpi_engine.root_broadcast_bytes(&mut leaves_bytes);
+/*
+In the rank local layout, each process stops after the local transpose above, and commits to
+its own interleaved codeword over the whole codeword length:
 
-        if !mpi_engine.is_root() {
-            leaves = Vec::deserialize_from(leaves_bytes.as_slice())?;
-        }
-    }
+p(0):     * * * * * * * * * *  ....  *        -> local MT over all the query indices
+          |/|/|/|/|/|/|/|/|/|  .... /|
+          * * * * * * * * * *  ....  *
 
-    scratch_pad.merkle_cap = leaves.clone();
+...
 
-    let root = {
-        let height = 1 + leaves.len().ilog2();
-        let internal = tree::Tree::new_with_leaf_nodes(&leaves, height);
-        internal[0]
+p(n - 1): * * * * * * * * * *  ....  *        -> local MT over all the query indices
+          |/|/|/|/|/|/|/|/|/|  .... /|
+          * * * * * * * * * *  ....  *
+
+The whole interleaved alphabet of a query index is the concatenation of the local ones of all
+the processes, which the opening gathers, rather than an all-to-all exchange of the codewords.
+Each local alphabet is padded to whole leaves, s.t. the local MTs can open it on its own.
+ */
+
+#[inline(always)]
+pub(crate) fn mpi_rank_local_commit_encoded<PackF>(
+    mpi_engine: &impl MPIEngine,
+    pk: &OrionSRS,
+    packed_evals: &[PackF],
+    scratch_pad: &mut OrionScratchPad,
+) -> OrionResult<OrionCommitment>
+where
+    PackF: SimdField,
+{
+    let packed_rows = pk.local_num_fs_per_query() / PackF::PACK_SIZE;
+    assert_eq!(
+        packed_rows * PackF::SIZE * mpi_engine.world_size(),
+        pk.num_leaves_per_mt_query() * LEAF_BYTES
+    );
+    let codewords = encode_interleaved(pk, packed_evals)?;
+
+    // NOTE: pad each local interleaved alphabet to whole leaves
+    let num_leaves_per_opening = pk.num_leaves_per_local_query(mpi_engine.world_size());
+    let padded_alphabet_len = num_leaves_per_opening * LEAF_BYTES / PackF::SIZE;
+    let mut codewords = if padded_alphabet_len > packed_rows {
+        let mut padded = vec![PackF::ZERO; pk.codeword_len() * padded_alphabet_len];
+        izip!(
+            codewords.chunks(packed_rows),
+            padded.chunks_mut(padded_alphabet_len)
+        )
+        .for_each(|(alphabet, padded_alphabet)| {
+            padded_alphabet[..packed_rows].copy_from_slice(alphabet)
+        });
+        padded
+    } else {
+        codewords
     };
 
-    Ok(root)
+    // NOTE: pad the interleaved codeword to po2 length to commit by merkle tree
+    if !codewords.len().is_power_of_two() {
+        let aligned_po2_len = codewords.len().next_power_of_two();
+        codewords.resize(aligned_po2_len, PackF::ZERO);
+    }
+
+    scratch_pad.interleaved_alphabet_commitment =
+        Tree::compact_new_with_packed_field_elems(codewords);
+
+    gather_merkle_cap(mpi_engine, scratch_pad)
 }
 
 #[inline(always)]
@@ -206,7 +294,7 @@ pub(crate) fn orion_mpi_mt_openings<T>(
     pk: &OrionSRS,
     scratch_pad: &OrionScratchPad,
     transcript: &mut T,
-) -> Option<Vec<RangePath>>
+) -> MPIResult<Option<Vec<RangePath>>>
 where
     T: Transcript,
 {
@@ -214,11 +302,7 @@ where
 
     // NOTE: MT opening for point queries
     let query_num = pk.query_complexity(PCS_SOUNDNESS_BITS);
-    let query_indices: Vec<usize> = {
-        let mut indices = transcript.generate_usize_vector(query_num);
-        indices.iter_mut().for_each(|q| *q %= pk.codeword_len());
-        indices
-    };
+    let query_indices = transcript.challenge_queries(pk.codeword_len(), query_num, false);
 
     let index_range_per_world = pk.codeword_len().next_power_of_two() / mpi_engine.world_size();
     let index_starts_this_world = index_range_per_world * mpi_engine.world_rank();
@@ -236,10 +320,10 @@ where
         .collect();
 
     let mut global_paths: Vec<Vec<RangePath>> = Vec::new();
-    mpi_engine.gather_varlen_vec(&local_paths, &mut global_paths);
+    mpi_engine.gather_varlen_vec(&local_paths, &mut global_paths)?;
 
     if !mpi_engine.is_root() {
-        return None;
+        return Ok(None);
     }
 
     let mut global_paths_deque: Vec<VecDeque<RangePath>> =
@@ -253,5 +337,55 @@ where
         })
         .collect();
 
-    flattened_paths.into()
+    Ok(flattened_paths.into())
+}
+
+/// The MT openings of `CommitLayout::RankLocal`: each process opens its local alphabet at every
+/// query index, and the openings are ordered by query index, then by rank.
+#[inline(always)]
+pub(crate) fn orion_mpi_rank_local_mt_openings<T>(
+    mpi_engine: &impl MPIEngine,
+    pk: &OrionSRS,
+    scratch_pad: &OrionScratchPad,
+    transcript: &mut T,
+) -> MPIResult<Option<Vec<RangePath>>>
+where
+    T: Transcript,
+{
+    let num_leaves_per_opening = pk.num_leaves_per_local_query(mpi_engine.world_size());
+
+    // NOTE: MT opening for point queries
+    let query_num = pk.query_complexity(PCS_SOUNDNESS_BITS);
+    let query_indices = transcript.challenge_queries(pk.codeword_len(), query_num, false);
+
+    let local_paths: Vec<RangePath> = query_indices
+        .iter()
+        .map(|index| {
+            let left = index * num_leaves_per_opening;
+            scratch_pad
+                .interleaved_alphabet_commitment
+                .range_query(left, left + num_leaves_per_opening - 1)
+        })
+        .collect();
+
+    let mut global_paths: Vec<Vec<RangePath>> = Vec::new();
+    mpi_engine.gather_varlen_vec(&local_paths, &mut global_paths)?;
+
+    if !mpi_engine.is_root() {
+        return Ok(None);
+    }
+
+    let mut global_paths_iters: Vec<_> = global_paths.into_iter().map(Vec::into_iter).collect();
+
+    let flattened_paths: Vec<RangePath> = query_indices
+        .iter()
+        .flat_map(|_| {
+            global_paths_iters
+                .iter_mut()
+                .map(|paths| paths.next().unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Ok(flattened_paths.into())
 }
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use arith::{ExtensionField, Field, SimdField};
-use gkr_engine::{StructuredReferenceString, Transcript};
+use gkr_engine::{CommitLayout, StructuredReferenceString, Transcript};
 use polynomials::{MultiLinearPoly, MultilinearExtension, RefMultiLinearPoly};
 
 use crate::{
@@ -127,11 +127,24 @@ where
         transcript: &mut impl Transcript,
     ) -> bool {
         assert_eq!(*params, vk.num_vars);
-        orion_verify::<_, OpenPackF, _, ComPackF>(vk, commitment, x, &[], v, transcript, opening)
+        orion_verify::<_, OpenPackF, _, ComPackF>(
+            vk,
+            commitment,
+            x,
+            &[],
+            v,
+            transcript,
+            opening,
+            CommitLayout::Global,
+        )
     }
 }
 
-pub struct OrionSIMDFieldPCS<F, SimdF, EvalF, ComPackF>
+/// Orion over the SIMD field `SimdF`, committing with `ComPackF`.
+///
+/// With `RANK_LOCAL`, the distributed commitment of `ExpanderPCS` is in the
+/// `CommitLayout::RankLocal` layout, see `OrionRankLocalPCSForGKR`.
+pub struct OrionSIMDFieldPCS<F, SimdF, EvalF, ComPackF, const RANK_LOCAL: bool = false>
 where
     F: Field,
     SimdF: SimdField<Scalar = F>,
@@ -228,6 +241,15 @@ where
     ) -> bool {
         assert_eq!(*params, vk.num_vars);
         assert_eq!(x.len(), vk.num_vars);
-        orion_verify::<_, SimdF, _, ComPackF>(vk, commitment, x, &[], v, transcript, opening)
+        orion_verify::<_, SimdF, _, ComPackF>(
+            vk,
+            commitment,
+            x,
+            &[],
+            v,
+            transcript,
+            opening,
+            CommitLayout::Global,
+        )
     }
 }
@@ -73,13 +73,15 @@ where
 
     let random_col_coeffs: Vec<_> = (0..proximity_test_num)
         .map(|_| {
-            let rand = transcript.generate_field_elements::<EvalF>(point.len() - num_vars_in_msg);
+            let rand = transcript.challenge_point::<EvalF>(point.len() - num_vars_in_msg);
             EqPolynomial::build_eq_x_r(&rand)
         })
         .collect();
 
+    // NOTE: the look up tables are indexed by the committed bits, the oblivious build sums
+    // the columns with the SIMD inner products instead
     match F::NAME {
-        GF2::NAME => lut_open_linear_combine(
+        GF2::NAME if !cfg!(feature = "oblivious") => lut_open_linear_combine(
             ComPackF::PACK_SIZE,
             poly.hypercube_basis_ref(),
             &eq_col_coeffs,
@@ -1,11 +1,14 @@
 use arith::{ExtensionField, Field, SimdField};
 use gf2::GF2;
-use gkr_engine::{MPIEngine, Transcript};
+use gkr_engine::{CommitLayout, MPIEngine, MPIResult, Transcript};
 use polynomials::{EqPolynomial, MultilinearExtension};
 
 use crate::{
     orion::{
-        mpi_utils::{mpi_commit_encoded, orion_mpi_mt_openings},
+        mpi_utils::{
+            mpi_commit_encoded, mpi_rank_local_commit_encoded, orion_mpi_mt_openings,
+            orion_mpi_rank_local_mt_openings,
+        },
         utils::{lut_open_linear_combine, simd_open_linear_combine},
         OrionCommitment, OrionProof, OrionResult, OrionSRS, OrionScratchPad,
     },
@@ -19,6 +22,7 @@ pub fn orion_mpi_commit_simd_field<F, SimdF, ComPackF>(
     pk: &OrionSRS,
     poly: &impl MultilinearExtension<SimdF>,
     scratch_pad: &mut OrionScratchPad,
+    layout: CommitLayout,
 ) -> OrionResult<OrionCommitment>
 where
     F: Field,
@@ -36,7 +40,12 @@ where
         std::slice::from_raw_parts(ptr as *const ComPackF, len)
     };
 
-    mpi_commit_encoded(mpi_engine, pk, packed_evals_ref, scratch_pad)
+    match layout {
+        CommitLayout::Global => mpi_commit_encoded(mpi_engine, pk, packed_evals_ref, scratch_pad),
+        CommitLayout::RankLocal => {
+            mpi_rank_local_commit_encoded(mpi_engine, pk, packed_evals_ref, scratch_pad)
+        }
+    }
 }
 
 #[inline(always)]
@@ -48,7 +57,8 @@ pub fn orion_mpi_open_simd_field<F, SimdF, EvalF, ComPackF>(
     mpi_point: &[EvalF],
     transcript: &mut impl Transcript,
     scratch_pad: &OrionScratchPad,
-) -> Option<OrionProof<EvalF>>
+    layout: CommitLayout,
+) -> MPIResult<Option<OrionProof<EvalF>>>
 where
     F: Field,
     SimdF: SimdField<Scalar = F>,
@@ -80,17 +90,20 @@ where
     let num_of_local_random_vars = point.len() - num_vars_in_msg;
     let local_random_coeffs: Vec<_> = (0..proximity_test_num)
         .map(|_| {
-            let local_rand = transcript.generate_field_elements::<EvalF>(num_of_local_random_vars);
-            let mpi_rand = transcript.generate_field_elements::<EvalF>(mpi_point.len());
-            let mut coeffs = EqPolynomial::build_eq_x_r(&local_rand);
-            let mpi_weight = EqPolynomial::ith_eq_vec_elem(&mpi_rand, mpi_engine.world_rank());
+            let rand =
+                transcript.challenge_point::<EvalF>(num_of_local_random_vars + mpi_point.len());
+            let (local_rand, mpi_rand) = rand.split_at(num_of_local_random_vars);
+            let mut coeffs = EqPolynomial::build_eq_x_r(local_rand);
+            let mpi_weight = EqPolynomial::ith_eq_vec_elem(mpi_rand, mpi_engine.world_rank());
             coeffs.iter_mut().for_each(|c| *c *= mpi_weight);
             coeffs
         })
         .collect();
 
+    // NOTE: the look up tables are indexed by the committed bits, the oblivious build sums
+    // the columns with the SIMD inner products instead
     match F::NAME {
-        GF2::NAME => lut_open_linear_combine(
+        GF2::NAME if !cfg!(feature = "oblivious") => lut_open_linear_combine(
             ComPackF::PACK_SIZE,
             poly.hypercube_basis_ref(),
             &eq_col_coeffs,
@@ -109,24 +122,29 @@ where
     }
 
     // NOTE: MPI sum up local weighed rows
-    eval_row = mpi_engine.sum_vec(&eval_row);
+    eval_row = mpi_engine.sum_vec(&eval_row)?;
     proximity_rows = proximity_rows
         .iter()
         .map(|r| mpi_engine.sum_vec(r))
-        .collect();
+        .collect::<MPIResult<_>>()?;
 
     // NOTE: MT opening for point queries
-    let query_openings = orion_mpi_mt_openings(mpi_engine, pk, scratch_pad, transcript);
+    let query_openings = match layout {
+        CommitLayout::Global => orion_mpi_mt_openings(mpi_engine, pk, scratch_pad, transcript)?,
+        CommitLayout::RankLocal => {
+            orion_mpi_rank_local_mt_openings(mpi_engine, pk, scratch_pad, transcript)?
+        }
+    };
 
     if !mpi_engine.is_root() {
-        return None;
+        return Ok(None);
     }
 
-    OrionProof {
+    Ok(OrionProof {
         eval_row,
         proximity_rows,
         query_openings: query_openings.unwrap(),
         merkle_cap: scratch_pad.merkle_cap.clone(),
     }
-    .into()
+    .into())
 }
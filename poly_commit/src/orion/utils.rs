@@ -1,5 +1,5 @@
 use arith::{ExtensionField, Field, SimdField};
-use gkr_engine::Transcript;
+use gkr_engine::{MPIError, Transcript};
 use itertools::izip;
 use serdes::{ExpSerde, SerdeError};
 use thiserror::Error;
@@ -23,6 +23,9 @@ pub enum OrionPCSError {
 
     #[error("field serde error")]
     SerializationError(#[from] SerdeError),
+
+    #[error(transparent)]
+    MPIError(#[from] MPIError),
 }
 
 pub type OrionResult<T> = std::result::Result<T, OrionPCSError>;
@@ -188,6 +191,12 @@ impl OrionSRS {
         let local_poly_len = 1 << self.num_vars;
         local_poly_len / self.message_len()
     }
+
+    /// The number of leaves each process opens per query in the `CommitLayout::RankLocal`
+    /// commitment, i.e., its share of the interleaved alphabet padded to whole leaves.
+    pub fn num_leaves_per_local_query(&self, world_size: usize) -> usize {
+        (self.num_leaves_per_mt_query * LEAF_BYTES / world_size).div_ceil(LEAF_BYTES)
+    }
 }
 
 pub type OrionCommitment = Node;
@@ -259,11 +268,10 @@ where
 
     // NOTE: MT opening for point queries
     let query_num = pk.query_complexity(PCS_SOUNDNESS_BITS);
-    let query_indices = transcript.generate_usize_vector(query_num);
+    let query_indices = transcript.challenge_queries(pk.codeword_len(), query_num, false);
     query_indices
         .iter()
-        .map(|qi| {
-            let index = *qi % pk.codeword_len();
+        .map(|&index| {
             let left = index * leaves_in_range_opening;
             let right = left + leaves_in_range_opening - 1;
 
@@ -284,8 +292,8 @@ pub(crate) fn orion_mt_verify(
     let world_size = merkle_cap.len();
     let indices_per_merkle_cap = vk.codeword_len().next_power_of_two() / world_size;
 
-    izip!(query_indices, range_openings).all(|(&qi, range_path)| {
-        let index = qi % vk.codeword_len();
+    // the queries are sampled in the domain of the codeword, see `Transcript::challenge_queries`
+    izip!(query_indices, range_openings).all(|(&index, range_path)| {
         let merkle_cap_index = index / indices_per_merkle_cap;
         let in_sub_tree_index = index % indices_per_merkle_cap;
 
@@ -294,6 +302,28 @@ pub(crate) fn orion_mt_verify(
     })
 }
 
+/// The MT check of `CommitLayout::RankLocal`: the openings of each query index, one per process,
+/// against the local roots in the merkle cap.
+#[inline(always)]
+pub(crate) fn orion_rank_local_mt_verify(
+    vk: &OrionSRS,
+    query_indices: &[usize],
+    range_openings: &[tree::RangePath],
+    merkle_cap: &[Node],
+) -> bool {
+    let world_size = merkle_cap.len();
+    let num_leaves_per_opening = vk.num_leaves_per_local_query(world_size);
+
+    range_openings.len() == query_indices.len() * world_size
+        && izip!(query_indices, range_openings.chunks(world_size)).all(|(&index, range_paths)| {
+            izip!(range_paths, merkle_cap).all(|(range_path, local_root)| {
+                range_path.leaves.len() == num_leaves_per_opening
+                    && range_path.left == index * num_leaves_per_opening
+                    && range_path.verify(local_root)
+            })
+        })
+}
+
 /*
  * LINEAR OPERATIONS FOR GF2 (LOOKUP TABLE BASED)
  */
@@ -421,8 +451,7 @@ where
 
     luts.build(fixed_rl);
 
-    izip!(query_indices, packed_interleaved_alphabets).all(|(qi, interleaved_alphabet)| {
-        let index = qi % codeword.len();
+    izip!(query_indices, packed_interleaved_alphabets).all(|(&index, interleaved_alphabet)| {
         let alphabet = luts.lookup_and_sum(interleaved_alphabet);
         alphabet == codeword[index]
     })
@@ -535,8 +564,7 @@ where
     transpose(&rl_limbs, &mut scratch, ExtF::DEGREE, fixed_rl.len());
     let simd_limbs: Vec<_> = scratch.chunks(SimdF::PACK_SIZE).map(SimdF::pack).collect();
 
-    izip!(query_indices, packed_interleaved_alphabets).all(|(qi, interleaved_alphabet)| {
-        let index = qi % codeword.len();
+    izip!(query_indices, packed_interleaved_alphabets).all(|(&index, interleaved_alphabet)| {
         let alphabet: ExtF = simd_ext_base_inner_prod(&simd_limbs, interleaved_alphabet);
         alphabet == codeword[index]
     })
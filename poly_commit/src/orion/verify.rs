@@ -2,14 +2,17 @@ use std::iter;
 
 use arith::{ExtensionField, Field, SimdField};
 use gf2::GF2;
-use gkr_engine::Transcript;
+use gkr_engine::{CommitLayout, Transcript};
 use itertools::{chain, izip};
-use polynomials::{EqPolynomial, MultilinearExtension, RefMultiLinearPoly};
+use polynomials::EqPolynomial;
 use tree::LEAF_BYTES;
 
 use crate::{
     orion::{
-        utils::{lut_verify_alphabet_check, orion_mt_verify, simd_verify_alphabet_check},
+        utils::{
+            lut_verify_alphabet_check, orion_mt_verify, orion_rank_local_mt_verify,
+            simd_verify_alphabet_check,
+        },
         OrionCommitment, OrionProof, OrionSRS,
     },
     traits::TensorCodeIOPPCS,
@@ -25,6 +28,7 @@ pub fn orion_verify<F, SimdF, EvalF, ComPackF>(
     evaluation: EvalF,
     transcript: &mut impl Transcript,
     proof: &OrionProof<EvalF>,
+    layout: CommitLayout,
 ) -> bool
 where
     F: Field,
@@ -39,10 +43,9 @@ where
     let num_vars_in_msg = msg_size.ilog2() as usize;
 
     // NOTE: working on evaluation response, evaluate the rest of the response
-    let mut scratch = vec![EvalF::ZERO; msg_size];
-    let final_eval = RefMultiLinearPoly::from_ref(&proof.eval_row).evaluate_with_buffer(
+    let final_eval = EqPolynomial::eq_weighted_sum(
         &point[num_vars_in_com_simd..num_vars_in_com_simd + num_vars_in_msg],
-        &mut scratch,
+        &proof.eval_row,
     );
 
     if final_eval != evaluation {
@@ -55,13 +58,13 @@ where
     let random_linear_combinations: Vec<_> = (0..proximity_reps)
         .map(|_| {
             let num_vars = point.len() - num_vars_in_msg + mpi_point.len();
-            let rand = transcript.generate_field_elements::<EvalF>(num_vars);
+            let rand = transcript.challenge_point::<EvalF>(num_vars);
             EqPolynomial::build_eq_x_r(&rand)
         })
         .collect();
 
     let query_num = vk.query_complexity(PCS_SOUNDNESS_BITS);
-    let query_indices = transcript.generate_usize_vector(query_num);
+    let query_indices = transcript.challenge_queries(vk.codeword_len(), query_num, false);
 
     // NOTE: check consistency in MT in the opening trees and against the commitment tree
     {
@@ -82,20 +85,32 @@ where
         }
     }
 
-    if !orion_mt_verify(vk, &query_indices, &proof.query_openings, &proof.merkle_cap) {
-        return false;
-    }
-
     // NOTE: prepare the interleaved alphabets from the MT paths
-    let num_simd_elems_per_leaf = vk.num_leaves_per_mt_query() * LEAF_BYTES / SimdF::SIZE;
-    let packed_interleaved_alphabets: Vec<Vec<SimdF>> = proof
-        .query_openings
-        .iter()
-        .map(|c| unsafe {
-            let ptr = c.leaves.as_ptr();
-            std::slice::from_raw_parts(ptr as *const SimdF, num_simd_elems_per_leaf).to_vec()
-        })
-        .collect();
+    let packed_interleaved_alphabets = match layout {
+        CommitLayout::Global => {
+            if !orion_mt_verify(vk, &query_indices, &proof.query_openings, &proof.merkle_cap) {
+                return false;
+            }
+
+            let Some(alphabets) = orion_interleaved_alphabets::<SimdF>(vk, &proof.query_openings)
+            else {
+                return false;
+            };
+            alphabets
+        }
+        CommitLayout::RankLocal => {
+            if !orion_rank_local_mt_verify(
+                vk,
+                &query_indices,
+                &proof.query_openings,
+                &proof.merkle_cap,
+            ) {
+                return false;
+            }
+
+            orion_rank_local_interleaved_alphabets::<SimdF>(vk, world_size, &proof.query_openings)
+        }
+    };
 
     let eq_col_coeffs = {
         let mut eq_vars = point[..num_vars_in_com_simd].to_vec();
@@ -104,11 +119,94 @@ where
         EqPolynomial::build_eq_x_r(&eq_vars)
     };
 
-    chain!(
-        izip!(&random_linear_combinations, &proof.proximity_rows),
-        iter::once((&eq_col_coeffs, &proof.eval_row))
+    orion_codeword_check::<F, SimdF, EvalF>(
+        vk,
+        &query_indices,
+        &packed_interleaved_alphabets,
+        chain!(
+            izip!(&random_linear_combinations, &proof.proximity_rows),
+            iter::once((&eq_col_coeffs, &proof.eval_row))
+        ),
     )
-    .all(|(rl, msg)| {
+}
+
+/// Reinterpret the leaves of the MT query openings as the packed interleaved alphabets of the
+/// queried columns, or `None` if an opening is short of leaves.
+#[inline(always)]
+pub(crate) fn orion_interleaved_alphabets<SimdF: SimdField>(
+    vk: &OrionSRS,
+    query_openings: &[tree::RangePath],
+) -> Option<Vec<Vec<SimdF>>> {
+    let num_simd_elems_per_leaf = vk.num_leaves_per_mt_query() * LEAF_BYTES / SimdF::SIZE;
+    query_openings
+        .iter()
+        .map(|c| {
+            if c.leaves.len() < vk.num_leaves_per_mt_query() {
+                return None;
+            }
+            let alphabet = unsafe {
+                let ptr = c.leaves.as_ptr();
+                std::slice::from_raw_parts(ptr as *const SimdF, num_simd_elems_per_leaf).to_vec()
+            };
+            Some(alphabet)
+        })
+        .collect()
+}
+
+/// Concatenate the local alphabets of the processes in the MT query openings of
+/// `CommitLayout::RankLocal`, ordered by query index then by rank, into the packed interleaved
+/// alphabets of the queried columns. The openings are of whole local alphabets, see
+/// `orion_rank_local_mt_verify`.
+#[inline(always)]
+pub(crate) fn orion_rank_local_interleaved_alphabets<SimdF: SimdField>(
+    vk: &OrionSRS,
+    world_size: usize,
+    query_openings: &[tree::RangePath],
+) -> Vec<Vec<SimdF>> {
+    let alphabet_bytes = vk.num_leaves_per_mt_query() * LEAF_BYTES;
+    let local_alphabet_bytes = alphabet_bytes / world_size;
+    query_openings
+        .chunks(world_size)
+        .map(|local_openings| {
+            let alphabet_bytes_concat: Vec<u8> = local_openings
+                .iter()
+                .flat_map(|c| {
+                    c.leaves
+                        .iter()
+                        .flat_map(|leaf| leaf.data)
+                        .take(local_alphabet_bytes)
+                })
+                .collect();
+            assert_eq!(alphabet_bytes_concat.len(), alphabet_bytes);
+
+            let mut alphabet = vec![SimdF::ZERO; alphabet_bytes / SimdF::SIZE];
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    alphabet_bytes_concat.as_ptr(),
+                    alphabet.as_mut_ptr() as *mut u8,
+                    alphabet_bytes,
+                )
+            };
+            alphabet
+        })
+        .collect()
+}
+
+/// Check that each of the rows, encoded, agrees at the queried columns with the same linear
+/// combination of the interleaved alphabets.
+#[inline(always)]
+pub(crate) fn orion_codeword_check<'a, F, SimdF, EvalF>(
+    vk: &OrionSRS,
+    query_indices: &[usize],
+    packed_interleaved_alphabets: &[Vec<SimdF>],
+    mut combinations_and_rows: impl Iterator<Item = (&'a Vec<EvalF>, &'a Vec<EvalF>)>,
+) -> bool
+where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+    EvalF: ExtensionField<BaseField = F> + 'a,
+{
+    combinations_and_rows.all(|(rl, msg)| {
         let codeword = match vk.code_instance.encode(msg) {
             Ok(c) => c,
             _ => return false,
@@ -118,14 +216,14 @@ where
             GF2::NAME => lut_verify_alphabet_check(
                 &codeword,
                 rl,
-                &query_indices,
-                &packed_interleaved_alphabets,
+                query_indices,
+                packed_interleaved_alphabets,
             ),
             _ => simd_verify_alphabet_check(
                 &codeword,
                 rl,
-                &query_indices,
-                &packed_interleaved_alphabets,
+                query_indices,
+                packed_interleaved_alphabets,
             ),
         }
     })
@@ -1,10 +1,12 @@
 /// Raw commitment for multi-linear polynomials
-use arith::{ExtensionField, Field};
+use arith::{ExtensionField, Field, SimdField};
 use ethnum::U256;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
-    StructuredReferenceString, Transcript,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
 };
+#[cfg(feature = "raw-distributed")]
+use polynomials::EqPolynomial;
 use polynomials::{MultiLinearPoly, MultilinearExtension};
 use rand::RngCore;
 use serdes::{ExpSerde, SerdeResult};
@@ -157,29 +159,29 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
         _proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
         poly: &impl MultilinearExtension<C::SimdCircuitField>,
         _scratch_pad: &mut Self::ScratchPad,
-    ) -> Option<Self::Commitment> {
+    ) -> MPIResult<Option<Self::Commitment>> {
         assert!(poly.num_vars() == *params);
 
         if mpi_engine.is_single_process() {
-            return Self::Commitment {
+            return Ok(Self::Commitment {
                 evals: poly.hypercube_basis(),
             }
-            .into();
+            .into());
         }
 
         let mut buffer = if mpi_engine.is_root() {
-            vec![C::SimdCircuitField::zero(); poly.hypercube_size() * mpi_engine.world_size()]
+            vec![C::SimdCircuitField::zero(); poly.hypercube_size() * mpi_engine.n_parties()]
         } else {
             vec![]
         };
 
-        mpi_engine.gather_vec(poly.hypercube_basis_ref(), &mut buffer);
+        mpi_engine.gather_padded_vec(poly.hypercube_basis_ref(), &mut buffer)?;
 
         if !mpi_engine.is_root() {
-            return None;
+            return Ok(None);
         }
 
-        Self::Commitment { evals: buffer }.into()
+        Ok(Self::Commitment { evals: buffer }.into())
     }
 
     fn open(
@@ -190,8 +192,8 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
         _x: &ExpanderSingleVarChallenge<C>,
         _transcript: &mut impl Transcript,
         _scratch_pad: &Self::ScratchPad,
-    ) -> Option<Self::Opening> {
-        Some(())
+    ) -> MPIResult<Option<Self::Opening>> {
+        Ok(Some(()))
     }
 
     fn verify(
@@ -208,3 +210,148 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
         v == v_target
     }
 }
+
+// =================================================================================================
+
+/// Per-process view of a raw commitment in the distributed setting.
+///
+/// Rather than gathering the whole polynomial onto the root (and later shipping it to the
+/// verifier), the commitment only records the number of MPI parties the polynomial is
+/// distributed over, virtual parties included.
+#[cfg(feature = "raw-distributed")]
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct RawDistributedCommitment {
+    pub world_size: usize,
+}
+
+/// Opening of [`RawExpanderGKRDistributed`]: the evaluation contributed by each MPI process,
+/// i.e., the local polynomial evaluated at the `rz` and `r_simd` parts of the challenge.
+#[cfg(feature = "raw-distributed")]
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct RawDistributedOpening<F: Field> {
+    pub local_evals: Vec<F>,
+}
+
+/// Raw PCS for expander GKR that avoids broadcasting the full polynomial in MPI mode.
+///
+/// Each process evaluates its own share of the polynomial at the local part of the challenge,
+/// the root gathers the `world_size` scalars, and the verifier checks the claimed value against
+/// the random linear combination of the contributions weighted by `eq(r_mpi, ·)`.
+///
+/// NOTE: the verifier never sees the polynomial itself, so the scheme is NOT binding.
+/// It is meant for tests and benchmarks with large world sizes, where the data movement of
+/// [`RawExpanderGKR`] dominates, and is only built with the `raw-distributed` feature.
+#[cfg(feature = "raw-distributed")]
+pub struct RawExpanderGKRDistributed<C: FieldEngine> {
+    _phantom: std::marker::PhantomData<C>,
+}
+
+#[cfg(feature = "raw-distributed")]
+impl<C: FieldEngine> RawExpanderGKRDistributed<C> {
+    /// Evaluate the local share of the polynomial at the `rz` and `r_simd` parts of the challenge.
+    #[inline]
+    pub fn local_eval(
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        x: &ExpanderSingleVarChallenge<C>,
+    ) -> C::ChallengeField {
+        let local_simd = C::eval_circuit_vals_at_challenge(poly.hypercube_basis_ref(), &x.rz);
+        EqPolynomial::eq_weighted_sum(&x.r_simd, &local_simd.unpack())
+    }
+}
+
+#[cfg(feature = "raw-distributed")]
+impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKRDistributed<C> {
+    const NAME: &'static str = "RawExpanderGKRDistributed";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::RawDistributed;
+
+    type Params = usize;
+
+    type ScratchPad = ();
+
+    type SRS = ();
+
+    type Commitment = RawDistributedCommitment;
+
+    type Opening = RawDistributedOpening<C::ChallengeField>;
+
+    type BatchOpening = ();
+
+    fn gen_srs(
+        _params: &Self::Params,
+        _mpi_engine: &impl MPIEngine,
+        _rng: impl RngCore,
+    ) -> Self::SRS {
+    }
+
+    fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
+        n_input_vars
+    }
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
+
+    fn commit(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        _proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        _scratch_pad: &mut Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Commitment>> {
+        assert!(poly.num_vars() == *params);
+
+        if !mpi_engine.is_root() {
+            return Ok(None);
+        }
+
+        Ok(Self::Commitment {
+            world_size: mpi_engine.n_parties(),
+        }
+        .into())
+    }
+
+    fn open(
+        _params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        _proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        x: &ExpanderSingleVarChallenge<C>,
+        _transcript: &mut impl Transcript,
+        _scratch_pad: &Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Opening>> {
+        let local_eval = Self::local_eval(poly, x);
+
+        let mut local_evals = if mpi_engine.is_root() {
+            vec![C::ChallengeField::ZERO; mpi_engine.n_parties()]
+        } else {
+            vec![]
+        };
+        mpi_engine.gather_padded_vec(&[local_eval], &mut local_evals)?;
+
+        if !mpi_engine.is_root() {
+            return Ok(None);
+        }
+
+        Ok(Self::Opening { local_evals }.into())
+    }
+
+    fn verify(
+        _params: &Self::Params,
+        _verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        challenge: &ExpanderSingleVarChallenge<C>,
+        v: C::ChallengeField,
+        _transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        if commitment.world_size != 1 << challenge.r_mpi.len()
+            || opening.local_evals.len() != commitment.world_size
+        {
+            return false;
+        }
+
+        // random linear combination of the per-process contributions with eq(r_mpi, ·)
+        let v_target = EqPolynomial::eq_weighted_sum(&challenge.r_mpi, &opening.local_evals);
+
+        v == v_target
+    }
+}
@@ -0,0 +1,342 @@
+//! A state committed once and opened at a few positions by many independent proofs.
+//!
+//! A large state, e.g., an account tree laid out as a multilinear polynomial, is committed once
+//! by a `SharedState`, and every proof over a snapshot of the state, of whatever circuit, opens
+//! the few positions it reads against that commitment.  The openings of a proof carry the
+//! `StateRoot`, the digest of the commitment, and the index of the proof, which are absorbed into
+//! the transcript of the proof before the openings, so an opening cannot be replayed in a proof
+//! over another state, or in another proof over the same state.
+//!
+//! A `SharedStateVerifier` checks the openings of all the proofs against the same root, and that
+//! the proofs agree on the value of every position opened by more than one of them, i.e., that
+//! they all read the same snapshot.
+//!
+//! Positions index the circuit field elements of the global polynomial, LSB-first: the SIMD
+//! lane, then the local variables, then the MPI party, a virtual party holding the state of the
+//! process it mirrors.
+use std::collections::{HashMap, HashSet};
+
+use arith::{Field, SimdField};
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    StructuredReferenceString, Transcript,
+};
+use polynomials::{HypercubeLayout, MultilinearExtension};
+use serdes::ExpSerde;
+use tiny_keccak::{Hasher, Keccak};
+
+/// The digest of the commitment to a shared state, binding the openings to the state.
+pub type StateRoot = [u8; 32];
+
+const DOMAIN_SEPARATOR: &[u8] = b"expander shared state";
+
+/// The root of the state committed to by `commitment`
+pub fn state_root<Commitment: ExpSerde>(commitment: &Commitment) -> StateRoot {
+    let mut bytes = vec![];
+    commitment.serialize_into(&mut bytes).unwrap();
+
+    let mut root = StateRoot::default();
+    let mut hasher = Keccak::v256();
+    hasher.update(&bytes);
+    hasher.finalize(&mut root);
+    root
+}
+
+/// The openings of the positions read by one proof over a shared state.
+#[derive(Clone, Debug, Default, PartialEq, ExpSerde)]
+pub struct StateOpenings<F: Field, Opening: ExpSerde> {
+    /// the root of the state the positions are opened against
+    pub root: StateRoot,
+    /// the index of the proof among the proofs over the state
+    pub proof_index: usize,
+    pub positions: Vec<usize>,
+    pub values: Vec<F>,
+    pub openings: Vec<Opening>,
+}
+
+/// The point of `position` over the `num_vars` variables of the global polynomial, LSB-first.
+#[inline]
+pub fn position_challenge<C: FieldEngine>(
+    position: usize,
+    num_vars: usize,
+    world_size: usize,
+) -> ExpanderSingleVarChallenge<C> {
+    let point = (0..num_vars)
+        .map(|i| match (position >> i) & 1 {
+            0 => C::ChallengeField::ZERO,
+            _ => C::ChallengeField::ONE,
+        })
+        .collect::<Vec<_>>();
+    ExpanderSingleVarChallenge::from_point(&point, HypercubeLayout::LsbFirst, world_size)
+}
+
+/// The values of the global polynomial, whose local share on this process is `poly`, at
+/// `positions`, on every process. Fails if the processes fail to communicate.
+pub fn position_values<C: FieldEngine>(
+    mpi_engine: &impl MPIEngine,
+    poly: &impl MultilinearExtension<C::SimdCircuitField>,
+    positions: &[usize],
+) -> MPIResult<Vec<C::ChallengeField>> {
+    // each value is on a single process, the one mirroring its party, the others contribute zero
+    let n_simd_vars = C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+    let n_local_vars = poly.num_vars();
+    let local_values = positions
+        .iter()
+        .map(|&p| {
+            let party = p >> (n_simd_vars + n_local_vars);
+            if party >= mpi_engine.n_parties()
+                || mpi_engine.mirror_rank(party) != mpi_engine.world_rank()
+            {
+                return C::ChallengeField::ZERO;
+            }
+            let elem = poly.hypercube_basis_ref()[(p >> n_simd_vars) & ((1 << n_local_vars) - 1)];
+            C::ChallengeField::from(elem.unpack()[p & ((1 << n_simd_vars) - 1)])
+        })
+        .collect::<Vec<_>>();
+    mpi_engine.allreduce_vec(&local_values)
+}
+
+/// Bind the transcript of a proof to the state and to the positions it reads.
+#[inline]
+fn absorb_positions<F: Field>(
+    transcript: &mut impl Transcript,
+    root: &StateRoot,
+    proof_index: usize,
+    positions: &[usize],
+    values: &[F],
+) {
+    transcript.append_domain_separator(DOMAIN_SEPARATOR);
+    transcript.append_u8_slice(root);
+    transcript.append_u8_slice(&(proof_index as u64).to_le_bytes());
+    positions.iter().zip(values).for_each(|(position, value)| {
+        transcript.append_u8_slice(&(*position as u64).to_le_bytes());
+        transcript.append_field_element(value);
+    });
+}
+
+/// Prover side of a shared state: the commitment to the state, and the number of proofs issued
+/// over it.
+///
+/// NOTE: committing and opening are collective operations, all the MPI processes should issue
+/// the proofs in the same order.  As with `ExpanderPCS::commit`, only the commitment on the root
+/// process is meaningful, the root is the same on all processes.
+pub struct SharedState<C: FieldEngine, PCS: ExpanderPCS<C>> {
+    root: StateRoot,
+    commitment: PCS::Commitment,
+    scratch_pad: PCS::ScratchPad,
+    num_vars: usize,
+    num_proofs: usize,
+}
+
+impl<C: FieldEngine, PCS: ExpanderPCS<C>> SharedState<C, PCS> {
+    /// Commit to the state `poly`, the local share of the state on this process. Fails if the
+    /// processes fail to communicate.
+    pub fn commit(
+        params: &PCS::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<PCS::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+    ) -> MPIResult<Self> {
+        let mut scratch_pad = PCS::init_scratch_pad(params, mpi_engine);
+        let commitment = PCS::commit(params, mpi_engine, proving_key, poly, &mut scratch_pad)?
+            .unwrap_or_default();
+
+        let mut root = state_root(&commitment).to_vec();
+        mpi_engine.root_broadcast_bytes(&mut root)?;
+
+        Ok(Self {
+            root: root.try_into().unwrap(),
+            commitment,
+            scratch_pad,
+            num_vars: poly.num_vars()
+                + C::SimdCircuitField::PACK_SIZE.ilog2() as usize
+                + mpi_engine.n_mpi_vars()?,
+            num_proofs: 0,
+        })
+    }
+
+    #[inline]
+    pub fn root(&self) -> StateRoot {
+        self.root
+    }
+
+    #[inline]
+    pub fn commitment(&self) -> &PCS::Commitment {
+        &self.commitment
+    }
+
+    /// The number of variables of the global state polynomial, over the circuit field elements
+    #[inline]
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// The number of proofs issued over the state so far
+    #[inline]
+    pub fn num_proofs(&self) -> usize {
+        self.num_proofs
+    }
+
+    /// Open the state `poly`, the one committed to, at `positions`, as the next proof over the
+    /// state. The openings go along the proof, whose transcript is `transcript`, and every
+    /// process gets the opened values.
+    ///
+    /// Only the openings on the root process are meaningful. Fails if the processes fail to
+    /// communicate.
+    pub fn open(
+        &mut self,
+        params: &PCS::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<PCS::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        positions: &[usize],
+        transcript: &mut impl Transcript,
+    ) -> MPIResult<StateOpenings<C::ChallengeField, PCS::Opening>> {
+        assert!(
+            positions.iter().all(|&p| p < 1 << self.num_vars),
+            "position out of the state"
+        );
+
+        let values = position_values::<C>(mpi_engine, poly, positions)?;
+
+        let proof_index = self.num_proofs;
+        self.num_proofs += 1;
+        absorb_positions(transcript, &self.root, proof_index, positions, &values);
+
+        let openings = positions
+            .iter()
+            .map(|&p| {
+                let x = position_challenge::<C>(p, self.num_vars, mpi_engine.world_size());
+                transcript.lock_proof();
+                let opening = PCS::open(
+                    params,
+                    mpi_engine,
+                    proving_key,
+                    poly,
+                    &x,
+                    transcript,
+                    &self.scratch_pad,
+                );
+                transcript.unlock_proof();
+                Ok(opening?.unwrap_or_default())
+            })
+            .collect::<MPIResult<_>>()?;
+
+        Ok(StateOpenings {
+            root: self.root,
+            proof_index,
+            positions: positions.to_vec(),
+            values,
+            openings,
+        })
+    }
+}
+
+/// Verifier side of a shared state: checks the openings of all the proofs over the state against
+/// its commitment, and keeps the values read so far to check that the proofs agree on them.
+pub struct SharedStateVerifier<C: FieldEngine, PCS: ExpanderPCS<C>> {
+    root: StateRoot,
+    commitment: PCS::Commitment,
+    num_vars: usize,
+    world_size: usize,
+    proof_indices: HashSet<usize>,
+    values: HashMap<usize, C::ChallengeField>,
+}
+
+impl<C: FieldEngine, PCS: ExpanderPCS<C>> SharedStateVerifier<C, PCS> {
+    /// `num_vars` is the number of variables of the global state polynomial, see
+    /// `SharedState::num_vars`, and `world_size` the number of MPI processes that committed it.
+    pub fn new(commitment: PCS::Commitment, num_vars: usize, world_size: usize) -> Self {
+        Self {
+            root: state_root(&commitment),
+            commitment,
+            num_vars,
+            world_size,
+            proof_indices: HashSet::new(),
+            values: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn root(&self) -> StateRoot {
+        self.root
+    }
+
+    /// The number of proofs whose openings were verified
+    #[inline]
+    pub fn num_proofs(&self) -> usize {
+        self.proof_indices.len()
+    }
+
+    /// The value of the state at `position`, if some verified proof opened it
+    #[inline]
+    pub fn value(&self, position: usize) -> Option<C::ChallengeField> {
+        self.values.get(&position).copied()
+    }
+
+    /// Verify the openings of a proof, whose transcript is `transcript`.
+    ///
+    /// Fails if the openings are not against this state, if a proof with the same index was
+    /// verified before, or if a value differs from the one read by a proof verified before.
+    /// The verifier is left as is on failure.
+    pub fn verify(
+        &mut self,
+        params: &PCS::Params,
+        verifying_key: &<PCS::SRS as StructuredReferenceString>::VKey,
+        openings: &StateOpenings<C::ChallengeField, PCS::Opening>,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        let n_positions = openings.positions.len();
+        if openings.root != self.root
+            || openings.values.len() != n_positions
+            || openings.openings.len() != n_positions
+            || self.proof_indices.contains(&openings.proof_index)
+            || openings.positions.iter().any(|&p| p >= 1 << self.num_vars)
+        {
+            return false;
+        }
+
+        // the snapshot read by this proof is the one read by the others
+        let mut read = HashMap::new();
+        for (&p, &v) in openings.positions.iter().zip(&openings.values) {
+            if *read.entry(p).or_insert(v) != v || self.value(p).is_some_and(|prev| prev != v) {
+                return false;
+            }
+        }
+
+        absorb_positions(
+            transcript,
+            &self.root,
+            openings.proof_index,
+            &openings.positions,
+            &openings.values,
+        );
+
+        let mut verified = true;
+        for ((&p, &v), opening) in openings
+            .positions
+            .iter()
+            .zip(&openings.values)
+            .zip(&openings.openings)
+        {
+            let x = position_challenge::<C>(p, self.num_vars, self.world_size);
+            transcript.lock_proof();
+            verified &= PCS::verify(
+                params,
+                verifying_key,
+                &self.commitment,
+                &x,
+                v,
+                transcript,
+                opening,
+            );
+            transcript.unlock_proof();
+        }
+
+        if verified {
+            self.proof_indices.insert(openings.proof_index);
+            self.values.extend(read);
+        }
+        verified
+    }
+}
@@ -0,0 +1,172 @@
+//! The long sequences of curve points of the SRS, e.g., the powers of tau of the KZG and the
+//! bases of the Pedersen commitments of Hyrax.
+//!
+//! The points are written as their affine coordinates, in the canonical form of the base field.
+//! An SRS loaded from a memory mapped file, see `serdes::load_from_file`, keeps referring to the
+//! map, and the points are only decoded, on the rayon thread pool, once they are first used: no
+//! time is spent on the points a process never uses, e.g., the KZG verifier, and the file is
+//! never copied out of the page cache but into the decoded points.
+use std::{
+    ops::Deref,
+    sync::{Arc, OnceLock},
+};
+
+use halo2curves::{ff::PrimeField, CurveAffine};
+use rayon::prelude::*;
+use serdes::{ExpSerde, Mmap, SerdeError, SerdeResult};
+
+/// A sequence of curve points, decoded from a memory mapped file on first use if loaded from one.
+///
+/// NOTE: a corrupted file is only detected once the points are decoded, which panics.
+#[derive(Clone, Debug)]
+pub struct SrsPoints<C: CurveAffine> {
+    points: OnceLock<Vec<C>>,
+    /// The map and the range of the encoded points, until they are decoded
+    mapped: Option<(Arc<Mmap>, usize, usize)>,
+}
+
+impl<C: CurveAffine> SrsPoints<C> {
+    /// The number of bytes of the encoding of a point
+    #[inline(always)]
+    fn point_bytes() -> usize {
+        2 * <C::Base as PrimeField>::Repr::default().as_ref().len()
+    }
+
+    #[inline]
+    fn decode_point(bytes: &[u8]) -> Option<C> {
+        let coord = |bytes: &[u8]| {
+            let mut repr = <C::Base as PrimeField>::Repr::default();
+            repr.as_mut().copy_from_slice(bytes);
+            Option::<C::Base>::from(C::Base::from_repr(repr))
+        };
+        let (x, y) = bytes.split_at(bytes.len() / 2);
+        Option::from(C::from_xy(coord(x)?, coord(y)?))
+    }
+
+    fn decode(bytes: &[u8]) -> SerdeResult<Vec<C>> {
+        bytes
+            .par_chunks(Self::point_bytes())
+            .map(|point| Self::decode_point(point).ok_or(SerdeError::DeserializeError))
+            .collect()
+    }
+
+    /// Whether the points are decoded already, i.e., they were not loaded from a map, or they
+    /// were used since
+    pub fn is_decoded(&self) -> bool {
+        self.points.get().is_some()
+    }
+}
+
+impl<C: CurveAffine> Deref for SrsPoints<C> {
+    type Target = [C];
+
+    fn deref(&self) -> &[C] {
+        self.points.get_or_init(|| {
+            let (mmap, start, end) = self.mapped.as_ref().unwrap();
+            Self::decode(&mmap[*start..*end]).expect("corrupted SRS points")
+        })
+    }
+}
+
+impl<C: CurveAffine> From<Vec<C>> for SrsPoints<C> {
+    fn from(points: Vec<C>) -> Self {
+        Self {
+            points: OnceLock::from(points),
+            mapped: None,
+        }
+    }
+}
+
+impl<C: CurveAffine> Default for SrsPoints<C> {
+    fn default() -> Self {
+        Vec::new().into()
+    }
+}
+
+impl<C: CurveAffine> PartialEq for SrsPoints<C> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<C: CurveAffine> Eq for SrsPoints<C> {}
+
+impl<C: CurveAffine> ExpSerde for SrsPoints<C> {
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        self.len().serialize_into(&mut writer)?;
+        for point in self.iter() {
+            let coord = point.coordinates().unwrap();
+            writer.write_all(coord.x().to_repr().as_ref())?;
+            writer.write_all(coord.y().to_repr().as_ref())?;
+        }
+        Ok(())
+    }
+
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> SerdeResult<Self> {
+        let len = usize::deserialize_from(&mut reader)?;
+        let size = len
+            .checked_mul(Self::point_bytes())
+            .ok_or(SerdeError::DeserializeError)?;
+
+        // `take` grows the buffer as the bytes arrive, so a corrupted length fails on the read
+        // instead of on a huge allocation
+        let mut bytes = vec![];
+        reader.take(size as u64).read_to_end(&mut bytes)?;
+        if bytes.len() != size {
+            return Err(SerdeError::DeserializeError);
+        }
+        Ok(Self::decode(&bytes)?.into())
+    }
+
+    fn deserialize_from_mmap(mmap: &Arc<Mmap>, offset: &mut usize) -> SerdeResult<Self> {
+        let len = usize::deserialize_from_mmap(mmap, offset)?;
+        let end = len
+            .checked_mul(Self::point_bytes())
+            .and_then(|size| offset.checked_add(size))
+            .filter(|end| *end <= mmap.len())
+            .ok_or(SerdeError::DeserializeError)?;
+
+        let points = Self {
+            points: OnceLock::new(),
+            mapped: Some((Arc::clone(mmap), *offset, end)),
+        };
+        *offset = end;
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_std::test_rng;
+    use halo2curves::{
+        bn256::{G1Affine, G1},
+        group::{Curve, Group},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_srs_points_mmap() {
+        let mut rng = test_rng();
+        let points: SrsPoints<G1Affine> = (0..37)
+            .map(|_| G1::random(&mut rng).to_affine())
+            .collect::<Vec<_>>()
+            .into();
+        let value = (points.clone(), 42usize);
+
+        let path = std::env::temp_dir().join(format!("srs_points_{}", std::process::id()));
+        serdes::store_to_file(&value, &path).unwrap();
+
+        let loaded: (SrsPoints<G1Affine>, usize) = serdes::load_from_file(&path).unwrap();
+        assert!(!loaded.0.is_decoded());
+        assert_eq!(loaded.1, 42);
+        assert_eq!(loaded.0, points);
+        assert!(loaded.0.is_decoded());
+
+        let mut bytes = vec![];
+        points.serialize_into(&mut bytes).unwrap();
+        assert_eq!(SrsPoints::deserialize_from(&bytes[..]).unwrap(), points);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,6 +1,7 @@
 use arith::{ExtensionField, Field};
 use gkr_engine::{StructuredReferenceString, Transcript};
-use rand::RngCore;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use serdes::ExpSerde;
 use std::fmt::Debug;
 use sumcheck::IOPProof;
@@ -26,6 +27,12 @@ pub trait PolynomialCommitmentScheme<F: ExtensionField> {
     /// version of polynomial as input.
     fn gen_srs_for_testing(params: &Self::Params, rng: impl RngCore) -> (Self::SRS, usize);
 
+    /// Same as `gen_srs_for_testing`, from `seed` rather than a random source, the same for the
+    /// same seed on every machine. Whoever knows the seed knows the trapdoor of the SRS.
+    fn gen_srs_from_seed(params: &Self::Params, seed: u64) -> (Self::SRS, usize) {
+        Self::gen_srs_for_testing(params, ChaCha20Rng::seed_from_u64(seed))
+    }
+
     /// Initialize the scratch pad.
     fn init_scratch_pad(params: &Self::Params) -> Self::ScratchPad;
 
@@ -118,6 +125,36 @@ pub trait BatchOpeningPCS<F: ExtensionField>: PolynomialCommitmentScheme<F> + Si
     ) -> bool;
 }
 
+/// Batch opening of the polynomials committed as univariate ones, e.g., the coefficients of the
+/// KZG based schemes, each at its own set of points, in a single opening of a constant size.
+pub trait UnivariateBatchOpeningPCS<F: ExtensionField>: PolynomialCommitmentScheme<F> {
+    type UnivariateBatchOpening: Clone + Debug + Default + ExpSerde;
+
+    /// Open the univariate polynomials of coefficients `polys`, each at the points of its set.
+    /// Requires the length of the polys to be the same as point_sets, and the points of a set to
+    /// be distinct.
+    ///
+    /// Returns the evaluations of each polynomial at the points of its set.
+    fn batch_open(
+        params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        polys: &[Vec<F>],
+        point_sets: &[Vec<F>],
+        transcript: &mut impl Transcript,
+    ) -> (Vec<Vec<F>>, Self::UnivariateBatchOpening);
+
+    /// Verify the opening of a set of polynomials, each at the points of its set.
+    fn batch_verify(
+        params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitments: &[Self::Commitment],
+        point_sets: &[Vec<F>],
+        evals: &[Vec<F>],
+        opening: &Self::UnivariateBatchOpening,
+        transcript: &mut impl Transcript,
+    ) -> bool;
+}
+
 pub(crate) trait TensorCodeIOPPCS {
     fn message_len(&self) -> usize;
 
@@ -14,6 +14,25 @@ pub fn expander_pcs_init_testing_only<FieldConfig: FieldEngine, PCS: ExpanderPCS
     <PCS::SRS as StructuredReferenceString>::PKey,
     <PCS::SRS as StructuredReferenceString>::VKey,
     PCS::ScratchPad,
+) {
+    expander_pcs_init_with_srs_file_testing_only::<FieldConfig, PCS>(n_input_vars, mpi_config, None)
+}
+
+/// Same as `expander_pcs_init_testing_only`, loading the SRS, with its precomputed tables, from
+/// `srs_file` if given, or storing it there once generated if there is no such file yet.
+#[allow(clippy::type_complexity)]
+pub fn expander_pcs_init_with_srs_file_testing_only<
+    FieldConfig: FieldEngine,
+    PCS: ExpanderPCS<FieldConfig>,
+>(
+    n_input_vars: usize,
+    mpi_config: &impl MPIEngine,
+    srs_file: Option<&str>,
+) -> (
+    PCS::Params,
+    <PCS::SRS as StructuredReferenceString>::PKey,
+    <PCS::SRS as StructuredReferenceString>::VKey,
+    PCS::ScratchPad,
 ) {
     let mut rng = test_rng();
 
@@ -23,7 +42,7 @@ pub fn expander_pcs_init_testing_only<FieldConfig: FieldEngine, PCS: ExpanderPCS
         &pcs_params,
         mpi_config,
         &mut rng,
-        None,
+        srs_file,
     );
 
     let (pcs_proving_key, pcs_verification_key) = pcs_setup.into_keys();
@@ -37,6 +56,38 @@ pub fn expander_pcs_init_testing_only<FieldConfig: FieldEngine, PCS: ExpanderPCS
     )
 }
 
+/// Same as `expander_pcs_init_testing_only`, generating the SRS from `seed`, so that the keys
+/// are the same across runs and machines without an SRS file.
+#[allow(clippy::type_complexity)]
+pub fn expander_pcs_init_from_seed_testing_only<
+    FieldConfig: FieldEngine,
+    PCS: ExpanderPCS<FieldConfig>,
+>(
+    n_input_vars: usize,
+    mpi_config: &impl MPIEngine,
+    seed: u64,
+) -> (
+    PCS::Params,
+    <PCS::SRS as StructuredReferenceString>::PKey,
+    <PCS::SRS as StructuredReferenceString>::VKey,
+    PCS::ScratchPad,
+) {
+    let pcs_params =
+        <PCS as ExpanderPCS<FieldConfig>>::gen_params(n_input_vars, mpi_config.world_size());
+    let pcs_setup =
+        <PCS as ExpanderPCS<FieldConfig>>::gen_srs_from_seed(&pcs_params, mpi_config, seed);
+
+    let (pcs_proving_key, pcs_verification_key) = pcs_setup.into_keys();
+    let pcs_scratch = <PCS as ExpanderPCS<FieldConfig>>::init_scratch_pad(&pcs_params, mpi_config);
+
+    (
+        pcs_params,
+        pcs_proving_key,
+        pcs_verification_key,
+        pcs_scratch,
+    )
+}
+
 #[inline(always)]
 pub fn lift_poly_to_n_vars<F: Field>(
     poly: &impl MultilinearExtension<F>,
@@ -73,3 +124,58 @@ pub fn lift_poly_and_expander_challenge_to_n_vars<F: FieldEngine>(
         lift_expander_challenge_to_n_vars(expander_challenge, n_vars),
     )
 }
+
+/// The round polynomial of the sumcheck of `f(x) * eq(x)` over the lowest variable, evaluated at
+/// 0, 1 and 2
+#[cfg(any(feature = "basefold", feature = "whir"))]
+#[inline(always)]
+pub(crate) fn sumcheck_round_message<EvalF: Field>(
+    evals: &[EvalF],
+    eq_evals: &[EvalF],
+) -> [EvalF; 3] {
+    evals
+        .chunks(2)
+        .zip(eq_evals.chunks(2))
+        .fold([EvalF::ZERO; 3], |[h0, h1, h2], (f, eq)| {
+            let f_at_2 = f[1].double() - f[0];
+            let eq_at_2 = eq[1].double() - eq[0];
+            [h0 + f[0] * eq[0], h1 + f[1] * eq[1], h2 + f_at_2 * eq_at_2]
+        })
+}
+
+/// Bind the lowest variable of a multilinear polynomial to `r`
+#[cfg(any(feature = "basefold", feature = "whir"))]
+#[inline(always)]
+pub(crate) fn fold_evals<EvalF: Field>(evals: &[EvalF], r: EvalF) -> Vec<EvalF> {
+    evals
+        .chunks(2)
+        .map(|pair| pair[0] + r * (pair[1] - pair[0]))
+        .collect()
+}
+
+/// The quadratic polynomial through `(0, h[0])`, `(1, h[1])` and `(2, h[2])`, evaluated at `r`
+#[cfg(any(feature = "basefold", feature = "whir"))]
+#[inline(always)]
+pub(crate) fn interpolate_at<EvalF: Field>(h: &[EvalF; 3], r: EvalF) -> EvalF {
+    let r_minus_1 = r - EvalF::ONE;
+    let r_minus_2 = r - EvalF::from(2u32);
+    (h[0] * r_minus_1 * r_minus_2 + h[2] * r * r_minus_1) * EvalF::INV_2 - h[1] * r * r_minus_2
+}
+
+/// The evaluations of the global polynomial over the hypercube on the root, None on the other
+/// processes. The SIMD field is the scalar field, the local variables come before the parties,
+/// virtual parties included.
+#[cfg(any(feature = "kzg", feature = "dory", feature = "ipa"))]
+pub(crate) fn gather_evals<F: arith::ExtensionField>(
+    mpi_engine: &impl MPIEngine,
+    poly: &impl MultilinearExtension<F>,
+) -> gkr_engine::MPIResult<Option<Vec<F>>> {
+    let mut buffer = if mpi_engine.is_root() {
+        vec![F::ZERO; poly.hypercube_size() * mpi_engine.n_parties()]
+    } else {
+        vec![]
+    };
+    mpi_engine.gather_padded_vec(poly.hypercube_basis_ref(), &mut buffer)?;
+
+    Ok(mpi_engine.is_root().then_some(buffer))
+}
@@ -0,0 +1,11 @@
+mod utils;
+pub use utils::{WhirCommitment, WhirProof, WhirSRS, WhirScratchPad};
+
+mod whir_impl;
+pub use whir_impl::{whir_commit, whir_open, whir_verify};
+
+mod pcs_trait_impl;
+pub use pcs_trait_impl::WhirPCS;
+
+mod expander_api;
+pub use expander_api::WhirPCSForGKR;
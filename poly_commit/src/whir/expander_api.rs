@@ -0,0 +1,198 @@
+use arith::{FFTField, Field, SimdField};
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, MPIResult,
+    PolynomialCommitmentType, StructuredReferenceString, Transcript,
+};
+use polynomials::MultilinearExtension;
+
+use crate::{
+    utils::{
+        lift_expander_challenge_to_n_vars, lift_poly_and_expander_challenge_to_n_vars,
+        lift_poly_to_n_vars,
+    },
+    whir::{
+        whir_impl::{whir_commit, whir_open, whir_verify},
+        WhirCommitment, WhirPCS, WhirProof, WhirSRS, WhirScratchPad,
+    },
+};
+
+/// The number of variables of the global polynomial, over the SIMD lanes, the local variables
+/// and the parties, virtual parties included
+#[inline(always)]
+fn global_num_vars<C: FieldEngine>(params: usize, world_size: usize) -> usize {
+    params
+        + C::SimdCircuitField::PACK_SIZE.ilog2() as usize
+        + world_size.next_power_of_two().ilog2() as usize
+}
+
+/// The evaluations of the global polynomial over the hypercube, unpacked on the root, None on the
+/// other processes
+fn gather_circuit_vals<C: FieldEngine>(
+    mpi_engine: &impl MPIEngine,
+    poly: &impl MultilinearExtension<C::SimdCircuitField>,
+) -> MPIResult<Option<Vec<C::CircuitField>>> {
+    let mut buffer = if mpi_engine.is_root() {
+        vec![C::SimdCircuitField::ZERO; poly.hypercube_size() * mpi_engine.n_parties()]
+    } else {
+        vec![]
+    };
+    mpi_engine.gather_padded_vec(poly.hypercube_basis_ref(), &mut buffer)?;
+
+    if !mpi_engine.is_root() {
+        return Ok(None);
+    }
+
+    // NOTE: the SIMD lanes are the lowest variables, then the local ones, then the processes
+    Ok(buffer
+        .iter()
+        .flat_map(SimdField::unpack)
+        .collect::<Vec<_>>()
+        .into())
+}
+
+impl<C> ExpanderPCS<C> for WhirPCS<C::CircuitField, C::ChallengeField>
+where
+    C: FieldEngine,
+    C::CircuitField: FFTField,
+{
+    const NAME: &'static str = "WhirPCSForExpanderGKR";
+
+    const PCS_TYPE: PolynomialCommitmentType = PolynomialCommitmentType::Whir;
+
+    type Params = usize;
+    type ScratchPad = WhirScratchPad;
+
+    type Commitment = WhirCommitment;
+    type Opening = WhirProof<C::ChallengeField>;
+    type BatchOpening = ();
+    type SRS = WhirSRS<C::CircuitField>;
+
+    /// NOTE: this is the number of variables for local polynomial w.r.t. SIMD field elements,
+    /// such that the global polynomial has at least a variable to fold.
+    fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params {
+        if global_num_vars::<C>(n_input_vars, world_size) == 0 {
+            1
+        } else {
+            n_input_vars
+        }
+    }
+
+    /// The code is public, the random source is left unused.
+    fn gen_srs(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        _rng: impl rand::RngCore,
+    ) -> Self::SRS {
+        WhirSRS::new(global_num_vars::<C>(*params, mpi_engine.world_size()))
+    }
+
+    fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {
+        Self::ScratchPad::default()
+    }
+
+    fn commit(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Commitment>> {
+        if poly.num_vars() < *params {
+            let poly = lift_poly_to_n_vars(poly, *params);
+            return <Self as ExpanderPCS<C>>::commit(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                scratch_pad,
+            );
+        }
+
+        assert_eq!(
+            global_num_vars::<C>(*params, mpi_engine.world_size()),
+            proving_key.num_vars
+        );
+
+        let Some(evals) = gather_circuit_vals::<C>(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        Ok(whir_commit(proving_key, &evals, scratch_pad).into())
+    }
+
+    fn open(
+        params: &Self::Params,
+        mpi_engine: &impl MPIEngine,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &impl MultilinearExtension<C::SimdCircuitField>,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        transcript: &mut impl Transcript,
+        scratch_pad: &Self::ScratchPad,
+    ) -> MPIResult<Option<Self::Opening>> {
+        if poly.num_vars() < *params {
+            let (poly, eval_point) =
+                lift_poly_and_expander_challenge_to_n_vars(poly, eval_point, *params);
+            return <Self as ExpanderPCS<C>>::open(
+                params,
+                mpi_engine,
+                proving_key,
+                &poly,
+                &eval_point,
+                transcript,
+                scratch_pad,
+            );
+        }
+
+        assert_eq!(
+            global_num_vars::<C>(*params, mpi_engine.world_size()),
+            proving_key.num_vars
+        );
+
+        // NOTE: the opening is run on the root alone, over the gathered polynomial
+        let Some(evals) = gather_circuit_vals::<C>(mpi_engine, poly)? else {
+            return Ok(None);
+        };
+        let (_, opening) = whir_open(
+            proving_key,
+            &evals,
+            &eval_point.global_xs(),
+            transcript,
+            scratch_pad,
+        );
+        Ok(opening.into())
+    }
+
+    fn verify(
+        params: &Self::Params,
+        verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        eval_point: &ExpanderSingleVarChallenge<C>,
+        eval: C::ChallengeField,
+        transcript: &mut impl Transcript,
+        opening: &Self::Opening,
+    ) -> bool {
+        if eval_point.rz.len() < *params {
+            let eval_point = lift_expander_challenge_to_n_vars(eval_point, *params);
+            return <Self as ExpanderPCS<C>>::verify(
+                params,
+                verifying_key,
+                commitment,
+                &eval_point,
+                eval,
+                transcript,
+                opening,
+            );
+        }
+
+        whir_verify(
+            verifying_key,
+            commitment,
+            &eval_point.global_xs(),
+            eval,
+            transcript,
+            opening,
+        )
+    }
+}
+
+pub type WhirPCSForGKR<C> =
+    WhirPCS<<C as FieldEngine>::CircuitField, <C as FieldEngine>::ChallengeField>;
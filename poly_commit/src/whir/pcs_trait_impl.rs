@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+
+use arith::{ExtensionField, FFTField};
+use gkr_engine::{StructuredReferenceString, Transcript};
+use polynomials::MultiLinearPoly;
+
+use crate::{
+    whir::{
+        whir_impl::{whir_commit, whir_open, whir_verify},
+        WhirCommitment, WhirProof, WhirSRS, WhirScratchPad,
+    },
+    PolynomialCommitmentScheme,
+};
+
+impl<F: FFTField> StructuredReferenceString for WhirSRS<F> {
+    type PKey = WhirSRS<F>;
+    type VKey = WhirSRS<F>;
+
+    fn into_keys(self) -> (Self::PKey, Self::VKey) {
+        (self.clone(), self)
+    }
+}
+
+pub struct WhirPCS<F, EvalF>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    _marker_f: PhantomData<F>,
+    _marker_eval_f: PhantomData<EvalF>,
+}
+
+impl<F, EvalF> PolynomialCommitmentScheme<EvalF> for WhirPCS<F, EvalF>
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    const NAME: &'static str = "WhirPCS";
+
+    type Params = usize;
+    type Poly = MultiLinearPoly<F>;
+    type EvalPoint = Vec<EvalF>;
+    type ScratchPad = WhirScratchPad;
+
+    type SRS = WhirSRS<F>;
+    type Commitment = WhirCommitment;
+    type Opening = WhirProof<EvalF>;
+
+    fn gen_srs_for_testing(params: &Self::Params, _rng: impl rand::RngCore) -> (Self::SRS, usize) {
+        (WhirSRS::new(*params), *params)
+    }
+
+    fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {
+        WhirScratchPad::default()
+    }
+
+    fn commit(
+        params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        scratch_pad: &mut Self::ScratchPad,
+    ) -> Self::Commitment {
+        assert_eq!(*params, proving_key.num_vars);
+        whir_commit(proving_key, &poly.coeffs, scratch_pad)
+    }
+
+    fn open(
+        params: &Self::Params,
+        proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
+        poly: &Self::Poly,
+        x: &Self::EvalPoint,
+        scratch_pad: &Self::ScratchPad,
+        transcript: &mut impl Transcript,
+    ) -> (EvalF, Self::Opening) {
+        assert_eq!(*params, proving_key.num_vars);
+        whir_open(proving_key, &poly.coeffs, x, transcript, scratch_pad)
+    }
+
+    fn verify(
+        params: &Self::Params,
+        vk: &<Self::SRS as StructuredReferenceString>::VKey,
+        commitment: &Self::Commitment,
+        x: &Self::EvalPoint,
+        v: EvalF,
+        opening: &Self::Opening,
+        transcript: &mut impl Transcript,
+    ) -> bool {
+        assert_eq!(*params, vk.num_vars);
+        whir_verify(vk, commitment, x, v, transcript, opening)
+    }
+}
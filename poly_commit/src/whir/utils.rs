@@ -0,0 +1,245 @@
+use arith::{ExtensionField, FFTField, FFTTwiddles, Field};
+use serdes::ExpSerde;
+use tree::{Leaf, Node, Path, Tree, LEAF_BYTES};
+
+/// The committed codeword is `2^WHIR_LOG_INV_RATE` times as long as the message.
+pub const WHIR_LOG_INV_RATE: usize = 2;
+
+/// The number of variables folded in a round, the codeword of a round being queried by cosets of
+/// `2^WHIR_FOLDING_FACTOR` entries.
+pub const WHIR_FOLDING_FACTOR: usize = 2;
+
+/*
+ * RELEVANT TYPES SETUP
+ */
+
+/// The Reed-Solomon codes of WHIR (ACFY24), shared by the prover and the verifier.
+///
+/// A multilinear polynomial `f` over `m` variables, whose coefficients in the monomial basis are
+/// `c`, is encoded as the evaluations of the univariate `f(x) = sum_b c_b x^b` over the subgroup
+/// of order `2^(m + WHIR_LOG_INV_RATE)`, i.e., of `f` at `(x, x^2, x^4, ...)`. Each round folds
+/// the lowest `WHIR_FOLDING_FACTOR` variables, and the folded polynomial is encoded over the
+/// subgroup of half the order, so that the rate of the code grows along the rounds.
+///
+/// The code is public, there is no trapdoor.
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct WhirSRS<F: FFTField> {
+    pub num_vars: usize,
+    /// The twiddles of the FFT of the committed codeword
+    pub twiddles: FFTTwiddles<F>,
+}
+
+impl<F: FFTField> WhirSRS<F> {
+    /// The code of the polynomials over `num_vars` variables.
+    ///
+    /// # Panics
+    /// If `num_vars` is zero, as a constant would be committed to without any folding, or if the
+    /// field has no subgroup of the order of the codeword.
+    pub fn new(num_vars: usize) -> Self {
+        assert!(num_vars > 0);
+        assert!(
+            num_vars + WHIR_LOG_INV_RATE <= F::TWO_ADICITY,
+            "The codeword is longer than the largest subgroup of order a power of two"
+        );
+
+        Self {
+            num_vars,
+            twiddles: FFTTwiddles::new(num_vars + WHIR_LOG_INV_RATE),
+        }
+    }
+
+    /// The schedule of the rounds, each folding `round_folds[i]` variables of the polynomial
+    pub fn round_folds(&self) -> Vec<usize> {
+        let mut num_vars = self.num_vars;
+        let mut folds = vec![];
+        while num_vars > 0 {
+            let fold = num_vars.min(WHIR_FOLDING_FACTOR);
+            folds.push(fold);
+            num_vars -= fold;
+        }
+        folds
+    }
+
+    /// The log of the length of the codeword of `round`, halved from a round to the next
+    #[inline(always)]
+    pub fn log_codeword_len(&self, round: usize) -> usize {
+        self.num_vars + WHIR_LOG_INV_RATE - round
+    }
+
+    /// The generator of the evaluation domain of `round`
+    #[inline(always)]
+    pub fn domain_generator(&self, round: usize) -> F {
+        F::two_adic_generator(self.log_codeword_len(round))
+    }
+
+    /// Encode the coefficients of a multilinear polynomial over the committed domain
+    pub fn encode(&self, coeffs: &[F]) -> Vec<F> {
+        assert_eq!(coeffs.len(), 1 << self.num_vars);
+
+        let mut codeword = coeffs.to_vec();
+        codeword.resize(1 << self.twiddles.log_n, F::ZERO);
+        self.twiddles.fft_in_place(&mut codeword);
+        codeword
+    }
+
+    /// Encode the coefficients of the folded polynomial of `round` over its domain, limb by limb
+    pub fn encode_ext<EvalF>(&self, round: usize, coeffs: &[EvalF]) -> Vec<EvalF>
+    where
+        EvalF: ExtensionField<BaseField = F>,
+    {
+        let codeword_len = 1 << self.log_codeword_len(round);
+        let limbs: Vec<Vec<F>> = coeffs.iter().map(|c| c.to_limbs()).collect();
+        let encoded_limbs: Vec<Vec<F>> = (0..EvalF::DEGREE)
+            .map(|i| {
+                let mut codeword: Vec<F> = limbs.iter().map(|l| l[i]).collect();
+                codeword.resize(codeword_len, F::ZERO);
+                F::fft_in_place(&mut codeword);
+                codeword
+            })
+            .collect();
+
+        (0..codeword_len)
+            .map(|j| {
+                let limbs: Vec<F> = encoded_limbs.iter().map(|c| c[j]).collect();
+                EvalF::from_limbs(&limbs)
+            })
+            .collect()
+    }
+}
+
+pub type WhirCommitment = Node;
+
+/// The Merkle tree over the committed codeword, kept by the prover between commit and open
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct WhirScratchPad {
+    pub codeword_tree: Tree,
+}
+
+#[derive(Clone, Debug, Default, ExpSerde)]
+pub struct WhirProof<EvalF: Field> {
+    /// The round polynomials of the sumchecks of `f(x) * w(x)`, evaluated at 0, 1 and 2, all the
+    /// rounds one after the other
+    pub sumcheck_messages: Vec<[EvalF; 3]>,
+    /// The roots of the trees of the codewords of the folded polynomials, but the last one
+    pub fold_roots: Vec<Node>,
+    /// The evaluations of the folded polynomials at the out of domain samples
+    pub ood_answers: Vec<EvalF>,
+    /// For each round, the paths to the queried cosets of the codeword of the round
+    pub query_paths: Vec<Vec<Path>>,
+    /// The polynomial folded over all the variables, a constant
+    pub final_eval: EvalF,
+}
+
+/// The number of queries to the codeword of rate `2^-log_inv_rate` for `soundness_bits` bits of
+/// security, in the unique decoding regime, see `basefold_query_complexity`
+#[inline(always)]
+pub(crate) fn whir_query_complexity(soundness_bits: usize, log_inv_rate: usize) -> usize {
+    let rate = 1f64 / (1 << log_inv_rate) as f64;
+    let avg_case_dist = (1f64 - rate) / 2f64;
+    let sec_bits = -(1f64 - avg_case_dist).log2();
+
+    (soundness_bits as f64 / sec_bits).ceil() as usize
+}
+
+/// The coefficients in the monomial basis of the multilinear polynomial of `evals` over the
+/// hypercube, the variables being LSB first
+pub(crate) fn evals_to_coeffs<F: Field>(evals: &[F]) -> Vec<F> {
+    let mut coeffs = evals.to_vec();
+    let num_vars = coeffs.len().ilog2();
+    (0..num_vars).for_each(|i| {
+        let stride = 1 << i;
+        coeffs.chunks_mut(2 * stride).for_each(|chunk| {
+            let (lo, hi) = chunk.split_at_mut(stride);
+            hi.iter_mut().zip(lo.iter()).for_each(|(h, l)| *h -= *l);
+        })
+    });
+    coeffs
+}
+
+/// The univariate of the coefficients `coeffs` evaluated at `x`
+#[inline(always)]
+pub(crate) fn eval_univariate<EvalF: Field>(coeffs: &[EvalF], x: EvalF) -> EvalF {
+    coeffs.iter().rev().fold(EvalF::ZERO, |acc, c| acc * x + c)
+}
+
+/// `(x, x^2, x^4, ...)` over `num_vars` variables, where the multilinear polynomial of
+/// coefficients `c` is the univariate of coefficients `c` at `x`
+#[inline(always)]
+pub(crate) fn pow_point<EvalF: Field>(x: EvalF, num_vars: usize) -> Vec<EvalF> {
+    (0..num_vars)
+        .scan(x, |power, _| {
+            let current = *power;
+            *power = power.square();
+            Some(current)
+        })
+        .collect()
+}
+
+/// The tree over the cosets of a codeword that are folded together by `fold` variables, i.e.,
+/// with leaf `i` holding the entries `i + j * len / 2^fold`
+pub(crate) fn coset_tree<V: Field>(codeword: &[V], fold: usize) -> Tree {
+    let num_cosets = codeword.len() >> fold;
+    Tree::new_with_leaves(
+        (0..num_cosets)
+            .map(|i| coset_leaf(codeword.iter().skip(i).step_by(num_cosets), 1 << fold))
+            .collect(),
+    )
+}
+
+#[inline(always)]
+pub(crate) fn coset_leaf<'a, V: Field + 'a>(
+    coset: impl Iterator<Item = &'a V>,
+    coset_len: usize,
+) -> Leaf {
+    assert!(coset_len * V::SIZE <= LEAF_BYTES);
+
+    let mut data = [0u8; LEAF_BYTES];
+    let mut writer = &mut data[..];
+    coset.for_each(|v| v.serialize_into(&mut writer).unwrap());
+    Leaf::new(data)
+}
+
+/// The coset of `coset_len` entries in a leaf of `coset_tree`, or None if the leaf does not hold
+/// as many field elements
+#[inline(always)]
+pub(crate) fn leaf_coset<V: Field>(leaf: &Leaf, coset_len: usize) -> Option<Vec<V>> {
+    let mut reader = &leaf.data[..];
+    (0..coset_len)
+        .map(|_| V::deserialize_from(&mut reader).ok())
+        .collect()
+}
+
+/// Fold the coset of entries `coset[j]` at the points `x * step^j` of a codeword by the
+/// `challenges`, one variable at a time from the lowest one, into the entry at `x^(2^k)` of the
+/// codeword of the folded polynomial, with `k` the number of challenges.
+pub(crate) fn fold_coset<F, EvalF>(coset: &[EvalF], x: F, step: F, challenges: &[EvalF]) -> EvalF
+where
+    F: Field,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    assert_eq!(coset.len(), 1 << challenges.len());
+
+    let mut values = coset.to_vec();
+    let mut points: Vec<F> = (0..coset.len())
+        .scan(x, |point, _| {
+            let current = *point;
+            *point *= step;
+            Some(current)
+        })
+        .collect();
+
+    for &r in challenges {
+        // NOTE: the entries at x and -x are the halves apart, f(x) = f_e(x^2) + x * f_o(x^2)
+        let half = values.len() / 2;
+        values = (0..half)
+            .map(|j| {
+                let (lo, hi) = (values[j], values[j + half]);
+                let odd = (lo - hi).mul_by_base_field(&points[j].inv().unwrap());
+                (lo + hi + r * odd) * EvalF::INV_2
+            })
+            .collect();
+        points.truncate(half);
+        points.iter_mut().for_each(|p| *p = p.square());
+    }
+    values[0]
+}
@@ -0,0 +1,274 @@
+use std::iter;
+
+use arith::{ExtensionField, FFTField, Field};
+use gkr_engine::Transcript;
+use polynomials::EqPolynomial;
+use tree::Tree;
+
+use crate::{
+    utils::{fold_evals, interpolate_at, sumcheck_round_message},
+    whir::{
+        utils::{
+            coset_tree, eval_univariate, evals_to_coeffs, fold_coset, leaf_coset, pow_point,
+            whir_query_complexity,
+        },
+        WhirCommitment, WhirProof, WhirSRS, WhirScratchPad,
+    },
+    PCS_SOUNDNESS_BITS,
+};
+
+/// Commit to the evaluations of a multilinear polynomial over the hypercube, by the root of the
+/// tree over the cosets of its codeword
+pub fn whir_commit<F: FFTField>(
+    srs: &WhirSRS<F>,
+    evals: &[F],
+    scratch_pad: &mut WhirScratchPad,
+) -> WhirCommitment {
+    assert_eq!(evals.len(), 1 << srs.num_vars);
+
+    let codeword = srs.encode(&evals_to_coeffs(evals));
+    scratch_pad.codeword_tree = coset_tree(&codeword, srs.round_folds()[0]);
+    scratch_pad.codeword_tree.root()
+}
+
+/// Open the committed polynomial at `point`.
+///
+/// Each round runs the sumcheck of `f(x) * w(x)` over the variables it folds, `w` being
+/// `eq(x, point)` to start with, commits to the folded polynomial, and samples it out of the
+/// domain. The codeword of the round is then queried by cosets, each folding into an entry of the
+/// folded codeword, and these entries and the sample are added to `w` at random, to be checked
+/// by the rounds that follow, down to the constant the last round folds to.
+pub fn whir_open<F, EvalF>(
+    srs: &WhirSRS<F>,
+    evals: &[F],
+    point: &[EvalF],
+    transcript: &mut impl Transcript,
+    scratch_pad: &WhirScratchPad,
+) -> (EvalF, WhirProof<EvalF>)
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let folds = srs.round_folds();
+    assert_eq!(evals.len(), 1 << srs.num_vars);
+    assert_eq!(point.len(), srs.num_vars);
+
+    let mut coeffs: Vec<EvalF> = evals_to_coeffs(evals)
+        .into_iter()
+        .map(EvalF::from)
+        .collect();
+    let mut evals: Vec<EvalF> = evals.iter().map(|&e| EvalF::from(e)).collect();
+    let mut weights = EqPolynomial::build_eq_x_r(point);
+    let eval = evals.iter().zip(&weights).map(|(f, w)| *f * *w).sum();
+
+    let mut proof = WhirProof::default();
+    let mut fold_trees: Vec<Tree> = Vec::with_capacity(folds.len() - 1);
+    for (round, &fold) in folds.iter().enumerate() {
+        let log_inv_rate = srs.log_codeword_len(round) - evals.len().ilog2() as usize;
+        for _ in 0..fold {
+            let message = sumcheck_round_message(&evals, &weights);
+            message
+                .iter()
+                .for_each(|h| transcript.append_field_element(h));
+            proof.sumcheck_messages.push(message);
+
+            let r = transcript.generate_field_element::<EvalF>();
+            evals = fold_evals(&evals, r);
+            weights = fold_evals(&weights, r);
+            coeffs = fold_coeffs(&coeffs, r);
+        }
+
+        // NOTE: commit to the folded polynomial and sample it out of the domain, but in the last
+        // round, where it is a constant sent in the clear
+        let ood_sample = if round + 1 == folds.len() {
+            proof.final_eval = coeffs[0];
+            transcript.append_field_element(&proof.final_eval);
+            None
+        } else {
+            let tree = coset_tree(&srs.encode_ext(round + 1, &coeffs), folds[round + 1]);
+            transcript.append_u8_slice(tree.root().as_bytes());
+            proof.fold_roots.push(tree.root());
+            fold_trees.push(tree);
+
+            let z = transcript.generate_field_element::<EvalF>();
+            let y = eval_univariate(&coeffs, z);
+            transcript.append_field_element(&y);
+            proof.ood_answers.push(y);
+            Some(z)
+        };
+
+        let num_cosets = 1 << (srs.log_codeword_len(round) - fold);
+        let query_num = whir_query_complexity(PCS_SOUNDNESS_BITS, log_inv_rate).min(num_cosets);
+        let query_indices = transcript.challenge_queries(num_cosets, query_num, true);
+        let tree = match round {
+            0 => &scratch_pad.codeword_tree,
+            _ => &fold_trees[round - 1],
+        };
+        proof.query_paths.push(
+            query_indices
+                .iter()
+                .map(|&index| tree.index_query(index))
+                .collect(),
+        );
+
+        let Some(z) = ood_sample else {
+            break;
+        };
+
+        // the sample and the queried entries of the folded codeword are checked by the next
+        // rounds, at the points (x, x^2, x^4, ...) of the folded polynomial
+        let gamma = transcript.generate_field_element::<EvalF>();
+        let num_vars = evals.len().ilog2() as usize;
+        let folded_generator = srs.domain_generator(round).exp(1 << fold);
+        let points = iter::once(z).chain(
+            query_indices
+                .iter()
+                .map(|&index| EvalF::from(folded_generator.exp(index as u128))),
+        );
+        let mut gamma_power = gamma;
+        points.for_each(|x| {
+            let eq_evals = EqPolynomial::build_eq_x_r(&pow_point(x, num_vars));
+            weights
+                .iter_mut()
+                .zip(eq_evals)
+                .for_each(|(w, eq)| *w += gamma_power * eq);
+            gamma_power *= gamma;
+        });
+    }
+
+    (eval, proof)
+}
+
+/// Verify the opening of the committed polynomial at `point` to `evaluation`
+pub fn whir_verify<F, EvalF>(
+    srs: &WhirSRS<F>,
+    commitment: &WhirCommitment,
+    point: &[EvalF],
+    evaluation: EvalF,
+    transcript: &mut impl Transcript,
+    proof: &WhirProof<EvalF>,
+) -> bool
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let folds = srs.round_folds();
+    if point.len() != srs.num_vars
+        || proof.sumcheck_messages.len() != srs.num_vars
+        || proof.fold_roots.len() != folds.len() - 1
+        || proof.ood_answers.len() != folds.len() - 1
+        || proof.query_paths.len() != folds.len()
+    {
+        return false;
+    }
+
+    // NOTE: the terms of `w`, each an eq polynomial at a point, over the variables left after
+    // the first `offset` challenges
+    let mut weights: Vec<(EvalF, Vec<EvalF>, usize)> = vec![(EvalF::ONE, point.to_vec(), 0)];
+    let mut claim = evaluation;
+    let mut challenges = Vec::with_capacity(srs.num_vars);
+    let mut messages = proof.sumcheck_messages.iter();
+    for (round, &fold) in folds.iter().enumerate() {
+        let log_inv_rate = srs.log_codeword_len(round) - (srs.num_vars - challenges.len());
+        let round_start = challenges.len();
+        for message in messages.by_ref().take(fold) {
+            if message[0] + message[1] != claim {
+                return false;
+            }
+
+            message
+                .iter()
+                .for_each(|h| transcript.append_field_element(h));
+            let r = transcript.generate_field_element::<EvalF>();
+            claim = interpolate_at(message, r);
+            challenges.push(r);
+        }
+
+        let ood_sample = if round + 1 == folds.len() {
+            transcript.append_field_element(&proof.final_eval);
+            None
+        } else {
+            transcript.append_u8_slice(proof.fold_roots[round].as_bytes());
+            let z = transcript.generate_field_element::<EvalF>();
+            transcript.append_field_element(&proof.ood_answers[round]);
+            Some((z, proof.ood_answers[round]))
+        };
+
+        let num_cosets = 1 << (srs.log_codeword_len(round) - fold);
+        let query_num = whir_query_complexity(PCS_SOUNDNESS_BITS, log_inv_rate).min(num_cosets);
+        let query_indices = transcript.challenge_queries(num_cosets, query_num, true);
+        let paths = &proof.query_paths[round];
+        if paths.len() != query_indices.len() {
+            return false;
+        }
+
+        // NOTE: fold each queried coset of the codeword of the round by the challenges of the round
+        let root = match round {
+            0 => *commitment,
+            _ => proof.fold_roots[round - 1],
+        };
+        let generator = srs.domain_generator(round);
+        let step = generator.exp(num_cosets as u128);
+        let Some(folded) = query_indices
+            .iter()
+            .zip(paths)
+            .map(|(&index, path)| {
+                if path.index != index || path.root() != root {
+                    return None;
+                }
+                let coset: Vec<EvalF> = match round {
+                    0 => leaf_coset::<F>(&path.leaf, 1 << fold)?
+                        .into_iter()
+                        .map(EvalF::from)
+                        .collect(),
+                    _ => leaf_coset::<EvalF>(&path.leaf, 1 << fold)?,
+                };
+                Some(fold_coset(
+                    &coset,
+                    generator.exp(index as u128),
+                    step,
+                    &challenges[round_start..],
+                ))
+            })
+            .collect::<Option<Vec<EvalF>>>()
+        else {
+            return false;
+        };
+
+        let Some((z, y)) = ood_sample else {
+            // the codeword of the last round folds into the constant
+            if folded.iter().any(|v| *v != proof.final_eval) {
+                return false;
+            }
+            break;
+        };
+
+        let gamma = transcript.generate_field_element::<EvalF>();
+        let num_vars = srs.num_vars - challenges.len();
+        let folded_generator = generator.exp(1 << fold);
+        let answers = iter::once((z, y)).chain(
+            query_indices
+                .iter()
+                .zip(folded)
+                .map(|(&index, v)| (EvalF::from(folded_generator.exp(index as u128)), v)),
+        );
+        let mut gamma_power = gamma;
+        answers.for_each(|(x, v)| {
+            claim += gamma_power * v;
+            weights.push((gamma_power, pow_point(x, num_vars), challenges.len()));
+            gamma_power *= gamma;
+        });
+    }
+
+    let weight: EvalF = weights
+        .iter()
+        .map(|(coeff, point, offset)| *coeff * EqPolynomial::eq_vec(point, &challenges[*offset..]))
+        .sum();
+    claim == proof.final_eval * weight
+}
+
+/// Bind the lowest variable of a multilinear polynomial in the monomial basis to `r`
+#[inline(always)]
+fn fold_coeffs<EvalF: Field>(coeffs: &[EvalF], r: EvalF) -> Vec<EvalF> {
+    coeffs.chunks(2).map(|pair| pair[0] + r * pair[1]).collect()
+}
@@ -7,6 +7,7 @@ use gkr_engine::{
 use poly_commit::{BatchOpeningPCS, PolynomialCommitmentScheme};
 use polynomials::{MultiLinearPoly, MultilinearExtension};
 use rand::{thread_rng, RngCore};
+use serdes::ExpSerde;
 
 #[allow(dead_code)]
 pub fn test_pcs<F: ExtensionField, T: Transcript, P: PolynomialCommitmentScheme<F>>(
@@ -204,6 +205,27 @@ where
     }
 }
 
+/// The seed of the SRS of the tests, the same on all processes
+#[allow(dead_code)]
+pub const SRS_SEED: u64 = 0x5eed;
+
+/// The SRS generated from a seed is the same for the same seed only
+#[allow(dead_code)]
+pub fn test_srs_from_seed<C: FieldEngine, P: ExpanderPCS<C>>(n_input_vars: usize) {
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let params = P::gen_params(n_input_vars, mpi_config.world_size());
+    let srs_bytes = |seed| {
+        let mut bytes = vec![];
+        P::gen_srs_from_seed(&params, &mpi_config, seed)
+            .serialize_into(&mut bytes)
+            .unwrap();
+        bytes
+    };
+
+    assert_eq!(srs_bytes(SRS_SEED), srs_bytes(SRS_SEED));
+    assert_ne!(srs_bytes(SRS_SEED), srs_bytes(SRS_SEED + 1));
+}
+
 #[allow(dead_code)]
 pub fn test_pcs_for_expander_gkr<C: FieldEngine, T: Transcript, P: ExpanderPCS<C>>(
     params: &P::Params,
@@ -211,15 +233,13 @@ pub fn test_pcs_for_expander_gkr<C: FieldEngine, T: Transcript, P: ExpanderPCS<C
     transcript: &mut T,
     poly: &impl MultilinearExtension<C::SimdCircuitField>,
     xs: &[ExpanderSingleVarChallenge<C>],
-    file: Option<&str>,
 ) {
-    let mut rng = test_rng();
     // NOTE(HS) we assume that the polynomials we pass in are of sufficient length.
-    let srs = P::gen_or_load_srs_for_testing(params, mpi_config, &mut rng, file);
+    let srs = P::gen_srs_from_seed(params, mpi_config, SRS_SEED);
     let (proving_key, verification_key) = srs.into_keys();
     let mut scratch_pad = P::init_scratch_pad(params, mpi_config);
 
-    let commitment = P::commit(params, mpi_config, &proving_key, poly, &mut scratch_pad);
+    let commitment = P::commit(params, mpi_config, &proving_key, poly, &mut scratch_pad).unwrap();
 
     // PCSForExpanderGKR does not require an evaluation value for the opening function
     // We use RawExpanderGKR as the golden standard for the evaluation value
@@ -229,7 +249,9 @@ pub fn test_pcs_for_expander_gkr<C: FieldEngine, T: Transcript, P: ExpanderPCS<C
     } else {
         vec![]
     };
-    mpi_config.gather_vec(poly.hypercube_basis_ref(), &mut coeffs_gathered);
+    mpi_config
+        .gather_vec(poly.hypercube_basis_ref(), &mut coeffs_gathered)
+        .unwrap();
 
     for xx in xs {
         let mut transcript_cloned = transcript.clone();
@@ -243,7 +265,8 @@ pub fn test_pcs_for_expander_gkr<C: FieldEngine, T: Transcript, P: ExpanderPCS<C
             xx,
             transcript,
             &mut scratch_pad,
-        );
+        )
+        .unwrap();
         transcript.unlock_proof();
 
         if mpi_config.is_root() {
@@ -296,7 +319,9 @@ where
                 .iter()
                 .map(|poly| {
                     let params = P::gen_params(poly.num_vars(), 1);
-                    P::commit(&params, &mpi_config, &proving_key, poly, &mut scratch_pad).unwrap()
+                    P::commit(&params, &mpi_config, &proving_key, poly, &mut scratch_pad)
+                        .unwrap()
+                        .unwrap()
                 })
                 .collect::<Vec<_>>();
 
@@ -321,7 +346,8 @@ where
                 &challenge_points,
                 &mut scratch_pad,
                 &mut transcript,
-            );
+            )
+            .unwrap();
 
             let mut transcript = T::new();
 
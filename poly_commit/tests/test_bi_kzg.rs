@@ -1,3 +1,5 @@
+#![cfg(feature = "kzg")]
+
 mod common;
 
 use arith::{Field, Fr};
@@ -75,7 +77,6 @@ fn test_hyper_bi_kzg_for_expander_gkr_generics(mpi_config_ref: &MPIConfig, total
         &mut transcript,
         &local_poly,
         &[challenge_point],
-        None,
     );
 }
 
@@ -0,0 +1,126 @@
+#![cfg(feature = "brakedown")]
+
+mod common;
+
+use arith::{Field, SimdField};
+use ark_std::test_rng;
+use gf2::GF2x128;
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GF2ExtConfig, Goldilocksx8Config,
+    M31x16Config, MPIConfig, MPIEngine, Transcript,
+};
+use gkr_hashers::Keccak256hasher;
+use goldilocks::Goldilocksx8;
+use mersenne31::M31x16;
+use poly_commit::*;
+use polynomials::MultiLinearPoly;
+use transcript::BytesHashTranscript;
+
+fn test_brakedown_for_expander_gkr_generics<C, ComPackF, T>(
+    mpi_config_ref: &MPIConfig,
+    total_num_vars: usize,
+) where
+    C: FieldEngine,
+    ComPackF: SimdField<Scalar = C::CircuitField>,
+    T: Transcript,
+{
+    let mut rng = test_rng();
+
+    // NOTE: generate global random polynomial
+    let num_vars_in_simd = C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+    let num_vars_in_mpi = mpi_config_ref.world_size().ilog2() as usize;
+    let num_vars_in_each_poly = total_num_vars - num_vars_in_mpi - num_vars_in_simd;
+    let num_vars_in_global_poly = total_num_vars - num_vars_in_simd;
+
+    let global_poly =
+        MultiLinearPoly::<C::SimdCircuitField>::random(num_vars_in_global_poly, &mut rng);
+
+    // NOTE generate srs for each party, and shared challenge point in each party
+    let challenge_point = ExpanderSingleVarChallenge::<C> {
+        r_mpi: (0..num_vars_in_mpi)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        r_simd: (0..num_vars_in_simd)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        rz: (0..num_vars_in_each_poly)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+    };
+
+    let mut transcript = T::new();
+
+    // NOTE separate polynomial into different pieces by mpi rank
+    let poly_vars_stride = (1 << global_poly.get_num_vars()) / mpi_config_ref.world_size();
+    let poly_coeff_starts = mpi_config_ref.world_rank() * poly_vars_stride;
+    let poly_coeff_ends = poly_coeff_starts + poly_vars_stride;
+    let local_poly =
+        MultiLinearPoly::new(global_poly.coeffs[poly_coeff_starts..poly_coeff_ends].to_vec());
+
+    common::test_pcs_for_expander_gkr::<C, T, BrakedownPCSForGKR<C, ComPackF>>(
+        &num_vars_in_each_poly,
+        mpi_config_ref,
+        &mut transcript,
+        &local_poly,
+        &[challenge_point],
+    );
+}
+
+#[test]
+fn test_brakedown_for_expander_gkr() {
+    let universe = MPIConfig::init().unwrap();
+    let world = universe.world();
+    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+
+    test_brakedown_for_expander_gkr_generics::<
+        GF2ExtConfig,
+        GF2x128,
+        BytesHashTranscript<Keccak256hasher>,
+    >(&mpi_config, 25);
+
+    test_brakedown_for_expander_gkr_generics::<
+        M31x16Config,
+        M31x16,
+        BytesHashTranscript<Keccak256hasher>,
+    >(&mpi_config, 22);
+
+    test_brakedown_for_expander_gkr_generics::<
+        Goldilocksx8Config,
+        Goldilocksx8,
+        BytesHashTranscript<Keccak256hasher>,
+    >(&mpi_config, 22);
+}
+
+#[test]
+fn test_brakedown_code_rate() {
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let params =
+        <BrakedownPCSForGKR<M31x16Config, M31x16> as ExpanderPCS<M31x16Config>>::gen_params(18, 1);
+
+    // the same messages are encoded into shorter codewords than the ones of Orion
+    let brakedown_srs =
+        <BrakedownPCSForGKR<M31x16Config, M31x16> as ExpanderPCS<M31x16Config>>::gen_srs_from_seed(
+            &params,
+            &mpi_config,
+            0,
+        );
+    let orion_srs =
+        <OrionPCSForGKR<M31x16Config, M31x16> as ExpanderPCS<M31x16Config>>::gen_srs_from_seed(
+            &params,
+            &mpi_config,
+            0,
+        );
+    assert_eq!(
+        brakedown_srs.code_instance.msg_len(),
+        orion_srs.code_instance.msg_len()
+    );
+    assert!(brakedown_srs.code_instance.code_len() < orion_srs.code_instance.code_len());
+    assert!(
+        brakedown_srs.code_instance.hamming_weight() < orion_srs.code_instance.hamming_weight()
+    );
+}
+
+#[test]
+fn test_brakedown_srs_from_seed() {
+    common::test_srs_from_seed::<M31x16Config, BrakedownPCSForGKR<M31x16Config, M31x16>>(12);
+}
@@ -1,11 +1,16 @@
+#![cfg(feature = "hyrax")]
+
 mod common;
 
 use arith::{Field, Fr};
 use ark_std::test_rng;
-use gkr_engine::{BN254Config, ExpanderSingleVarChallenge, MPIConfig, MPIEngine, Transcript};
+use gkr_engine::{
+    BN254Config, BatchOpenError, ChallengeShapeError, ExpanderPCS, ExpanderSingleVarChallenge,
+    MPIConfig, MPIEngine, StructuredReferenceString, Transcript,
+};
 use gkr_hashers::Keccak256hasher;
 use halo2curves::bn256::G1Affine;
-use poly_commit::HyraxPCS;
+use poly_commit::{HyraxCommitment, HyraxPCS, HyraxShape, PolynomialCommitmentScheme};
 use polynomials::MultiLinearPoly;
 use transcript::BytesHashTranscript;
 
@@ -31,6 +36,129 @@ fn test_hyrax_pcs_e2e() {
     test_hyrax_pcs_generics(1, 17)
 }
 
+#[test]
+fn test_hyrax_pcs_shapes() {
+    let mut rng = test_rng();
+    let num_vars = 9;
+    let poly = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+    let x: Vec<_> = (0..num_vars).map(|_| Fr::random_unsafe(&mut rng)).collect();
+
+    assert_eq!(HyraxShape::Auto.row_vars(num_vars, 0), 5);
+    assert_eq!(HyraxShape::Auto.row_vars(num_vars, 12), num_vars);
+
+    // from a single row to rows of a single value, the SRS fixing the shape
+    for row_vars in [num_vars, 7, 2, 0] {
+        let shape = HyraxShape::RowVars(row_vars);
+        assert_eq!(shape.row_vars(num_vars, 0), row_vars);
+        let srs = HyraxPCS::<G1Affine>::gen_srs_with_shape(num_vars, 0, shape, &mut rng);
+
+        let commitment = HyraxPCS::commit(&num_vars, &srs, &poly, &mut ());
+        assert_eq!(commitment.0.len(), 1 << (num_vars - row_vars));
+
+        let mut transcript = BytesHashTranscript::<Keccak256hasher>::new();
+        let (v, opening) = HyraxPCS::open(&num_vars, &srs, &poly, &x, &(), &mut transcript);
+        assert_eq!(opening.0.len(), 1 << row_vars);
+        assert_eq!(v, poly.evaluate_jolt(&x));
+
+        let mut transcript = BytesHashTranscript::<Keccak256hasher>::new();
+        assert!(HyraxPCS::verify(
+            &num_vars,
+            &srs,
+            &commitment,
+            &x,
+            v,
+            &opening,
+            &mut transcript
+        ));
+    }
+}
+
+#[test]
+fn test_hyrax_aggregated_opening() {
+    let mut rng = test_rng();
+    let num_vars = 10;
+    let num_shards = 4;
+    let (srs, _) = <HyraxPCS<G1Affine> as PolynomialCommitmentScheme<Fr>>::gen_srs_for_testing(
+        &num_vars, &mut rng,
+    );
+
+    let polys: Vec<_> = (0..num_shards)
+        .map(|_| MultiLinearPoly::<Fr>::random(num_vars, &mut rng))
+        .collect();
+    let commitments: Vec<_> = polys
+        .iter()
+        .map(|poly| {
+            <HyraxPCS<G1Affine> as PolynomialCommitmentScheme<Fr>>::commit(
+                &num_vars,
+                &srs,
+                poly,
+                &mut (),
+            )
+        })
+        .collect();
+    let scalars: Vec<_> = (0..num_shards)
+        .map(|_| Fr::random_unsafe(&mut rng))
+        .collect();
+    let x: Vec<_> = (0..num_vars).map(|_| Fr::random_unsafe(&mut rng)).collect();
+
+    // the aggregated commitment is that of the aggregated polynomial
+    let aggregated_poly = MultiLinearPoly::new(
+        (0..1 << num_vars)
+            .map(|i| {
+                polys
+                    .iter()
+                    .zip(scalars.iter())
+                    .map(|(poly, s)| poly.coeffs[i] * s)
+                    .sum()
+            })
+            .collect(),
+    );
+    let aggregated = HyraxCommitment::aggregate(&commitments, &scalars);
+    let expected = <HyraxPCS<G1Affine> as PolynomialCommitmentScheme<Fr>>::commit(
+        &num_vars,
+        &srs,
+        &aggregated_poly,
+        &mut (),
+    );
+    assert_eq!(aggregated.0, expected.0);
+
+    let (v, opening) = HyraxPCS::open_aggregated(&srs, &polys, &scalars, &x);
+    let evals: Fr = polys
+        .iter()
+        .zip(scalars.iter())
+        .map(|(poly, s)| poly.evaluate_jolt(&x) * s)
+        .sum();
+    assert_eq!(v, evals);
+    assert!(HyraxPCS::verify_aggregated(
+        &srs,
+        &commitments,
+        &scalars,
+        &x,
+        v,
+        &opening
+    ));
+
+    // the opening is bound to the scalars and the evaluation
+    let mut wrong_scalars = scalars.clone();
+    wrong_scalars[1] += Fr::ONE;
+    assert!(!HyraxPCS::verify_aggregated(
+        &srs,
+        &commitments,
+        &wrong_scalars,
+        &x,
+        v,
+        &opening
+    ));
+    assert!(!HyraxPCS::verify_aggregated(
+        &srs,
+        &commitments,
+        &scalars,
+        &x,
+        v + Fr::ONE,
+        &opening
+    ));
+}
+
 fn test_hyrax_for_expander_gkr_generics(mpi_config_ref: &MPIConfig, total_num_vars: usize) {
     let mut rng = test_rng();
 
@@ -70,10 +198,14 @@ fn test_hyrax_for_expander_gkr_generics(mpi_config_ref: &MPIConfig, total_num_va
         &mut transcript,
         &local_poly,
         &[challenge_point],
-        Some("../data/hyrax_srs.bin"),
     );
 }
 
+#[test]
+fn test_hyrax_srs_from_seed() {
+    common::test_srs_from_seed::<BN254Config, HyraxPCS<G1Affine>>(10);
+}
+
 #[test]
 fn test_hyrax_for_expander_gkr() {
     let universe = MPIConfig::init().unwrap();
@@ -91,3 +223,79 @@ fn test_hyrax_batch_open() {
         HyraxPCS<G1Affine>,
     >(false);
 }
+
+#[test]
+fn test_hyrax_batch_challenge_shape() {
+    // NOTE: in scope here only, the scheme of the other tests is `PolynomialCommitmentScheme`
+    use gkr_engine::ExpanderPCS;
+
+    let mut rng = test_rng();
+    let num_vars = 4;
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let srs =
+        <HyraxPCS<G1Affine> as ExpanderPCS<BN254Config>>::gen_srs(&num_vars, &mpi_config, &mut rng);
+    let (proving_key, verification_key) = srs.into_keys();
+    let poly = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+    let commitment = <HyraxPCS<G1Affine> as ExpanderPCS<BN254Config>>::commit(
+        &num_vars,
+        &mpi_config,
+        &proving_key,
+        &poly,
+        &mut (),
+    )
+    .unwrap()
+    .unwrap();
+    let point = ExpanderSingleVarChallenge::<BN254Config>::new(
+        (0..num_vars).map(|_| Fr::random_unsafe(&mut rng)).collect(),
+        vec![],
+        vec![],
+    );
+
+    // a SIMD variable for the single lane of BN254 is rejected before the opening
+    let mut simd_point = point.clone();
+    simd_point.r_simd.push(Fr::random_unsafe(&mut rng));
+    assert!(matches!(
+        <HyraxPCS<G1Affine> as ExpanderPCS<BN254Config>>::multi_points_batch_open(
+            &num_vars,
+            &mpi_config,
+            &proving_key,
+            &[poly.clone()],
+            &[simd_point],
+            &(),
+            &mut BytesHashTranscript::<Keccak256hasher>::new(),
+        )
+        .unwrap_err(),
+        BatchOpenError::ChallengeShapeError(ChallengeShapeError::SimdVars {
+            n_lanes: 1,
+            expected: 0,
+            got: 1
+        })
+    ));
+
+    let (evals, opening) =
+        <HyraxPCS<G1Affine> as ExpanderPCS<BN254Config>>::multi_points_batch_open(
+            &num_vars,
+            &mpi_config,
+            &proving_key,
+            &[poly],
+            &[point.clone()],
+            &(),
+            &mut BytesHashTranscript::<Keccak256hasher>::new(),
+        )
+        .unwrap();
+
+    // the opening does not verify at a point over the processes of another world
+    let mut mpi_point = point;
+    mpi_point.r_mpi.push(Fr::random_unsafe(&mut rng));
+    assert!(
+        !<HyraxPCS<G1Affine> as ExpanderPCS<BN254Config>>::multi_points_batch_verify(
+            &num_vars,
+            &verification_key,
+            &[commitment],
+            &[mpi_point],
+            &evals,
+            &opening,
+            &mut BytesHashTranscript::<Keccak256hasher>::new(),
+        )
+    );
+}
@@ -0,0 +1,98 @@
+use arith::Field;
+use ark_std::test_rng;
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, M31x16Config, MPIConfig,
+    StructuredReferenceString, Transcript,
+};
+use gkr_hashers::SHA256hasher;
+use mersenne31::{M31Ext3, M31x16};
+use poly_commit::{open_in_layout, verify_in_layout, RawExpanderGKR};
+use polynomials::{HypercubeLayout, MultiLinearPoly};
+use transcript::BytesHashTranscript;
+
+#[test]
+fn test_open_in_foreign_layout() {
+    type C = M31x16Config;
+    type PCS = RawExpanderGKR<C>;
+
+    let num_vars = 6;
+    let mut rng = test_rng();
+    let mpi_config = MPIConfig::prover_new(None, None);
+
+    let params = <PCS as ExpanderPCS<C>>::gen_params(num_vars, 1);
+    let srs = <PCS as ExpanderPCS<C>>::gen_srs(&params, &mpi_config, &mut rng);
+    let (proving_key, verification_key) = srs.into_keys();
+    let mut scratch_pad = <PCS as ExpanderPCS<C>>::init_scratch_pad(&params, &mpi_config);
+
+    let poly = MultiLinearPoly::<M31x16>::random(num_vars, &mut rng);
+    let commitment = <PCS as ExpanderPCS<C>>::commit(
+        &params,
+        &mpi_config,
+        &proving_key,
+        &poly,
+        &mut scratch_pad,
+    )
+    .unwrap()
+    .unwrap();
+
+    let challenge = ExpanderSingleVarChallenge::<C>::new(
+        (0..num_vars)
+            .map(|_| M31Ext3::random_unsafe(&mut rng))
+            .collect(),
+        (0..4).map(|_| M31Ext3::random_unsafe(&mut rng)).collect(),
+        vec![],
+    );
+    let v = C::single_core_eval_circuit_vals_at_expander_challenge(&poly.coeffs, &challenge);
+
+    for layout in [HypercubeLayout::LsbFirst, HypercubeLayout::MsbFirst] {
+        // the claim as an MSB-first system would state it
+        let point = challenge.to_point(layout);
+        let converted = ExpanderSingleVarChallenge::<C>::from_point(&point, layout, 1);
+        assert_eq!(converted.global_xs(), challenge.global_xs());
+
+        let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+        let opening = open_in_layout::<C, PCS>(
+            &params,
+            &mpi_config,
+            &proving_key,
+            &poly,
+            &point,
+            layout,
+            &mut transcript,
+            &scratch_pad,
+        )
+        .unwrap()
+        .unwrap();
+
+        let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+        assert!(verify_in_layout::<C, PCS>(
+            &params,
+            &verification_key,
+            &commitment,
+            &point,
+            layout,
+            1,
+            v,
+            &mut transcript,
+            &opening,
+        ));
+
+        // the same point read in the wrong layout does not open to `v`
+        let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+        let wrong_layout = match layout {
+            HypercubeLayout::LsbFirst => HypercubeLayout::MsbFirst,
+            HypercubeLayout::MsbFirst => HypercubeLayout::LsbFirst,
+        };
+        assert!(!verify_in_layout::<C, PCS>(
+            &params,
+            &verification_key,
+            &commitment,
+            &point,
+            wrong_layout,
+            1,
+            v,
+            &mut transcript,
+            &opening,
+        ));
+    }
+}
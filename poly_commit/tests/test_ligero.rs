@@ -0,0 +1,170 @@
+#![cfg(feature = "ligero")]
+
+mod common;
+
+use arith::{ExtensionField, FFTField, Field};
+use ark_std::test_rng;
+use gkr_engine::{
+    ExpanderSingleVarChallenge, FieldEngine, Goldilocksx1Config, Goldilocksx8Config, MPIConfig,
+    MPIEngine, StructuredReferenceString, Transcript,
+};
+use gkr_hashers::Keccak256hasher;
+use goldilocks::{Goldilocks, GoldilocksExt2};
+use halo2curves::bn256::Fr;
+use poly_commit::*;
+use polynomials::MultiLinearPoly;
+use transcript::BytesHashTranscript;
+
+const TEST_REPETITION: usize = 3;
+
+fn test_ligero_pcs_generics<F, EvalF>(num_vars_start: usize, num_vars_end: usize)
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    let mut rng = test_rng();
+
+    (num_vars_start..=num_vars_end).for_each(|num_vars| {
+        let xs: Vec<_> = (0..TEST_REPETITION)
+            .map(|_| -> Vec<EvalF> {
+                (0..num_vars)
+                    .map(|_| EvalF::random_unsafe(&mut rng))
+                    .collect()
+            })
+            .collect();
+        let poly = MultiLinearPoly::<F>::random(num_vars, &mut rng);
+
+        common::test_pcs::<EvalF, BytesHashTranscript<Keccak256hasher>, LigeroPCS<F, EvalF>>(
+            &num_vars, &poly, &xs,
+        );
+    })
+}
+
+#[test]
+fn test_ligero_pcs_full_e2e() {
+    test_ligero_pcs_generics::<Goldilocks, GoldilocksExt2>(1, 12);
+    test_ligero_pcs_generics::<Fr, Fr>(1, 10);
+}
+
+fn test_ligero_rejects_generics<F, EvalF>(num_vars: usize)
+where
+    F: FFTField,
+    EvalF: ExtensionField<BaseField = F>,
+{
+    type T = BytesHashTranscript<Keccak256hasher>;
+    let mut rng = test_rng();
+
+    let (srs, _) = LigeroPCS::<F, EvalF>::gen_srs_for_testing(&num_vars, &mut rng);
+    let (pk, vk) = srs.into_keys();
+    let poly = MultiLinearPoly::<F>::random(num_vars, &mut rng);
+    let x: Vec<EvalF> = (0..num_vars)
+        .map(|_| EvalF::random_unsafe(&mut rng))
+        .collect();
+
+    let mut scratch_pad = LigeroScratchPad::default();
+    let commitment = ligero_commit(&pk, &poly.coeffs, &mut scratch_pad);
+    let (eval, opening) = ligero_open(&pk, &poly.coeffs, &x, &mut T::new(), &scratch_pad);
+
+    let verify = |eval, opening: &LigeroProof<EvalF>| {
+        ligero_verify(&vk, &commitment, &x, eval, &mut T::new(), opening)
+    };
+    assert!(verify(eval, &opening));
+    assert!(!verify(eval + EvalF::ONE, &opening));
+
+    let mut bad_opening = opening.clone();
+    bad_opening.eval_row[0] += EvalF::ONE;
+    assert!(!verify(eval, &bad_opening));
+
+    let mut bad_opening = opening.clone();
+    bad_opening.proximity_rows[0][0] += EvalF::ONE;
+    assert!(!verify(eval, &bad_opening));
+
+    let mut bad_opening = opening;
+    bad_opening.query_openings.swap(0, 1);
+    assert!(!verify(eval, &bad_opening));
+}
+
+#[test]
+fn test_ligero_rejects_bad_openings() {
+    test_ligero_rejects_generics::<Goldilocks, GoldilocksExt2>(10);
+    test_ligero_rejects_generics::<Fr, Fr>(10);
+}
+
+fn test_ligero_for_expander_gkr_generics<C, T>(mpi_config_ref: &MPIConfig, total_num_vars: usize)
+where
+    C: FieldEngine,
+    C::CircuitField: FFTField,
+    T: Transcript,
+{
+    let mut rng = test_rng();
+
+    // NOTE: generate global random polynomial
+    let num_vars_in_simd = C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+    let num_vars_in_mpi = mpi_config_ref.world_size().ilog2() as usize;
+    let num_vars_in_each_poly = total_num_vars - num_vars_in_mpi - num_vars_in_simd;
+    let num_vars_in_global_poly = total_num_vars - num_vars_in_simd;
+
+    let global_poly =
+        MultiLinearPoly::<C::SimdCircuitField>::random(num_vars_in_global_poly, &mut rng);
+
+    // NOTE generate srs for each party, and shared challenge point in each party
+    let challenge_point = ExpanderSingleVarChallenge::<C> {
+        r_mpi: (0..num_vars_in_mpi)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        r_simd: (0..num_vars_in_simd)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        rz: (0..num_vars_in_each_poly)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+    };
+
+    let mut transcript = T::new();
+
+    // NOTE separate polynomial into different pieces by mpi rank
+    let poly_vars_stride = (1 << global_poly.get_num_vars()) / mpi_config_ref.world_size();
+    let poly_coeff_starts = mpi_config_ref.world_rank() * poly_vars_stride;
+    let poly_coeff_ends = poly_coeff_starts + poly_vars_stride;
+    let local_poly =
+        MultiLinearPoly::new(global_poly.coeffs[poly_coeff_starts..poly_coeff_ends].to_vec());
+
+    common::test_pcs_for_expander_gkr::<C, T, LigeroPCSForGKR<C>>(
+        &num_vars_in_each_poly,
+        mpi_config_ref,
+        &mut transcript,
+        &local_poly,
+        &[challenge_point],
+    );
+}
+
+#[test]
+fn test_ligero_for_expander_gkr() {
+    let universe = MPIConfig::init().unwrap();
+    let world = universe.world();
+    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+
+    test_ligero_for_expander_gkr_generics::<Goldilocksx1Config, BytesHashTranscript<Keccak256hasher>>(
+        &mpi_config,
+        12,
+    );
+
+    test_ligero_for_expander_gkr_generics::<Goldilocksx8Config, BytesHashTranscript<Keccak256hasher>>(
+        &mpi_config,
+        16,
+    );
+}
+
+#[test]
+fn test_ligero_batch_open() {
+    common::test_batching_for_expander_gkr::<
+        Goldilocksx1Config,
+        BytesHashTranscript<Keccak256hasher>,
+        LigeroPCSForGKR<Goldilocksx1Config>,
+    >(true);
+}
+
+#[test]
+fn test_ligero_srs_from_seed() {
+    common::test_srs_from_seed::<Goldilocksx8Config, LigeroPCSForGKR<Goldilocksx8Config>>(8);
+}
@@ -1,18 +1,22 @@
+#![cfg(feature = "orion")]
+
 mod common;
 
 use arith::{ExtensionField, Field, SimdField};
 use ark_std::test_rng;
+use babybear::{BabyBear, BabyBearExt3, BabyBearx16};
 use gf2::{GF2x128, GF2x64, GF2x8, GF2};
 use gf2_128::GF2_128;
 use gkr_engine::{
-    ExpanderSingleVarChallenge, FieldEngine, GF2ExtConfig, Goldilocksx8Config, M31x16Config,
-    MPIConfig, MPIEngine, Transcript,
+    BabyBearx16Config, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GF2ExtConfig,
+    Goldilocksx8Config, M31x16Config, MPIConfig, MPIEngine, StructuredReferenceString,
+    ThreadedEngine, Transcript, WorldSizeError,
 };
 use gkr_hashers::Keccak256hasher;
 use goldilocks::{Goldilocks, GoldilocksExt2, Goldilocksx8};
 use mersenne31::{M31Ext3, M31x16, M31};
 use poly_commit::*;
-use polynomials::MultiLinearPoly;
+use polynomials::{MultiLinearPoly, RefMultiLinearPoly};
 use transcript::BytesHashTranscript;
 
 const TEST_REPETITION: usize = 3;
@@ -52,7 +56,8 @@ fn test_orion_simd_pcs_full_e2e() {
     test_orion_simd_pcs_generics::<GF2, GF2x8, GF2_128, GF2x64>(19, 25);
     test_orion_simd_pcs_generics::<GF2, GF2x8, GF2_128, GF2x128>(19, 25);
     test_orion_simd_pcs_generics::<M31, M31x16, M31Ext3, M31x16>(16, 22);
-    test_orion_simd_pcs_generics::<Goldilocks, Goldilocksx8, GoldilocksExt2, Goldilocksx8>(16, 22)
+    test_orion_simd_pcs_generics::<Goldilocks, Goldilocksx8, GoldilocksExt2, Goldilocksx8>(16, 22);
+    test_orion_simd_pcs_generics::<BabyBear, BabyBearx16, BabyBearExt3, BabyBearx16>(16, 22)
 }
 
 fn test_orion_for_expander_gkr_generics<C, ComPackF, T>(
@@ -112,7 +117,6 @@ fn test_orion_for_expander_gkr_generics<C, ComPackF, T>(
         &mut transcript,
         &local_poly,
         &[challenge_point],
-        None,
     );
 }
 
@@ -138,4 +142,217 @@ fn test_orion_for_expander_gkr() {
         Goldilocksx8,
         BytesHashTranscript<Keccak256hasher>,
     >(&mpi_config, 25);
+
+    test_orion_for_expander_gkr_generics::<
+        BabyBearx16Config,
+        BabyBearx16,
+        BytesHashTranscript<Keccak256hasher>,
+    >(&mpi_config, 25);
+}
+
+fn test_orion_rank_local_for_expander_gkr_generics<C, ComPackF>(
+    world_size: usize,
+    total_num_vars: usize,
+) where
+    C: FieldEngine,
+    ComPackF: SimdField<Scalar = C::CircuitField>,
+{
+    type T = BytesHashTranscript<Keccak256hasher>;
+
+    let mut rng = test_rng();
+
+    let num_vars_in_simd = C::SimdCircuitField::PACK_SIZE.ilog2() as usize;
+    let num_vars_in_mpi = world_size.ilog2() as usize;
+    let num_vars_in_each_poly = total_num_vars - num_vars_in_mpi - num_vars_in_simd;
+
+    let global_poly =
+        MultiLinearPoly::<C::SimdCircuitField>::random(total_num_vars - num_vars_in_simd, &mut rng);
+    let challenge_point = ExpanderSingleVarChallenge::<C> {
+        r_mpi: (0..num_vars_in_mpi)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        r_simd: (0..num_vars_in_simd)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        rz: (0..num_vars_in_each_poly)
+            .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+    };
+    let eval = C::single_core_eval_circuit_vals_at_expander_challenge(
+        &global_poly.coeffs,
+        &challenge_point,
+    );
+
+    let params = <OrionRankLocalPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::gen_params(
+        num_vars_in_each_poly,
+        world_size,
+    );
+
+    // NOTE: each rank commits to and opens its own share, as held by the GKR prover
+    let mut results = ThreadedEngine::run(world_size, |engine| {
+        let srs = <OrionRankLocalPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::gen_srs_from_seed(
+            &params,
+            engine,
+            common::SRS_SEED,
+        );
+        let (proving_key, verification_key) = srs.into_keys();
+        let mut scratch_pad =
+            <OrionRankLocalPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::init_scratch_pad(
+                &params, engine,
+            );
+
+        let local_poly = RefMultiLinearPoly::from_ref(
+            global_poly
+                .coeffs
+                .chunks(global_poly.coeffs.len() / world_size)
+                .nth(engine.world_rank())
+                .unwrap(),
+        );
+
+        let commitment = <OrionRankLocalPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::commit(
+            &params,
+            engine,
+            &proving_key,
+            &local_poly,
+            &mut scratch_pad,
+        )
+        .unwrap();
+        let opening = <OrionRankLocalPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::open(
+            &params,
+            engine,
+            &proving_key,
+            &local_poly,
+            &challenge_point,
+            &mut T::new(),
+            &scratch_pad,
+        )
+        .unwrap();
+
+        (verification_key, commitment, opening)
+    });
+
+    let (verification_key, commitment, opening) = results.swap_remove(0);
+    let (commitment, opening) = (commitment.unwrap(), opening.unwrap());
+    assert_eq!(opening.query_openings.len() % world_size, 0);
+
+    assert!(
+        <OrionRankLocalPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::verify(
+            &params,
+            &verification_key,
+            &commitment,
+            &challenge_point,
+            eval,
+            &mut T::new(),
+            &opening,
+        )
+    );
+    assert!(
+        !<OrionRankLocalPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::verify(
+            &params,
+            &verification_key,
+            &commitment,
+            &challenge_point,
+            eval + C::ChallengeField::ONE,
+            &mut T::new(),
+            &opening,
+        )
+    );
+
+    // NOTE: on a single process, the layouts commit to the same tree
+    if world_size == 1 {
+        assert!(<OrionPCSForGKR<C, ComPackF> as ExpanderPCS<C>>::verify(
+            &params,
+            &verification_key,
+            &commitment,
+            &challenge_point,
+            eval,
+            &mut T::new(),
+            &opening,
+        ));
+    }
+}
+
+#[test]
+fn test_orion_rank_local_for_expander_gkr() {
+    for world_size in [1, 2, 4] {
+        test_orion_rank_local_for_expander_gkr_generics::<GF2ExtConfig, GF2x128>(world_size, 22);
+        test_orion_rank_local_for_expander_gkr_generics::<M31x16Config, M31x16>(world_size, 18);
+        test_orion_rank_local_for_expander_gkr_generics::<Goldilocksx8Config, Goldilocksx8>(
+            world_size, 18,
+        );
+    }
+}
+
+fn test_orion_data_availability_generics<F, SimdF, EvalF, ComPackF>(num_elems: usize)
+where
+    F: Field,
+    SimdF: SimdField<Scalar = F>,
+    EvalF: ExtensionField<BaseField = F>,
+    ComPackF: SimdField<Scalar = F>,
+{
+    let mut rng = test_rng();
+
+    let data: Vec<F> = (0..num_elems).map(|_| F::random_unsafe(&mut rng)).collect();
+    let srs = orion_da_setup::<F, ComPackF>(num_elems, &mut rng);
+
+    let mut scratch_pad = OrionScratchPad::default();
+    let commitment = orion_da_commit::<F, SimdF, ComPackF>(&srs, &data, &mut scratch_pad).unwrap();
+
+    // NOTE: the sampler picks its own samples through a nonce
+    let mut prover_transcript = BytesHashTranscript::<Keccak256hasher>::new();
+    prover_transcript.append_u8_slice(b"sampler nonce");
+    let samples = orion_da_sample::<F, SimdF, EvalF, ComPackF>(
+        &srs,
+        &data,
+        &mut prover_transcript,
+        &scratch_pad,
+    )
+    .unwrap();
+
+    let verify = |samples: &OrionDASamples<EvalF>, commitment| {
+        let mut verifier_transcript = BytesHashTranscript::<Keccak256hasher>::new();
+        verifier_transcript.append_u8_slice(b"sampler nonce");
+        orion_da_verify::<F, SimdF, EvalF>(&srs, commitment, &mut verifier_transcript, samples)
+    };
+    assert!(verify(&samples, &commitment));
+
+    let mut bad_samples = samples.clone();
+    bad_samples.proximity_rows[0][0] += EvalF::ONE;
+    assert!(!verify(&bad_samples, &commitment));
+
+    let mut other_data = data.clone();
+    other_data[0] += F::ONE;
+    let other_commitment =
+        orion_da_commit::<F, SimdF, ComPackF>(&srs, &other_data, &mut OrionScratchPad::default())
+            .unwrap();
+    assert!(!verify(&samples, &other_commitment));
+
+    let too_much_data = vec![F::ZERO; (1 << srs.num_vars) + 1];
+    assert!(orion_da_commit::<F, SimdF, ComPackF>(&srs, &too_much_data, &mut scratch_pad).is_err());
+}
+
+#[test]
+fn test_orion_data_availability() {
+    test_orion_data_availability_generics::<GF2, GF2x8, GF2_128, GF2x128>(1 << 20);
+    test_orion_data_availability_generics::<M31, M31x16, M31Ext3, M31x16>((1 << 16) - 5);
+    test_orion_data_availability_generics::<Goldilocks, Goldilocksx8, GoldilocksExt2, Goldilocksx8>(
+        1 << 16,
+    );
+}
+
+#[test]
+fn test_orion_world_size() {
+    type PCS = OrionPCSForGKR<M31x16Config, M31x16>;
+
+    assert!(<PCS as ExpanderPCS<M31x16Config>>::check_world_size(4).is_ok());
+    assert_eq!(
+        <PCS as ExpanderPCS<M31x16Config>>::check_world_size(3),
+        Err(WorldSizeError {
+            pcs: "OrionPCSForExpanderGKR",
+            world_size: 3,
+        })
+    );
+    assert!(
+        <RawExpanderGKR<M31x16Config> as ExpanderPCS<M31x16Config>>::check_world_size(3).is_ok()
+    );
 }
@@ -2,10 +2,12 @@ mod common;
 
 use arith::{Field, Fr};
 use gkr_engine::{
-    BN254Config, ExpanderSingleVarChallenge, FieldEngine, GF2ExtConfig, M31x16Config, MPIConfig,
-    MPIEngine, Transcript,
+    BN254Config, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GF2ExtConfig, M31x16Config,
+    MPIConfig, MPIEngine, Transcript,
 };
 use gkr_hashers::{Keccak256hasher, SHA256hasher};
+#[cfg(feature = "raw-distributed")]
+use poly_commit::raw::RawExpanderGKRDistributed;
 use poly_commit::raw::{RawExpanderGKR, RawMultiLinearPCS};
 use polynomials::{MultiLinearPoly, RefMultiLinearPoly};
 use rand::thread_rng;
@@ -30,7 +32,10 @@ fn test_raw() {
     );
 }
 
-fn test_raw_gkr_helper<C: FieldEngine, T: Transcript>(mpi_config: &MPIConfig, transcript: &mut T) {
+fn test_raw_gkr_helper<C: FieldEngine, T: Transcript, P: ExpanderPCS<C, Params = usize>>(
+    mpi_config: &MPIConfig,
+    transcript: &mut T,
+) {
     // NOTE(HS) local variables being 8
     let params = 8;
     let mut rng = thread_rng();
@@ -51,9 +56,7 @@ fn test_raw_gkr_helper<C: FieldEngine, T: Transcript>(mpi_config: &MPIConfig, tr
                 .collect::<Vec<C::ChallengeField>>(),
         })
         .collect::<Vec<ExpanderSingleVarChallenge<C>>>();
-    common::test_pcs_for_expander_gkr::<C, T, RawExpanderGKR<C>>(
-        &params, mpi_config, transcript, &poly, &xs, None,
-    );
+    common::test_pcs_for_expander_gkr::<C, T, P>(&params, mpi_config, transcript, &poly, &xs);
 }
 
 #[test]
@@ -62,11 +65,45 @@ fn test_raw_gkr() {
     let world = universe.world();
     let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
     type TM31 = BytesHashTranscript<Keccak256hasher>;
-    test_raw_gkr_helper::<M31x16Config, TM31>(&mpi_config, &mut TM31::new());
+    test_raw_gkr_helper::<M31x16Config, TM31, RawExpanderGKR<M31x16Config>>(
+        &mpi_config,
+        &mut TM31::new(),
+    );
+
+    type TGF2 = BytesHashTranscript<SHA256hasher>;
+    test_raw_gkr_helper::<GF2ExtConfig, TGF2, RawExpanderGKR<GF2ExtConfig>>(
+        &mpi_config,
+        &mut TGF2::new(),
+    );
+
+    type TBN254 = BytesHashTranscript<Keccak256hasher>;
+    test_raw_gkr_helper::<BN254Config, TBN254, RawExpanderGKR<BN254Config>>(
+        &mpi_config,
+        &mut TBN254::new(),
+    );
+}
+
+#[cfg(feature = "raw-distributed")]
+#[test]
+fn test_raw_gkr_distributed() {
+    let universe = MPIConfig::init().unwrap();
+    let world = universe.world();
+    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+    type TM31 = BytesHashTranscript<Keccak256hasher>;
+    test_raw_gkr_helper::<M31x16Config, TM31, RawExpanderGKRDistributed<M31x16Config>>(
+        &mpi_config,
+        &mut TM31::new(),
+    );
 
     type TGF2 = BytesHashTranscript<SHA256hasher>;
-    test_raw_gkr_helper::<GF2ExtConfig, TGF2>(&mpi_config, &mut TGF2::new());
+    test_raw_gkr_helper::<GF2ExtConfig, TGF2, RawExpanderGKRDistributed<GF2ExtConfig>>(
+        &mpi_config,
+        &mut TGF2::new(),
+    );
 
     type TBN254 = BytesHashTranscript<Keccak256hasher>;
-    test_raw_gkr_helper::<BN254Config, TBN254>(&mpi_config, &mut TBN254::new());
+    test_raw_gkr_helper::<BN254Config, TBN254, RawExpanderGKRDistributed<BN254Config>>(
+        &mpi_config,
+        &mut TBN254::new(),
+    );
 }
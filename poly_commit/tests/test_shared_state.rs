@@ -0,0 +1,122 @@
+use arith::SimdField;
+use ark_std::test_rng;
+use gkr_engine::{
+    ExpanderPCS, FieldEngine, M31x16Config, MPIConfig, StructuredReferenceString, Transcript,
+};
+use gkr_hashers::SHA256hasher;
+use mersenne31::{M31Ext3, M31x16};
+use poly_commit::{RawExpanderGKR, SharedState, SharedStateVerifier, StateOpenings};
+use polynomials::MultiLinearPoly;
+use serdes::ExpSerde;
+use transcript::BytesHashTranscript;
+
+#[test]
+fn test_shared_state_openings() {
+    type C = M31x16Config;
+    type PCS = RawExpanderGKR<C>;
+    type Openings = StateOpenings<M31Ext3, <PCS as ExpanderPCS<C>>::Opening>;
+
+    let num_vars = 6;
+    let mut rng = test_rng();
+    let mpi_config = MPIConfig::prover_new(None, None);
+
+    let params = <PCS as ExpanderPCS<C>>::gen_params(num_vars, 1);
+    let srs = <PCS as ExpanderPCS<C>>::gen_srs(&params, &mpi_config, &mut rng);
+    let (proving_key, verification_key) = srs.into_keys();
+
+    let state_poly = MultiLinearPoly::<M31x16>::random(num_vars, &mut rng);
+    let mut state =
+        SharedState::<C, PCS>::commit(&params, &mpi_config, &proving_key, &state_poly).unwrap();
+    assert_eq!(state.num_vars(), num_vars + 4);
+
+    let mut verifier =
+        SharedStateVerifier::<C, PCS>::new(state.commitment().clone(), state.num_vars(), 1);
+    assert_eq!(verifier.root(), state.root());
+
+    // two proofs over the state, reading overlapping positions
+    let reads = [vec![0, 17, 1000], vec![17, 1023, 5]];
+    let proofs: Vec<Openings> = reads
+        .iter()
+        .map(|positions| {
+            let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+            state
+                .open(
+                    &params,
+                    &mpi_config,
+                    &proving_key,
+                    &state_poly,
+                    positions,
+                    &mut transcript,
+                )
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(state.num_proofs(), 2);
+
+    let expected = |p: usize| M31Ext3::from(state_poly.coeffs[p >> 4].unpack()[p & 15]);
+    for (openings, positions) in proofs.iter().zip(&reads) {
+        assert_eq!(
+            openings.values,
+            positions.iter().map(|&p| expected(p)).collect::<Vec<_>>()
+        );
+    }
+
+    // the openings go along the proofs
+    let mut bytes = vec![];
+    proofs[1].serialize_into(&mut bytes).unwrap();
+    assert_eq!(
+        Openings::deserialize_from(bytes.as_slice()).unwrap(),
+        proofs[1]
+    );
+
+    let verify = |verifier: &mut SharedStateVerifier<C, PCS>, openings: &Openings| {
+        let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+        verifier.verify(&params, &verification_key, openings, &mut transcript)
+    };
+
+    // a wrong value fails, and leaves the verifier as is
+    let mut tampered = proofs[0].clone();
+    tampered.values[1] += M31Ext3::from(1u32);
+    assert!(!verify(&mut verifier, &tampered));
+    assert_eq!(verifier.num_proofs(), 0);
+
+    assert!(verify(&mut verifier, &proofs[0]));
+    assert!(verify(&mut verifier, &proofs[1]));
+    assert_eq!(verifier.num_proofs(), 2);
+    assert_eq!(verifier.value(17), Some(expected(17)));
+    assert_eq!(verifier.value(18), None);
+
+    // a proof is only verified once
+    assert!(!verify(&mut verifier, &proofs[1]));
+
+    // openings against another state are rejected
+    let other_poly = MultiLinearPoly::<M31x16>::random(num_vars, &mut rng);
+    let mut other_state =
+        SharedState::<C, PCS>::commit(&params, &mpi_config, &proving_key, &other_poly).unwrap();
+    for _ in 0..2 {
+        other_state
+            .open(
+                &params,
+                &mpi_config,
+                &proving_key,
+                &other_poly,
+                &[],
+                &mut BytesHashTranscript::<SHA256hasher>::new(),
+            )
+            .unwrap();
+    }
+    let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+    let other_openings = other_state
+        .open(
+            &params,
+            &mpi_config,
+            &proving_key,
+            &other_poly,
+            &[17],
+            &mut transcript,
+        )
+        .unwrap();
+    assert_eq!(other_openings.proof_index, 2);
+    assert_ne!(other_state.root(), state.root());
+    assert!(!verify(&mut verifier, &other_openings));
+}
@@ -1,12 +1,17 @@
+#![cfg(feature = "kzg")]
+
 mod common;
 
 use arith::{Field, Fr};
 use ark_std::test_rng;
 use gkr_engine::ExpanderPCS;
-use gkr_engine::{BN254Config, ExpanderSingleVarChallenge, MPIConfig, MPIEngine, Transcript};
+use gkr_engine::{
+    BN254Config, ExpanderSingleVarChallenge, MPIConfig, MPIEngine, StructuredReferenceString,
+    Transcript,
+};
 use gkr_hashers::Keccak256hasher;
 use halo2curves::bn256::Bn256;
-use poly_commit::HyperUniKZGPCS;
+use poly_commit::{HyperUniKZGPCS, PolynomialCommitmentScheme, UnivariateBatchOpeningPCS};
 use polynomials::MultiLinearPoly;
 use transcript::BytesHashTranscript;
 
@@ -75,10 +80,14 @@ fn test_hyper_unikzg_for_expander_gkr_generics(mpi_config_ref: &MPIConfig, total
         &mut transcript,
         &local_poly,
         &[challenge_point],
-        None,
     );
 }
 
+#[test]
+fn test_hyper_unikzg_srs_from_seed() {
+    common::test_srs_from_seed::<BN254Config, HyperUniKZGPCS<Bn256>>(10);
+}
+
 #[test]
 fn test_hyper_unikzg_for_expander_gkr() {
     let universe = MPIConfig::init().unwrap();
@@ -99,3 +108,83 @@ fn test_uni_kzg_batch_open() {
         HyperUniKZGPCS<Bn256>,
     >(true);
 }
+
+#[test]
+fn test_uni_kzg_shplonk_batch_open() {
+    type T = BytesHashTranscript<Keccak256hasher>;
+    type PCS = HyperUniKZGPCS<Bn256>;
+    let mut rng = test_rng();
+
+    let num_vars = 6;
+    let (srs, _) =
+        <PCS as PolynomialCommitmentScheme<Fr>>::gen_srs_for_testing(&num_vars, &mut rng);
+    let (pk, vk) = srs.into_keys();
+
+    // polynomials of fewer variables, each at a set of points of its own
+    let polys: Vec<MultiLinearPoly<Fr>> = [6, 3, 6, 0]
+        .iter()
+        .map(|n| MultiLinearPoly::random(*n, &mut rng))
+        .collect();
+    let point_sets: Vec<Vec<Fr>> = [3, 1, 5, 2]
+        .iter()
+        .map(|n| (0..*n).map(|_| Fr::random_unsafe(&mut rng)).collect())
+        .collect();
+    let commitments: Vec<_> = polys
+        .iter()
+        .map(|poly| <PCS as PolynomialCommitmentScheme<Fr>>::commit(&num_vars, &pk, poly, &mut ()))
+        .collect();
+    let coeffs: Vec<Vec<Fr>> = polys.iter().map(|poly| poly.coeffs.clone()).collect();
+
+    let (evals, opening) = PCS::batch_open(&num_vars, &pk, &coeffs, &point_sets, &mut T::new());
+    assert!(PCS::batch_verify(
+        &num_vars,
+        &vk,
+        &commitments,
+        &point_sets,
+        &evals,
+        &opening,
+        &mut T::new()
+    ));
+
+    let mut wrong_evals = evals.clone();
+    wrong_evals[3][0] += Fr::ONE;
+    assert!(!PCS::batch_verify(
+        &num_vars,
+        &vk,
+        &commitments,
+        &point_sets,
+        &wrong_evals,
+        &opening,
+        &mut T::new()
+    ));
+}
+
+#[test]
+fn test_hyper_uni_kzg_commit_with_tables() {
+    type PCS = HyperUniKZGPCS<Bn256>;
+
+    let mut rng = test_rng();
+    let max_num_vars = 8;
+    let (srs, _) =
+        <PCS as PolynomialCommitmentScheme<Fr>>::gen_srs_for_testing(&max_num_vars, &mut rng);
+    let (pk, _) = srs.into_keys();
+
+    for window_bits in [1, 5, 8, 13] {
+        let tables = PCS::precompute_tables(&pk, window_bits);
+        assert_eq!(tables.len(), 1 << max_num_vars);
+
+        // the polynomials shorter than the SRS are committed to from a prefix of the tables
+        for num_vars in 0..=max_num_vars {
+            let mut poly = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+            poly.coeffs[0] = -Fr::ONE;
+            if num_vars > 0 {
+                poly.coeffs[1] = Fr::ZERO;
+            }
+
+            assert_eq!(
+                PCS::commit_with_tables(&tables, &poly),
+                <PCS as PolynomialCommitmentScheme<Fr>>::commit(&num_vars, &pk, &poly, &mut ()),
+            );
+        }
+    }
+}
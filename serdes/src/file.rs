@@ -0,0 +1,61 @@
+//! Persistence of expensive precomputed values, e.g., the SRS of a PCS, to files.
+//!
+//! Files are memory mapped when loaded, so that the value is deserialized straight from the page
+//! cache, or keeps referring to the map, see `ExpSerde::deserialize_from_mmap`, and written through
+//! a temporary file renamed into place, so that a reader never sees a partially written file.
+use std::{
+    fs::{self, File},
+    io::{BufWriter, ErrorKind, Write},
+    path::Path,
+    sync::Arc,
+};
+
+use memmap2::Mmap;
+
+use crate::{ExpSerde, SerdeError, SerdeResult};
+
+/// Deserialize a value from the file at `path`, failing if the file has trailing bytes.
+pub fn load_from_file<T: ExpSerde>(path: impl AsRef<Path>) -> SerdeResult<T> {
+    let file = File::open(path)?;
+    // SAFETY: the files are only ever replaced by renaming, never written in place
+    let mmap = Arc::new(unsafe { Mmap::map(&file)? });
+
+    let mut offset = 0;
+    let value = T::deserialize_from_mmap(&mmap, &mut offset)?;
+    if offset != mmap.len() {
+        return Err(SerdeError::DeserializeError);
+    }
+    Ok(value)
+}
+
+/// Serialize `value` into the file at `path`, replacing it atomically if it exists.
+pub fn store_to_file<T: ExpSerde>(value: &T, path: impl AsRef<Path>) -> SerdeResult<()> {
+    let path = path.as_ref();
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(format!(".tmp{}", std::process::id()));
+
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    value.serialize_into(&mut writer)?;
+    writer.flush()?;
+    drop(writer);
+
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Load the value stored at `path`, or compute it with `compute` and store it there if there is
+/// no such file yet. A file that exists but does not deserialize is an error, and is left as is.
+pub fn load_or_compute<T: ExpSerde>(
+    path: impl AsRef<Path>,
+    compute: impl FnOnce() -> T,
+) -> SerdeResult<T> {
+    let path = path.as_ref();
+    match load_from_file(path) {
+        Err(SerdeError::IOError(e)) if e.kind() == ErrorKind::NotFound => {
+            let value = compute();
+            store_to_file(&value, path)?;
+            Ok(value)
+        }
+        result => result,
+    }
+}
@@ -1,7 +1,12 @@
 pub mod error;
+pub mod file;
 pub mod macros;
+pub mod parallel;
 pub mod serdes;
 
 pub use error::{SerdeError, SerdeResult};
+pub use file::{load_from_file, load_or_compute, store_to_file};
+pub use memmap2::Mmap;
+pub use parallel::{par_deserialize_fixed_size, par_deserialize_records, par_parse_fixed_size};
 pub use serdes::ExpSerde;
 pub use serdes_derive::ExpSerde;
@@ -0,0 +1,129 @@
+//! Parallel deserialization of long sequences.
+//!
+//! The bytes of the sequence are first copied out of the reader, recording chunk boundaries on
+//! the way, and the chunks are then deserialized, and validated, on the rayon thread pool.  The
+//! serialized format is the same as `Vec<V>` without the length prefix.
+use std::io::Read;
+
+use rayon::prelude::*;
+
+use crate::{ExpSerde, SerdeError, SerdeResult};
+
+/// Number of values deserialized by a single rayon task.
+pub const PAR_DESERIALIZE_CHUNK_LEN: usize = 1 << 14;
+
+/// Deserialize `len` values, each serialized into exactly `elem_size` bytes.
+pub fn par_deserialize_fixed_size<V, R>(
+    reader: R,
+    len: usize,
+    elem_size: usize,
+) -> SerdeResult<Vec<V>>
+where
+    V: ExpSerde + Send,
+    R: Read,
+{
+    let bytes = read_fixed_size(reader, len, elem_size)?;
+    let chunks = bytes
+        .par_chunks(PAR_DESERIALIZE_CHUNK_LEN * elem_size)
+        .map(|chunk| deserialize_chunk(chunk, chunk.len() / elem_size))
+        .collect::<SerdeResult<Vec<Vec<V>>>>()?;
+
+    Ok(chunks.into_iter().flatten().collect())
+}
+
+/// Parse `len` values, each encoded into exactly `elem_size` bytes, with `parse`, e.g., a
+/// conversion rejecting the encodings that `ExpSerde` would correct instead.
+pub fn par_parse_fixed_size<V, R, P>(
+    reader: R,
+    len: usize,
+    elem_size: usize,
+    parse: P,
+) -> SerdeResult<Vec<V>>
+where
+    V: Send,
+    R: Read,
+    P: Fn(&[u8]) -> Option<V> + Sync,
+{
+    let bytes = read_fixed_size(reader, len, elem_size)?;
+    bytes
+        .par_chunks(elem_size)
+        .map(|elem| parse(elem).ok_or(SerdeError::DeserializeError))
+        .collect()
+}
+
+/// Deserialize `len` values of variable size.
+///
+/// The size of each value is determined by `record_size` from its first `header_size` bytes,
+/// which lets the values be split into chunks without deserializing them.
+pub fn par_deserialize_records<V, R, F>(
+    mut reader: R,
+    len: usize,
+    header_size: usize,
+    record_size: F,
+) -> SerdeResult<Vec<V>>
+where
+    V: ExpSerde + Send,
+    R: Read,
+    F: Fn(&[u8]) -> SerdeResult<usize>,
+{
+    let mut bytes = vec![];
+    let mut chunk_starts = vec![];
+
+    for i in 0..len {
+        if i % PAR_DESERIALIZE_CHUNK_LEN == 0 {
+            chunk_starts.push(bytes.len());
+        }
+
+        let start = bytes.len();
+        bytes.resize(start + header_size, 0);
+        reader.read_exact(&mut bytes[start..])?;
+
+        let size = record_size(&bytes[start..])?;
+        if size < header_size {
+            return Err(SerdeError::DeserializeError);
+        }
+        bytes.resize(start + size, 0);
+        reader.read_exact(&mut bytes[start + header_size..])?;
+    }
+    chunk_starts.push(bytes.len());
+
+    let chunks = chunk_starts
+        .par_windows(2)
+        .enumerate()
+        .map(|(i, w)| {
+            let n = PAR_DESERIALIZE_CHUNK_LEN.min(len - i * PAR_DESERIALIZE_CHUNK_LEN);
+            deserialize_chunk(&bytes[w[0]..w[1]], n)
+        })
+        .collect::<SerdeResult<Vec<Vec<V>>>>()?;
+
+    Ok(chunks.into_iter().flatten().collect())
+}
+
+fn read_fixed_size<R: Read>(reader: R, len: usize, elem_size: usize) -> SerdeResult<Vec<u8>> {
+    assert!(elem_size > 0);
+    let total_size = len
+        .checked_mul(elem_size)
+        .ok_or(SerdeError::DeserializeError)?;
+
+    // `take` grows the buffer as the bytes arrive, so a corrupted length fails on the read
+    // instead of on a huge allocation
+    let mut bytes = vec![];
+    reader.take(total_size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != total_size {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(bytes)
+}
+
+#[inline]
+fn deserialize_chunk<V: ExpSerde>(mut chunk: &[u8], n: usize) -> SerdeResult<Vec<V>> {
+    let ret = (0..n)
+        .map(|_| V::deserialize_from(&mut chunk))
+        .collect::<SerdeResult<Vec<V>>>()?;
+
+    // every value must consume exactly the bytes it was assigned
+    if !chunk.is_empty() {
+        return Err(SerdeError::DeserializeError);
+    }
+    Ok(ret)
+}
@@ -2,13 +2,15 @@ use std::{
     collections::HashMap,
     hash::Hash,
     io::{Read, Write},
+    sync::Arc,
 };
 
 use ethnum::U256;
 use halo2curves::{
-    bn256::{Fr, G1Affine, G2Affine},
+    bn256::{Fq, Fq12, Fq2, Fq6, Fr, G1Affine, G2Affine},
     group::GroupEncoding,
 };
+use memmap2::Mmap;
 
 use crate::{exp_serde_for_generic_slices, exp_serde_for_number, SerdeError, SerdeResult};
 
@@ -19,6 +21,17 @@ pub trait ExpSerde: Sized {
 
     /// deserialize bytes into field
     fn deserialize_from<R: Read>(reader: R) -> SerdeResult<Self>;
+
+    /// Deserialize from the memory mapped file `mmap` at `*offset`, advancing the offset past the
+    /// value. The bytes are read as by `deserialize_from` unless the type keeps a reference to the
+    /// map instead, e.g., to decode a long sequence of values only once it is used.
+    fn deserialize_from_mmap(mmap: &Arc<Mmap>, offset: &mut usize) -> SerdeResult<Self> {
+        let mut bytes = mmap.get(*offset..).ok_or(SerdeError::DeserializeError)?;
+        let len = bytes.len();
+        let value = Self::deserialize_from(&mut bytes)?;
+        *offset += len - bytes.len();
+        Ok(value)
+    }
 }
 
 impl ExpSerde for () {
@@ -137,6 +150,49 @@ impl ExpSerde for G2Affine {
     }
 }
 
+/// The 12 coordinates over Fq, e.g., of the outputs of the Miller loop before the final
+/// exponentiation. The elements of the target group have no encoding, as halo2curves exposes
+/// neither their value nor a checked constructor.
+impl ExpSerde for Fq12 {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+        for fq6 in [&self.c0, &self.c1] {
+            for fq2 in [&fq6.c0, &fq6.c1, &fq6.c2] {
+                writer.write_all(fq2.c0.to_bytes().as_ref())?;
+                writer.write_all(fq2.c1.to_bytes().as_ref())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
+        let mut coords = [Fq::zero(); 12];
+        for coord in coords.iter_mut() {
+            let mut buffer = [0u8; 32];
+            reader.read_exact(&mut buffer)?;
+            *coord = Fq::from_bytes(&buffer)
+                .into_option()
+                .ok_or(SerdeError::DeserializeError)?;
+        }
+
+        let fq2 = |i: usize| Fq2 {
+            c0: coords[2 * i],
+            c1: coords[2 * i + 1],
+        };
+        Ok(Fq12 {
+            c0: Fq6 {
+                c0: fq2(0),
+                c1: fq2(1),
+                c2: fq2(2),
+            },
+            c1: Fq6 {
+                c0: fq2(3),
+                c1: fq2(4),
+                c2: fq2(5),
+            },
+        })
+    }
+}
+
 impl<T1: ExpSerde, T2: ExpSerde> ExpSerde for (T1, T2) {
     fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
         self.0.serialize_into(&mut writer)?;
@@ -149,6 +205,12 @@ impl<T1: ExpSerde, T2: ExpSerde> ExpSerde for (T1, T2) {
         let t2 = T2::deserialize_from(&mut reader)?;
         Ok((t1, t2))
     }
+
+    fn deserialize_from_mmap(mmap: &Arc<Mmap>, offset: &mut usize) -> SerdeResult<Self> {
+        let t1 = T1::deserialize_from_mmap(mmap, offset)?;
+        let t2 = T2::deserialize_from_mmap(mmap, offset)?;
+        Ok((t1, t2))
+    }
 }
 
 impl<K: ExpSerde + Eq + Hash, V: ExpSerde> ExpSerde for HashMap<K, V> {
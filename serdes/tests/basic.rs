@@ -1,6 +1,9 @@
 use std::fmt::Debug;
 
-use halo2curves::bn256::{G1Affine, G2Affine};
+use halo2curves::{
+    bn256::{Fq12, G1Affine, G2Affine},
+    ff::Field,
+};
 use serdes::ExpSerde;
 
 fn test_serialize_deserialize_helper<T: ExpSerde + Debug + PartialEq>(obj: T) {
@@ -20,3 +23,71 @@ fn test_g1_g2_serialization() {
     test_serialize_deserialize_helper(g1);
     test_serialize_deserialize_helper(g2);
 }
+
+#[test]
+fn test_fq12_serialization() {
+    let mut rng = rand::thread_rng();
+    test_serialize_deserialize_helper(Fq12::random(&mut rng));
+}
+
+#[test]
+fn test_par_deserialize() {
+    use serdes::{par_deserialize_fixed_size, par_deserialize_records};
+
+    // spans several chunks
+    let len = serdes::parallel::PAR_DESERIALIZE_CHUNK_LEN * 2 + 5;
+
+    let values: Vec<u64> = (0..len as u64).map(|i| i * i).collect();
+    let mut buf = Vec::new();
+    values
+        .iter()
+        .for_each(|v| v.serialize_into(&mut buf).unwrap());
+    let deserialized: Vec<u64> = par_deserialize_fixed_size(&buf[..], len, 8).unwrap();
+    assert_eq!(values, deserialized);
+    assert!(par_deserialize_fixed_size::<u64, _>(&buf[..buf.len() - 1], len, 8).is_err());
+
+    // records of variable size, the length prefix of a `Vec<u32>` determines its size
+    let records: Vec<Vec<u32>> = (0..len as u32).map(|i| vec![i; (i % 3) as usize]).collect();
+    let mut buf = Vec::new();
+    records
+        .iter()
+        .for_each(|r| r.serialize_into(&mut buf).unwrap());
+    let record_size = |header: &[u8]| {
+        let n = usize::deserialize_from(header)?;
+        Ok(8 + 4 * n)
+    };
+    let deserialized: Vec<Vec<u32>> =
+        par_deserialize_records(&buf[..], len, 8, record_size).unwrap();
+    assert_eq!(records, deserialized);
+    assert!(
+        par_deserialize_records::<Vec<u32>, _, _>(&buf[..buf.len() - 4], len, 8, record_size)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_load_or_compute() {
+    use serdes::{load_from_file, load_or_compute, store_to_file};
+
+    let path = std::env::temp_dir().join(format!("serdes_test_{}.bin", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let values: Vec<u64> = (0..1000).collect();
+    let computed: Vec<u64> = load_or_compute(&path, || values.clone()).unwrap();
+    assert_eq!(computed, values);
+
+    // the stored values are loaded instead of computed again
+    let loaded: Vec<u64> = load_or_compute(&path, || unreachable!()).unwrap();
+    assert_eq!(loaded, values);
+
+    // trailing bytes are rejected
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes.push(0);
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(load_from_file::<Vec<u64>>(&path).is_err());
+    assert!(load_or_compute(&path, || values.clone()).is_err());
+
+    store_to_file(&values, &path).unwrap();
+    assert_eq!(load_from_file::<Vec<u64>>(&path).unwrap(), values);
+    std::fs::remove_file(&path).unwrap();
+}
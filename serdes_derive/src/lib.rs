@@ -46,10 +46,26 @@ pub fn serdes_derive(input: TokenStream) -> TokenStream {
                 }
             };
 
+            // the fields are deserialized from the map in turn, so that each may keep referring to
+            // it
+            let deserialize_mmap_impl = quote! {
+                fn deserialize_from_mmap(
+                    mmap: &::std::sync::Arc<::serdes::Mmap>,
+                    offset: &mut usize,
+                ) -> ::serdes::SerdeResult<Self> {
+                    Ok(Self {
+                        #(
+                            #field_names: <#field_types as ::serdes::ExpSerde>::deserialize_from_mmap(mmap, offset)?,
+                        )*
+                    })
+                }
+            };
+
             quote! {
                 impl #impl_generics ::serdes::ExpSerde for #name #ty_generics #where_clause {
                     #serialize_impl
                     #deserialize_impl
+                    #deserialize_mmap_impl
                 }
             }
         }
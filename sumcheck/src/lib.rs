@@ -4,6 +4,9 @@ pub use sumcheck::*;
 mod sumcheck_generic;
 pub use sumcheck_generic::*;
 
+mod protocols;
+pub use protocols::*;
+
 mod prover_helper;
 
 mod verifier_helper;
@@ -12,5 +15,11 @@ pub use verifier_helper::*;
 mod scratch_pad;
 pub use scratch_pad::{ProverScratchPad, VerifierScratchPad};
 
+mod public_input;
+pub use public_input::*;
+
 mod utils;
 pub use utils::*;
+
+mod wiring;
+pub use wiring::*;
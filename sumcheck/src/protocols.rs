@@ -0,0 +1,13 @@
+//! Protocols reducing to the sumcheck of a sum of products, see `SumCheck`, for the PIOPs built
+//! atop Expander. Each protocol reduces its claim to evaluations of the polynomials, for the
+//! caller to check against the commitments, and shares the transcript with the surrounding
+//! protocol: the prover and the verifier draw the same challenges in the same order.
+
+mod zero_check;
+pub use zero_check::*;
+
+mod permutation_check;
+pub use permutation_check::*;
+
+#[cfg(test)]
+mod tests;
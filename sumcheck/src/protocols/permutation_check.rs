@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use arith::Field;
+use gkr_engine::Transcript;
+use polynomials::{MultiLinearPoly, MultilinearExtension, SumOfProductsPoly};
+use rayon::prelude::*;
+use serdes::ExpSerde;
+
+use super::{ZeroCheck, ZeroCheckProof};
+
+/// The challenges of the permutation check, drawn once f, s_f, g and s_g are committed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PermutationCheckChallenges<F: Field> {
+    /// Combines the values with their indices
+    pub beta: F,
+    /// The pole of the fractions
+    pub gamma: F,
+}
+
+/// Proof that the multisets {(s_f(x), f(x))} and {(s_g(x), g(x))} over {0,1}^`num_vars` are
+/// equal, e.g., that g is the permutation sigma of f, with s_f the identity and s_g = sigma.
+///
+/// Following logUp, the multisets are equal iff, with high probability over beta and gamma,
+/// `sum_x h_f(x) = sum_x h_g(x)` for the fractions h_f = 1 / (gamma + f + beta s_f) and
+/// h_g = 1 / (gamma + g + beta s_g). The prover commits to the fractions, whose means over the
+/// hypercube are their evaluations at (1/2, ..., 1/2), and a zero check proves them well formed.
+///
+/// NOTE: the field is of characteristic larger than 2^`num_vars`, for the sums of the fractions to
+/// count the multiplicities.
+#[derive(Clone, Debug, Default, PartialEq, Eq, ExpSerde)]
+pub struct PermutationCheckProof<F: Field> {
+    /// h_f and h_g at (1/2, ..., 1/2), i.e., their means over the hypercube
+    pub fractions_mean: F,
+    /// The zero check of h_f (gamma + f + beta s_f) - 1 + alpha (h_g (gamma + g + beta s_g) - 1)
+    pub zero_check_proof: ZeroCheckProof<F>,
+    /// f, s_f, g and s_g at the point the denominators are evaluated at by the zero check
+    pub poly_evals: Vec<F>,
+}
+
+/// The claims a permutation check reduces to, for the caller to check against the commitments.
+/// The points are MSB first, as `IOPProof::point`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PermutationCheckSubClaim<F: Field> {
+    /// h_f and h_g both evaluate to `fractions_mean` at (1/2, ..., 1/2)
+    pub fractions_mean: F,
+    /// h_f and h_g at `fractions_point`
+    pub fractions_point: Vec<F>,
+    pub fractions_evals: [F; 2],
+    /// f, s_f, g and s_g at `point`
+    pub point: Vec<F>,
+    pub poly_evals: [F; 4],
+}
+
+pub struct PermutationCheck<F: Field> {
+    phantom: std::marker::PhantomData<F>,
+}
+
+impl<F: Field> PermutationCheck<F> {
+    /// Draw the challenges, on both sides, once f, s_f, g and s_g are committed
+    pub fn challenges(transcript: &mut impl Transcript) -> PermutationCheckChallenges<F> {
+        PermutationCheckChallenges {
+            beta: transcript.generate_field_element::<F>(),
+            gamma: transcript.generate_field_element::<F>(),
+        }
+    }
+
+    #[inline]
+    fn denominators(
+        poly: &MultiLinearPoly<F>,
+        index: &MultiLinearPoly<F>,
+        challenges: &PermutationCheckChallenges<F>,
+    ) -> Vec<F> {
+        poly.coeffs
+            .par_iter()
+            .zip(index.coeffs.par_iter())
+            .map(|(p, s)| challenges.gamma + *p + challenges.beta * *s)
+            .collect()
+    }
+
+    /// The fractions h_f and h_g, for the prover to commit to before `prove`.
+    /// Panics if a denominator vanishes, which happens with a negligible probability.
+    pub fn fractions(
+        f: &MultiLinearPoly<F>,
+        s_f: &MultiLinearPoly<F>,
+        g: &MultiLinearPoly<F>,
+        s_g: &MultiLinearPoly<F>,
+        challenges: &PermutationCheckChallenges<F>,
+    ) -> (MultiLinearPoly<F>, MultiLinearPoly<F>) {
+        let invert = |denominators: Vec<F>| {
+            let inverses = denominators
+                .par_iter()
+                .map(|d| d.inv().expect("vanishing denominator"))
+                .collect();
+            MultiLinearPoly::new(inverses)
+        };
+        (
+            invert(Self::denominators(f, s_f, challenges)),
+            invert(Self::denominators(g, s_g, challenges)),
+        )
+    }
+
+    /// Prove the multisets {(s_f(x), f(x))} and {(s_g(x), g(x))} equal, given the committed
+    /// fractions of `fractions`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn prove(
+        f: &MultiLinearPoly<F>,
+        s_f: &MultiLinearPoly<F>,
+        g: &MultiLinearPoly<F>,
+        s_g: &MultiLinearPoly<F>,
+        h_f: &MultiLinearPoly<F>,
+        h_g: &MultiLinearPoly<F>,
+        challenges: &PermutationCheckChallenges<F>,
+        transcript: &mut impl Transcript,
+    ) -> PermutationCheckProof<F> {
+        let num_vars = f.num_vars();
+        assert!([s_f, g, s_g, h_f, h_g]
+            .iter()
+            .all(|poly| poly.num_vars() == num_vars));
+
+        let half_point = vec![F::INV_2; num_vars];
+        let fractions_mean = h_f.eval_reverse_order(&half_point);
+        transcript.append_field_element(&fractions_mean);
+
+        let alpha = transcript.generate_field_element::<F>();
+        let one = Arc::new(MultiLinearPoly::new(vec![F::ONE; 1 << num_vars]));
+        let mut poly = SumOfProductsPoly::new();
+        poly.add_pair(
+            h_f.clone(),
+            MultiLinearPoly::new(Self::denominators(f, s_f, challenges)),
+        );
+        poly.add_weighted_pair(
+            h_g.clone(),
+            MultiLinearPoly::new(Self::denominators(g, s_g, challenges)),
+            alpha,
+        );
+        poly.add_weighted_pair(Arc::clone(&one), one, -(F::ONE + alpha));
+        let zero_check_proof = ZeroCheck::prove(&poly, transcript);
+
+        let point = &zero_check_proof.sumcheck_proof.point;
+        let poly_evals: Vec<F> = [f, s_f, g, s_g]
+            .iter()
+            .map(|poly| poly.eval_reverse_order(point))
+            .collect();
+        poly_evals
+            .iter()
+            .for_each(|e| transcript.append_field_element(e));
+
+        PermutationCheckProof {
+            fractions_mean,
+            zero_check_proof,
+            poly_evals,
+        }
+    }
+
+    /// Verify the multisets equal, returning the claims on the polynomials, or None if the proof is
+    /// rejected.
+    pub fn verify(
+        num_vars: usize,
+        challenges: &PermutationCheckChallenges<F>,
+        proof: &PermutationCheckProof<F>,
+        transcript: &mut impl Transcript,
+    ) -> Option<PermutationCheckSubClaim<F>> {
+        let &[f, s_f, g, s_g] = proof.poly_evals.as_slice() else {
+            return None;
+        };
+        transcript.append_field_element(&proof.fractions_mean);
+
+        let alpha = transcript.generate_field_element::<F>();
+        let weights = [F::ONE, alpha, -(F::ONE + alpha)];
+        let subclaim = ZeroCheck::verify(num_vars, &weights, &proof.zero_check_proof, transcript)?;
+
+        // the denominators are evaluated from f, s_f, g and s_g, and the constant pair is one
+        let denominators = [
+            challenges.gamma + f + challenges.beta * s_f,
+            challenges.gamma + g + challenges.beta * s_g,
+            F::ONE,
+        ];
+        if subclaim.g_evals != denominators || subclaim.f_evals[2] != F::ONE {
+            return None;
+        }
+        proof
+            .poly_evals
+            .iter()
+            .for_each(|e| transcript.append_field_element(e));
+
+        Some(PermutationCheckSubClaim {
+            fractions_mean: proof.fractions_mean,
+            fractions_point: subclaim.f_point,
+            fractions_evals: [subclaim.f_evals[0], subclaim.f_evals[1]],
+            point: subclaim.g_point,
+            poly_evals: [f, s_f, g, s_g],
+        })
+    }
+}
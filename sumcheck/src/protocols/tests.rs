@@ -0,0 +1,123 @@
+use super::*;
+
+use arith::{Field, Fr};
+use ark_std::test_rng;
+use gkr_engine::Transcript;
+use gkr_hashers::SHA256hasher;
+use polynomials::{MultiLinearPoly, SumOfProductsPoly};
+use transcript::BytesHashTranscript;
+
+type T = BytesHashTranscript<SHA256hasher>;
+
+#[test]
+fn test_zero_check() {
+    let mut rng = test_rng();
+
+    for num_vars in 1..8 {
+        // f g - h, with h the product of f and g
+        let f = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+        let g = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+        let mut h = MultiLinearPoly::new(
+            f.coeffs
+                .iter()
+                .zip(g.coeffs.iter())
+                .map(|(f, g)| *f * *g)
+                .collect(),
+        );
+        let one = MultiLinearPoly::new(vec![Fr::ONE; 1 << num_vars]);
+
+        let mut poly = SumOfProductsPoly::new();
+        poly.add_pair(f.clone(), g.clone());
+        poly.sub_pair(h.clone(), one.clone());
+        let proof = ZeroCheck::prove(&poly, &mut T::new());
+
+        let subclaim = ZeroCheck::verify(num_vars, &poly.weights, &proof, &mut T::new())
+            .expect("zero check verification failed");
+        let f_evals: Vec<Fr> = [&f, &h]
+            .iter()
+            .map(|poly| poly.eval_reverse_order(&subclaim.f_point))
+            .collect();
+        let g_evals: Vec<Fr> = [&g, &one]
+            .iter()
+            .map(|poly| poly.eval_reverse_order(&subclaim.g_point))
+            .collect();
+        assert_eq!(subclaim.f_evals, f_evals);
+        assert_eq!(subclaim.g_evals, g_evals);
+
+        // a single nonzero evaluation is caught
+        h.coeffs[num_vars - 1] += Fr::ONE;
+        let mut poly = SumOfProductsPoly::new();
+        poly.add_pair(f, g);
+        poly.sub_pair(h, one);
+        let proof = ZeroCheck::prove(&poly, &mut T::new());
+        assert!(ZeroCheck::verify(num_vars, &poly.weights, &proof, &mut T::new()).is_none());
+    }
+}
+
+#[test]
+fn test_permutation_check() {
+    let mut rng = test_rng();
+
+    for num_vars in 1..8 {
+        let size = 1usize << num_vars;
+
+        // g(x) = f(sigma(x)) for the permutation sigma(x) = 5 x + 3 mod 2^num_vars
+        let sigma: Vec<usize> = (0..size).map(|i| (5 * i + 3) % size).collect();
+        let f = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+        let g = MultiLinearPoly::new(sigma.iter().map(|i| f.coeffs[*i]).collect());
+        let s_f = MultiLinearPoly::new((0..size).map(|i| Fr::from(i as u32)).collect());
+        let s_g = MultiLinearPoly::new(sigma.iter().map(|i| Fr::from(*i as u32)).collect());
+
+        let mut p_transcript = T::new();
+        let challenges = PermutationCheck::challenges(&mut p_transcript);
+        let (h_f, h_g) = PermutationCheck::fractions(&f, &s_f, &g, &s_g, &challenges);
+        let proof = PermutationCheck::prove(
+            &f,
+            &s_f,
+            &g,
+            &s_g,
+            &h_f,
+            &h_g,
+            &challenges,
+            &mut p_transcript,
+        );
+
+        let mut v_transcript = T::new();
+        let challenges = PermutationCheck::challenges(&mut v_transcript);
+        let subclaim = PermutationCheck::verify(num_vars, &challenges, &proof, &mut v_transcript)
+            .expect("permutation check verification failed");
+        assert_eq!(
+            p_transcript.generate_field_element::<Fr>(),
+            v_transcript.generate_field_element::<Fr>()
+        );
+
+        let half_point = vec![Fr::INV_2; num_vars];
+        assert_eq!(h_f.eval_reverse_order(&half_point), subclaim.fractions_mean);
+        assert_eq!(h_g.eval_reverse_order(&half_point), subclaim.fractions_mean);
+        assert_eq!(
+            subclaim.fractions_evals,
+            [
+                h_f.eval_reverse_order(&subclaim.fractions_point),
+                h_g.eval_reverse_order(&subclaim.fractions_point)
+            ]
+        );
+        assert_eq!(
+            subclaim.poly_evals,
+            [&f, &s_f, &g, &s_g].map(|poly| poly.eval_reverse_order(&subclaim.point))
+        );
+
+        // g no longer a permutation of f: the means of the fractions differ
+        let mut g = g;
+        g.coeffs[0] += Fr::ONE;
+        let mut transcript = T::new();
+        let challenges = PermutationCheck::challenges(&mut transcript);
+        let (h_f, h_g) = PermutationCheck::fractions(&f, &s_f, &g, &s_g, &challenges);
+        let proof =
+            PermutationCheck::prove(&f, &s_f, &g, &s_g, &h_f, &h_g, &challenges, &mut transcript);
+        let mut transcript = T::new();
+        let challenges = PermutationCheck::challenges(&mut transcript);
+        let subclaim = PermutationCheck::verify(num_vars, &challenges, &proof, &mut transcript)
+            .expect("the fractions are well formed");
+        assert_ne!(h_g.eval_reverse_order(&half_point), subclaim.fractions_mean);
+    }
+}
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use arith::Field;
+use gkr_engine::Transcript;
+use polynomials::{EqPolynomial, MultiLinearPoly, MultilinearExtension, SumOfProductsPoly};
+use serdes::ExpSerde;
+
+use crate::{IOPProof, SumCheck, SumcheckInstance};
+
+/// Proof that a sum of products `V(x) = sum_i w_i f_i(x) g_i(x)` vanishes over {0,1}^`num_vars`.
+///
+/// For r drawn from the transcript, V vanishes iff, with high probability, its multilinear
+/// extension does at r, i.e., `sum_x eq(r, x) V(x) = 0`. The eq factor is multiplied into the f_i,
+/// keeping the sumcheck of degree 2, and a second sumcheck reduces the evaluations of the eq(r, .)
+/// f_i to evaluations of the f_i.
+#[derive(Clone, Debug, Default, PartialEq, Eq, ExpSerde)]
+pub struct ZeroCheckProof<F: Field> {
+    /// The sumcheck of `sum_x sum_i w_i (eq(r, .) f_i)(x) g_i(x) = 0`
+    pub sumcheck_proof: IOPProof<F>,
+    /// (eq(r, .) f_i)(p) and g_i(p) of each pair, at the point p of `sumcheck_proof`
+    pub eq_f_evals: Vec<F>,
+    pub g_evals: Vec<F>,
+    /// The sumcheck of `sum_x sum_i lambda^i eq(p, x) eq(r, x) f_i(x) = sum_i lambda^i
+    /// eq_f_evals[i]`
+    pub reduction_proof: IOPProof<F>,
+    /// f_i at the point of `reduction_proof`
+    pub f_evals: Vec<F>,
+}
+
+/// The claims a zero check reduces to, for the caller to check against the commitments.
+/// The points are MSB first, as `IOPProof::point`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ZeroCheckSubClaim<F: Field> {
+    /// The point the first polynomial of each pair is evaluated at
+    pub f_point: Vec<F>,
+    pub f_evals: Vec<F>,
+    /// The point the second polynomial of each pair is evaluated at
+    pub g_point: Vec<F>,
+    pub g_evals: Vec<F>,
+}
+
+pub struct ZeroCheck<F: Field> {
+    phantom: std::marker::PhantomData<F>,
+}
+
+/// The evaluations of eq(r, .) over the hypercube, r being MSB first
+#[inline]
+fn eq_evals_msb_first<F: Field>(r: &[F]) -> Vec<F> {
+    let r_lsb_first: Vec<F> = r.iter().rev().cloned().collect();
+    EqPolynomial::build_eq_x_r(&r_lsb_first)
+}
+
+/// `sum_x eq(a, x) eq(b, x) eq(c, x)` over the hypercube, i.e., the multilinear extension of the
+/// product eq(b, .) eq(c, .) at a
+#[inline]
+fn eq3_eval<F: Field>(a: &[F], b: &[F], c: &[F]) -> F {
+    a.iter()
+        .zip(b.iter())
+        .zip(c.iter())
+        .map(|((a, b), c)| *a * *b * *c + (F::ONE - *a) * (F::ONE - *b) * (F::ONE - *c))
+        .product()
+}
+
+#[inline]
+fn powers<F: Field>(base: F, n: usize) -> Vec<F> {
+    std::iter::successors(Some(F::ONE), |p| Some(*p * base))
+        .take(n)
+        .collect()
+}
+
+/// Run the sumcheck rounds as `SumCheck::prove` does, also returning the final evaluations
+fn prove_sum<F: Field>(
+    poly: &SumOfProductsPoly<F>,
+    transcript: &mut impl Transcript,
+) -> (IOPProof<F>, Vec<(F, F)>) {
+    let mut instance = SumcheckInstance::new(poly);
+    let mut proofs = Vec::with_capacity(instance.num_vars());
+    while !instance.is_finished() {
+        let prover_msg = instance.round_polynomial();
+        transcript.append_serializable_data(&prover_msg);
+        proofs.push(prover_msg);
+        instance.bind_challenge(transcript.generate_field_element::<F>());
+    }
+
+    let proof = IOPProof {
+        proofs,
+        point: instance.challenges().to_vec(),
+    };
+    (proof, instance.final_evals())
+}
+
+impl<F: Field> ZeroCheck<F> {
+    /// Prove that `poly` vanishes over {0,1}^`num_vars`. All the pairs have the same number of
+    /// variables, at least one.
+    pub fn prove(
+        poly: &SumOfProductsPoly<F>,
+        transcript: &mut impl Transcript,
+    ) -> ZeroCheckProof<F> {
+        let num_vars = poly.num_vars();
+        assert!(num_vars > 0, "the zero check of a constant");
+        assert!(
+            poly.f_and_g_pairs
+                .iter()
+                .all(|(f, _)| f.num_vars() == num_vars),
+            "the pairs have different numbers of variables"
+        );
+
+        let r = transcript.generate_field_elements::<F>(num_vars);
+        let eq_r = eq_evals_msb_first(&r);
+
+        let mut eq_weighted = SumOfProductsPoly::new();
+        poly.f_and_g_pairs
+            .iter()
+            .zip(poly.weights.iter())
+            .for_each(|((f, g), w)| {
+                let eq_f = f.coeffs.iter().zip(eq_r.iter()).map(|(f, e)| *f * *e);
+                eq_weighted.add_weighted_pair(
+                    MultiLinearPoly::new(eq_f.collect()),
+                    Arc::clone(g),
+                    *w,
+                );
+            });
+        let (sumcheck_proof, final_evals) = prove_sum(&eq_weighted, transcript);
+        let (eq_f_evals, g_evals): (Vec<F>, Vec<F>) = final_evals.into_iter().unzip();
+        eq_f_evals
+            .iter()
+            .chain(g_evals.iter())
+            .for_each(|e| transcript.append_field_element(e));
+
+        let lambda = transcript.generate_field_element::<F>();
+        let eq_p = eq_evals_msb_first(&sumcheck_proof.point);
+        let eq_p_eq_r: Arc<MultiLinearPoly<F>> = Arc::new(MultiLinearPoly::new(
+            eq_p.iter().zip(eq_r.iter()).map(|(p, r)| *p * *r).collect(),
+        ));
+        let mut reduction = SumOfProductsPoly::new();
+        poly.f_and_g_pairs
+            .iter()
+            .zip(powers(lambda, poly.f_and_g_pairs.len()))
+            .for_each(|((f, _), lambda_i)| {
+                reduction.add_weighted_pair(Arc::clone(&eq_p_eq_r), Arc::clone(f), lambda_i)
+            });
+        let (reduction_proof, final_evals) = prove_sum(&reduction, transcript);
+        let f_evals: Vec<F> = final_evals.into_iter().map(|(_, f)| f).collect();
+        f_evals
+            .iter()
+            .for_each(|e| transcript.append_field_element(e));
+
+        ZeroCheckProof {
+            sumcheck_proof,
+            eq_f_evals,
+            g_evals,
+            reduction_proof,
+            f_evals,
+        }
+    }
+
+    /// Verify that the sum of products of weights `weights` vanishes, returning the claims on the
+    /// polynomials, or None if the proof is rejected.
+    pub fn verify(
+        num_vars: usize,
+        weights: &[F],
+        proof: &ZeroCheckProof<F>,
+        transcript: &mut impl Transcript,
+    ) -> Option<ZeroCheckSubClaim<F>> {
+        let num_pairs = weights.len();
+        if num_vars == 0
+            || proof.eq_f_evals.len() != num_pairs
+            || proof.g_evals.len() != num_pairs
+            || proof.f_evals.len() != num_pairs
+        {
+            return None;
+        }
+
+        let r = transcript.generate_field_elements::<F>(num_vars);
+
+        let (verified, subclaim) =
+            SumCheck::verify(F::ZERO, &proof.sumcheck_proof, num_vars, transcript);
+        let expected: F = weights
+            .iter()
+            .zip(proof.eq_f_evals.iter().zip(proof.g_evals.iter()))
+            .map(|(w, (eq_f, g))| *w * *eq_f * *g)
+            .sum();
+        if !verified || expected != subclaim.expected_evaluation {
+            return None;
+        }
+        proof
+            .eq_f_evals
+            .iter()
+            .chain(proof.g_evals.iter())
+            .for_each(|e| transcript.append_field_element(e));
+
+        let lambda = transcript.generate_field_element::<F>();
+        let lambda_powers = powers(lambda, num_pairs);
+        let claimed_sum: F = lambda_powers
+            .iter()
+            .zip(proof.eq_f_evals.iter())
+            .map(|(l, e)| *l * *e)
+            .sum();
+        let (verified, reduction_subclaim) =
+            SumCheck::verify(claimed_sum, &proof.reduction_proof, num_vars, transcript);
+        let expected = eq3_eval(&reduction_subclaim.point, &subclaim.point, &r)
+            * lambda_powers
+                .iter()
+                .zip(proof.f_evals.iter())
+                .map(|(l, f)| *l * *f)
+                .sum::<F>();
+        if !verified || expected != reduction_subclaim.expected_evaluation {
+            return None;
+        }
+        proof
+            .f_evals
+            .iter()
+            .for_each(|e| transcript.append_field_element(e));
+
+        Some(ZeroCheckSubClaim {
+            f_point: reduction_subclaim.point,
+            f_evals: proof.f_evals.clone(),
+            g_point: subclaim.point,
+            g_evals: proof.g_evals.clone(),
+        })
+    }
+}
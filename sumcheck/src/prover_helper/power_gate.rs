@@ -4,6 +4,7 @@
 use std::ops::Mul;
 
 use arith::{Field, SimdField};
+use circuit::CustomGateDef;
 use gkr_engine::FieldEngine;
 
 pub(crate) struct SumcheckPowerGateHelper<const D: usize> {
@@ -44,30 +45,28 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
             + p_add_coef_2 * F::CircuitField::from(36);
     }
 
+    /// Add the evaluations of `sum hg(x) * gate(v(x))` along the current variable to `p`
     #[inline]
-    fn evaluate<VF: Field, ChallengeF: Field, EvalF>(
+    fn evaluate_uni<VF: Field, ChallengeF: Field, EvalF>(
         eval_size: usize,
         src_v: &[VF],
-        bk_hg_5: &[ChallengeF],
-        bk_hg_1: &[ChallengeF],
-        gate_exists_5: &[bool],
-        gate_exists_1: &[bool],
+        bk_hg: &[ChallengeF],
+        gate_exists: &[bool],
+        gate: impl Fn(VF) -> VF,
         p: &mut [EvalF],
-    ) -> [EvalF; 3]
-    where
-        EvalF: Field + From<ChallengeF> + Mul<ChallengeF, Output = EvalF> + Mul<VF, Output = EvalF>,
+    ) where
+        EvalF: Field + From<ChallengeF> + Mul<VF, Output = EvalF>,
     {
-        log::trace!("Eval size: {eval_size}");
         for i in 0..eval_size {
-            if !gate_exists_5[i * 2] && !gate_exists_5[i * 2 + 1] {
+            if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
                 continue;
             }
             let mut f_v = [VF::ZERO; D];
             let mut hg_v = [ChallengeF::ZERO; D];
             f_v[0] = src_v[i * 2];
             f_v[1] = src_v[i * 2 + 1];
-            hg_v[0] = bk_hg_5[i * 2];
-            hg_v[1] = bk_hg_5[i * 2 + 1];
+            hg_v[0] = bk_hg[i * 2];
+            hg_v[1] = bk_hg[i * 2 + 1];
             let delta_f = f_v[1] - f_v[0];
             let delta_hg = hg_v[1] - hg_v[0];
 
@@ -76,10 +75,39 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
                 hg_v[i] = hg_v[i - 1] + delta_hg;
             }
             for i in 0..D {
-                let pow5 = f_v[i].square().square() * f_v[i];
-                p[i] += EvalF::from(hg_v[i]) * pow5;
+                p[i] += EvalF::from(hg_v[i]) * gate(f_v[i]);
             }
         }
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::type_complexity)]
+    fn evaluate<VF: Field, ChallengeF: Field, EvalF>(
+        eval_size: usize,
+        src_v: &[VF],
+        bk_hg_5: &[ChallengeF],
+        bk_hg_1: &[ChallengeF],
+        gate_exists_5: &[bool],
+        gate_exists_1: &[bool],
+        custom_gates: &[(fn(VF) -> VF, &[ChallengeF], &[bool])],
+        p: &mut [EvalF],
+    ) -> [EvalF; 3]
+    where
+        EvalF: Field + From<ChallengeF> + Mul<ChallengeF, Output = EvalF> + Mul<VF, Output = EvalF>,
+    {
+        log::trace!("Eval size: {eval_size}");
+        Self::evaluate_uni(
+            eval_size,
+            src_v,
+            bk_hg_5,
+            gate_exists_5,
+            |v| v.square().square() * v,
+            p,
+        );
+        for &(gate, bk_hg, gate_exists) in custom_gates {
+            Self::evaluate_uni(eval_size, src_v, bk_hg, gate_exists, gate, p);
+        }
 
         let mut p_add = [EvalF::ZERO; 3];
         for i in 0..eval_size {
@@ -115,6 +143,9 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
         init_v: &[F::SimdCircuitField],
         gate_exists_5: &[bool],
         gate_exists_1: &[bool],
+        custom_gates: &[CustomGateDef<F>],
+        bk_custom_hg: &[Vec<F::ChallengeField>],
+        custom_gate_exists: &[Vec<bool>],
     ) -> [F::Field; D] {
         let mut p = [F::Field::zero(); D];
         log::trace!("bk_f: {:?}", &bk_f[..4]);
@@ -124,6 +155,11 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
         let eval_size = 1 << (self.var_num - var_idx - 1);
         let p_add = {
             if var_idx == 0 {
+                let custom_gates = custom_gates
+                    .iter()
+                    .zip(bk_custom_hg.iter().zip(custom_gate_exists))
+                    .map(|(def, (hg, exists))| (def.eval_circuit, &hg[..], &exists[..]))
+                    .collect::<Vec<_>>();
                 Self::evaluate(
                     eval_size,
                     init_v,
@@ -131,9 +167,15 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
                     bk_hg_1,
                     gate_exists_5,
                     gate_exists_1,
+                    &custom_gates,
                     &mut p,
                 )
             } else {
+                let custom_gates = custom_gates
+                    .iter()
+                    .zip(bk_custom_hg.iter().zip(custom_gate_exists))
+                    .map(|(def, (hg, exists))| (def.eval_field, &hg[..], &exists[..]))
+                    .collect::<Vec<_>>();
                 Self::evaluate(
                     eval_size,
                     bk_f,
@@ -141,6 +183,7 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
                     bk_hg_1,
                     gate_exists_5,
                     gate_exists_1,
+                    &custom_gates,
                     &mut p,
                 )
             }
@@ -161,6 +204,8 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
         init_v: &[F::SimdCircuitField],
         gate_exists_5: &mut [bool],
         gate_exists_1: &mut [bool],
+        bk_custom_hg: &mut [Vec<F::ChallengeField>],
+        custom_gate_exists: &mut [Vec<bool>],
     ) {
         assert_eq!(var_idx, self.sumcheck_var_idx);
         assert!(var_idx < self.var_num);
@@ -179,24 +224,34 @@ impl<const D: usize> SumcheckPowerGateHelper<D> {
             }
         }
 
-        for i in 0..self.cur_eval_size {
-            if !gate_exists_5[i * 2] && !gate_exists_5[i * 2 + 1] {
-                gate_exists_5[i] = false;
-                bk_hg_5[i] = F::ChallengeField::zero();
-            } else {
-                gate_exists_5[i] = true;
-                bk_hg_5[i] = bk_hg_5[2 * i] + (bk_hg_5[2 * i + 1] - bk_hg_5[2 * i]) * r;
-            }
+        Self::fold_hg::<F>(self.cur_eval_size, r, bk_hg_5, gate_exists_5);
+        Self::fold_hg::<F>(self.cur_eval_size, r, bk_hg_1, gate_exists_1);
+        bk_custom_hg
+            .iter_mut()
+            .zip(custom_gate_exists.iter_mut())
+            .for_each(|(bk_hg, gate_exists)| {
+                Self::fold_hg::<F>(self.cur_eval_size, r, bk_hg, gate_exists)
+            });
 
-            if !gate_exists_1[i * 2] && !gate_exists_1[i * 2 + 1] {
-                gate_exists_1[i] = false;
-                bk_hg_1[i] = F::ChallengeField::zero();
+        self.sumcheck_var_idx += 1;
+    }
+
+    /// Fix the current variable of `bk_hg` to `r`
+    #[inline]
+    fn fold_hg<F: FieldEngine>(
+        eval_size: usize,
+        r: F::ChallengeField,
+        bk_hg: &mut [F::ChallengeField],
+        gate_exists: &mut [bool],
+    ) {
+        for i in 0..eval_size {
+            if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
+                gate_exists[i] = false;
+                bk_hg[i] = F::ChallengeField::zero();
             } else {
-                gate_exists_1[i] = true;
-                bk_hg_1[i] = bk_hg_1[2 * i] + (bk_hg_1[2 * i + 1] - bk_hg_1[2 * i]) * r;
+                gate_exists[i] = true;
+                bk_hg[i] = bk_hg[2 * i] + (bk_hg[2 * i + 1] - bk_hg[2 * i]) * r;
             }
         }
-
-        self.sumcheck_var_idx += 1;
     }
 }
@@ -29,6 +29,8 @@ impl SumcheckProductGateHelper {
         let mut p1 = EvalF::ZERO;
         let mut p2 = EvalF::ZERO;
         for i in 0..eval_size {
+            // NOTE: whether a gate exists follows from the wiring of the circuit, never from the
+            // witness, so skipping the missing gates leaks nothing about the witness
             if !gate_exists[i * 2] && !gate_exists[i * 2 + 1] {
                 continue;
             }
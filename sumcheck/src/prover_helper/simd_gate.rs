@@ -91,6 +91,7 @@ impl<F: FieldEngine> SumcheckSimdProdGateHelper<F> {
     /// Evaluate the GKR2 sumcheck polynomial at a SIMD variable,
     /// after x-sumcheck rounds have fixed the x variables. The
     /// polynomial is degree (D-1) in the SIMD variables.
+    #[allow(clippy::type_complexity)]
     pub(crate) fn gkr2_poly_eval_at<const D: usize>(
         &self,
         var_idx: usize,
@@ -98,6 +99,10 @@ impl<F: FieldEngine> SumcheckSimdProdGateHelper<F> {
         bk_v_simd: &[F::ChallengeField],
         add_eval: F::ChallengeField,
         pow_5_eval: F::ChallengeField,
+        custom_gate_evals: &[(
+            F::ChallengeField,
+            fn(F::ChallengeField) -> F::ChallengeField,
+        )],
     ) -> [F::ChallengeField; D] {
         let mut p = [F::ChallengeField::zero(); D];
         let mut p_add = [F::ChallengeField::zero(); 3];
@@ -125,6 +130,13 @@ impl<F: FieldEngine> SumcheckSimdProdGateHelper<F> {
                 p[i] += pow_5_eval * pow5 * eq_v[i];
             }
 
+            // Evaluate the terms eq(A, r_z) * G(r_z, r_x) * gate(V(A, r_x)) of the custom gates
+            for &(gate_eval, gate) in custom_gate_evals {
+                for i in 0..D {
+                    p[i] += gate_eval * gate(f_v[i]) * eq_v[i];
+                }
+            }
+
             // Evaluate term eq(A, r_z) * Add(r_z, r_x) * V(A, r_x)
             p_add[0] += add_eval * f_v[0] * eq_v[0];
             p_add[1] += add_eval * f_v[1] * eq_v[1];
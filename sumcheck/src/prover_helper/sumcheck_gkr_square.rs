@@ -1,7 +1,7 @@
 use crate::{unpack_and_combine, ProverScratchPad};
 use arith::{Field, SimdField};
-use circuit::CircuitLayer;
-use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine};
+use circuit::{CircuitLayer, CustomGateDef, POW1_GATE_TYPE, POW5_GATE_TYPE};
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine, MPIResult};
 use polynomials::EqPolynomial;
 
 use super::{power_gate::SumcheckPowerGateHelper, simd_gate::SumcheckSimdProdGateHelper};
@@ -13,6 +13,7 @@ pub(crate) struct SumcheckGkrSquareHelper<'a, F: FieldEngine, const D: usize> {
     pub(crate) r_mpi_var: Vec<F::ChallengeField>,
 
     layer: &'a CircuitLayer<F>,
+    custom_gates: Vec<CustomGateDef<F>>,
     sp: &'a mut ProverScratchPad<F>,
 
     challenge: &'a ExpanderSingleVarChallenge<F>,
@@ -43,6 +44,7 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             r_mpi_var: vec![],
 
             layer,
+            custom_gates: layer.custom_gates(),
             sp,
             challenge,
             simd_var_num,
@@ -50,14 +52,14 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             x_helper: SumcheckPowerGateHelper::new(layer.input_var_num),
             simd_helper: SumcheckSimdProdGateHelper::new(simd_var_num),
             mpi_helper: SumcheckSimdProdGateHelper::new(
-                mpi_config.world_size().trailing_zeros() as usize
+                mpi_config.n_parties().trailing_zeros() as usize
             ),
             mpi_config,
         }
     }
 
     #[inline]
-    pub(crate) fn poly_evals_at_x(&self, var_idx: usize) -> [F::ChallengeField; D] {
+    pub(crate) fn poly_evals_at_x(&self, var_idx: usize) -> MPIResult<[F::ChallengeField; D]> {
         let local_vals_simd = self.x_helper.poly_eval_at::<F>(
             var_idx,
             &self.sp.v_evals,
@@ -66,6 +68,9 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             &self.layer.input_vals,
             &self.sp.gate_exists_5,
             &self.sp.gate_exists_1,
+            &self.custom_gates,
+            &self.sp.custom_hg_evals,
+            &self.sp.custom_gate_exists,
         );
 
         // SIMD
@@ -74,14 +79,15 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             .map(|p| unpack_and_combine(p, &self.sp.eq_evals_at_r_simd0))
             .collect::<Vec<F::ChallengeField>>();
 
-        self.mpi_config
-            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)
+        Ok(self
+            .mpi_config
+            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)?
             .try_into()
-            .unwrap()
+            .unwrap())
     }
 
     #[inline]
-    pub(crate) fn poly_evals_at_simd(&self, var_idx: usize) -> [F::ChallengeField; D] {
+    pub(crate) fn poly_evals_at_simd(&self, var_idx: usize) -> MPIResult<[F::ChallengeField; D]> {
         let local_vals = self
             .simd_helper
             .gkr2_poly_eval_at::<D>(
@@ -90,23 +96,26 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
                 &self.sp.simd_var_v_evals,
                 self.sp.hg_evals_1[0],
                 self.sp.hg_evals_5[0],
+                &self.custom_gate_evals(),
             )
             .to_vec();
 
-        self.mpi_config
-            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)
+        Ok(self
+            .mpi_config
+            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)?
             .try_into()
-            .unwrap()
+            .unwrap())
     }
 
     pub(crate) fn poly_evals_at_mpi(&mut self, var_idx: usize) -> [F::ChallengeField; D] {
-        assert!(var_idx < self.mpi_config.world_size().trailing_zeros() as usize);
+        assert!(var_idx < self.mpi_config.n_parties().trailing_zeros() as usize);
         let mut evals = self.mpi_helper.gkr2_poly_eval_at::<D>(
             var_idx,
             &self.sp.eq_evals_at_r_mpi0,
             &self.sp.mpi_var_v_evals,
             self.sp.hg_evals_1[0],
             self.sp.hg_evals_5[0],
+            &self.custom_gate_evals(),
         );
         for eval in evals.iter_mut() {
             *eval *= self.sp.eq_evals_at_r_simd0[0];
@@ -125,6 +134,8 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             &self.layer.input_vals,
             &mut self.sp.gate_exists_5,
             &mut self.sp.gate_exists_1,
+            &mut self.sp.custom_hg_evals,
+            &mut self.sp.custom_gate_exists,
         );
         log::trace!("v_eval[0]:= {:?}", self.sp.v_evals[0]);
         self.rx.push(r);
@@ -154,6 +165,22 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
         self.r_mpi_var.push(r);
     }
 
+    /// The custom gates and their `hg` once the x variables are fixed
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn custom_gate_evals(
+        &self,
+    ) -> Vec<(
+        F::ChallengeField,
+        fn(F::ChallengeField) -> F::ChallengeField,
+    )> {
+        self.custom_gates
+            .iter()
+            .zip(&self.sp.custom_hg_evals)
+            .map(|(def, hg)| (hg[0], def.eval_challenge))
+            .collect()
+    }
+
     #[inline(always)]
     pub(crate) fn vx_claim(&self) -> F::ChallengeField {
         self.sp.mpi_var_v_evals[0]
@@ -188,9 +215,9 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
     }
 
     #[inline]
-    pub(crate) fn prepare_mpi_var_vals(&mut self) {
+    pub(crate) fn prepare_mpi_var_vals(&mut self) -> MPIResult<()> {
         self.mpi_config
-            .gather_vec(&[self.sp.simd_var_v_evals[0]], &mut self.sp.mpi_var_v_evals);
+            .gather_padded_vec(&[self.sp.simd_var_v_evals[0]], &mut self.sp.mpi_var_v_evals)
     }
 
     #[inline]
@@ -220,17 +247,43 @@ impl<'a, F: FieldEngine, const D: usize> SumcheckGkrSquareHelper<'a, F, D> {
             &mut self.sp.eq_evals_second_half,
         );
 
+        self.sp
+            .custom_hg_evals
+            .resize(self.custom_gates.len(), vec![]);
+        self.sp
+            .custom_gate_exists
+            .resize(self.custom_gates.len(), vec![]);
+        self.sp.custom_hg_evals.iter_mut().for_each(|hg_evals| {
+            hg_evals.clear();
+            hg_evals.resize(vals.len(), F::ChallengeField::zero());
+        });
+        self.sp
+            .custom_gate_exists
+            .iter_mut()
+            .for_each(|gate_exists| {
+                gate_exists.clear();
+                gate_exists.resize(vals.len(), false);
+            });
+
         for g in uni.iter() {
             match g.gate_type {
-                12345 => {
+                POW5_GATE_TYPE => {
                     hg_evals_5[g.i_ids[0]] += eq_evals_at_rz0[g.o_id] * g.coef;
                     gate_exists_5[g.i_ids[0]] = true;
                 }
-                12346 => {
+                POW1_GATE_TYPE => {
                     hg_evals_1[g.i_ids[0]] += eq_evals_at_rz0[g.o_id] * g.coef;
                     gate_exists_1[g.i_ids[0]] = true;
                 }
-                _ => panic!("Unsupported gate type"),
+                gate_type => {
+                    let k = self
+                        .custom_gates
+                        .iter()
+                        .position(|def| def.gate_type == gate_type)
+                        .unwrap();
+                    self.sp.custom_hg_evals[k][g.i_ids[0]] += eq_evals_at_rz0[g.o_id] * g.coef;
+                    self.sp.custom_gate_exists[k][g.i_ids[0]] = true;
+                }
             }
         }
     }
@@ -1,6 +1,6 @@
 use arith::{Field, SimdField};
 use circuit::CircuitLayer;
-use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, MPIEngine};
+use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, MPIEngine, MPIResult};
 use polynomials::EqPolynomial;
 
 use crate::{unpack_and_combine, ProverScratchPad};
@@ -74,7 +74,7 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
             xy_helper: SumcheckProductGateHelper::new(layer.input_var_num),
             simd_var_helper: SumcheckSimdProdGateHelper::new(simd_var_num),
             mpi_var_helper: SumcheckSimdProdGateHelper::new(
-                mpi_config.world_size().trailing_zeros() as usize,
+                mpi_config.n_parties().trailing_zeros() as usize,
             ),
             is_output_layer,
         }
@@ -85,7 +85,7 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
         var_idx: usize,
         degree: usize,
         mpi_config: &impl MPIEngine,
-    ) -> [F::ChallengeField; 3] {
+    ) -> MPIResult<[F::ChallengeField; 3]> {
         assert!(var_idx < self.input_var_num);
         let local_vals_simd = self.xy_helper.poly_eval_at::<F>(
             var_idx,
@@ -103,10 +103,10 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
             .collect::<Vec<F::ChallengeField>>();
 
         // MPI
-        mpi_config
-            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)
+        Ok(mpi_config
+            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)?
             .try_into()
-            .unwrap()
+            .unwrap())
     }
 
     pub(crate) fn poly_evals_at_r_simd_var(
@@ -114,7 +114,7 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
         var_idx: usize,
         degree: usize,
         mpi_config: &impl MPIEngine,
-    ) -> [F::ChallengeField; 4] {
+    ) -> MPIResult<[F::ChallengeField; 4]> {
         assert!(var_idx < self.simd_var_num);
         let local_vals = self
             .simd_var_helper
@@ -127,10 +127,10 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
             )
             .to_vec();
 
-        mpi_config
-            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)
+        Ok(mpi_config
+            .coef_combine_vec(&local_vals, &self.sp.eq_evals_at_r_mpi0)?
             .try_into()
-            .unwrap()
+            .unwrap())
     }
 
     pub(crate) fn poly_evals_at_r_mpi_var(
@@ -154,13 +154,13 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
         var_idx: usize,
         degree: usize,
         mpi_config: &impl MPIEngine,
-    ) -> [F::ChallengeField; 3] {
-        let [p0, p1, p2] = self.poly_evals_at_rx(var_idx, degree, mpi_config);
-        [
+    ) -> MPIResult<[F::ChallengeField; 3]> {
+        let [p0, p1, p2] = self.poly_evals_at_rx(var_idx, degree, mpi_config)?;
+        Ok([
             p0 * self.sp.phase2_coef,
             p1 * self.sp.phase2_coef,
             p2 * self.sp.phase2_coef,
-        ]
+        ])
     }
 
     #[inline]
@@ -207,9 +207,9 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
     }
 
     #[inline(always)]
-    pub(crate) fn vy_claim(&self, mpi_config: &impl MPIEngine) -> F::ChallengeField {
+    pub(crate) fn vy_claim(&self, mpi_config: &impl MPIEngine) -> MPIResult<F::ChallengeField> {
         let vy_local = unpack_and_combine(&self.sp.v_evals[0], &self.sp.eq_evals_at_r_simd0);
-        mpi_config.coef_combine_vec(&[vy_local], &self.sp.eq_evals_at_r_mpi0)[0]
+        Ok(mpi_config.coef_combine_vec(&[vy_local], &self.sp.eq_evals_at_r_mpi0)?[0])
     }
 
     #[inline]
@@ -305,22 +305,55 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
     }
 
     #[inline]
-    pub(crate) fn prepare_mpi_var_vals(&mut self, mpi_config: &impl MPIEngine) {
-        mpi_config.gather_vec(&[self.sp.simd_var_v_evals[0]], &mut self.sp.mpi_var_v_evals);
-        mpi_config.gather_vec(
+    pub(crate) fn prepare_mpi_var_vals(&mut self, mpi_config: &impl MPIEngine) -> MPIResult<()> {
+        mpi_config
+            .gather_padded_vec(&[self.sp.simd_var_v_evals[0]], &mut self.sp.mpi_var_v_evals)?;
+        mpi_config.gather_padded_vec(
             &[self.sp.simd_var_hg_evals[0] * self.sp.eq_evals_at_r_simd0[0]],
             &mut self.sp.mpi_var_hg_evals,
-        );
+        )
     }
 
     #[inline]
-    pub(crate) fn prepare_y_vals(&mut self, mpi_config: &impl MPIEngine) {
+    pub(crate) fn prepare_y_vals(&mut self, mpi_config: &impl MPIEngine) -> MPIResult<()> {
+        // TODO-Optimization: For root process, _eq_vec does not have to be recomputed
+        let phase2_eq =
+            EqPolynomial::<F::ChallengeField>::eq_vec(&self.challenge.r_mpi, &self.r_mpi_var)
+                * self.sp.eq_evals_at_r_simd0[0];
+
+        // v(rx, r_simd, r_mpi) is only known to the root, broadcast it while the EQ polys for
+        // the next round are evaluated
         let mut v_rx_rsimd_rw = self.sp.mpi_var_v_evals[0];
-        mpi_config.root_broadcast_f(&mut v_rx_rsimd_rw);
+        mpi_config.ibroadcast_f(&mut v_rx_rsimd_rw, || {
+            EqPolynomial::<F::ChallengeField>::eq_eval_at(
+                &self.r_mpi_var,
+                &F::ChallengeField::ONE,
+                &mut self.sp.eq_evals_at_r_mpi0,
+                &mut self.sp.eq_evals_first_half,
+                &mut self.sp.eq_evals_second_half,
+            );
+
+            EqPolynomial::<F::ChallengeField>::eq_eval_at(
+                &self.rx,
+                &F::ChallengeField::ONE,
+                &mut self.sp.eq_evals_at_rx,
+                &mut self.sp.eq_evals_first_half,
+                &mut self.sp.eq_evals_second_half,
+            );
+
+            EqPolynomial::<F::ChallengeField>::eq_eval_at(
+                &self.r_simd_var,
+                &F::ChallengeField::ONE,
+                &mut self.sp.eq_evals_at_r_simd0,
+                &mut self.sp.eq_evals_first_half,
+                &mut self.sp.eq_evals_second_half,
+            );
+        })?;
+        self.sp.phase2_coef = phase2_eq * v_rx_rsimd_rw;
 
         let mul = &self.layer.mul;
         let eq_evals_at_rz0 = &self.sp.eq_evals_at_rz0;
-        let eq_evals_at_rx = &mut self.sp.eq_evals_at_rx;
+        let eq_evals_at_rx = &self.sp.eq_evals_at_rx;
         let gate_exists = &mut self.sp.gate_exists_5;
         let hg_vals = &mut self.sp.hg_evals;
         let fill_len = 1 << self.rx.len();
@@ -333,42 +366,12 @@ impl<'a, F: FieldEngine> SumcheckGkrVanillaHelper<'a, F> {
             std::ptr::write_bytes(gate_exists.as_mut_ptr(), 0, fill_len);
         }
 
-        // TODO-Optimization: For root process, _eq_vec does not have to be recomputed
-        self.sp.phase2_coef =
-            EqPolynomial::<F::ChallengeField>::eq_vec(&self.challenge.r_mpi, &self.r_mpi_var)
-                * self.sp.eq_evals_at_r_simd0[0]
-                * v_rx_rsimd_rw;
-
-        // EQ Polys for next round
-        EqPolynomial::<F::ChallengeField>::eq_eval_at(
-            &self.r_mpi_var,
-            &F::ChallengeField::ONE,
-            &mut self.sp.eq_evals_at_r_mpi0,
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
-        );
-
-        EqPolynomial::<F::ChallengeField>::eq_eval_at(
-            &self.rx,
-            &F::ChallengeField::ONE,
-            eq_evals_at_rx,
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
-        );
-
-        EqPolynomial::<F::ChallengeField>::eq_eval_at(
-            &self.r_simd_var,
-            &F::ChallengeField::ONE,
-            &mut self.sp.eq_evals_at_r_simd0,
-            &mut self.sp.eq_evals_first_half,
-            &mut self.sp.eq_evals_second_half,
-        );
-
         // TODO-OPTIMIZATION: hg_vals does not have to be simd here
         for g in mul.iter() {
             hg_vals[g.i_ids[1]] +=
                 F::Field::from(eq_evals_at_rz0[g.o_id] * eq_evals_at_rx[g.i_ids[0]] * g.coef);
             gate_exists[g.i_ids[1]] = true;
         }
+        Ok(())
     }
 }
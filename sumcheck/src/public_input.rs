@@ -0,0 +1,74 @@
+//! Evaluations of the public input at the points of the verifier, computed from the public input
+//! itself rather than committed by the prover and opened.
+//!
+//! The public input is laid out as the input of the circuit is, process after process: the
+//! `local_input_size` values of process `i` start at `i * local_input_size`, each packing the
+//! values of all the SIMD lanes. Its multilinear extension is
+//!
+//! ```text
+//! pi~(rz, r_simd, r_mpi) = sum_{i, j, l} eq(r_mpi, i) * eq(rz, j) * eq(r_simd, l) * pi[i][j][l]
+//! ```
+//!
+//! over the public inputs `j` of each process, zero padded to `2^rz.len()`. As the public input
+//! is known to the verifier and usually small, evaluating it costs `O(#public inputs)` field
+//! operations, which is cheaper than an opening of the PCS and keeps it out of the proof.
+use arith::Field;
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine};
+use polynomials::EqPolynomial;
+
+use crate::unpack_and_combine;
+
+/// The public input `input_idx` of all processes, combined with the eq evaluations at the MPI
+/// and at the SIMD variables, i.e., `pi~(input_idx, r_simd, r_mpi)`, in `O(#processes)`
+#[inline]
+pub fn combine_public_input<F: FieldEngine>(
+    public_input: &[F::SimdCircuitField],
+    input_idx: usize,
+    eq_evals_at_r_simd: &[F::ChallengeField],
+    eq_evals_at_r_mpi: &[F::ChallengeField],
+) -> F::ChallengeField {
+    let local_input_size = public_input.len() / eq_evals_at_r_mpi.len();
+
+    let input_mpi_combined: F::Field = public_input
+        .iter()
+        .skip(input_idx)
+        .step_by(local_input_size)
+        .zip(eq_evals_at_r_mpi)
+        .map(|(v, c)| *c * *v)
+        .sum();
+    unpack_and_combine::<F::Field>(&input_mpi_combined, eq_evals_at_r_simd)
+}
+
+/// Evaluate the multilinear extension of `public_input` at `challenge`, in `O(#public inputs)`
+///
+/// # Panics
+/// If the public input is not split evenly between the processes of `challenge.r_mpi`, or the
+/// share of a process has more than `2^challenge.rz.len()` values
+pub fn eval_public_input_mle<F: FieldEngine>(
+    public_input: &[F::SimdCircuitField],
+    challenge: &ExpanderSingleVarChallenge<F>,
+) -> F::ChallengeField {
+    let world_size = 1 << challenge.r_mpi.len();
+    assert_eq!(public_input.len() % world_size, 0);
+    let local_input_size = public_input.len() / world_size;
+    assert!(local_input_size <= 1 << challenge.rz.len());
+    if public_input.is_empty() {
+        return F::ChallengeField::ZERO;
+    }
+
+    let eq_evals_at_rz = EqPolynomial::<F::ChallengeField>::build_eq_x_r(&challenge.rz);
+    let eq_evals_at_r_simd = EqPolynomial::<F::ChallengeField>::build_eq_x_r(&challenge.r_simd);
+    let eq_evals_at_r_mpi = EqPolynomial::<F::ChallengeField>::build_eq_x_r(&challenge.r_mpi);
+
+    let combined: F::Field = public_input
+        .chunks(local_input_size)
+        .zip(&eq_evals_at_r_mpi)
+        .flat_map(|(local_input, eq_mpi)| {
+            local_input
+                .iter()
+                .zip(&eq_evals_at_rz)
+                .map(move |(v, eq_z)| (*eq_mpi * *eq_z) * *v)
+        })
+        .sum();
+    unpack_and_combine::<F::Field>(&combined, &eq_evals_at_r_simd)
+}
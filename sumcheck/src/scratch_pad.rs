@@ -27,6 +27,11 @@ pub struct ProverScratchPad<F: FieldEngine> {
     pub gate_exists_5: Vec<bool>,
     pub gate_exists_1: Vec<bool>,
 
+    /// The `hg_evals_5` and `gate_exists_5` of each custom gate of the layer, in the order of
+    /// `CircuitLayer::custom_gates`
+    pub custom_hg_evals: Vec<Vec<F::ChallengeField>>,
+    pub custom_gate_exists: Vec<Vec<bool>>,
+
     pub phase2_coef: F::ChallengeField,
 }
 
@@ -35,6 +40,8 @@ impl<F: FieldEngine> ProverScratchPad<F> {
         let max_input_size = 1 << max_num_input_var;
         let max_output_size = 1 << max_num_output_var;
         let max_io_size = max(max_input_size, max_output_size);
+        // the processes padded with virtual parties, see `MPIEngine::n_parties`
+        let n_parties = mpi_world_size.next_power_of_two();
         ProverScratchPad {
             v_evals: vec![F::Field::default(); max_input_size],
             hg_evals_5: vec![F::ChallengeField::default(); max_input_size],
@@ -42,30 +49,26 @@ impl<F: FieldEngine> ProverScratchPad<F> {
             hg_evals: vec![F::Field::default(); max_input_size],
             simd_var_v_evals: vec![F::ChallengeField::default(); F::get_field_pack_size()],
             simd_var_hg_evals: vec![F::ChallengeField::default(); F::get_field_pack_size()],
-            mpi_var_v_evals: vec![F::ChallengeField::default(); mpi_world_size],
-            mpi_var_hg_evals: vec![F::ChallengeField::default(); mpi_world_size],
+            mpi_var_v_evals: vec![F::ChallengeField::default(); n_parties],
+            mpi_var_hg_evals: vec![F::ChallengeField::default(); n_parties],
 
             eq_evals_at_rx: vec![F::ChallengeField::default(); max_input_size],
             eq_evals_at_rz0: vec![F::ChallengeField::default(); max_output_size],
             eq_evals_at_r_simd0: vec![F::ChallengeField::default(); F::get_field_pack_size()],
-            eq_evals_at_r_mpi0: vec![F::ChallengeField::default(); mpi_world_size],
+            eq_evals_at_r_mpi0: vec![F::ChallengeField::default(); n_parties],
             eq_evals_first_half: vec![
                 F::ChallengeField::default();
-                max(
-                    max(max_io_size, F::get_field_pack_size()),
-                    mpi_world_size
-                )
+                max(max(max_io_size, F::get_field_pack_size()), n_parties)
             ],
             eq_evals_second_half: vec![
                 F::ChallengeField::default();
-                max(
-                    max(max_io_size, F::get_field_pack_size()),
-                    mpi_world_size
-                )
+                max(max(max_io_size, F::get_field_pack_size()), n_parties)
             ],
 
             gate_exists_5: vec![false; max_input_size],
             gate_exists_1: vec![false; max_input_size],
+            custom_hg_evals: vec![],
+            custom_gate_exists: vec![],
             phase2_coef: F::ChallengeField::ZERO,
         }
     }
@@ -170,21 +173,23 @@ impl<F: FieldEngine> VerifierScratchPad<F> {
             deg6_lag_denoms_inv[i] = denominator.inv().unwrap();
         }
 
+        // the processes padded with virtual parties, see `MPIEngine::n_parties`
+        let n_parties = mpi_world_size.next_power_of_two();
         Self {
             eq_evals_at_rz0: vec![F::ChallengeField::zero(); max_io_size],
             eq_evals_at_r_simd: vec![F::ChallengeField::zero(); simd_size],
-            eq_evals_at_r_mpi: vec![F::ChallengeField::zero(); mpi_world_size],
+            eq_evals_at_r_mpi: vec![F::ChallengeField::zero(); n_parties],
 
             eq_evals_at_rx: vec![F::ChallengeField::zero(); max_io_size],
             eq_evals_at_ry: vec![F::ChallengeField::zero(); max_io_size],
 
             eq_evals_first_part: vec![
                 F::ChallengeField::zero();
-                max(max(max_io_size, simd_size), mpi_world_size)
+                max(max(max_io_size, simd_size), n_parties)
             ],
             eq_evals_second_part: vec![
                 F::ChallengeField::zero();
-                max(max(max_io_size, simd_size), mpi_world_size)
+                max(max(max_io_size, simd_size), n_parties)
             ],
 
             r_simd: vec![],
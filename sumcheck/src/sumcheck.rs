@@ -1,7 +1,7 @@
 use circuit::CircuitLayer;
 use gkr_engine::{
     ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine,
-    Transcript,
+    MPIResult, Transcript,
 };
 
 use crate::{
@@ -32,7 +32,7 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     sp: &mut ProverScratchPad<F>,
     mpi_config: &impl MPIEngine,
     is_output_layer: bool,
-) -> (F::ChallengeField, Option<F::ChallengeField>) {
+) -> MPIResult<(F::ChallengeField, Option<F::ChallengeField>)> {
     let mut helper =
         SumcheckGkrVanillaHelper::new(layer, challenge, alpha, sp, mpi_config, is_output_layer);
 
@@ -42,8 +42,8 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     // gkr phase 1 over variable x
     helper.prepare_x_vals();
     for i_var in 0..helper.input_var_num {
-        let evals = helper.poly_evals_at_rx(i_var, SUMCHECK_GKR_DEGREE, mpi_config);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let evals = helper.poly_evals_at_rx(i_var, SUMCHECK_GKR_DEGREE, mpi_config)?;
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
         helper.receive_rx(i_var, r);
         log::trace!("x i_var={i_var} evals: {evals:?} r: {r:?}");
     }
@@ -51,16 +51,16 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     helper.prepare_simd_var_vals();
     for i_var in 0..helper.simd_var_num {
         let evals =
-            helper.poly_evals_at_r_simd_var(i_var, SUMCHECK_GKR_SIMD_MPI_DEGREE, mpi_config);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+            helper.poly_evals_at_r_simd_var(i_var, SUMCHECK_GKR_SIMD_MPI_DEGREE, mpi_config)?;
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
         helper.receive_r_simd_var(i_var, r);
         log::trace!("SIMD i_var={i_var} evals: {evals:?} r: {r:?}");
     }
 
-    helper.prepare_mpi_var_vals(mpi_config);
-    for i_var in 0..mpi_config.world_size().trailing_zeros() as usize {
+    helper.prepare_mpi_var_vals(mpi_config)?;
+    for i_var in 0..mpi_config.n_mpi_vars()? {
         let evals = helper.poly_evals_at_r_mpi_var(i_var, SUMCHECK_GKR_SIMD_MPI_DEGREE);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
         helper.receive_r_mpi_var(i_var, r);
     }
 
@@ -70,13 +70,13 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     // gkr phase 2 over variable y
     let mut vy_claim = None;
     if !layer.structure_info.skip_sumcheck_phase_two {
-        helper.prepare_y_vals(mpi_config);
+        helper.prepare_y_vals(mpi_config)?;
         for i_var in 0..helper.input_var_num {
-            let evals = helper.poly_evals_at_ry(i_var, SUMCHECK_GKR_DEGREE, mpi_config);
-            let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+            let evals = helper.poly_evals_at_ry(i_var, SUMCHECK_GKR_DEGREE, mpi_config)?;
+            let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
             helper.receive_ry(i_var, r);
         }
-        vy_claim = Some(helper.vy_claim(mpi_config));
+        vy_claim = Some(helper.vy_claim(mpi_config)?);
         transcript.append_field_element(&vy_claim.unwrap());
     }
 
@@ -90,7 +90,7 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     let r_mpi = helper.r_mpi_var;
 
     *challenge = ExpanderDualVarChallenge::new(rx, ry, r_simd, r_mpi);
-    (vx_claim, vy_claim)
+    Ok((vx_claim, vy_claim))
 }
 
 // FIXME
@@ -102,7 +102,7 @@ pub fn sumcheck_prove_gkr_square_layer<F: FieldEngine, T: Transcript>(
     transcript: &mut T,
     sp: &mut ProverScratchPad<F>,
     mpi_config: &MPIConfig,
-) {
+) -> MPIResult<()> {
     const D: usize = SUMCHECK_GKR_SQUARE_DEGREE + 1;
     let mut helper = SumcheckGkrSquareHelper::<F, D>::new(layer, challenge, sp, mpi_config);
 
@@ -112,8 +112,8 @@ pub fn sumcheck_prove_gkr_square_layer<F: FieldEngine, T: Transcript>(
 
     // x-variable sumcheck rounds
     for i_var in 0..layer.input_var_num {
-        let evals = helper.poly_evals_at_x(i_var);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let evals = helper.poly_evals_at_x(i_var)?;
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
         log::trace!("x i_var={i_var} evals: {evals:?} r: {r:?}");
         helper.receive_x_challenge(i_var, r);
     }
@@ -123,16 +123,16 @@ pub fn sumcheck_prove_gkr_square_layer<F: FieldEngine, T: Transcript>(
 
     // SIMD-variable sumcheck rounds
     for i_var in 0..helper.simd_var_num {
-        let evals = helper.poly_evals_at_simd(i_var);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let evals = helper.poly_evals_at_simd(i_var)?;
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
         log::trace!("SIMD i_var={i_var} evals: {evals:?} r: {r:?}");
         helper.receive_simd_challenge(i_var, r);
     }
 
-    helper.prepare_mpi_var_vals();
-    for i_var in 0..mpi_config.world_size().trailing_zeros() as usize {
+    helper.prepare_mpi_var_vals()?;
+    for i_var in 0..mpi_config.n_mpi_vars()? {
         let evals = helper.poly_evals_at_mpi(i_var);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript)?;
         helper.receive_mpi_challenge(i_var, r);
     }
 
@@ -140,4 +140,5 @@ pub fn sumcheck_prove_gkr_square_layer<F: FieldEngine, T: Transcript>(
     transcript.append_field_element(&helper.vx_claim());
 
     *challenge = ExpanderSingleVarChallenge::new(helper.rx, helper.r_simd_var, helper.r_mpi_var);
+    Ok(())
 }
@@ -5,12 +5,15 @@
 
 use arith::Field;
 use gkr_engine::Transcript;
-use polynomials::SumOfProductsPoly;
+use polynomials::{HypercubeLayout, SumOfProductsPoly};
 use serdes::ExpSerde;
 
+mod instance;
 mod prover;
 mod verifier;
 
+pub use instance::SumcheckInstance;
+
 #[cfg(test)]
 mod tests;
 
@@ -26,9 +29,13 @@ pub struct IOPProof<F: Field> {
 impl<F: Field> IOPProof<F> {
     /// The endianness of SumCheck is reversed c.f. the rest of expander.
     pub fn export_point_to_expander(&self) -> Vec<F> {
-        let mut point = self.point.clone();
-        point.reverse();
-        point
+        self.export_point(HypercubeLayout::LsbFirst)
+    }
+
+    /// Export the sumcheck point in the requested layout.
+    /// SumCheck binds the variables MSB-first.
+    pub fn export_point(&self, layout: HypercubeLayout) -> Vec<F> {
+        HypercubeLayout::MsbFirst.convert_point(&self.point, layout)
     }
 }
 
@@ -39,6 +46,13 @@ pub struct IOPProverMessage<F: Field> {
     pub(crate) evaluations: Vec<F>,
 }
 
+impl<F: Field> IOPProverMessage<F> {
+    /// The evaluations of the round polynomial at 0, 1, ..., its degree
+    pub fn evaluations(&self) -> &[F] {
+        &self.evaluations
+    }
+}
+
 /// Prover State of a PolyIOP.
 pub struct IOPProverState<F: Field> {
     /// sampled randomness given by the verifier
@@ -47,8 +61,7 @@ pub struct IOPProverState<F: Field> {
     pub(crate) init_num_vars: usize,
     /// the current round number
     pub(crate) round: usize,
-    /// list of MLE poly
-    // todo: change this to reference
+    /// list of MLE poly, sharing the input polynomials until their first variable is fixed
     pub mle_list: SumOfProductsPoly<F>,
 
     pub init_sum_of_vals: Vec<F>,
@@ -80,6 +93,13 @@ pub struct SumCheckSubClaim<F: Field> {
     pub expected_evaluation: F,
 }
 
+impl<F: Field> SumCheckSubClaim<F> {
+    /// Export the evaluation point in the requested layout, see `IOPProof::export_point`.
+    pub fn export_point(&self, layout: HypercubeLayout) -> Vec<F> {
+        HypercubeLayout::MsbFirst.convert_point(&self.point, layout)
+    }
+}
+
 pub struct SumCheck<F: Field> {
     phantom: std::marker::PhantomData<F>,
 }
@@ -0,0 +1,78 @@
+use arith::Field;
+use polynomials::SumOfProductsPoly;
+
+use super::{IOPProverMessage, IOPProverState};
+
+/// The round by round interface to the prover of the sum of products, for protocols built atop
+/// the sumcheck, e.g., zero-check or permutation-check, that drive the rounds themselves.
+///
+/// The invariants, for the sum `sum_x sum_i w_i f_i(x) g_i(x)` over {0,1}^`num_vars`:
+/// - the variables are bound from the top one down, i.e., in the order of `IOPProof::point`;
+/// - with k variables bound to r_1, ..., r_k, the round polynomial is h(X) = sum_x sum_i w_i
+///   f_i(r_1, ..., r_k, X, x) g_i(r_1, ..., r_k, X, x), of degree 2, so that h(0) + h(1) is the
+///   claimed sum in the first round, and the previous h(r_k) otherwise;
+/// - a pair of fewer variables is located at the top variables being zero, as in
+///   `SumOfProductsPoly::evaluate`;
+/// - once all the variables are bound, `final_evals` gives f_i(r) and g_i(r), so that the last
+///   h(r_n) is `sum_i w_i f_i(r) g_i(r)`.
+///
+/// The transcript is left to the caller, the instance neither appends the round polynomials nor
+/// draws the challenges.
+pub struct SumcheckInstance<F: Field> {
+    state: IOPProverState<F>,
+}
+
+impl<F: Field> SumcheckInstance<F> {
+    /// The instance for the sum of `poly` over {0,1}^`num_vars`, no variable bound.
+    pub fn new(poly: &SumOfProductsPoly<F>) -> Self {
+        Self {
+            state: IOPProverState::prover_init(poly),
+        }
+    }
+
+    /// The number of variables of the sum, i.e., the number of rounds
+    pub fn num_vars(&self) -> usize {
+        self.state.init_num_vars
+    }
+
+    /// The challenges bound so far, the top variable first
+    pub fn challenges(&self) -> &[F] {
+        &self.state.challenges
+    }
+
+    /// Whether all the variables are bound
+    pub fn is_finished(&self) -> bool {
+        self.state.challenges.len() == self.num_vars()
+    }
+
+    /// The evaluations at 0, 1 and 2 of the polynomial of the current round, in the top unbound
+    /// variable. Panics if all the variables are bound.
+    pub fn round_polynomial(&self) -> IOPProverMessage<F> {
+        assert!(!self.is_finished(), "all the variables are bound");
+        self.state
+            .round_evaluations(self.state.challenges.len() + 1)
+    }
+
+    /// Bind the top unbound variable to `r`, proceeding to the next round. Panics if all the
+    /// variables are bound.
+    pub fn bind_challenge(&mut self, r: F) {
+        assert!(!self.is_finished(), "all the variables are bound");
+        self.state.challenges.push(r);
+        let round = self.state.challenges.len();
+        self.state.fix_top_variable_for_poly_pairs(round, &r);
+        self.state.round = round;
+    }
+
+    /// The evaluations (f_i(r), g_i(r)) of each pair at the point r of the challenges, the weights
+    /// not applied. Panics unless all the variables are bound.
+    pub fn final_evals(&self) -> Vec<(F, F)> {
+        assert!(self.is_finished(), "not all the variables are bound");
+        self.state
+            .mle_list
+            .f_and_g_pairs
+            .iter()
+            .zip(self.state.eq_prefix.iter())
+            .map(|((f, g), eq_prefix)| (f.coeffs[0] * *eq_prefix, g.coeffs[0] * *eq_prefix))
+            .collect()
+    }
+}
@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use arith::Field;
-use polynomials::{MultilinearExtension, SumOfProductsPoly};
+use polynomials::{MultiLinearPoly, MultilinearExtension, SumOfProductsPoly};
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
 };
@@ -11,6 +13,7 @@ impl<F: Field> IOPProverState<F> {
     /// over {0,1}^`num_vars`.
     pub fn prover_init(polynomials: &SumOfProductsPoly<F>) -> Self {
         let num_vars = polynomials.num_vars();
+        assert_eq!(polynomials.f_and_g_pairs.len(), polynomials.weights.len());
         Self {
             challenges: Vec::with_capacity(num_vars),
             round: 0,
@@ -60,13 +63,19 @@ impl<F: Field> IOPProverState<F> {
 
             let r = self.challenges[self.round - 1];
 
-            self.fix_top_variable_for_poly_pairs(&r);
+            self.fix_top_variable_for_poly_pairs(self.round, &r);
         } else if self.round > 0 {
             panic!("verifier message is empty")
         }
 
         self.round += 1;
 
+        self.round_evaluations(self.round)
+    }
+
+    /// The evaluations at 0, 1 and 2 of the univariate polynomial of the given round, indexed
+    /// from 1, the previous rounds being bound already.
+    pub(crate) fn round_evaluations(&self, round: usize) -> IOPProverMessage<F> {
         let mut h_0 = F::zero();
         let mut h_1 = F::zero();
         let mut h_2 = F::zero();
@@ -103,9 +112,7 @@ impl<F: Field> IOPProverState<F> {
                 // evaluate the polynomial at 0, 1 and 2
                 // and obtain f(0)g(0) and f(1)g(1) and f(2)g(2)
 
-                if let Some(sub_idx) =
-                    Self::get_sub_idx(self.init_num_vars, self.round, f.num_vars())
-                {
+                if let Some(sub_idx) = Self::get_sub_idx(self.init_num_vars, round, f.num_vars()) {
                     let len = 1 << (f.num_vars() - sub_idx - 1);
                     let f_coeffs = f.coeffs.as_slice();
                     let g_coeffs = g.coeffs.as_slice();
@@ -135,14 +142,16 @@ impl<F: Field> IOPProverState<F> {
                         .map(|(a, b)| a * b)
                         .sum::<F>();
 
-                    let eq_prefix_i = self.eq_prefix[i].square();
+                    let eq_prefix_i = self.eq_prefix[i].square() * self.mle_list.weights[i];
                     (
                         h_0_local * eq_prefix_i,
                         h_1_local * eq_prefix_i,
                         h_2_local * eq_prefix_i,
                     )
                 } else {
-                    let h = self.eq_prefix[i].square() * self.init_sum_of_vals[i];
+                    let h = self.eq_prefix[i].square()
+                        * self.mle_list.weights[i]
+                        * self.init_sum_of_vals[i];
                     (h, F::zero(), h)
                 }
             })
@@ -167,21 +176,38 @@ impl<F: Field> IOPProverState<F> {
         }
     }
 
-    fn fix_top_variable_for_poly_pairs(&mut self, challenge: &F) {
+    /// Bind the top variable of the given round, indexed from 1, to the challenge.
+    pub(crate) fn fix_top_variable_for_poly_pairs(&mut self, round: usize, challenge: &F) {
+        let init_num_vars = self.init_num_vars;
         self.mle_list
             .f_and_g_pairs
             .par_iter_mut()
             .zip(self.eq_prefix.par_iter_mut())
             .for_each(|((f, g), eq_prefix)| {
-                if let Some(_sub_idx) =
-                    Self::get_sub_idx(self.init_num_vars, self.round, f.num_vars())
-                {
+                if let Some(_sub_idx) = Self::get_sub_idx(init_num_vars, round, f.num_vars()) {
                     // fix the top variable for each polynomial pair
-                    f.fix_top_variable(*challenge);
-                    g.fix_top_variable(*challenge);
+                    Self::fix_top_variable_of_shared(f, challenge);
+                    Self::fix_top_variable_of_shared(g, challenge);
                 } else {
                     *eq_prefix *= F::one() - *challenge; // eq(challenge, 0)
                 }
             });
     }
+
+    /// Fix the top variable of `poly` in place if it is not shared, or into a new half sized
+    /// polynomial otherwise, so that a shared polynomial is never copied in full.
+    fn fix_top_variable_of_shared(poly: &mut Arc<MultiLinearPoly<F>>, challenge: &F) {
+        match Arc::get_mut(poly) {
+            Some(owned) => owned.fix_top_variable(*challenge),
+            None => {
+                let (left, right) = poly.coeffs.split_at(poly.coeffs.len() / 2);
+                let coeffs = left
+                    .iter()
+                    .zip(right.iter())
+                    .map(|(&a, &b)| a + *challenge * (b - a))
+                    .collect();
+                *poly = Arc::new(MultiLinearPoly { coeffs });
+            }
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use super::*;
 
+use std::sync::Arc;
+
 use arith::Fr;
 use ark_std::{rand::thread_rng, test_rng};
 use gkr_hashers::{Keccak256hasher, SHA256hasher};
@@ -28,7 +30,7 @@ fn test_sumcheck_subroutine() {
                 })
                 .collect::<Vec<_>>();
 
-            let mle_list = SumOfProductsPoly::<Fr> { f_and_g_pairs };
+            let mle_list = SumOfProductsPoly::<Fr>::from_pairs(f_and_g_pairs);
 
             let asserted_sum = mle_list.sum();
 
@@ -83,7 +85,7 @@ fn test_sumcheck_e2e() {
                 })
                 .collect::<Vec<_>>();
 
-            let mle_list = SumOfProductsPoly::<Fr> { f_and_g_pairs };
+            let mle_list = SumOfProductsPoly::<Fr>::from_pairs(f_and_g_pairs);
 
             let asserted_sum = mle_list.sum();
 
@@ -107,8 +109,8 @@ fn test_sumcheck_generic_padding_helper<F: Field, T: Transcript>() {
     let max_num_vars = 10;
     let mut rng = thread_rng();
 
-    let mle_list = SumOfProductsPoly {
-        f_and_g_pairs: (0..num_polys)
+    let mle_list = SumOfProductsPoly::from_pairs(
+        (0..num_polys)
             .map(|i| {
                 let num_vars = i % (max_num_vars + 1);
                 let poly0 = MultiLinearPoly::<F>::random(num_vars, &mut rng);
@@ -116,24 +118,24 @@ fn test_sumcheck_generic_padding_helper<F: Field, T: Transcript>() {
                 (poly0, poly1)
             })
             .collect(),
-    };
+    );
     let claimed_sum = mle_list.sum();
 
     let proof = SumCheck::prove(&mle_list, &mut T::new());
 
-    let padded_mle_list = SumOfProductsPoly {
-        f_and_g_pairs: mle_list
+    let padded_mle_list = SumOfProductsPoly::from_pairs(
+        mle_list
             .f_and_g_pairs
             .iter()
             .map(|(f, g)| {
-                let mut f_padded = f.clone();
+                let mut f_padded = (**f).clone();
                 f_padded.lift_to_n_vars(max_num_vars);
-                let mut g_padded = g.clone();
+                let mut g_padded = (**g).clone();
                 g_padded.lift_to_n_vars(max_num_vars);
                 (f_padded, g_padded)
             })
             .collect(),
-    };
+    );
 
     let proof_with_padded_mle_list = SumCheck::prove(&padded_mle_list, &mut T::new());
 
@@ -154,3 +156,132 @@ fn test_sumcheck_generic_padding_helper<F: Field, T: Transcript>() {
 fn test_sumcheck_generic_padding() {
     test_sumcheck_generic_padding_helper::<Fr, BytesHashTranscript<SHA256hasher>>();
 }
+
+#[test]
+fn test_sumcheck_weighted_pairs() {
+    let mut rng = test_rng();
+    let num_vars = 6;
+
+    // a random linear combination of products, one of them subtracted, and a smaller pair
+    let mut mle_list = SumOfProductsPoly::<Fr>::new();
+    for (n_vars, weight) in [
+        (num_vars, Fr::random_unsafe(&mut rng)),
+        (num_vars, Fr::random_unsafe(&mut rng)),
+        (num_vars - 2, Fr::random_unsafe(&mut rng)),
+    ] {
+        let f = MultiLinearPoly::<Fr>::random(n_vars, &mut rng);
+        let g = MultiLinearPoly::<Fr>::random(n_vars, &mut rng);
+        mle_list.add_weighted_pair(f, g, weight);
+    }
+    let f = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+    let g = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+    mle_list.sub_pair(f.clone(), g.clone());
+
+    let mut unweighted = mle_list.clone();
+    unweighted.weights.pop();
+    unweighted.f_and_g_pairs.pop();
+    let expected_sum = unweighted.sum()
+        - f.coeffs
+            .iter()
+            .zip(g.coeffs.iter())
+            .map(|(&f, &g)| f * g)
+            .sum::<Fr>();
+    let asserted_sum = mle_list.sum();
+    assert_eq!(asserted_sum, expected_sum);
+
+    let proof = SumCheck::<Fr>::prove(&mle_list, &mut BytesHashTranscript::<SHA256hasher>::new());
+    let (verified, subclaim) = SumCheck::<Fr>::verify(
+        asserted_sum,
+        &proof,
+        num_vars,
+        &mut BytesHashTranscript::<SHA256hasher>::new(),
+    );
+    assert!(verified, "sumcheck verification failed");
+    assert_eq!(
+        mle_list.evaluate(&subclaim.point),
+        subclaim.expected_evaluation
+    );
+}
+
+#[test]
+fn test_sumcheck_shared_polys() {
+    let mut rng = test_rng();
+    let num_vars = 5;
+
+    // the same polynomial in several pairs, shared rather than copied
+    let f = Arc::new(MultiLinearPoly::<Fr>::random(num_vars, &mut rng));
+    let mut mle_list = SumOfProductsPoly::<Fr>::new();
+    for _ in 0..3 {
+        let g = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+        mle_list.add_pair(Arc::clone(&f), g);
+    }
+    let f_coeffs = f.coeffs.clone();
+    let asserted_sum = mle_list.sum();
+
+    let proof = SumCheck::<Fr>::prove(&mle_list, &mut BytesHashTranscript::<SHA256hasher>::new());
+    assert_eq!(f.coeffs, f_coeffs);
+    assert!(mle_list
+        .f_and_g_pairs
+        .iter()
+        .all(|(shared, _)| Arc::ptr_eq(shared, &f)));
+
+    let (verified, subclaim) = SumCheck::<Fr>::verify(
+        asserted_sum,
+        &proof,
+        num_vars,
+        &mut BytesHashTranscript::<SHA256hasher>::new(),
+    );
+    assert!(verified, "sumcheck verification failed");
+    assert_eq!(
+        mle_list.evaluate(&subclaim.point),
+        subclaim.expected_evaluation
+    );
+}
+
+#[test]
+fn test_sumcheck_instance_rounds() {
+    let mut rng = test_rng();
+    let num_vars = 6;
+
+    let mut mle_list = SumOfProductsPoly::<Fr>::new();
+    for n_vars in [num_vars, num_vars, num_vars - 3, 0] {
+        let f = MultiLinearPoly::<Fr>::random(n_vars, &mut rng);
+        let g = MultiLinearPoly::<Fr>::random(n_vars, &mut rng);
+        mle_list.add_weighted_pair(f, g, Fr::random_unsafe(&mut rng));
+    }
+
+    // the rounds driven by hand give the same proof as the sumcheck prover
+    let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+    let mut instance = SumcheckInstance::new(&mle_list);
+    let mut prover_msgs = vec![];
+    let mut expected = mle_list.sum();
+    while !instance.is_finished() {
+        let prover_msg = instance.round_polynomial();
+        let evals = prover_msg.evaluations();
+        assert_eq!(evals[0] + evals[1], expected);
+
+        transcript.append_serializable_data(&prover_msg);
+        let r = transcript.generate_field_element::<Fr>();
+        instance.bind_challenge(r);
+
+        // h(r) by the Lagrange interpolation over 0, 1 and 2
+        let two_inv = Fr::from(2u32).inv().unwrap();
+        expected = evals[0] * (r - Fr::one()) * (r - Fr::from(2u32)) * two_inv
+            - evals[1] * r * (r - Fr::from(2u32))
+            + evals[2] * r * (r - Fr::one()) * two_inv;
+        prover_msgs.push(prover_msg);
+    }
+
+    let proof = SumCheck::<Fr>::prove(&mle_list, &mut BytesHashTranscript::<SHA256hasher>::new());
+    assert_eq!(proof.proofs, prover_msgs);
+    assert_eq!(proof.point, instance.challenges());
+
+    let final_evals = instance.final_evals();
+    let final_sum = final_evals
+        .iter()
+        .zip(mle_list.weights.iter())
+        .map(|((f, g), w)| *f * *g * *w)
+        .sum::<Fr>();
+    assert_eq!(final_sum, expected);
+    assert_eq!(mle_list.evaluate(instance.challenges()), expected);
+}
@@ -1,5 +1,5 @@
 use arith::{ExtensionField, SimdField};
-use gkr_engine::{MPIEngine, Transcript};
+use gkr_engine::{MPIEngine, MPIResult, Transcript};
 
 #[inline(always)]
 /// Input
@@ -24,7 +24,11 @@ pub fn unpack_and_combine<F: SimdField>(p: &F, coef: &[F::Scalar]) -> F::Scalar
 
 /// Transcript IO between sumcheck steps
 #[inline]
-pub fn transcript_io<F, T>(mpi_config: &impl MPIEngine, ps: &[F], transcript: &mut T) -> F
+pub fn transcript_io<F, T>(
+    mpi_config: &impl MPIEngine,
+    ps: &[F],
+    transcript: &mut T,
+) -> MPIResult<F>
 where
     F: ExtensionField,
     T: Transcript,
@@ -38,6 +42,6 @@ where
         transcript.append_field_element(p);
     }
     let mut r = transcript.generate_field_element::<F>();
-    mpi_config.root_broadcast_f(&mut r);
-    r
+    mpi_config.root_broadcast_f(&mut r)?;
+    Ok(r)
 }
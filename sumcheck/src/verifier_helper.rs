@@ -1,9 +1,11 @@
 use arith::{ExtensionField, Field};
-use circuit::{CircuitLayer, CoefType, GateAdd, GateConst, GateMul, GateUni};
+use circuit::{
+    CircuitLayer, CoefType, GateAdd, GateConst, GateMul, GateUni, POW1_GATE_TYPE, POW5_GATE_TYPE,
+};
 use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, FieldType};
 use polynomials::EqPolynomial;
 
-use crate::{scratch_pad::VerifierScratchPad, unpack_and_combine};
+use crate::{combine_public_input, scratch_pad::VerifierScratchPad};
 
 #[derive(Default)]
 pub struct GKRVerifierHelper<F: FieldEngine> {
@@ -124,29 +126,15 @@ impl<F: FieldEngine> GKRVerifierHelper<F> {
     ) -> F::ChallengeField {
         let mut v = F::ChallengeField::zero();
 
-        let mpi_world_size = sp.eq_evals_at_r_mpi.len();
-        let local_input_size = public_input.len() / mpi_world_size;
-
         for cst_gate in cst_gates {
             let tmp = match cst_gate.coef_type {
                 CoefType::PublicInput(input_idx) => {
-                    let mut input = vec![];
-                    for i in 0..mpi_world_size {
-                        input.push(public_input[i * local_input_size + input_idx]);
-                    }
-
-                    // mpi combined
-                    let input_mpi_combined: F::Field = input
-                        .iter()
-                        .zip(&sp.eq_evals_at_r_mpi)
-                        .map(|(v, c)| *c * *v)
-                        .sum();
-
-                    // simd combined
                     sp.eq_evals_at_rz0[cst_gate.o_id]
-                        * unpack_and_combine::<F::Field>(
-                            &input_mpi_combined,
+                        * combine_public_input::<F>(
+                            public_input,
+                            input_idx,
                             &sp.eq_evals_at_r_simd,
+                            &sp.eq_evals_at_r_mpi,
                         )
                 }
                 _ => sp.eq_evals_at_rz0[cst_gate.o_id] * cst_gate.coef,
@@ -185,8 +173,7 @@ impl<F: FieldEngine> GKRVerifierHelper<F> {
     pub fn eval_pow_1(gates: &[GateUni<F>], sp: &VerifierScratchPad<F>) -> F::ChallengeField {
         let mut v = F::ChallengeField::zero();
         for gate in gates {
-            // Gates of type 12346 represent an add gate
-            if gate.gate_type == 12346 {
+            if gate.gate_type == POW1_GATE_TYPE {
                 v += sp.eq_evals_at_rz0[gate.o_id] * sp.eq_evals_at_rx[gate.i_ids[0]] * gate.coef;
             }
         }
@@ -197,14 +184,42 @@ impl<F: FieldEngine> GKRVerifierHelper<F> {
     pub fn eval_pow_5(gates: &[GateUni<F>], sp: &VerifierScratchPad<F>) -> F::ChallengeField {
         let mut v = F::ChallengeField::zero();
         for gate in gates {
-            // Gates of type 12345 represent a pow5 gate
-            if gate.gate_type == 12345 {
+            if gate.gate_type == POW5_GATE_TYPE {
                 v += sp.eq_evals_at_rz0[gate.o_id] * sp.eq_evals_at_rx[gate.i_ids[0]] * gate.coef;
             }
         }
         v * sp.eq_r_simd_r_simd_xy * sp.eq_r_mpi_r_mpi_xy
     }
 
+    /// GKR2 equivalent of `eval_pow_5` for the custom gates of `layer`, each gate evaluated at
+    /// the claim `v_claim` on its input, see `circuit::CustomGate`.
+    #[inline(always)]
+    pub fn eval_custom_gates(
+        layer: &CircuitLayer<F>,
+        v_claim: &F::ChallengeField,
+        sp: &VerifierScratchPad<F>,
+    ) -> F::ChallengeField {
+        let custom_gates = layer.custom_gates();
+        let gate_vals = custom_gates
+            .iter()
+            .map(|def| (def.eval_challenge)(*v_claim))
+            .collect::<Vec<_>>();
+
+        let mut v = F::ChallengeField::zero();
+        for gate in &layer.uni {
+            if let Some(k) = custom_gates
+                .iter()
+                .position(|def| def.gate_type == gate.gate_type)
+            {
+                v += sp.eq_evals_at_rz0[gate.o_id]
+                    * sp.eq_evals_at_rx[gate.i_ids[0]]
+                    * gate.coef
+                    * gate_vals[k];
+            }
+        }
+        v * sp.eq_r_simd_r_simd_xy * sp.eq_r_mpi_r_mpi_xy
+    }
+
     #[inline(always)]
     pub fn set_rx(rx: &[F::ChallengeField], sp: &mut VerifierScratchPad<F>) {
         EqPolynomial::<F::ChallengeField>::eq_eval_at(
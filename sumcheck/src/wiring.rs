@@ -0,0 +1,101 @@
+//! Reference evaluations of the wiring predicates of a layer at arbitrary points.
+//!
+//! For a layer whose multiplication gates compute `out[o] += coef * in[i0] * in[i1]`, the
+//! multilinear extension of the wiring predicate is
+//!
+//! ```text
+//! mult~(rz, rx, ry) = sum_{gates} coef * eq(rz, o) * eq(rx, i0) * eq(ry, i1)
+//! ```
+//!
+//! and likewise `add~(rz, rx) = sum_{gates} coef * eq(rz, o) * eq(rx, i0)` for the addition
+//! gates. Gate indices are read LSB-first, i.e., `rz[0]` binds the least significant bit of
+//! `o`. Since all the SIMD lanes and MPI processes run the same circuit, the predicates over the
+//! full instance are multiplied by `eq(r_simd, r_simd_xy) * eq(r_mpi, r_mpi_xy)`.
+//!
+//! These are the values `GKRVerifierHelper::eval_add` and `GKRVerifierHelper::eval_mul` compute
+//! from the verifier scratch pad, computed here from the points alone, so that verifiers
+//! implemented elsewhere can be tested against them. The gate coefficients are taken from the
+//! layer as is, i.e., random coefficients must have been filled in beforehand.
+use arith::Field;
+use circuit::CircuitLayer;
+use gkr_engine::FieldEngine;
+use polynomials::EqPolynomial;
+
+/// A point at which the wiring predicates of a layer are evaluated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WiringPoint<F: FieldEngine> {
+    /// output gate variables, `layer.output_var_num` of them
+    pub rz: Vec<F::ChallengeField>,
+    /// first input gate variables, `layer.input_var_num` of them
+    pub rx: Vec<F::ChallengeField>,
+    /// second input gate variables, `layer.input_var_num` of them, unused by `add~`
+    pub ry: Vec<F::ChallengeField>,
+    /// SIMD variables of the output, empty to evaluate over a single SIMD lane
+    pub r_simd: Vec<F::ChallengeField>,
+    /// SIMD variables of the inputs
+    pub r_simd_xy: Vec<F::ChallengeField>,
+    /// MPI variables of the output, empty to evaluate over a single process
+    pub r_mpi: Vec<F::ChallengeField>,
+    /// MPI variables of the inputs
+    pub r_mpi_xy: Vec<F::ChallengeField>,
+}
+
+impl<F: FieldEngine> WiringPoint<F> {
+    /// eq(r_simd, r_simd_xy) * eq(r_mpi, r_mpi_xy)
+    #[inline]
+    fn eq_simd_mpi(&self) -> F::ChallengeField {
+        assert_eq!(self.r_simd.len(), self.r_simd_xy.len());
+        assert_eq!(self.r_mpi.len(), self.r_mpi_xy.len());
+
+        EqPolynomial::<F::ChallengeField>::eq_vec(&self.r_simd, &self.r_simd_xy)
+            * EqPolynomial::<F::ChallengeField>::eq_vec(&self.r_mpi, &self.r_mpi_xy)
+    }
+}
+
+/// Evaluate `add~` of `layer` at `point`.
+pub fn eval_add_wiring<F: FieldEngine>(
+    layer: &CircuitLayer<F>,
+    point: &WiringPoint<F>,
+) -> F::ChallengeField {
+    assert_eq!(point.rz.len(), layer.output_var_num);
+    assert_eq!(point.rx.len(), layer.input_var_num);
+
+    let eq_z = eq_evals(&point.rz);
+    let eq_x = eq_evals(&point.rx);
+
+    let v = layer
+        .add
+        .iter()
+        .map(|gate| eq_z[gate.o_id] * eq_x[gate.i_ids[0]] * gate.coef)
+        .fold(F::ChallengeField::ZERO, |acc, t| acc + t);
+    v * point.eq_simd_mpi()
+}
+
+/// Evaluate `mult~` of `layer` at `point`.
+pub fn eval_mul_wiring<F: FieldEngine>(
+    layer: &CircuitLayer<F>,
+    point: &WiringPoint<F>,
+) -> F::ChallengeField {
+    assert_eq!(point.rz.len(), layer.output_var_num);
+    assert_eq!(point.rx.len(), layer.input_var_num);
+    assert_eq!(point.ry.len(), layer.input_var_num);
+
+    let eq_z = eq_evals(&point.rz);
+    let eq_x = eq_evals(&point.rx);
+    let eq_y = eq_evals(&point.ry);
+
+    let v = layer
+        .mul
+        .iter()
+        .map(|gate| eq_z[gate.o_id] * eq_x[gate.i_ids[0]] * eq_y[gate.i_ids[1]] * gate.coef)
+        .fold(F::ChallengeField::ZERO, |acc, t| acc + t);
+    v * point.eq_simd_mpi()
+}
+
+/// eq(r, i) for all i in the hypercube, LSB-first
+#[inline]
+fn eq_evals<F: Field>(r: &[F]) -> Vec<F> {
+    let mut evals = vec![F::ZERO; 1 << r.len()];
+    EqPolynomial::<F>::build_eq_x_r_with_buf(r, &F::ONE, &mut evals);
+    evals
+}
@@ -0,0 +1,101 @@
+use arith::Field;
+use ark_std::{rand::RngCore, test_rng};
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, M31x16Config};
+use polynomials::EqPolynomial;
+use sumcheck::{combine_public_input, eval_public_input_mle};
+
+type C = M31x16Config;
+type SimdF = <C as FieldEngine>::SimdCircuitField;
+type ChallengeF = <C as FieldEngine>::ChallengeField;
+
+const MPI_VAR_NUM: usize = 2;
+
+fn random_vec(n: usize, mut rng: impl RngCore) -> Vec<ChallengeF> {
+    (0..n)
+        .map(|_| ChallengeF::random_unsafe(&mut rng))
+        .collect()
+}
+
+fn random_challenge(rz_var_num: usize, mut rng: impl RngCore) -> ExpanderSingleVarChallenge<C> {
+    let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+    ExpanderSingleVarChallenge::<C> {
+        rz: random_vec(rz_var_num, &mut rng),
+        r_simd: random_vec(simd_var_num, &mut rng),
+        r_mpi: random_vec(MPI_VAR_NUM, &mut rng),
+    }
+}
+
+#[test]
+fn test_public_input_mle_matches_dense_evaluation() {
+    let mut rng = test_rng();
+
+    // the public input of each process, zero padded to a power of two
+    for local_input_size in [1, 3, 4, 7] {
+        let public_input: Vec<_> = (0..local_input_size << MPI_VAR_NUM)
+            .map(|_| SimdF::random_unsafe(&mut rng))
+            .collect();
+        let rz_var_num = local_input_size.next_power_of_two().trailing_zeros() as usize;
+        let challenge = random_challenge(rz_var_num, &mut rng);
+
+        let padded: Vec<_> = public_input
+            .chunks(local_input_size)
+            .flat_map(|local_input| {
+                let mut local_input = local_input.to_vec();
+                local_input.resize(1 << rz_var_num, SimdF::ZERO);
+                local_input
+            })
+            .collect();
+
+        assert_eq!(
+            eval_public_input_mle(&public_input, &challenge),
+            C::single_core_eval_circuit_vals_at_expander_challenge(&padded, &challenge)
+        );
+    }
+}
+
+#[test]
+fn test_combined_public_input_on_hypercube() {
+    const LOCAL_INPUT_SIZE: usize = 5;
+    const RZ_VAR_NUM: usize = 3;
+
+    let mut rng = test_rng();
+    let public_input: Vec<_> = (0..LOCAL_INPUT_SIZE << MPI_VAR_NUM)
+        .map(|_| SimdF::random_unsafe(&mut rng))
+        .collect();
+    let bits = |index: usize| -> Vec<ChallengeF> {
+        (0..RZ_VAR_NUM)
+            .map(|i| ChallengeF::from((index >> i) as u32 & 1))
+            .collect()
+    };
+
+    // at the public input of index `input_idx`, the multilinear extension is the combination
+    // of that input over the processes and the lanes
+    let challenge = random_challenge(RZ_VAR_NUM, &mut rng);
+    let eq_evals_at_r_simd = EqPolynomial::<ChallengeF>::build_eq_x_r(&challenge.r_simd);
+    let eq_evals_at_r_mpi = EqPolynomial::<ChallengeF>::build_eq_x_r(&challenge.r_mpi);
+    for input_idx in 0..LOCAL_INPUT_SIZE {
+        let point = ExpanderSingleVarChallenge::<C> {
+            rz: bits(input_idx),
+            ..challenge.clone()
+        };
+        assert_eq!(
+            eval_public_input_mle(&public_input, &point),
+            combine_public_input::<C>(
+                &public_input,
+                input_idx,
+                &eq_evals_at_r_simd,
+                &eq_evals_at_r_mpi
+            )
+        );
+    }
+
+    // past the public input, the padding is zero
+    let point = ExpanderSingleVarChallenge::<C> {
+        rz: bits(LOCAL_INPUT_SIZE),
+        ..challenge
+    };
+    assert_eq!(
+        eval_public_input_mle(&public_input, &point),
+        ChallengeF::ZERO
+    );
+}
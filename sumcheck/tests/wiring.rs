@@ -0,0 +1,121 @@
+use arith::Field;
+use ark_std::{rand::RngCore, test_rng};
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul};
+use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, M31x16Config};
+use sumcheck::{
+    eval_add_wiring, eval_mul_wiring, GKRVerifierHelper, VerifierScratchPad, WiringPoint,
+};
+
+type C = M31x16Config;
+type F = <C as FieldEngine>::CircuitField;
+type ChallengeF = <C as FieldEngine>::ChallengeField;
+
+fn random_layer(input_var_num: usize, output_var_num: usize) -> CircuitLayer<C> {
+    let mut rng = test_rng();
+    let mut layer = CircuitLayer::<C> {
+        input_var_num,
+        output_var_num,
+        ..Default::default()
+    };
+
+    for o_id in 0..1 << output_var_num {
+        layer.mul.push(GateMul {
+            i_ids: [
+                (o_id * 3) % (1 << input_var_num),
+                (o_id * 5 + 1) % (1 << input_var_num),
+            ],
+            o_id,
+            coef: F::random_unsafe(&mut rng),
+            coef_type: CoefType::Constant,
+            gate_type: 0,
+        });
+        layer.add.push(GateAdd {
+            i_ids: [(o_id * 7 + 2) % (1 << input_var_num)],
+            o_id,
+            coef: F::random_unsafe(&mut rng),
+            coef_type: CoefType::Constant,
+            gate_type: 1,
+        });
+    }
+    layer
+}
+
+fn random_vec(n: usize, mut rng: impl RngCore) -> Vec<ChallengeF> {
+    (0..n)
+        .map(|_| ChallengeF::random_unsafe(&mut rng))
+        .collect()
+}
+
+#[test]
+fn test_wiring_matches_verifier() {
+    let layer = random_layer(4, 3);
+    let circuit = Circuit::<C> {
+        layers: vec![layer.clone()],
+        ..Default::default()
+    };
+    let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+
+    let mut rng = test_rng();
+
+    let point = WiringPoint::<C> {
+        rz: random_vec(3, &mut rng),
+        rx: random_vec(4, &mut rng),
+        ry: random_vec(4, &mut rng),
+        r_simd: random_vec(simd_var_num, &mut rng),
+        r_simd_xy: random_vec(simd_var_num, &mut rng),
+        r_mpi: random_vec(1, &mut rng),
+        r_mpi_xy: random_vec(1, &mut rng),
+    };
+
+    let mut sp = VerifierScratchPad::<C>::new(&circuit, 2);
+    let challenge = ExpanderDualVarChallenge::<C> {
+        rz_0: point.rz.clone(),
+        rz_1: None,
+        r_simd: point.r_simd.clone(),
+        r_mpi: point.r_mpi.clone(),
+    };
+    GKRVerifierHelper::<C>::prepare_layer_non_sequential(&layer, &None, &challenge, &mut sp);
+    GKRVerifierHelper::<C>::set_rx(&point.rx, &mut sp);
+    GKRVerifierHelper::<C>::set_r_simd_xy(&point.r_simd_xy, &mut sp);
+    GKRVerifierHelper::<C>::set_r_mpi_xy(&point.r_mpi_xy, &mut sp);
+    GKRVerifierHelper::<C>::set_ry(&point.ry, &mut sp);
+
+    assert_eq!(
+        eval_add_wiring(&layer, &point),
+        GKRVerifierHelper::<C>::eval_add(&layer.add, &sp)
+    );
+    assert_eq!(
+        eval_mul_wiring(&layer, &point),
+        GKRVerifierHelper::<C>::eval_mul(&layer.mul, &sp)
+    );
+}
+
+#[test]
+fn test_wiring_on_hypercube() {
+    let layer = random_layer(4, 3);
+    let bits = |index: usize, n: usize| -> Vec<ChallengeF> {
+        (0..n)
+            .map(|i| ChallengeF::from((index >> i) as u32 & 1))
+            .collect()
+    };
+
+    // each output has exactly one gate of each kind, so on the hypercube the predicates
+    // select the gate coefficients
+    for gate in &layer.mul {
+        let point = WiringPoint::<C> {
+            rz: bits(gate.o_id, 3),
+            rx: bits(gate.i_ids[0], 4),
+            ry: bits(gate.i_ids[1], 4),
+            ..Default::default()
+        };
+        assert_eq!(eval_mul_wiring(&layer, &point), ChallengeF::from(gate.coef));
+    }
+    for gate in &layer.add {
+        let point = WiringPoint::<C> {
+            rz: bits(gate.o_id, 3),
+            rx: bits(gate.i_ids[0], 4),
+            ..Default::default()
+        };
+        assert_eq!(eval_add_wiring(&layer, &point), ChallengeF::from(gate.coef));
+    }
+}
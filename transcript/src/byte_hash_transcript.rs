@@ -1,5 +1,6 @@
 use gkr_engine::{Proof, Transcript};
 use gkr_hashers::FiatShamirHasher;
+use serdes::{ExpSerde, SerdeError, SerdeResult};
 
 // When appending the initial commitment, we hash the commitment bytes
 // for sufficient number of times, so that the FS hash has a sufficient circuit depth
@@ -69,6 +70,16 @@ impl<H: FiatShamirHasher> Transcript for BytesHashTranscript<H> {
         self.proof.bytes.extend_from_slice(buffer);
     }
 
+    /// Hash the label into the digest directly, so that the label does not end up in the proof.
+    #[inline]
+    fn append_domain_separator(&mut self, label: &[u8]) {
+        self.refresh_digest();
+
+        let mut hash_inputs = self.digest.clone();
+        hash_inputs.extend_from_slice(label);
+        self.hasher.hash(&mut self.digest, &hash_inputs);
+    }
+
     #[inline]
     fn generate_u8_slice(&mut self, n_bytes: usize) -> Vec<u8> {
         let mut ret = vec![0u8; n_bytes];
@@ -139,4 +150,33 @@ impl<H: FiatShamirHasher> Transcript for BytesHashTranscript<H> {
             self.hasher.hash_inplace(&mut self.digest);
         }
     }
+
+    fn export_state(&self) -> Vec<u8> {
+        let mut state = vec![];
+        self.digest.serialize_into(&mut state).unwrap();
+        self.proof.serialize_into(&mut state).unwrap();
+        self.hash_start_index.serialize_into(&mut state).unwrap();
+        self.proof_locked.serialize_into(&mut state).unwrap();
+        self.proof_locked_at.serialize_into(&mut state).unwrap();
+        state
+    }
+
+    fn import_state(mut state: &[u8]) -> SerdeResult<Self> {
+        let transcript = Self {
+            hasher: H::new(),
+            digest: Vec::deserialize_from(&mut state)?,
+            proof: Proof::deserialize_from(&mut state)?,
+            hash_start_index: usize::deserialize_from(&mut state)?,
+            proof_locked: bool::deserialize_from(&mut state)?,
+            proof_locked_at: usize::deserialize_from(&mut state)?,
+        };
+
+        if transcript.digest.len() != H::DIGEST_SIZE
+            || transcript.hash_start_index > transcript.proof.bytes.len()
+            || transcript.proof_locked_at > transcript.proof.bytes.len()
+        {
+            return Err(SerdeError::DeserializeError);
+        }
+        Ok(transcript)
+    }
 }
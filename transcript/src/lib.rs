@@ -6,6 +6,9 @@ pub use byte_hash_transcript::BytesHashTranscript;
 mod random_tape_transcript;
 pub use random_tape_transcript::RandomTape;
 
+mod recording_transcript;
+pub use recording_transcript::{RecordingTranscript, Traced, TranscriptEvent, TranscriptTrace};
+
 mod transcript_utils;
 pub use transcript_utils::{transcript_root_broadcast, transcript_verifier_sync};
 
@@ -1,5 +1,6 @@
 use arith::{ExtensionField, Field};
 use gkr_engine::Transcript;
+use serdes::{ExpSerde, SerdeResult};
 
 /// A transcript that uses a random tape to generate challenges.
 #[derive(Default, Clone, Debug, PartialEq)]
@@ -53,6 +54,9 @@ impl<ChallengeF: ExtensionField> Transcript for RandomTape<ChallengeF> {
     // Do nothing, randomness are already stored in the tape
     fn append_u8_slice(&mut self, _buffer: &[u8]) {}
 
+    // Do nothing, randomness are already stored in the tape
+    fn append_domain_separator(&mut self, _label: &[u8]) {}
+
     fn generate_u8_slice(&mut self, _n_bytes: usize) -> Vec<u8> {
         unimplemented!()
     }
@@ -80,4 +84,18 @@ impl<ChallengeF: ExtensionField> Transcript for RandomTape<ChallengeF> {
     fn refresh_digest(&mut self) {
         unimplemented!()
     }
+
+    fn export_state(&self) -> Vec<u8> {
+        let mut state = vec![];
+        self.tape.serialize_into(&mut state).unwrap();
+        self.position.serialize_into(&mut state).unwrap();
+        state
+    }
+
+    fn import_state(mut state: &[u8]) -> SerdeResult<Self> {
+        Ok(Self {
+            tape: Vec::deserialize_from(&mut state)?,
+            position: usize::deserialize_from(&mut state)?,
+        })
+    }
 }
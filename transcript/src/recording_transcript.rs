@@ -0,0 +1,295 @@
+use std::{fmt, marker::PhantomData};
+
+use arith::Field;
+use gkr_engine::{GKREngine, GKRScheme, Proof, Transcript};
+use serdes::{ExpSerde, SerdeResult};
+
+/// An operation on a transcript, as recorded by `RecordingTranscript`.
+#[derive(Debug, Clone, PartialEq, Eq, ExpSerde)]
+pub enum TranscriptEvent {
+    /// The start of a protocol phase, see `Transcript::begin_phase`
+    Phase(String),
+    /// Bytes appended to the proof
+    Append(Vec<u8>),
+    /// A commitment appended to the proof
+    Commitment(Vec<u8>),
+    /// A domain separation label
+    DomainSeparator(Vec<u8>),
+    /// Challenge bytes, or the serialized challenges, squeezed from the transcript
+    Challenge(Vec<u8>),
+    /// The state returned by `hash_and_return_state`
+    State(Vec<u8>),
+    /// The state set by `set_state`
+    SetState(Vec<u8>),
+    LockProof,
+    UnlockProof,
+}
+
+impl TranscriptEvent {
+    fn kind(&self) -> &'static str {
+        match self {
+            TranscriptEvent::Phase(_) => "phase",
+            TranscriptEvent::Append(_) => "append",
+            TranscriptEvent::Commitment(_) => "commitment",
+            TranscriptEvent::DomainSeparator(_) => "separator",
+            TranscriptEvent::Challenge(_) => "challenge",
+            TranscriptEvent::State(_) => "state",
+            TranscriptEvent::SetState(_) => "set state",
+            TranscriptEvent::LockProof => "lock proof",
+            TranscriptEvent::UnlockProof => "unlock proof",
+        }
+    }
+}
+
+impl fmt::Display for TranscriptEvent {
+    /// The kind of the event and its bytes in hex, the first 32 of them unless `{:#}`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SHOWN_BYTES: usize = 32;
+
+        let bytes = match self {
+            TranscriptEvent::Phase(phase) => return write!(f, "{:<12} {phase}", self.kind()),
+            TranscriptEvent::Append(bytes)
+            | TranscriptEvent::Commitment(bytes)
+            | TranscriptEvent::DomainSeparator(bytes)
+            | TranscriptEvent::Challenge(bytes)
+            | TranscriptEvent::State(bytes)
+            | TranscriptEvent::SetState(bytes) => bytes,
+            TranscriptEvent::LockProof | TranscriptEvent::UnlockProof => {
+                return write!(f, "{}", self.kind())
+            }
+        };
+
+        let shown = if f.alternate() {
+            bytes.len()
+        } else {
+            bytes.len().min(SHOWN_BYTES)
+        };
+        write!(f, "{:<12} {:>6} bytes  ", self.kind(), bytes.len())?;
+        bytes[..shown]
+            .iter()
+            .try_for_each(|b| write!(f, "{b:02x}"))?;
+        if shown < bytes.len() {
+            write!(f, "..")?;
+        }
+        Ok(())
+    }
+}
+
+/// The operations on a transcript over a proof, in order.
+///
+/// The traces of the prover and of the verifier of a proof are the same, event for event, so the
+/// first event where they differ is where the two went apart.
+#[derive(Debug, Clone, Default, PartialEq, Eq, ExpSerde)]
+pub struct TranscriptTrace {
+    pub events: Vec<TranscriptEvent>,
+}
+
+impl TranscriptTrace {
+    /// The index of the first event where the traces differ, None if they are the same
+    pub fn first_divergence(&self, other: &Self) -> Option<usize> {
+        let common = self.events.len().min(other.events.len());
+        (0..common)
+            .find(|&i| self.events[i] != other.events[i])
+            .or((self.events.len() != other.events.len()).then_some(common))
+    }
+
+    /// The phase the event at `index` is in, i.e., the last phase started before it
+    pub fn phase_of(&self, index: usize) -> Option<&str> {
+        self.events[..index.min(self.events.len())]
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                TranscriptEvent::Phase(phase) => Some(phase.as_str()),
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Display for TranscriptTrace {
+    /// One event per line, numbered, under the phase they are in
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.events
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, event)| match event {
+                TranscriptEvent::Phase(phase) => writeln!(f, "[{phase}]"),
+                _ if f.alternate() => writeln!(f, "{i:>8}  {event:#}"),
+                _ => writeln!(f, "{i:>8}  {event}"),
+            })
+    }
+}
+
+/// A transcript recording the operations on the transcript `T` it wraps into a
+/// `TranscriptTrace`, to debug a proof failing to verify.
+///
+/// The challenges and the proof are the same as those of `T`. The trace is not part of the
+/// exported state, and starts empty on `import_state`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordingTranscript<T: Transcript> {
+    inner: T,
+    trace: TranscriptTrace,
+}
+
+impl<T: Transcript> RecordingTranscript<T> {
+    /// The operations recorded so far
+    #[inline]
+    pub fn trace(&self) -> &TranscriptTrace {
+        &self.trace
+    }
+
+    #[inline]
+    pub fn into_trace(self) -> TranscriptTrace {
+        self.trace
+    }
+
+    #[inline]
+    fn record(&mut self, event: TranscriptEvent) {
+        self.trace.events.push(event);
+    }
+}
+
+#[inline]
+fn serialized<V: ExpSerde>(values: &[V]) -> Vec<u8> {
+    let mut buf = vec![];
+    values
+        .iter()
+        .for_each(|v| v.serialize_into(&mut buf).unwrap());
+    buf
+}
+
+impl<T: Transcript> Transcript for RecordingTranscript<T> {
+    fn new() -> Self {
+        Self {
+            inner: T::new(),
+            trace: TranscriptTrace::default(),
+        }
+    }
+
+    fn append_commitment(&mut self, commitment_bytes: &[u8]) {
+        self.record(TranscriptEvent::Commitment(commitment_bytes.to_vec()));
+        self.inner.append_commitment(commitment_bytes);
+    }
+
+    fn append_field_element<F: Field>(&mut self, f: &F) {
+        self.record(TranscriptEvent::Append(serialized(&[*f])));
+        self.inner.append_field_element(f);
+    }
+
+    fn append_u8_slice(&mut self, buffer: &[u8]) {
+        self.record(TranscriptEvent::Append(buffer.to_vec()));
+        self.inner.append_u8_slice(buffer);
+    }
+
+    fn append_serializable_data<D: ExpSerde>(&mut self, data: &D) {
+        let mut buf = vec![];
+        data.serialize_into(&mut buf).unwrap();
+        self.record(TranscriptEvent::Append(buf));
+        self.inner.append_serializable_data(data);
+    }
+
+    fn generate_u8_slice(&mut self, n_bytes: usize) -> Vec<u8> {
+        let bytes = self.inner.generate_u8_slice(n_bytes);
+        self.record(TranscriptEvent::Challenge(bytes.clone()));
+        bytes
+    }
+
+    fn generate_usize_vector(&mut self, n: usize) -> Vec<usize> {
+        let res = self.inner.generate_usize_vector(n);
+        self.record(TranscriptEvent::Challenge(serialized(&res)));
+        res
+    }
+
+    fn generate_field_element<F: Field>(&mut self) -> F {
+        let f = self.inner.generate_field_element::<F>();
+        self.record(TranscriptEvent::Challenge(serialized(&[f])));
+        f
+    }
+
+    fn generate_field_elements<F: Field>(&mut self, n: usize) -> Vec<F> {
+        let res = self.inner.generate_field_elements::<F>(n);
+        self.record(TranscriptEvent::Challenge(serialized(&res)));
+        res
+    }
+
+    fn append_domain_separator(&mut self, label: &[u8]) {
+        self.record(TranscriptEvent::DomainSeparator(label.to_vec()));
+        self.inner.append_domain_separator(label);
+    }
+
+    fn begin_phase(&mut self, phase: &str) {
+        self.record(TranscriptEvent::Phase(phase.to_string()));
+        self.inner.begin_phase(phase);
+    }
+
+    fn challenge_point<F: Field>(&mut self, n_vars: usize) -> Vec<F> {
+        let res = self.inner.challenge_point::<F>(n_vars);
+        self.record(TranscriptEvent::Challenge(serialized(&res)));
+        res
+    }
+
+    fn challenge_rlc<F: Field>(&mut self, n: usize) -> Vec<F> {
+        let res = self.inner.challenge_rlc::<F>(n);
+        self.record(TranscriptEvent::Challenge(serialized(&res)));
+        res
+    }
+
+    fn challenge_queries(&mut self, domain_size: usize, k: usize, dedup: bool) -> Vec<usize> {
+        let res = self.inner.challenge_queries(domain_size, k, dedup);
+        self.record(TranscriptEvent::Challenge(serialized(&res)));
+        res
+    }
+
+    fn finalize_and_get_proof(&mut self) -> Proof {
+        self.inner.finalize_and_get_proof()
+    }
+
+    fn hash_and_return_state(&mut self) -> Vec<u8> {
+        let state = self.inner.hash_and_return_state();
+        self.record(TranscriptEvent::State(state.clone()));
+        state
+    }
+
+    fn set_state(&mut self, state: &[u8]) {
+        self.record(TranscriptEvent::SetState(state.to_vec()));
+        self.inner.set_state(state);
+    }
+
+    fn lock_proof(&mut self) {
+        self.record(TranscriptEvent::LockProof);
+        self.inner.lock_proof();
+    }
+
+    fn unlock_proof(&mut self) {
+        self.record(TranscriptEvent::UnlockProof);
+        self.inner.unlock_proof();
+    }
+
+    fn refresh_digest(&mut self) {
+        self.inner.refresh_digest();
+    }
+
+    fn export_state(&self) -> Vec<u8> {
+        self.inner.export_state()
+    }
+
+    fn import_state(state: &[u8]) -> SerdeResult<Self> {
+        Ok(Self {
+            inner: T::import_state(state)?,
+            trace: TranscriptTrace::default(),
+        })
+    }
+}
+
+/// The config `Cfg` with its transcript recorded by a `RecordingTranscript`. The proofs are those
+/// of `Cfg`, and verify with either config.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Traced<Cfg>(PhantomData<Cfg>);
+
+impl<Cfg: GKREngine> GKREngine for Traced<Cfg> {
+    type FieldConfig = Cfg::FieldConfig;
+    type MPIConfig = Cfg::MPIConfig;
+    type TranscriptConfig = RecordingTranscript<Cfg::TranscriptConfig>;
+    type PCSConfig = Cfg::PCSConfig;
+    const SCHEME: GKRScheme = Cfg::SCHEME;
+    const FINGERPRINT: [u8; 32] = Cfg::FINGERPRINT;
+}
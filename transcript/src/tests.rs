@@ -1,10 +1,14 @@
 use arith::{ExtensionField, Fr};
-use gkr_engine::Transcript;
-use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+use gkr_engine::{sample_queries, unbiased_index, Transcript};
+use gkr_hashers::{
+    AnemoiFiatShamirHasher, GriffinFiatShamirHasher, Keccak256hasher, MiMC5FiatShamirHasher,
+    PoseidonFiatShamirHasher, SHA256hasher,
+};
 use mersenne31::{M31Ext3, M31x16};
+use serdes::ExpSerde;
 use sha2::{Digest, Sha256};
 
-use crate::BytesHashTranscript;
+use crate::{BytesHashTranscript, RecordingTranscript, TranscriptEvent, TranscriptTrace};
 
 const EXAMPLE_IN: [u8; 32] = [
     40, 75, 185, 12, 169, 4, 108, 43, 211, 74, 219, 14, 2, 133, 97, 27, 200, 245, 110, 1, 253, 219,
@@ -77,6 +81,10 @@ fn test_transcript_expected_behavior() {
     >();
     test_transcript_expected_behavior_helper::<Fr, BytesHashTranscript<MiMC5FiatShamirHasher<Fr>>>(
     );
+    test_transcript_expected_behavior_helper::<Fr, BytesHashTranscript<AnemoiFiatShamirHasher<Fr>>>(
+    );
+    test_transcript_expected_behavior_helper::<Fr, BytesHashTranscript<GriffinFiatShamirHasher<Fr>>>(
+    );
 }
 
 fn get_transcript_output_helper<F, T>(input: &[u32]) -> F
@@ -103,3 +111,178 @@ fn get_transcript_output() {
         get_transcript_output_helper::<Fr, BytesHashTranscript<MiMC5FiatShamirHasher<Fr>>>(&input);
     println!("{:?}", output);
 }
+
+fn test_transcript_typed_challenges_helper<F, T>()
+where
+    F: ExtensionField,
+    T: Transcript,
+{
+    let mut transcript = T::new();
+    transcript.append_u8_slice(b"input");
+    let mut transcript_cloned = transcript.clone();
+
+    // domain separation changes the challenges, but not the proof
+    let point = transcript.challenge_point::<F>(4);
+    let rlc = transcript_cloned.challenge_rlc::<F>(4);
+    assert_eq!(point.len(), 4);
+    assert_eq!(rlc.len(), 4);
+    assert_ne!(point, rlc);
+    assert_eq!(
+        transcript.finalize_and_get_proof(),
+        transcript_cloned.finalize_and_get_proof()
+    );
+
+    // query indices are within the domain, and distinct if deduplicated
+    const DOMAIN_SIZE: usize = 37;
+    let queries = transcript.challenge_queries(DOMAIN_SIZE, 100, false);
+    assert_eq!(queries.len(), 100);
+    assert!(queries.iter().all(|&q| q < DOMAIN_SIZE));
+
+    let mut queries = transcript.challenge_queries(DOMAIN_SIZE, DOMAIN_SIZE, true);
+    queries.sort();
+    assert_eq!(queries, (0..DOMAIN_SIZE).collect::<Vec<_>>());
+
+    // queries drawn for another domain size are not the same queries reduced
+    let mut transcript_cloned = transcript.clone();
+    let queries = transcript.challenge_queries(DOMAIN_SIZE * 2, 100, false);
+    let queries_cloned = transcript_cloned.challenge_queries(DOMAIN_SIZE, 100, false);
+    assert_ne!(
+        queries.iter().map(|q| q % DOMAIN_SIZE).collect::<Vec<_>>(),
+        queries_cloned
+    );
+}
+
+#[test]
+fn test_transcript_typed_challenges() {
+    test_transcript_typed_challenges_helper::<M31Ext3, BytesHashTranscript<Keccak256hasher>>();
+    test_transcript_typed_challenges_helper::<M31Ext3, BytesHashTranscript<SHA256hasher>>();
+    test_transcript_typed_challenges_helper::<Fr, BytesHashTranscript<Keccak256hasher>>();
+    test_transcript_typed_challenges_helper::<
+        M31Ext3,
+        BytesHashTranscript<PoseidonFiatShamirHasher<M31x16>>,
+    >();
+    test_transcript_typed_challenges_helper::<
+        M31Ext3,
+        RecordingTranscript<BytesHashTranscript<SHA256hasher>>,
+    >();
+}
+
+fn test_transcript_export_import_helper<F, T>()
+where
+    F: ExtensionField,
+    T: Transcript,
+{
+    let mut transcript = T::new();
+    transcript.append_u8_slice(b"commitment");
+    let _ = transcript.generate_field_element::<F>();
+    // leave some data unhashed at the suspension point
+    transcript.append_u8_slice(b"input");
+
+    let mut resumed = T::import_state(&transcript.export_state()).unwrap();
+    assert_eq!(resumed.export_state(), transcript.export_state());
+
+    transcript.append_u8_slice(b"external challenge");
+    resumed.append_u8_slice(b"external challenge");
+    assert_eq!(
+        transcript.generate_field_element::<F>(),
+        resumed.generate_field_element::<F>()
+    );
+    assert_eq!(
+        transcript.finalize_and_get_proof(),
+        resumed.finalize_and_get_proof()
+    );
+
+    let state = transcript.export_state();
+    assert!(T::import_state(&state[..state.len() - 1]).is_err());
+}
+
+#[test]
+fn test_transcript_export_import() {
+    test_transcript_export_import_helper::<M31Ext3, BytesHashTranscript<Keccak256hasher>>();
+    test_transcript_export_import_helper::<Fr, BytesHashTranscript<SHA256hasher>>();
+    test_transcript_export_import_helper::<
+        M31Ext3,
+        BytesHashTranscript<PoseidonFiatShamirHasher<M31x16>>,
+    >();
+}
+
+fn run_recorded_protocol<T: Transcript>(transcript: &mut T, input: &[u8]) -> Vec<M31Ext3> {
+    transcript.begin_phase("commitment");
+    transcript.append_commitment(b"commitment");
+    transcript.begin_phase("sumcheck");
+    transcript.append_u8_slice(input);
+    let mut challenges = transcript.challenge_point::<M31Ext3>(2);
+    challenges.push(transcript.generate_field_element::<M31Ext3>());
+    let state = transcript.hash_and_return_state();
+    transcript.set_state(&state);
+    challenges
+}
+
+#[test]
+fn test_recording_transcript() {
+    type T = BytesHashTranscript<SHA256hasher>;
+
+    // recording changes neither the challenges nor the proof
+    let mut transcript = T::new();
+    let mut recording = RecordingTranscript::<T>::new();
+    assert_eq!(
+        run_recorded_protocol(&mut transcript, b"input"),
+        run_recorded_protocol(&mut recording, b"input")
+    );
+    assert_eq!(
+        transcript.finalize_and_get_proof(),
+        recording.finalize_and_get_proof()
+    );
+
+    let trace = recording.into_trace();
+    assert_eq!(trace.events.len(), 8);
+    assert_eq!(
+        trace.events[0],
+        TranscriptEvent::Phase("commitment".to_string())
+    );
+    assert_eq!(trace.events[3], TranscriptEvent::Append(b"input".to_vec()));
+    assert_eq!(trace.first_divergence(&trace), None);
+
+    // the first operation on a different input is where the traces differ
+    let mut recording = RecordingTranscript::<T>::new();
+    run_recorded_protocol(&mut recording, b"other input");
+    let other_trace = recording.into_trace();
+    assert_eq!(trace.first_divergence(&other_trace), Some(3));
+    assert_eq!(trace.phase_of(3), Some("sumcheck"));
+
+    let mut truncated_trace = trace.clone();
+    truncated_trace.events.truncate(5);
+    assert_eq!(trace.first_divergence(&truncated_trace), Some(5));
+    assert_eq!(truncated_trace.first_divergence(&trace), Some(5));
+
+    let mut bytes = vec![];
+    trace.serialize_into(&mut bytes).unwrap();
+    assert_eq!(
+        TranscriptTrace::deserialize_from(bytes.as_slice()).unwrap(),
+        trace
+    );
+}
+
+#[test]
+fn test_unbiased_query_sampling() {
+    // a domain dividing 2^64 rejects no word
+    assert_eq!(unbiased_index(usize::MAX, 1 << 10), Some((1 << 10) - 1));
+    assert_eq!(unbiased_index(usize::MAX, 1), Some(0));
+
+    // 2^64 = 3 * (2^64 / 3) + 1: the last word alone is rejected
+    assert_eq!(unbiased_index(usize::MAX, 3), None);
+    assert_eq!(
+        unbiased_index(usize::MAX - 1, 3),
+        Some((usize::MAX - 1) % 3)
+    );
+    assert_eq!(unbiased_index(5, 3), Some(2));
+
+    // rejected words are replaced by new ones, and repeated indices if deduplicated
+    let mut words = vec![vec![usize::MAX, 4, 4, 7], vec![usize::MAX], vec![1]].into_iter();
+    let queries = sample_queries(3, 4, false, |_| words.next().unwrap());
+    assert_eq!(queries, vec![1, 1, 1, 1]);
+
+    let mut words = vec![vec![usize::MAX, 4, 4], vec![4], vec![usize::MAX - 1]].into_iter();
+    let queries = sample_queries(3, 2, true, |_| words.next().unwrap());
+    assert_eq!(queries, vec![1, (usize::MAX - 1) % 3]);
+}
@@ -1,5 +1,6 @@
 use std::{fmt, fmt::Display};
 
+use arith::impl_pod;
 use serdes::ExpSerde;
 use tiny_keccak::{Hasher, Keccak};
 
@@ -11,6 +12,11 @@ pub struct Node {
     pub(crate) data: [u8; LEAF_HASH_BYTES],
 }
 
+// NOTE: the roots of the local trees are gathered from the processes, see the Orion PCS
+impl_pod!(Node {
+    data: [u8; LEAF_HASH_BYTES]
+});
+
 impl Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Display the first and last byte of the node for brevity